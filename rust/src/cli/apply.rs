@@ -14,7 +14,7 @@ const DEFAULT_TIMEOUT: u32 = 60;
 pub(crate) fn apply_from_stdin(
     matches: &clap::ArgMatches,
 ) -> Result<String, CliError> {
-    apply(&mut stdin(), matches)
+    json_error_if_requested(apply(&mut stdin(), matches), matches)
 }
 
 pub(crate) fn apply_from_files(
@@ -23,11 +23,29 @@ pub(crate) fn apply_from_files(
 ) -> Result<String, CliError> {
     let mut ret = String::new();
     for file_path in file_paths {
-        ret += &apply(&mut std::fs::File::open(file_path)?, matches)?;
+        ret += &json_error_if_requested(
+            std::fs::File::open(file_path)
+                .map_err(CliError::from)
+                .and_then(|mut fd| apply(&mut fd, matches)),
+            matches,
+        )?;
     }
     Ok(ret)
 }
 
+// With `--json`, the error is printed as JSON object with structured
+// verification difference included.
+fn json_error_if_requested(
+    result: Result<String, CliError>,
+    matches: &clap::ArgMatches,
+) -> Result<String, CliError> {
+    if matches.try_contains_id("JSON").unwrap_or_default() {
+        result.map_err(CliError::into_json)
+    } else {
+        result
+    }
+}
+
 pub(crate) fn apply<R>(
     reader: &mut R,
     matches: &clap::ArgMatches,
@@ -46,6 +64,7 @@ where
                     return Err(CliError {
                         code: crate::error::EX_DATAERR,
                         error_msg: e.to_string(),
+                        ..Default::default()
                     });
                 }
             },
@@ -54,6 +73,7 @@ where
                 return Err(CliError {
                     code: crate::error::EX_DATAERR,
                     error_msg: e.to_string(),
+                    ..Default::default()
                 });
             }
         }
@@ -61,11 +81,59 @@ where
         DEFAULT_TIMEOUT
     };
     let mut content = String::new();
-    // Replace non-breaking space '\u{A0}'  to normal space
     reader.read_to_string(&mut content)?;
+    let mut net_state = state_from_content(&content)?;
+
+    net_state.set_kernel_only(kernel_only);
+    if let Some(netns) = matches.try_get_one::<String>("NETNS").ok().flatten() {
+        net_state.set_kernel_only(true);
+        net_state.set_netns(netns);
+    }
+    net_state.set_verify_change(!no_verify);
+    net_state.set_commit(!no_commit);
+    net_state.set_timeout(timeout);
+    net_state.set_memory_only(
+        matches.try_contains_id("MEMORY_ONLY").unwrap_or_default(),
+    );
+    net_state.set_create_route_table_names(
+        matches
+            .try_contains_id("CREATE_ROUTE_TABLE_NAMES")
+            .unwrap_or_default(),
+    );
+    if matches.try_contains_id("DRY_RUN").unwrap_or_default() {
+        let report = net_state.apply_dry_run()?;
+        return if matches.try_contains_id("JSON").unwrap_or_default() {
+            Ok(serde_json::to_string_pretty(&report)?)
+        } else {
+            Ok(serde_yaml::to_string(&report)?)
+        };
+    }
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()
+        .map_err(|e| {
+            CliError::from(format!("tokio::runtime::Builder failed with {e}"))
+        })?;
+    let mut diff_state = rt.block_on(apply_state_async(net_state))?;
+    if !matches.try_contains_id("SHOW_SECRETS").unwrap_or_default() {
+        diff_state.hide_secrets();
+    }
+    let sorted_net_state = crate::query::sort_netstate(diff_state)?;
+    if matches.try_contains_id("JSON").unwrap_or_default() {
+        Ok(serde_json::to_string_pretty(&sorted_net_state)?)
+    } else {
+        Ok(serde_yaml::to_string(&sorted_net_state)?)
+    }
+}
+
+// Parse NetworkState or NetworkPolicy
+pub(crate) fn state_from_content(
+    content: &str,
+) -> Result<NetworkState, CliError> {
+    // Replace non-breaking space '\u{A0}'  to normal space
     let content = content.replace('\u{A0}', " ");
 
-    let mut net_state: NetworkState = match serde_yaml::from_str(&content) {
+    Ok(match serde_yaml::from_str(&content) {
         Ok(s) => s,
         Err(state_error) => {
             // Try NetworkPolicy
@@ -88,27 +156,7 @@ where
             };
             NetworkState::try_from(net_policy)?
         }
-    };
-
-    net_state.set_kernel_only(kernel_only);
-    net_state.set_verify_change(!no_verify);
-    net_state.set_commit(!no_commit);
-    net_state.set_timeout(timeout);
-    net_state.set_memory_only(
-        matches.try_contains_id("MEMORY_ONLY").unwrap_or_default(),
-    );
-    let rt = tokio::runtime::Builder::new_current_thread()
-        .enable_io()
-        .build()
-        .map_err(|e| {
-            CliError::from(format!("tokio::runtime::Builder failed with {e}"))
-        })?;
-    let mut diff_state = rt.block_on(apply_state_async(net_state))?;
-    if !matches.try_contains_id("SHOW_SECRETS").unwrap_or_default() {
-        diff_state.hide_secrets();
-    }
-    let sorted_net_state = crate::query::sort_netstate(diff_state)?;
-    Ok(serde_yaml::to_string(&sorted_net_state)?)
+    })
 }
 
 pub(crate) fn commit(checkpoint: &str) -> Result<String, CliError> {
@@ -148,6 +196,7 @@ pub(crate) fn state_edit(
             return Err(CliError {
                 code: crate::error::EX_DATAERR,
                 error_msg: format!("Interface {ifname} not found"),
+                ..Default::default()
             });
         }
         net_state
@@ -216,12 +265,14 @@ fn run_editor(tmp_file_path: &str) -> Result<NetworkState, CliError> {
             .map_err(|e| CliError {
                 code: crate::error::EX_DATAERR,
                 error_msg: format!("Editor '{editor}' failed with {e}"),
+                ..Default::default()
             })?
             .success()
         {
             return Err(CliError {
                 code: crate::error::EX_DATAERR,
                 error_msg: format!("Editor '{editor}' failed"),
+                ..Default::default()
             });
         }
         let fd = std::fs::File::open(tmp_file_path)?;
@@ -232,6 +283,7 @@ fn run_editor(tmp_file_path: &str) -> Result<NetworkState, CliError> {
                     return Err(CliError {
                         code: crate::error::EX_DATAERR,
                         error_msg: format!("{e}"),
+                        ..Default::default()
                     });
                 } else {
                     eprintln!("{e}");
@@ -268,6 +320,9 @@ async fn apply_state_async(
     let mut cur_state = NetworkState::new();
     cur_state.set_kernel_only(net_state.kernel_only());
     cur_state.set_running_config_only(true);
+    if let Some(netns) = net_state.netns() {
+        cur_state.set_netns(netns);
+    }
     cur_state.retrieve_async().await?;
 
     let mut ctrlc_stream = tokio::signal::unix::signal(
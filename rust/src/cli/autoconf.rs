@@ -73,6 +73,7 @@ fn filter_net_state(
                 return Err(CliError {
                     code: crate::error::EX_DATAERR,
                     error_msg: format!("Interface {filter} not found"),
+                    ..Default::default()
                 });
             }
         }
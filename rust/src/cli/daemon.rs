@@ -0,0 +1,536 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::UnixDatagram;
+use std::path::Path;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use nmstate::{ErrorKind, NetworkState, NetworkStateWatcher, NmstateError};
+use serde::Serialize;
+
+use crate::{
+    apply::state_from_content,
+    error::CliError,
+    service::{get_applied_state_files, DriftAction, ServiceConfig},
+};
+
+const JOURNAL_SOCKET_PATH: &str = "/run/systemd/journal/socket";
+const SYSLOG_IDENTIFIER: &str = "nmstate";
+// Syslog priorities used by journal
+const PRIORITY_ERR: u8 = 3;
+const PRIORITY_WARNING: u8 = 4;
+const PRIORITY_INFO: u8 = 6;
+
+// Network state might be changing right after notification(e.g.
+// NetworkManager activating profile), confirm the drift after this delay.
+const DRIFT_CONFIRM_DELAY_SECONDS: u64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum DaemonState {
+    #[default]
+    Starting,
+    InSync,
+    Drifted,
+    Failed,
+}
+
+// What to do with the result of drift check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DriftDecision {
+    InSync,
+    // Check again after DRIFT_CONFIRM_DELAY_SECONDS
+    Confirm,
+    Alert,
+    Reapply,
+    Failed,
+}
+
+// Reported on status socket in JSON
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct DaemonStatus {
+    state: DaemonState,
+    drift_action: DriftAction,
+    state_files: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_check: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_drift: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+    drift_count: u64,
+    reapply_count: u64,
+}
+
+// Keep the applied state files enforced till SIGINT or SIGTERM
+pub(crate) fn run_daemon(
+    folder: &str,
+    config: &ServiceConfig,
+) -> Result<(), CliError> {
+    let mut status = DaemonStatus {
+        drift_action: config.drift_action,
+        ..Default::default()
+    };
+    let mut desired_state = NetworkState::new();
+    for (path, content) in get_applied_state_files(folder)? {
+        desired_state.merge_desire(&state_from_content(&content)?);
+        status.state_files.push(path.display().to_string());
+    }
+    if status.state_files.is_empty() {
+        log::info!(
+            "No applied state file found in config folder {folder}, \
+            nothing to enforce"
+        );
+    }
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .enable_time()
+        .build()
+        .map_err(|e| {
+            CliError::from(format!("tokio::runtime::Builder failed with {e}"))
+        })?;
+    rt.block_on(daemon_loop(desired_state, config, status))
+}
+
+async fn daemon_loop(
+    desired_state: NetworkState,
+    config: &ServiceConfig,
+    mut status: DaemonStatus,
+) -> Result<(), CliError> {
+    let listener = bind_status_socket(&config.status_socket)?;
+    let mut sigint = tokio::signal::unix::signal(
+        tokio::signal::unix::SignalKind::interrupt(),
+    )
+    .map_err(|e| format!("tokio failed to hook on signal SIGINT: {e}"))?;
+    let mut sigterm = tokio::signal::unix::signal(
+        tokio::signal::unix::SignalKind::terminate(),
+    )
+    .map_err(|e| format!("tokio failed to hook on signal SIGTERM: {e}"))?;
+    let mut watcher: Option<NetworkStateWatcher> =
+        match NetworkState::new().watch().await {
+            Ok(w) => Some(w),
+            Err(e) => {
+                log::warn!(
+                    "Failed to monitor network state changes, only \
+                    periodic drift check will be done: {e}"
+                );
+                None
+            }
+        };
+    let mut interval = tokio::time::interval(Duration::from_secs(
+        config.drift_check_interval.max(1),
+    ));
+    // Deadline of confirming the drift found, other drift checks are
+    // skipped till then.
+    let mut confirm_at: Option<tokio::time::Instant> = None;
+
+    journal_send(
+        PRIORITY_INFO,
+        "daemon-started",
+        &format!(
+            "Nmstate daemon started enforcing {} state files",
+            status.state_files.len()
+        ),
+        &[],
+    );
+
+    let ret = loop {
+        tokio::select! {
+            _ = sigint.recv() => break Ok(()),
+            _ = sigterm.recv() => break Ok(()),
+            conn = listener.accept() => {
+                match conn {
+                    Ok((stream, _)) => send_status(stream, &status),
+                    Err(e) => log::warn!("Failed to accept connection on \
+                        status socket: {e}"),
+                }
+            }
+            _ = interval.tick(), if confirm_at.is_none() => {
+                confirm_at =
+                    check_drift(&desired_state, config, &mut status, false)
+                        .await;
+            }
+            _ = tokio::time::sleep_until(
+                confirm_at.unwrap_or_else(tokio::time::Instant::now)
+            ), if confirm_at.is_some() => {
+                confirm_at =
+                    check_drift(&desired_state, config, &mut status, true)
+                        .await;
+            }
+            delta = next_change(&mut watcher) => {
+                match delta {
+                    Some(Ok(_)) if confirm_at.is_none() => {
+                        confirm_at = check_drift(
+                            &desired_state,
+                            config,
+                            &mut status,
+                            false,
+                        )
+                        .await;
+                    }
+                    Some(Ok(_)) => (),
+                    Some(Err(e)) => {
+                        log::warn!("Failed to query network state change: {e}")
+                    }
+                    None => {
+                        log::warn!(
+                            "Network state monitors stopped, only periodic \
+                            drift check will be done"
+                        );
+                        watcher = None;
+                    }
+                }
+            }
+        }
+    };
+    journal_send(
+        PRIORITY_INFO,
+        "daemon-stopped",
+        "Nmstate daemon stopped",
+        &[],
+    );
+    if let Err(e) = std::fs::remove_file(&config.status_socket) {
+        log::warn!(
+            "Failed to remove status socket {}: {e}",
+            config.status_socket
+        );
+    }
+    ret
+}
+
+async fn next_change(
+    watcher: &mut Option<NetworkStateWatcher>,
+) -> Option<Result<NetworkState, nmstate::NmstateError>> {
+    if let Some(watcher) = watcher.as_mut() {
+        watcher.next().await
+    } else {
+        std::future::pending().await
+    }
+}
+
+// Return the deadline for confirming the drift if drift found but not
+// `confirming`.
+async fn check_drift(
+    desired_state: &NetworkState,
+    config: &ServiceConfig,
+    status: &mut DaemonStatus,
+    confirming: bool,
+) -> Option<tokio::time::Instant> {
+    if desired_state.is_empty() {
+        status.state = DaemonState::InSync;
+        return None;
+    }
+    status.last_check = Some(now());
+    let result = desired_state.verify_async().await;
+    match drift_decision(&result, confirming, config.drift_action) {
+        DriftDecision::Confirm => {
+            log::info!(
+                "Network state differs from applied state, confirming \
+                in {DRIFT_CONFIRM_DELAY_SECONDS} seconds"
+            );
+            return Some(
+                tokio::time::Instant::now()
+                    + Duration::from_secs(DRIFT_CONFIRM_DELAY_SECONDS),
+            );
+        }
+        DriftDecision::InSync => {
+            if status.state != DaemonState::InSync {
+                log::info!("Network state is in sync with applied state");
+            }
+            status.state = DaemonState::InSync;
+            status.last_error = None;
+        }
+        decision @ (DriftDecision::Alert | DriftDecision::Reapply) => {
+            let e = result.err().map(|e| e.to_string()).unwrap_or_default();
+            status.state = DaemonState::Drifted;
+            status.drift_count += 1;
+            status.last_drift = Some(now());
+            journal_send(
+                PRIORITY_WARNING,
+                "drift-detected",
+                &format!("Network state drifted from applied state: {e}"),
+                &[("NMSTATE_DRIFT_ACTION", drift_action_str(config))],
+            );
+            status.last_error = Some(e);
+            if decision == DriftDecision::Reapply {
+                reapply(desired_state, status).await;
+            }
+        }
+        DriftDecision::Failed => {
+            let e = result.err().map(|e| e.to_string()).unwrap_or_default();
+            journal_send(
+                PRIORITY_ERR,
+                "drift-check-failed",
+                &format!("Failed to check network state drift: {e}"),
+                &[],
+            );
+            status.state = DaemonState::Failed;
+            status.last_error = Some(e);
+        }
+    }
+    None
+}
+
+// Network state might be changing right after notification, hence only
+// treat verification failure as drift when confirming.
+fn drift_decision(
+    result: &Result<(), NmstateError>,
+    confirming: bool,
+    action: DriftAction,
+) -> DriftDecision {
+    match result {
+        Ok(()) => DriftDecision::InSync,
+        Err(e) if e.kind() == ErrorKind::VerificationError => {
+            if !confirming {
+                DriftDecision::Confirm
+            } else if action == DriftAction::Reapply {
+                DriftDecision::Reapply
+            } else {
+                DriftDecision::Alert
+            }
+        }
+        Err(_) => DriftDecision::Failed,
+    }
+}
+
+async fn reapply(desired_state: &NetworkState, status: &mut DaemonStatus) {
+    status.reapply_count += 1;
+    match desired_state.apply_async().await {
+        Ok(()) => {
+            status.state = DaemonState::InSync;
+            status.last_error = None;
+            journal_send(
+                PRIORITY_INFO,
+                "reapply-succeeded",
+                "Reapplied state files to fix network state drift",
+                &[],
+            );
+        }
+        Err(e) => {
+            status.state = DaemonState::Failed;
+            status.last_error = Some(e.to_string());
+            journal_send(
+                PRIORITY_ERR,
+                "reapply-failed",
+                &format!("Failed to reapply state files: {e}"),
+                &[],
+            );
+        }
+    }
+}
+
+fn drift_action_str(config: &ServiceConfig) -> &'static str {
+    match config.drift_action {
+        DriftAction::Reapply => "reapply",
+        DriftAction::Alert => "alert",
+    }
+}
+
+fn now() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+fn bind_status_socket(
+    path: &str,
+) -> Result<tokio::net::UnixListener, CliError> {
+    let path = Path::new(path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Remove stale socket left by previous run
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    let listener = tokio::net::UnixListener::bind(path).map_err(|e| {
+        CliError::from(format!(
+            "Failed to bind status socket {}: {e}",
+            path.display()
+        ))
+    })?;
+    // Status might contain error message of network state, only root
+    // is allowed to read.
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .map_err(|e| {
+            CliError::from(format!(
+                "Failed to set permission of status socket {}: {e}",
+                path.display()
+            ))
+        })?;
+    Ok(listener)
+}
+
+fn send_status(stream: tokio::net::UnixStream, status: &DaemonStatus) {
+    let result = stream.into_std().and_then(|mut stream| {
+        stream.set_nonblocking(false)?;
+        write_status(&mut stream, status)
+    });
+    if let Err(e) = result {
+        log::warn!("Failed to send status on status socket: {e}");
+    }
+}
+
+// The status is a single line of JSON object
+fn write_status(
+    writer: &mut impl Write,
+    status: &DaemonStatus,
+) -> std::io::Result<()> {
+    let content = serde_json::to_string(status)?;
+    writeln!(writer, "{content}")
+}
+
+// Log the event and send it to systemd journal with `NMSTATE_EVENT` field
+// using the journal native protocol.
+fn journal_send(
+    priority: u8,
+    event: &str,
+    message: &str,
+    fields: &[(&str, &str)],
+) {
+    match priority {
+        PRIORITY_ERR => log::error!("{message}"),
+        PRIORITY_WARNING => log::warn!("{message}"),
+        _ => log::info!("{message}"),
+    }
+    let mut data = Vec::new();
+    let priority = priority.to_string();
+    for (key, value) in [
+        ("MESSAGE", message),
+        ("PRIORITY", priority.as_str()),
+        ("SYSLOG_IDENTIFIER", SYSLOG_IDENTIFIER),
+        ("NMSTATE_EVENT", event),
+    ]
+    .iter()
+    .chain(fields.iter())
+    {
+        append_journal_field(&mut data, key, value);
+    }
+    let socket = match UnixDatagram::unbound() {
+        Ok(s) => s,
+        Err(e) => {
+            log::debug!("Failed to create journal socket: {e}");
+            return;
+        }
+    };
+    if let Err(e) = socket.send_to(&data, JOURNAL_SOCKET_PATH) {
+        log::debug!("Failed to send event to journal: {e}");
+    }
+}
+
+// Value with newline is encoded as key, newline, 64 bits little endian
+// length, value, newline.
+fn append_journal_field(data: &mut Vec<u8>, key: &str, value: &str) {
+    data.extend_from_slice(key.as_bytes());
+    if value.contains('\n') {
+        data.push(b'\n');
+        data.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        data.push(b'=');
+    }
+    data.extend_from_slice(value.as_bytes());
+    data.push(b'\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn verify_error() -> Result<(), NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::VerificationError,
+            "eth1.interface.mtu desire '1500', current '9000'".to_string(),
+        ))
+    }
+
+    #[test]
+    fn test_drift_decision_in_sync() {
+        assert_eq!(
+            drift_decision(&Ok(()), false, DriftAction::Reapply),
+            DriftDecision::InSync
+        );
+        assert_eq!(
+            drift_decision(&Ok(()), true, DriftAction::Reapply),
+            DriftDecision::InSync
+        );
+    }
+
+    #[test]
+    fn test_drift_decision_confirm_before_action() {
+        for action in [DriftAction::Reapply, DriftAction::Alert] {
+            assert_eq!(
+                drift_decision(&verify_error(), false, action),
+                DriftDecision::Confirm
+            );
+        }
+    }
+
+    #[test]
+    fn test_drift_decision_confirmed() {
+        assert_eq!(
+            drift_decision(&verify_error(), true, DriftAction::Reapply),
+            DriftDecision::Reapply
+        );
+        assert_eq!(
+            drift_decision(&verify_error(), true, DriftAction::Alert),
+            DriftDecision::Alert
+        );
+    }
+
+    #[test]
+    fn test_drift_decision_failed_without_confirm() {
+        let result = Err(NmstateError::new(
+            ErrorKind::PluginFailure,
+            "Failed to connect NetworkManager".to_string(),
+        ));
+        assert_eq!(
+            drift_decision(&result, false, DriftAction::Reapply),
+            DriftDecision::Failed
+        );
+    }
+
+    #[test]
+    fn test_write_status() {
+        let status = DaemonStatus {
+            state: DaemonState::Drifted,
+            drift_action: DriftAction::Alert,
+            state_files: vec!["/etc/nmstate/eth1.applied".to_string()],
+            last_check: Some("2024-01-01T00:00:00Z".to_string()),
+            last_drift: Some("2024-01-01T00:00:00Z".to_string()),
+            last_error: None,
+            drift_count: 1,
+            reapply_count: 0,
+        };
+        let mut output = Vec::new();
+        write_status(&mut output, &status).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"state\":\"drifted\",\"drift-action\":\"alert\",\
+            \"state-files\":[\"/etc/nmstate/eth1.applied\"],\
+            \"last-check\":\"2024-01-01T00:00:00Z\",\
+            \"last-drift\":\"2024-01-01T00:00:00Z\",\
+            \"drift-count\":1,\"reapply-count\":0}\n"
+        );
+    }
+
+    #[test]
+    fn test_status_socket_permission() {
+        let path = std::env::temp_dir()
+            .join(format!("nmstate-test-{}", std::process::id()))
+            .join("status.sock");
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_io()
+            .build()
+            .unwrap();
+        let _listener = rt
+            .block_on(async { bind_status_socket(path.to_str().unwrap()) })
+            .unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
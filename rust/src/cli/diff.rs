@@ -0,0 +1,470 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::os::unix::io::AsRawFd;
+
+use nmstate::{InterfaceState, NetworkState};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{error::CliError, state::state_from_file};
+
+const DIFF_CONTEXT_LINES: usize = 3;
+
+const COLOR_RED: &str = "\x1b[31m";
+const COLOR_GREEN: &str = "\x1b[32m";
+const COLOR_CYAN: &str = "\x1b[36m";
+const COLOR_BOLD: &str = "\x1b[1m";
+const COLOR_RESET: &str = "\x1b[0m";
+
+// Return the output and whether difference found.
+pub(crate) fn diff(
+    matches: &clap::ArgMatches,
+) -> Result<(String, bool), CliError> {
+    let desired_state =
+        state_from_file(matches.value_of("STATE_FILE").unwrap_or("-"))?;
+    let current_state =
+        if let Some(cur_state_file) = matches.value_of("CURRENT_STATE") {
+            state_from_file(cur_state_file)?
+        } else {
+            let mut net_state = NetworkState::new();
+            net_state.set_running_config_only(true);
+            net_state.retrieve()?;
+            net_state
+        };
+
+    let diff_state = desired_state.gen_diff(&current_state)?;
+    if diff_state.is_empty() {
+        return Ok((String::new(), false));
+    }
+
+    let output = if matches.is_present("JSON") {
+        serde_json::to_string_pretty(&diff_state)?
+    } else if matches.is_present("YAML") {
+        serde_yaml::to_string(&diff_state)?
+    } else {
+        let color = !matches.is_present("NO_COLOR")
+            && std::env::var_os("NO_COLOR").is_none()
+            && stdout_is_tty();
+        gen_unified_view(&diff_state, &current_state, color)?
+    };
+    Ok((output, true))
+}
+
+// Do not emit color escape codes when piping output to file or other command
+fn stdout_is_tty() -> bool {
+    nix::unistd::isatty(std::io::stdout().as_raw_fd()).unwrap_or(false)
+}
+
+// Show each changed section of current state and the state after applying
+// as unified diff.
+fn gen_unified_view(
+    diff_state: &NetworkState,
+    current: &NetworkState,
+    color: bool,
+) -> Result<String, CliError> {
+    let mut ret = Vec::new();
+    let mut push_line = |line: String, line_color: &str| {
+        if color && !line_color.is_empty() {
+            ret.push(format!("{line_color}{line}{COLOR_RESET}"));
+        } else {
+            ret.push(line);
+        }
+    };
+    push_line("--- current".to_string(), COLOR_BOLD);
+    push_line("+++ desired".to_string(), COLOR_BOLD);
+
+    let mut sections: Vec<(String, Vec<DiffLine>)> = Vec::new();
+    if let Some(hostname) = diff_state.hostname.as_ref() {
+        sections.push((
+            "hostname".to_string(),
+            diff_lines(
+                &to_yaml_lines(&current.hostname)?,
+                &to_yaml_lines(hostname)?,
+            ),
+        ));
+    }
+    if let Some(dns) = diff_state.dns.as_ref() {
+        sections.push((
+            "dns-resolver".to_string(),
+            diff_lines(
+                &to_yaml_lines(
+                    &current.dns.as_ref().and_then(|d| d.config.as_ref()),
+                )?,
+                &to_yaml_lines(&dns.config)?,
+            ),
+        ));
+    }
+    for (section, entries) in [
+        ("route-rules", to_json_list(&diff_state.rules.config)?),
+        ("nexthops", to_json_list(&diff_state.nexthops.config)?),
+        ("routes", to_json_list(&diff_state.routes.config)?),
+    ] {
+        // Absent entries are shown as removed, others as added.
+        let mut lines = Vec::new();
+        for mut entry in entries {
+            let is_absent =
+                entry.get("state").and_then(|s| s.as_str()) == Some("absent");
+            if let Some(obj) = entry.as_object_mut() {
+                obj.shift_remove("state");
+            }
+            for line in to_yaml_lines(&vec![entry])? {
+                lines.push(if is_absent {
+                    DiffLine::Removed(line)
+                } else {
+                    DiffLine::Added(line)
+                });
+            }
+        }
+        if !lines.is_empty() {
+            sections.push((section.to_string(), lines));
+        }
+    }
+    for iface in diff_state.interfaces.to_vec() {
+        let cur_value = match current
+            .interfaces
+            .get_iface(iface.name(), iface.iface_type())
+        {
+            Some(cur_iface) => serde_json::to_value(cur_iface)?,
+            None => Value::Null,
+        };
+        let new_value = if iface.base_iface().state == InterfaceState::Absent {
+            Value::Null
+        } else {
+            let mut new_value = cur_value.clone();
+            merge_json_value(&mut new_value, &serde_json::to_value(iface)?);
+            new_value
+        };
+        sections.push((
+            format!("interface {} ({})", iface.name(), iface.iface_type()),
+            diff_lines(
+                &to_yaml_lines(&cur_value)?,
+                &to_yaml_lines(&new_value)?,
+            ),
+        ));
+    }
+    if let Some(ovsdb) = diff_state.ovsdb.as_ref() {
+        sections.push((
+            "ovs-db".to_string(),
+            diff_lines(&to_yaml_lines(&current.ovsdb)?, &to_yaml_lines(ovsdb)?),
+        ));
+    }
+    if diff_state.ovn.bridge_mappings.is_some() {
+        sections.push((
+            "ovn".to_string(),
+            diff_lines(
+                &to_yaml_lines(&current.ovn)?,
+                &to_yaml_lines(&diff_state.ovn)?,
+            ),
+        ));
+    }
+
+    for (section, lines) in sections {
+        for hunk in gen_hunks(&lines) {
+            push_line(format!("@@ {section} @@"), COLOR_CYAN);
+            for line in hunk {
+                match line {
+                    DiffLine::Same(l) => push_line(format!(" {l}"), ""),
+                    DiffLine::Removed(l) => {
+                        push_line(format!("-{l}"), COLOR_RED)
+                    }
+                    DiffLine::Added(l) => {
+                        push_line(format!("+{l}"), COLOR_GREEN)
+                    }
+                }
+            }
+        }
+    }
+    Ok(ret.join("\n"))
+}
+
+fn to_json_list<T: Serialize>(
+    items: &Option<Vec<T>>,
+) -> Result<Vec<Value>, CliError> {
+    let mut ret = Vec::new();
+    for item in items.as_deref().unwrap_or_default() {
+        ret.push(serde_json::to_value(item)?);
+    }
+    Ok(ret)
+}
+
+fn to_yaml_lines<T: Serialize>(value: &T) -> Result<Vec<String>, CliError> {
+    let value = serde_json::to_value(value)?;
+    if value.is_null() {
+        return Ok(Vec::new());
+    }
+    Ok(serde_yaml::to_string(&value)?
+        .lines()
+        .filter(|l| *l != "---")
+        .map(|l| l.to_string())
+        .collect())
+}
+
+// Override `base` with values in `new`, objects are merged recursively.
+fn merge_json_value(base: &mut Value, new: &Value) {
+    match (base, new) {
+        (Value::Object(base), Value::Object(new)) => {
+            for (key, new_value) in new.iter() {
+                match base.get_mut(key) {
+                    Some(base_value) => merge_json_value(base_value, new_value),
+                    None => {
+                        base.insert(key.clone(), new_value.clone());
+                    }
+                }
+            }
+        }
+        (base, new) => *base = new.clone(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffLine {
+    Same(String),
+    Removed(String),
+    Added(String),
+}
+
+impl DiffLine {
+    fn is_same(&self) -> bool {
+        matches!(self, Self::Same(_))
+    }
+}
+
+// Line based diff using longest common subsequence.
+fn diff_lines(old: &[String], new: &[String]) -> Vec<DiffLine> {
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut ret = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            ret.push(DiffLine::Same(old[i].clone()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ret.push(DiffLine::Removed(old[i].clone()));
+            i += 1;
+        } else {
+            ret.push(DiffLine::Added(new[j].clone()));
+            j += 1;
+        }
+    }
+    ret.extend(old[i..].iter().map(|l| DiffLine::Removed(l.clone())));
+    ret.extend(new[j..].iter().map(|l| DiffLine::Added(l.clone())));
+    ret
+}
+
+// Group changed lines with their surrounding context lines.
+fn gen_hunks(lines: &[DiffLine]) -> Vec<&[DiffLine]> {
+    let mut ret = Vec::new();
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !l.is_same())
+        .map(|(i, _)| i)
+        .collect();
+    let mut index = 0;
+    while index < changed.len() {
+        let start = changed[index].saturating_sub(DIFF_CONTEXT_LINES);
+        let mut end = changed[index];
+        while index < changed.len()
+            && changed[index] <= end + 2 * DIFF_CONTEXT_LINES
+        {
+            end = changed[index];
+            index += 1;
+        }
+        let end = (end + DIFF_CONTEXT_LINES + 1).min(lines.len());
+        ret.push(&lines[start..end]);
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn test_diff_lines_changed() {
+        let old = to_lines(&["a", "b", "c"]);
+        let new = to_lines(&["a", "x", "c"]);
+
+        assert_eq!(
+            diff_lines(&old, &new),
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Removed("b".to_string()),
+                DiffLine::Added("x".to_string()),
+                DiffLine::Same("c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_added_and_removed_at_end() {
+        let old = to_lines(&["a", "b"]);
+        let new = to_lines(&["b", "c"]);
+
+        assert_eq!(
+            diff_lines(&old, &new),
+            vec![
+                DiffLine::Removed("a".to_string()),
+                DiffLine::Same("b".to_string()),
+                DiffLine::Added("c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_from_empty() {
+        let new = to_lines(&["a", "b"]);
+
+        assert_eq!(
+            diff_lines(&[], &new),
+            vec![
+                DiffLine::Added("a".to_string()),
+                DiffLine::Added("b".to_string()),
+            ]
+        );
+        assert!(diff_lines(&new, &new).iter().all(|l| l.is_same()));
+    }
+
+    #[test]
+    fn test_gen_hunks_no_change() {
+        let lines = diff_lines(&to_lines(&["a", "b"]), &to_lines(&["a", "b"]));
+
+        assert!(gen_hunks(&lines).is_empty());
+    }
+
+    #[test]
+    fn test_gen_hunks_context_lines() {
+        let old: Vec<String> = (0..20).map(|i| i.to_string()).collect();
+        let mut new = old.clone();
+        new[10] = "changed".to_string();
+        let lines = diff_lines(&old, &new);
+
+        let hunks = gen_hunks(&lines);
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].len(), 2 * DIFF_CONTEXT_LINES + 2);
+        assert_eq!(hunks[0][0], DiffLine::Same("7".to_string()));
+        assert_eq!(
+            hunks[0][hunks[0].len() - 1],
+            DiffLine::Same("13".to_string())
+        );
+    }
+
+    #[test]
+    fn test_gen_hunks_merge_nearby_changes() {
+        let old: Vec<String> = (0..20).map(|i| i.to_string()).collect();
+        let mut new = old.clone();
+        new[5] = "changed".to_string();
+        new[5 + 2 * DIFF_CONTEXT_LINES] = "changed".to_string();
+        let lines = diff_lines(&old, &new);
+
+        assert_eq!(gen_hunks(&lines).len(), 1);
+    }
+
+    #[test]
+    fn test_gen_hunks_split_distant_changes() {
+        let old: Vec<String> = (0..30).map(|i| i.to_string()).collect();
+        let mut new = old.clone();
+        new[0] = "changed".to_string();
+        new[29] = "changed".to_string();
+        let lines = diff_lines(&old, &new);
+
+        let hunks = gen_hunks(&lines);
+
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0][0], DiffLine::Removed("0".to_string()));
+        assert_eq!(
+            hunks[1][hunks[1].len() - 1],
+            DiffLine::Added("changed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_gen_unified_view_color() {
+        let current: NetworkState = serde_yaml::from_str(
+            r"---
+hostname:
+  running: host-a
+",
+        )
+        .unwrap();
+        let desired: NetworkState = serde_yaml::from_str(
+            r"---
+hostname:
+  running: host-b
+",
+        )
+        .unwrap();
+
+        let output = gen_unified_view(&desired, &current, false).unwrap();
+        assert!(!output.contains('\x1b'));
+        assert!(output.contains("-running: host-a"));
+        assert!(output.contains("+running: host-b"));
+
+        let output = gen_unified_view(&desired, &current, true).unwrap();
+        assert!(output.contains(&format!("{COLOR_RED}-running: host-a")));
+        assert!(output.contains(&format!("{COLOR_GREEN}+running: host-b")));
+    }
+
+    #[test]
+    fn test_gen_unified_view_routes() {
+        let diff_state: NetworkState = serde_yaml::from_str(
+            r"---
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    state: absent
+  - destination: 203.0.113.0/24
+    next-hop-interface: eth1
+",
+        )
+        .unwrap();
+
+        let output =
+            gen_unified_view(&diff_state, &NetworkState::new(), false).unwrap();
+
+        assert!(output.contains("@@ routes @@"));
+        assert!(output.contains("-- destination: 198.51.100.0/24"));
+        assert!(output.contains("+- destination: 203.0.113.0/24"));
+        assert!(!output.contains("state: absent"));
+    }
+
+    #[test]
+    fn test_gen_unified_view_nexthops() {
+        let diff_state: NetworkState = serde_yaml::from_str(
+            r"---
+nexthops:
+  config:
+  - id: 1
+    state: absent
+  - id: 2
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+",
+        )
+        .unwrap();
+
+        let output =
+            gen_unified_view(&diff_state, &NetworkState::new(), false).unwrap();
+
+        assert!(output.contains("@@ nexthops @@"));
+        assert!(output.contains("-- id: 1"));
+        assert!(output.contains("+- id: 2"));
+        assert!(output.contains("+  next-hop-address: 192.0.2.1"));
+    }
+}
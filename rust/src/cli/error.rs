@@ -4,12 +4,33 @@ use nmstate::NmstateError;
 
 pub(crate) const DEFAULT_ERROR_CODE: i32 = 1;
 pub(crate) const EX_DATAERR: i32 = 65;
+// Distinguish from DEFAULT_ERROR_CODE, so drift check could tell whether
+// difference found or failure.
+pub(crate) const EX_DIFF_FOUND: i32 = 2;
 const EX_USAGE: i32 = 64;
 
 #[derive(Debug, Default)]
 pub(crate) struct CliError {
     pub(crate) code: i32,
     pub(crate) error_msg: String,
+    pub(crate) nmstate_error: Option<NmstateError>,
+}
+
+impl CliError {
+    // Replace the error message with JSON serialized error, including the
+    // verification difference of NmstateError if any.
+    pub(crate) fn into_json(self) -> Self {
+        let json = match self.nmstate_error.as_ref() {
+            Some(e) => serde_json::to_string(e),
+            None => serde_json::to_string(&serde_json::json!({
+                "msg": self.error_msg.as_str(),
+            })),
+        };
+        match json {
+            Ok(error_msg) => Self { error_msg, ..self },
+            Err(_) => self,
+        }
+    }
 }
 
 impl From<&str> for CliError {
@@ -17,6 +38,7 @@ impl From<&str> for CliError {
         Self {
             code: DEFAULT_ERROR_CODE,
             error_msg: msg.into(),
+            ..Default::default()
         }
     }
 }
@@ -26,6 +48,7 @@ impl From<String> for CliError {
         Self {
             code: DEFAULT_ERROR_CODE,
             error_msg,
+            ..Default::default()
         }
     }
 }
@@ -41,6 +64,7 @@ impl From<std::io::Error> for CliError {
         Self {
             code: DEFAULT_ERROR_CODE,
             error_msg: format!("std::io::Error: {e}"),
+            ..Default::default()
         }
     }
 }
@@ -50,6 +74,7 @@ impl From<NmstateError> for CliError {
         Self {
             code: DEFAULT_ERROR_CODE,
             error_msg: format!("NmstateError: {e}"),
+            nmstate_error: Some(e),
         }
     }
 }
@@ -59,6 +84,7 @@ impl From<serde_yaml::Error> for CliError {
         Self {
             code: EX_DATAERR,
             error_msg: format!("serde_yaml::Error: {e}"),
+            ..Default::default()
         }
     }
 }
@@ -68,6 +94,7 @@ impl From<clap::Error> for CliError {
         Self {
             code: EX_USAGE,
             error_msg: format!("clap::Error {e}"),
+            ..Default::default()
         }
     }
 }
@@ -77,6 +104,7 @@ impl From<serde_json::Error> for CliError {
         Self {
             code: EX_DATAERR,
             error_msg: format!("serde_json::Error {e}"),
+            ..Default::default()
         }
     }
 }
@@ -1,9 +1,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use nmstate::GenConfBackend;
+
 use crate::{error::CliError, state::state_from_file};
 
-pub(crate) fn gen_conf(file_path: &str) -> Result<String, CliError> {
-    let net_state = state_from_file(file_path)?;
+pub(crate) fn gen_conf(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    let file_path = matches.value_of("STATE_FILE").unwrap_or("-");
+    let mut net_state = state_from_file(file_path)?;
+    match matches.value_of("BACKEND") {
+        Some("networkd") => {
+            net_state.set_gen_conf_backend(GenConfBackend::SystemdNetworkd);
+        }
+        Some("netplan") => {
+            net_state.set_gen_conf_backend(GenConfBackend::Netplan);
+        }
+        _ => {
+            net_state.set_gen_conf_backend(GenConfBackend::NetworkManager);
+        }
+    }
     let confs = net_state.gen_conf()?;
     let escaped_string = serde_yaml::to_string(&confs)?;
     Ok(escaped_string.replace("\\n", "\n\n"))
@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use nmstate::NetworkState;
+
+use crate::error::CliError;
+
+pub(crate) fn import(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    // The required() has ensured the unwrap() will never fail
+    let path = matches.value_of("PATH").unwrap();
+    // The untranslated configurations are logged as warning by nmstate
+    let (net_state, _) = match matches.value_of("FROM") {
+        Some("ifcfg") => NetworkState::from_ifcfg_dir(path)?,
+        Some("debian") => NetworkState::from_debian_interfaces(path)?,
+        _ => (NetworkState::from_nm_keyfiles(path)?, Vec::new()),
+    };
+    Ok(serde_yaml::to_string(&net_state)?)
+}
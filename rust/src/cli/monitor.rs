@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+
+use futures_util::StreamExt;
+use nmstate::NetworkState;
+
+use crate::error::CliError;
+
+// Print partial network state on every change till interrupted by SIGINT
+pub(crate) fn monitor(matches: &clap::ArgMatches) -> Result<String, CliError> {
+    let mut net_state = NetworkState::new();
+    net_state.set_kernel_only(matches.is_present("KERNEL"));
+    net_state
+        .set_running_config_only(matches.is_present("RUNNING_CONFIG_ONLY"));
+    net_state.set_include_secrets(matches.is_present("SHOW_SECRETS"));
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .enable_time()
+        .build()
+        .map_err(|e| {
+            CliError::from(format!("tokio::runtime::Builder failed with {e}"))
+        })?;
+    rt.block_on(monitor_async(net_state, matches.is_present("JSON")))?;
+    Ok(String::new())
+}
+
+async fn monitor_async(
+    net_state: NetworkState,
+    is_json: bool,
+) -> Result<(), CliError> {
+    let mut watcher = net_state.watch().await?;
+    let mut ctrlc_stream = tokio::signal::unix::signal(
+        tokio::signal::unix::SignalKind::interrupt(),
+    )
+    .map_err(|e| format!("tokio failed to hook on signal SIGINT: {e}"))?;
+    log::info!("Monitoring network state changes, press Ctrl+C to stop");
+    loop {
+        tokio::select! {
+            _ = ctrlc_stream.recv() => {
+                return Ok(());
+            }
+            delta = watcher.next() => {
+                match delta {
+                    Some(delta) => print_delta(&delta?, is_json)?,
+                    None => {
+                        return Err("All network state monitors stopped".into())
+                    }
+                }
+            }
+        }
+    }
+}
+
+// JSON output is one change per line, YAML output is one document per change
+fn print_delta(delta: &NetworkState, is_json: bool) -> Result<(), CliError> {
+    let mut stdout = std::io::stdout().lock();
+    if is_json {
+        writeln!(stdout, "{}", serde_json::to_string(delta)?)?;
+    } else {
+        write!(stdout, "---\n{}", serde_yaml::to_string(delta)?)?;
+    }
+    stdout.flush()?;
+    Ok(())
+}
@@ -4,12 +4,19 @@
 mod apply;
 #[cfg(feature = "query_apply")]
 mod autoconf;
+#[cfg(feature = "query_apply")]
+mod daemon;
+#[cfg(feature = "query_apply")]
+mod diff;
 mod error;
 mod format;
 #[cfg(feature = "gen_conf")]
 mod gen_conf;
 #[cfg(feature = "gen_revert")]
 mod gen_revert;
+mod import;
+#[cfg(feature = "query_apply")]
+mod monitor;
 #[cfg(feature = "query_apply")]
 pub(crate) mod persist_nic;
 #[cfg(feature = "query_apply")]
@@ -32,15 +39,19 @@ use crate::apply::{
 };
 #[cfg(feature = "query_apply")]
 use crate::autoconf::autoconf;
+#[cfg(feature = "query_apply")]
+use crate::diff::diff;
 #[cfg(feature = "gen_conf")]
 use crate::gen_conf::gen_conf;
 #[cfg(feature = "gen_revert")]
 use crate::gen_revert::gen_revert;
 #[cfg(feature = "query_apply")]
+use crate::monitor::monitor;
+#[cfg(feature = "query_apply")]
 use crate::policy::policy;
 #[cfg(feature = "query_apply")]
 use crate::query::show;
-use crate::result::print_result_and_exit;
+use crate::result::{print_diff_result_and_exit, print_result_and_exit};
 #[cfg(feature = "query_apply")]
 use crate::service::ncl_service;
 #[cfg(feature = "query_apply")]
@@ -65,6 +76,9 @@ const SUB_CMD_POLICY: &str = "policy";
 const SUB_CMD_FORMAT: &str = "format";
 const SUB_CMD_GEN_REVERT: &str = "gr";
 const SUB_CMD_STATISTIC: &str = "statistic";
+const SUB_CMD_IMPORT: &str = "import";
+const SUB_CMD_DIFF: &str = "diff";
+const SUB_CMD_MONITOR: &str = "monitor";
 
 fn main() {
     let argv: Vec<String> = std::env::args().collect();
@@ -115,6 +129,15 @@ fn main() {
                         .takes_value(false)
                         .help("Show kernel network state only")
                 )
+                .arg(
+                    clap::Arg::new("NETNS")
+                        .long("netns")
+                        .takes_value(true)
+                        .help(
+                            "Show network state of specified network \
+                            namespace(name, path or PID) in kernel only mode",
+                        ),
+                )
                 .arg(
                     clap::Arg::new("JSON")
                         .long("json")
@@ -193,6 +216,42 @@ fn main() {
                         .takes_value(false)
                         .help("Do not make the state persistent"),
                 )
+                .arg(
+                    clap::Arg::new("NETNS")
+                        .long("netns")
+                        .takes_value(true)
+                        .help(
+                            "Apply network state to specified network \
+                            namespace(name, path or PID) in kernel only mode",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("DRY_RUN")
+                        .long("dry-run")
+                        .takes_value(false)
+                        .help(
+                            "Validate the state and show the planned \
+                            changes without applying them",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("CREATE_ROUTE_TABLE_NAMES")
+                        .long("create-route-table-names")
+                        .takes_value(false)
+                        .help(
+                            "Store unknown route table names to \
+                            /etc/iproute2/rt_tables.d/nmstate.conf",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("JSON")
+                        .long("json")
+                        .takes_value(false)
+                        .help(
+                            "Show state and error in json format, \
+                            verification error includes the difference",
+                        ),
+                )
         )
         .subcommand(
             clap::Command::new(SUB_CMD_GEN_CONF)
@@ -202,6 +261,15 @@ fn main() {
                         .required(true)
                         .index(1)
                         .help("Network state file"),
+                )
+                .arg(
+                    clap::Arg::new("BACKEND")
+                        .short('b')
+                        .long("backend")
+                        .takes_value(true)
+                        .possible_values(["nm", "networkd", "netplan"])
+                        .default_value("nm")
+                        .help("Network backend to generate configuration for"),
                 ),
         )
         .subcommand(
@@ -275,6 +343,16 @@ fn main() {
                         .takes_value(true)
                         .default_value(DEFAULT_SERVICE_FOLDER)
                         .help("Folder hold network state files"),
+                )
+                .arg(
+                    clap::Arg::new("DAEMON")
+                        .long("daemon")
+                        .short('d')
+                        .takes_value(false)
+                        .help(
+                            "Keep running to detect and fix drift from the \
+                            applied state files",
+                        ),
                 ),
         )
         .subcommand(
@@ -330,6 +408,30 @@ fn main() {
                         .help("Network state file"),
                 ),
         )
+        .subcommand(
+            clap::Command::new(SUB_CMD_IMPORT)
+                .about(
+                    "Generate network state from existing network \
+                    configuration files",
+                )
+                .arg(
+                    clap::Arg::new("PATH")
+                        .required(true)
+                        .index(1)
+                        .help(
+                            "Folder holding the network configuration files \
+                            or the Debian interfaces file",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("FROM")
+                        .long("from")
+                        .takes_value(true)
+                        .possible_values(["nm-keyfile", "ifcfg", "debian"])
+                        .default_value("nm-keyfile")
+                        .help("Format of the network configuration files"),
+                ),
+        )
         .subcommand(
             clap::Command::new(SUB_CMD_GEN_REVERT)
                 .alias("gr")
@@ -354,6 +456,85 @@ fn main() {
                         .help("Show state in json format"),
                 )
         )
+        .subcommand(
+            clap::Command::new(SUB_CMD_DIFF)
+                .about(
+                    "Show what applying the desired state would change. \
+                    Exit with 2 when difference found",
+                )
+                .arg(
+                    clap::Arg::new("STATE_FILE")
+                        .required(true)
+                        .index(1)
+                        .help("Network state file"),
+                )
+                .arg(
+                    clap::Arg::new("CURRENT_STATE")
+                        .short('c')
+                        .long("current")
+                        .takes_value(true)
+                        .help(
+                            "Read current network state from file instead \
+                            of querying the system",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("YAML")
+                        .long("yaml")
+                        .takes_value(false)
+                        .conflicts_with("JSON")
+                        .help("Show the minimal network state in yaml format"),
+                )
+                .arg(
+                    clap::Arg::new("JSON")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Show the minimal network state in json format"),
+                )
+                .arg(
+                    clap::Arg::new("NO_COLOR")
+                        .long("no-color")
+                        .takes_value(false)
+                        .help(
+                            "Do not colorize the unified view, also honored \
+                            via the NO_COLOR environment variable",
+                        ),
+                )
+        )
+        .subcommand(
+            clap::Command::new(SUB_CMD_MONITOR)
+                .about(
+                    "Print changed network state on every change till \
+                    interrupted",
+                )
+                .arg(
+                    clap::Arg::new("KERNEL")
+                        .short('k')
+                        .long("kernel")
+                        .takes_value(false)
+                        .help("Monitor kernel network state only")
+                )
+                .arg(
+                    clap::Arg::new("JSON")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Show each change as single line json"),
+                )
+                .arg(
+                    clap::Arg::new("RUNNING_CONFIG_ONLY")
+                        .short('r')
+                        .long("running-config")
+                        .takes_value(false)
+                        .help("Monitor running configuration only"),
+                )
+                .arg(
+                    clap::Arg::new("SHOW_SECRETS")
+                        .short('s')
+                        .long("show-secrets")
+                        .takes_value(false)
+                        .help("Show secrets(hide by default)"),
+                )
+        )
    .subcommand(
             clap::Command::new(SUB_CMD_STATISTIC)
                 .alias("st")
@@ -457,9 +638,7 @@ fn main() {
     log::info!("Nmstate version: {}", clap::crate_version!());
 
     if let Some(matches) = matches.subcommand_matches(SUB_CMD_GEN_CONF) {
-        if let Some(file_path) = matches.value_of("STATE_FILE") {
-            print_result_and_exit(gen_conf(file_path));
-        }
+        print_result_and_exit(gen_conf(matches));
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_SHOW) {
         print_result_and_exit(show(matches));
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_APPLY) {
@@ -500,6 +679,12 @@ fn main() {
         print_result_and_exit(format::format(
             matches.value_of("STATE_FILE").unwrap(),
         ));
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_IMPORT) {
+        print_result_and_exit(import::import(matches));
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_DIFF) {
+        print_diff_result_and_exit(diff(matches));
+    } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_MONITOR) {
+        print_result_and_exit(monitor(matches));
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_STATISTIC)
     {
         print_result_and_exit(statistic(matches));
@@ -538,7 +723,9 @@ fn main() {
 }
 
 #[cfg(not(feature = "gen_conf"))]
-fn gen_conf(_file_path: &str) -> Result<String, crate::error::CliError> {
+fn gen_conf(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
     Err("The gc sub-command require `gen_conf` feature been \
         enabled during compiling"
         .into())
@@ -553,6 +740,26 @@ fn gen_revert(
         .into())
 }
 
+#[cfg(not(feature = "query_apply"))]
+fn diff(
+    _matches: &clap::ArgMatches,
+) -> Result<(String, bool), crate::error::CliError> {
+    Err("The diff sub-command require `query_apply` feature been \
+        enabled during compiling"
+        .into())
+}
+
+#[cfg(not(feature = "query_apply"))]
+fn monitor(
+    _matches: &clap::ArgMatches,
+) -> Result<String, crate::error::CliError> {
+    Err(
+        "The monitor sub-command require `query_apply` feature been \
+        enabled during compiling"
+            .into(),
+    )
+}
+
 #[cfg(not(feature = "query_apply"))]
 fn show(_matches: &clap::ArgMatches) -> Result<String, crate::error::CliError> {
     Err("The show sub-command require `query_apply` feature been \
@@ -1,8 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use nmstate::{
-    DnsState, HostNameState, NetworkState, OvnConfiguration, OvsDbGlobalConfig,
-    RouteRules, Routes,
+    DnsState, HostNameState, NetworkState, Nexthops, OvnConfiguration,
+    OvsDbGlobalConfig, RouteRules, Routes,
 };
 use serde::Serialize;
 use serde_yaml::Value;
@@ -17,6 +17,8 @@ pub(crate) struct SortedNetworkState {
     dns: Option<DnsState>,
     #[serde(rename = "route-rules", default)]
     rules: RouteRules,
+    #[serde(default, skip_serializing_if = "Nexthops::is_empty")]
+    nexthops: Nexthops,
     routes: Routes,
     interfaces: Vec<Value>,
     #[serde(rename = "ovs-db", skip_serializing_if = "Option::is_none")]
@@ -38,6 +40,10 @@ pub(crate) fn show(matches: &clap::ArgMatches) -> Result<String, CliError> {
     if matches.is_present("RUNNING_CONFIG_ONLY") {
         net_state.set_running_config_only(true);
     }
+    if let Some(netns) = matches.value_of("NETNS") {
+        net_state.set_kernel_only(true);
+        net_state.set_netns(netns);
+    }
     net_state.set_include_secrets(matches.is_present("SHOW_SECRETS"));
     net_state.retrieve()?;
     Ok(if let Some(ifname) = matches.value_of("IFNAME") {
@@ -93,6 +99,7 @@ pub(crate) fn sort_netstate(
             interfaces: new_ifaces,
             routes: net_state.routes,
             rules: net_state.rules,
+            nexthops: net_state.nexthops,
             dns: net_state.dns,
             ovsdb: net_state.ovsdb,
             ovn: net_state.ovn,
@@ -105,6 +112,7 @@ pub(crate) fn sort_netstate(
         interfaces: Vec::new(),
         routes: net_state.routes,
         rules: net_state.rules,
+        nexthops: net_state.nexthops,
         dns: net_state.dns,
         ovsdb: net_state.ovsdb,
         ovn: net_state.ovn,
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::error::CliError;
+use crate::error::{CliError, EX_DIFF_FOUND};
 
 pub(crate) fn print_result_and_exit(result: Result<String, CliError>) {
     match result {
@@ -14,3 +14,17 @@ pub(crate) fn print_result_and_exit(result: Result<String, CliError>) {
         }
     }
 }
+
+// Exit with EX_DIFF_FOUND when difference found
+pub(crate) fn print_diff_result_and_exit(
+    result: Result<(String, bool), CliError>,
+) {
+    match result {
+        Ok((s, true)) => {
+            println!("{s}");
+            std::process::exit(EX_DIFF_FOUND);
+        }
+        Ok((_, false)) => std::process::exit(0),
+        Err(e) => print_result_and_exit(Err(e)),
+    }
+}
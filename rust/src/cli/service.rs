@@ -6,13 +6,15 @@ use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{apply::apply, error::CliError};
+use crate::{apply::apply, daemon::run_daemon, error::CliError};
 
 const CONFIG_FILE_EXTENTION: &str = "yml";
 const APPLIED_FILE_EXTENTION: &str = "applied";
 const CONFIG_FILE_NAME: &str = "nmstate.conf";
+const DEFAULT_DRIFT_CHECK_INTERVAL: u64 = 60;
+const DEFAULT_STATUS_SOCKET: &str = "/run/nmstate/nmstate.sock";
 
 #[derive(Debug, Default, Deserialize)]
 struct Config {
@@ -20,10 +22,55 @@ struct Config {
     service: ServiceConfig,
 }
 
-#[derive(Debug, Default, Deserialize)]
-struct ServiceConfig {
+#[derive(Debug, Deserialize)]
+pub(crate) struct ServiceConfig {
     #[serde(default)]
     keep_state_file_after_apply: bool,
+    /// Action taken by daemon mode when network state drifted from
+    /// applied state files.
+    #[serde(default)]
+    pub(crate) drift_action: DriftAction,
+    /// Seconds between periodic drift checks in daemon mode, drift check is
+    /// also triggered by network state change.
+    #[serde(default = "default_drift_check_interval")]
+    pub(crate) drift_check_interval: u64,
+    /// Unix socket path daemon mode reporting its status on.
+    #[serde(default = "default_status_socket")]
+    pub(crate) status_socket: String,
+}
+
+impl Default for ServiceConfig {
+    fn default() -> Self {
+        Self {
+            keep_state_file_after_apply: false,
+            drift_action: DriftAction::default(),
+            drift_check_interval: default_drift_check_interval(),
+            status_socket: default_status_socket(),
+        }
+    }
+}
+
+fn default_drift_check_interval() -> u64 {
+    DEFAULT_DRIFT_CHECK_INTERVAL
+}
+
+fn default_status_socket() -> String {
+    DEFAULT_STATUS_SOCKET.to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum DriftAction {
+    /// Reapply the state files
+    Reapply,
+    /// Only log the drift
+    Alert,
+}
+
+impl Default for DriftAction {
+    fn default() -> Self {
+        Self::Reapply
+    }
 }
 
 #[derive(Eq, Hash, PartialEq, Clone, PartialOrd, Ord)]
@@ -47,6 +94,19 @@ pub(crate) fn ncl_service(
 
     let config = load_config(folder)?;
 
+    apply_unapplied_state_files(folder, &config, matches)?;
+
+    if matches.try_contains_id("DAEMON").unwrap_or_default() {
+        run_daemon(folder, &config.service)?;
+    }
+    Ok(String::new())
+}
+
+fn apply_unapplied_state_files(
+    folder: &str,
+    config: &Config,
+    matches: &clap::ArgMatches,
+) -> Result<(), CliError> {
     let state_files = match get_unapplied_state_files(
         folder,
         config.service.keep_state_file_after_apply,
@@ -57,7 +117,7 @@ pub(crate) fn ncl_service(
                 "Failed to read config folder {folder} due to \
                     error {e}, ignoring"
             );
-            return Ok(String::new());
+            return Ok(());
         }
     };
     if state_files.is_empty() {
@@ -66,7 +126,7 @@ pub(crate) fn ncl_service(
             CONFIG_FILE_EXTENTION,
             folder
         );
-        return Ok(String::new());
+        return Ok(());
     }
 
     // Due to bug of NetworkManager, the `After=NetworkManager.service` in
@@ -119,7 +179,7 @@ pub(crate) fn ncl_service(
         }
     }
 
-    Ok("".to_string())
+    Ok(())
 }
 
 // If `keep_state_file_after_apply` is true, we collect all file ending with
@@ -165,6 +225,23 @@ fn get_unapplied_state_files(
     Ok(ret)
 }
 
+// Collect content of all `.applied` files which hold the state files applied
+// successfully.
+pub(crate) fn get_applied_state_files(
+    folder: &str,
+) -> Result<Vec<(PathBuf, String)>, CliError> {
+    let mut ret = Vec::new();
+    for entry in Path::new(folder).read_dir()? {
+        let file = entry?.path();
+        if file.extension() == Some(OsStr::new(APPLIED_FILE_EXTENTION)) {
+            let content = fs::read_to_string(&file)?;
+            ret.push((file, content));
+        }
+    }
+    ret.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(ret)
+}
+
 // Dump state to `.applied` file.
 pub(crate) fn write_content(
     file_path: &Path,
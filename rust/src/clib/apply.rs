@@ -8,10 +8,11 @@ use libc::{c_char, c_int};
 use crate::{
     init_logger,
     query::{
-        NMSTATE_FLAG_KERNEL_ONLY, NMSTATE_FLAG_MEMORY_ONLY,
-        NMSTATE_FLAG_NO_COMMIT, NMSTATE_FLAG_NO_VERIFY,
+        NMSTATE_FLAG_JSON_ERROR, NMSTATE_FLAG_KERNEL_ONLY,
+        NMSTATE_FLAG_MEMORY_ONLY, NMSTATE_FLAG_NO_COMMIT,
+        NMSTATE_FLAG_NO_VERIFY,
     },
-    state::c_str_to_net_state,
+    state::{c_str_to_net_state, is_state_in_json},
     NMSTATE_FAIL, NMSTATE_PASS,
 };
 
@@ -83,8 +84,13 @@ pub extern "C" fn nmstate_net_state_apply(
     }
 
     if let Err(e) = result {
+        let msg = if (flags & NMSTATE_FLAG_JSON_ERROR) > 0 {
+            serde_json::to_string(&e).unwrap_or_else(|_| e.msg().to_string())
+        } else {
+            e.msg().to_string()
+        };
         unsafe {
-            *err_msg = CString::new(e.msg()).unwrap().into_raw();
+            *err_msg = CString::new(msg).unwrap().into_raw();
             *err_kind =
                 CString::new(format!("{}", &e.kind())).unwrap().into_raw();
         }
@@ -93,3 +99,96 @@ pub extern "C" fn nmstate_net_state_apply(
         NMSTATE_PASS
     }
 }
+
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[no_mangle]
+pub extern "C" fn nmstate_net_state_apply_dry_run(
+    flags: u32,
+    state: *const c_char,
+    report: *mut *mut c_char,
+    log: *mut *mut c_char,
+    err_kind: *mut *mut c_char,
+    err_msg: *mut *mut c_char,
+) -> c_int {
+    assert!(!report.is_null());
+    assert!(!log.is_null());
+    assert!(!err_kind.is_null());
+    assert!(!err_msg.is_null());
+
+    unsafe {
+        *report = std::ptr::null_mut();
+        *log = std::ptr::null_mut();
+        *err_kind = std::ptr::null_mut();
+        *err_msg = std::ptr::null_mut();
+    }
+
+    if state.is_null() {
+        return NMSTATE_PASS;
+    }
+
+    let logger = match init_logger() {
+        Ok(l) => l,
+        Err(e) => {
+            unsafe {
+                *err_msg = CString::new(format!("Failed to setup logger: {e}"))
+                    .unwrap()
+                    .into_raw();
+            }
+            return NMSTATE_FAIL;
+        }
+    };
+    let now = SystemTime::now();
+
+    let mut net_state = match c_str_to_net_state(state, err_kind, err_msg) {
+        Ok(s) => s,
+        Err(rc) => {
+            return rc;
+        }
+    };
+
+    if (flags & NMSTATE_FLAG_KERNEL_ONLY) > 0 {
+        net_state.set_kernel_only(true);
+    }
+
+    if (flags & NMSTATE_FLAG_MEMORY_ONLY) > 0 {
+        net_state.set_memory_only(true);
+    }
+
+    let input_is_json = is_state_in_json(state);
+
+    let result = net_state.apply_dry_run();
+    unsafe {
+        *log = CString::new(logger.drain(now)).unwrap().into_raw();
+    }
+
+    let serialize = result.and_then(|r| {
+        if input_is_json {
+            serde_json::to_string(&r).map_err(|e| {
+                nmstate::NmstateError::new(
+                    nmstate::ErrorKind::Bug,
+                    format!("Failed to convert report {r:?} to JSON: {e}"),
+                )
+            })
+        } else {
+            serde_yaml::to_string(&r).map_err(|e| {
+                nmstate::NmstateError::new(
+                    nmstate::ErrorKind::Bug,
+                    format!("Failed to convert report {r:?} to YAML: {e}"),
+                )
+            })
+        }
+    });
+
+    match serialize {
+        Ok(report_str) => unsafe {
+            *report = CString::new(report_str).unwrap().into_raw();
+            NMSTATE_PASS
+        },
+        Err(e) => unsafe {
+            *err_msg = CString::new(e.msg()).unwrap().into_raw();
+            *err_kind =
+                CString::new(format!("{}", &e.kind())).unwrap().into_raw();
+            NMSTATE_FAIL
+        },
+    }
+}
@@ -25,7 +25,9 @@ use once_cell::sync::OnceCell;
 use crate::logger::MemoryLogger;
 
 #[cfg(feature = "query_apply")]
-pub use crate::apply::nmstate_net_state_apply;
+pub use crate::apply::{
+    nmstate_net_state_apply, nmstate_net_state_apply_dry_run,
+};
 #[cfg(feature = "query_apply")]
 pub use crate::checkpoint::{
     nmstate_checkpoint_commit, nmstate_checkpoint_rollback,
@@ -15,6 +15,7 @@ pub(crate) const NMSTATE_FLAG_NO_COMMIT: u32 = 1 << 5;
 pub(crate) const NMSTATE_FLAG_MEMORY_ONLY: u32 = 1 << 6;
 pub(crate) const NMSTATE_FLAG_RUNNING_CONFIG_ONLY: u32 = 1 << 7;
 pub(crate) const NMSTATE_FLAG_YAML_OUTPUT: u32 = 1 << 8;
+pub(crate) const NMSTATE_FLAG_JSON_ERROR: u32 = 1 << 10;
 
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
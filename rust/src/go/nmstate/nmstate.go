@@ -6,6 +6,7 @@ package nmstate
 // #include <stdlib.h>
 import "C"
 import (
+	"encoding/json"
 	"fmt"
 	"io"
 	"time"
@@ -15,6 +16,7 @@ type Nmstate struct {
 	timeout    uint
 	logsWriter io.Writer
 	flags      byte
+	jsonError  bool
 }
 
 const (
@@ -27,6 +29,53 @@ const (
 	runningConfigOnly
 )
 
+// Used by ApplyNetState() when WithVerificationError() is set.
+const jsonError = 1 << 10
+
+// VerificationError is returned by ApplyNetState() when WithVerificationError()
+// is set and the current network state does not match the desired state after
+// applied. The Path is the first mismatched property, e.g.
+// `interfaces[name=eth1].ipv4.dhcp`.
+type VerificationError struct {
+	Msg     string
+	Path    string
+	Desired interface{}
+	Current interface{}
+}
+
+func (e *VerificationError) Error() string {
+	return e.Msg
+}
+
+type jsonErrorMsg struct {
+	Kind             string `json:"kind"`
+	Msg              string `json:"msg"`
+	VerificationDiff *struct {
+		Path    string      `json:"path"`
+		Desired interface{} `json:"desired"`
+		Current interface{} `json:"current"`
+	} `json:"verification-diff"`
+}
+
+// Parse the error generated with jsonError flag, return the error message
+// and VerificationError if any.
+func parseJsonError(errMsg string) (string, *VerificationError) {
+	var jsonErr jsonErrorMsg
+	if err := json.Unmarshal([]byte(errMsg), &jsonErr); err != nil {
+		return errMsg, nil
+	}
+	if jsonErr.Kind != "VerificationError" {
+		return jsonErr.Msg, nil
+	}
+	verifyErr := &VerificationError{Msg: jsonErr.Msg}
+	if jsonErr.VerificationDiff != nil {
+		verifyErr.Path = jsonErr.VerificationDiff.Path
+		verifyErr.Desired = jsonErr.VerificationDiff.Desired
+		verifyErr.Current = jsonErr.VerificationDiff.Current
+	}
+	return jsonErr.Msg, verifyErr
+}
+
 func New(options ...func(*Nmstate)) *Nmstate {
 	nms := &Nmstate{}
 	for _, option := range options {
@@ -89,6 +138,13 @@ func WithRunningConfigOnly() func(*Nmstate) {
 	}
 }
 
+// Make ApplyNetState() wrap a *VerificationError on verification failure.
+func WithVerificationError() func(*Nmstate) {
+	return func(n *Nmstate) {
+		n.jsonError = true
+	}
+}
+
 // Retrieve the network state in json format. This function returns the current
 // network state or an error.
 func (n *Nmstate) RetrieveNetState() (string, error) {
@@ -124,7 +180,11 @@ func (n *Nmstate) ApplyNetState(state string) (string, error) {
 		err_msg  *C.char
 	)
 	c_state = C.CString(state)
-	rc := C.nmstate_net_state_apply(C.uint(n.flags), c_state, C.uint(n.timeout), &log, &err_kind, &err_msg)
+	flags := C.uint(n.flags)
+	if n.jsonError {
+		flags |= jsonError
+	}
+	rc := C.nmstate_net_state_apply(flags, c_state, C.uint(n.timeout), &log, &err_kind, &err_msg)
 
 	defer func() {
 		C.nmstate_cstring_free(c_state)
@@ -133,7 +193,15 @@ func (n *Nmstate) ApplyNetState(state string) (string, error) {
 		C.nmstate_cstring_free(log)
 	}()
 	if rc != 0 {
-		return "", fmt.Errorf("failed applying nmstate net state %s with rc: %d, err_msg: %s, err_kind: %s", state, rc, C.GoString(err_msg), C.GoString(err_kind))
+		msg := C.GoString(err_msg)
+		var verifyErr *VerificationError
+		if n.jsonError {
+			msg, verifyErr = parseJsonError(msg)
+		}
+		if verifyErr != nil {
+			return "", fmt.Errorf("failed applying nmstate net state %s with rc: %d, err_kind: %s: %w", state, rc, C.GoString(err_kind), verifyErr)
+		}
+		return "", fmt.Errorf("failed applying nmstate net state %s with rc: %d, err_msg: %s, err_kind: %s", state, rc, msg, C.GoString(err_kind))
 	}
 	if err := n.writeLog(log); err != nil {
 		return "", fmt.Errorf("failed when applying state: %v", err)
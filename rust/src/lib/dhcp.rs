@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// DHCP lease obtained by the nmstate built-in DHCP client which is only used
+/// in kernel mode.
+/// This property is query only, it will be ignored when applying.
+/// Example YAML output:
+/// ```yaml
+/// ---
+/// interfaces:
+/// - name: eth1
+///   ipv4:
+///     enabled: true
+///     dhcp: true
+///     dhcp-lease:
+///       state: bound
+///       address: 192.0.2.100
+///       prefix-length: 24
+///       server: 192.0.2.1
+///       obtained: 1700000000
+///       lease-time: 3600
+///       renew-time: 1800
+///       rebind-time: 3150
+///       gateways:
+///       - 192.0.2.1
+///       dns-servers:
+///       - 192.0.2.1
+/// ```
+pub struct DhcpLease {
+    /// State of lease calculated at the time of query.
+    #[serde(default)]
+    pub state: DhcpLeaseState,
+    /// Leased IP address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<IpAddr>,
+    /// Prefix length of leased IP address.
+    /// Serialize and deserialize to/from `prefix-length`.
+    #[serde(default)]
+    pub prefix_length: u8,
+    /// Address of DHCP server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server: Option<IpAddr>,
+    /// Seconds since UNIX epoch when this lease was obtained or last renewed.
+    #[serde(default)]
+    pub obtained: u64,
+    /// Lease time in seconds. For DHCPv6, this is the valid lifetime of
+    /// leased address.
+    /// Serialize and deserialize to/from `lease-time`.
+    #[serde(default)]
+    pub lease_time: u32,
+    /// Preferred lifetime in seconds of leased address, only for DHCPv6.
+    /// Serialize and deserialize to/from `preferred-time`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_time: Option<u32>,
+    /// Seconds after `obtained` to renew this lease, a.k.a T1.
+    /// Serialize and deserialize to/from `renew-time`.
+    #[serde(default)]
+    pub renew_time: u32,
+    /// Seconds after `obtained` to rebind this lease, a.k.a T2.
+    /// Serialize and deserialize to/from `rebind-time`.
+    #[serde(default)]
+    pub rebind_time: u32,
+    /// Routers provided by DHCP server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gateways: Option<Vec<IpAddr>>,
+    /// Classless static routes(RFC 3442) provided by DHCP server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routes: Option<Vec<DhcpLeaseRoute>>,
+    /// DNS name servers provided by DHCP server.
+    /// Serialize and deserialize to/from `dns-servers`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns_servers: Option<Vec<IpAddr>>,
+    /// DNS search domains provided by DHCP server.
+    /// Serialize and deserialize to/from `dns-searches`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns_searches: Option<Vec<String>>,
+    /// Interface MTU provided by DHCP server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct DhcpLeaseRoute {
+    /// Route destination in the format of `<ip>/<prefix-length>`.
+    pub destination: String,
+    /// Router of this route.
+    /// Serialize and deserialize to/from `next-hop-address`.
+    pub next_hop_address: IpAddr,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// The DHCP client state of a lease defined in RFC 2131 section 4.4.
+pub enum DhcpLeaseState {
+    /// Lease is valid, no action required till T1.
+    #[default]
+    Bound,
+    /// T1 passed, client should unicast renew request to the lease server.
+    Renewing,
+    /// T2 passed, client should broadcast rebind request to any server.
+    Rebinding,
+    /// Lease time passed, client should start over.
+    Expired,
+}
+
+impl std::fmt::Display for DhcpLeaseState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Bound => "bound",
+                Self::Renewing => "renewing",
+                Self::Rebinding => "rebinding",
+                Self::Expired => "expired",
+            }
+        )
+    }
+}
+
+impl DhcpLease {
+    /// Calculate the lease state at specified seconds since UNIX epoch.
+    pub fn state_at(&self, now: u64) -> DhcpLeaseState {
+        let elapsed = now.saturating_sub(self.obtained);
+        if elapsed >= self.lease_time as u64 {
+            DhcpLeaseState::Expired
+        } else if elapsed >= self.rebind_time as u64 {
+            DhcpLeaseState::Rebinding
+        } else if elapsed >= self.renew_time as u64 {
+            DhcpLeaseState::Renewing
+        } else {
+            DhcpLeaseState::Bound
+        }
+    }
+
+    /// Remaining seconds of lease time at specified seconds since UNIX epoch.
+    pub fn remaining_at(&self, now: u64) -> u32 {
+        (self.lease_time as u64)
+            .saturating_sub(now.saturating_sub(self.obtained))
+            .try_into()
+            .unwrap_or(u32::MAX)
+    }
+
+    /// Remaining seconds of preferred lifetime at specified seconds since
+    /// UNIX epoch. For DHCPv4, it is the same as [DhcpLease::remaining_at].
+    pub fn preferred_remaining_at(&self, now: u64) -> u32 {
+        let preferred = self.preferred_time.unwrap_or(self.lease_time);
+        (preferred as u64)
+            .saturating_sub(now.saturating_sub(self.obtained))
+            .try_into()
+            .unwrap_or(u32::MAX)
+    }
+
+    // Fill T1 and T2 with default values defined in RFC 2131 section 4.4.5
+    // if DHCP server does not provide them.
+    #[cfg_attr(not(feature = "query_apply"), allow(dead_code))]
+    pub(crate) fn sanitize(&mut self) {
+        if self.renew_time == 0 || self.renew_time > self.lease_time {
+            self.renew_time = self.lease_time / 2;
+        }
+        if self.rebind_time == 0
+            || self.rebind_time > self.lease_time
+            || self.rebind_time < self.renew_time
+        {
+            self.rebind_time = (self.lease_time as u64 * 7 / 8)
+                .try_into()
+                .unwrap_or(u32::MAX);
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::error::Error;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 #[allow(dead_code)]
 pub enum ErrorKind {
@@ -65,13 +67,60 @@ impl std::fmt::Display for NmstateError {
 
 impl Error for NmstateError {}
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
 pub struct NmstateError {
     kind: ErrorKind,
     msg: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
     line: String,
+    #[serde(skip_serializing_if = "is_zero")]
     position: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verification_diff: Option<Box<VerificationDiff>>,
+}
+
+fn is_zero(v: &usize) -> bool {
+    *v == 0
+}
+
+/// The property failed the verification after apply, attached to
+/// [NmstateError] of [ErrorKind::VerificationError].
+///
+/// Example JSON serialized VerificationDiff would be:
+///
+/// ```json
+/// {
+///   "path": "interfaces[name=eth1].ipv4.address[0].prefix-length",
+///   "desired": 24,
+///   "current": 16
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct VerificationDiff {
+    /// JSON path of the property. Interfaces are indexed by name, e.g.
+    /// `interfaces[name=eth1].ipv4.address[0]`.
+    pub path: String,
+    /// Desired value, `null` means desired to be removed.
+    pub desired: serde_json::Value,
+    /// Current value, `null` means not found in current state.
+    pub current: serde_json::Value,
+}
+
+impl VerificationDiff {
+    pub fn new(
+        path: String,
+        desired: serde_json::Value,
+        current: serde_json::Value,
+    ) -> Self {
+        Self {
+            path,
+            desired,
+            current,
+        }
+    }
 }
 
 impl NmstateError {
@@ -89,6 +138,19 @@ impl NmstateError {
             line: line.to_string(),
             msg,
             position,
+            ..Default::default()
+        }
+    }
+
+    pub fn new_verification_error(
+        msg: String,
+        verification_diff: VerificationDiff,
+    ) -> Self {
+        Self {
+            kind: ErrorKind::VerificationError,
+            msg,
+            verification_diff: Some(Box::new(verification_diff)),
+            ..Default::default()
         }
     }
 
@@ -109,6 +171,12 @@ impl NmstateError {
     pub fn position(&self) -> usize {
         self.position
     }
+
+    /// The property which failed the verification, only available for
+    /// [ErrorKind::VerificationError].
+    pub fn verification_diff(&self) -> Option<&VerificationDiff> {
+        self.verification_diff.as_deref()
+    }
 }
 
 impl From<serde_json::Error> for NmstateError {
@@ -2,17 +2,29 @@
 
 use std::collections::HashMap;
 
-use crate::{nm::nm_gen_conf, MergedNetworkState, NetworkState, NmstateError};
+use crate::{
+    netplan::netplan_gen_conf, networkd::networkd_gen_conf, nm::nm_gen_conf,
+    ovsdb::ovsdb_gen_conf, GenConfBackend, InterfaceType, MergedNetworkState,
+    NetworkState, NmstateError,
+};
+
+const OVSDB_GEN_CONF_KEY: &str = "ovsdb";
 
 impl NetworkState {
-    /// Generate offline network configurations.
-    /// Currently only support generate NetworkManager key file out of
-    /// NetworkState.
+    /// Generate offline network configurations for the backend chosen by
+    /// [NetworkState::set_gen_conf_backend()].
     ///
     /// The output is a [HashMap] with backend name as key and
     /// `Vec<(config_file_name, config_content>)>` as value.
     ///
-    /// The backend name for NetworkManager is `NetworkManager`.
+    /// The backend name for NetworkManager is `NetworkManager`, for
+    /// systemd-networkd is `systemd-networkd`, for netplan is `netplan`.
+    ///
+    /// When OVSDB global configuration or OVN bridge mappings are desired,
+    /// an additional `ovsdb` entry holds the OVSDB JSON-RPC `transact`
+    /// request (`ovsdb-transact.json`) and the equivalent `ovs-vsctl`
+    /// script (`ovs-vsctl.sh`). For backends other than NetworkManager,
+    /// the OVS bridges are also included in this entry.
     pub fn gen_conf(
         &self,
     ) -> Result<HashMap<String, Vec<(String, String)>>, NmstateError> {
@@ -23,7 +35,31 @@ impl NetworkState {
             true,  // gen_conf mode
             false, // memory only
         )?;
-        ret.insert("NetworkManager".to_string(), nm_gen_conf(&merged_state)?);
+        let confs = match self.gen_conf_backend {
+            GenConfBackend::NetworkManager => nm_gen_conf(&merged_state)?,
+            GenConfBackend::SystemdNetworkd => {
+                networkd_gen_conf(&merged_state)?
+            }
+            GenConfBackend::Netplan => netplan_gen_conf(&merged_state)?,
+        };
+        ret.insert(self.gen_conf_backend.to_string(), confs);
+
+        let include_bridges =
+            self.gen_conf_backend != GenConfBackend::NetworkManager;
+        // Only emit `ovsdb` entry when desired state has OVSDB or OVN changes
+        if merged_state.ovsdb.is_changed
+            || (include_bridges
+                && self
+                    .interfaces
+                    .user_ifaces
+                    .values()
+                    .any(|i| i.iface_type() == InterfaceType::OvsBridge))
+        {
+            let ovsdb_confs = ovsdb_gen_conf(&merged_state, include_bridges)?;
+            if !ovsdb_confs.is_empty() {
+                ret.insert(OVSDB_GEN_CONF_KEY.to_string(), ovsdb_confs);
+            }
+        }
         Ok(ret)
     }
 }
@@ -5,11 +5,12 @@ use std::collections::HashSet;
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
-    BaseInterface, BondInterface, DummyInterface, ErrorKind, EthernetInterface,
-    HsrInterface, InfiniBandInterface, IpsecInterface, LinuxBridgeInterface,
+    BareUdpInterface, BaseInterface, BondInterface, DummyInterface, ErrorKind,
+    EthernetInterface, GeneveInterface, HsrInterface, InfiniBandInterface,
+    IpTunnelInterface, IpVlanInterface, IpsecInterface, LinuxBridgeInterface,
     LoopbackInterface, MacSecInterface, MacVlanInterface, MacVtapInterface,
     NmstateError, OvsBridgeInterface, OvsInterface, VlanInterface,
-    VrfInterface, VxlanInterface, XfrmInterface,
+    VrfInterface, VxlanInterface, WireGuardInterface, XfrmInterface,
 };
 
 use crate::state::merge_json_value;
@@ -78,6 +79,38 @@ pub enum InterfaceType {
     Ipsec,
     /// Linux Xfrm kernel interface
     Xfrm,
+    /// [WireGuard interface](https://www.wireguard.com)
+    /// Deserialize and serialize from/to 'wireguard'.
+    #[serde(rename = "wireguard")]
+    WireGuard,
+    /// IPv4 over GRE tunnel.
+    /// Deserialize and serialize from/to 'gre'.
+    Gre,
+    /// Ethernet over GRE tunnel.
+    /// Deserialize and serialize from/to 'gretap'.
+    #[serde(rename = "gretap")]
+    GreTap,
+    /// IPv4 over IPv4 tunnel.
+    /// Deserialize and serialize from/to 'ipip'.
+    Ipip,
+    /// IPv6 over IPv4 tunnel.
+    /// Deserialize and serialize from/to 'sit'.
+    Sit,
+    /// IPv4 or IPv6 over IPv6 tunnel.
+    /// Deserialize and serialize from/to 'ip6tnl'.
+    #[serde(rename = "ip6tnl")]
+    Ip6Tnl,
+    /// [Geneve interface](https://datatracker.ietf.org/doc/html/rfc8926)
+    /// Deserialize and serialize from/to 'geneve'.
+    Geneve,
+    /// [Bare UDP interface](https://docs.kernel.org/networking/bareudp.html)
+    /// Deserialize and serialize from/to 'bareudp'.
+    #[serde(rename = "bareudp")]
+    BareUdp,
+    /// [IPVLAN interface](https://docs.kernel.org/networking/ipvlan.html)
+    /// Deserialize and serialize from/to 'ipvlan'.
+    #[serde(rename = "ipvlan")]
+    IpVlan,
     /// Unknown interface.
     Unknown,
     /// Reserved for future use.
@@ -118,6 +151,15 @@ impl std::fmt::Display for InterfaceType {
                 InterfaceType::MacSec => "macsec",
                 InterfaceType::Ipsec => "ipsec",
                 InterfaceType::Xfrm => "xfrm",
+                InterfaceType::WireGuard => "wireguard",
+                InterfaceType::Gre => "gre",
+                InterfaceType::GreTap => "gretap",
+                InterfaceType::Ipip => "ipip",
+                InterfaceType::Sit => "sit",
+                InterfaceType::Ip6Tnl => "ip6tnl",
+                InterfaceType::Geneve => "geneve",
+                InterfaceType::BareUdp => "bareudp",
+                InterfaceType::IpVlan => "ipvlan",
                 InterfaceType::Other(ref s) => s,
             }
         )
@@ -278,6 +320,16 @@ pub enum Interface {
     Ipsec(IpsecInterface),
     /// Linux xfrm interface
     Xfrm(XfrmInterface),
+    /// [WireGuard interface](https://www.wireguard.com)
+    WireGuard(WireGuardInterface),
+    /// IP tunnel interface: GRE, GRETAP, IPIP, SIT or ip6tnl.
+    IpTunnel(IpTunnelInterface),
+    /// [Geneve interface](https://datatracker.ietf.org/doc/html/rfc8926)
+    Geneve(GeneveInterface),
+    /// [Bare UDP interface](https://docs.kernel.org/networking/bareudp.html)
+    BareUdp(BareUdpInterface),
+    /// [IPVLAN interface](https://docs.kernel.org/networking/ipvlan.html)
+    IpVlan(IpVlanInterface),
 }
 
 impl<'de> Deserialize<'de> for Interface {
@@ -399,6 +451,37 @@ impl<'de> Deserialize<'de> for Interface {
                     .map_err(serde::de::Error::custom)?;
                 Ok(Interface::Xfrm(inner))
             }
+            Some(InterfaceType::WireGuard) => {
+                let inner = WireGuardInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::WireGuard(inner))
+            }
+            Some(
+                InterfaceType::Gre
+                | InterfaceType::GreTap
+                | InterfaceType::Ipip
+                | InterfaceType::Sit
+                | InterfaceType::Ip6Tnl,
+            ) => {
+                let inner = IpTunnelInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::IpTunnel(inner))
+            }
+            Some(InterfaceType::Geneve) => {
+                let inner = GeneveInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Geneve(inner))
+            }
+            Some(InterfaceType::BareUdp) => {
+                let inner = BareUdpInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::BareUdp(inner))
+            }
+            Some(InterfaceType::IpVlan) => {
+                let inner = IpVlanInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::IpVlan(inner))
+            }
             Some(iface_type) => {
                 log::warn!("Unsupported interface type {}", iface_type);
                 let inner = UnknownInterface::deserialize(v)
@@ -524,6 +607,31 @@ impl Interface {
                 new_iface.base = iface.base.clone_name_type_only();
                 Self::Xfrm(new_iface)
             }
+            Self::WireGuard(iface) => {
+                let mut new_iface = WireGuardInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::WireGuard(new_iface)
+            }
+            Self::IpTunnel(iface) => {
+                let mut new_iface = IpTunnelInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::IpTunnel(new_iface)
+            }
+            Self::Geneve(iface) => {
+                let mut new_iface = GeneveInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Geneve(new_iface)
+            }
+            Self::BareUdp(iface) => {
+                let mut new_iface = BareUdpInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::BareUdp(new_iface)
+            }
+            Self::IpVlan(iface) => {
+                let mut new_iface = IpVlanInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::IpVlan(new_iface)
+            }
             Self::Unknown(iface) => {
                 let mut new_iface = UnknownInterface::new();
                 new_iface.base = iface.base.clone_name_type_only();
@@ -622,6 +730,11 @@ impl Interface {
             Self::MacSec(iface) => &iface.base,
             Self::Ipsec(iface) => &iface.base,
             Self::Xfrm(iface) => &iface.base,
+            Self::WireGuard(iface) => &iface.base,
+            Self::IpTunnel(iface) => &iface.base,
+            Self::Geneve(iface) => &iface.base,
+            Self::BareUdp(iface) => &iface.base,
+            Self::IpVlan(iface) => &iface.base,
             Self::Unknown(iface) => &iface.base,
         }
     }
@@ -645,6 +758,11 @@ impl Interface {
             Self::MacSec(iface) => &mut iface.base,
             Self::Ipsec(iface) => &mut iface.base,
             Self::Xfrm(iface) => &mut iface.base,
+            Self::WireGuard(iface) => &mut iface.base,
+            Self::IpTunnel(iface) => &mut iface.base,
+            Self::Geneve(iface) => &mut iface.base,
+            Self::BareUdp(iface) => &mut iface.base,
+            Self::IpVlan(iface) => &mut iface.base,
             Self::Unknown(iface) => &mut iface.base,
         }
     }
@@ -696,6 +814,10 @@ impl Interface {
             Interface::Loopback(iface) => iface.sanitize(is_desired)?,
             Interface::MacSec(iface) => iface.sanitize(is_desired)?,
             Interface::Ipsec(iface) => iface.sanitize(is_desired),
+            Interface::WireGuard(iface) => iface.sanitize(is_desired)?,
+            Interface::IpTunnel(iface) => iface.sanitize(is_desired)?,
+            Interface::Geneve(iface) => iface.sanitize(is_desired)?,
+            Interface::IpVlan(iface) => iface.sanitize(is_desired)?,
             _ => (),
         }
         Ok(())
@@ -710,6 +832,8 @@ impl Interface {
             Interface::MacVtap(vtap) => vtap.parent(),
             Interface::InfiniBand(ib) => ib.parent(),
             Interface::MacSec(macsec) => macsec.parent(),
+            Interface::IpTunnel(tunnel) => tunnel.parent(),
+            Interface::IpVlan(ipvlan) => ipvlan.parent(),
             _ => None,
         }
     }
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, InterfaceType};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel [Bare UDP](https://docs.kernel.org/networking/bareudp.html)
+/// interface for tunnelling layer 3 protocols like MPLS and IP. The example
+/// yaml output of [crate::NetworkState] with a bareudp interface would be:
+/// ```yml
+/// interfaces:
+/// - name: bareudp1
+///   type: bareudp
+///   state: up
+///   bareudp:
+///     destination-port: 6635
+///     ethertype: mpls-uc
+/// ```
+pub struct BareUdpInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bareudp: Option<BareUdpConfig>,
+}
+
+impl Default for BareUdpInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::BareUdp,
+                ..Default::default()
+            },
+            bareudp: None,
+        }
+    }
+}
+
+impl BareUdpInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct BareUdpConfig {
+    #[serde(
+        rename = "destination-port",
+        deserialize_with = "crate::deserializer::u16_or_string"
+    )]
+    /// UDP destination port of tunnel.
+    /// Deserialize and serialize from/to `destination-port`.
+    pub dst_port: u16,
+    /// The layer 3 protocol being tunnelled.
+    pub ethertype: BareUdpEtherType,
+}
+
+impl BareUdpConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum BareUdpEtherType {
+    /// IPv4 packets.
+    /// Deserialize and serialize from/to `ipv4`.
+    Ipv4,
+    /// IPv6 packets.
+    /// Deserialize and serialize from/to `ipv6`.
+    Ipv6,
+    /// MPLS unicast packets.
+    /// Deserialize and serialize from/to `mpls-uc`.
+    #[default]
+    MplsUc,
+    /// MPLS multicast packets.
+    /// Deserialize and serialize from/to `mpls-mc`.
+    MplsMc,
+}
+
+const ETH_P_IP: u16 = 0x0800;
+const ETH_P_IPV6: u16 = 0x86DD;
+const ETH_P_MPLS_UC: u16 = 0x8847;
+const ETH_P_MPLS_MC: u16 = 0x8848;
+
+impl From<BareUdpEtherType> for u16 {
+    fn from(v: BareUdpEtherType) -> u16 {
+        match v {
+            BareUdpEtherType::Ipv4 => ETH_P_IP,
+            BareUdpEtherType::Ipv6 => ETH_P_IPV6,
+            BareUdpEtherType::MplsUc => ETH_P_MPLS_UC,
+            BareUdpEtherType::MplsMc => ETH_P_MPLS_MC,
+        }
+    }
+}
+
+impl TryFrom<u16> for BareUdpEtherType {
+    type Error = u16;
+    fn try_from(v: u16) -> Result<Self, u16> {
+        match v {
+            ETH_P_IP => Ok(Self::Ipv4),
+            ETH_P_IPV6 => Ok(Self::Ipv6),
+            ETH_P_MPLS_UC => Ok(Self::MplsUc),
+            ETH_P_MPLS_MC => Ok(Self::MplsMc),
+            _ => Err(v),
+        }
+    }
+}
+
+impl std::fmt::Display for BareUdpEtherType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Ipv4 => "ipv4",
+                Self::Ipv6 => "ipv6",
+                Self::MplsUc => "mpls-uc",
+                Self::MplsMc => "mpls-mc",
+            }
+        )
+    }
+}
@@ -127,6 +127,15 @@ pub struct BaseInterface {
     /// Dispatch script configurations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dispatch: Option<DispatchConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Move the interface into specified network namespace after all other
+    /// changes applied. The value could be name of namespace in `/run/netns`,
+    /// absolute path of namespace file like `/proc/<pid>/ns/net` or PID of
+    /// process holding the namespace. The interface not found in current
+    /// network namespace is treated as moved already.
+    /// Only valid for applying, not rolled back on failure.
+    /// Been always set to `None` by [crate::NetworkState::retrieve()].
+    pub netns: Option<String>,
     #[serde(skip)]
     pub controller_type: Option<InterfaceType>,
     // The interface lowest up_priority will be activated first.
@@ -280,6 +289,22 @@ impl BaseInterface {
             ));
         }
 
+        if is_desired
+            && (self.iface_type.is_userspace() || self.has_controller())
+            && self.netns.is_some()
+        {
+            return Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Interface {}/{} is user space interface or port of \
+                    controller, not allowed to move to other network \
+                    namespace",
+                    self.name.as_str(),
+                    self.iface_type,
+                ),
+            ));
+        }
+
         // Remove permanent_mac_address in desired state as it is query only
         if is_desired {
             self.permanent_mac_address = None;
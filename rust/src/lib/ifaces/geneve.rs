@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+const GENEVE_VNI_MAX: u32 = (1 << 24) - 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel [Geneve](https://datatracker.ietf.org/doc/html/rfc8926)
+/// interface. The example yaml output of [crate::NetworkState] with a Geneve
+/// interface would be:
+/// ```yml
+/// interfaces:
+/// - name: geneve1
+///   type: geneve
+///   state: up
+///   mtu: 1450
+///   geneve:
+///     id: 102
+///     remote: 192.0.2.1
+///     destination-port: 6081
+///     ttl: 64
+///     tos: 0
+///     df: unset
+///     udp-checksum: true
+/// ```
+pub struct GeneveInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geneve: Option<GeneveConfig>,
+}
+
+impl Default for GeneveInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Geneve,
+                ..Default::default()
+            },
+            geneve: None,
+        }
+    }
+}
+
+impl GeneveInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(&self, is_desired: bool) -> Result<(), NmstateError> {
+        if let (true, Some(conf)) = (is_desired, self.geneve.as_ref()) {
+            if conf.id > GENEVE_VNI_MAX {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Geneve VNI {} of interface {} exceeds the maximum \
+                        value {GENEVE_VNI_MAX}",
+                        conf.id, self.base.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct GeneveConfig {
+    /// Geneve Network Identifier(VNI).
+    #[serde(deserialize_with = "crate::deserializer::u32_or_string")]
+    pub id: u32,
+    /// Unicast IPv4 or IPv6 address of remote tunnel endpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<IpAddr>,
+    #[serde(
+        rename = "destination-port",
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// UDP destination port of remote tunnel endpoint, kernel default is
+    /// 6081.
+    /// Deserialize and serialize from/to `destination-port`.
+    pub dst_port: Option<u16>,
+    /// Time to live of outgoing packets. 0 means using route default.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    pub ttl: Option<u8>,
+    /// Type of service of outgoing packets. 1 means inherit from inner
+    /// packet.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    pub tos: Option<u8>,
+    /// Don't Fragment bit of outer IPv4 header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub df: Option<GeneveDf>,
+    /// Whether to calculate UDP checksum for outgoing packets. For IPv6
+    /// remote, disabling it means sending packets with zero UDP checksum.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub udp_checksum: Option<bool>,
+}
+
+impl GeneveConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum GeneveDf {
+    /// Do not set Don't Fragment bit.
+    /// Deserialize and serialize from/to `unset`.
+    #[default]
+    Unset,
+    /// Always set Don't Fragment bit.
+    /// Deserialize and serialize from/to `set`.
+    Set,
+    /// Copy Don't Fragment bit from inner IPv4 header.
+    /// Deserialize and serialize from/to `inherit`.
+    Inherit,
+}
@@ -169,6 +169,11 @@ impl Interfaces {
             if let Interface::Ipsec(ipsec_iface) = iface {
                 ipsec_iface.hide_secrets();
             }
+            if let Interface::WireGuard(wg_iface) = iface {
+                if let Some(wg_conf) = wg_iface.wireguard.as_mut() {
+                    wg_conf.hide_secrets();
+                }
+            }
         }
     }
 
@@ -281,6 +286,54 @@ impl Interfaces {
         }
     }
 
+    // Interface with `netns` defined but not found in current network
+    // namespace is treated as moved already unless it is veth peer pending
+    // creation.
+    // The veth with peer moved to other network namespace has no peer
+    // information in current, hence remove the veth section from desired.
+    pub(crate) fn remove_ifaces_moved_to_netns(&mut self, current: &Self) {
+        let netns_ifaces: HashSet<String> = self
+            .kernel_ifaces
+            .values()
+            .filter(|i| !i.is_absent() && i.base_iface().netns.is_some())
+            .map(|i| i.name().to_string())
+            .collect();
+        if netns_ifaces.is_empty() {
+            return;
+        }
+        let mut new_veth_peers: HashSet<String> = HashSet::new();
+        for iface in self.kernel_ifaces.values_mut() {
+            if let Interface::Ethernet(eth_iface) = iface {
+                let peer = if let Some(v) = eth_iface.veth.as_ref() {
+                    v.peer.to_string()
+                } else {
+                    continue;
+                };
+                let cur_peer_hidden = matches!(
+                    current.kernel_ifaces.get(eth_iface.base.name.as_str()),
+                    Some(Interface::Ethernet(cur_iface))
+                        if cur_iface.veth.is_none()
+                );
+                if netns_ifaces.contains(&peer) && cur_peer_hidden {
+                    eth_iface.veth = None;
+                } else {
+                    new_veth_peers.insert(peer);
+                }
+            }
+        }
+        for iface_name in netns_ifaces.iter() {
+            if !current.kernel_ifaces.contains_key(iface_name)
+                && !new_veth_peers.contains(iface_name)
+            {
+                log::info!(
+                    "Interface {iface_name} not found, treating it as \
+                    moved to other network namespace already"
+                );
+                self.kernel_ifaces.remove(iface_name);
+            }
+        }
+    }
+
     pub(crate) fn resolve_unknown_ifaces(
         &mut self,
         cur_ifaces: &Self,
@@ -635,6 +688,7 @@ impl MergedInterfaces {
         } else {
             desired.resolve_sriov_reference(&current)?;
             desired.resolve_mac_identifider_in_current(&current)?;
+            desired.remove_ifaces_moved_to_netns(&current);
             desired.resolve_unknown_ifaces(&current)?;
             desired.resolve_mac_identifider_in_desired(&current)?;
         }
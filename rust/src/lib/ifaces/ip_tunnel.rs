@@ -0,0 +1,290 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel IP tunnel interface, the interface type could be:
+///  * [InterfaceType::Gre]: IPv4 over GRE tunnel.
+///  * [InterfaceType::GreTap]: Ethernet over GRE tunnel.
+///  * [InterfaceType::Ipip]: IPv4 over IPv4 tunnel.
+///  * [InterfaceType::Sit]: IPv6 over IPv4 tunnel.
+///  * [InterfaceType::Ip6Tnl]: IPv4 or IPv6 over IPv6 tunnel.
+///
+/// The example yaml output of [crate::NetworkState] with a GRE tunnel
+/// interface would be:
+/// ```yaml
+/// ---
+/// interfaces:
+///   - name: gre1
+///     type: gre
+///     state: up
+///     ip-tunnel:
+///       local: 192.0.2.1
+///       remote: 198.51.100.1
+///       ttl: 64
+///       ikey: 100
+///       okey: 100
+///       pmtudisc: true
+/// ```
+pub struct IpTunnelInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "ip-tunnel")]
+    /// Deserialize and serialize from/to `ip-tunnel`.
+    pub ip_tunnel: Option<IpTunnelConfig>,
+}
+
+impl Default for IpTunnelInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Gre,
+                ..Default::default()
+            },
+            ip_tunnel: None,
+        }
+    }
+}
+
+impl IpTunnelInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn parent(&self) -> Option<&str> {
+        self.ip_tunnel
+            .as_ref()
+            .and_then(|cfg| cfg.base_iface.as_deref())
+    }
+
+    pub(crate) fn sanitize(
+        &mut self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        let iface_type = self.base.iface_type.clone();
+        if let Some(conf) = self.ip_tunnel.as_mut() {
+            if is_desired {
+                conf.validate(&self.base.name, &iface_type)?;
+            }
+            conf.sanitize();
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct IpTunnelConfig {
+    /// The interface which tunnel packets are bound to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_iface: Option<String>,
+    /// Local endpoint of tunnel. IPv6 address for `ip6tnl` and IPv4 address
+    /// for other tunnel types.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<IpAddr>,
+    /// Remote endpoint of tunnel. IPv6 address for `ip6tnl` and IPv4 address
+    /// for other tunnel types.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote: Option<IpAddr>,
+    /// Time to live(or hop limit for `ip6tnl`) of tunneled packets. 0 means
+    /// inherit from inner packet.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    pub ttl: Option<u8>,
+    /// Type of service of tunneled packets. 1 means inherit from inner
+    /// packet. Not supported by `ip6tnl`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    pub tos: Option<u8>,
+    /// Shortcut of setting `ikey` and `okey` to the same value. Only for
+    /// `gre` and `gretap`. Will not be included in query result.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub key: Option<u32>,
+    /// GRE key of incoming packets. Only for `gre` and `gretap`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub ikey: Option<u32>,
+    /// GRE key of outgoing packets. Only for `gre` and `gretap`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub okey: Option<u32>,
+    /// Whether to enable Path MTU Discovery on tunnel. Not supported by
+    /// `ip6tnl`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub pmtudisc: Option<bool>,
+    /// Encapsulate tunneled packets into UDP. Only supported in kernel mode.
+    /// Setting `type: none` will remove existing encapsulation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encap: Option<IpTunnelEncapConfig>,
+}
+
+impl IpTunnelConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn validate(
+        &self,
+        iface_name: &str,
+        iface_type: &InterfaceType,
+    ) -> Result<(), NmstateError> {
+        let is_gre =
+            matches!(iface_type, InterfaceType::Gre | InterfaceType::GreTap);
+        let is_ipv6 = iface_type == &InterfaceType::Ip6Tnl;
+        if !is_gre
+            && (self.key.is_some()
+                || self.ikey.is_some()
+                || self.okey.is_some())
+        {
+            return Err(invalid_arg(format!(
+                "The key, ikey and okey are only supported by gre and \
+                gretap tunnel, but interface {iface_name} is {iface_type}"
+            )));
+        }
+        if let Some(key) = self.key {
+            if self.ikey.map(|k| k != key) == Some(true)
+                || self.okey.map(|k| k != key) == Some(true)
+            {
+                return Err(invalid_arg(format!(
+                    "The key {key} of interface {iface_name} conflicts with \
+                    its ikey or okey"
+                )));
+            }
+        }
+        for (prop, ip) in [("local", self.local), ("remote", self.remote)] {
+            if let Some(ip) = ip {
+                if ip.is_ipv6() != is_ipv6 {
+                    return Err(invalid_arg(format!(
+                        "The {prop} address {ip} of {iface_type} interface \
+                        {iface_name} should be {}",
+                        if is_ipv6 { "IPv6" } else { "IPv4" }
+                    )));
+                }
+            }
+        }
+        if is_ipv6 && (self.tos.is_some() || self.pmtudisc.is_some()) {
+            return Err(invalid_arg(format!(
+                "The tos and pmtudisc are not supported by ip6tnl interface \
+                {iface_name}"
+            )));
+        }
+        Ok(())
+    }
+
+    // Expand `key` into `ikey` and `okey`.
+    fn sanitize(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.ikey = Some(key);
+            self.okey = Some(key);
+        }
+    }
+
+    pub(crate) fn is_encap_none(&self) -> bool {
+        self.encap.as_ref().map(|e| e.encap_type)
+            == Some(IpTunnelEncapType::None)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct IpTunnelEncapConfig {
+    /// Deserialize and serialize from/to `type`.
+    #[serde(rename = "type")]
+    pub encap_type: IpTunnelEncapType,
+    /// UDP source port, 0 or undefined means automatically chosen by kernel.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    pub source_port: Option<u16>,
+    /// UDP destination port.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    pub destination_port: Option<u16>,
+    /// Whether to calculate the UDP checksum.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub checksum: Option<bool>,
+}
+
+impl IpTunnelEncapConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum IpTunnelEncapType {
+    /// No UDP encapsulation.
+    /// Deserialize and serialize from/to `none`.
+    #[default]
+    None,
+    /// [Foo over UDP](https://lwn.net/Articles/614348/)
+    /// Deserialize and serialize from/to `fou`.
+    Fou,
+    /// Generic UDP Encapsulation.
+    /// Deserialize and serialize from/to `gue`.
+    Gue,
+}
+
+impl From<IpTunnelEncapType> for u16 {
+    fn from(v: IpTunnelEncapType) -> u16 {
+        match v {
+            IpTunnelEncapType::None => 0,
+            IpTunnelEncapType::Fou => 1,
+            IpTunnelEncapType::Gue => 2,
+        }
+    }
+}
+
+impl From<u16> for IpTunnelEncapType {
+    fn from(v: u16) -> Self {
+        match v {
+            1 => Self::Fou,
+            2 => Self::Gue,
+            _ => Self::None,
+        }
+    }
+}
+
+fn invalid_arg(msg: String) -> NmstateError {
+    let e = NmstateError::new(ErrorKind::InvalidArgument, msg);
+    log::error!("{}", e);
+    e
+}
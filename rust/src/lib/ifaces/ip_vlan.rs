@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel IPVLAN interface. All IPVLAN interfaces share the MAC address
+/// of their base interface. The example yaml output of
+/// [crate::NetworkState] with a IPVLAN interface would be:
+/// ```yaml
+/// ---
+/// interfaces:
+///   - name: ipvlan0
+///     type: ipvlan
+///     state: up
+///     ipvlan:
+///       base-iface: eth1
+///       mode: l3
+///       private: true
+/// ```
+pub struct IpVlanInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipvlan: Option<IpVlanConfig>,
+}
+
+impl Default for IpVlanInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::IpVlan,
+                ..Default::default()
+            },
+            ipvlan: None,
+        }
+    }
+}
+
+impl IpVlanInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(conf) = &self.ipvlan {
+                if conf.private == Some(true) && conf.vepa == Some(true) {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "IPVLAN interface {} cannot enable both private \
+                            and vepa",
+                            self.base.name
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parent(&self) -> Option<&str> {
+        self.ipvlan.as_ref().map(|cfg| cfg.base_iface.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct IpVlanConfig {
+    pub base_iface: String,
+    #[serde(default)]
+    pub mode: IpVlanMode,
+    /// Do not allow traffic between IPVLAN interfaces sharing the same base
+    /// interface. Cannot be enabled together with `vepa`.
+    /// When both `private` and `vepa` are disabled, the IPVLAN is in bridge
+    /// mode.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub private: Option<bool>,
+    /// Send all traffic to external switch even destination is IPVLAN
+    /// interface sharing the same base interface.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub vepa: Option<bool>,
+}
+
+impl IpVlanConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default,
+)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum IpVlanMode {
+    /// Layer 2 mode, base interface will handle the layer 2 processing.
+    /// Deserialize and serialize from/to `l2`.
+    L2,
+    /// Layer 3 mode, IPVLAN interface will do the layer 3 processing.
+    /// Deserialize and serialize from/to `l3`.
+    #[default]
+    L3,
+    /// Like layer 3 mode but with netfilter applied on ingress traffic.
+    /// Deserialize and serialize from/to `l3s`.
+    L3S,
+}
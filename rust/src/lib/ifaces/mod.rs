@@ -1,11 +1,15 @@
+mod bareudp;
 mod base;
 mod bond;
 mod bridge_vlan;
 mod dummy;
 mod ethernet;
 mod ethtool;
+mod geneve;
 mod hsr;
 pub(crate) mod inter_ifaces;
+mod ip_tunnel;
+mod ip_vlan;
 mod ipsec;
 mod loopback;
 mod vrf;
@@ -21,8 +25,10 @@ mod macsec;
 mod ovs;
 mod sriov;
 mod vlan;
+mod wireguard;
 
 pub use self::xfrm::XfrmInterface;
+pub use bareudp::{BareUdpConfig, BareUdpEtherType, BareUdpInterface};
 pub use base::*;
 pub use bond::{
     BondAdSelect, BondAllPortsActive, BondArpAllTargets, BondArpValidate,
@@ -41,10 +47,15 @@ pub use ethtool::{
     EthtoolCoalesceConfig, EthtoolConfig, EthtoolFeatureConfig,
     EthtoolPauseConfig, EthtoolRingConfig,
 };
+pub use geneve::{GeneveConfig, GeneveDf, GeneveInterface};
 pub use hsr::{HsrConfig, HsrInterface, HsrProtocol};
 pub use infiniband::{InfiniBandConfig, InfiniBandInterface, InfiniBandMode};
 pub(crate) use inter_ifaces::MergedInterfaces;
 pub use inter_ifaces::*;
+pub use ip_tunnel::{
+    IpTunnelConfig, IpTunnelEncapConfig, IpTunnelEncapType, IpTunnelInterface,
+};
+pub use ip_vlan::{IpVlanConfig, IpVlanInterface, IpVlanMode};
 pub use ipsec::{
     IpsecInterface, LibreswanAddressFamily, LibreswanConfig,
     LibreswanConnectionType,
@@ -70,3 +81,6 @@ pub use vlan::{
 };
 pub use vrf::{VrfConfig, VrfInterface};
 pub use vxlan::{VxlanConfig, VxlanInterface};
+#[cfg(feature = "query_apply")]
+pub(crate) use wireguard::{wireguard_key_from_bytes, wireguard_key_to_bytes};
+pub use wireguard::{WireGuardConfig, WireGuardInterface, WireGuardPeerConfig};
@@ -0,0 +1,281 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ip::sanitize_ip_network, BaseInterface, ErrorKind, InterfaceType,
+    NetworkState, NmstateError,
+};
+
+const WIREGUARD_KEY_LEN: usize = 32;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// WireGuard interface. The example YAML output of a
+/// [crate::NetworkState] with an WireGuard interface would be:
+/// ```yaml
+/// ---
+/// interfaces:
+///   - name: wg0
+///     type: wireguard
+///     state: up
+///     wireguard:
+///       private-key: yAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=
+///       listen-port: 51820
+///       fwmark: 0
+///       peers:
+///         - public-key: xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=
+///           endpoint: 192.0.2.1:51820
+///           allowed-ips:
+///             - 10.10.10.0/24
+///             - fd00:10::/64
+///           persistent-keepalive: 25
+/// ```
+pub struct WireGuardInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Deserialize and serialize to `wireguard`.
+    pub wireguard: Option<WireGuardConfig>,
+}
+
+impl Default for WireGuardInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::WireGuard,
+                ..Default::default()
+            },
+            wireguard: None,
+        }
+    }
+}
+
+impl WireGuardInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &mut self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(conf) = self.wireguard.as_mut() {
+            conf.sanitize(is_desired)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct WireGuardConfig {
+    /// The base64 encoded private key of this interface.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    /// The UDP port to listen on. When undefined or set to 0, a random port
+    /// will be chosen by kernel.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    pub listen_port: Option<u16>,
+    /// The firewall mark of outgoing packets. 0 means disabled.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub fwmark: Option<u32>,
+    /// The peers of this interface. When defined, peers not mentioned will
+    /// be removed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peers: Option<Vec<WireGuardPeerConfig>>,
+}
+
+impl WireGuardConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn hide_secrets(&mut self) {
+        if self.private_key.is_some() {
+            self.private_key =
+                Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+        }
+        for peer in self.peers.as_mut().into_iter().flatten() {
+            if peer.preshared_key.is_some() {
+                peer.preshared_key =
+                    Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+            }
+        }
+    }
+
+    // * Validate the keys are base64 encoded 32 bytes.
+    // * Normalize allowed IPs to network address and sort them as kernel
+    //   does not preserve their order.
+    // * Normalize endpoint in the format of IP address to kernel style.
+    fn sanitize(&mut self, is_desired: bool) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(key) = self.private_key.as_deref() {
+                validate_wireguard_key("private-key", key)?;
+            }
+        }
+        for peer in self.peers.as_mut().into_iter().flatten() {
+            if is_desired {
+                validate_wireguard_key("public-key", &peer.public_key)?;
+                if let Some(key) = peer.preshared_key.as_deref() {
+                    validate_wireguard_key("preshared-key", key)?;
+                }
+            }
+            if let Some(endpoint) = peer.endpoint.as_mut() {
+                if let Ok(addr) = SocketAddr::from_str(endpoint) {
+                    *endpoint = addr.to_string();
+                }
+            }
+            if let Some(allowed_ips) = peer.allowed_ips.as_mut() {
+                let mut new_allowed_ips = Vec::new();
+                for allowed_ip in allowed_ips.iter() {
+                    new_allowed_ips.push(sanitize_ip_network(allowed_ip)?);
+                }
+                new_allowed_ips.sort_unstable();
+                new_allowed_ips.dedup();
+                *allowed_ips = new_allowed_ips;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for WireGuardConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WireGuardConfig")
+            .field(
+                "private_key",
+                &Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string()),
+            )
+            .field("listen_port", &self.listen_port)
+            .field("fwmark", &self.fwmark)
+            .field("peers", &self.peers)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct WireGuardPeerConfig {
+    /// The base64 encoded public key of peer.
+    pub public_key: String,
+    /// The base64 encoded pre-shared key for additional layer of symmetric
+    /// encryption.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preshared_key: Option<String>,
+    /// The endpoint of peer in the format of `<ip>:<port>` or
+    /// `[<ipv6>]:<port>`. The NetworkManager backend also support
+    /// hostname.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// The IP networks allowed to be received from and routed to this peer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_ips: Option<Vec<String>>,
+    /// The interval in seconds of sending keepalive packet to peer.
+    /// 0 means disabled.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    pub persistent_keepalive: Option<u16>,
+}
+
+impl WireGuardPeerConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl std::fmt::Debug for WireGuardPeerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WireGuardPeerConfig")
+            .field("public_key", &self.public_key)
+            .field(
+                "preshared_key",
+                &Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string()),
+            )
+            .field("endpoint", &self.endpoint)
+            .field("allowed_ips", &self.allowed_ips)
+            .field("persistent_keepalive", &self.persistent_keepalive)
+            .finish()
+    }
+}
+
+fn validate_wireguard_key(prop: &str, key: &str) -> Result<(), NmstateError> {
+    if key == NetworkState::PASSWORD_HID_BY_NMSTATE
+        || wireguard_key_to_bytes(key).is_some()
+    {
+        Ok(())
+    } else {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "The WireGuard {prop} must be base64 encoded string of \
+                {WIREGUARD_KEY_LEN} bytes"
+            ),
+        );
+        log::error!("{}", e);
+        Err(e)
+    }
+}
+
+const BASE64_CHARS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Decode the base64 encoded WireGuard key
+pub(crate) fn wireguard_key_to_bytes(
+    key: &str,
+) -> Option<[u8; WIREGUARD_KEY_LEN]> {
+    let key = key.trim_end_matches('=');
+    let mut bytes: Vec<u8> = Vec::with_capacity(WIREGUARD_KEY_LEN + 2);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in key.bytes() {
+        let value = BASE64_CHARS.iter().position(|b| *b == c)? as u32;
+        buffer = (buffer << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    bytes.try_into().ok()
+}
+
+/// Encode the WireGuard key into base64 string
+#[cfg(feature = "query_apply")]
+pub(crate) fn wireguard_key_from_bytes(key: &[u8]) -> String {
+    let mut ret = String::new();
+    for chunk in key.chunks(3) {
+        let mut buffer = [0u8; 3];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        let value = u32::from_be_bytes([0, buffer[0], buffer[1], buffer[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                ret.push(
+                    BASE64_CHARS[((value >> (18 - 6 * i)) & 0x3f) as usize]
+                        as char,
+                );
+            } else {
+                ret.push('=');
+            }
+        }
+    }
+    ret
+}
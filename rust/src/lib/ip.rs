@@ -7,7 +7,7 @@ use std::str::FromStr;
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::{
-    BaseInterface, DnsClientState, ErrorKind, MergedInterface,
+    BaseInterface, DhcpLease, DnsClientState, ErrorKind, MergedInterface,
     MptcpAddressFlag, NmstateError, RouteRuleEntry,
 };
 
@@ -102,6 +102,8 @@ struct InterfaceIp {
         rename = "dhcp-custom-hostname"
     )]
     pub dhcp_custom_hostname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "dhcp-lease")]
+    pub dhcp_lease: Option<DhcpLease>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
@@ -190,6 +192,10 @@ pub struct InterfaceIpv4 {
     /// If not defined, current non-dynamic hostname will be used.
     /// Deserialize from `dhcp-custom-hostname`
     pub dhcp_custom_hostname: Option<String>,
+    /// DHCP lease obtained by nmstate built-in DHCP client in kernel mode.
+    /// Query only, ignored when applying.
+    /// Serialize and deserialize to/from `dhcp-lease`.
+    pub dhcp_lease: Option<DhcpLease>,
     pub(crate) dns: Option<DnsClientState>,
     pub(crate) rules: Option<Vec<RouteRuleEntry>>,
 }
@@ -272,6 +278,7 @@ impl InterfaceIpv4 {
     // * Remove auto IP address.
     // * Set DHCP options to None if DHCP is false
     // * Remove mptcp_flags is they are for query only
+    // * Remove dhcp_lease as it is for query only
     pub(crate) fn sanitize(
         &mut self,
         is_desired: bool,
@@ -378,6 +385,7 @@ impl InterfaceIpv4 {
                 addr.mptcp_flags = None;
             }
         }
+        self.dhcp_lease = None;
         Ok(())
     }
 }
@@ -429,6 +437,7 @@ impl From<InterfaceIp> for InterfaceIpv4 {
             auto_route_metric: ip.auto_route_metric,
             dhcp_send_hostname: ip.dhcp_send_hostname,
             dhcp_custom_hostname: ip.dhcp_custom_hostname,
+            dhcp_lease: ip.dhcp_lease,
             ..Default::default()
         }
     }
@@ -454,6 +463,7 @@ impl From<InterfaceIpv4> for InterfaceIp {
             auto_route_metric: ip.auto_route_metric,
             dhcp_send_hostname: ip.dhcp_send_hostname,
             dhcp_custom_hostname: ip.dhcp_custom_hostname,
+            dhcp_lease: ip.dhcp_lease,
             ..Default::default()
         }
     }
@@ -548,6 +558,10 @@ pub struct InterfaceIpv6 {
     /// If not defined, current non-dynamic hostname will be used.
     /// Deserialize from `dhcp-custom-hostname`
     pub dhcp_custom_hostname: Option<String>,
+    /// DHCPv6 lease obtained by nmstate built-in DHCP client in kernel mode.
+    /// Query only, ignored when applying.
+    /// Serialize and deserialize to/from `dhcp-lease`.
+    pub dhcp_lease: Option<DhcpLease>,
 
     pub(crate) dns: Option<DnsClientState>,
     pub(crate) rules: Option<Vec<RouteRuleEntry>>,
@@ -573,6 +587,7 @@ impl InterfaceIpv6 {
     //   those options is None
     // * Disable DHCP and remove address if enabled: false
     // * Set DHCP options to None if DHCP is false
+    // * Remove `dhcp_lease` as it is for query only
     // * Remove `mptcp_flags` as they are for query only
     pub(crate) fn sanitize(
         &mut self,
@@ -677,6 +692,7 @@ impl InterfaceIpv6 {
                 addr.mptcp_flags = None;
             }
         }
+        self.dhcp_lease = None;
         if let Some(token) = self.token.as_mut() {
             if is_desired
                 && self.autoconf == Some(false)
@@ -818,6 +834,7 @@ impl From<InterfaceIp> for InterfaceIpv6 {
             token: ip.token,
             dhcp_send_hostname: ip.dhcp_send_hostname,
             dhcp_custom_hostname: ip.dhcp_custom_hostname,
+            dhcp_lease: ip.dhcp_lease,
             ..Default::default()
         }
     }
@@ -846,6 +863,7 @@ impl From<InterfaceIpv6> for InterfaceIp {
             token: ip.token,
             dhcp_send_hostname: ip.dhcp_send_hostname,
             dhcp_custom_hostname: ip.dhcp_custom_hostname,
+            dhcp_lease: ip.dhcp_lease,
             ..Default::default()
         }
     }
@@ -0,0 +1,667 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use super::{
+    ip_cmd::{parse_ip_route_args, parse_ip_rule_args},
+    netmask_to_prefix, read_dir, read_file, ImportWarning, LegacyState,
+};
+use crate::{
+    InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6, InterfaceState,
+    InterfaceType, NetworkState, NmstateError, RouteEntry,
+};
+
+// Options controlling ifupdown itself which have no meaning to nmstate
+const IGNORED_OPTIONS: [&str; 3] =
+    ["bridge_maxwait", "bridge_waitport", "bond_give_a_chance"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Family {
+    Inet,
+    Inet6,
+}
+
+#[derive(Debug, Clone)]
+struct Stanza {
+    file: String,
+    iface_name: String,
+    family: Family,
+    method: String,
+    // (line, option name, option value)
+    opts: Vec<(usize, String, String)>,
+}
+
+#[derive(Debug, Default)]
+struct DebianContext {
+    auto_ifaces: Vec<String>,
+    parsed_files: Vec<String>,
+}
+
+// General work flow:
+//  * Parse the `interfaces` file and the files included by `source` and
+//    `source-directory` into stanzas.
+//  * Convert each `iface` stanza into `LegacyIface` when the stanza ends.
+//  * Interface not marked as `auto` or `allow-*` is set to down unless it
+//    is port of other interface.
+pub(crate) fn import_debian_interfaces(
+    file_path: &str,
+) -> Result<(NetworkState, Vec<ImportWarning>), NmstateError> {
+    let mut state = LegacyState::default();
+    let mut ctx = DebianContext::default();
+    parse_file(&mut state, &mut ctx, file_path)?;
+
+    let mut port_names: Vec<String> = Vec::new();
+    for iface in state.ifaces.iter() {
+        port_names.extend(iface.ports.iter().cloned());
+        if iface.controller.is_some() {
+            port_names.push(iface.name.clone());
+        }
+    }
+    for iface in state.ifaces.iter_mut().filter(|i| i.file.is_some()) {
+        if !ctx.auto_ifaces.contains(&iface.name)
+            && !port_names.contains(&iface.name)
+        {
+            iface.state = Some(InterfaceState::Down);
+        }
+    }
+    Ok(state.into_net_state())
+}
+
+fn parse_file(
+    state: &mut LegacyState,
+    ctx: &mut DebianContext,
+    file_path: &str,
+) -> Result<(), NmstateError> {
+    if ctx.parsed_files.iter().any(|f| f == file_path) {
+        return Ok(());
+    }
+    ctx.parsed_files.push(file_path.to_string());
+    let content = read_file(file_path)?;
+    let dir = Path::new(file_path)
+        .parent()
+        .map(|p| p.display().to_string())
+        .unwrap_or_default();
+
+    let mut stanza: Option<Stanza> = None;
+    // Whether we are in stanza not supported, its options are ignored.
+    let mut in_ignored_stanza = false;
+    for (line_num, line) in logical_lines(&content) {
+        let mut words = line.split_whitespace();
+        let keyword = match words.next() {
+            Some(k) => k,
+            None => continue,
+        };
+        let args: Vec<&str> = words.collect();
+        let is_stanza = matches!(
+            keyword,
+            "iface"
+                | "mapping"
+                | "auto"
+                | "source"
+                | "source-directory"
+                | "source-dir"
+        ) || keyword.starts_with("allow-");
+        if !is_stanza {
+            if let Some(stanza) = stanza.as_mut() {
+                stanza.opts.push((
+                    line_num,
+                    keyword.to_string(),
+                    args.join(" "),
+                ));
+            } else if !in_ignored_stanza {
+                state.warn(
+                    file_path,
+                    Some(line_num),
+                    None,
+                    &line,
+                    "option defined outside of iface stanza",
+                );
+            }
+            continue;
+        }
+        if let Some(stanza) = stanza.take() {
+            apply_stanza(state, stanza);
+        }
+        in_ignored_stanza = false;
+
+        match keyword {
+            "iface" => {
+                if let Some(s) =
+                    parse_iface_line(state, file_path, line_num, &line, &args)
+                {
+                    stanza = Some(s);
+                } else {
+                    in_ignored_stanza = true;
+                }
+            }
+            "mapping" => {
+                state.warn(
+                    file_path,
+                    Some(line_num),
+                    None,
+                    &line,
+                    "mapping stanza is not supported",
+                );
+                in_ignored_stanza = true;
+            }
+            "source" => {
+                for path in args {
+                    for path in expand_source_path(&dir, path)? {
+                        parse_file(state, ctx, &path)?;
+                    }
+                }
+            }
+            "source-directory" | "source-dir" => {
+                for src_dir in args {
+                    let src_dir = join_path(&dir, src_dir);
+                    for file_name in read_dir(&src_dir)? {
+                        // Same rule as `run-parts`
+                        if file_name.chars().all(|c| {
+                            c.is_ascii_alphanumeric() || c == '_' || c == '-'
+                        }) {
+                            parse_file(
+                                state,
+                                ctx,
+                                &join_path(&src_dir, &file_name),
+                            )?;
+                        }
+                    }
+                }
+            }
+            // `auto` and `allow-*`
+            _ => {
+                ctx.auto_ifaces.extend(args.iter().map(|a| a.to_string()));
+            }
+        }
+    }
+    if let Some(stanza) = stanza.take() {
+        apply_stanza(state, stanza);
+    }
+    Ok(())
+}
+
+// Join lines ended with backslash and remove comments.
+// Return (line number, line) where line number is the first line of the
+// logical line starting from 1.
+fn logical_lines(content: &str) -> Vec<(usize, String)> {
+    let mut ret = Vec::new();
+    let mut cur: Option<(usize, String)> = None;
+    for (index, line) in content.lines().enumerate() {
+        let is_continued = line.ends_with('\\');
+        let line = line.trim_end_matches('\\');
+        match cur.as_mut() {
+            Some((_, cur_line)) => {
+                cur_line.push(' ');
+                cur_line.push_str(line.trim());
+            }
+            None => {
+                let line = line.trim();
+                if line.starts_with('#') {
+                    continue;
+                }
+                cur = Some((index + 1, line.to_string()));
+            }
+        }
+        if !is_continued {
+            if let Some((line_num, line)) = cur.take() {
+                if !line.is_empty() {
+                    ret.push((line_num, line));
+                }
+            }
+        }
+    }
+    if let Some((line_num, line)) = cur.take() {
+        ret.push((line_num, line));
+    }
+    ret
+}
+
+fn join_path(dir: &str, path: &str) -> String {
+    Path::new(dir).join(path).display().to_string()
+}
+
+// Only wildcard `*` in the file name part is supported.
+fn expand_source_path(
+    dir: &str,
+    path: &str,
+) -> Result<Vec<String>, NmstateError> {
+    let path = join_path(dir, path);
+    let (parent, pattern) = match path.rsplit_once('/') {
+        Some((p, f)) if f.contains('*') => (p.to_string(), f.to_string()),
+        _ => return Ok(vec![path]),
+    };
+    Ok(read_dir(&parent)?
+        .into_iter()
+        .filter(|f| wildcard_match(&pattern, f))
+        .map(|f| join_path(&parent, &f))
+        .collect())
+}
+
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, remain)) => {
+            if let Some(name) = name.strip_prefix(prefix) {
+                (0..=name.len())
+                    .filter(|i| name.is_char_boundary(*i))
+                    .any(|i| wildcard_match(remain, &name[i..]))
+            } else {
+                false
+            }
+        }
+    }
+}
+
+fn parse_iface_line(
+    state: &mut LegacyState,
+    file_path: &str,
+    line_num: usize,
+    line: &str,
+    args: &[&str],
+) -> Option<Stanza> {
+    let (iface_name, family, method) = match args {
+        [iface_name, family, method] => (*iface_name, *family, *method),
+        // ifupdown2 allows stanza without family and method
+        [iface_name] => (*iface_name, "inet", "manual"),
+        _ => {
+            state.warn(
+                file_path,
+                Some(line_num),
+                None,
+                line,
+                "invalid iface stanza",
+            );
+            return None;
+        }
+    };
+    let family = match family {
+        "inet" => Family::Inet,
+        "inet6" => Family::Inet6,
+        _ => {
+            state.warn(
+                file_path,
+                Some(line_num),
+                Some(iface_name),
+                line,
+                "address family is not supported",
+            );
+            return None;
+        }
+    };
+    let is_supported = match family {
+        Family::Inet => {
+            matches!(method, "static" | "dhcp" | "manual" | "loopback")
+        }
+        Family::Inet6 => {
+            matches!(method, "static" | "dhcp" | "auto" | "manual" | "loopback")
+        }
+    };
+    if !is_supported {
+        state.warn(
+            file_path,
+            Some(line_num),
+            Some(iface_name),
+            line,
+            "configuration method is not supported",
+        );
+        return None;
+    }
+    if method == "loopback" {
+        // Loopback is always configured by kernel
+        if iface_name != "lo" {
+            state.warn(
+                file_path,
+                Some(line_num),
+                Some(iface_name),
+                line,
+                "loopback method is only supported on interface lo",
+            );
+        }
+        return None;
+    }
+    Some(Stanza {
+        file: file_path.to_string(),
+        iface_name: iface_name.to_string(),
+        family,
+        method: method.to_string(),
+        opts: Vec::new(),
+    })
+}
+
+// Normalize option names of ifupdown and ifupdown2, for example
+// `bond-mode`, `bond_mode` and `bridge-stp`.
+fn normalize_opt_name(name: &str) -> String {
+    let name = name.replace('-', "_");
+    match name.as_str() {
+        "slaves" => "bond_slaves".to_string(),
+        "dns_nameserver" => "dns_nameservers".to_string(),
+        "dns_domain" => "dns_search".to_string(),
+        "vlan_raw_device" | "raw_device" => "vlan_raw_device".to_string(),
+        "post_up" => "up".to_string(),
+        "pre_down" | "post_down" => "down".to_string(),
+        _ => name,
+    }
+}
+
+fn apply_stanza(state: &mut LegacyState, stanza: Stanza) {
+    let mut warnings: Vec<(usize, String, String)> = Vec::new();
+    // (line, option name, normalized option name, value)
+    let opts: Vec<(usize, &str, String, &str)> = stanza
+        .opts
+        .iter()
+        .map(|(l, k, v)| (*l, k.as_str(), normalize_opt_name(k), v.as_str()))
+        .collect();
+    let has_opt = |name: &str| opts.iter().any(|(_, _, k, _)| k == name);
+    let is_bond_port = has_opt("bond_master");
+    let is_bond =
+        !is_bond_port && opts.iter().any(|(_, _, k, _)| k.starts_with("bond_"));
+    let is_bridge = has_opt("bridge_ports");
+    let is_vlan = has_opt("vlan_raw_device")
+        || has_opt("vlan_id")
+        || stanza.iface_name.contains('.');
+    let iface_name = stanza.iface_name.as_str();
+
+    let mut addrs: Vec<(usize, String)> = Vec::new();
+    let mut netmask: Option<String> = None;
+    let mut gateway: Option<String> = None;
+    let mut metric: Option<u32> = None;
+    let mut dns_servers: Vec<String> = Vec::new();
+    let mut dns_searches: Vec<String> = Vec::new();
+    let mut routes: Vec<RouteEntry> = Vec::new();
+    let mut rules = Vec::new();
+    // (line, port name, port option name, value)
+    let mut br_port_opts: Vec<(usize, String, String, String)> = Vec::new();
+    let mut ports: Vec<String> = Vec::new();
+
+    let iface = state.iface_mut(iface_name);
+    if iface.file.is_none() {
+        iface.file = Some(stanza.file.clone());
+    }
+    if is_bond {
+        iface.iface_type = Some(InterfaceType::Bond);
+    } else if is_bridge {
+        iface.iface_type = Some(InterfaceType::LinuxBridge);
+    } else if is_vlan {
+        iface.iface_type = Some(InterfaceType::Vlan);
+        if let Some((base, id)) = iface_name.rsplit_once('.') {
+            iface.vlan_base_iface = Some(base.to_string());
+            iface.vlan_id = id.parse::<u16>().ok();
+        } else if let Some(id) = iface_name.strip_prefix("vlan") {
+            iface.vlan_id = id.parse::<u16>().ok();
+        }
+    }
+
+    for (line, orig_key, key, value) in opts.iter() {
+        let line = *line;
+        let value = *value;
+        let values: Vec<&str> = value.split_whitespace().collect();
+        let mut unsupported = |reason: &str| {
+            warnings.push((
+                line,
+                format!("{orig_key} {value}"),
+                reason.to_string(),
+            ));
+        };
+        match key.as_str() {
+            _ if IGNORED_OPTIONS.contains(&key.as_str()) => (),
+            "address" => addrs.push((line, value.to_string())),
+            "netmask" => netmask = Some(value.to_string()),
+            "gateway" => gateway = Some(value.to_string()),
+            "metric" => match value.parse::<u32>() {
+                Ok(m) => metric = Some(m),
+                Err(e) => unsupported(&format!("invalid metric: {e}")),
+            },
+            "mtu" => match value.parse::<u64>() {
+                Ok(m) => iface.mtu = Some(m),
+                Err(e) => unsupported(&format!("invalid MTU: {e}")),
+            },
+            "hwaddress" | "bridge_hw" => {
+                iface.mac_address = values
+                    .iter()
+                    .find(|v| **v != "ether")
+                    .map(|v| v.to_uppercase());
+            }
+            "dns_nameservers" => {
+                dns_servers.extend(values.iter().map(|v| v.to_string()))
+            }
+            "dns_search" => {
+                dns_searches.extend(values.iter().map(|v| v.to_string()))
+            }
+            "bond_slaves" => {
+                if values != ["none"] {
+                    ports.extend(values.iter().map(|v| v.to_string()));
+                }
+            }
+            "bond_master" => iface.controller = Some(value.to_string()),
+            // Bond options might be repeated in port stanza
+            _ if key.starts_with("bond_") && is_bond_port => (),
+            _ if key.starts_with("bond_") => {
+                let opt_name = key.trim_start_matches("bond_");
+                if let Err(e) = iface.set_bond_opt(opt_name, value) {
+                    unsupported(&e);
+                }
+            }
+            "bridge_ports" => match values.as_slice() {
+                ["none"] => (),
+                ["all"] | ["regex", ..] => {
+                    unsupported("port matching is not supported");
+                }
+                _ => ports.extend(values.iter().map(|v| v.to_string())),
+            },
+            "bridge_pathcost" | "bridge_portprio" => {
+                if let [port, v] = values.as_slice() {
+                    let opt_name = if key == "bridge_pathcost" {
+                        "path_cost"
+                    } else {
+                        "priority"
+                    };
+                    br_port_opts.push((
+                        line,
+                        port.to_string(),
+                        opt_name.to_string(),
+                        v.to_string(),
+                    ));
+                } else {
+                    unsupported("expecting port name and value");
+                }
+            }
+            _ if key.starts_with("bridge_") => {
+                let opt_name = match key.trim_start_matches("bridge_") {
+                    "stp" => "stp",
+                    "fd" => "forward_delay",
+                    "hello" => "hello_time",
+                    "maxage" => "max_age",
+                    "ageing" => "ageing_time",
+                    "bridgeprio" => "priority",
+                    "mcsnoop" => "multicast_snooping",
+                    "mcrouter" => "multicast_router",
+                    "mcquerier" => "multicast_querier",
+                    o => o,
+                };
+                if let Err(e) = iface.set_bridge_opt(opt_name, value) {
+                    unsupported(&e);
+                }
+            }
+            "vlan_raw_device" => {
+                iface.vlan_base_iface = Some(value.to_string());
+            }
+            "vlan_id" => match value.parse::<u16>() {
+                Ok(i) => iface.vlan_id = Some(i),
+                Err(e) => unsupported(&format!("invalid VLAN ID: {e}")),
+            },
+            "up" | "pre_up" => match parse_ip_cmd(&values) {
+                Some((IpCmd::Route, true, is_ipv6, args)) => {
+                    match parse_ip_route_args(&args, iface_name, is_ipv6) {
+                        Ok(rt) => routes.push(rt),
+                        Err(e) => unsupported(&e),
+                    }
+                }
+                Some((IpCmd::Rule, true, is_ipv6, args)) => {
+                    match parse_ip_rule_args(&args, is_ipv6) {
+                        Ok(rule) => rules.push(rule),
+                        Err(e) => unsupported(&e),
+                    }
+                }
+                _ => unsupported(
+                    "only `ip route add` and `ip rule add` commands are \
+                    supported",
+                ),
+            },
+            // Deleting the routes and rules is done by nmstate
+            "down"
+                if matches!(parse_ip_cmd(&values), Some((_, false, _, _))) => {}
+            _ => unsupported("not supported"),
+        }
+    }
+    if !ports.is_empty() {
+        iface.ports.extend(ports.iter().cloned());
+    }
+
+    // IP configuration
+    let mut ip_addrs: Vec<InterfaceIpAddr> = Vec::new();
+    for (line, addr) in addrs {
+        let addr_str = match (addr.contains('/'), netmask.as_deref()) {
+            (true, _) => Some(addr.clone()),
+            (false, Some(mask)) => if mask.contains('.') {
+                netmask_to_prefix(mask).map(|p| p.to_string())
+            } else {
+                Some(mask.to_string())
+            }
+            .map(|p| format!("{addr}/{p}")),
+            (false, None) => Some(addr.clone()),
+        };
+        match addr_str.as_deref().and_then(parse_ip_addr) {
+            Some(a) => ip_addrs.push(a),
+            None => warnings.push((
+                line,
+                format!("address {addr}"),
+                "invalid IP address or netmask".to_string(),
+            )),
+        }
+    }
+    let method = stanza.method.as_str();
+    let ip_addrs = if ip_addrs.is_empty() {
+        None
+    } else {
+        Some(ip_addrs)
+    };
+    match stanza.family {
+        Family::Inet => {
+            let ip_conf = iface.ipv4.get_or_insert_with(InterfaceIpv4::new);
+            if method != "manual" {
+                ip_conf.enabled = true;
+                ip_conf.dhcp = Some(method == "dhcp");
+                if method == "dhcp" {
+                    ip_conf.auto_route_metric = metric;
+                }
+            }
+            if ip_addrs.is_some() {
+                ip_conf.addresses = ip_addrs;
+            }
+        }
+        Family::Inet6 => {
+            let ip_conf = iface.ipv6.get_or_insert_with(InterfaceIpv6::new);
+            if method != "manual" {
+                ip_conf.enabled = true;
+                ip_conf.dhcp = Some(method == "dhcp" || method == "auto");
+                ip_conf.autoconf = Some(method == "auto");
+                if method != "static" {
+                    ip_conf.auto_route_metric = metric;
+                }
+            }
+            if ip_addrs.is_some() {
+                ip_conf.addresses = ip_addrs;
+            }
+        }
+    }
+    if let Some(gw) = gateway {
+        let mut rt = RouteEntry::new();
+        rt.destination = Some(if stanza.family == Family::Inet6 {
+            "::/0".to_string()
+        } else {
+            "0.0.0.0/0".to_string()
+        });
+        rt.next_hop_iface = Some(iface_name.to_string());
+        rt.next_hop_addr = Some(gw);
+        rt.metric = metric.map(i64::from);
+        routes.push(rt);
+    }
+
+    for (line, port, opt_name, value) in br_port_opts {
+        if let Err(e) = state
+            .iface_mut(&port)
+            .set_bridge_port_opt(&opt_name, &value)
+        {
+            warnings.push((line, format!("{opt_name} {port} {value}"), e));
+        }
+    }
+    // Ports without stanza are ethernet interfaces
+    for port in ports {
+        state.iface_mut(&port);
+    }
+    for server in dns_servers {
+        state.add_dns_server(&server);
+    }
+    for search in dns_searches {
+        state.add_dns_search(&search);
+    }
+    state.routes.extend(routes);
+    state.rules.extend(rules);
+    for (line, config, reason) in warnings {
+        state.warn(
+            &stanza.file,
+            Some(line),
+            Some(iface_name),
+            &config,
+            &reason,
+        );
+    }
+}
+
+fn parse_ip_addr(addr: &str) -> Option<InterfaceIpAddr> {
+    let ip = addr.split('/').next().unwrap_or_default();
+    ip.parse::<std::net::IpAddr>().ok()?;
+    InterfaceIpAddr::try_from(addr).ok()
+}
+
+enum IpCmd {
+    Route,
+    Rule,
+}
+
+// Parse command like `ip -6 route add 2001:db8::/64 via 2001:db8:1::1` or
+// `/sbin/ip rule del from 192.0.2.1 table 100`, return the command type,
+// whether it is adding, whether it is IPv6 and the arguments after `add` or
+// `del`.
+fn parse_ip_cmd<'a>(
+    words: &[&'a str],
+) -> Option<(IpCmd, bool, bool, Vec<&'a str>)> {
+    let mut iter = words.iter();
+    if !iter.next()?.ends_with("ip") {
+        return None;
+    }
+    let mut is_ipv6 = false;
+    let mut cmd = None;
+    for word in iter.by_ref() {
+        match *word {
+            "-4" => is_ipv6 = false,
+            "-6" => is_ipv6 = true,
+            "route" | "r" | "ro" => {
+                cmd = Some(IpCmd::Route);
+                break;
+            }
+            "rule" | "ru" => {
+                cmd = Some(IpCmd::Rule);
+                break;
+            }
+            _ => return None,
+        }
+    }
+    let cmd = cmd?;
+    let is_add = match *iter.next()? {
+        "add" | "a" => true,
+        "del" | "delete" | "d" => false,
+        _ => return None,
+    };
+    Some((cmd, is_add, is_ipv6, iter.copied().collect()))
+}
@@ -0,0 +1,699 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::{
+    ip_cmd::{parse_ip_route_args, parse_ip_rule_args},
+    netmask_to_prefix, parse_bool, read_dir, read_file, ImportWarning,
+    LegacyState,
+};
+use crate::{
+    InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6, InterfaceState,
+    InterfaceType, Ipv6AddrGenMode, NetworkState, NmstateError, RouteEntry,
+};
+
+const IFCFG_PREFIX: &str = "ifcfg-";
+const ROUTE_PREFIX: &str = "route-";
+const ROUTE6_PREFIX: &str = "route6-";
+const RULE_PREFIX: &str = "rule-";
+const RULE6_PREFIX: &str = "rule6-";
+
+// Files ignored by initscripts
+const IGNORED_SUFFIXES: [&str; 6] =
+    ["~", ".bak", ".orig", ".rpmnew", ".rpmorig", ".rpmsave"];
+
+// Keys holding information not required by nmstate
+const IGNORED_KEYS: [&str; 8] = [
+    "NAME",
+    "UUID",
+    "NM_CONTROLLED",
+    "USERCTL",
+    "PROXY_METHOD",
+    "BROWSER_ONLY",
+    "IPV4_FAILURE_FATAL",
+    "IPV6_FAILURE_FATAL",
+];
+
+const IPV6_DEFAULT_PREFIX: u8 = 64;
+
+// General work flow:
+//  * Parse each `ifcfg-*` file into `LegacyIface`.
+//  * Parse `route-*`, `route6-*`, `rule-*` and `rule6-*` files.
+//  * Convert `LegacyState` to `NetworkState` with ports attached to their
+//    controllers.
+pub(crate) fn import_ifcfg_dir(
+    dir: &str,
+) -> Result<(NetworkState, Vec<ImportWarning>), NmstateError> {
+    let mut state = LegacyState::default();
+    let file_names: Vec<String> = read_dir(dir)?
+        .into_iter()
+        .filter(|n| !IGNORED_SUFFIXES.iter().any(|s| n.ends_with(s)))
+        .collect();
+
+    for file_name in file_names.as_slice() {
+        let file_path = Path::new(dir).join(file_name).display().to_string();
+        if let Some(iface_name) = file_name.strip_prefix(IFCFG_PREFIX) {
+            // Loopback is always managed by initscripts
+            if iface_name != "lo" {
+                let content = read_file(&file_path)?;
+                parse_ifcfg(&mut state, &file_path, iface_name, &content);
+            }
+        }
+    }
+    for file_name in file_names.as_slice() {
+        let file_path = Path::new(dir).join(file_name).display().to_string();
+        if let Some(iface_name) = file_name.strip_prefix(ROUTE_PREFIX) {
+            let content = read_file(&file_path)?;
+            parse_route_file(
+                &mut state, &file_path, iface_name, &content, false,
+            );
+        } else if let Some(iface_name) = file_name.strip_prefix(ROUTE6_PREFIX) {
+            let content = read_file(&file_path)?;
+            parse_route_file(
+                &mut state, &file_path, iface_name, &content, true,
+            );
+        } else if file_name.starts_with(RULE_PREFIX) {
+            let content = read_file(&file_path)?;
+            parse_rule_file(&mut state, &file_path, &content, false);
+        } else if file_name.starts_with(RULE6_PREFIX) {
+            let content = read_file(&file_path)?;
+            parse_rule_file(&mut state, &file_path, &content, true);
+        }
+    }
+    Ok(state.into_net_state())
+}
+
+// Shell variables defined in ifcfg file with tracking on which variable is
+// not used.
+#[derive(Debug, Default)]
+struct IfcfgVars {
+    vars: Vec<(usize, String, String)>,
+    used: HashSet<String>,
+}
+
+impl IfcfgVars {
+    fn get(&mut self, key: &str) -> Option<String> {
+        self.used.insert(key.to_string());
+        self.vars
+            .iter()
+            .rev()
+            .find(|(_, k, _)| k == key)
+            .map(|(_, _, v)| v.clone())
+            .filter(|v| !v.is_empty())
+    }
+
+    fn get_bool(&mut self, key: &str) -> Option<bool> {
+        self.get(key).and_then(|v| parse_bool(&v))
+    }
+
+    fn line_of(&self, key: &str) -> Option<usize> {
+        self.vars
+            .iter()
+            .find(|(_, k, _)| k == key)
+            .map(|(line, _, _)| *line)
+    }
+
+    // Keys in the format of `<prefix>[N]` sorted by N, key without number is
+    // placed first.
+    fn indexed_keys(&self, prefix: &str) -> Vec<String> {
+        let mut ret: Vec<(Option<u32>, String)> = Vec::new();
+        for (_, key, _) in self.vars.iter() {
+            if let Some(index) = key.strip_prefix(prefix) {
+                let index = if index.is_empty() {
+                    None
+                } else if let Ok(i) = index.parse::<u32>() {
+                    Some(i)
+                } else {
+                    continue;
+                };
+                if !ret.iter().any(|(_, k)| k == key) {
+                    ret.push((index, key.to_string()));
+                }
+            }
+        }
+        ret.sort_unstable();
+        ret.into_iter().map(|(_, k)| k).collect()
+    }
+
+    fn unused(&self) -> Vec<(usize, String, String)> {
+        self.vars
+            .iter()
+            .filter(|(_, k, _)| {
+                !self.used.contains(k) && !IGNORED_KEYS.contains(&k.as_str())
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+fn parse_shell_vars(
+    state: &mut LegacyState,
+    file_path: &str,
+    content: &str,
+) -> IfcfgVars {
+    let mut ret = IfcfgVars::default();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.split_once('=') {
+            Some((key, value))
+                if !key.is_empty()
+                    && key
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+            {
+                ret.vars.push((
+                    index + 1,
+                    key.to_string(),
+                    unquote_shell_value(value),
+                ));
+            }
+            _ => {
+                state.warn(
+                    file_path,
+                    Some(index + 1),
+                    None,
+                    line,
+                    "not a shell variable assignment",
+                );
+            }
+        }
+    }
+    ret
+}
+
+// Remove shell quoting, only the quoting used by ifcfg files is supported.
+fn unquote_shell_value(value: &str) -> String {
+    let value = value.trim();
+    let mut ret = String::new();
+    let mut chars = value.chars();
+    let mut quote: Option<char> = None;
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (Some('\''), c) => ret.push(c),
+            (_, '\\') => {
+                if let Some(c) = chars.next() {
+                    ret.push(c);
+                }
+            }
+            (None, c) if c.is_whitespace() => break,
+            (_, c) => ret.push(c),
+        }
+    }
+    ret
+}
+
+fn parse_ifcfg(
+    state: &mut LegacyState,
+    file_path: &str,
+    file_iface_name: &str,
+    content: &str,
+) {
+    let mut vars = parse_shell_vars(state, file_path, content);
+    let iface_name = vars
+        .get("DEVICE")
+        .unwrap_or_else(|| file_iface_name.to_string());
+
+    if let Some(dev_type) = vars.get("DEVICETYPE") {
+        state.warn(
+            file_path,
+            vars.line_of("DEVICETYPE"),
+            Some(&iface_name),
+            &format!("DEVICETYPE={dev_type}"),
+            "interface type is not supported",
+        );
+        return;
+    }
+    let iface_type = match vars.get("TYPE").as_deref() {
+        Some("Bond") => InterfaceType::Bond,
+        Some("Bridge") => InterfaceType::LinuxBridge,
+        Some("Vlan") => InterfaceType::Vlan,
+        Some("Ethernet") | None => {
+            if vars.get_bool("BONDING_MASTER") == Some(true)
+                || vars.get("BONDING_OPTS").is_some()
+            {
+                InterfaceType::Bond
+            } else if vars.get_bool("VLAN") == Some(true) {
+                InterfaceType::Vlan
+            } else {
+                InterfaceType::Ethernet
+            }
+        }
+        Some(t) => {
+            state.warn(
+                file_path,
+                vars.line_of("TYPE"),
+                Some(&iface_name),
+                &format!("TYPE={t}"),
+                "interface type is not supported",
+            );
+            return;
+        }
+    };
+
+    let mut warnings: Vec<(Option<usize>, String, String)> = Vec::new();
+    let mut routes: Vec<RouteEntry> = Vec::new();
+    let mut dns_servers: Vec<String> = Vec::new();
+    let mut dns_searches: Vec<String> = Vec::new();
+    {
+        let iface = state.iface_mut(&iface_name);
+        iface.file = Some(file_path.to_string());
+        iface.iface_type = Some(iface_type.clone());
+        if vars.get_bool("ONBOOT") == Some(false) {
+            iface.state = Some(InterfaceState::Down);
+        }
+        if let Some(mtu) = vars.get("MTU") {
+            match mtu.parse::<u64>() {
+                Ok(m) => iface.mtu = Some(m),
+                Err(e) => warnings.push((
+                    vars.line_of("MTU"),
+                    format!("MTU={mtu}"),
+                    format!("invalid MTU: {e}"),
+                )),
+            }
+        }
+        iface.mac_address = vars.get("MACADDR");
+        // HWADDR is used for matching the interface which is already done
+        // by DEVICE
+        vars.get("HWADDR");
+
+        // Port configurations
+        if let Some(ctrl) = vars.get("MASTER") {
+            if vars.get_bool("SLAVE") != Some(false) {
+                iface.controller = Some(ctrl);
+            }
+        } else if let Some(ctrl) = vars.get("BRIDGE") {
+            iface.controller = Some(ctrl);
+            if let Some(opts) = vars.get("BRIDGING_OPTS") {
+                for (key, value) in split_opts(&opts) {
+                    if let Err(e) = iface.set_bridge_port_opt(key, value) {
+                        warnings.push((
+                            vars.line_of("BRIDGING_OPTS"),
+                            format!("BRIDGING_OPTS {key}={value}"),
+                            e,
+                        ));
+                    }
+                }
+            }
+        }
+
+        match iface_type {
+            InterfaceType::Bond => {
+                vars.get("BONDING_MASTER");
+                let opts = vars.get("BONDING_OPTS").unwrap_or_default();
+                for (key, value) in split_opts(&opts) {
+                    if let Err(e) = iface.set_bond_opt(key, value) {
+                        warnings.push((
+                            vars.line_of("BONDING_OPTS"),
+                            format!("BONDING_OPTS {key}={value}"),
+                            e,
+                        ));
+                    }
+                }
+            }
+            InterfaceType::LinuxBridge => {
+                let mut opts: Vec<(String, String, String)> = Vec::new();
+                for (key, br_key) in
+                    [("STP", "stp"), ("DELAY", "forward_delay")]
+                {
+                    if let Some(value) = vars.get(key) {
+                        opts.push((key.to_string(), br_key.to_string(), value));
+                    }
+                }
+                if let Some(br_opts) = vars.get("BRIDGING_OPTS") {
+                    for (key, value) in split_opts(&br_opts) {
+                        opts.push((
+                            "BRIDGING_OPTS".to_string(),
+                            key.to_string(),
+                            value.to_string(),
+                        ));
+                    }
+                }
+                for (var, key, value) in opts {
+                    if let Err(e) = iface.set_bridge_opt(&key, &value) {
+                        warnings.push((
+                            vars.line_of(&var),
+                            format!("{var} {key}={value}"),
+                            e,
+                        ));
+                    }
+                }
+            }
+            InterfaceType::Vlan => {
+                vars.get("VLAN");
+                let (name_base, name_id) = match iface_name.rsplit_once('.') {
+                    Some((base, id)) => (Some(base.to_string()), Some(id)),
+                    None => (None, iface_name.strip_prefix("vlan")),
+                };
+                iface.vlan_base_iface = vars.get("PHYSDEV").or(name_base);
+                iface.vlan_id = vars
+                    .get("VLAN_ID")
+                    .as_deref()
+                    .or(name_id)
+                    .and_then(|i| i.parse::<u16>().ok());
+            }
+            _ => (),
+        }
+
+        iface.ipv4 = Some(get_ipv4_conf(
+            &mut vars,
+            &iface_name,
+            &mut routes,
+            &mut warnings,
+        ));
+        iface.ipv6 =
+            get_ipv6_conf(&mut vars, &iface_name, &mut routes, &mut warnings);
+    }
+
+    for key in vars.indexed_keys("DNS") {
+        if let Some(server) = vars.get(&key) {
+            dns_servers.push(server);
+        }
+    }
+    if let Some(domains) = vars.get("DOMAIN") {
+        dns_searches.extend(domains.split_whitespace().map(|d| d.to_string()));
+    }
+    for server in dns_servers {
+        state.add_dns_server(&server);
+    }
+    for search in dns_searches {
+        state.add_dns_search(&search);
+    }
+    state.routes.extend(routes);
+    for (line, config, reason) in warnings {
+        state.warn(file_path, line, Some(&iface_name), &config, &reason);
+    }
+    for (line, key, value) in vars.unused() {
+        state.warn(
+            file_path,
+            Some(line),
+            Some(&iface_name),
+            &format!("{key}={value}"),
+            "not supported",
+        );
+    }
+}
+
+fn get_ipv4_conf(
+    vars: &mut IfcfgVars,
+    iface_name: &str,
+    routes: &mut Vec<RouteEntry>,
+    warnings: &mut Vec<(Option<usize>, String, String)>,
+) -> InterfaceIpv4 {
+    let mut ip_conf = InterfaceIpv4::new();
+    let dhcp = matches!(
+        vars.get("BOOTPROTO").map(|b| b.to_lowercase()).as_deref(),
+        Some("dhcp") | Some("bootp")
+    );
+    let mut addrs: Vec<InterfaceIpAddr> = Vec::new();
+    for key in vars.indexed_keys("IPADDR") {
+        let index = key.trim_start_matches("IPADDR");
+        let ip = match vars.get(&key) {
+            Some(i) => i,
+            None => continue,
+        };
+        let prefix = if let Some(prefix) = vars.get(&format!("PREFIX{index}")) {
+            prefix.parse::<u8>().ok()
+        } else if let Some(netmask) = vars.get(&format!("NETMASK{index}")) {
+            netmask_to_prefix(&netmask)
+        } else {
+            classful_prefix(&ip)
+        };
+        match prefix.and_then(|p| {
+            InterfaceIpAddr::try_from(format!("{ip}/{p}").as_str()).ok()
+        }) {
+            Some(addr) => addrs.push(addr),
+            None => warnings.push((
+                vars.line_of(&key),
+                format!("{key}={ip}"),
+                "invalid IPv4 address or prefix".to_string(),
+            )),
+        }
+    }
+    let def_route = vars.get_bool("DEFROUTE") != Some(false);
+    if dhcp {
+        if vars.get_bool("PEERDNS") == Some(false) {
+            ip_conf.auto_dns = Some(false);
+        }
+        if vars.get_bool("PEERROUTES") == Some(false) {
+            ip_conf.auto_routes = Some(false);
+        }
+        if !def_route {
+            ip_conf.auto_gateway = Some(false);
+        }
+    }
+    if let Some(gw) = vars.get("GATEWAY") {
+        if def_route {
+            routes.push(gen_default_route(iface_name, &gw, false));
+        }
+    }
+    ip_conf.enabled = dhcp || !addrs.is_empty();
+    if ip_conf.enabled {
+        ip_conf.dhcp = Some(dhcp);
+    }
+    if !addrs.is_empty() {
+        ip_conf.addresses = Some(addrs);
+    }
+    ip_conf
+}
+
+fn get_ipv6_conf(
+    vars: &mut IfcfgVars,
+    iface_name: &str,
+    routes: &mut Vec<RouteEntry>,
+    warnings: &mut Vec<(Option<usize>, String, String)>,
+) -> Option<InterfaceIpv6> {
+    let mut ip_conf = InterfaceIpv6::new();
+    match vars.get_bool("IPV6INIT") {
+        Some(true) => ip_conf.enabled = true,
+        Some(false) => return Some(ip_conf),
+        None => {
+            vars.get("IPV6ADDR")?;
+            ip_conf.enabled = true;
+        }
+    }
+    let autoconf = vars.get_bool("IPV6_AUTOCONF").unwrap_or(true);
+    let dhcp = vars.get_bool("DHCPV6C").unwrap_or_default();
+    // nmstate does not support IPv6 autoconf without DHCPv6
+    ip_conf.dhcp = Some(autoconf || dhcp);
+    ip_conf.autoconf = Some(autoconf);
+
+    let mut addrs: Vec<InterfaceIpAddr> = Vec::new();
+    let mut addr_strs: Vec<(String, String)> = Vec::new();
+    if let Some(addr) = vars.get("IPV6ADDR") {
+        addr_strs.push(("IPV6ADDR".to_string(), addr));
+    }
+    if let Some(addrs) = vars.get("IPV6ADDR_SECONDARIES") {
+        for addr in addrs.split_whitespace() {
+            addr_strs
+                .push(("IPV6ADDR_SECONDARIES".to_string(), addr.to_string()));
+        }
+    }
+    for (key, addr_str) in addr_strs {
+        let addr_str = if addr_str.contains('/') {
+            addr_str
+        } else {
+            format!("{addr_str}/{IPV6_DEFAULT_PREFIX}")
+        };
+        match addr_str
+            .split_once('/')
+            .and_then(|(ip, _)| ip.parse::<std::net::Ipv6Addr>().ok())
+            .and_then(|_| InterfaceIpAddr::try_from(addr_str.as_str()).ok())
+        {
+            Some(addr) => addrs.push(addr),
+            None => warnings.push((
+                vars.line_of(&key),
+                format!("{key}={addr_str}"),
+                "invalid IPv6 address".to_string(),
+            )),
+        }
+    }
+    if !addrs.is_empty() {
+        ip_conf.addresses = Some(addrs);
+    }
+    let def_route = vars.get_bool("IPV6_DEFROUTE") != Some(false);
+    if ip_conf.is_auto() {
+        if vars.get_bool("IPV6_PEERDNS") == Some(false) {
+            ip_conf.auto_dns = Some(false);
+        }
+        if vars.get_bool("IPV6_PEERROUTES") == Some(false) {
+            ip_conf.auto_routes = Some(false);
+        }
+        if !def_route {
+            ip_conf.auto_gateway = Some(false);
+        }
+    }
+    if let Some(gw) = vars.get("IPV6_DEFAULTGW") {
+        // The gateway might be in the format of `<addr>%<iface>`
+        let gw = gw.split('%').next().unwrap_or_default();
+        if def_route {
+            routes.push(gen_default_route(iface_name, gw, true));
+        }
+    }
+    if let Some(mode) = vars.get("IPV6_ADDR_GEN_MODE") {
+        match serde_json::from_value::<Ipv6AddrGenMode>(
+            serde_json::Value::String(mode.clone()),
+        ) {
+            Ok(m) => ip_conf.addr_gen_mode = Some(m),
+            Err(e) => warnings.push((
+                vars.line_of("IPV6_ADDR_GEN_MODE"),
+                format!("IPV6_ADDR_GEN_MODE={mode}"),
+                format!("unsupported IPv6 address generation mode: {e}"),
+            )),
+        }
+    }
+    Some(ip_conf)
+}
+
+fn gen_default_route(iface_name: &str, gw: &str, is_ipv6: bool) -> RouteEntry {
+    let mut rt = RouteEntry::new();
+    rt.destination = Some(if is_ipv6 {
+        "::/0".to_string()
+    } else {
+        "0.0.0.0/0".to_string()
+    });
+    rt.next_hop_iface = Some(iface_name.to_string());
+    rt.next_hop_addr = Some(gw.to_string());
+    rt
+}
+
+// Split options like `mode=active-backup miimon=100`
+fn split_opts(opts: &str) -> Vec<(&str, &str)> {
+    opts.split_whitespace()
+        .map(|opt| opt.split_once('=').unwrap_or((opt, "")))
+        .collect()
+}
+
+// Prefix length derived from IPv4 address class used by initscripts when
+// neither PREFIX nor NETMASK is defined.
+fn classful_prefix(ip: &str) -> Option<u8> {
+    let first_octet = ip.parse::<std::net::Ipv4Addr>().ok()?.octets()[0];
+    Some(if first_octet < 128 {
+        8
+    } else if first_octet < 192 {
+        16
+    } else {
+        24
+    })
+}
+
+fn parse_route_file(
+    state: &mut LegacyState,
+    file_path: &str,
+    iface_name: &str,
+    content: &str,
+    is_ipv6: bool,
+) {
+    let is_key_format = !is_ipv6
+        && content
+            .lines()
+            .any(|l| l.trim_start().starts_with("ADDRESS"));
+    if is_key_format {
+        parse_route_file_key_format(state, file_path, iface_name, content);
+        return;
+    }
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let args: Vec<&str> = line.split_whitespace().collect();
+        match parse_ip_route_args(&args, iface_name, is_ipv6) {
+            Ok(rt) => state.routes.push(rt),
+            Err(e) => state.warn(
+                file_path,
+                Some(index + 1),
+                Some(iface_name),
+                line,
+                &e,
+            ),
+        }
+    }
+}
+
+// The `ADDRESS0=`, `NETMASK0=`, `GATEWAY0=`, `METRIC0=` format
+fn parse_route_file_key_format(
+    state: &mut LegacyState,
+    file_path: &str,
+    iface_name: &str,
+    content: &str,
+) {
+    let mut vars = parse_shell_vars(state, file_path, content);
+    let mut routes = Vec::new();
+    let mut warnings = Vec::new();
+    for key in vars.indexed_keys("ADDRESS") {
+        let index = key.trim_start_matches("ADDRESS");
+        let dest = match vars.get(&key) {
+            Some(d) => d,
+            None => continue,
+        };
+        let prefix = match vars.get(&format!("NETMASK{index}")) {
+            Some(netmask) => netmask_to_prefix(&netmask),
+            None => Some(32),
+        };
+        let mut args: Vec<String> = match prefix {
+            Some(p) => vec![format!("{dest}/{p}")],
+            None => {
+                warnings.push((
+                    vars.line_of(&format!("NETMASK{index}")),
+                    format!("NETMASK{index}"),
+                    "invalid netmask".to_string(),
+                ));
+                continue;
+            }
+        };
+        if let Some(gw) = vars.get(&format!("GATEWAY{index}")) {
+            args.push("via".to_string());
+            args.push(gw);
+        }
+        if let Some(metric) = vars.get(&format!("METRIC{index}")) {
+            args.push("metric".to_string());
+            args.push(metric);
+        }
+        if let Some(opts) = vars.get(&format!("OPTIONS{index}")) {
+            args.extend(opts.split_whitespace().map(|o| o.to_string()));
+        }
+        let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+        match parse_ip_route_args(&args, iface_name, false) {
+            Ok(rt) => routes.push(rt),
+            Err(e) => warnings.push((vars.line_of(&key), args.join(" "), e)),
+        }
+    }
+    state.routes.extend(routes);
+    for (line, config, reason) in warnings {
+        state.warn(file_path, line, Some(iface_name), &config, &reason);
+    }
+    for (line, key, value) in vars.unused() {
+        state.warn(
+            file_path,
+            Some(line),
+            Some(iface_name),
+            &format!("{key}={value}"),
+            "not supported",
+        );
+    }
+}
+
+fn parse_rule_file(
+    state: &mut LegacyState,
+    file_path: &str,
+    content: &str,
+    is_ipv6: bool,
+) {
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let args: Vec<&str> = line.split_whitespace().collect();
+        match parse_ip_rule_args(&args, is_ipv6) {
+            Ok(rule) => state.rules.push(rule),
+            Err(e) => state.warn(file_path, Some(index + 1), None, line, &e),
+        }
+    }
+}
@@ -0,0 +1,288 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Parsers for the arguments of `ip route add` and `ip rule add` commands
+// which are used by both ifcfg-rh `route-*`/`rule-*` files and the `up`
+// commands of Debian ifupdown.
+
+use std::net::IpAddr;
+
+use crate::{
+    AddressFamily, RouteEntry, RouteRuleAction, RouteRuleEntry, RouteRuleRange,
+    RouteScope, RouteType,
+};
+
+const MAIN_ROUTE_TABLE_ID: u32 = 254;
+
+pub(crate) fn parse_ip_route_args(
+    args: &[&str],
+    iface_name: &str,
+    is_ipv6: bool,
+) -> Result<RouteEntry, String> {
+    let mut rt = RouteEntry::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match *arg {
+            "unicast" => (),
+            "blackhole" => rt.route_type = Some(RouteType::Blackhole),
+            "unreachable" => rt.route_type = Some(RouteType::Unreachable),
+            "prohibit" => rt.route_type = Some(RouteType::Prohibit),
+            "throw" => rt.route_type = Some(RouteType::Throw),
+            "local" => rt.route_type = Some(RouteType::Local),
+            "broadcast" => rt.route_type = Some(RouteType::Broadcast),
+            "anycast" => rt.route_type = Some(RouteType::Anycast),
+            "multicast" => rt.route_type = Some(RouteType::Multicast),
+            "to" => {
+                rt.destination =
+                    Some(parse_prefix(next_arg(&mut iter, arg)?, is_ipv6)?);
+            }
+            "via" => {
+                let gw = next_arg(&mut iter, arg)?;
+                parse_ip(gw)?;
+                rt.next_hop_addr = Some(gw.to_string());
+            }
+            "dev" => {
+                rt.next_hop_iface = Some(next_arg(&mut iter, arg)?.to_string());
+            }
+            "metric" | "preference" | "priority" => {
+                rt.metric = Some(parse_num(next_arg(&mut iter, arg)?)?);
+            }
+            "table" => {
+                (rt.table_id, rt.table_name) =
+                    parse_table(next_arg(&mut iter, arg)?);
+            }
+            "weight" => {
+                rt.weight = Some(parse_num(next_arg(&mut iter, arg)?)?);
+            }
+            "cwnd" => {
+                rt.cwnd = Some(parse_num(next_arg(&mut iter, arg)?)?);
+            }
+            "src" => {
+                let src = next_arg(&mut iter, arg)?;
+                parse_ip(src)?;
+                rt.source = Some(src.to_string());
+            }
+            "mtu" => {
+                let mut value = next_arg(&mut iter, arg)?;
+                if value == "lock" {
+                    rt.lock_mtu = Some(true);
+                    value = next_arg(&mut iter, arg)?;
+                }
+                rt.mtu = Some(parse_num(value)?);
+            }
+            "advmss" => {
+                rt.advmss = Some(parse_num(next_arg(&mut iter, arg)?)?);
+            }
+            "initcwnd" => {
+                rt.initcwnd = Some(parse_num(next_arg(&mut iter, arg)?)?);
+            }
+            "initrwnd" => {
+                rt.initrwnd = Some(parse_num(next_arg(&mut iter, arg)?)?);
+            }
+            "quickack" => {
+                rt.quickack =
+                    Some(parse_num::<u8>(next_arg(&mut iter, arg)?)? > 0);
+            }
+            "onlink" => rt.onlink = Some(true),
+            "scope" => {
+                let value = next_arg(&mut iter, arg)?;
+                rt.scope = Some(
+                    value
+                        .parse::<RouteScope>()
+                        .map_err(|e| e.msg().to_string())?,
+                );
+            }
+            // Determined by the backend applying the route
+            "proto" => {
+                next_arg(&mut iter, arg)?;
+            }
+            _ if rt.destination.is_none() => {
+                rt.destination = Some(parse_prefix(arg, is_ipv6)?);
+            }
+            _ => return Err(format!("unsupported route argument {arg}")),
+        }
+    }
+    if rt.destination.is_none() {
+        return Err("route destination is not defined".to_string());
+    }
+    if rt.has_next_hop() && rt.next_hop_iface.is_none() {
+        rt.next_hop_iface = Some(iface_name.to_string());
+    }
+    Ok(rt)
+}
+
+pub(crate) fn parse_ip_rule_args(
+    args: &[&str],
+    is_ipv6: bool,
+) -> Result<RouteRuleEntry, String> {
+    let mut rule = RouteRuleEntry::new();
+    let mut is_ipv6 = is_ipv6;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match *arg {
+            "from" | "to" => {
+                let value = next_arg(&mut iter, arg)?;
+                let prefix = if value == "all" {
+                    None
+                } else {
+                    if value.contains(':') {
+                        is_ipv6 = true;
+                    }
+                    Some(parse_prefix(value, is_ipv6)?)
+                };
+                if *arg == "from" {
+                    rule.ip_from = prefix;
+                } else {
+                    rule.ip_to = prefix;
+                }
+            }
+            "priority" | "preference" | "pref" | "prio" => {
+                rule.priority = Some(parse_num(next_arg(&mut iter, arg)?)?);
+            }
+            "table" | "lookup" => {
+                (rule.table_id, rule.table_name) =
+                    parse_table(next_arg(&mut iter, arg)?);
+                if rule.table_name.is_none() {
+                    rule.table_id = rule.table_id.or(Some(MAIN_ROUTE_TABLE_ID));
+                }
+            }
+            "iif" | "dev" => {
+                rule.iif = Some(next_arg(&mut iter, arg)?.to_string());
+            }
+            "oif" => {
+                rule.oif = Some(next_arg(&mut iter, arg)?.to_string());
+            }
+            "tos" | "dsfield" => {
+                rule.tos = Some(
+                    u8::try_from(parse_hex_or_dec(next_arg(&mut iter, arg)?)?)
+                        .map_err(|e| format!("invalid tos: {e}"))?,
+                );
+            }
+            "dscp" => {
+                rule.dscp = Some(parse_num(next_arg(&mut iter, arg)?)?);
+            }
+            "ipproto" => {
+                rule.ipproto = Some(parse_num(next_arg(&mut iter, arg)?)?);
+            }
+            "sport" => {
+                rule.sport = Some(parse_range(next_arg(&mut iter, arg)?)?);
+            }
+            "dport" => {
+                rule.dport = Some(parse_range(next_arg(&mut iter, arg)?)?);
+            }
+            "uidrange" => {
+                rule.uidrange = Some(parse_range(next_arg(&mut iter, arg)?)?);
+            }
+            "l3mdev" => rule.l3mdev = Some(true),
+            "goto" => {
+                rule.action = Some(RouteRuleAction::Goto);
+                rule.goto_priority =
+                    Some(parse_num(next_arg(&mut iter, arg)?)?);
+            }
+            "nop" => rule.action = Some(RouteRuleAction::Nop),
+            "fwmark" => {
+                let value = next_arg(&mut iter, arg)?;
+                let (mark, mask) = match value.split_once('/') {
+                    Some((mark, mask)) => (mark, Some(mask)),
+                    None => (value, None),
+                };
+                rule.fwmark = Some(parse_hex_or_dec(mark)?);
+                rule.fwmask = mask.map(parse_hex_or_dec).transpose()?;
+            }
+            "suppress_prefixlength" => {
+                rule.suppress_prefix_length =
+                    Some(parse_num(next_arg(&mut iter, arg)?)?);
+            }
+            "blackhole" => rule.action = Some(RouteRuleAction::Blackhole),
+            "unreachable" => rule.action = Some(RouteRuleAction::Unreachable),
+            "prohibit" => rule.action = Some(RouteRuleAction::Prohibit),
+            _ => return Err(format!("unsupported route rule argument {arg}")),
+        }
+    }
+    rule.family = Some(if is_ipv6 {
+        AddressFamily::IPv6
+    } else {
+        AddressFamily::IPv4
+    });
+    Ok(rule)
+}
+
+fn next_arg<'a>(
+    iter: &mut std::slice::Iter<'_, &'a str>,
+    arg: &str,
+) -> Result<&'a str, String> {
+    iter.next()
+        .copied()
+        .ok_or_else(|| format!("no value defined for {arg}"))
+}
+
+fn parse_ip(value: &str) -> Result<IpAddr, String> {
+    value
+        .parse::<IpAddr>()
+        .map_err(|e| format!("invalid IP address {value}: {e}"))
+}
+
+// Parse prefix with `default` and host address supported.
+fn parse_prefix(value: &str, is_ipv6: bool) -> Result<String, String> {
+    if value == "default" {
+        return Ok(if is_ipv6 {
+            "::/0".to_string()
+        } else {
+            "0.0.0.0/0".to_string()
+        });
+    }
+    let (ip, prefix) = match value.split_once('/') {
+        Some((ip, prefix)) => (parse_ip(ip)?, Some(prefix)),
+        None => (parse_ip(value)?, None),
+    };
+    let max_prefix = if ip.is_ipv6() { 128 } else { 32 };
+    let prefix = match prefix {
+        Some(p) => p
+            .parse::<u8>()
+            .ok()
+            .filter(|p| *p <= max_prefix)
+            .ok_or_else(|| format!("invalid prefix length in {value}"))?,
+        None => max_prefix,
+    };
+    Ok(format!("{ip}/{prefix}"))
+}
+
+// Only numeric route table ID and `main` are supported, `main` is stored as
+// `None` which means the default table.
+// Return route table ID and route table name, the name will be resolved
+// through `/etc/iproute2/rt_tables` when applying.
+fn parse_table(value: &str) -> (Option<u32>, Option<String>) {
+    if value == "main" {
+        (None, None)
+    } else if let Ok(table_id) = value.parse::<u32>() {
+        (Some(table_id), None)
+    } else {
+        (None, Some(value.to_string()))
+    }
+}
+
+fn parse_range(value: &str) -> Result<RouteRuleRange, String> {
+    value
+        .parse::<RouteRuleRange>()
+        .map_err(|e| e.msg().to_string())
+}
+
+fn parse_num<T: std::str::FromStr>(value: &str) -> Result<T, String>
+where
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+{
+    value
+        .parse::<T>()
+        .map_err(|e| format!("invalid number {value}: {e}"))
+}
+
+fn parse_hex_or_dec(value: &str) -> Result<u32, String> {
+    if let Some(hex) = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        u32::from_str_radix(hex, 16)
+            .map_err(|e| format!("invalid hex number {value}: {e}"))
+    } else {
+        parse_num(value)
+    }
+}
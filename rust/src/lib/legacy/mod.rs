@@ -0,0 +1,511 @@
+// SPDX-License-Identifier: Apache-2.0
+
+mod debian;
+mod ifcfg;
+mod ip_cmd;
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+pub(crate) use self::debian::import_debian_interfaces;
+pub(crate) use self::ifcfg::import_ifcfg_dir;
+
+use crate::{
+    BaseInterface, BondConfig, BondInterface, BondMode, BondOptions,
+    DnsClientState, DnsState, DummyInterface, ErrorKind, EthernetInterface,
+    Interface, InterfaceIpv4, InterfaceIpv6, InterfaceState, InterfaceType,
+    LinuxBridgeConfig, LinuxBridgeInterface, LinuxBridgeOptions,
+    LinuxBridgePortConfig, NetworkState, NmstateError, RouteEntry,
+    RouteRuleEntry, VlanConfig, VlanInterface,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// Configuration found in legacy network configuration files which cannot
+/// be translated into nmstate schema.
+pub struct ImportWarning {
+    /// Path of the file holding the untranslated configuration.
+    pub file: String,
+    /// Line number of the untranslated configuration, starting from 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Interface the untranslated configuration belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iface: Option<String>,
+    /// The untranslated configuration.
+    pub config: String,
+    /// Why the configuration cannot be translated.
+    pub reason: String,
+}
+
+impl std::fmt::Display for ImportWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.file)?;
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+        }
+        if let Some(iface) = self.iface.as_ref() {
+            write!(f, ": interface {iface}")?;
+        }
+        write!(f, ": {}: {}", self.config, self.reason)
+    }
+}
+
+// Intermediate interface configuration shared by all legacy formats.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LegacyIface {
+    pub(crate) name: String,
+    // The file defining this interface, `None` means the interface is only
+    // referred as port by other interface.
+    pub(crate) file: Option<String>,
+    pub(crate) iface_type: Option<InterfaceType>,
+    pub(crate) state: Option<InterfaceState>,
+    pub(crate) mtu: Option<u64>,
+    pub(crate) mac_address: Option<String>,
+    pub(crate) controller: Option<String>,
+    pub(crate) ports: Vec<String>,
+    pub(crate) ipv4: Option<InterfaceIpv4>,
+    pub(crate) ipv6: Option<InterfaceIpv6>,
+    pub(crate) bond_mode: Option<BondMode>,
+    pub(crate) bond_opts: serde_json::Map<String, serde_json::Value>,
+    pub(crate) bridge_opts: serde_json::Map<String, serde_json::Value>,
+    // Options of this interface when acting as linux bridge port
+    pub(crate) bridge_port_conf: Option<LinuxBridgePortConfig>,
+    pub(crate) vlan_base_iface: Option<String>,
+    pub(crate) vlan_id: Option<u16>,
+}
+
+impl LegacyIface {
+    // Store bond option using kernel sysfs name after validation.
+    pub(crate) fn set_bond_opt(
+        &mut self,
+        key: &str,
+        value: &str,
+    ) -> Result<(), String> {
+        if key == "mode" {
+            self.bond_mode = Some(
+                serde_json::from_value::<BondMode>(serde_json::Value::String(
+                    value.to_string(),
+                ))
+                .map_err(|e| format!("invalid bond mode: {e}"))?,
+            );
+            return Ok(());
+        }
+        let mut opts = serde_json::Map::new();
+        opts.insert(
+            key.to_string(),
+            serde_json::Value::String(value.to_string()),
+        );
+        serde_json::from_value::<BondOptions>(serde_json::Value::Object(
+            opts.clone(),
+        ))
+        .map_err(|e| format!("unsupported bond option: {e}"))?;
+        self.bond_opts.extend(opts);
+        Ok(())
+    }
+
+    // Store linux bridge option using kernel sysfs name after validation.
+    pub(crate) fn set_bridge_opt(
+        &mut self,
+        key: &str,
+        value: &str,
+    ) -> Result<(), String> {
+        let value = serde_json::Value::String(value.to_string());
+        let (key, is_stp) = match key {
+            "stp" | "stp_state" => ("enabled", true),
+            "priority" | "forward_delay" | "hello_time" | "max_age" => {
+                (key, true)
+            }
+            "ageing_time" => ("mac-ageing-time", false),
+            _ => (key, false),
+        };
+        let key = key.replace('_', "-");
+        let mut opts = serde_json::Map::new();
+        if is_stp {
+            let mut stp_opts = serde_json::Map::new();
+            stp_opts.insert(key, value);
+            opts.insert("stp".to_string(), serde_json::Value::Object(stp_opts));
+        } else {
+            opts.insert(key, value);
+        }
+        serde_json::from_value::<LinuxBridgeOptions>(
+            serde_json::Value::Object(opts.clone()),
+        )
+        .map_err(|e| format!("unsupported linux bridge option: {e}"))?;
+        for (key, value) in opts {
+            match (self.bridge_opts.get_mut(&key), value) {
+                (
+                    Some(serde_json::Value::Object(cur)),
+                    serde_json::Value::Object(new),
+                ) => cur.extend(new),
+                (_, value) => {
+                    self.bridge_opts.insert(key, value);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Store linux bridge port option using kernel sysfs name.
+    pub(crate) fn set_bridge_port_opt(
+        &mut self,
+        key: &str,
+        value: &str,
+    ) -> Result<(), String> {
+        let port_conf = self
+            .bridge_port_conf
+            .get_or_insert_with(LinuxBridgePortConfig::new);
+        match key {
+            "priority" => {
+                port_conf.stp_priority = Some(value.parse().map_err(|e| {
+                    format!("invalid linux bridge port priority: {e}")
+                })?);
+            }
+            "path_cost" => {
+                port_conf.stp_path_cost = Some(value.parse().map_err(|e| {
+                    format!("invalid linux bridge port path cost: {e}")
+                })?);
+            }
+            "hairpin_mode" => {
+                port_conf.stp_hairpin_mode =
+                    Some(parse_bool(value).ok_or_else(|| {
+                        format!(
+                            "invalid linux bridge port hairpin mode {value}"
+                        )
+                    })?);
+            }
+            _ => {
+                return Err("unsupported linux bridge port option".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+// Intermediate state shared by all legacy formats.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LegacyState {
+    pub(crate) ifaces: Vec<LegacyIface>,
+    pub(crate) routes: Vec<RouteEntry>,
+    pub(crate) rules: Vec<RouteRuleEntry>,
+    pub(crate) dns: DnsClientState,
+    pub(crate) warnings: Vec<ImportWarning>,
+}
+
+impl LegacyState {
+    pub(crate) fn iface_mut(&mut self, name: &str) -> &mut LegacyIface {
+        let index = match self.ifaces.iter().position(|i| i.name == name) {
+            Some(i) => i,
+            None => {
+                self.ifaces.push(LegacyIface {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                self.ifaces.len() - 1
+            }
+        };
+        &mut self.ifaces[index]
+    }
+
+    pub(crate) fn warn(
+        &mut self,
+        file: &str,
+        line: Option<usize>,
+        iface: Option<&str>,
+        config: &str,
+        reason: &str,
+    ) {
+        log::warn!(
+            "Ignoring {config} in {file}{}: {reason}",
+            line.map(|l| format!(":{l}")).unwrap_or_default()
+        );
+        self.warnings.push(ImportWarning {
+            file: file.to_string(),
+            line,
+            iface: iface.map(|i| i.to_string()),
+            config: config.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+
+    pub(crate) fn add_dns_server(&mut self, server: &str) {
+        push_uniq(self.dns.server.get_or_insert_with(Vec::new), server);
+    }
+
+    pub(crate) fn add_dns_search(&mut self, search: &str) {
+        push_uniq(self.dns.search.get_or_insert_with(Vec::new), search);
+    }
+
+    pub(crate) fn into_net_state(
+        mut self,
+    ) -> (NetworkState, Vec<ImportWarning>) {
+        // Collect ports from both controller and port side, the `controller`
+        // property of port is only kept when controller is not imported.
+        let ctrl_names: Vec<String> = self
+            .ifaces
+            .iter()
+            .filter(|i| i.file.is_some())
+            .map(|i| i.name.clone())
+            .collect();
+        let mut ctrl_ports: HashMap<String, Vec<String>> = HashMap::new();
+        for iface in self.ifaces.iter_mut() {
+            for port in iface.ports.iter() {
+                push_uniq(
+                    ctrl_ports.entry(iface.name.clone()).or_default(),
+                    port,
+                );
+            }
+            if iface
+                .controller
+                .as_ref()
+                .map(|c| ctrl_names.contains(c))
+                .unwrap_or_default()
+            {
+                if let Some(ctrl) = iface.controller.take() {
+                    push_uniq(ctrl_ports.entry(ctrl).or_default(), &iface.name);
+                }
+            }
+        }
+        let port_names: Vec<String> =
+            ctrl_ports.values().flatten().cloned().collect();
+        let br_port_confs: HashMap<String, LinuxBridgePortConfig> = self
+            .ifaces
+            .iter()
+            .filter_map(|i| {
+                i.bridge_port_conf.clone().map(|c| (i.name.clone(), c))
+            })
+            .collect();
+
+        let mut net_state = NetworkState::new();
+        let legacy_ifaces = std::mem::take(&mut self.ifaces);
+        for legacy_iface in legacy_ifaces {
+            // Interface only referred as port of unknown controller
+            if legacy_iface.file.is_none()
+                && !port_names.contains(&legacy_iface.name)
+            {
+                continue;
+            }
+            let is_port = port_names.contains(&legacy_iface.name);
+            let ports = ctrl_ports.remove(&legacy_iface.name);
+            match self.legacy_iface_to_iface(
+                legacy_iface,
+                is_port,
+                ports,
+                &br_port_confs,
+            ) {
+                Some(iface) => net_state.append_interface_data(iface),
+                None => continue,
+            }
+        }
+        if !self.routes.is_empty() {
+            net_state.routes.config = Some(self.routes);
+        }
+        if !self.rules.is_empty() {
+            net_state.rules.config = Some(self.rules);
+        }
+        if !self.dns.is_empty() {
+            net_state.dns = Some(DnsState {
+                config: Some(self.dns),
+                ..Default::default()
+            });
+        }
+        (net_state, self.warnings)
+    }
+
+    fn legacy_iface_to_iface(
+        &mut self,
+        legacy_iface: LegacyIface,
+        is_port: bool,
+        ports: Option<Vec<String>>,
+        br_port_confs: &HashMap<String, LinuxBridgePortConfig>,
+    ) -> Option<Interface> {
+        let file = legacy_iface.file.clone().unwrap_or_default();
+        let mut base_iface = BaseInterface::new();
+        base_iface.name = legacy_iface.name.clone();
+        base_iface.iface_type = legacy_iface
+            .iface_type
+            .clone()
+            .unwrap_or(InterfaceType::Ethernet);
+        base_iface.state = legacy_iface.state.unwrap_or(InterfaceState::Up);
+        base_iface.mtu = legacy_iface.mtu;
+        base_iface.mac_address = legacy_iface.mac_address.clone();
+        if is_port {
+            if legacy_iface.ipv4.as_ref().map(|i| i.enabled) == Some(true)
+                || legacy_iface.ipv6.as_ref().map(|i| i.enabled) == Some(true)
+            {
+                self.warn(
+                    &file,
+                    None,
+                    Some(&legacy_iface.name),
+                    "IP configuration",
+                    "IP configuration is not allowed on port",
+                );
+            }
+        } else {
+            base_iface.ipv4 = legacy_iface.ipv4.map(|mut ip_conf| {
+                ip_conf.enabled_defined = true;
+                ip_conf
+            });
+            base_iface.ipv6 = legacy_iface.ipv6.map(|mut ip_conf| {
+                ip_conf.enabled_defined = true;
+                ip_conf
+            });
+        }
+        base_iface.controller = legacy_iface.controller;
+
+        Some(match base_iface.iface_type {
+            InterfaceType::Ethernet => Interface::Ethernet({
+                let mut iface = EthernetInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            InterfaceType::Dummy => Interface::Dummy({
+                let mut iface = DummyInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            InterfaceType::Bond => Interface::Bond({
+                let mut iface = BondInterface::new();
+                let mut bond_conf = BondConfig::new();
+                bond_conf.mode = legacy_iface.bond_mode;
+                if !legacy_iface.bond_opts.is_empty() {
+                    bond_conf.options = serde_json::from_value(
+                        serde_json::Value::Object(legacy_iface.bond_opts),
+                    )
+                    .ok();
+                }
+                bond_conf.port = Some(ports.unwrap_or_default());
+                iface.bond = Some(bond_conf);
+                iface.base = base_iface;
+                iface
+            }),
+            InterfaceType::LinuxBridge => Interface::LinuxBridge({
+                let mut iface = LinuxBridgeInterface::new();
+                let mut br_conf = LinuxBridgeConfig::new();
+                if !legacy_iface.bridge_opts.is_empty() {
+                    br_conf.options = serde_json::from_value(
+                        serde_json::Value::Object(legacy_iface.bridge_opts),
+                    )
+                    .ok();
+                }
+                br_conf.port = Some(
+                    ports
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|name| {
+                            let mut port_conf = br_port_confs
+                                .get(&name)
+                                .cloned()
+                                .unwrap_or_default();
+                            port_conf.name = name;
+                            port_conf
+                        })
+                        .collect(),
+                );
+                iface.bridge = Some(br_conf);
+                iface.base = base_iface;
+                iface
+            }),
+            InterfaceType::Vlan => {
+                let (base, id) = match (
+                    legacy_iface.vlan_base_iface,
+                    legacy_iface.vlan_id,
+                ) {
+                    (Some(b), Some(i)) => (b, i),
+                    _ => {
+                        self.warn(
+                            &file,
+                            None,
+                            Some(&legacy_iface.name),
+                            "VLAN interface",
+                            "VLAN parent interface or VLAN ID is not defined",
+                        );
+                        return None;
+                    }
+                };
+                Interface::Vlan({
+                    let mut iface = VlanInterface::new();
+                    iface.vlan = Some(VlanConfig {
+                        base_iface: base,
+                        id,
+                        ..Default::default()
+                    });
+                    iface.base = base_iface;
+                    iface
+                })
+            }
+            _ => {
+                self.warn(
+                    &file,
+                    None,
+                    Some(&legacy_iface.name),
+                    &format!("interface type {}", base_iface.iface_type),
+                    "interface type is not supported",
+                );
+                return None;
+            }
+        })
+    }
+}
+
+pub(crate) fn read_file(file_path: &str) -> Result<String, NmstateError> {
+    std::fs::read_to_string(file_path).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Failed to read file {file_path}: {e}"),
+        )
+    })
+}
+
+// Return sorted file names in specified folder.
+pub(crate) fn read_dir(dir: &str) -> Result<Vec<String>, NmstateError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Failed to read folder {dir}: {e}"),
+        )
+    })?;
+    let mut ret = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Failed to read folder {dir}: {e}"),
+            )
+        })?;
+        if entry.path().is_file() {
+            if let Some(name) = entry.file_name().to_str() {
+                ret.push(name.to_string());
+            }
+        }
+    }
+    // We should be persistent on output, hence sort by file name.
+    ret.sort_unstable();
+    Ok(ret)
+}
+
+pub(crate) fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" | "y" => Some(true),
+        "0" | "false" | "no" | "off" | "n" => Some(false),
+        _ => None,
+    }
+}
+
+// Convert dotted IPv4 netmask like `255.255.255.0` to prefix length.
+pub(crate) fn netmask_to_prefix(netmask: &str) -> Option<u8> {
+    let mask = u32::from(netmask.parse::<std::net::Ipv4Addr>().ok()?);
+    if mask.leading_ones() + mask.trailing_zeros() == 32 {
+        Some(mask.leading_ones() as u8)
+    } else {
+        None
+    }
+}
+
+fn push_uniq(items: &mut Vec<String>, item: &str) {
+    if !items.iter().any(|i| i == item) {
+        items.push(item.to_string());
+    }
+}
@@ -84,6 +84,7 @@
 //! ```
 
 mod deserializer;
+mod dhcp;
 mod dispatch;
 mod dns;
 mod error;
@@ -94,16 +95,22 @@ mod ieee8021x;
 mod iface;
 mod ifaces;
 mod ip;
+mod legacy;
 mod lldp;
 mod mptcp;
 mod net_state;
+#[cfg(feature = "gen_conf")]
+mod netplan;
+#[cfg(feature = "gen_conf")]
+mod networkd;
+mod nexthop;
 #[cfg(feature = "query_apply")]
 mod nispor;
 mod nm;
 #[allow(deprecated)]
 mod ovn;
 mod ovs;
-#[cfg(feature = "query_apply")]
+#[cfg(any(feature = "query_apply", feature = "gen_conf"))]
 mod ovsdb;
 #[cfg(feature = "query_apply")]
 mod policy;
@@ -113,16 +120,18 @@ mod query_apply;
 mod revert;
 mod route;
 mod route_rule;
+mod route_table;
 mod serializer;
 mod state;
 #[cfg(feature = "query_apply")]
 mod statistic;
 mod unit_tests;
 
+pub use crate::dhcp::{DhcpLease, DhcpLeaseRoute, DhcpLeaseState};
 pub use crate::dispatch::DispatchConfig;
 pub(crate) use crate::dns::MergedDnsState;
 pub use crate::dns::{DnsClientState, DnsState};
-pub use crate::error::{ErrorKind, NmstateError};
+pub use crate::error::{ErrorKind, NmstateError, VerificationDiff};
 pub use crate::hostname::HostNameState;
 pub(crate) use crate::hostname::MergedHostNameState;
 pub use crate::ieee8021x::Ieee8021XConfig;
@@ -133,31 +142,37 @@ pub use crate::iface::{
 };
 pub(crate) use crate::ifaces::MergedInterfaces;
 pub use crate::ifaces::{
-    BaseInterface, BondAdSelect, BondAllPortsActive, BondArpAllTargets,
-    BondArpValidate, BondConfig, BondFailOverMac, BondInterface, BondLacpRate,
-    BondMode, BondOptions, BondPortConfig, BondPrimaryReselect,
-    BondXmitHashPolicy, BridgePortTrunkTag, BridgePortVlanConfig,
-    BridgePortVlanMode, BridgePortVlanRange, DummyInterface, EthernetConfig,
-    EthernetDuplex, EthernetInterface, EthtoolCoalesceConfig, EthtoolConfig,
-    EthtoolFeatureConfig, EthtoolPauseConfig, EthtoolRingConfig, HsrConfig,
-    HsrInterface, HsrProtocol, InfiniBandConfig, InfiniBandInterface,
-    InfiniBandMode, Interfaces, IpsecInterface, LibreswanAddressFamily,
-    LibreswanConfig, LibreswanConnectionType, LinuxBridgeConfig,
-    LinuxBridgeInterface, LinuxBridgeMulticastRouterType, LinuxBridgeOptions,
-    LinuxBridgePortConfig, LinuxBridgeStpOptions, LoopbackInterface,
-    MacSecConfig, MacSecInterface, MacSecOffload, MacSecValidate,
-    MacVlanConfig, MacVlanInterface, MacVlanMode, MacVtapConfig,
-    MacVtapInterface, MacVtapMode, OvsBridgeBondConfig, OvsBridgeBondMode,
-    OvsBridgeBondPortConfig, OvsBridgeConfig, OvsBridgeInterface,
-    OvsBridgeOptions, OvsBridgePortConfig, OvsBridgeStpOptions, OvsDpdkConfig,
-    OvsInterface, OvsPatchConfig, SrIovConfig, SrIovVfConfig, VethConfig,
-    VlanConfig, VlanInterface, VlanProtocol, VlanRegistrationProtocol,
-    VrfConfig, VrfInterface, VxlanConfig, VxlanInterface, XfrmInterface,
+    BareUdpConfig, BareUdpEtherType, BareUdpInterface, BaseInterface,
+    BondAdSelect, BondAllPortsActive, BondArpAllTargets, BondArpValidate,
+    BondConfig, BondFailOverMac, BondInterface, BondLacpRate, BondMode,
+    BondOptions, BondPortConfig, BondPrimaryReselect, BondXmitHashPolicy,
+    BridgePortTrunkTag, BridgePortVlanConfig, BridgePortVlanMode,
+    BridgePortVlanRange, DummyInterface, EthernetConfig, EthernetDuplex,
+    EthernetInterface, EthtoolCoalesceConfig, EthtoolConfig,
+    EthtoolFeatureConfig, EthtoolPauseConfig, EthtoolRingConfig, GeneveConfig,
+    GeneveDf, GeneveInterface, HsrConfig, HsrInterface, HsrProtocol,
+    InfiniBandConfig, InfiniBandInterface, InfiniBandMode, Interfaces,
+    IpTunnelConfig, IpTunnelEncapConfig, IpTunnelEncapType, IpTunnelInterface,
+    IpVlanConfig, IpVlanInterface, IpVlanMode, IpsecInterface,
+    LibreswanAddressFamily, LibreswanConfig, LibreswanConnectionType,
+    LinuxBridgeConfig, LinuxBridgeInterface, LinuxBridgeMulticastRouterType,
+    LinuxBridgeOptions, LinuxBridgePortConfig, LinuxBridgeStpOptions,
+    LoopbackInterface, MacSecConfig, MacSecInterface, MacSecOffload,
+    MacSecValidate, MacVlanConfig, MacVlanInterface, MacVlanMode,
+    MacVtapConfig, MacVtapInterface, MacVtapMode, OvsBridgeBondConfig,
+    OvsBridgeBondMode, OvsBridgeBondPortConfig, OvsBridgeConfig,
+    OvsBridgeInterface, OvsBridgeOptions, OvsBridgePortConfig,
+    OvsBridgeStpOptions, OvsDpdkConfig, OvsInterface, OvsPatchConfig,
+    SrIovConfig, SrIovVfConfig, VethConfig, VlanConfig, VlanInterface,
+    VlanProtocol, VlanRegistrationProtocol, VrfConfig, VrfInterface,
+    VxlanConfig, VxlanInterface, WireGuardConfig, WireGuardInterface,
+    WireGuardPeerConfig, XfrmInterface,
 };
 pub use crate::ip::{
     AddressFamily, Dhcpv4ClientId, Dhcpv6Duid, InterfaceIpAddr, InterfaceIpv4,
     InterfaceIpv6, Ipv6AddrGenMode, WaitIp,
 };
+pub use crate::legacy::ImportWarning;
 pub use crate::lldp::{
     LldpAddressFamily, LldpChassisId, LldpChassisIdType, LldpConfig,
     LldpMacPhy, LldpMaxFrameSize, LldpMgmtAddr, LldpMgmtAddrs, LldpNeighborTlv,
@@ -167,7 +182,11 @@ pub use crate::lldp::{
 };
 pub use crate::mptcp::{MptcpAddressFlag, MptcpConfig};
 pub(crate) use crate::net_state::MergedNetworkState;
-pub use crate::net_state::NetworkState;
+pub use crate::net_state::{GenConfBackend, NetworkState};
+pub(crate) use crate::nexthop::MergedNexthops;
+pub use crate::nexthop::{
+    NexthopEntry, NexthopGroupMember, NexthopState, Nexthops,
+};
 pub(crate) use crate::ovn::MergedOvnConfiguration;
 pub use crate::ovn::{
     OvnBridgeMapping, OvnBridgeMappingState, OvnConfiguration,
@@ -178,11 +197,19 @@ pub use crate::ovs::{OvsDbGlobalConfig, OvsDbIfaceConfig};
 pub use crate::policy::{
     NetworkCaptureRules, NetworkPolicy, NetworkStateTemplate,
 };
+#[cfg(feature = "query_apply")]
+pub use crate::query_apply::{
+    ApplyReport, NetworkStateWatcher, NmProfileReport,
+};
 pub(crate) use crate::route::MergedRoutes;
-pub use crate::route::{RouteEntry, RouteState, RouteType, Routes};
+pub use crate::route::{
+    RouteEntry, RouteProtocol, RouteScope, RouteState, RouteType, Routes,
+};
 pub(crate) use crate::route_rule::MergedRouteRules;
+pub(crate) use crate::route_table::RouteTableNames;
 pub use crate::route_rule::{
-    RouteRuleAction, RouteRuleEntry, RouteRuleState, RouteRules,
+    RouteRuleAction, RouteRuleEntry, RouteRuleRange, RouteRuleState,
+    RouteRules,
 };
 #[cfg(feature = "query_apply")]
 pub use crate::statistic::{NmstateFeature, NmstateStatistic};
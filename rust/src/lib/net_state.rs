@@ -6,10 +6,12 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    DnsState, ErrorKind, HostNameState, Interface, Interfaces, MergedDnsState,
-    MergedHostNameState, MergedInterfaces, MergedOvnConfiguration,
-    MergedOvsDbGlobalConfig, MergedRouteRules, MergedRoutes, NmstateError,
-    OvnConfiguration, OvsDbGlobalConfig, RouteRules, Routes,
+    legacy::{import_debian_interfaces, import_ifcfg_dir},
+    DnsState, ErrorKind, HostNameState, ImportWarning, Interface, Interfaces,
+    MergedDnsState, MergedHostNameState, MergedInterfaces, MergedNexthops,
+    MergedOvnConfiguration, MergedOvsDbGlobalConfig, MergedRouteRules,
+    MergedRoutes, Nexthops, NmstateError, OvnConfiguration, OvsDbGlobalConfig,
+    RouteRules, RouteTableNames, Routes,
 };
 
 /// The [NetworkState] represents the whole network state including both
@@ -96,6 +98,9 @@ pub struct NetworkState {
         skip_serializing_if = "RouteRules::is_empty"
     )]
     pub rules: RouteRules,
+    /// Nexthop objects referred by routes
+    #[serde(default, skip_serializing_if = "Nexthops::is_empty")]
+    pub nexthops: Nexthops,
     /// Route
     #[serde(default, skip_serializing_if = "Routes::is_empty")]
     pub routes: Routes,
@@ -124,6 +129,14 @@ pub struct NetworkState {
     pub(crate) running_config_only: bool,
     #[serde(skip)]
     pub(crate) memory_only: bool,
+    #[serde(skip)]
+    pub(crate) dry_run: bool,
+    #[serde(skip)]
+    pub(crate) create_route_table_names: bool,
+    #[serde(skip)]
+    pub(crate) gen_conf_backend: GenConfBackend,
+    #[serde(skip)]
+    pub(crate) netns: Option<String>,
 }
 
 impl NetworkState {
@@ -132,6 +145,7 @@ impl NetworkState {
             && self.dns.is_none()
             && self.ovsdb.is_none()
             && self.rules.is_empty()
+            && self.nexthops.is_empty()
             && self.routes.is_empty()
             && self.interfaces.is_empty()
             && self.ovn.is_none()
@@ -157,18 +171,20 @@ impl NetworkState {
     /// By default(true), When nmstate applying the network state, after applied
     /// the network state, nmstate will verify whether the outcome network
     /// configuration matches with desired, if not, will rollback to state
-    /// before apply(only when [NetworkState::set_kernel_only()] set to false.
+    /// before apply.
     /// When set to false, no verification will be performed.
     pub fn set_verify_change(&mut self, value: bool) -> &mut Self {
         self.no_verify = !value;
         self
     }
 
-    /// Only available when [NetworkState::set_kernel_only()] set to false.
     /// When set to false, the network configuration will not commit
     /// persistently, and will rollback after timeout defined by
     /// [NetworkState::set_timeout()].  Default to true for making the network
     /// state persistent.
+    /// In [NetworkState::set_kernel_only()] mode, there is no daemon tracking
+    /// the timeout, the expired checkpoint will be rolled back by next kernel
+    /// mode apply action or [NetworkState::checkpoint_rollback()].
     pub fn set_commit(&mut self, value: bool) -> &mut Self {
         self.no_commit = !value;
         self
@@ -212,6 +228,56 @@ impl NetworkState {
         self
     }
 
+    /// When set to true, [NetworkState::apply()] validates the desired state
+    /// and plans the backend actions without changing anything, the planned
+    /// actions are logged. Use [NetworkState::apply_dry_run()] to get them
+    /// as report.
+    /// Default is false.
+    pub fn set_dry_run(&mut self, value: bool) -> &mut Self {
+        self.dry_run = value;
+        self
+    }
+
+    /// When set to true, route table names of routes and route rules unknown
+    /// to `/etc/iproute2/rt_tables` and `/etc/iproute2/rt_tables.d/*.conf`
+    /// are created using the route table ID of the same entry when applying.
+    /// The mappings are stored in `/etc/iproute2/rt_tables.d/nmstate.conf`
+    /// only after the checkpoint is committed, and never stored when
+    /// [NetworkState::set_memory_only()] is true. With NetworkManager
+    /// backend and [NetworkState::set_commit()] set to false, the mappings
+    /// are not stored.
+    /// Default is false.
+    pub fn set_create_route_table_names(&mut self, value: bool) -> &mut Self {
+        self.create_route_table_names = value;
+        self
+    }
+
+    /// The network backend to generate configurations for in
+    /// [NetworkState::gen_conf()].
+    /// Default is [GenConfBackend::NetworkManager].
+    pub fn set_gen_conf_backend(&mut self, value: GenConfBackend) -> &mut Self {
+        self.gen_conf_backend = value;
+        self
+    }
+
+    /// Query and apply the network state inside specified network namespace
+    /// instead of the one nmstate is running in. The value could be name of
+    /// namespace in `/run/netns`, absolute path of namespace file or PID of
+    /// process holding the namespace.
+    /// NetworkManager and OpenvSwitch only manage their own network
+    /// namespace, hence [NetworkState::set_kernel_only()] is required to be
+    /// true, otherwise [ErrorKind::NotSupportedError] is returned.
+    /// DNS, hostname and [NetworkState::set_create_route_table_names()] are
+    /// not supported.
+    pub fn set_netns(&mut self, value: &str) -> &mut Self {
+        self.netns = Some(value.to_string());
+        self
+    }
+
+    pub fn netns(&self) -> Option<&str> {
+        self.netns.as_deref()
+    }
+
     /// Create empty [NetworkState]
     pub fn new() -> Self {
         Default::default()
@@ -246,6 +312,38 @@ impl NetworkState {
         self.interfaces.push(iface);
     }
 
+    /// Create [NetworkState] from the legacy ifcfg-rh files (`ifcfg-*`,
+    /// `route-*`, `route6-*`, `rule-*` and `rule6-*`) in specified folder,
+    /// e.g. `/etc/sysconfig/network-scripts`.
+    /// Configurations which cannot be translated are reported in the
+    /// returned [ImportWarning] list.
+    pub fn from_ifcfg_dir(
+        dir: &str,
+    ) -> Result<(Self, Vec<ImportWarning>), NmstateError> {
+        import_ifcfg_dir(dir)
+    }
+
+    /// Create [NetworkState] from Debian ifupdown configuration file, e.g.
+    /// `/etc/network/interfaces`. Files included by `source` and
+    /// `source-directory` are also parsed.
+    /// Configurations which cannot be translated are reported in the
+    /// returned [ImportWarning] list.
+    pub fn from_debian_interfaces(
+        file_path: &str,
+    ) -> Result<(Self, Vec<ImportWarning>), NmstateError> {
+        import_debian_interfaces(file_path)
+    }
+
+    #[cfg(not(feature = "query_apply"))]
+    pub fn from_nm_keyfiles(_dir: &str) -> Result<Self, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::from_nm_keyfiles() need `query_apply` feature \
+            enabled"
+                .into(),
+        ))
+    }
+
     #[cfg(not(feature = "query_apply"))]
     pub fn retrieve(&mut self) -> Result<&mut Self, NmstateError> {
         Err(NmstateError::new(
@@ -316,6 +414,42 @@ impl NetworkState {
     }
 }
 
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// Network backend used by [NetworkState::gen_conf()] to generate offline
+/// configurations.
+pub enum GenConfBackend {
+    /// NetworkManager keyfiles.
+    /// Serialize and deserialize to/from `network-manager`, displayed as
+    /// `NetworkManager` which is also the key of [NetworkState::gen_conf()]
+    /// output.
+    #[default]
+    NetworkManager,
+    /// systemd-networkd `.netdev`, `.network` and `.link` files.
+    /// Serialize and deserialize to/from `systemd-networkd`.
+    SystemdNetworkd,
+    /// Netplan YAML.
+    /// Serialize and deserialize to/from `netplan`.
+    Netplan,
+}
+
+impl std::fmt::Display for GenConfBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::NetworkManager => "NetworkManager",
+                Self::SystemdNetworkd => "systemd-networkd",
+                Self::Netplan => "netplan",
+            }
+        )
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub(crate) struct MergedNetworkState {
     pub(crate) interfaces: MergedInterfaces,
@@ -323,8 +457,10 @@ pub(crate) struct MergedNetworkState {
     pub(crate) dns: MergedDnsState,
     pub(crate) ovn: MergedOvnConfiguration,
     pub(crate) ovsdb: MergedOvsDbGlobalConfig,
+    pub(crate) nexthops: MergedNexthops,
     pub(crate) routes: MergedRoutes,
     pub(crate) rules: MergedRouteRules,
+    pub(crate) route_table_names: RouteTableNames,
     pub(crate) memory_only: bool,
 }
 
@@ -343,11 +479,33 @@ impl MergedNetworkState {
         )?;
         let ignored_ifaces = interfaces.ignored_ifaces.as_slice();
 
-        let mut routes =
-            MergedRoutes::new(desired.routes, current.routes, &interfaces)?;
+        let nexthops = MergedNexthops::new(
+            desired.nexthops,
+            current.nexthops,
+            &interfaces,
+            desired.kernel_only,
+        )?;
+
+        let route_table_names = RouteTableNames::new(
+            &desired.routes,
+            &desired.rules,
+            desired.create_route_table_names,
+        )?;
+
+        let mut routes = MergedRoutes::new(
+            desired.routes,
+            current.routes,
+            &interfaces,
+            &nexthops,
+            &route_table_names,
+        )?;
         routes.remove_routes_to_ignored_ifaces(ignored_ifaces);
 
-        let mut rules = MergedRouteRules::new(desired.rules, current.rules)?;
+        let mut rules = MergedRouteRules::new(
+            desired.rules,
+            current.rules,
+            &route_table_names,
+        )?;
         rules.remove_rules_to_ignored_ifaces(ignored_ifaces);
 
         let hostname =
@@ -363,8 +521,10 @@ impl MergedNetworkState {
 
         let ret = Self {
             interfaces,
+            nexthops,
             routes,
             rules,
+            route_table_names,
             dns: MergedDnsState::new(
                 desired.dns,
                 current.dns.unwrap_or_default(),
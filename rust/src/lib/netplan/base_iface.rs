@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::conf::NetplanIface;
+use crate::{
+    BaseInterface, ErrorKind, InterfaceIdentifier, InterfaceState, NmstateError,
+};
+
+pub(crate) fn not_supported(iface_name: &str, prop: &str) -> NmstateError {
+    NmstateError::new(
+        ErrorKind::NotSupportedError,
+        format!(
+            "Property {prop} of interface {iface_name} is not supported by \
+            netplan"
+        ),
+    )
+}
+
+pub(crate) fn gen_base_netplan_conf(
+    base_iface: &BaseInterface,
+    np_iface: &mut NetplanIface,
+) -> Result<(), NmstateError> {
+    let iface_name = base_iface.name.as_str();
+    if base_iface.ovsdb.is_some() {
+        return Err(not_supported(iface_name, "ovs-db"));
+    }
+    if base_iface.ieee8021x.is_some() {
+        return Err(not_supported(iface_name, "802.1x"));
+    }
+    if base_iface.ethtool.is_some() {
+        return Err(not_supported(iface_name, "ethtool"));
+    }
+    if base_iface.description.is_some() {
+        return Err(not_supported(iface_name, "description"));
+    }
+    if base_iface.wait_ip.is_some() {
+        return Err(not_supported(iface_name, "wait-ip"));
+    }
+    if base_iface.accept_all_mac_addresses == Some(true) {
+        return Err(not_supported(iface_name, "accept-all-mac-addresses"));
+    }
+    // Netplan can only emit LLDP packets but nmstate LLDP is about
+    // receiving.
+    if base_iface.lldp.as_ref().map(|l| l.enabled) == Some(true) {
+        return Err(not_supported(iface_name, "lldp"));
+    }
+    if base_iface
+        .mptcp
+        .as_ref()
+        .and_then(|m| m.address_flags.as_ref())
+        .map(|f| !f.is_empty())
+        == Some(true)
+    {
+        return Err(not_supported(iface_name, "mptcp.address-flags"));
+    }
+
+    // The MAC address is used for matching the interface when identifier is
+    // `mac-address`.
+    if base_iface.identifier != Some(InterfaceIdentifier::MacAddress) {
+        np_iface.macaddress = base_iface.mac_address.clone();
+    }
+    np_iface.mtu = base_iface.mtu;
+    if base_iface.state == InterfaceState::Down {
+        np_iface.activation_mode = Some("off".to_string());
+    }
+    Ok(())
+}
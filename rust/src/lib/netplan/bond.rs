@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    base_iface::not_supported,
+    conf::{NetplanBondParameters, NetplanIface, NetplanParameters},
+};
+use crate::{BondAllPortsActive, BondArpValidate, BondInterface, NmstateError};
+
+pub(crate) fn gen_bond_netplan_conf(
+    iface: &BondInterface,
+    np_iface: &mut NetplanIface,
+) -> Result<(), NmstateError> {
+    let iface_name = iface.base.name.as_str();
+    let bond_conf = if let Some(c) = iface.bond.as_ref() {
+        c
+    } else {
+        return Ok(());
+    };
+    let mut params = NetplanBondParameters {
+        mode: bond_conf.mode.as_ref().map(|m| m.to_string()),
+        ..Default::default()
+    };
+    if let Some(opts) = bond_conf.options.as_ref() {
+        for (prop, is_set) in [
+            ("ad_actor_sys_prio", opts.ad_actor_sys_prio.is_some()),
+            ("ad_actor_system", opts.ad_actor_system.is_some()),
+            ("ad_user_port_key", opts.ad_user_port_key.is_some()),
+            ("arp_missed_max", opts.arp_missed_max.is_some()),
+            ("balance_slb", opts.balance_slb.is_some()),
+            ("tlb_dynamic_lb", opts.tlb_dynamic_lb.is_some()),
+            ("use_carrier", opts.use_carrier.is_some()),
+            (
+                "num_unsol_na",
+                opts.num_unsol_na.is_some()
+                    && opts.num_unsol_na != opts.num_grat_arp,
+            ),
+        ] {
+            if is_set {
+                return Err(not_supported(
+                    iface_name,
+                    &format!("link-aggregation.options.{prop}"),
+                ));
+            }
+        }
+        if let Some(v) = opts.arp_validate.as_ref() {
+            if matches!(
+                v,
+                BondArpValidate::Filter
+                    | BondArpValidate::FilterActive
+                    | BondArpValidate::FilterBackup
+            ) {
+                return Err(not_supported(
+                    iface_name,
+                    &format!("link-aggregation.options.arp_validate: {v}"),
+                ));
+            }
+            params.arp_validate = Some(v.to_string());
+        }
+        params.lacp_rate = opts.lacp_rate.as_ref().map(|v| v.to_string());
+        params.mii_monitor_interval = opts.miimon;
+        params.min_links = opts.min_links;
+        params.transmit_hash_policy =
+            opts.xmit_hash_policy.as_ref().map(|v| v.to_string());
+        params.ad_select = opts.ad_select.as_ref().map(|v| v.to_string());
+        params.all_slaves_active = opts
+            .all_slaves_active
+            .as_ref()
+            .map(|v| v == &BondAllPortsActive::Delivered);
+        params.arp_interval = opts.arp_interval;
+        params.arp_ip_targets = opts
+            .arp_ip_target
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string())
+            .collect();
+        params.arp_all_targets =
+            opts.arp_all_targets.as_ref().map(|v| v.to_string());
+        params.up_delay = opts.updelay;
+        params.down_delay = opts.downdelay;
+        params.fail_over_mac_policy =
+            opts.fail_over_mac.as_ref().map(|v| v.to_string());
+        params.gratuitous_arp = opts.num_grat_arp;
+        params.packets_per_slave = opts.packets_per_slave;
+        params.primary_reselect_policy =
+            opts.primary_reselect.as_ref().map(|v| v.to_string());
+        params.resend_igmp = opts.resend_igmp;
+        params.learn_packet_interval = opts.lp_interval;
+        params.primary = opts.primary.clone();
+    }
+    for port_conf in bond_conf.ports_config.as_deref().unwrap_or_default() {
+        if port_conf.priority.is_some() {
+            return Err(not_supported(
+                port_conf.name.as_str(),
+                "link-aggregation.ports-config.priority",
+            ));
+        }
+        if port_conf.queue_id.is_some() {
+            return Err(not_supported(
+                port_conf.name.as_str(),
+                "link-aggregation.ports-config.queue-id",
+            ));
+        }
+    }
+    if params != NetplanBondParameters::default() {
+        np_iface.parameters = Some(NetplanParameters::Bond(Box::new(params)));
+    }
+    Ok(())
+}
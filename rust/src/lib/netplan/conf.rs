@@ -0,0 +1,294 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+const NETPLAN_VERSION: u8 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub(crate) struct NetplanConf {
+    pub(crate) network: NetplanNetwork,
+}
+
+// Use BTreeMap to be persistent on output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct NetplanNetwork {
+    pub(crate) version: u8,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) ethernets: BTreeMap<String, NetplanIface>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) bonds: BTreeMap<String, NetplanIface>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) bridges: BTreeMap<String, NetplanIface>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) vlans: BTreeMap<String, NetplanIface>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) vrfs: BTreeMap<String, NetplanIface>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) tunnels: BTreeMap<String, NetplanIface>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) dummy_devices: BTreeMap<String, NetplanIface>,
+}
+
+impl Default for NetplanNetwork {
+    fn default() -> Self {
+        Self {
+            version: NETPLAN_VERSION,
+            ethernets: BTreeMap::new(),
+            bonds: BTreeMap::new(),
+            bridges: BTreeMap::new(),
+            vlans: BTreeMap::new(),
+            vrfs: BTreeMap::new(),
+            tunnels: BTreeMap::new(),
+            dummy_devices: BTreeMap::new(),
+        }
+    }
+}
+
+impl NetplanNetwork {
+    pub(crate) fn contains_key(&self, iface_name: &str) -> bool {
+        [
+            &self.ethernets,
+            &self.bonds,
+            &self.bridges,
+            &self.vlans,
+            &self.vrfs,
+            &self.tunnels,
+            &self.dummy_devices,
+        ]
+        .iter()
+        .any(|m| m.contains_key(iface_name))
+    }
+
+    pub(crate) fn get_mut(
+        &mut self,
+        iface_name: &str,
+    ) -> Option<&mut NetplanIface> {
+        [
+            &mut self.ethernets,
+            &mut self.bonds,
+            &mut self.bridges,
+            &mut self.vlans,
+            &mut self.vrfs,
+            &mut self.tunnels,
+            &mut self.dummy_devices,
+        ]
+        .into_iter()
+        .find_map(|m| m.get_mut(iface_name))
+    }
+}
+
+// All netplan device types share the same struct, the generator is
+// responsible for only setting properties valid for the device type.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct NetplanIface {
+    #[serde(rename = "match", skip_serializing_if = "Option::is_none")]
+    pub(crate) match_conf: Option<NetplanMatch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) set_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) local: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) remote: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) mac_learning: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) table: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) interfaces: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) parameters: Option<NetplanParameters>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) macaddress: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) mtu: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) activation_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) dhcp4: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) dhcp6: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) dhcp_identifier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) dhcp4_overrides: Option<NetplanDhcpOverrides>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) dhcp6_overrides: Option<NetplanDhcpOverrides>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) accept_ra: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) ra_overrides: Option<NetplanRaOverrides>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) link_local: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) ipv6_address_generation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) ipv6_address_token: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) addresses: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) nameservers: Option<NetplanNameservers>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) routes: Vec<NetplanRoute>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) routing_policy: Vec<NetplanRoutingPolicy>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub(crate) struct NetplanMatch {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) macaddress: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub(crate) enum NetplanParameters {
+    Bond(Box<NetplanBondParameters>),
+    Bridge(NetplanBridgeParameters),
+}
+
+// The time values without suffix are treated as milliseconds by netplan
+// except `learn-packet-interval` which is in seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct NetplanBondParameters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) lacp_rate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) mii_monitor_interval: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) min_links: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) transmit_hash_policy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) ad_select: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) all_slaves_active: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) arp_interval: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) arp_ip_targets: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) arp_validate: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) arp_all_targets: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) up_delay: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) down_delay: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) fail_over_mac_policy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) gratuitous_arp: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) packets_per_slave: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) primary_reselect_policy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) resend_igmp: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) learn_packet_interval: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) primary: Option<String>,
+}
+
+// The time values without suffix are treated as seconds by netplan.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct NetplanBridgeParameters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) ageing_time: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) priority: Option<u16>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) port_priority: BTreeMap<String, u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) forward_delay: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hello_time: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) max_age: Option<u8>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) path_cost: BTreeMap<String, u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) stp: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct NetplanDhcpOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) use_dns: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) use_routes: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) route_metric: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) send_hostname: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hostname: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct NetplanRaOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) use_dns: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) table: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub(crate) struct NetplanNameservers {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) addresses: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) search: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub(crate) struct NetplanRoute {
+    pub(crate) to: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) via: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) metric: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) table: Option<u32>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub(crate) route_type: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub(crate) struct NetplanRoutingPolicy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) table: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) priority: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) mark: Option<u32>,
+    #[serde(
+        rename = "type-of-service",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(crate) type_of_service: Option<u8>,
+}
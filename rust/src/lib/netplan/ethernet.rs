@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    base_iface::not_supported,
+    conf::{NetplanIface, NetplanMatch},
+};
+use crate::{EthernetInterface, InterfaceIdentifier, NmstateError};
+
+pub(crate) fn gen_ethernet_netplan_conf(
+    iface: &EthernetInterface,
+    np_iface: &mut NetplanIface,
+) -> Result<(), NmstateError> {
+    let iface_name = iface.base.name.as_str();
+    if iface.veth.is_some() {
+        return Err(not_supported(iface_name, "veth"));
+    }
+    if let Some(eth_conf) = iface.ethernet.as_ref() {
+        if eth_conf.sr_iov.is_some() {
+            return Err(not_supported(iface_name, "ethernet.sr-iov"));
+        }
+        if eth_conf.auto_neg.is_some() {
+            return Err(not_supported(iface_name, "ethernet.auto-negotiation"));
+        }
+        if eth_conf.speed.is_some() {
+            return Err(not_supported(iface_name, "ethernet.speed"));
+        }
+        if eth_conf.duplex.is_some() {
+            return Err(not_supported(iface_name, "ethernet.duplex"));
+        }
+    }
+    if iface.base.identifier == Some(InterfaceIdentifier::MacAddress) {
+        if let Some(mac) = iface.base.mac_address.as_ref() {
+            np_iface.match_conf = Some(NetplanMatch {
+                macaddress: Some(mac.to_string()),
+            });
+            np_iface.set_name = Some(iface_name.to_string());
+        }
+    }
+    Ok(())
+}
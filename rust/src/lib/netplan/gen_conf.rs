@@ -0,0 +1,242 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    base_iface::gen_base_netplan_conf,
+    bond::gen_bond_netplan_conf,
+    conf::{NetplanConf, NetplanIface},
+    ethernet::gen_ethernet_netplan_conf,
+    ip::gen_ip_netplan_conf,
+    linux_bridge::gen_bridge_netplan_conf,
+    route::gen_route_netplan_conf,
+    vlan::gen_vlan_netplan_conf,
+    vxlan::gen_vxlan_netplan_conf,
+};
+use crate::{
+    nm::{
+        dns::{store_dns_config_to_iface, store_dns_search_or_option_to_iface},
+        route::store_route_config,
+        route_rule::store_route_rule_config,
+    },
+    ErrorKind, Interface, InterfaceType, MergedNetworkState, NmstateError,
+};
+
+const NETPLAN_CONF_FILE_NAME: &str = "90-nmstate.yaml";
+const LOOPBACK_IFACE_NAME: &str = "lo";
+
+// General work flow:
+//  * Reuse the NM plugin code to store routes, route rules and DNS into
+//    the `for_apply` interface holding them.
+//  * Convert each desired interface into netplan device definition under
+//    the section of its type.
+//  * Fill the `interfaces` list of controller and define the parent of
+//    VLAN and VXLAN if not desired, as netplan require every referred
+//    interface to be defined.
+pub(crate) fn netplan_gen_conf(
+    merged_state: &MergedNetworkState,
+) -> Result<Vec<(String, String)>, NmstateError> {
+    if merged_state
+        .hostname
+        .desired
+        .as_ref()
+        .and_then(|c| c.config.as_ref())
+        .is_some()
+    {
+        log::warn!(
+            "Cannot store hostname configuration to netplan, \
+            please edit /etc/hostname manually"
+        );
+    }
+
+    // The routes referring nexthop should not be expanded to plain routes
+    // silently.
+    if merged_state
+        .nexthops
+        .desired
+        .config
+        .iter()
+        .flatten()
+        .any(|nh| !nh.is_absent())
+        || !merged_state.routes.nexthop_routes.is_empty()
+    {
+        return Err(NmstateError::new(
+            ErrorKind::NotSupportedError,
+            "Nexthop objects and routes.nexthop-id are not supported by \
+            netplan"
+                .to_string(),
+        ));
+    }
+
+    let mut merged_state = merged_state.clone();
+    store_route_config(&mut merged_state)?;
+    store_route_rule_config(&mut merged_state)?;
+    if merged_state.dns.is_search_or_option_only() {
+        store_dns_search_or_option_to_iface(&mut merged_state, &[], &[])?;
+    } else {
+        store_dns_config_to_iface(&mut merged_state, &[], &[])?;
+    }
+
+    if let Some(iface) = merged_state
+        .interfaces
+        .user_ifaces
+        .values()
+        .filter_map(|i| i.for_apply.as_ref())
+        // The OVS bridges are stored into OVSDB configurations
+        .filter(|i| i.iface_type() != InterfaceType::OvsBridge)
+        .find(|i| i.is_up() || i.is_down())
+    {
+        return Err(not_supported_iface_type(iface));
+    }
+
+    let mut ifaces: Vec<&Interface> = merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter_map(|i| i.for_apply.as_ref())
+        .filter(|i| i.is_up() || i.is_down())
+        .collect();
+    // We should be persistent on output, hence sort by interface name.
+    ifaces.sort_unstable_by_key(|i| i.name());
+
+    let mut conf = NetplanConf::default();
+    for iface in ifaces.as_slice() {
+        let mut np_iface = NetplanIface::default();
+        gen_base_netplan_conf(iface.base_iface(), &mut np_iface)?;
+        gen_ip_netplan_conf(iface.base_iface(), &mut np_iface)?;
+        let devices = match iface {
+            Interface::Ethernet(iface) => {
+                gen_ethernet_netplan_conf(iface, &mut np_iface)?;
+                &mut conf.network.ethernets
+            }
+            // The OVS internal interface is created by OVS daemon, netplan
+            // only need to configure its IP.
+            Interface::Loopback(_) | Interface::OvsInterface(_) => {
+                &mut conf.network.ethernets
+            }
+            Interface::Bond(iface) => {
+                gen_bond_netplan_conf(iface, &mut np_iface)?;
+                &mut conf.network.bonds
+            }
+            Interface::LinuxBridge(iface) => {
+                gen_bridge_netplan_conf(iface, &mut np_iface)?;
+                &mut conf.network.bridges
+            }
+            Interface::Vlan(iface) => {
+                gen_vlan_netplan_conf(iface, &mut np_iface)?;
+                &mut conf.network.vlans
+            }
+            Interface::Vrf(iface) => {
+                if let Some(table_id) = iface.vrf.as_ref().map(|v| v.table_id) {
+                    if table_id == 0 {
+                        return Err(NmstateError::new(
+                            ErrorKind::NotSupportedError,
+                            format!(
+                                "Netplan cannot generate VRF {} without \
+                                route-table-id defined",
+                                iface.base.name
+                            ),
+                        ));
+                    }
+                    np_iface.table = Some(table_id);
+                }
+                &mut conf.network.vrfs
+            }
+            Interface::Vxlan(iface) => {
+                gen_vxlan_netplan_conf(iface, &mut np_iface)?;
+                &mut conf.network.tunnels
+            }
+            Interface::Dummy(_) => &mut conf.network.dummy_devices,
+            _ => {
+                return Err(not_supported_iface_type(iface));
+            }
+        };
+        devices.insert(iface.name().to_string(), np_iface);
+    }
+
+    // The blackhole, unreachable and prohibit routes are stored to loopback
+    // interface which might not be desired.
+    if !conf.network.contains_key(LOOPBACK_IFACE_NAME) {
+        if let Some(rts) = merged_state.routes.merged.get(LOOPBACK_IFACE_NAME) {
+            let mut np_iface = NetplanIface::default();
+            gen_route_netplan_conf(LOOPBACK_IFACE_NAME, rts, &mut np_iface)?;
+            conf.network
+                .ethernets
+                .insert(LOOPBACK_IFACE_NAME.to_string(), np_iface);
+        }
+    }
+
+    for iface in ifaces.as_slice() {
+        if let Some(ctrl_name) = iface
+            .base_iface()
+            .controller
+            .as_deref()
+            .filter(|c| !c.is_empty())
+            // The OVS bridge ports are attached by OVSDB configurations
+            .filter(|c| {
+                !merged_state
+                    .interfaces
+                    .user_ifaces
+                    .contains_key(&(c.to_string(), InterfaceType::OvsBridge))
+            })
+        {
+            let ctrl_type = merged_state
+                .interfaces
+                .kernel_ifaces
+                .get(ctrl_name)
+                .map(|i| i.merged.iface_type());
+            if !matches!(
+                ctrl_type,
+                Some(InterfaceType::Bond)
+                    | Some(InterfaceType::LinuxBridge)
+                    | Some(InterfaceType::Vrf)
+            ) {
+                return Err(NmstateError::new(
+                    ErrorKind::NotSupportedError,
+                    format!(
+                        "Controller {ctrl_name} of interface {} is not \
+                        supported by netplan",
+                        iface.name()
+                    ),
+                ));
+            }
+            if let Some(np_ctrl) = conf.network.get_mut(ctrl_name) {
+                np_ctrl.interfaces.push(iface.name().to_string());
+            }
+        }
+
+        let parent = match iface {
+            Interface::Vlan(vlan_iface) => {
+                vlan_iface.vlan.as_ref().map(|v| v.base_iface.as_str())
+            }
+            Interface::Vxlan(vxlan_iface) => {
+                vxlan_iface.vxlan.as_ref().map(|v| v.base_iface.as_str())
+            }
+            _ => continue,
+        };
+        if let Some(parent) = parent.filter(|p| !p.is_empty()) {
+            if !conf.network.contains_key(parent) {
+                conf.network
+                    .ethernets
+                    .insert(parent.to_string(), NetplanIface::default());
+            }
+        }
+    }
+
+    let content = serde_yaml::to_string(&conf).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::Bug,
+            format!("Failed to serialize netplan configuration {conf:?}: {e}"),
+        )
+    })?;
+    Ok(vec![(NETPLAN_CONF_FILE_NAME.to_string(), content)])
+}
+
+fn not_supported_iface_type(iface: &Interface) -> NmstateError {
+    NmstateError::new(
+        ErrorKind::NotSupportedError,
+        format!(
+            "Interface {} with type {} is not supported by netplan",
+            iface.name(),
+            iface.iface_type()
+        ),
+    )
+}
@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    base_iface::not_supported,
+    conf::{
+        NetplanDhcpOverrides, NetplanIface, NetplanNameservers,
+        NetplanRaOverrides,
+    },
+    route::gen_route_netplan_conf,
+    route_rule::gen_route_rule_netplan_conf,
+};
+use crate::{
+    BaseInterface, Dhcpv4ClientId, DnsClientState, InterfaceIpAddr,
+    Ipv6AddrGenMode, NmstateError,
+};
+
+pub(crate) fn gen_ip_netplan_conf(
+    base_iface: &BaseInterface,
+    np_iface: &mut NetplanIface,
+) -> Result<(), NmstateError> {
+    let iface_name = base_iface.name.as_str();
+    let ipv4 = base_iface.ipv4.as_ref().filter(|i| i.enabled);
+    let ipv6 = base_iface.ipv6.as_ref().filter(|i| i.enabled);
+
+    let dhcpv4 = ipv4.and_then(|i| i.dhcp) == Some(true);
+    let dhcpv6 = ipv6.and_then(|i| i.dhcp) == Some(true);
+    let autoconf = ipv6.and_then(|i| i.autoconf) == Some(true);
+
+    np_iface.dhcp4 = Some(dhcpv4);
+    np_iface.dhcp6 = Some(dhcpv6);
+    np_iface.accept_ra = Some(autoconf);
+    np_iface.link_local = Some(if ipv6.is_some() {
+        vec!["ipv6".to_string()]
+    } else {
+        Vec::new()
+    });
+
+    if let Some(ipv4) = ipv4 {
+        if let Some(addrs) = ipv4.addresses.as_deref() {
+            gen_addr_conf(addrs, np_iface);
+        }
+        if dhcpv4 {
+            match ipv4.dhcp_client_id.as_ref() {
+                Some(Dhcpv4ClientId::LinkLayerAddress) => {
+                    np_iface.dhcp_identifier = Some("mac".to_string());
+                }
+                Some(Dhcpv4ClientId::IaidPlusDuid) => {
+                    np_iface.dhcp_identifier = Some("duid".to_string());
+                }
+                Some(Dhcpv4ClientId::Other(_)) => {
+                    return Err(not_supported(
+                        iface_name,
+                        "ipv4.dhcp-client-id",
+                    ));
+                }
+                None => (),
+            }
+            // Netplan cannot ignore the DHCP gateway while using the DHCP
+            // routes.
+            if ipv4.auto_gateway.is_some()
+                && ipv4.auto_gateway != Some(ipv4.auto_routes.unwrap_or(true))
+            {
+                return Err(not_supported(iface_name, "ipv4.auto-gateway"));
+            }
+            if ipv4.auto_table_id.filter(|v| *v != 0).is_some() {
+                return Err(not_supported(
+                    iface_name,
+                    "ipv4.auto-route-table-id",
+                ));
+            }
+            np_iface.dhcp4_overrides = gen_dhcp_overrides(
+                ipv4.auto_dns,
+                ipv4.auto_routes,
+                ipv4.auto_route_metric,
+                ipv4.dhcp_send_hostname,
+                ipv4.dhcp_custom_hostname.as_ref(),
+            );
+        }
+    }
+
+    if let Some(ipv6) = ipv6 {
+        if let Some(addrs) = ipv6.addresses.as_deref() {
+            gen_addr_conf(addrs, np_iface);
+        }
+        match ipv6.addr_gen_mode.as_ref() {
+            Some(Ipv6AddrGenMode::Eui64) => {
+                np_iface.ipv6_address_generation = Some("eui64".to_string());
+            }
+            Some(Ipv6AddrGenMode::StablePrivacy) => {
+                np_iface.ipv6_address_generation =
+                    Some("stable-privacy".to_string());
+            }
+            Some(Ipv6AddrGenMode::Other(_)) => {
+                return Err(not_supported(iface_name, "ipv6.addr-gen-mode"));
+            }
+            None => (),
+        }
+        if dhcpv6 {
+            if ipv6.dhcp_duid.is_some() {
+                return Err(not_supported(iface_name, "ipv6.dhcp-duid"));
+            }
+            np_iface.dhcp6_overrides = gen_dhcp_overrides(
+                ipv6.auto_dns,
+                ipv6.auto_routes,
+                ipv6.auto_route_metric,
+                ipv6.dhcp_send_hostname,
+                ipv6.dhcp_custom_hostname.as_ref(),
+            );
+        }
+        if autoconf {
+            if ipv6.auto_gateway == Some(false) {
+                return Err(not_supported(iface_name, "ipv6.auto-gateway"));
+            }
+            if ipv6.auto_routes == Some(false) {
+                return Err(not_supported(iface_name, "ipv6.auto-routes"));
+            }
+            if !dhcpv6 && ipv6.auto_route_metric.is_some() {
+                return Err(not_supported(
+                    iface_name,
+                    "ipv6.auto-route-metric",
+                ));
+            }
+            let table = ipv6.auto_table_id.filter(|v| *v != 0);
+            if ipv6.auto_dns.is_some() || table.is_some() {
+                np_iface.ra_overrides = Some(NetplanRaOverrides {
+                    use_dns: ipv6.auto_dns,
+                    table,
+                });
+            }
+            np_iface.ipv6_address_token =
+                ipv6.token.clone().filter(|t| !t.is_empty());
+        }
+    }
+
+    let mut dns_confs: Vec<&DnsClientState> = ipv4
+        .and_then(|i| i.dns.as_ref())
+        .into_iter()
+        .chain(ipv6.and_then(|i| i.dns.as_ref()))
+        .collect();
+    // Smaller priority number means preferred
+    dns_confs.sort_by_key(|d| d.priority.unwrap_or_default());
+    for dns_conf in dns_confs {
+        gen_dns_conf(iface_name, dns_conf, np_iface)?;
+    }
+
+    if let Some(rts) = base_iface.routes.as_deref() {
+        gen_route_netplan_conf(iface_name, rts, np_iface)?;
+    }
+    for rules in [
+        ipv4.and_then(|i| i.rules.as_deref()),
+        ipv6.and_then(|i| i.rules.as_deref()),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        gen_route_rule_netplan_conf(iface_name, rules, np_iface)?;
+    }
+    Ok(())
+}
+
+fn gen_addr_conf(addrs: &[InterfaceIpAddr], np_iface: &mut NetplanIface) {
+    for addr in addrs {
+        np_iface
+            .addresses
+            .push(format!("{}/{}", addr.ip, addr.prefix_length));
+    }
+}
+
+fn gen_dhcp_overrides(
+    auto_dns: Option<bool>,
+    auto_routes: Option<bool>,
+    auto_route_metric: Option<u32>,
+    send_hostname: Option<bool>,
+    custom_hostname: Option<&String>,
+) -> Option<NetplanDhcpOverrides> {
+    let overrides = NetplanDhcpOverrides {
+        use_dns: auto_dns,
+        use_routes: auto_routes,
+        route_metric: auto_route_metric,
+        send_hostname,
+        hostname: custom_hostname.cloned(),
+    };
+    if overrides == NetplanDhcpOverrides::default() {
+        None
+    } else {
+        Some(overrides)
+    }
+}
+
+fn gen_dns_conf(
+    iface_name: &str,
+    dns_conf: &DnsClientState,
+    np_iface: &mut NetplanIface,
+) -> Result<(), NmstateError> {
+    if dns_conf.options.as_ref().map(|o| !o.is_empty()) == Some(true) {
+        return Err(not_supported(iface_name, "dns-resolver.options"));
+    }
+    let servers = dns_conf.server.as_deref().unwrap_or_default();
+    let searches = dns_conf.search.as_deref().unwrap_or_default();
+    if servers.is_empty() && searches.is_empty() {
+        return Ok(());
+    }
+    let nameservers = np_iface
+        .nameservers
+        .get_or_insert_with(NetplanNameservers::default);
+    // The IPv4 and IPv6 DNS configurations might hold the same search list.
+    for (dst, src) in [
+        (&mut nameservers.addresses, servers),
+        (&mut nameservers.search, searches),
+    ] {
+        for v in src {
+            if !dst.contains(v) {
+                dst.push(v.to_string());
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    base_iface::not_supported,
+    conf::{NetplanBridgeParameters, NetplanIface, NetplanParameters},
+};
+use crate::{LinuxBridgeInterface, NmstateError};
+
+pub(crate) fn gen_bridge_netplan_conf(
+    iface: &LinuxBridgeInterface,
+    np_iface: &mut NetplanIface,
+) -> Result<(), NmstateError> {
+    let iface_name = iface.base.name.as_str();
+    if iface.vlan_filtering_is_enabled() {
+        return Err(not_supported(iface_name, "bridge.port.vlan"));
+    }
+    let br_conf = if let Some(c) = iface.bridge.as_ref() {
+        c
+    } else {
+        return Ok(());
+    };
+    let mut params = NetplanBridgeParameters::default();
+
+    if let Some(opts) = br_conf.options.as_ref() {
+        for (prop, is_set) in [
+            ("group-addr", opts.group_addr.is_some()),
+            (
+                "group-forward-mask",
+                opts.group_fwd_mask.or(opts.group_forward_mask).is_some(),
+            ),
+            ("hash-max", opts.hash_max.is_some()),
+            (
+                "multicast-last-member-count",
+                opts.multicast_last_member_count.is_some(),
+            ),
+            (
+                "multicast-last-member-interval",
+                opts.multicast_last_member_interval.is_some(),
+            ),
+            (
+                "multicast-membership-interval",
+                opts.multicast_membership_interval.is_some(),
+            ),
+            ("multicast-querier", opts.multicast_querier.is_some()),
+            (
+                "multicast-querier-interval",
+                opts.multicast_querier_interval.is_some(),
+            ),
+            (
+                "multicast-query-interval",
+                opts.multicast_query_interval.is_some(),
+            ),
+            (
+                "multicast-query-response-interval",
+                opts.multicast_query_response_interval.is_some(),
+            ),
+            (
+                "multicast-query-use-ifaddr",
+                opts.multicast_query_use_ifaddr.is_some(),
+            ),
+            ("multicast-router", opts.multicast_router.is_some()),
+            ("multicast-snooping", opts.multicast_snooping.is_some()),
+            (
+                "multicast-startup-query-count",
+                opts.multicast_startup_query_count.is_some(),
+            ),
+            (
+                "multicast-startup-query-interval",
+                opts.multicast_startup_query_interval.is_some(),
+            ),
+            ("vlan-protocol", opts.vlan_protocol.is_some()),
+            ("vlan-default-pvid", opts.vlan_default_pvid.is_some()),
+        ] {
+            if is_set {
+                return Err(not_supported(
+                    iface_name,
+                    &format!("bridge.options.{prop}"),
+                ));
+            }
+        }
+        params.ageing_time = opts.mac_ageing_time;
+        if let Some(stp_opts) = opts.stp.as_ref() {
+            params.stp = stp_opts.enabled;
+            params.forward_delay = stp_opts.forward_delay;
+            params.hello_time = stp_opts.hello_time;
+            params.max_age = stp_opts.max_age;
+            params.priority = stp_opts.priority;
+        }
+    }
+
+    for port_conf in br_conf.port.as_deref().unwrap_or_default() {
+        if port_conf.stp_hairpin_mode.is_some() {
+            return Err(not_supported(
+                port_conf.name.as_str(),
+                "bridge.port.stp-hairpin-mode",
+            ));
+        }
+        if let Some(v) = port_conf.stp_priority {
+            params.port_priority.insert(port_conf.name.clone(), v);
+        }
+        if let Some(v) = port_conf.stp_path_cost {
+            params.path_cost.insert(port_conf.name.clone(), v);
+        }
+    }
+    if params != NetplanBridgeParameters::default() {
+        np_iface.parameters = Some(NetplanParameters::Bridge(params));
+    }
+    Ok(())
+}
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+
+mod base_iface;
+mod bond;
+mod conf;
+mod ethernet;
+mod gen_conf;
+mod ip;
+mod linux_bridge;
+mod route;
+mod route_rule;
+mod vlan;
+mod vxlan;
+
+pub(crate) use gen_conf::netplan_gen_conf;
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    base_iface::not_supported,
+    conf::{NetplanIface, NetplanRoute},
+};
+use crate::{NmstateError, RouteEntry};
+
+pub(crate) fn gen_route_netplan_conf(
+    iface_name: &str,
+    routes: &[RouteEntry],
+    np_iface: &mut NetplanIface,
+) -> Result<(), NmstateError> {
+    for route in routes.iter().filter(|r| !r.is_absent()) {
+        if route.weight.is_some() {
+            return Err(not_supported(iface_name, "routes.weight"));
+        }
+        if route.cwnd.is_some() {
+            return Err(not_supported(iface_name, "routes.cwnd"));
+        }
+        for (prop, is_set) in [
+            ("source", route.source.is_some()),
+            ("mtu", route.mtu.is_some()),
+            ("lock-mtu", route.lock_mtu.is_some()),
+            ("advmss", route.advmss.is_some()),
+            ("initcwnd", route.initcwnd.is_some()),
+            ("initrwnd", route.initrwnd.is_some()),
+            ("quickack", route.quickack.is_some()),
+            ("onlink", route.onlink.is_some()),
+            ("scope", route.scope.is_some()),
+            ("protocol", route.protocol.is_some()),
+            ("nexthop-id", route.nexthop_id.is_some()),
+        ] {
+            if is_set {
+                return Err(not_supported(
+                    iface_name,
+                    &format!("routes.{prop}"),
+                ));
+            }
+        }
+        let dst = if let Some(d) = route.destination.as_ref() {
+            d
+        } else {
+            continue;
+        };
+        let via = route
+            .next_hop_addr
+            .as_ref()
+            .filter(|g| !g.is_empty())
+            .cloned();
+        // Netplan requires gateway for unicast route in global scope.
+        let scope = if via.is_none() && route.route_type.is_none() {
+            Some("link".to_string())
+        } else {
+            None
+        };
+        np_iface.routes.push(NetplanRoute {
+            to: if dst == "0.0.0.0/0" || dst == "::/0" {
+                "default".to_string()
+            } else {
+                dst.to_string()
+            },
+            via,
+            scope,
+            metric: route
+                .metric
+                .filter(|m| *m != RouteEntry::USE_DEFAULT_METRIC)
+                .map(|m| m as u32),
+            table: route
+                .table_id
+                .filter(|t| *t != RouteEntry::USE_DEFAULT_ROUTE_TABLE),
+            route_type: route.route_type.map(|t| t.to_string()),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    base_iface::not_supported,
+    conf::{NetplanIface, NetplanRoutingPolicy},
+};
+use crate::{NmstateError, RouteRuleEntry};
+
+pub(crate) fn gen_route_rule_netplan_conf(
+    iface_name: &str,
+    rules: &[RouteRuleEntry],
+    np_iface: &mut NetplanIface,
+) -> Result<(), NmstateError> {
+    for rule in rules.iter().filter(|r| !r.is_absent()) {
+        if rule.fwmask.is_some() {
+            return Err(not_supported(iface_name, "route-rules.fwmask"));
+        }
+        if rule.iif.is_some() {
+            return Err(not_supported(iface_name, "route-rules.iif"));
+        }
+        if rule.suppress_prefix_length.is_some() {
+            return Err(not_supported(
+                iface_name,
+                "route-rules.suppress-prefix-length",
+            ));
+        }
+        if rule.action.is_some() {
+            return Err(not_supported(iface_name, "route-rules.action"));
+        }
+        for (prop, is_set) in [
+            ("sport", rule.sport.is_some()),
+            ("dport", rule.dport.is_some()),
+            ("ipproto", rule.ipproto.is_some()),
+            ("uidrange", rule.uidrange.is_some()),
+            ("oif", rule.oif.is_some()),
+            ("l3mdev", rule.l3mdev.is_some()),
+            ("dscp", rule.dscp.is_some()),
+        ] {
+            if is_set {
+                return Err(not_supported(
+                    iface_name,
+                    &format!("route-rules.{prop}"),
+                ));
+            }
+        }
+        let from = rule.ip_from.as_ref().filter(|i| !i.is_empty()).cloned();
+        let to = rule.ip_to.as_ref().filter(|i| !i.is_empty()).cloned();
+        // Netplan cannot tell the address family of route rule without
+        // `from` or `to`.
+        if from.is_none() && to.is_none() {
+            return Err(not_supported(
+                iface_name,
+                "route-rules without ip-from or ip-to",
+            ));
+        }
+        np_iface.routing_policy.push(NetplanRoutingPolicy {
+            from,
+            to,
+            table: rule
+                .table_id
+                .filter(|t| *t != RouteRuleEntry::USE_DEFAULT_ROUTE_TABLE),
+            priority: rule
+                .priority
+                .filter(|p| *p != RouteRuleEntry::USE_DEFAULT_PRIORITY)
+                .map(|p| p as u32),
+            mark: rule.fwmark,
+            type_of_service: rule.tos,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{base_iface::not_supported, conf::NetplanIface};
+use crate::{
+    NmstateError, VlanInterface, VlanProtocol, VlanRegistrationProtocol,
+};
+
+pub(crate) fn gen_vlan_netplan_conf(
+    iface: &VlanInterface,
+    np_iface: &mut NetplanIface,
+) -> Result<(), NmstateError> {
+    let iface_name = iface.base.name.as_str();
+    if let Some(vlan_conf) = iface.vlan.as_ref() {
+        if vlan_conf.protocol == Some(VlanProtocol::Ieee8021Ad) {
+            return Err(not_supported(iface_name, "vlan.protocol"));
+        }
+        if matches!(
+            vlan_conf.registration_protocol,
+            Some(VlanRegistrationProtocol::Gvrp)
+                | Some(VlanRegistrationProtocol::Mvrp)
+        ) {
+            return Err(not_supported(
+                iface_name,
+                "vlan.registration-protocol",
+            ));
+        }
+        if vlan_conf.reorder_headers == Some(false) {
+            return Err(not_supported(iface_name, "vlan.reorder-headers"));
+        }
+        if vlan_conf.loose_binding == Some(true) {
+            return Err(not_supported(iface_name, "vlan.loose-binding"));
+        }
+        np_iface.id = Some(vlan_conf.id.into());
+        np_iface.link = Some(vlan_conf.base_iface.clone());
+    }
+    Ok(())
+}
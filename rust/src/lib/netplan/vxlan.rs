@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::conf::NetplanIface;
+use crate::{NmstateError, VxlanInterface};
+
+pub(crate) fn gen_vxlan_netplan_conf(
+    iface: &VxlanInterface,
+    np_iface: &mut NetplanIface,
+) -> Result<(), NmstateError> {
+    np_iface.mode = Some("vxlan".to_string());
+    if let Some(vxlan_conf) = iface.vxlan.as_ref() {
+        np_iface.id = Some(vxlan_conf.id);
+        np_iface.local = vxlan_conf.local.map(|v| v.to_string());
+        np_iface.remote = vxlan_conf.remote.map(|v| v.to_string());
+        np_iface.port = vxlan_conf.dst_port;
+        np_iface.mac_learning = vxlan_conf.learning;
+        if !vxlan_conf.base_iface.is_empty() {
+            np_iface.link = Some(vxlan_conf.base_iface.clone());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,16 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::conf::NetworkdConf;
+use crate::{BareUdpInterface, NmstateError};
+
+pub(crate) fn gen_bareudp_netdev_conf(
+    iface: &BareUdpInterface,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    if let Some(bareudp_conf) = iface.bareudp.as_ref() {
+        conf.section("BareUDP")
+            .set("DestinationPort", bareudp_conf.dst_port)
+            .set("EtherType", bareudp_conf.ethertype);
+    }
+    Ok(())
+}
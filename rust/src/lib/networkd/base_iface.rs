@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::conf::NetworkdConf;
+use crate::{
+    BaseInterface, ErrorKind, InterfaceIdentifier, InterfaceState,
+    NmstateError, WaitIp,
+};
+
+pub(crate) fn not_supported(iface_name: &str, prop: &str) -> NmstateError {
+    NmstateError::new(
+        ErrorKind::NotSupportedError,
+        format!(
+            "Property {prop} of interface {iface_name} is not supported by \
+            systemd-networkd"
+        ),
+    )
+}
+
+pub(crate) fn gen_base_network_conf(
+    base_iface: &BaseInterface,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    let iface_name = base_iface.name.as_str();
+    if base_iface.ovsdb.is_some() {
+        return Err(not_supported(iface_name, "ovs-db"));
+    }
+    if base_iface.ieee8021x.is_some() {
+        return Err(not_supported(iface_name, "802.1x"));
+    }
+    if base_iface.ethtool.is_some() {
+        return Err(not_supported(iface_name, "ethtool"));
+    }
+    if base_iface
+        .mptcp
+        .as_ref()
+        .and_then(|m| m.address_flags.as_ref())
+        .map(|f| !f.is_empty())
+        == Some(true)
+    {
+        return Err(not_supported(iface_name, "mptcp.address-flags"));
+    }
+
+    conf.section("Match").set("Name", iface_name);
+
+    let link_section = conf.section("Link");
+    // The MAC address is used for matching the interface in `.link` file
+    // when identifier is `mac-address`.
+    if base_iface.identifier != Some(InterfaceIdentifier::MacAddress) {
+        if let Some(mac) = base_iface.mac_address.as_ref() {
+            link_section.set("MACAddress", mac);
+        }
+    }
+    if let Some(mtu) = base_iface.mtu {
+        link_section.set("MTUBytes", mtu);
+    }
+    if let Some(v) = base_iface.accept_all_mac_addresses {
+        link_section.set_bool("Promiscuous", v);
+    }
+    if base_iface.state == InterfaceState::Down {
+        link_section.set("ActivationPolicy", "down");
+    }
+    if let Some(wait_ip) = base_iface.wait_ip.as_ref() {
+        link_section.set(
+            "RequiredFamilyForOnline",
+            match wait_ip {
+                WaitIp::Any => "any",
+                WaitIp::Ipv4 => "ipv4",
+                WaitIp::Ipv6 => "ipv6",
+                WaitIp::Ipv4AndIpv6 => "both",
+            },
+        );
+    }
+
+    let network_section = conf.section("Network");
+    if let Some(desc) = base_iface.description.as_ref() {
+        network_section.set("Description", desc);
+    }
+    if let Some(lldp_conf) = base_iface.lldp.as_ref() {
+        network_section.set_bool("LLDP", lldp_conf.enabled);
+        network_section.set_bool("EmitLLDP", lldp_conf.enabled);
+    }
+    Ok(())
+}
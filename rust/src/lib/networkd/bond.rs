@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{base_iface::not_supported, conf::NetworkdConf};
+use crate::{BondAllPortsActive, BondArpValidate, BondInterface, NmstateError};
+
+pub(crate) fn gen_bond_netdev_conf(
+    iface: &BondInterface,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    let iface_name = iface.base.name.as_str();
+    let bond_conf = if let Some(c) = iface.bond.as_ref() {
+        c
+    } else {
+        return Ok(());
+    };
+    let section = conf.section("Bond");
+    if let Some(mode) = bond_conf.mode.as_ref() {
+        section.set("Mode", mode);
+    }
+    let opts = if let Some(o) = bond_conf.options.as_ref() {
+        o
+    } else {
+        return Ok(());
+    };
+
+    if opts.use_carrier.is_some() {
+        return Err(not_supported(
+            iface_name,
+            "link-aggregation.options.use_carrier",
+        ));
+    }
+    if opts.balance_slb.is_some() {
+        return Err(not_supported(
+            iface_name,
+            "link-aggregation.options.balance_slb",
+        ));
+    }
+    if opts.arp_missed_max.is_some() {
+        return Err(not_supported(
+            iface_name,
+            "link-aggregation.options.arp_missed_max",
+        ));
+    }
+    if opts.num_unsol_na.is_some() && opts.num_unsol_na != opts.num_grat_arp {
+        return Err(not_supported(
+            iface_name,
+            "link-aggregation.options.num_unsol_na",
+        ));
+    }
+
+    if let Some(v) = opts.ad_actor_sys_prio.as_ref() {
+        section.set("AdActorSystemPriority", v);
+    }
+    if let Some(v) = opts.ad_actor_system.as_ref() {
+        section.set("AdActorSystem", v);
+    }
+    if let Some(v) = opts.ad_select.as_ref() {
+        section.set("AdSelect", v);
+    }
+    if let Some(v) = opts.ad_user_port_key.as_ref() {
+        section.set("AdUserPortKey", v);
+    }
+    if let Some(v) = opts.all_slaves_active.as_ref() {
+        section
+            .set_bool("AllSlavesActive", v == &BondAllPortsActive::Delivered);
+    }
+    if let Some(v) = opts.arp_all_targets.as_ref() {
+        section.set("ARPAllTargets", v);
+    }
+    if let Some(v) = opts.arp_interval.as_ref() {
+        section.set("ARPIntervalSec", format!("{v}ms"));
+    }
+    if let Some(v) = opts.arp_ip_target.as_ref().filter(|v| !v.is_empty()) {
+        section.set("ARPIPTargets", v.split(',').collect::<Vec<_>>().join(" "));
+    }
+    if let Some(v) = opts.arp_validate.as_ref() {
+        if matches!(
+            v,
+            BondArpValidate::Filter
+                | BondArpValidate::FilterActive
+                | BondArpValidate::FilterBackup
+        ) {
+            return Err(not_supported(
+                iface_name,
+                &format!("link-aggregation.options.arp_validate: {v}"),
+            ));
+        }
+        section.set("ARPValidate", v);
+    }
+    if let Some(v) = opts.downdelay.as_ref() {
+        section.set("DownDelaySec", format!("{v}ms"));
+    }
+    if let Some(v) = opts.fail_over_mac.as_ref() {
+        section.set("FailOverMACPolicy", v);
+    }
+    if let Some(v) = opts.lacp_rate.as_ref() {
+        section.set("LACPTransmitRate", v);
+    }
+    if let Some(v) = opts.lp_interval.as_ref() {
+        section.set("LearnPacketIntervalSec", v);
+    }
+    if let Some(v) = opts.miimon.as_ref() {
+        section.set("MIIMonitorSec", format!("{v}ms"));
+    }
+    if let Some(v) = opts.min_links.as_ref() {
+        section.set("MinLinks", v);
+    }
+    if let Some(v) = opts.num_grat_arp.as_ref() {
+        section.set("GratuitousARP", v);
+    }
+    if let Some(v) = opts.packets_per_slave.as_ref() {
+        section.set("PacketsPerSlave", v);
+    }
+    if let Some(v) = opts.primary_reselect.as_ref() {
+        section.set("PrimaryReselectPolicy", v);
+    }
+    if let Some(v) = opts.resend_igmp.as_ref() {
+        section.set("ResendIGMP", v);
+    }
+    if let Some(v) = opts.tlb_dynamic_lb.as_ref() {
+        section.set_bool("DynamicTransmitLoadBalancing", *v);
+    }
+    if let Some(v) = opts.updelay.as_ref() {
+        section.set("UpDelaySec", format!("{v}ms"));
+    }
+    if let Some(v) = opts.xmit_hash_policy.as_ref() {
+        section.set("TransmitHashPolicy", v);
+    }
+    Ok(())
+}
+
+pub(crate) fn gen_bond_port_conf(
+    bond_iface: &BondInterface,
+    port_name: &str,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    let bond_conf = bond_iface.bond.as_ref();
+    if let Some(port_conf) = bond_conf
+        .and_then(|b| b.ports_config.as_ref())
+        .and_then(|p| p.iter().find(|p| p.name == port_name))
+    {
+        if port_conf.priority.is_some() {
+            return Err(not_supported(
+                port_name,
+                "link-aggregation.ports-config.priority",
+            ));
+        }
+        if port_conf.queue_id.is_some() {
+            return Err(not_supported(
+                port_name,
+                "link-aggregation.ports-config.queue-id",
+            ));
+        }
+    }
+    let section = conf.section("Network");
+    section.set("Bond", bond_iface.base.name.as_str());
+    if bond_conf
+        .and_then(|b| b.options.as_ref())
+        .and_then(|o| o.primary.as_deref())
+        == Some(port_name)
+    {
+        section.set_bool("PrimarySlave", true);
+    }
+    Ok(())
+}
@@ -0,0 +1,86 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// The systemd-networkd configuration files are INI style with section allowed
+// to be repeated (e.g. `[Address]`, `[Route]`), hence we store sections in
+// vector to preserve the order and duplication.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct NetworkdConf {
+    sections: Vec<NetworkdSection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NetworkdSection {
+    name: &'static str,
+    entries: Vec<(&'static str, String)>,
+}
+
+impl NetworkdSection {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            entries: Vec::new(),
+        }
+    }
+
+    pub(crate) fn set<T: ToString>(
+        &mut self,
+        key: &'static str,
+        value: T,
+    ) -> &mut Self {
+        self.entries.push((key, value.to_string()));
+        self
+    }
+
+    pub(crate) fn set_bool(
+        &mut self,
+        key: &'static str,
+        value: bool,
+    ) -> &mut Self {
+        self.set(key, if value { "yes" } else { "no" })
+    }
+}
+
+impl NetworkdConf {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    // Get the first section with specified name, create if not exist.
+    pub(crate) fn section(
+        &mut self,
+        name: &'static str,
+    ) -> &mut NetworkdSection {
+        let index = match self.sections.iter().position(|s| s.name == name) {
+            Some(i) => i,
+            None => {
+                self.sections.push(NetworkdSection::new(name));
+                self.sections.len() - 1
+            }
+        };
+        &mut self.sections[index]
+    }
+
+    // Append new section even section with the same name exists.
+    pub(crate) fn new_section(
+        &mut self,
+        name: &'static str,
+    ) -> &mut NetworkdSection {
+        self.sections.push(NetworkdSection::new(name));
+        let index = self.sections.len() - 1;
+        &mut self.sections[index]
+    }
+}
+
+impl std::fmt::Display for NetworkdConf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut sections = Vec::new();
+        for section in self.sections.iter().filter(|s| !s.entries.is_empty()) {
+            let mut lines = vec![format!("[{}]", section.name)];
+            for (key, value) in section.entries.as_slice() {
+                lines.push(format!("{key}={value}"));
+            }
+            sections.push(lines.join("\n"));
+        }
+        writeln!(f, "{}", sections.join("\n\n"))
+    }
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{base_iface::not_supported, conf::NetworkdConf};
+use crate::{EthernetInterface, InterfaceIdentifier, NmstateError};
+
+// The udev level settings like interface name and link speed are stored
+// in `.link` file, return None if not required.
+pub(crate) fn gen_ethernet_link_conf(
+    iface: &EthernetInterface,
+) -> Result<Option<NetworkdConf>, NmstateError> {
+    let iface_name = iface.base.name.as_str();
+    if iface.veth.is_some() {
+        return Err(not_supported(iface_name, "veth"));
+    }
+    if iface
+        .ethernet
+        .as_ref()
+        .and_then(|e| e.sr_iov.as_ref())
+        .is_some()
+    {
+        return Err(not_supported(iface_name, "ethernet.sr-iov"));
+    }
+
+    let mut conf = NetworkdConf::new();
+    let mac_identifier =
+        if iface.base.identifier == Some(InterfaceIdentifier::MacAddress) {
+            iface.base.mac_address.as_deref()
+        } else {
+            None
+        };
+    if let Some(mac) = mac_identifier {
+        conf.section("Match").set("PermanentMACAddress", mac);
+        conf.section("Link").set("Name", iface_name);
+    } else {
+        conf.section("Match").set("OriginalName", iface_name);
+    }
+
+    let mut has_eth_conf = false;
+    if let Some(eth_conf) = iface.ethernet.as_ref() {
+        let section = conf.section("Link");
+        if let Some(v) = eth_conf.auto_neg {
+            section.set_bool("AutoNegotiation", v);
+            has_eth_conf = true;
+        }
+        if let Some(v) = eth_conf.speed {
+            section.set("BitsPerSecond", format!("{v}M"));
+            has_eth_conf = true;
+        }
+        if let Some(v) = eth_conf.duplex {
+            section.set("Duplex", v);
+            has_eth_conf = true;
+        }
+    }
+
+    if mac_identifier.is_some() || has_eth_conf {
+        Ok(Some(conf))
+    } else {
+        Ok(None)
+    }
+}
@@ -0,0 +1,280 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use super::{
+    bareudp::gen_bareudp_netdev_conf,
+    base_iface::gen_base_network_conf,
+    bond::{gen_bond_netdev_conf, gen_bond_port_conf},
+    conf::NetworkdConf,
+    ethernet::gen_ethernet_link_conf,
+    geneve::gen_geneve_netdev_conf,
+    ip::gen_ip_network_conf,
+    linux_bridge::{gen_bridge_netdev_conf, gen_bridge_port_conf},
+    route::gen_route_network_conf,
+    vlan::gen_vlan_netdev_conf,
+    vrf::gen_vrf_netdev_conf,
+    vxlan::gen_vxlan_netdev_conf,
+};
+use crate::{
+    nm::{
+        dns::{store_dns_config_to_iface, store_dns_search_or_option_to_iface},
+        route::store_route_config,
+        route_rule::store_route_rule_config,
+    },
+    ErrorKind, Interface, InterfaceType, MergedNetworkState, NmstateError,
+};
+
+const NETWORKD_CONF_PREFIX: &str = "10-";
+const LOOPBACK_IFACE_NAME: &str = "lo";
+
+// General work flow:
+//  * Reuse the NM plugin code to store routes, route rules and DNS into
+//    the `for_apply` interface holding them.
+//  * Generate `.netdev` for virtual interfaces, `.link` for ethernet needing
+//    udev level settings and `.network` for every desired interface.
+//  * Append port, VLAN and VXLAN settings to the `.network` of port or
+//    parent interface as systemd-networkd require.
+pub(crate) fn networkd_gen_conf(
+    merged_state: &MergedNetworkState,
+) -> Result<Vec<(String, String)>, NmstateError> {
+    if merged_state
+        .hostname
+        .desired
+        .as_ref()
+        .and_then(|c| c.config.as_ref())
+        .is_some()
+    {
+        log::warn!(
+            "Cannot store hostname configuration to systemd-networkd, \
+            please edit /etc/hostname manually"
+        );
+    }
+
+    // The routes referring nexthop should not be expanded to plain routes
+    // silently.
+    if merged_state
+        .nexthops
+        .desired
+        .config
+        .iter()
+        .flatten()
+        .any(|nh| !nh.is_absent())
+        || !merged_state.routes.nexthop_routes.is_empty()
+    {
+        return Err(NmstateError::new(
+            ErrorKind::NotSupportedError,
+            "Nexthop objects and routes.nexthop-id are not supported by \
+            systemd-networkd"
+                .to_string(),
+        ));
+    }
+
+    let mut merged_state = merged_state.clone();
+    store_route_config(&mut merged_state)?;
+    store_route_rule_config(&mut merged_state)?;
+    if merged_state.dns.is_search_or_option_only() {
+        store_dns_search_or_option_to_iface(&mut merged_state, &[], &[])?;
+    } else {
+        store_dns_config_to_iface(&mut merged_state, &[], &[])?;
+    }
+
+    if let Some(iface) = merged_state
+        .interfaces
+        .user_ifaces
+        .values()
+        .filter_map(|i| i.for_apply.as_ref())
+        // The OVS bridges are stored into OVSDB configurations
+        .filter(|i| i.iface_type() != InterfaceType::OvsBridge)
+        .find(|i| i.is_up() || i.is_down())
+    {
+        return Err(NmstateError::new(
+            ErrorKind::NotSupportedError,
+            format!(
+                "Interface {} with type {} is not supported by \
+                systemd-networkd",
+                iface.name(),
+                iface.iface_type()
+            ),
+        ));
+    }
+
+    let mut ifaces: Vec<&Interface> = merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter_map(|i| i.for_apply.as_ref())
+        .filter(|i| i.is_up() || i.is_down())
+        .collect();
+    // We should be persistent on output, hence sort by interface name.
+    ifaces.sort_unstable_by_key(|i| i.name());
+
+    let mut ret = Vec::new();
+    let mut network_confs: BTreeMap<String, NetworkdConf> = BTreeMap::new();
+
+    for iface in ifaces.as_slice() {
+        match iface {
+            Interface::Ethernet(eth_iface) => {
+                if let Some(link_conf) = gen_ethernet_link_conf(eth_iface)? {
+                    ret.push((
+                        format!("{NETWORKD_CONF_PREFIX}{}.link", iface.name()),
+                        link_conf.to_string(),
+                    ));
+                }
+            }
+            // The OVS internal interface is created by OVS daemon.
+            Interface::Loopback(_) | Interface::OvsInterface(_) => (),
+            _ => {
+                ret.push((
+                    format!("{NETWORKD_CONF_PREFIX}{}.netdev", iface.name()),
+                    gen_netdev_conf(iface)?.to_string(),
+                ));
+            }
+        }
+        let mut network_conf = NetworkdConf::new();
+        gen_base_network_conf(iface.base_iface(), &mut network_conf)?;
+        gen_ip_network_conf(iface.base_iface(), &mut network_conf)?;
+        network_confs.insert(iface.name().to_string(), network_conf);
+    }
+
+    // The blackhole, unreachable and prohibit routes are stored to loopback
+    // interface which might not be desired.
+    if !network_confs.contains_key(LOOPBACK_IFACE_NAME) {
+        if let Some(rts) = merged_state.routes.merged.get(LOOPBACK_IFACE_NAME) {
+            let mut network_conf = new_network_conf(LOOPBACK_IFACE_NAME);
+            gen_route_network_conf(
+                LOOPBACK_IFACE_NAME,
+                rts,
+                &mut network_conf,
+            )?;
+            network_confs.insert(LOOPBACK_IFACE_NAME.to_string(), network_conf);
+        }
+    }
+
+    for iface in ifaces.as_slice() {
+        gen_port_network_conf(&merged_state, iface, &mut network_confs)?;
+        let (parent, key) = match iface {
+            Interface::Vlan(vlan_iface) => (
+                vlan_iface.vlan.as_ref().map(|v| v.base_iface.as_str()),
+                "VLAN",
+            ),
+            Interface::Vxlan(vxlan_iface) => (
+                vxlan_iface.vxlan.as_ref().map(|v| v.base_iface.as_str()),
+                "VXLAN",
+            ),
+            _ => continue,
+        };
+        if let Some(parent) = parent.filter(|p| !p.is_empty()) {
+            network_confs
+                .entry(parent.to_string())
+                .or_insert_with(|| new_network_conf(parent))
+                .section("Network")
+                .set(key, iface.name());
+        }
+    }
+
+    for (iface_name, network_conf) in network_confs {
+        ret.push((
+            format!("{NETWORKD_CONF_PREFIX}{iface_name}.network"),
+            network_conf.to_string(),
+        ));
+    }
+    Ok(ret)
+}
+
+fn new_network_conf(iface_name: &str) -> NetworkdConf {
+    let mut conf = NetworkdConf::new();
+    conf.section("Match").set("Name", iface_name);
+    conf
+}
+
+fn gen_netdev_conf(iface: &Interface) -> Result<NetworkdConf, NmstateError> {
+    let kind = match iface {
+        Interface::Bond(_) => "bond",
+        Interface::LinuxBridge(_) => "bridge",
+        Interface::Vlan(_) => "vlan",
+        Interface::Vrf(_) => "vrf",
+        Interface::Vxlan(_) => "vxlan",
+        Interface::Dummy(_) => "dummy",
+        Interface::Geneve(_) => "geneve",
+        Interface::BareUdp(_) => "bareudp",
+        _ => {
+            return Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Interface {} with type {} is not supported by \
+                    systemd-networkd",
+                    iface.name(),
+                    iface.iface_type()
+                ),
+            ));
+        }
+    };
+    let mut conf = NetworkdConf::new();
+    conf.section("NetDev")
+        .set("Name", iface.name())
+        .set("Kind", kind);
+
+    match iface {
+        Interface::Bond(iface) => gen_bond_netdev_conf(iface, &mut conf)?,
+        Interface::LinuxBridge(iface) => {
+            gen_bridge_netdev_conf(iface, &mut conf)?
+        }
+        Interface::Vlan(iface) => gen_vlan_netdev_conf(iface, &mut conf)?,
+        Interface::Vrf(iface) => gen_vrf_netdev_conf(iface, &mut conf)?,
+        Interface::Vxlan(iface) => gen_vxlan_netdev_conf(iface, &mut conf)?,
+        Interface::Geneve(iface) => gen_geneve_netdev_conf(iface, &mut conf)?,
+        Interface::BareUdp(iface) => gen_bareudp_netdev_conf(iface, &mut conf)?,
+        _ => (),
+    }
+    Ok(conf)
+}
+
+fn gen_port_network_conf(
+    merged_state: &MergedNetworkState,
+    iface: &Interface,
+    network_confs: &mut BTreeMap<String, NetworkdConf>,
+) -> Result<(), NmstateError> {
+    let ctrl_name = match iface.base_iface().controller.as_deref() {
+        Some(c) if !c.is_empty() => c,
+        _ => return Ok(()),
+    };
+    // The OVS bridge ports are attached by OVSDB configurations
+    if merged_state
+        .interfaces
+        .user_ifaces
+        .contains_key(&(ctrl_name.to_string(), InterfaceType::OvsBridge))
+    {
+        return Ok(());
+    }
+    let conf = if let Some(c) = network_confs.get_mut(iface.name()) {
+        c
+    } else {
+        return Ok(());
+    };
+    match merged_state
+        .interfaces
+        .kernel_ifaces
+        .get(ctrl_name)
+        .map(|i| &i.merged)
+    {
+        Some(Interface::Bond(bond_iface)) => {
+            gen_bond_port_conf(bond_iface, iface.name(), conf)
+        }
+        Some(Interface::LinuxBridge(br_iface)) => {
+            gen_bridge_port_conf(br_iface, iface.name(), conf)
+        }
+        Some(Interface::Vrf(_)) => {
+            conf.section("Network").set("VRF", ctrl_name);
+            Ok(())
+        }
+        _ => Err(NmstateError::new(
+            ErrorKind::NotSupportedError,
+            format!(
+                "Controller {ctrl_name} of interface {} is not supported \
+                by systemd-networkd",
+                iface.name()
+            ),
+        )),
+    }
+}
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::conf::NetworkdConf;
+use crate::{GeneveDf, GeneveInterface, NmstateError};
+
+pub(crate) fn gen_geneve_netdev_conf(
+    iface: &GeneveInterface,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    if let Some(geneve_conf) = iface.geneve.as_ref() {
+        let section = conf.section("GENEVE");
+        section.set("Id", geneve_conf.id);
+        if let Some(v) = geneve_conf.remote {
+            section.set("Remote", v);
+        }
+        if let Some(v) = geneve_conf.dst_port {
+            section.set("DestinationPort", v);
+        }
+        if let Some(v) = geneve_conf.ttl {
+            section.set("TTL", v);
+        }
+        if let Some(v) = geneve_conf.tos {
+            section.set("TOS", v);
+        }
+        if let Some(v) = geneve_conf.df {
+            section.set(
+                "IPDoNotFragment",
+                match v {
+                    GeneveDf::Unset => "no",
+                    GeneveDf::Set => "yes",
+                    GeneveDf::Inherit => "inherit",
+                },
+            );
+        }
+        if let Some(v) = geneve_conf.udp_checksum {
+            section.set_bool("UDPChecksum", v);
+            // systemd-networkd is using dedicated options for IPv6
+            if geneve_conf.remote.map(|ip| ip.is_ipv6()) == Some(true) {
+                section.set_bool("UDP6ZeroChecksumTx", !v);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,210 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    base_iface::not_supported, conf::NetworkdConf,
+    route::gen_route_network_conf, route_rule::gen_route_rule_network_conf,
+};
+use crate::{
+    BaseInterface, Dhcpv4ClientId, Dhcpv6Duid, DnsClientState, InterfaceIpAddr,
+    Ipv6AddrGenMode, NmstateError,
+};
+
+pub(crate) fn gen_ip_network_conf(
+    base_iface: &BaseInterface,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    let iface_name = base_iface.name.as_str();
+    let ipv4 = base_iface.ipv4.as_ref().filter(|i| i.enabled);
+    let ipv6 = base_iface.ipv6.as_ref().filter(|i| i.enabled);
+
+    let dhcpv4 = ipv4.and_then(|i| i.dhcp) == Some(true);
+    let dhcpv6 = ipv6.and_then(|i| i.dhcp) == Some(true);
+    let autoconf = ipv6.and_then(|i| i.autoconf) == Some(true);
+
+    let network_section = conf.section("Network");
+    match (dhcpv4, dhcpv6) {
+        (true, true) => network_section.set("DHCP", "yes"),
+        (true, false) => network_section.set("DHCP", "ipv4"),
+        (false, true) => network_section.set("DHCP", "ipv6"),
+        (false, false) => network_section.set("DHCP", "no"),
+    };
+    if ipv6.is_some() {
+        network_section.set("LinkLocalAddressing", "ipv6");
+        network_section.set_bool("IPv6AcceptRA", autoconf);
+    } else {
+        network_section.set("LinkLocalAddressing", "no");
+        network_section.set_bool("IPv6AcceptRA", false);
+    }
+
+    if let Some(ipv4) = ipv4 {
+        if let Some(addrs) = ipv4.addresses.as_deref() {
+            gen_addr_conf(addrs, conf);
+        }
+        if dhcpv4 {
+            let section = conf.section("DHCPv4");
+            match ipv4.dhcp_client_id.as_ref() {
+                Some(Dhcpv4ClientId::LinkLayerAddress) => {
+                    section.set("ClientIdentifier", "mac");
+                }
+                Some(Dhcpv4ClientId::IaidPlusDuid) => {
+                    section.set("ClientIdentifier", "duid");
+                }
+                Some(Dhcpv4ClientId::Other(_)) => {
+                    return Err(not_supported(
+                        iface_name,
+                        "ipv4.dhcp-client-id",
+                    ));
+                }
+                None => (),
+            }
+            if let Some(v) = ipv4.auto_dns {
+                section.set_bool("UseDNS", v);
+            }
+            if let Some(v) = ipv4.auto_routes {
+                section.set_bool("UseRoutes", v);
+            }
+            if let Some(v) = ipv4.auto_gateway {
+                section.set_bool("UseGateway", v);
+            } else if ipv4.auto_routes == Some(false) {
+                section.set_bool("UseGateway", false);
+            }
+            if let Some(v) = ipv4.auto_table_id.filter(|v| *v != 0) {
+                section.set("RouteTable", v);
+            }
+            if let Some(v) = ipv4.auto_route_metric {
+                section.set("RouteMetric", v);
+            }
+            if let Some(v) = ipv4.dhcp_send_hostname {
+                section.set_bool("SendHostname", v);
+            }
+            if let Some(v) = ipv4.dhcp_custom_hostname.as_ref() {
+                section.set("Hostname", v);
+            }
+        }
+    }
+
+    if let Some(ipv6) = ipv6 {
+        if let Some(addrs) = ipv6.addresses.as_deref() {
+            gen_addr_conf(addrs, conf);
+        }
+        match ipv6.addr_gen_mode.as_ref() {
+            Some(Ipv6AddrGenMode::Eui64) => {
+                conf.section("Network")
+                    .set("IPv6LinkLocalAddressGenerationMode", "eui64");
+            }
+            Some(Ipv6AddrGenMode::StablePrivacy) => {
+                conf.section("Network").set(
+                    "IPv6LinkLocalAddressGenerationMode",
+                    "stable-privacy",
+                );
+            }
+            Some(Ipv6AddrGenMode::Other(_)) => {
+                return Err(not_supported(iface_name, "ipv6.addr-gen-mode"));
+            }
+            None => (),
+        }
+        if dhcpv6 {
+            let section = conf.section("DHCPv6");
+            match ipv6.dhcp_duid.as_ref() {
+                Some(Dhcpv6Duid::LinkLayerAddressPlusTime) => {
+                    section.set("DUIDType", "link-layer-time");
+                }
+                Some(Dhcpv6Duid::LinkLayerAddress) => {
+                    section.set("DUIDType", "link-layer");
+                }
+                Some(Dhcpv6Duid::EnterpriseNumber) => {
+                    section.set("DUIDType", "vendor");
+                }
+                Some(Dhcpv6Duid::Uuid) => {
+                    section.set("DUIDType", "uuid");
+                }
+                Some(Dhcpv6Duid::Other(_)) => {
+                    return Err(not_supported(iface_name, "ipv6.dhcp-duid"));
+                }
+                None => (),
+            }
+            if let Some(v) = ipv6.auto_dns {
+                section.set_bool("UseDNS", v);
+            }
+            if let Some(v) = ipv6.dhcp_send_hostname {
+                section.set_bool("SendHostname", v);
+            }
+            if let Some(v) = ipv6.dhcp_custom_hostname.as_ref() {
+                section.set("Hostname", v);
+            }
+        }
+        if autoconf {
+            let section = conf.section("IPv6AcceptRA");
+            if let Some(v) = ipv6.auto_dns {
+                section.set_bool("UseDNS", v);
+            }
+            if let Some(v) = ipv6.auto_gateway {
+                section.set_bool("UseGateway", v);
+            } else if ipv6.auto_routes == Some(false) {
+                section.set_bool("UseGateway", false);
+            }
+            if let Some(v) = ipv6.auto_routes {
+                section.set_bool("UseRoutePrefix", v);
+            }
+            if let Some(v) = ipv6.auto_table_id.filter(|v| *v != 0) {
+                section.set("RouteTable", v);
+            }
+            if let Some(v) = ipv6.auto_route_metric {
+                section.set("RouteMetric", v);
+            }
+            if let Some(v) = ipv6.token.as_ref().filter(|t| !t.is_empty()) {
+                section.set("Token", format!("static:{v}"));
+            }
+        }
+    }
+
+    let mut dns_confs: Vec<&DnsClientState> = ipv4
+        .and_then(|i| i.dns.as_ref())
+        .into_iter()
+        .chain(ipv6.and_then(|i| i.dns.as_ref()))
+        .collect();
+    // Smaller priority number means preferred
+    dns_confs.sort_by_key(|d| d.priority.unwrap_or_default());
+    for dns_conf in dns_confs {
+        gen_dns_conf(iface_name, dns_conf, conf)?;
+    }
+
+    if let Some(rts) = base_iface.routes.as_deref() {
+        gen_route_network_conf(iface_name, rts, conf)?;
+    }
+    for rules in [
+        ipv4.and_then(|i| i.rules.as_deref()),
+        ipv6.and_then(|i| i.rules.as_deref()),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        gen_route_rule_network_conf(iface_name, rules, conf)?;
+    }
+    Ok(())
+}
+
+fn gen_addr_conf(addrs: &[InterfaceIpAddr], conf: &mut NetworkdConf) {
+    for addr in addrs {
+        conf.new_section("Address")
+            .set("Address", format!("{}/{}", addr.ip, addr.prefix_length));
+    }
+}
+
+fn gen_dns_conf(
+    iface_name: &str,
+    dns_conf: &DnsClientState,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    if dns_conf.options.as_ref().map(|o| !o.is_empty()) == Some(true) {
+        return Err(not_supported(iface_name, "dns-resolver.options"));
+    }
+    let section = conf.section("Network");
+    for srv in dns_conf.server.as_deref().unwrap_or_default() {
+        section.set("DNS", srv);
+    }
+    if let Some(searches) = dns_conf.search.as_ref().filter(|s| !s.is_empty()) {
+        section.set("Domains", searches.join(" "));
+    }
+    Ok(())
+}
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{base_iface::not_supported, conf::NetworkdConf};
+use crate::{
+    BridgePortTrunkTag, BridgePortVlanConfig, BridgePortVlanMode,
+    LinuxBridgeInterface, NmstateError,
+};
+
+pub(crate) fn gen_bridge_netdev_conf(
+    iface: &LinuxBridgeInterface,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    let iface_name = iface.base.name.as_str();
+    let section = conf.section("Bridge");
+    if iface.vlan_filtering_is_enabled() {
+        section.set_bool("VLANFiltering", true);
+    }
+    let opts = if let Some(o) =
+        iface.bridge.as_ref().and_then(|b| b.options.as_ref())
+    {
+        o
+    } else {
+        return Ok(());
+    };
+
+    for (prop, is_set) in [
+        ("group-addr", opts.group_addr.is_some()),
+        ("hash-max", opts.hash_max.is_some()),
+        (
+            "multicast-last-member-count",
+            opts.multicast_last_member_count.is_some(),
+        ),
+        (
+            "multicast-last-member-interval",
+            opts.multicast_last_member_interval.is_some(),
+        ),
+        (
+            "multicast-membership-interval",
+            opts.multicast_membership_interval.is_some(),
+        ),
+        (
+            "multicast-querier-interval",
+            opts.multicast_querier_interval.is_some(),
+        ),
+        (
+            "multicast-query-interval",
+            opts.multicast_query_interval.is_some(),
+        ),
+        (
+            "multicast-query-response-interval",
+            opts.multicast_query_response_interval.is_some(),
+        ),
+        (
+            "multicast-query-use-ifaddr",
+            opts.multicast_query_use_ifaddr.is_some(),
+        ),
+        ("multicast-router", opts.multicast_router.is_some()),
+        (
+            "multicast-startup-query-count",
+            opts.multicast_startup_query_count.is_some(),
+        ),
+        (
+            "multicast-startup-query-interval",
+            opts.multicast_startup_query_interval.is_some(),
+        ),
+    ] {
+        if is_set {
+            return Err(not_supported(
+                iface_name,
+                &format!("bridge.options.{prop}"),
+            ));
+        }
+    }
+
+    if let Some(v) = opts.group_fwd_mask.or(opts.group_forward_mask) {
+        section.set("GroupForwardMask", v);
+    }
+    if let Some(v) = opts.mac_ageing_time {
+        section.set("AgeingTimeSec", v);
+    }
+    if let Some(v) = opts.multicast_querier {
+        section.set_bool("MulticastQuerier", v);
+    }
+    if let Some(v) = opts.multicast_snooping {
+        section.set_bool("MulticastSnooping", v);
+    }
+    if let Some(v) = opts.vlan_protocol.as_ref() {
+        section.set("VLANProtocol", v);
+    }
+    if let Some(v) = opts.vlan_default_pvid {
+        section.set("DefaultPVID", v);
+    }
+    if let Some(stp_opts) = opts.stp.as_ref() {
+        if let Some(v) = stp_opts.enabled {
+            section.set_bool("STP", v);
+        }
+        if let Some(v) = stp_opts.forward_delay {
+            section.set("ForwardDelaySec", v);
+        }
+        if let Some(v) = stp_opts.hello_time {
+            section.set("HelloTimeSec", v);
+        }
+        if let Some(v) = stp_opts.max_age {
+            section.set("MaxAgeSec", v);
+        }
+        if let Some(v) = stp_opts.priority {
+            section.set("Priority", v);
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn gen_bridge_port_conf(
+    br_iface: &LinuxBridgeInterface,
+    port_name: &str,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    conf.section("Network")
+        .set("Bridge", br_iface.base.name.as_str());
+
+    let port_conf = if let Some(p) = br_iface.get_port_conf(port_name) {
+        p
+    } else {
+        return Ok(());
+    };
+    let section = conf.section("Bridge");
+    if let Some(v) = port_conf.stp_hairpin_mode {
+        section.set_bool("HairPin", v);
+    }
+    if let Some(v) = port_conf.stp_path_cost {
+        section.set("Cost", v);
+    }
+    if let Some(v) = port_conf.stp_priority {
+        section.set("Priority", v);
+    }
+    if let Some(vlan_conf) = port_conf.vlan.as_ref() {
+        gen_bridge_port_vlan_conf(vlan_conf, conf);
+    }
+    Ok(())
+}
+
+fn gen_bridge_port_vlan_conf(
+    vlan_conf: &BridgePortVlanConfig,
+    conf: &mut NetworkdConf,
+) {
+    let mode = vlan_conf.mode.unwrap_or_default();
+    if mode == BridgePortVlanMode::Trunk {
+        for trunk_tag in vlan_conf.trunk_tags.as_deref().unwrap_or_default() {
+            let vlan = match trunk_tag {
+                BridgePortTrunkTag::Id(id) => id.to_string(),
+                BridgePortTrunkTag::IdRange(range) => {
+                    format!("{}-{}", range.min, range.max)
+                }
+            };
+            conf.new_section("BridgeVLAN").set("VLAN", vlan);
+        }
+    }
+
+    // The native VLAN of trunk port or the VLAN of access port
+    if mode == BridgePortVlanMode::Access
+        || vlan_conf.enable_native == Some(true)
+    {
+        if let Some(tag) = vlan_conf.tag {
+            conf.new_section("BridgeVLAN")
+                .set("PVID", tag)
+                .set("EgressUntagged", tag);
+        }
+    }
+}
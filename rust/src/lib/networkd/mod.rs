@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: Apache-2.0
+
+mod bareudp;
+mod base_iface;
+mod bond;
+mod conf;
+mod ethernet;
+mod gen_conf;
+mod geneve;
+mod ip;
+mod linux_bridge;
+mod route;
+mod route_rule;
+mod vlan;
+mod vrf;
+mod vxlan;
+
+pub(crate) use gen_conf::networkd_gen_conf;
@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{base_iface::not_supported, conf::NetworkdConf};
+use crate::{NmstateError, RouteEntry, RouteProtocol, RouteScope};
+
+pub(crate) fn gen_route_network_conf(
+    iface_name: &str,
+    routes: &[RouteEntry],
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    for route in routes.iter().filter(|r| !r.is_absent()) {
+        if route.weight.is_some() {
+            return Err(not_supported(iface_name, "routes.weight"));
+        }
+        if route.cwnd.is_some() {
+            return Err(not_supported(iface_name, "routes.cwnd"));
+        }
+        if route.lock_mtu == Some(true) {
+            return Err(not_supported(iface_name, "routes.lock-mtu"));
+        }
+        if route.nexthop_id.is_some() {
+            return Err(not_supported(iface_name, "routes.nexthop-id"));
+        }
+        let section = conf.new_section("Route");
+        if let Some(dst) = route.destination.as_ref() {
+            section.set("Destination", dst);
+        }
+        if let Some(gw) = route.next_hop_addr.as_ref().filter(|g| !g.is_empty())
+        {
+            section.set("Gateway", gw);
+        }
+        if let Some(metric) = route
+            .metric
+            .filter(|m| *m != RouteEntry::USE_DEFAULT_METRIC)
+        {
+            section.set("Metric", metric);
+        }
+        if let Some(table_id) = route
+            .table_id
+            .filter(|t| *t != RouteEntry::USE_DEFAULT_ROUTE_TABLE)
+        {
+            section.set("Table", table_id);
+        }
+        if let Some(route_type) = route.route_type {
+            section.set("Type", route_type);
+        }
+        if let Some(src) = route.source.as_ref() {
+            section.set("PreferredSource", src);
+        }
+        if let Some(mtu) = route.mtu {
+            section.set("MTUBytes", mtu);
+        }
+        if let Some(advmss) = route.advmss {
+            section.set("TCPAdvertisedMaximumSegmentSize", advmss);
+        }
+        if let Some(initcwnd) = route.initcwnd {
+            section.set("InitialCongestionWindow", initcwnd);
+        }
+        if let Some(initrwnd) = route.initrwnd {
+            section.set("InitialAdvertisedReceiveWindow", initrwnd);
+        }
+        if let Some(quickack) = route.quickack {
+            section.set_bool("QuickAck", quickack);
+        }
+        if let Some(onlink) = route.onlink {
+            section.set_bool("GatewayOnLink", onlink);
+        }
+        if let Some(scope) = route.scope {
+            section.set(
+                "Scope",
+                match scope {
+                    RouteScope::Universe => "global",
+                    RouteScope::Site => "site",
+                    RouteScope::Link => "link",
+                    RouteScope::Host => "host",
+                },
+            );
+        }
+        // systemd-networkd only has names for these protocols, use the
+        // protocol number for others.
+        if let Some(protocol) = route.protocol {
+            match protocol {
+                RouteProtocol::Boot
+                | RouteProtocol::Static
+                | RouteProtocol::Ra
+                | RouteProtocol::Dhcp => section.set("Protocol", protocol),
+                _ => section.set("Protocol", u8::from(protocol)),
+            };
+        }
+    }
+    Ok(())
+}
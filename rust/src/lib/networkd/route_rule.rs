@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{base_iface::not_supported, conf::NetworkdConf};
+use crate::{NmstateError, RouteRuleEntry};
+
+pub(crate) fn gen_route_rule_network_conf(
+    iface_name: &str,
+    rules: &[RouteRuleEntry],
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    for rule in rules.iter().filter(|r| !r.is_absent()) {
+        // The `dscp` is converted to `tos` by sanitize, this should not
+        // happen.
+        if rule.dscp.is_some() {
+            return Err(not_supported(iface_name, "route-rules.dscp"));
+        }
+        let section = conf.new_section("RoutingPolicyRule");
+        let ip_from = rule.ip_from.as_ref().filter(|i| !i.is_empty());
+        let ip_to = rule.ip_to.as_ref().filter(|i| !i.is_empty());
+        if let Some(ip_from) = ip_from {
+            section.set("From", ip_from);
+        }
+        if let Some(ip_to) = ip_to {
+            section.set("To", ip_to);
+        }
+        if ip_from.is_none() && ip_to.is_none() {
+            section.set("Family", if rule.is_ipv6() { "ipv6" } else { "ipv4" });
+        }
+        if let Some(priority) = rule
+            .priority
+            .filter(|p| *p != RouteRuleEntry::USE_DEFAULT_PRIORITY)
+        {
+            section.set("Priority", priority);
+        }
+        if let Some(table_id) = rule
+            .table_id
+            .filter(|t| *t != RouteRuleEntry::USE_DEFAULT_ROUTE_TABLE)
+        {
+            section.set("Table", table_id);
+        }
+        if let Some(fwmark) = rule.fwmark {
+            if let Some(fwmask) = rule.fwmask {
+                section.set("FirewallMark", format!("{fwmark}/{fwmask}"));
+            } else {
+                section.set("FirewallMark", fwmark);
+            }
+        }
+        if let Some(iif) = rule.iif.as_ref() {
+            section.set("IncomingInterface", iif);
+        }
+        if let Some(oif) = rule.oif.as_ref() {
+            section.set("OutgoingInterface", oif);
+        }
+        if let Some(v) = rule.sport {
+            section.set("SourcePort", v);
+        }
+        if let Some(v) = rule.dport {
+            section.set("DestinationPort", v);
+        }
+        if let Some(v) = rule.ipproto {
+            section.set("IPProtocol", v);
+        }
+        if let Some(v) = rule.uidrange {
+            section.set("User", v);
+        }
+        if let Some(v) = rule.tos {
+            section.set("TypeOfService", v);
+        }
+        if let Some(v) = rule.l3mdev {
+            section.set_bool("L3MasterDevice", v);
+        }
+        if let Some(v) = rule.goto_priority {
+            section.set("GoTo", v);
+        }
+        if let Some(v) = rule.suppress_prefix_length {
+            section.set("SuppressPrefixLength", v);
+        }
+        if let Some(action) = rule.action {
+            section.set("Type", action);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::conf::NetworkdConf;
+use crate::{NmstateError, VlanInterface, VlanRegistrationProtocol};
+
+pub(crate) fn gen_vlan_netdev_conf(
+    iface: &VlanInterface,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    if let Some(vlan_conf) = iface.vlan.as_ref() {
+        let section = conf.section("VLAN");
+        section.set("Id", vlan_conf.id);
+        if let Some(v) = vlan_conf.protocol {
+            section.set("Protocol", v);
+        }
+        match vlan_conf.registration_protocol {
+            Some(VlanRegistrationProtocol::Gvrp) => {
+                section.set_bool("GVRP", true);
+            }
+            Some(VlanRegistrationProtocol::Mvrp) => {
+                section.set_bool("MVRP", true);
+            }
+            Some(VlanRegistrationProtocol::None) => {
+                section.set_bool("GVRP", false).set_bool("MVRP", false);
+            }
+            None => (),
+        }
+        if let Some(v) = vlan_conf.reorder_headers {
+            section.set_bool("ReorderHeader", v);
+        }
+        if let Some(v) = vlan_conf.loose_binding {
+            section.set_bool("LooseBinding", v);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{base_iface::not_supported, conf::NetworkdConf};
+use crate::{NmstateError, VrfInterface};
+
+pub(crate) fn gen_vrf_netdev_conf(
+    iface: &VrfInterface,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    match iface.vrf.as_ref().map(|v| v.table_id) {
+        Some(table_id) if table_id != 0 => {
+            conf.section("VRF").set("Table", table_id);
+            Ok(())
+        }
+        // Preserving current route table ID is meaningless for offline
+        // configuration.
+        _ => Err(not_supported(
+            iface.base.name.as_str(),
+            "vrf.route-table-id: 0",
+        )),
+    }
+}
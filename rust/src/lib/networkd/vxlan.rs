@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::conf::NetworkdConf;
+use crate::{NmstateError, VxlanInterface};
+
+pub(crate) fn gen_vxlan_netdev_conf(
+    iface: &VxlanInterface,
+    conf: &mut NetworkdConf,
+) -> Result<(), NmstateError> {
+    if let Some(vxlan_conf) = iface.vxlan.as_ref() {
+        let section = conf.section("VXLAN");
+        section.set("VNI", vxlan_conf.id);
+        if let Some(v) = vxlan_conf.local {
+            section.set("Local", v);
+        }
+        if let Some(v) = vxlan_conf.remote {
+            section.set("Remote", v);
+        }
+        if let Some(v) = vxlan_conf.dst_port {
+            section.set("DestinationPort", v);
+        }
+        if let Some(v) = vxlan_conf.learning {
+            section.set_bool("MacLearning", v);
+        }
+        // Without underlay interface, the VXLAN should not be bound to any
+        // interface.
+        if vxlan_conf.base_iface.is_empty() {
+            section.set_bool("Independent", true);
+        }
+    }
+    Ok(())
+}
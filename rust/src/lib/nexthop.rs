@@ -0,0 +1,550 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ip::is_ipv6_addr, AddressFamily, ErrorKind, MergedInterfaces, NmstateError,
+    RouteEntry,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+#[serde(deny_unknown_fields)]
+/// Nexthop objects which could be shared by routes via
+/// [crate::RouteEntry.nexthop_id]. A nexthop is either a single next hop
+/// or a group of other nexthops for ECMP(Equal-Cost Multi-Path) routing.
+///
+/// Example yaml:
+/// ```yml
+/// ---
+/// nexthops:
+///   config:
+///   - id: 1
+///     next-hop-interface: eth1
+///     next-hop-address: 192.0.2.1
+///   - id: 2
+///     next-hop-interface: eth2
+///     next-hop-address: 198.51.100.1
+///   - id: 100
+///     group:
+///     - id: 1
+///       weight: 2
+///     - id: 2
+/// routes:
+///   config:
+///   - destination: 203.0.113.0/24
+///     nexthop-id: 100
+/// ```
+///
+/// NetworkManager does not support nexthop objects, when applying with
+/// NetworkManager backend, the nexthops are not created but used to store
+/// routes referring them as ECMP routes of the member interfaces which kernel
+/// merges into a single multipath route.
+pub struct Nexthops {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// All nexthops in kernel, including those created by routing daemons.
+    /// Ignored when applying.
+    pub running: Option<Vec<NexthopEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Nexthops not created by routing daemons.
+    /// When applying, `None` means preserve current nexthops. Desired nexthop
+    /// replaces current nexthop holding the same ID. To delete a nexthop,
+    /// please set [NexthopEntry.state] to [NexthopState::Absent]. Any
+    /// property of absent [NexthopEntry] set to `None` means wildcard.
+    pub config: Option<Vec<NexthopEntry>>,
+}
+
+impl Nexthops {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.running.is_none() && self.config.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum NexthopState {
+    /// Mark a nexthop as absent to remove it.
+    Absent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+#[serde(deny_unknown_fields)]
+/// Nexthop entry
+pub struct NexthopEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Only used for delete nexthop when applying.
+    pub state: Option<NexthopState>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Nexthop ID referred by routes and nexthop groups.
+    /// Mandatory for every non-absent nexthop, 0 is not allowed.
+    pub id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Address family of single nexthop. Default to the family of
+    /// `next-hop-address` or IPv4 if `next-hop-address` not defined.
+    /// Not used by nexthop group.
+    pub family: Option<AddressFamily>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "next-hop-interface"
+    )]
+    /// Next hop interface name of single nexthop.
+    /// Serialize and deserialize to/from `next-hop-interface`.
+    pub next_hop_iface: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "next-hop-address"
+    )]
+    /// Next hop IP address(gateway) of single nexthop.
+    /// Serialize and deserialize to/from `next-hop-address`.
+    pub next_hop_addr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Members of nexthop group. Only single nexthop could be member of
+    /// group. Cannot be used with `next-hop-interface` or
+    /// `next-hop-address`.
+    pub group: Option<Vec<NexthopGroupMember>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+#[serde(deny_unknown_fields)]
+/// Member of nexthop group
+pub struct NexthopGroupMember {
+    #[serde(deserialize_with = "crate::deserializer::u32_or_string")]
+    /// ID of member nexthop
+    pub id: u32,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// Weight of this member in ECMP group, default to 1.
+    /// The valid range of this property is 1-256.
+    pub weight: Option<u16>,
+}
+
+const DEFAULT_NEXTHOP_WEIGHT: u16 = 1;
+
+impl NexthopEntry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn is_absent(&self) -> bool {
+        matches!(self.state, Some(NexthopState::Absent))
+    }
+
+    pub(crate) fn is_group(&self) -> bool {
+        self.group.is_some()
+    }
+
+    /// Whether the desired nexthop (self) matches with another.
+    pub(crate) fn is_match(&self, other: &Self) -> bool {
+        if self.id.is_some() && self.id != other.id {
+            return false;
+        }
+        if self.next_hop_iface.is_some()
+            && self.next_hop_iface != other.next_hop_iface
+        {
+            return false;
+        }
+        if self.next_hop_addr.is_some()
+            && self.next_hop_addr != other.next_hop_addr
+        {
+            return false;
+        }
+        true
+    }
+
+    pub(crate) fn sanitize(&mut self) -> Result<(), NmstateError> {
+        if let Some(via) = self.next_hop_addr.as_ref() {
+            let new_via = match via.parse::<std::net::IpAddr>() {
+                Ok(i) => i.to_string(),
+                Err(e) => {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!("Invalid nexthop address {via}: {e}"),
+                    ));
+                }
+            };
+            if via != &new_via {
+                log::warn!(
+                    "Nexthop next-hop-address {} sanitized to {}",
+                    via,
+                    new_via
+                );
+                self.next_hop_addr = Some(new_via);
+            }
+        }
+        if self.is_absent() {
+            return Ok(());
+        }
+        let id = match self.id {
+            Some(0) | None => {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Nexthop ID should be defined and not 0: {self}"),
+                ));
+            }
+            Some(i) => i,
+        };
+        if let Some(members) = self.group.as_mut() {
+            if self.next_hop_iface.is_some() || self.next_hop_addr.is_some() {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Nexthop group {id} cannot have next-hop-interface \
+                        or next-hop-address"
+                    ),
+                ));
+            }
+            if members.is_empty() {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Nexthop group {id} has no member"),
+                ));
+            }
+            let mut member_ids: HashSet<u32> = HashSet::new();
+            for member in members.iter_mut() {
+                if member.id == id || !member_ids.insert(member.id) {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Nexthop group {id} has invalid or duplicate \
+                            member {}",
+                            member.id
+                        ),
+                    ));
+                }
+                let weight = member.weight.unwrap_or(DEFAULT_NEXTHOP_WEIGHT);
+                if !(1..=256).contains(&weight) {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Invalid weight {weight} of nexthop group {id} \
+                            member {}, should be in the range of 1 to 256",
+                            member.id
+                        ),
+                    ));
+                }
+                member.weight = Some(weight);
+            }
+            members.sort_unstable_by_key(|m| m.id);
+            self.family = None;
+        } else {
+            if self.next_hop_iface.is_none() {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Nexthop {id} should have next-hop-interface or \
+                        group defined"
+                    ),
+                ));
+            }
+            let family = match self.next_hop_addr.as_deref() {
+                Some(addr) if is_ipv6_addr(addr) => AddressFamily::IPv6,
+                Some(_) => AddressFamily::IPv4,
+                None => self.family.unwrap_or_default(),
+            };
+            if self.family.is_some() && self.family != Some(family)
+                || family == AddressFamily::Unknown
+            {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid family of nexthop {id}, should be the same \
+                        as family of next-hop-address"
+                    ),
+                ));
+            }
+            self.family = Some(family);
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for NexthopEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut props = Vec::new();
+        if self.is_absent() {
+            props.push("state: absent".to_string());
+        }
+        if let Some(v) = self.id {
+            props.push(format!("id: {v}"));
+        }
+        if let Some(v) = self.family {
+            props.push(format!("family: {v}"));
+        }
+        if let Some(v) = self.next_hop_iface.as_ref() {
+            props.push(format!("next-hop-interface: {v}"));
+        }
+        if let Some(v) = self.next_hop_addr.as_ref() {
+            props.push(format!("next-hop-address: {v}"));
+        }
+        if let Some(members) = self.group.as_ref() {
+            props.push(format!(
+                "group: {}",
+                members
+                    .iter()
+                    .map(|m| match m.weight {
+                        Some(w) => format!("{}(weight {w})", m.id),
+                        None => m.id.to_string(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ));
+        }
+
+        write!(f, "{}", props.join(" "))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct MergedNexthops {
+    // Nexthops after applying, indexed by nexthop ID.
+    pub(crate) merged: HashMap<u32, NexthopEntry>,
+    // The `changed` contains nexthops to create, replace or remove(marked as
+    // absent). Not including desired nexthop equal to current one.
+    pub(crate) changed: Vec<NexthopEntry>,
+    pub(crate) desired: Nexthops,
+    pub(crate) current: Nexthops,
+    // NetworkManager does not support nexthop objects, they are only used to
+    // expand routes referring them.
+    pub(crate) kernel_only: bool,
+}
+
+impl MergedNexthops {
+    pub(crate) fn new(
+        desired: Nexthops,
+        current: Nexthops,
+        merged_ifaces: &MergedInterfaces,
+        kernel_only: bool,
+    ) -> Result<Self, NmstateError> {
+        let mut desired_nhs = Vec::new();
+        if let Some(nhs) = desired.config.as_ref() {
+            for nh in nhs {
+                let mut nh = nh.clone();
+                nh.sanitize()?;
+                desired_nhs.push(nh);
+            }
+        }
+
+        let ifaces_marked_as_absent: Vec<&str> = merged_ifaces
+            .kernel_ifaces
+            .values()
+            .filter(|i| i.merged.is_absent())
+            .map(|i| i.merged.name())
+            .collect();
+
+        let mut desired_ids: HashSet<u32> = HashSet::new();
+        for nh in desired_nhs.iter().filter(|nh| !nh.is_absent()) {
+            if let Some(id) = nh.id {
+                if !desired_ids.insert(id) {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!("Duplicate desired nexthop ID {id}"),
+                    ));
+                }
+            }
+            if let Some(iface) = nh.next_hop_iface.as_deref() {
+                if ifaces_marked_as_absent.contains(&iface) {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The next hop interface of desired nexthop \
+                            '{nh}' has been marked as absent"
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let mut merged: HashMap<u32, NexthopEntry> = HashMap::new();
+        let mut changed: Vec<NexthopEntry> = Vec::new();
+        let cur_nhs = current.config.as_deref().unwrap_or_default();
+
+        for cur_nh in cur_nhs {
+            let id = match cur_nh.id {
+                Some(i) => i,
+                None => continue,
+            };
+            if desired_nhs
+                .iter()
+                .filter(|nh| nh.is_absent())
+                .any(|absent_nh| absent_nh.is_match(cur_nh))
+            {
+                let mut new_nh = cur_nh.clone();
+                new_nh.state = Some(NexthopState::Absent);
+                changed.push(new_nh);
+            } else if !cur_nh
+                .next_hop_iface
+                .as_deref()
+                .map(|i| ifaces_marked_as_absent.contains(&i))
+                .unwrap_or_default()
+            {
+                merged.insert(id, cur_nh.clone());
+            }
+        }
+
+        for nh in desired_nhs.into_iter().filter(|nh| !nh.is_absent()) {
+            if let Some(id) = nh.id {
+                if !cur_nhs.contains(&nh) {
+                    changed.push(nh.clone());
+                }
+                merged.insert(id, nh);
+            }
+        }
+
+        for nh in merged.values() {
+            for member in nh.group.as_deref().unwrap_or_default() {
+                if !merged
+                    .get(&member.id)
+                    .map(|m| !m.is_group())
+                    .unwrap_or_default()
+                {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Nexthop group {} is referring nonexistent \
+                            nexthop or nexthop group {} as member",
+                            nh.id.unwrap_or_default(),
+                            member.id
+                        ),
+                    ));
+                }
+            }
+        }
+
+        Ok(Self {
+            merged,
+            changed,
+            desired,
+            current,
+            kernel_only,
+        })
+    }
+
+    #[cfg_attr(not(feature = "query_apply"), allow(dead_code))]
+    pub(crate) fn is_changed(&self) -> bool {
+        !self.changed.is_empty()
+    }
+
+    // Whether specified nexthop or any member of it has been changed.
+    pub(crate) fn is_nexthop_changed(&self, id: u32) -> bool {
+        let member_ids: Vec<u32> = self
+            .merged
+            .get(&id)
+            .and_then(|nh| nh.group.as_ref())
+            .map(|g| g.iter().map(|m| m.id).collect())
+            .unwrap_or_default();
+        self.changed.iter().any(|nh| {
+            nh.id == Some(id)
+                || nh.id.map(|i| member_ids.contains(&i)).unwrap_or_default()
+        })
+    }
+
+    // Interfaces used by specified nexthop or members of nexthop group.
+    pub(crate) fn get_ifaces(&self, id: u32) -> Vec<&str> {
+        let mut ret = Vec::new();
+        if let Some(nh) = self.merged.get(&id) {
+            if let Some(members) = nh.group.as_ref() {
+                for member in members {
+                    if let Some(iface) = self
+                        .merged
+                        .get(&member.id)
+                        .and_then(|m| m.next_hop_iface.as_deref())
+                    {
+                        ret.push(iface);
+                    }
+                }
+            } else if let Some(iface) = nh.next_hop_iface.as_deref() {
+                ret.push(iface);
+            }
+        }
+        ret
+    }
+
+    // Convert route referring nexthop into routes to each next hop, ECMP
+    // weight is set to IPv4 routes of nexthop group.
+    // Used by backends without nexthop object support.
+    pub(crate) fn expand_route(
+        &self,
+        rt: &RouteEntry,
+    ) -> Result<Vec<RouteEntry>, NmstateError> {
+        let id = match rt.nexthop_id {
+            Some(i) => i,
+            None => return Ok(vec![rt.clone()]),
+        };
+        let nh = self.merged.get(&id).ok_or_else(|| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Route {rt} is referring nonexistent nexthop {id}"),
+            )
+        })?;
+        let mut members: Vec<(&NexthopEntry, Option<u16>)> = Vec::new();
+        if let Some(group) = nh.group.as_ref() {
+            for member in group {
+                if let Some(member_nh) = self.merged.get(&member.id) {
+                    members.push((
+                        member_nh,
+                        Some(member.weight.unwrap_or(DEFAULT_NEXTHOP_WEIGHT)),
+                    ));
+                }
+            }
+            if rt.is_ipv6() {
+                if members
+                    .iter()
+                    .any(|(_, w)| *w != Some(DEFAULT_NEXTHOP_WEIGHT))
+                {
+                    return Err(NmstateError::new(
+                        ErrorKind::NotSupportedError,
+                        format!(
+                            "IPv6 ECMP route with weight is not supported \
+                            yet: nexthop group {id}"
+                        ),
+                    ));
+                }
+                for (_, weight) in members.iter_mut() {
+                    *weight = None;
+                }
+            }
+        } else {
+            members.push((nh, None));
+        }
+
+        let mut ret = Vec::new();
+        for (member_nh, weight) in members {
+            let is_ipv6 = member_nh.family == Some(AddressFamily::IPv6);
+            if is_ipv6 != rt.is_ipv6() {
+                return Err(NmstateError::new(
+                    ErrorKind::NotSupportedError,
+                    format!(
+                        "Route {rt} is referring nexthop {} of different IP \
+                        family",
+                        member_nh.id.unwrap_or_default()
+                    ),
+                ));
+            }
+            let mut new_rt = rt.clone();
+            new_rt.nexthop_id = None;
+            new_rt.next_hop_iface.clone_from(&member_nh.next_hop_iface);
+            new_rt.next_hop_addr.clone_from(&member_nh.next_hop_addr);
+            new_rt.weight = weight;
+            ret.push(new_rt);
+        }
+        Ok(ret)
+    }
+}
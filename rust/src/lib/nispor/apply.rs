@@ -1,13 +1,34 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use netlink_packet_route::link::InfoKind;
+
 use crate::{
     nispor::{
+        bareudp::nms_bareudp_conf_to_nl,
+        bond::{
+            apply_bond_conf_via_sysfs, apply_bond_ports_conf_via_sysfs,
+            validate_bond_for_kernel_mode,
+        },
+        dhcp::{append_leased_ip, nispor_dhcp_apply},
         dns::apply_dns_conf_to_etc,
+        geneve::nms_geneve_conf_to_nl,
         hostname::set_running_hostname,
+        hsr::nms_hsr_conf_to_nl,
         ip::{nmstate_ipv4_to_np, nmstate_ipv6_to_np},
-        route::gen_nispor_route_confs,
+        ip_tunnel::nms_ip_tunnel_conf_to_nl,
+        ip_vlan::nms_ip_vlan_conf_to_nl,
+        linux_bridge::apply_linux_bridge_conf,
+        mac_vlan::{nms_mac_vlan_conf_to_nl, nms_mac_vtap_conf_to_nl},
+        netlink::{link_add, link_del, NlLinkConf},
+        netns::apply_ifaces_netns,
+        nexthop::apply_nexthops,
+        route::{apply_nl_routes, gen_nispor_route_confs},
+        route_rule::apply_route_rules,
         veth::nms_veth_conf_to_np,
         vlan::nms_vlan_conf_to_np,
+        vrf::nms_vrf_conf_to_nl,
+        vxlan::nms_vxlan_conf_to_nl,
+        wireguard::apply_wireguard_conf,
     },
     ErrorKind, Interface, InterfaceType, MergedInterface, MergedInterfaces,
     MergedNetworkState, NmstateError,
@@ -16,6 +37,16 @@ use crate::{
 pub(crate) async fn nispor_apply(
     merged_state: &MergedNetworkState,
 ) -> Result<(), NmstateError> {
+    for merged_iface in merged_state
+        .interfaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed())
+    {
+        validate_bond_for_kernel_mode(merged_iface)?;
+        validate_macsec_for_kernel_mode(merged_iface)?;
+    }
+
     delete_ifaces(&merged_state.interfaces).await?;
 
     let mut ifaces: Vec<&MergedInterface> = merged_state
@@ -35,6 +66,9 @@ pub(crate) async fn nispor_apply(
         }
     });
 
+    let mut detached_ports = pre_apply_bond_ifaces(ifaces.as_slice()).await?;
+    detached_ports.extend(pre_apply_virtual_ifaces(ifaces.as_slice())?);
+
     let mut np_ifaces: Vec<nispor::IfaceConf> = Vec::new();
     for merged_iface in ifaces.iter().filter(|i| {
         i.merged.iface_type() != InterfaceType::Unknown && !i.merged.is_absent()
@@ -43,6 +77,30 @@ pub(crate) async fn nispor_apply(
             np_ifaces.push(nmstate_iface_to_np(iface)?);
         }
     }
+    for (port_name, ctrl_name) in detached_ports {
+        if let Some(np_iface) =
+            np_ifaces.iter_mut().find(|i| i.name == port_name)
+        {
+            if np_iface.controller.is_none() {
+                np_iface.controller = Some(ctrl_name);
+            }
+            continue;
+        }
+        if let Some(cur_iface) = merged_state
+            .interfaces
+            .kernel_ifaces
+            .get(&port_name)
+            .and_then(|i| i.current.as_ref())
+        {
+            let mut port_np_iface = nispor::IfaceConf::default();
+            port_np_iface.name = port_name;
+            port_np_iface.iface_type =
+                Some(nmstate_cur_iface_type_to_np(cur_iface));
+            port_np_iface.state = nispor::IfaceState::Up;
+            port_np_iface.controller = Some(ctrl_name);
+            np_ifaces.push(port_np_iface);
+        }
+    }
 
     let mut net_conf = nispor::NetConf::default();
     net_conf.ifaces = Some(np_ifaces);
@@ -58,6 +116,33 @@ pub(crate) async fn nispor_apply(
         ));
     }
 
+    if merged_state.nexthops.is_changed() {
+        apply_nexthops(&merged_state.nexthops)?;
+    }
+
+    if merged_state.routes.is_changed() {
+        apply_nl_routes(&merged_state.routes)?;
+    }
+
+    if merged_state.rules.is_changed() {
+        apply_route_rules(&merged_state.rules)?;
+    }
+
+    for merged_iface in ifaces.iter().filter(|i| !i.merged.is_absent()) {
+        match merged_iface.merged.iface_type() {
+            InterfaceType::Bond => {
+                apply_bond_ports_conf_via_sysfs(merged_iface)?
+            }
+            InterfaceType::LinuxBridge => {
+                apply_linux_bridge_conf(merged_iface)?
+            }
+            InterfaceType::WireGuard => apply_wireguard_conf(merged_iface)?,
+            _ => (),
+        }
+    }
+
+    apply_ifaces_netns(&merged_state.interfaces)?;
+
     if let Some(running_hostname) = merged_state
         .hostname
         .desired
@@ -71,6 +156,10 @@ pub(crate) async fn nispor_apply(
         apply_dns_conf_to_etc(&merged_state.dns)?;
     }
 
+    // DHCP lease should be applied after DNS, so DNS servers retrieved from
+    // DHCP are appended after static ones.
+    nispor_dhcp_apply(merged_state).await?;
+
     Ok(())
 }
 
@@ -83,10 +172,28 @@ fn nmstate_iface_type_to_np(
         InterfaceType::Ethernet => nispor::IfaceType::Ethernet,
         InterfaceType::Veth => nispor::IfaceType::Veth,
         InterfaceType::Vlan => nispor::IfaceType::Vlan,
+        InterfaceType::Vrf => nispor::IfaceType::Vrf,
+        InterfaceType::Vxlan => nispor::IfaceType::Vxlan,
+        InterfaceType::MacVlan => nispor::IfaceType::MacVlan,
+        InterfaceType::MacVtap => nispor::IfaceType::MacVtap,
+        InterfaceType::IpVlan => nispor::IfaceType::IpVlan,
+        InterfaceType::Dummy => nispor::IfaceType::Dummy,
+        InterfaceType::Loopback => nispor::IfaceType::Loopback,
+        InterfaceType::Hsr => nispor::IfaceType::Hsr,
+        InterfaceType::MacSec => nispor::IfaceType::MacSec,
         _ => nispor::IfaceType::Unknown,
     }
 }
 
+fn nmstate_cur_iface_type_to_np(cur_iface: &Interface) -> nispor::IfaceType {
+    if let Interface::Ethernet(eth_iface) = cur_iface {
+        if eth_iface.veth.is_some() {
+            return nispor::IfaceType::Veth;
+        }
+    }
+    nmstate_iface_type_to_np(&cur_iface.iface_type())
+}
+
 fn nmstate_iface_to_np(
     nms_iface: &Interface,
 ) -> Result<nispor::IfaceConf, NmstateError> {
@@ -116,6 +223,7 @@ fn nmstate_iface_to_np(
     if base_iface.can_have_ip() {
         np_iface.ipv4 = Some(nmstate_ipv4_to_np(base_iface.ipv4.as_ref()));
         np_iface.ipv6 = Some(nmstate_ipv6_to_np(base_iface.ipv6.as_ref()));
+        append_leased_ip(nms_iface, &mut np_iface);
     }
 
     np_iface.mac_address.clone_from(&base_iface.mac_address);
@@ -171,3 +279,211 @@ async fn delete_ifaces(
         Ok(())
     }
 }
+
+// Bond mode can only be changed when bond has no port, hence we create
+// the bond and set its options before nispor attaching ports to it.
+// Return the `(port_name, bond_name)` list required to attach back.
+async fn pre_apply_bond_ifaces(
+    ifaces: &[&MergedInterface],
+) -> Result<Vec<(String, String)>, NmstateError> {
+    let bond_ifaces: Vec<&MergedInterface> = ifaces
+        .iter()
+        .filter(|i| {
+            i.merged.iface_type() == InterfaceType::Bond
+                && !i.merged.is_absent()
+                && i.for_apply.is_some()
+        })
+        .copied()
+        .collect();
+
+    let mut np_ifaces: Vec<nispor::IfaceConf> = Vec::new();
+    for merged_iface in bond_ifaces.iter().filter(|i| i.current.is_none()) {
+        let mut np_iface = nispor::IfaceConf::default();
+        np_iface.name = merged_iface.merged.name().to_string();
+        np_iface.iface_type = Some(nispor::IfaceType::Bond);
+        np_iface.state = nispor::IfaceState::Up;
+        np_ifaces.push(np_iface);
+    }
+    if !np_ifaces.is_empty() {
+        let mut net_conf = nispor::NetConf::default();
+        net_conf.ifaces = Some(np_ifaces);
+        if let Err(e) = net_conf.apply_async().await {
+            return Err(NmstateError::new(
+                ErrorKind::PluginFailure,
+                format!(
+                    "Unknown error from nipsor plugin: {}, {}",
+                    e.kind, e.msg
+                ),
+            ));
+        }
+    }
+
+    let mut ret = Vec::new();
+    for merged_iface in bond_ifaces {
+        for port_name in apply_bond_conf_via_sysfs(merged_iface)? {
+            ret.push((port_name, merged_iface.merged.name().to_string()));
+        }
+    }
+    Ok(ret)
+}
+
+// Nispor cannot create these virtual interfaces, hence we create them via
+// netlink before nispor configuring their IP and ports.
+// The kernel does not allow changing most of their properties after creation,
+// so we recreate them when changed.
+// Return the `(port_name, controller_name)` list required to attach back.
+fn pre_apply_virtual_ifaces(
+    ifaces: &[&MergedInterface],
+) -> Result<Vec<(String, String)>, NmstateError> {
+    let mut ret = Vec::new();
+    for merged_iface in ifaces
+        .iter()
+        .filter(|i| !i.merged.is_absent() && i.for_apply.is_some())
+    {
+        let iface_name = merged_iface.merged.name();
+        if merged_iface.merged.iface_type() == InterfaceType::Loopback
+            && merged_iface.current.is_none()
+        {
+            return Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!("Cannot create loopback interface {iface_name}"),
+            ));
+        }
+        let nl_conf = match gen_nl_link_conf(&merged_iface.merged)? {
+            Some(c) => c,
+            None => continue,
+        };
+        if let Some(cur_iface) = merged_iface.current.as_ref() {
+            let cur_nl_conf = gen_nl_link_conf(cur_iface).ok().flatten();
+            if cur_nl_conf.as_ref() == Some(&nl_conf) {
+                continue;
+            }
+            log::info!(
+                "Recreating {} interface {} for changed properties",
+                nl_conf.kind,
+                iface_name
+            );
+            ret.extend(gen_reattach_list(merged_iface)?);
+            link_del(iface_name)?;
+        }
+        link_add(iface_name, &nl_conf)?;
+    }
+    Ok(ret)
+}
+
+// Deleting interface detaches its ports and detaches itself from its
+// controller, generate the `(port_name, controller_name)` list to restore
+// these relationships after recreation.
+pub(crate) fn gen_reattach_list(
+    merged_iface: &MergedInterface,
+) -> Result<Vec<(String, String)>, NmstateError> {
+    let iface = &merged_iface.merged;
+    let iface_name = iface.name();
+    let mut ret: Vec<(String, String)> = iface
+        .ports()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|port_name| (port_name.to_string(), iface_name.to_string()))
+        .collect();
+    if let Some(ctrl_name) = iface
+        .base_iface()
+        .controller
+        .as_deref()
+        .filter(|c| !c.is_empty())
+    {
+        if iface.base_iface().controller_type.as_ref()
+            == Some(&InterfaceType::OvsBridge)
+        {
+            return Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Recreating interface {iface_name} attached to OVS \
+                    bridge {ctrl_name} is not supported in kernel mode"
+                ),
+            ));
+        }
+        ret.push((iface_name.to_string(), ctrl_name.to_string()));
+    }
+    Ok(ret)
+}
+
+pub(crate) fn gen_nl_link_conf(
+    iface: &Interface,
+) -> Result<Option<NlLinkConf>, NmstateError> {
+    let conf = match iface {
+        Interface::Dummy(_) => {
+            return Ok(Some(NlLinkConf::new(InfoKind::Dummy)))
+        }
+        Interface::WireGuard(_) => {
+            return Ok(Some(NlLinkConf::new(InfoKind::Wireguard)))
+        }
+        Interface::Vrf(i) => i.vrf.as_ref().map(nms_vrf_conf_to_nl),
+        Interface::Vxlan(i) => {
+            i.vxlan.as_ref().map(nms_vxlan_conf_to_nl).transpose()?
+        }
+        Interface::MacVlan(i) => i
+            .mac_vlan
+            .as_ref()
+            .map(nms_mac_vlan_conf_to_nl)
+            .transpose()?,
+        Interface::MacVtap(i) => i
+            .mac_vtap
+            .as_ref()
+            .map(nms_mac_vtap_conf_to_nl)
+            .transpose()?,
+        Interface::Hsr(i) => {
+            i.hsr.as_ref().map(nms_hsr_conf_to_nl).transpose()?
+        }
+        Interface::IpTunnel(i) => i
+            .ip_tunnel
+            .as_ref()
+            .map(|c| nms_ip_tunnel_conf_to_nl(&i.base.iface_type, c))
+            .transpose()?,
+        Interface::Geneve(i) => i.geneve.as_ref().map(nms_geneve_conf_to_nl),
+        Interface::BareUdp(i) => i.bareudp.as_ref().map(nms_bareudp_conf_to_nl),
+        Interface::IpVlan(i) => i.ipvlan.as_ref().map(nms_ip_vlan_conf_to_nl),
+        _ => return Ok(None),
+    };
+    if conf.is_none() {
+        return Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Missing {} configuration for interface {}",
+                iface.iface_type(),
+                iface.name()
+            ),
+        ));
+    }
+    Ok(conf)
+}
+
+// MACsec requires wpa_supplicant for MKA and nmstate schema does not
+// support static secure association keys, hence we only support
+// changing IP and state of existing MACsec interface in kernel mode.
+fn validate_macsec_for_kernel_mode(
+    merged_iface: &MergedInterface,
+) -> Result<(), NmstateError> {
+    if let Some(Interface::MacSec(apply_iface)) =
+        merged_iface.for_apply.as_ref()
+    {
+        if merged_iface.merged.is_absent() {
+            return Ok(());
+        }
+        let mka_defined = apply_iface
+            .macsec
+            .as_ref()
+            .map(|c| c.mka_cak.is_some() || c.mka_ckn.is_some())
+            .unwrap_or_default();
+        if merged_iface.current.is_none() || mka_defined {
+            return Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Creating MACsec interface or configuring MACsec MKA is \
+                    not supported in kernel mode: {}",
+                    apply_iface.base.name
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Nispor does not support bareudp yet, the bareudp properties are queried
+// via `RTM_GETLINK` directly.
+
+use netlink_packet_core::{parse_u16_be, DefaultNla, Nla};
+use netlink_packet_route::link::{InfoData, InfoKind};
+
+use crate::{
+    nispor::netlink::{
+        default_nla_value, info_data_from_nlas, info_data_to_nlas,
+        link_get_info_data, NlLinkConf,
+    },
+    BareUdpConfig, BareUdpEtherType, BareUdpInterface, BaseInterface,
+};
+
+const IFLA_BAREUDP_PORT: u16 = 1;
+const IFLA_BAREUDP_ETHERTYPE: u16 = 2;
+
+pub(crate) fn np_bareudp_to_nmstate(
+    np_iface: &nispor::Iface,
+    base_iface: BaseInterface,
+) -> BareUdpInterface {
+    let bareudp = match link_get_info_data(np_iface.index) {
+        Ok(Some(info_data)) => parse_bareudp_conf(&info_data),
+        Ok(None) => None,
+        Err(e) => {
+            log::warn!(
+                "Failed to query bareudp configuration of {}: {e}",
+                np_iface.name
+            );
+            None
+        }
+    };
+    BareUdpInterface {
+        base: base_iface,
+        bareudp,
+    }
+}
+
+fn parse_bareudp_conf(info_data: &InfoData) -> Option<BareUdpConfig> {
+    let mut conf = BareUdpConfig::new();
+    for nla in info_data_to_nlas(info_data) {
+        let data = default_nla_value(&nla);
+        match nla.kind() {
+            IFLA_BAREUDP_PORT => {
+                conf.dst_port = parse_u16_be(&data).unwrap_or_default()
+            }
+            IFLA_BAREUDP_ETHERTYPE => {
+                let ethertype = parse_u16_be(&data).unwrap_or_default();
+                match BareUdpEtherType::try_from(ethertype) {
+                    Ok(v) => conf.ethertype = v,
+                    Err(v) => {
+                        log::warn!(
+                            "Unsupported bareudp ethertype {v:#06x}, \
+                            ignoring bareudp configuration"
+                        );
+                        return None;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    Some(conf)
+}
+
+pub(crate) fn nms_bareudp_conf_to_nl(conf: &BareUdpConfig) -> NlLinkConf {
+    let ethertype: u16 = conf.ethertype.into();
+    let mut nl_conf = NlLinkConf::new(InfoKind::Other("bareudp".to_string()));
+    nl_conf.info_data = Some(info_data_from_nlas(&[
+        DefaultNla::new(
+            IFLA_BAREUDP_PORT,
+            conf.dst_port.to_be_bytes().to_vec(),
+        ),
+        DefaultNla::new(
+            IFLA_BAREUDP_ETHERTYPE,
+            ethertype.to_be_bytes().to_vec(),
+        ),
+    ]));
+    nl_conf
+}
@@ -20,6 +20,7 @@ fn np_iface_type_to_nmstate(
         nispor::IfaceType::MacSec => InterfaceType::MacSec,
         nispor::IfaceType::MacVlan => InterfaceType::MacVlan,
         nispor::IfaceType::MacVtap => InterfaceType::MacVtap,
+        nispor::IfaceType::IpVlan => InterfaceType::IpVlan,
         nispor::IfaceType::OpenvSwitch => InterfaceType::OvsInterface,
         nispor::IfaceType::Veth => InterfaceType::Veth,
         nispor::IfaceType::Vlan => InterfaceType::Vlan,
@@ -28,7 +29,19 @@ fn np_iface_type_to_nmstate(
         nispor::IfaceType::Ipoib => InterfaceType::InfiniBand,
         nispor::IfaceType::Tun => InterfaceType::Tun,
         nispor::IfaceType::Xfrm => InterfaceType::Xfrm,
-        nispor::IfaceType::Other(v) => InterfaceType::Other(v.to_lowercase()),
+        // Nispor names the link kinds known by netlink-packet-route after its
+        // `InfoKind` variants, e.g. `gretun` for `gre`.
+        nispor::IfaceType::Other(v) => match v.as_str() {
+            "wireguard" => InterfaceType::WireGuard,
+            "geneve" => InterfaceType::Geneve,
+            "bareudp" => InterfaceType::BareUdp,
+            "gre" | "gretun" => InterfaceType::Gre,
+            "gretap" => InterfaceType::GreTap,
+            "ipip" | "iptun" => InterfaceType::Ipip,
+            "sit" | "sittun" => InterfaceType::Sit,
+            "ip6tnl" => InterfaceType::Ip6Tnl,
+            _ => InterfaceType::Other(v.to_lowercase()),
+        },
         _ => InterfaceType::Other(format!("{np_iface_type:?}").to_lowercase()),
     }
 }
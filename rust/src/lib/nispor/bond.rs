@@ -1,10 +1,17 @@
 use log::warn;
 
 use crate::{
+    nispor::{
+        netlink::link_set_bond_port_prio,
+        sysfs::{
+            read_sysfs, set_iface_admin_down, sysfs_iface_path, write_sysfs,
+        },
+    },
     BaseInterface, BondAdSelect, BondAllPortsActive, BondArpAllTargets,
     BondArpValidate, BondConfig, BondFailOverMac, BondInterface, BondLacpRate,
     BondMode, BondOptions, BondPortConfig, BondPrimaryReselect,
-    BondXmitHashPolicy,
+    BondXmitHashPolicy, ErrorKind, Interface, InterfaceType, Interfaces,
+    MergedInterface, MergedInterfaces, NmstateError, VerificationDiff,
 };
 
 pub(crate) fn np_bond_to_nmstate(
@@ -220,3 +227,337 @@ fn np_bond_options_to_nmstate(np_iface: &nispor::Iface) -> BondOptions {
     }
     options
 }
+
+// Nispor can only create bond interface, the bond mode, bond options and
+// bond port queue ID are applied through sysfs, bond port priority is applied
+// through netlink.
+pub(crate) fn validate_bond_for_kernel_mode(
+    merged_iface: &MergedInterface,
+) -> Result<(), NmstateError> {
+    let apply_iface = match merged_iface.for_apply.as_ref() {
+        Some(Interface::Bond(a)) => a,
+        _ => return Ok(()),
+    };
+    if apply_iface
+        .bond
+        .as_ref()
+        .and_then(|c| c.options.as_ref())
+        .and_then(|o| o.balance_slb)
+        == Some(true)
+    {
+        return Err(NmstateError::new(
+            ErrorKind::NotSupportedError,
+            format!(
+                "The bond option balance-slb of bond {} is only supported \
+                by NetworkManager",
+                apply_iface.base.name
+            ),
+        ));
+    }
+    Ok(())
+}
+
+// The bond option difference is ignored by generic verification as
+// NetworkManager might refuse some of them. In kernel mode, nmstate wrote the
+// options to sysfs by itself, the kernel silently ignoring them should fail
+// the verification.
+pub(crate) fn nispor_verify_bond_opts(
+    merged_ifaces: &MergedInterfaces,
+    current: &Interfaces,
+) -> Result<(), NmstateError> {
+    for merged_iface in merged_ifaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_desired() && i.merged.is_up())
+    {
+        let mut des_iface = match merged_iface.for_verify.as_ref() {
+            Some(Interface::Bond(i)) => i.clone(),
+            _ => continue,
+        };
+        let mut cur_iface = match current
+            .get_iface(des_iface.base.name.as_str(), InterfaceType::Bond)
+        {
+            Some(Interface::Bond(i)) => i.clone(),
+            _ => continue,
+        };
+        des_iface.sanitize().ok();
+        cur_iface.sanitize().ok();
+        let des_opts =
+            match des_iface.bond.as_ref().and_then(|c| c.options.as_ref()) {
+                Some(o) => o,
+                None => continue,
+            };
+        let cur_opts = cur_iface
+            .bond
+            .as_ref()
+            .and_then(|c| c.options.as_ref())
+            .map(gen_bond_sysfs_opts)
+            .unwrap_or_default();
+        let des_value = serde_json::to_value(des_opts)?;
+        let cur_value = serde_json::to_value(
+            cur_iface.bond.as_ref().and_then(|c| c.options.as_ref()),
+        )?;
+        for (name, value) in gen_bond_sysfs_opts(des_opts) {
+            if cur_opts.contains(&(name, value.clone())) {
+                continue;
+            }
+            let desire = des_value.get(name).cloned().unwrap_or_default();
+            let current = cur_value.get(name).cloned().unwrap_or_default();
+            let e = NmstateError::new_verification_error(
+                format!(
+                    "Verification failure: {}.interface.link-aggregation.\
+                    options.{name} desire '{desire}', current '{current}'",
+                    des_iface.base.name
+                ),
+                VerificationDiff::new(
+                    format!(
+                        "interfaces[name={}].link-aggregation.options.{name}",
+                        des_iface.base.name
+                    ),
+                    desire,
+                    current,
+                ),
+            );
+            log::error!("{e}");
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+// Apply bond mode and options through sysfs. The bond mode can only be
+// changed when bond is down and has no port, hence the ports are detached
+// here and returned for caller to attach them back.
+pub(crate) fn apply_bond_conf_via_sysfs(
+    merged_iface: &MergedInterface,
+) -> Result<Vec<String>, NmstateError> {
+    let mut detached_ports = Vec::new();
+    let apply_iface = match merged_iface.for_apply.as_ref() {
+        Some(Interface::Bond(i)) => i,
+        _ => return Ok(detached_ports),
+    };
+    let cur_iface = match merged_iface.current.as_ref() {
+        Some(Interface::Bond(i)) => Some(i),
+        _ => None,
+    };
+    let bond_name = apply_iface.base.name.as_str();
+    let bond_conf = match apply_iface.bond.as_ref() {
+        Some(c) => c,
+        None => return Ok(detached_ports),
+    };
+
+    if let Some(mode) = bond_conf.mode {
+        let cur_mode = read_sysfs(&sysfs_iface_path(bond_name, "bonding/mode"))
+            .and_then(|s| s.split(' ').next().map(|m| m.to_string()));
+        if cur_mode.as_deref() != Some(mode.to_string().as_str()) {
+            log::info!("Changing bond {bond_name} mode to {mode}");
+            if let Some(ports) =
+                read_sysfs(&sysfs_iface_path(bond_name, "bonding/slaves"))
+            {
+                for port in ports.split(' ').filter(|p| !p.is_empty()) {
+                    write_sysfs(
+                        &sysfs_iface_path(bond_name, "bonding/slaves"),
+                        &format!("-{port}"),
+                    )?;
+                    detached_ports.push(port.to_string());
+                }
+            }
+            set_iface_admin_down(bond_name)?;
+            write_sysfs(
+                &sysfs_iface_path(bond_name, "bonding/mode"),
+                &mode.to_string(),
+            )?;
+        }
+    }
+
+    if let Some(opts) = bond_conf.options.as_ref() {
+        let cur_opts = if detached_ports.is_empty() {
+            cur_iface
+                .and_then(|i| i.bond.as_ref())
+                .and_then(|c| c.options.as_ref())
+                .map(gen_bond_sysfs_opts)
+                .unwrap_or_default()
+        } else {
+            // Kernel reset all options on mode change
+            Vec::new()
+        };
+        for (name, value) in gen_bond_sysfs_opts(opts) {
+            if cur_opts.contains(&(name, value.clone())) {
+                continue;
+            }
+            if name == "arp_ip_target" {
+                apply_bond_arp_ip_target(bond_name, value.as_str())?;
+            } else {
+                write_sysfs(
+                    &sysfs_iface_path(bond_name, &format!("bonding/{name}")),
+                    value.as_str(),
+                )?;
+            }
+        }
+    }
+
+    // Only attach back ports which are still desired
+    if let Some(ports) = merged_iface.merged.ports() {
+        detached_ports.retain(|p| ports.contains(&p.as_str()));
+    }
+    Ok(detached_ports)
+}
+
+// The bond port options can only be set after port attached.
+// Kernel does not expose bond port priority in sysfs, we set it via netlink.
+pub(crate) fn apply_bond_ports_conf_via_sysfs(
+    merged_iface: &MergedInterface,
+) -> Result<(), NmstateError> {
+    let (apply_iface, cur_iface) = match (
+        merged_iface.for_apply.as_ref(),
+        merged_iface.current.as_ref(),
+    ) {
+        (Some(Interface::Bond(a)), Some(Interface::Bond(c))) => (a, Some(c)),
+        (Some(Interface::Bond(a)), _) => (a, None),
+        _ => return Ok(()),
+    };
+    for port_conf in apply_iface
+        .bond
+        .as_ref()
+        .and_then(|c| c.ports_config.as_deref())
+        .unwrap_or_default()
+    {
+        if let Some(queue_id) = port_conf.queue_id {
+            let cur_queue_id = cur_iface
+                .and_then(|c| c.get_port_conf(port_conf.name.as_str()))
+                .and_then(|c| c.queue_id);
+            if cur_queue_id != Some(queue_id) {
+                write_sysfs(
+                    &sysfs_iface_path(
+                        apply_iface.base.name.as_str(),
+                        "bonding/queue_id",
+                    ),
+                    &format!("{}:{}", port_conf.name, queue_id),
+                )?;
+            }
+        }
+        if let Some(priority) = port_conf.priority {
+            let cur_priority = cur_iface
+                .and_then(|c| c.get_port_conf(port_conf.name.as_str()))
+                .and_then(|c| c.priority);
+            if cur_priority != Some(priority) {
+                link_set_bond_port_prio(port_conf.name.as_str(), priority)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// The kernel sysfs `arp_ip_target` expect `+<ip>` for adding and `-<ip>` for
+// removal.
+fn apply_bond_arp_ip_target(
+    bond_name: &str,
+    targets: &str,
+) -> Result<(), NmstateError> {
+    let path = sysfs_iface_path(bond_name, "bonding/arp_ip_target");
+    let cur_targets = read_sysfs(&path).unwrap_or_default();
+    let cur_targets: Vec<&str> =
+        cur_targets.split(' ').filter(|t| !t.is_empty()).collect();
+    let des_targets: Vec<&str> = targets
+        .split(',')
+        .map(|t| t.trim())
+        .filter(|t| !t.is_empty())
+        .collect();
+    for target in cur_targets.as_slice() {
+        if !des_targets.contains(target) {
+            write_sysfs(&path, &format!("-{target}"))?;
+        }
+    }
+    for target in des_targets.as_slice() {
+        if !cur_targets.contains(target) {
+            write_sysfs(&path, &format!("+{target}"))?;
+        }
+    }
+    Ok(())
+}
+
+// The order matters, `arp_ip_target` should be set before `arp_interval`.
+pub(crate) fn gen_bond_sysfs_opts(
+    opts: &BondOptions,
+) -> Vec<(&'static str, String)> {
+    let mut ret = Vec::new();
+    if let Some(v) = opts.ad_actor_sys_prio {
+        ret.push(("ad_actor_sys_prio", v.to_string()));
+    }
+    if let Some(v) = opts.ad_actor_system.as_ref() {
+        ret.push(("ad_actor_system", v.to_string()));
+    }
+    if let Some(v) = opts.ad_select.as_ref() {
+        ret.push(("ad_select", v.to_string()));
+    }
+    if let Some(v) = opts.ad_user_port_key {
+        ret.push(("ad_user_port_key", v.to_string()));
+    }
+    if let Some(v) = opts.all_slaves_active {
+        ret.push(("all_slaves_active", u8::from(v).to_string()));
+    }
+    if let Some(v) = opts.arp_all_targets.as_ref() {
+        ret.push(("arp_all_targets", v.to_string()));
+    }
+    if let Some(v) = opts.arp_ip_target.as_ref() {
+        ret.push(("arp_ip_target", v.to_string()));
+    }
+    if let Some(v) = opts.arp_interval {
+        ret.push(("arp_interval", v.to_string()));
+    }
+    if let Some(v) = opts.arp_validate.as_ref() {
+        ret.push(("arp_validate", v.to_string()));
+    }
+    if let Some(v) = opts.arp_missed_max {
+        ret.push(("arp_missed_max", v.to_string()));
+    }
+    if let Some(v) = opts.downdelay {
+        ret.push(("downdelay", v.to_string()));
+    }
+    if let Some(v) = opts.fail_over_mac.as_ref() {
+        ret.push(("fail_over_mac", v.to_string()));
+    }
+    if let Some(v) = opts.lacp_rate.as_ref() {
+        ret.push(("lacp_rate", v.to_string()));
+    }
+    if let Some(v) = opts.lp_interval {
+        ret.push(("lp_interval", v.to_string()));
+    }
+    if let Some(v) = opts.miimon {
+        ret.push(("miimon", v.to_string()));
+    }
+    if let Some(v) = opts.min_links {
+        ret.push(("min_links", v.to_string()));
+    }
+    if let Some(v) = opts.num_grat_arp {
+        ret.push(("num_grat_arp", v.to_string()));
+    }
+    if let Some(v) = opts.num_unsol_na {
+        ret.push(("num_unsol_na", v.to_string()));
+    }
+    if let Some(v) = opts.packets_per_slave {
+        ret.push(("packets_per_slave", v.to_string()));
+    }
+    if let Some(v) = opts.primary.as_ref() {
+        ret.push(("primary", v.to_string()));
+    }
+    if let Some(v) = opts.primary_reselect.as_ref() {
+        ret.push(("primary_reselect", v.to_string()));
+    }
+    if let Some(v) = opts.resend_igmp {
+        ret.push(("resend_igmp", v.to_string()));
+    }
+    if let Some(v) = opts.tlb_dynamic_lb {
+        ret.push(("tlb_dynamic_lb", u8::from(v).to_string()));
+    }
+    if let Some(v) = opts.updelay {
+        ret.push(("updelay", v.to_string()));
+    }
+    if let Some(v) = opts.use_carrier {
+        ret.push(("use_carrier", u8::from(v).to_string()));
+    }
+    if let Some(v) = opts.xmit_hash_policy.as_ref() {
+        ret.push(("xmit_hash_policy", v.to_string()));
+    }
+    ret
+}
@@ -0,0 +1,412 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Kernel mode has no daemon holding the checkpoint for us, hence we store the
+// pre-apply network state along with the desired state into a file under
+// `/run` which is purged on reboot. The revert state is generated at rollback
+// time via `NetworkState::generate_revert()`.
+//
+// Since no daemon is running the rollback timer, when the checkpoint is not
+// committed by the apply action, a transient systemd timer is armed to invoke
+// `nmstatectl rollback <checkpoint>` on timeout. When systemd is not
+// available, the expired checkpoint will be rolled back by next nmstate
+// kernel mode action.
+
+use std::io::{Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    query_apply::entered_netns, ErrorKind, NetworkState, NmstateError,
+};
+
+const KERNEL_CHECKPOINT_DIR: &str = "/run/nmstate/checkpoint";
+const KERNEL_CHECKPOINT_FILE_EXT: &str = "yml";
+const ROLLBACK_TIMER_UNIT_PREFIX: &str = "nmstate-checkpoint-";
+const NMSTATECTL_BIN: &str = "nmstatectl";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct KernelCheckpoint {
+    #[serde(skip)]
+    pub(crate) name: String,
+    /// Seconds since UNIX epoch when this checkpoint should be rolled back
+    pub(crate) expire: u64,
+    pub(crate) desired: NetworkState,
+    pub(crate) pre_apply: NetworkState,
+    /// The network namespace this checkpoint created in, `None` means the
+    /// network namespace of nmstate process.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) netns: Option<String>,
+    /// Route table names created by this apply, only stored to
+    /// `/etc/iproute2/rt_tables.d` when this checkpoint is committed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) route_table_names: Vec<(u32, String)>,
+    /// The transient systemd timer unit armed to rollback this checkpoint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) rollback_timer: Option<String>,
+}
+
+impl KernelCheckpoint {
+    pub(crate) fn is_expired(&self) -> bool {
+        now_in_secs() >= self.expire
+    }
+
+    /// The state to apply for reverting the changes of this checkpoint.
+    pub(crate) fn revert_state(&self) -> Result<NetworkState, NmstateError> {
+        let mut revert_state = self.desired.generate_revert(&self.pre_apply)?;
+        revert_state.set_kernel_only(true);
+        Ok(revert_state)
+    }
+}
+
+pub(crate) fn is_kernel_checkpoint(checkpoint: &str) -> bool {
+    checkpoint.starts_with(KERNEL_CHECKPOINT_DIR)
+}
+
+pub(crate) fn nispor_checkpoint_create(
+    desired: &NetworkState,
+    pre_apply: &NetworkState,
+    route_table_names: &[(u32, String)],
+    timeout: u32,
+) -> Result<String, NmstateError> {
+    checkpoint_create(
+        KERNEL_CHECKPOINT_DIR,
+        desired,
+        pre_apply,
+        route_table_names,
+        timeout,
+    )
+}
+
+pub(crate) fn checkpoint_create(
+    dir_path: &str,
+    desired: &NetworkState,
+    pre_apply: &NetworkState,
+    route_table_names: &[(u32, String)],
+    timeout: u32,
+) -> Result<String, NmstateError> {
+    if let Some(cp) = checkpoints(dir_path)?
+        .into_iter()
+        .find(|cp| !cp.is_expired())
+    {
+        return Err(NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Another kernel mode checkpoint {} is still active, please \
+                commit or rollback it first",
+                cp.name
+            ),
+        ));
+    }
+
+    std::fs::create_dir_all(dir_path).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PermissionError,
+            format!("Failed to create folder {dir_path}: {e}"),
+        )
+    })?;
+
+    let name = format!("{dir_path}/{}", uuid::Uuid::new_v4().simple());
+    let cp = KernelCheckpoint {
+        name: name.clone(),
+        expire: now_in_secs() + timeout as u64,
+        desired: desired.clone(),
+        pre_apply: pre_apply.clone(),
+        netns: entered_netns(),
+        route_table_names: route_table_names.to_vec(),
+        rollback_timer: None,
+    };
+    write_checkpoint(dir_path, &cp)?;
+    Ok(name)
+}
+
+pub(crate) fn nispor_checkpoint_timeout_extend(
+    checkpoint: &str,
+    added_time_sec: u32,
+) -> Result<(), NmstateError> {
+    checkpoint_timeout_extend(KERNEL_CHECKPOINT_DIR, checkpoint, added_time_sec)
+}
+
+pub(crate) fn checkpoint_timeout_extend(
+    dir_path: &str,
+    checkpoint: &str,
+    added_time_sec: u32,
+) -> Result<(), NmstateError> {
+    let mut cp = checkpoint_get(dir_path, checkpoint)?;
+    cp.expire = now_in_secs() + added_time_sec as u64;
+    write_checkpoint(dir_path, &cp)
+}
+
+/// Arm a transient systemd timer to rollback the checkpoint on timeout.
+/// Failure is only logged as expired checkpoint will also be rolled back by
+/// next kernel mode action.
+pub(crate) fn nispor_checkpoint_arm_rollback_timer(
+    checkpoint: &str,
+    timeout: u32,
+) -> Result<(), NmstateError> {
+    let mut cp = nispor_checkpoint_get(checkpoint)?;
+    let unit = rollback_timer_unit(checkpoint)?;
+    let args = rollback_timer_args(&unit, checkpoint, timeout, &nmstatectl());
+    match std::process::Command::new("systemd-run")
+        .args(&args)
+        .output()
+    {
+        Ok(output) if output.status.success() => {
+            log::info!(
+                "Armed systemd timer {unit} to rollback checkpoint \
+                {checkpoint} in {timeout} seconds"
+            );
+            cp.rollback_timer = Some(unit);
+            write_checkpoint(KERNEL_CHECKPOINT_DIR, &cp)
+        }
+        Ok(output) => {
+            log::warn!(
+                "Failed to arm systemd timer for checkpoint {checkpoint}, \
+                it will be rolled back by next kernel mode action once \
+                expired: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            Ok(())
+        }
+        Err(e) => {
+            log::warn!(
+                "Failed to invoke systemd-run for checkpoint {checkpoint}, \
+                it will be rolled back by next kernel mode action once \
+                expired: {e}"
+            );
+            Ok(())
+        }
+    }
+}
+
+// Stop the rollback timer of checkpoint, failure is ignored as the timer
+// might already elapsed.
+fn disarm_rollback_timer(cp: &KernelCheckpoint) {
+    if let Some(unit) = cp.rollback_timer.as_deref() {
+        match std::process::Command::new("systemctl")
+            .args(["stop", "--quiet", &format!("{unit}.timer")])
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                log::info!("Stopped systemd timer {unit}");
+            }
+            Ok(output) => {
+                log::debug!(
+                    "Failed to stop systemd timer {unit}: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Err(e) => {
+                log::debug!("Failed to stop systemd timer {unit}: {e}");
+            }
+        }
+    }
+}
+
+pub(crate) fn rollback_timer_unit(
+    checkpoint: &str,
+) -> Result<String, NmstateError> {
+    let file_path = checkpoint_file_path(KERNEL_CHECKPOINT_DIR, checkpoint)?;
+    let id = file_path
+        .rsplit('/')
+        .next()
+        .and_then(|f| f.strip_suffix(&format!(".{KERNEL_CHECKPOINT_FILE_EXT}")))
+        .unwrap_or_default();
+    Ok(format!("{ROLLBACK_TIMER_UNIT_PREFIX}{id}"))
+}
+
+pub(crate) fn rollback_timer_args(
+    unit: &str,
+    checkpoint: &str,
+    timeout: u32,
+    nmstatectl: &str,
+) -> Vec<String> {
+    vec![
+        "--quiet".to_string(),
+        format!("--unit={unit}"),
+        format!("--on-active={timeout}s"),
+        "--timer-property=AccuracySec=1s".to_string(),
+        nmstatectl.to_string(),
+        "rollback".to_string(),
+        checkpoint.to_string(),
+    ]
+}
+
+// Prefer the nmstatectl binary we are running as, otherwise let systemd
+// search it in PATH.
+fn nmstatectl() -> String {
+    std::env::current_exe()
+        .ok()
+        .filter(|p| {
+            p.file_name().and_then(|n| n.to_str()) == Some(NMSTATECTL_BIN)
+        })
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| NMSTATECTL_BIN.to_string())
+}
+
+/// Retrieve checkpoint by name, empty name means the last created checkpoint.
+pub(crate) fn nispor_checkpoint_get(
+    checkpoint: &str,
+) -> Result<KernelCheckpoint, NmstateError> {
+    checkpoint_get(KERNEL_CHECKPOINT_DIR, checkpoint)
+}
+
+pub(crate) fn checkpoint_get(
+    dir_path: &str,
+    checkpoint: &str,
+) -> Result<KernelCheckpoint, NmstateError> {
+    if checkpoint.is_empty() {
+        return checkpoints(dir_path)?.pop().ok_or_else(|| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                "No active kernel mode checkpoint".to_string(),
+            )
+        });
+    }
+    let file_path = checkpoint_file_path(dir_path, checkpoint)?;
+    read_checkpoint(&file_path)
+}
+
+/// Remove the checkpoint and its rollback timer.
+pub(crate) fn nispor_checkpoint_destroy(
+    checkpoint: &str,
+) -> Result<(), NmstateError> {
+    if let Ok(cp) = nispor_checkpoint_get(checkpoint) {
+        disarm_rollback_timer(&cp);
+    }
+    checkpoint_destroy(KERNEL_CHECKPOINT_DIR, checkpoint)
+}
+
+pub(crate) fn checkpoint_destroy(
+    dir_path: &str,
+    checkpoint: &str,
+) -> Result<(), NmstateError> {
+    let file_path = checkpoint_file_path(dir_path, checkpoint)?;
+    std::fs::remove_file(&file_path).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Failed to remove kernel checkpoint {checkpoint}: {e}"),
+        )
+    })
+}
+
+/// All kernel mode checkpoints sorted by creation time.
+pub(crate) fn nispor_checkpoints() -> Result<Vec<KernelCheckpoint>, NmstateError>
+{
+    checkpoints(KERNEL_CHECKPOINT_DIR)
+}
+
+pub(crate) fn checkpoints(
+    dir_path: &str,
+) -> Result<Vec<KernelCheckpoint>, NmstateError> {
+    let mut ret: Vec<(SystemTime, KernelCheckpoint)> = Vec::new();
+    let dir = match std::fs::read_dir(dir_path) {
+        Ok(d) => d,
+        Err(e) => {
+            log::debug!("Failed to read {dir_path}: {e}");
+            return Ok(Vec::new());
+        }
+    };
+    for entry in dir.flatten() {
+        let file_path = entry.path();
+        if file_path.extension().and_then(|e| e.to_str())
+            != Some(KERNEL_CHECKPOINT_FILE_EXT)
+        {
+            continue;
+        }
+        let file_path = file_path.display().to_string();
+        let created = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .unwrap_or(UNIX_EPOCH);
+        match read_checkpoint(&file_path) {
+            Ok(cp) => ret.push((created, cp)),
+            Err(e) => {
+                log::warn!("Ignoring invalid kernel checkpoint: {e}");
+            }
+        }
+    }
+    ret.sort_unstable_by_key(|(created, _)| *created);
+    Ok(ret.into_iter().map(|(_, cp)| cp).collect())
+}
+
+pub(crate) fn checkpoint_file_path(
+    dir_path: &str,
+    checkpoint: &str,
+) -> Result<String, NmstateError> {
+    // Only allow checkpoint generated by nmstate, do not follow any path
+    // provided by user.
+    let is_valid = checkpoint
+        .strip_prefix(dir_path)
+        .and_then(|s| s.strip_prefix('/'))
+        .map(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_hexdigit()))
+        .unwrap_or_default();
+    if is_valid {
+        Ok(format!("{checkpoint}.{KERNEL_CHECKPOINT_FILE_EXT}"))
+    } else {
+        Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid kernel mode checkpoint {checkpoint}"),
+        ))
+    }
+}
+
+fn read_checkpoint(file_path: &str) -> Result<KernelCheckpoint, NmstateError> {
+    let mut content = String::new();
+    std::fs::File::open(file_path)
+        .and_then(|mut fd| fd.read_to_string(&mut content))
+        .map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Failed to read kernel checkpoint {file_path}: {e}"),
+            )
+        })?;
+    let mut cp: KernelCheckpoint =
+        serde_yaml::from_str(&content).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Invalid kernel checkpoint file {file_path}: {e}"),
+            )
+        })?;
+    cp.name = file_path
+        .strip_suffix(&format!(".{KERNEL_CHECKPOINT_FILE_EXT}"))
+        .unwrap_or(file_path)
+        .to_string();
+    Ok(cp)
+}
+
+fn write_checkpoint(
+    dir_path: &str,
+    cp: &KernelCheckpoint,
+) -> Result<(), NmstateError> {
+    let file_path = checkpoint_file_path(dir_path, &cp.name)?;
+    let content = serde_yaml::to_string(cp).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::Bug,
+            format!("Failed to serialize kernel checkpoint: {e}"),
+        )
+    })?;
+    // The pre-apply state contains secrets, only root can read it.
+    std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&file_path)
+        .and_then(|mut fd| fd.write_all(content.as_bytes()))
+        .map_err(|e| {
+            NmstateError::new(
+                ErrorKind::PermissionError,
+                format!("Failed to write kernel checkpoint {file_path}: {e}"),
+            )
+        })
+}
+
+fn now_in_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
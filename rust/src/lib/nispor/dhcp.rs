@@ -0,0 +1,845 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Kernel mode has no NetworkManager running DHCP client for us, hence we use
+// mozim to obtain the DHCP lease and apply it via netlink:
+//  * Leased IP address is applied with infinite lifetime.
+//  * Gateway and classless routes are applied with `proto dhcp`.
+//  * DNS servers are appended to `/etc/resolv.conf`.
+//
+// The lease is stored in `/run/nmstate/dhcp` which is purged on reboot.
+// Since no daemon is running the lease timer, the lease in renewing,
+// rebinding or expired state will be refreshed by next nmstate kernel mode
+// apply, similar to kernel mode checkpoint. Hence the leased IP address is
+// not applied with lease time, otherwise kernel will purge it before the
+// renew.
+// The DHCP request is blocking for up to `DHCP_TIMEOUT` seconds, hence done
+// in tokio blocking thread.
+
+use std::future::Future;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::unix::fs::OpenOptionsExt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nispor::{
+        dns::append_dhcp_dns_to_etc,
+        ip::parse_ip_net,
+        netlink::{
+            addr_del, addr_replace, route_del, route_replace, NlRoute,
+            RTN_UNICAST, RTPROT_DHCP,
+        },
+        sysfs::{read_sysfs, sysfs_iface_path, write_sysfs},
+    },
+    query_apply::spawn_blocking_in_netns,
+    DhcpLease, DhcpLeaseRoute, DhcpLeaseState, Dhcpv4ClientId, Dhcpv6Duid,
+    ErrorKind, Interface, InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6,
+    MergedInterface, MergedNetworkState, NmstateError,
+};
+
+const DHCP_LEASE_DIR: &str = "/run/nmstate/dhcp";
+const DHCP_TIMEOUT: u32 = 45;
+const DHCP_POLL_WAIT_TIME: u32 = 5;
+// Same as NetworkManager default route metric of ethernet
+const DEFAULT_DHCP_ROUTE_METRIC: u32 = 100;
+const DEFAULT_ROUTE_TABLE: u32 = 254;
+const INFINITY_LIFE_TIME: u32 = u32::MAX;
+const DUID_TYPE_LLT: u16 = 1;
+const DUID_TYPE_LL: u16 = 3;
+const DUID_TYPE_UUID: u16 = 4;
+const ARPHRD_ETHER: u16 = 1;
+// Seconds between UNIX epoch and 2000-01-01 00:00:00 UTC, used by DUID-LLT
+const DUID_TIME_EPOCH: u64 = 946684800;
+const ETC_MACHINE_ID_PATH: &str = "/etc/machine-id";
+
+pub(crate) async fn nispor_dhcp_apply(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    for merged_iface in merged_state.interfaces.kernel_ifaces.values() {
+        let iface_name = merged_iface.merged.name();
+        if merged_iface.merged.is_absent() {
+            remove_lease(iface_name, false);
+            remove_lease(iface_name, true);
+            continue;
+        }
+        let base_iface = merged_iface.merged.base_iface();
+        if base_iface.can_have_ip() {
+            apply_dhcpv4(merged_iface, base_iface.ipv4.as_ref()).await?;
+            apply_dhcpv6(merged_iface, base_iface.ipv6.as_ref()).await?;
+        }
+    }
+    Ok(())
+}
+
+// Include DHCP lease with its state at current time into queried interface.
+// The leased IP address is applied with infinite lifetime, hence we mark it
+// as dynamic IP address using the lease time.
+pub(crate) fn append_dhcp_lease(
+    iface: &mut Interface,
+    running_config_only: bool,
+) {
+    let iface_name = iface.name().to_string();
+    let now = now_in_secs();
+    let base_iface = iface.base_iface_mut();
+    if let Some(ipv4) = base_iface.ipv4.as_mut().filter(|i| i.enabled) {
+        if let Some(stored) = read_lease(&iface_name, false) {
+            let mut lease = stored.lease;
+            lease.state = lease.state_at(now);
+            ipv4.dhcp = Some(true);
+            mark_leased_addr(
+                &mut ipv4.addresses,
+                &lease,
+                now,
+                running_config_only,
+            );
+            if !running_config_only {
+                ipv4.dhcp_lease = Some(lease);
+            }
+        }
+    }
+    if let Some(ipv6) = base_iface.ipv6.as_mut().filter(|i| i.enabled) {
+        if let Some(stored) = read_lease(&iface_name, true) {
+            let mut lease = stored.lease;
+            lease.state = lease.state_at(now);
+            ipv6.dhcp = Some(true);
+            mark_leased_addr(
+                &mut ipv6.addresses,
+                &lease,
+                now,
+                running_config_only,
+            );
+            if !running_config_only {
+                ipv6.dhcp_lease = Some(lease);
+            }
+        }
+    }
+}
+
+// Set lifetime of leased IP address to the remaining lease time or remove it
+// when querying running config only.
+pub(crate) fn mark_leased_addr(
+    addresses: &mut Option<Vec<InterfaceIpAddr>>,
+    lease: &DhcpLease,
+    now: u64,
+    running_config_only: bool,
+) {
+    let (ip, addrs) = match (lease.address.as_ref(), addresses.as_mut()) {
+        (Some(i), Some(a)) => (i, a),
+        _ => return,
+    };
+    let is_leased = |addr: &InterfaceIpAddr| {
+        &addr.ip == ip && addr.prefix_length == lease.prefix_length
+    };
+    if running_config_only {
+        addrs.retain(|a| !is_leased(a));
+    } else if let Some(addr) = addrs.iter_mut().find(|a| is_leased(a)) {
+        addr.valid_life_time = Some(format!("{}sec", lease.remaining_at(now)));
+        addr.preferred_life_time =
+            Some(format!("{}sec", lease.preferred_remaining_at(now)));
+    }
+}
+
+async fn apply_dhcpv4(
+    merged_iface: &MergedInterface,
+    ipv4: Option<&InterfaceIpv4>,
+) -> Result<(), NmstateError> {
+    let iface_name = merged_iface.merged.name();
+    let old_lease = read_lease(iface_name, false);
+    // Only take over the interface desired or DHCP started by us.
+    if !merged_iface.is_changed() && old_lease.is_none() {
+        return Ok(());
+    }
+    let ipv4 = match ipv4.filter(|i| i.is_auto()) {
+        Some(i) => i,
+        None => {
+            if let Some(old_lease) = old_lease {
+                stop_dhcp(
+                    iface_name,
+                    false,
+                    &old_lease,
+                    ipv4.filter(|i| i.enabled)
+                        .and_then(|i| i.addresses.as_deref())
+                        .unwrap_or_default(),
+                )?;
+            }
+            return Ok(());
+        }
+    };
+
+    let route_conf = DhcpRouteConf {
+        auto_gateway: ipv4.auto_gateway != Some(false),
+        auto_routes: ipv4.auto_routes != Some(false),
+        table: ipv4.auto_table_id.unwrap_or(DEFAULT_ROUTE_TABLE),
+        metric: ipv4.auto_route_metric.unwrap_or(DEFAULT_DHCP_ROUTE_METRIC),
+    };
+    if let Some(lease) = refresh_lease(
+        merged_iface,
+        false,
+        old_lease.as_ref(),
+        route_conf,
+        || {
+            let iface_name = iface_name.to_string();
+            let ipv4 = ipv4.clone();
+            spawn_blocking_in_netns(move || dhcpv4_request(&iface_name, &ipv4))
+        },
+    )
+    .await?
+    {
+        if ipv4.auto_dns != Some(false) {
+            append_dhcp_dns_to_etc(
+                lease.dns_servers.as_deref().unwrap_or_default(),
+                lease.dns_searches.as_deref().unwrap_or_default(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+async fn apply_dhcpv6(
+    merged_iface: &MergedInterface,
+    ipv6: Option<&InterfaceIpv6>,
+) -> Result<(), NmstateError> {
+    let iface_name = merged_iface.merged.name();
+    if merged_iface.is_changed() {
+        if let Some(ipv6) = ipv6.filter(|i| i.enabled) {
+            set_ipv6_autoconf(iface_name, ipv6.autoconf == Some(true))?;
+        }
+    }
+
+    let old_lease = read_lease(iface_name, true);
+    if !merged_iface.is_changed() && old_lease.is_none() {
+        return Ok(());
+    }
+    let ipv6 = match ipv6.filter(|i| i.enabled && i.dhcp == Some(true)) {
+        Some(i) => i,
+        None => {
+            if let Some(old_lease) = old_lease {
+                stop_dhcp(
+                    iface_name,
+                    true,
+                    &old_lease,
+                    ipv6.filter(|i| i.enabled)
+                        .and_then(|i| i.addresses.as_deref())
+                        .unwrap_or_default(),
+                )?;
+            }
+            return Ok(());
+        }
+    };
+
+    // DHCPv6 does not provide routes, IPv6 routes are learned from router
+    // advertisement by kernel when autoconf enabled.
+    refresh_lease(
+        merged_iface,
+        true,
+        old_lease.as_ref(),
+        DhcpRouteConf::default(),
+        || {
+            let iface_name = iface_name.to_string();
+            let ipv6 = ipv6.clone();
+            spawn_blocking_in_netns(move || dhcpv6_request(&iface_name, &ipv6))
+        },
+    )
+    .await?;
+    Ok(())
+}
+
+// The DHCP lease state machine:
+//  * Bound: Reapply the lease only when interface changed.
+//  * Renewing, Rebinding or Expired: Request new lease.
+// Return the lease been applied.
+async fn refresh_lease<F, Fut>(
+    merged_iface: &MergedInterface,
+    is_ipv6: bool,
+    old_lease: Option<&StoredDhcpLease>,
+    route_conf: DhcpRouteConf,
+    request: F,
+) -> Result<Option<DhcpLease>, NmstateError>
+where
+    F: FnOnce() -> Fut,
+    // Once `std::ops::AsyncFnOnce` is stable, use it instead
+    Fut: Future<Output = Result<DhcpLease, NmstateError>>,
+{
+    let iface_name = merged_iface.merged.name();
+    let lease = match old_lease {
+        Some(old)
+            if old.lease.state_at(now_in_secs()) == DhcpLeaseState::Bound =>
+        {
+            if !merged_iface.is_changed() {
+                return Ok(None);
+            }
+            if old.route_conf != route_conf {
+                purge_lease_routes(iface_name, old)?;
+            }
+            old.lease.clone()
+        }
+        _ => {
+            let lease = request().await?;
+            if let Some(old) = old_lease {
+                purge_lease_routes(iface_name, old)?;
+                if let Some(ip) = old.lease.address.as_ref() {
+                    if old.lease.address != lease.address {
+                        addr_del(iface_name, ip, old.lease.prefix_length)?;
+                    }
+                }
+            }
+            lease
+        }
+    };
+
+    if let Some(ip) = lease.address.as_ref() {
+        addr_replace(
+            iface_name,
+            ip,
+            lease.prefix_length,
+            INFINITY_LIFE_TIME,
+            INFINITY_LIFE_TIME,
+        )?;
+    }
+    for route in gen_lease_routes(iface_name, &lease, route_conf) {
+        route_replace(&route)?;
+    }
+    write_lease(
+        iface_name,
+        is_ipv6,
+        &StoredDhcpLease {
+            route_conf,
+            lease: lease.clone(),
+        },
+    )?;
+    Ok(Some(lease))
+}
+
+// Remove the leased IP address unless it is desired as static IP address,
+// the routes are always removed.
+fn stop_dhcp(
+    iface_name: &str,
+    is_ipv6: bool,
+    old: &StoredDhcpLease,
+    static_addrs: &[InterfaceIpAddr],
+) -> Result<(), NmstateError> {
+    log::info!("Stopping DHCP on {iface_name}");
+    purge_lease_routes(iface_name, old)?;
+    if let Some(ip) = old.lease.address.as_ref() {
+        if static_addrs
+            .iter()
+            .any(|a| &a.ip == ip && a.prefix_length == old.lease.prefix_length)
+        {
+            addr_replace(
+                iface_name,
+                ip,
+                old.lease.prefix_length,
+                INFINITY_LIFE_TIME,
+                INFINITY_LIFE_TIME,
+            )?;
+        } else {
+            addr_del(iface_name, ip, old.lease.prefix_length)?;
+        }
+    }
+    remove_lease(iface_name, is_ipv6);
+    Ok(())
+}
+
+fn purge_lease_routes(
+    iface_name: &str,
+    old: &StoredDhcpLease,
+) -> Result<(), NmstateError> {
+    for route in gen_lease_routes(iface_name, &old.lease, old.route_conf) {
+        route_del(&route)?;
+    }
+    Ok(())
+}
+
+// The lease file also holds the route configuration used when applying the
+// lease, so we can remove these routes afterwards.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct StoredDhcpLease {
+    route_conf: DhcpRouteConf,
+    lease: DhcpLease,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize,
+)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct DhcpRouteConf {
+    auto_gateway: bool,
+    auto_routes: bool,
+    table: u32,
+    metric: u32,
+}
+
+// RFC 3442: If the DHCP server returns both a Classless Static Routes option
+// and a Router option, the DHCP client MUST ignore the Router option.
+fn gen_lease_routes(
+    iface_name: &str,
+    lease: &DhcpLease,
+    route_conf: DhcpRouteConf,
+) -> Vec<NlRoute> {
+    let mut ret = Vec::new();
+    let metric = if route_conf.metric == 0 {
+        None
+    } else {
+        Some(route_conf.metric)
+    };
+    let classless_routes = lease.routes.as_deref().unwrap_or_default();
+    if route_conf.auto_routes {
+        for route in classless_routes {
+            let (dst, dst_len) = match parse_ip_net(&route.destination) {
+                Ok(d) => d,
+                Err(e) => {
+                    log::warn!("Ignoring invalid DHCP route destination: {e}");
+                    continue;
+                }
+            };
+            if dst_len == 0 && !route_conf.auto_gateway {
+                continue;
+            }
+            ret.push(NlRoute {
+                oif: Some(iface_name.to_string()),
+                dst,
+                dst_len,
+                gateway: if route.next_hop_address.is_unspecified() {
+                    None
+                } else {
+                    Some(route.next_hop_address)
+                },
+                table: route_conf.table,
+                metric,
+                protocol: RTPROT_DHCP,
+                nexthop_id: None,
+                route_type: RTN_UNICAST,
+                scope: None,
+                prefsrc: None,
+                metrics: Vec::new(),
+                onlink: false,
+            });
+        }
+    }
+    if route_conf.auto_gateway && classless_routes.is_empty() {
+        if let Some(gateway) = lease.gateways.as_deref().and_then(|g| g.first())
+        {
+            ret.push(NlRoute {
+                oif: Some(iface_name.to_string()),
+                dst: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                dst_len: 0,
+                gateway: Some(*gateway),
+                table: route_conf.table,
+                metric,
+                protocol: RTPROT_DHCP,
+                nexthop_id: None,
+                route_type: RTN_UNICAST,
+                scope: None,
+                prefsrc: None,
+                metrics: Vec::new(),
+                onlink: false,
+            });
+        }
+    }
+    ret
+}
+
+fn dhcpv4_request(
+    iface_name: &str,
+    ipv4: &InterfaceIpv4,
+) -> Result<DhcpLease, NmstateError> {
+    let mut config = mozim::DhcpV4Config::new(iface_name);
+    config.set_timeout(DHCP_TIMEOUT);
+    match ipv4.dhcp_client_id.as_ref() {
+        Some(Dhcpv4ClientId::LinkLayerAddress) => {
+            config.use_mac_as_client_id();
+        }
+        Some(Dhcpv4ClientId::IaidPlusDuid) => {
+            return Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "DHCPv4 client ID {} is not supported in kernel mode",
+                    Dhcpv4ClientId::IaidPlusDuid
+                ),
+            ));
+        }
+        Some(Dhcpv4ClientId::Other(s)) => {
+            let client_id = parse_hex_str(s)?;
+            if client_id.len() < 2 {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid DHCPv4 client ID {s}, should be hex string \
+                        with type byte as prefix"
+                    ),
+                ));
+            }
+            config.set_client_id(client_id[0], &client_id[1..]);
+        }
+        None => (),
+    }
+    if let Some(host_name) = get_dhcp_host_name(
+        ipv4.dhcp_send_hostname,
+        ipv4.dhcp_custom_hostname.as_deref(),
+    ) {
+        config.set_host_name(&host_name);
+    }
+
+    log::info!("Requesting DHCPv4 lease on {iface_name}");
+    let mut cli =
+        mozim::DhcpV4Client::init(config, None).map_err(mozim_error)?;
+    let deadline = now_in_secs() + DHCP_TIMEOUT as u64;
+    while now_in_secs() < deadline {
+        for event in cli.poll(DHCP_POLL_WAIT_TIME).map_err(mozim_error)? {
+            if let Some(lease) = cli.process(event).map_err(mozim_error)? {
+                log::info!(
+                    "Got DHCPv4 lease {} on {}",
+                    lease.yiaddr,
+                    iface_name
+                );
+                return Ok(mozim_v4_lease_to_nmstate(&lease));
+            }
+        }
+    }
+    Err(NmstateError::new(
+        ErrorKind::PluginFailure,
+        format!(
+            "Failed to obtain DHCPv4 lease on {iface_name} within \
+            {DHCP_TIMEOUT} seconds"
+        ),
+    ))
+}
+
+fn dhcpv6_request(
+    iface_name: &str,
+    ipv6: &InterfaceIpv6,
+) -> Result<DhcpLease, NmstateError> {
+    let mut config = mozim::DhcpV6Config::new(
+        iface_name,
+        mozim::DhcpV6IaType::NonTemporaryAddresses,
+    );
+    config.set_timeout(DHCP_TIMEOUT);
+    if let Some(duid) = ipv6.dhcp_duid.as_ref() {
+        config.set_duid(mozim::Dhcpv6Duid::Other(gen_duid(iface_name, duid)?));
+    }
+
+    log::info!("Requesting DHCPv6 lease on {iface_name}");
+    let mut cli =
+        mozim::DhcpV6Client::init(config, None).map_err(mozim_error)?;
+    let deadline = now_in_secs() + DHCP_TIMEOUT as u64;
+    while now_in_secs() < deadline {
+        for event in cli.poll(DHCP_POLL_WAIT_TIME).map_err(mozim_error)? {
+            if let Some(lease) = cli.process(event).map_err(mozim_error)? {
+                log::info!("Got DHCPv6 lease {} on {}", lease.addr, iface_name);
+                return Ok(mozim_v6_lease_to_nmstate(&lease));
+            }
+        }
+    }
+    Err(NmstateError::new(
+        ErrorKind::PluginFailure,
+        format!(
+            "Failed to obtain DHCPv6 lease on {iface_name} within \
+            {DHCP_TIMEOUT} seconds"
+        ),
+    ))
+}
+
+fn mozim_v4_lease_to_nmstate(mozim_lease: &mozim::DhcpV4Lease) -> DhcpLease {
+    let mut lease = DhcpLease {
+        address: Some(IpAddr::V4(mozim_lease.yiaddr)),
+        prefix_length: u32::from(mozim_lease.subnet_mask).count_ones() as u8,
+        server: Some(IpAddr::V4(mozim_lease.srv_id)),
+        obtained: now_in_secs(),
+        lease_time: mozim_lease.lease_time,
+        renew_time: mozim_lease.t1,
+        rebind_time: mozim_lease.t2,
+        gateways: mozim_lease
+            .gateways
+            .as_ref()
+            .map(|gws| gws.iter().map(|g| IpAddr::V4(*g)).collect()),
+        routes: mozim_lease.classless_routes.as_ref().map(|rts| {
+            rts.iter()
+                .map(|r| DhcpLeaseRoute {
+                    destination: format!(
+                        "{}/{}",
+                        r.destination, r.prefix_length
+                    ),
+                    next_hop_address: IpAddr::V4(r.router),
+                })
+                .collect()
+        }),
+        dns_servers: mozim_lease
+            .dns_srvs
+            .as_ref()
+            .map(|srvs| srvs.iter().map(|s| IpAddr::V4(*s)).collect()),
+        dns_searches: mozim_lease.domain_name.as_ref().map(|d| vec![d.clone()]),
+        mtu: mozim_lease.mtu,
+        ..Default::default()
+    };
+    lease.sanitize();
+    lease
+}
+
+fn mozim_v6_lease_to_nmstate(mozim_lease: &mozim::DhcpV6Lease) -> DhcpLease {
+    let mut lease = DhcpLease {
+        address: Some(IpAddr::V6(mozim_lease.addr)),
+        prefix_length: mozim_lease.prefix_len,
+        server: if mozim_lease.srv_ip == Ipv6Addr::UNSPECIFIED {
+            None
+        } else {
+            Some(IpAddr::V6(mozim_lease.srv_ip))
+        },
+        obtained: now_in_secs(),
+        lease_time: mozim_lease.valid_life,
+        preferred_time: Some(mozim_lease.preferred_life),
+        renew_time: mozim_lease.t1,
+        rebind_time: mozim_lease.t2,
+        ..Default::default()
+    };
+    lease.sanitize();
+    lease
+}
+
+fn mozim_error(e: mozim::DhcpError) -> NmstateError {
+    NmstateError::new(
+        ErrorKind::PluginFailure,
+        format!("DHCP client failure: {e}"),
+    )
+}
+
+// Kernel SLAAC is used for IPv6 autoconf in kernel mode.
+fn set_ipv6_autoconf(
+    iface_name: &str,
+    enabled: bool,
+) -> Result<(), NmstateError> {
+    let value = if enabled { "1" } else { "0" };
+    for name in ["accept_ra", "autoconf"] {
+        let path = format!("/proc/sys/net/ipv6/conf/{iface_name}/{name}");
+        if read_sysfs(&path).as_deref() != Some(value) {
+            write_sysfs(&path, value)?;
+        }
+    }
+    Ok(())
+}
+
+fn get_dhcp_host_name(
+    send_hostname: Option<bool>,
+    custom_hostname: Option<&str>,
+) -> Option<String> {
+    if send_hostname == Some(false) {
+        return None;
+    }
+    if let Some(h) = custom_hostname.filter(|h| !h.is_empty()) {
+        return Some(h.to_string());
+    }
+    nix::unistd::gethostname()
+        .ok()
+        .and_then(|h| h.into_string().ok())
+        .filter(|h| !h.is_empty() && h != "localhost")
+}
+
+fn gen_duid(
+    iface_name: &str,
+    duid: &Dhcpv6Duid,
+) -> Result<Vec<u8>, NmstateError> {
+    let mut ret = Vec::new();
+    match duid {
+        Dhcpv6Duid::LinkLayerAddressPlusTime => {
+            ret.extend_from_slice(&DUID_TYPE_LLT.to_be_bytes());
+            ret.extend_from_slice(&ARPHRD_ETHER.to_be_bytes());
+            let time = now_in_secs().saturating_sub(DUID_TIME_EPOCH) as u32;
+            ret.extend_from_slice(&time.to_be_bytes());
+            ret.extend_from_slice(&get_iface_mac(iface_name)?);
+        }
+        Dhcpv6Duid::LinkLayerAddress => {
+            ret.extend_from_slice(&DUID_TYPE_LL.to_be_bytes());
+            ret.extend_from_slice(&ARPHRD_ETHER.to_be_bytes());
+            ret.extend_from_slice(&get_iface_mac(iface_name)?);
+        }
+        Dhcpv6Duid::Uuid => {
+            ret.extend_from_slice(&DUID_TYPE_UUID.to_be_bytes());
+            let machine_id = read_sysfs(ETC_MACHINE_ID_PATH)
+                .and_then(|s| uuid::Uuid::parse_str(&s).ok())
+                .ok_or_else(|| {
+                    NmstateError::new(
+                        ErrorKind::NotSupportedError,
+                        format!(
+                            "Failed to read machine ID from \
+                            {ETC_MACHINE_ID_PATH} for DHCPv6 DUID-UUID"
+                        ),
+                    )
+                })?;
+            ret.extend_from_slice(machine_id.as_bytes());
+        }
+        Dhcpv6Duid::EnterpriseNumber => {
+            return Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "DHCPv6 DUID {} is not supported in kernel mode",
+                    Dhcpv6Duid::EnterpriseNumber
+                ),
+            ));
+        }
+        Dhcpv6Duid::Other(s) => ret = parse_hex_str(s)?,
+    }
+    Ok(ret)
+}
+
+fn get_iface_mac(iface_name: &str) -> Result<Vec<u8>, NmstateError> {
+    read_sysfs(&sysfs_iface_path(iface_name, "address"))
+        .and_then(|s| parse_hex_str(&s).ok())
+        .filter(|m| !m.is_empty())
+        .ok_or_else(|| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Failed to get MAC address of {iface_name}"),
+            )
+        })
+}
+
+// Parse hex string like `01:aa:bb:cc` or `01aabbcc` into bytes
+fn parse_hex_str(value: &str) -> Result<Vec<u8>, NmstateError> {
+    let hex: String = value
+        .trim_start_matches("0x")
+        .chars()
+        .filter(|c| *c != ':' && *c != '-')
+        .collect();
+    if hex.len() % 2 != 0 {
+        return Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid hex string {value}"),
+        ));
+    }
+    let mut ret = Vec::new();
+    for i in (0..hex.len()).step_by(2) {
+        ret.push(u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid hex string {value}: {e}"),
+            )
+        })?);
+    }
+    Ok(ret)
+}
+
+fn lease_file_path(iface_name: &str, is_ipv6: bool) -> String {
+    format!(
+        "{DHCP_LEASE_DIR}/{iface_name}.{}.yml",
+        if is_ipv6 { "dhcp6" } else { "dhcp4" }
+    )
+}
+
+fn read_lease(iface_name: &str, is_ipv6: bool) -> Option<StoredDhcpLease> {
+    let file_path = lease_file_path(iface_name, is_ipv6);
+    let mut content = String::new();
+    if let Err(e) = std::fs::File::open(&file_path)
+        .and_then(|mut fd| fd.read_to_string(&mut content))
+    {
+        log::debug!("Failed to read DHCP lease {file_path}: {e}");
+        return None;
+    }
+    match serde_yaml::from_str(&content) {
+        Ok(l) => Some(l),
+        Err(e) => {
+            log::warn!("Ignoring invalid DHCP lease file {file_path}: {e}");
+            None
+        }
+    }
+}
+
+fn write_lease(
+    iface_name: &str,
+    is_ipv6: bool,
+    lease: &StoredDhcpLease,
+) -> Result<(), NmstateError> {
+    std::fs::create_dir_all(DHCP_LEASE_DIR).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PermissionError,
+            format!("Failed to create folder {DHCP_LEASE_DIR}: {e}"),
+        )
+    })?;
+    let file_path = lease_file_path(iface_name, is_ipv6);
+    let content = serde_yaml::to_string(lease).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::Bug,
+            format!("Failed to serialize DHCP lease: {e}"),
+        )
+    })?;
+    std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&file_path)
+        .and_then(|mut fd| fd.write_all(content.as_bytes()))
+        .map_err(|e| {
+            NmstateError::new(
+                ErrorKind::PermissionError,
+                format!("Failed to write DHCP lease {file_path}: {e}"),
+            )
+        })
+}
+
+fn remove_lease(iface_name: &str, is_ipv6: bool) {
+    let file_path = lease_file_path(iface_name, is_ipv6);
+    if let Err(e) = std::fs::remove_file(&file_path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            log::warn!("Failed to remove DHCP lease {file_path}: {e}");
+        }
+    }
+}
+
+fn now_in_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+// The `nispor_apply()` sets the full IP address list of interface which
+// will remove leased IP address, hence we include valid leased IP address
+// for DHCP enabled interface.
+pub(crate) fn append_leased_ip(
+    nms_iface: &Interface,
+    np_iface: &mut nispor::IfaceConf,
+) {
+    let iface_name = nms_iface.name();
+    let base_iface = nms_iface.base_iface();
+    let now = now_in_secs();
+    if base_iface.ipv4.as_ref().map(|i| i.is_auto()) == Some(true) {
+        if let (Some(np_ip), Some(lease)) =
+            (np_iface.ipv4.as_mut(), read_lease(iface_name, false))
+        {
+            append_lease_ip_to_np(np_ip, &lease.lease, now);
+        }
+    }
+    if base_iface
+        .ipv6
+        .as_ref()
+        .map(|i| i.enabled && i.dhcp == Some(true))
+        == Some(true)
+    {
+        if let (Some(np_ip), Some(lease)) =
+            (np_iface.ipv6.as_mut(), read_lease(iface_name, true))
+        {
+            append_lease_ip_to_np(np_ip, &lease.lease, now);
+        }
+    }
+}
+
+fn append_lease_ip_to_np(
+    np_ip: &mut nispor::IpConf,
+    lease: &DhcpLease,
+    now: u64,
+) {
+    if lease.state_at(now) == DhcpLeaseState::Expired {
+        return;
+    }
+    if let Some(ip) = lease.address.as_ref() {
+        let ip = ip.to_string();
+        if !np_ip.addresses.iter().any(|a| a.address == ip) {
+            let mut ip_conf = nispor::IpAddrConf::default();
+            ip_conf.address = ip;
+            ip_conf.prefix_len = lease.prefix_length;
+            np_ip.addresses.push(ip_conf);
+        }
+    }
+}
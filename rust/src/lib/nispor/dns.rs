@@ -2,6 +2,7 @@
 
 use std::fmt::Write as _FmtWrite;
 use std::io::{Read, Write};
+use std::net::IpAddr;
 use std::os::unix::fs::OpenOptionsExt;
 
 use crate::{
@@ -76,6 +77,52 @@ pub(crate) fn apply_dns_conf_to_etc(
         }
     }
 
+    write_etc_resolv_conf(&content)
+}
+
+// Append DNS servers and searches retrieved from DHCP after existing ones.
+pub(crate) fn append_dhcp_dns_to_etc(
+    servers: &[IpAddr],
+    searches: &[String],
+) -> Result<(), NmstateError> {
+    if servers.is_empty() && searches.is_empty() {
+        return Ok(());
+    }
+    let cur_conf = get_dns().and_then(|d| d.config).unwrap_or_default();
+    let mut cur_servers = cur_conf.server.unwrap_or_default();
+    let mut cur_searches = cur_conf.search.unwrap_or_default();
+    let cur_options = cur_conf.options.unwrap_or_default();
+    let mut changed = false;
+    for srv in servers.iter().map(|s| s.to_string()) {
+        if !cur_servers.contains(&srv) {
+            cur_servers.push(srv);
+            changed = true;
+        }
+    }
+    for search in searches {
+        if !cur_searches.contains(search) {
+            cur_searches.push(search.to_string());
+            changed = true;
+        }
+    }
+    if !changed {
+        return Ok(());
+    }
+
+    let mut content = String::new();
+    if !cur_options.is_empty() {
+        writeln!(content, "options {}", cur_options.join(" ")).ok();
+    }
+    if !cur_searches.is_empty() {
+        writeln!(content, "search {}", cur_searches.join(" ")).ok();
+    }
+    for srv in cur_servers {
+        writeln!(content, "nameserver {}", srv).ok();
+    }
+    write_etc_resolv_conf(&content)
+}
+
+fn write_etc_resolv_conf(content: &str) -> Result<(), NmstateError> {
     log::info!("Overriding {ETC_RESOLV_CONF_PATH} with:\n{content}");
 
     match std::fs::OpenOptions::new()
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Nispor does not support Geneve yet, the Geneve properties are queried
+// via `RTM_GETLINK` directly.
+
+use std::net::IpAddr;
+
+use netlink_packet_route::link::{
+    GeneveDf as NlGeneveDf, InfoData, InfoGeneve, InfoKind,
+};
+
+use crate::{
+    nispor::netlink::{link_get_info_data, NlLinkConf},
+    BaseInterface, GeneveConfig, GeneveDf, GeneveInterface,
+};
+
+pub(crate) fn np_geneve_to_nmstate(
+    np_iface: &nispor::Iface,
+    base_iface: BaseInterface,
+) -> GeneveInterface {
+    let geneve = match link_get_info_data(np_iface.index) {
+        Ok(Some(InfoData::Geneve(nlas))) => Some(parse_geneve_conf(&nlas)),
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!(
+                "Failed to query Geneve configuration of {}: {e}",
+                np_iface.name
+            );
+            None
+        }
+    };
+    GeneveInterface {
+        base: base_iface,
+        geneve,
+    }
+}
+
+fn parse_geneve_conf(nlas: &[InfoGeneve]) -> GeneveConfig {
+    let mut conf = GeneveConfig::new();
+    let mut udp_csum = None;
+    let mut udp_zero_csum6_tx = None;
+    for nla in nlas {
+        match nla {
+            InfoGeneve::Id(v) => conf.id = *v,
+            InfoGeneve::Remote(ip) => {
+                conf.remote =
+                    Some(IpAddr::V4(*ip)).filter(|ip| !ip.is_unspecified());
+            }
+            InfoGeneve::Remote6(ip) => {
+                conf.remote =
+                    Some(IpAddr::V6(*ip)).filter(|ip| !ip.is_unspecified());
+            }
+            InfoGeneve::Ttl(v) => conf.ttl = Some(*v),
+            InfoGeneve::Tos(v) => conf.tos = Some(*v),
+            InfoGeneve::Port(v) => conf.dst_port = Some(*v),
+            InfoGeneve::Df(v) => conf.df = Some((*v).into()),
+            InfoGeneve::UdpCsum(v) => udp_csum = Some(*v),
+            InfoGeneve::UdpZeroCsum6Tx(v) => udp_zero_csum6_tx = Some(*v),
+            _ => (),
+        }
+    }
+    // Kernel is using dedicated option for IPv6 UDP checksum
+    conf.udp_checksum = if conf.remote.map(|ip| ip.is_ipv6()) == Some(true) {
+        udp_zero_csum6_tx.map(|v| !v)
+    } else {
+        udp_csum
+    };
+    conf
+}
+
+pub(crate) fn nms_geneve_conf_to_nl(conf: &GeneveConfig) -> NlLinkConf {
+    let mut nlas = vec![InfoGeneve::Id(conf.id)];
+    let is_ipv6 = matches!(conf.remote, Some(IpAddr::V6(_)));
+    match conf.remote {
+        Some(IpAddr::V4(remote)) => nlas.push(InfoGeneve::Remote(remote)),
+        Some(IpAddr::V6(remote)) => nlas.push(InfoGeneve::Remote6(remote)),
+        None => (),
+    }
+    if let Some(ttl) = conf.ttl {
+        nlas.push(InfoGeneve::Ttl(ttl));
+    }
+    if let Some(tos) = conf.tos {
+        nlas.push(InfoGeneve::Tos(tos));
+    }
+    if let Some(port) = conf.dst_port {
+        nlas.push(InfoGeneve::Port(port));
+    }
+    if let Some(df) = conf.df {
+        nlas.push(InfoGeneve::Df(df.into()));
+    }
+    if let Some(udp_checksum) = conf.udp_checksum {
+        if is_ipv6 {
+            nlas.push(InfoGeneve::UdpZeroCsum6Tx(!udp_checksum));
+        } else {
+            nlas.push(InfoGeneve::UdpCsum(udp_checksum));
+        }
+    }
+    let mut nl_conf = NlLinkConf::new(InfoKind::Geneve);
+    nl_conf.info_data = Some(InfoData::Geneve(nlas));
+    nl_conf
+}
+
+impl From<NlGeneveDf> for GeneveDf {
+    fn from(v: NlGeneveDf) -> Self {
+        match v {
+            NlGeneveDf::Set => Self::Set,
+            NlGeneveDf::Inherit => Self::Inherit,
+            _ => Self::Unset,
+        }
+    }
+}
+
+impl From<GeneveDf> for NlGeneveDf {
+    fn from(v: GeneveDf) -> Self {
+        match v {
+            GeneveDf::Unset => Self::Unset,
+            GeneveDf::Set => Self::Set,
+            GeneveDf::Inherit => Self::Inherit,
+        }
+    }
+}
@@ -1,6 +1,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{BaseInterface, HsrConfig, HsrInterface, HsrProtocol};
+use netlink_packet_route::link::{
+    HsrProtocol as NlHsrProtocol, InfoData, InfoHsr, InfoKind,
+};
+
+use crate::{
+    nispor::netlink::{get_iface_index, NlLinkConf},
+    BaseInterface, HsrConfig, HsrInterface, HsrProtocol, NmstateError,
+};
 
 impl From<nispor::HsrProtocol> for HsrProtocol {
     fn from(v: nispor::HsrProtocol) -> Self {
@@ -50,3 +57,19 @@ pub(crate) fn np_hsr_to_nmstate(
         hsr: hsr_conf,
     }
 }
+
+pub(crate) fn nms_hsr_conf_to_nl(
+    nms_hsr_conf: &HsrConfig,
+) -> Result<NlLinkConf, NmstateError> {
+    let mut conf = NlLinkConf::new(InfoKind::Hsr);
+    conf.info_data = Some(InfoData::Hsr(vec![
+        InfoHsr::Port1(get_iface_index(nms_hsr_conf.port1.as_str())?),
+        InfoHsr::Port2(get_iface_index(nms_hsr_conf.port2.as_str())?),
+        InfoHsr::MulticastSpec(nms_hsr_conf.multicast_spec),
+        InfoHsr::Protocol(match nms_hsr_conf.protocol {
+            HsrProtocol::Hsr => NlHsrProtocol::Hsr,
+            HsrProtocol::Prp => NlHsrProtocol::Prp,
+        }),
+    ]));
+    Ok(conf)
+}
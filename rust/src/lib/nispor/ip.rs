@@ -1,10 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::net::IpAddr;
 use std::str::FromStr;
 
 use crate::{
-    nispor::mptcp::get_mptcp_flags, InterfaceIpAddr, InterfaceIpv4,
-    InterfaceIpv6,
+    nispor::mptcp::get_mptcp_flags, ErrorKind, InterfaceIpAddr, InterfaceIpv4,
+    InterfaceIpv6, NmstateError,
 };
 
 pub(crate) fn np_ipv4_to_nmstate(
@@ -169,3 +170,34 @@ pub(crate) fn nmstate_ipv6_to_np(
     }
     np_ip_conf
 }
+
+// Parse `<ip>/<prefix>` string, host prefix length is used when prefix
+// is not defined.
+pub(crate) fn parse_ip_net(ip_net: &str) -> Result<(IpAddr, u8), NmstateError> {
+    let (ip, prefix) = match ip_net.split_once('/') {
+        Some((ip, prefix)) => (ip, Some(prefix)),
+        None => (ip_net, None),
+    };
+    let ip = IpAddr::from_str(ip).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid IP address {ip_net}: {e}"),
+        )
+    })?;
+    let prefix = match prefix {
+        Some(p) => p.parse::<u8>().map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid prefix length of {ip_net}: {e}"),
+            )
+        })?,
+        None => {
+            if ip.is_ipv6() {
+                128
+            } else {
+                32
+            }
+        }
+    };
+    Ok((ip, prefix))
+}
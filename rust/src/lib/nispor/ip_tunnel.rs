@@ -0,0 +1,264 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Nispor does not support IP tunnels yet, the tunnel properties are queried
+// via `RTM_GETLINK` directly.
+
+use std::collections::HashMap;
+
+use netlink_packet_core::{
+    parse_ip, parse_u16, parse_u16_be, parse_u32, parse_u32_be, parse_u8,
+    DefaultNla, Nla,
+};
+use netlink_packet_route::link::{InfoData, InfoKind};
+
+use crate::{
+    nispor::netlink::{
+        default_nla_value, get_iface_index, info_data_from_nlas,
+        info_data_to_nlas, ip_nla, link_get_info_data, NlLinkConf,
+    },
+    BaseInterface, ErrorKind, InterfaceType, IpTunnelConfig,
+    IpTunnelEncapConfig, IpTunnelEncapType, IpTunnelInterface, NmstateError,
+};
+
+const GRE_KEY: u16 = 0x2000;
+const TUNNEL_ENCAP_FLAG_CSUM: u16 = 1 << 0;
+
+// The GRE tunnel is using `IFLA_GRE_*` while IPIP, SIT and ip6tnl are using
+// `IFLA_IPTUN_*`.
+struct TunnelNlaTypes {
+    link: u16,
+    local: u16,
+    remote: u16,
+    ttl: u16,
+    tos: u16,
+    pmtudisc: u16,
+    iflags: Option<u16>,
+    oflags: Option<u16>,
+    ikey: Option<u16>,
+    okey: Option<u16>,
+    encap_type: u16,
+    encap_flags: u16,
+    encap_sport: u16,
+    encap_dport: u16,
+}
+
+const IFLA_GRE_NLA_TYPES: TunnelNlaTypes = TunnelNlaTypes {
+    link: 1,
+    iflags: Some(2),
+    oflags: Some(3),
+    ikey: Some(4),
+    okey: Some(5),
+    local: 6,
+    remote: 7,
+    ttl: 8,
+    tos: 9,
+    pmtudisc: 10,
+    encap_type: 14,
+    encap_flags: 15,
+    encap_sport: 16,
+    encap_dport: 17,
+};
+
+const IFLA_IPTUN_NLA_TYPES: TunnelNlaTypes = TunnelNlaTypes {
+    link: 1,
+    local: 2,
+    remote: 3,
+    ttl: 4,
+    tos: 5,
+    pmtudisc: 10,
+    iflags: None,
+    oflags: None,
+    ikey: None,
+    okey: None,
+    encap_type: 15,
+    encap_flags: 16,
+    encap_sport: 17,
+    encap_dport: 18,
+};
+
+fn get_nla_types(iface_type: &InterfaceType) -> &'static TunnelNlaTypes {
+    match iface_type {
+        InterfaceType::Gre | InterfaceType::GreTap => &IFLA_GRE_NLA_TYPES,
+        _ => &IFLA_IPTUN_NLA_TYPES,
+    }
+}
+
+pub(crate) fn np_ip_tunnel_to_nmstate(
+    np_iface: &nispor::Iface,
+    base_iface: BaseInterface,
+    np_ifaces: &HashMap<String, nispor::Iface>,
+) -> IpTunnelInterface {
+    let ip_tunnel = match link_get_info_data(np_iface.index) {
+        Ok(Some(info_data)) => Some(parse_ip_tunnel_conf(
+            &info_data,
+            &base_iface.iface_type,
+            np_ifaces,
+        )),
+        Ok(None) => None,
+        Err(e) => {
+            log::warn!(
+                "Failed to query IP tunnel configuration of {}: {e}",
+                np_iface.name
+            );
+            None
+        }
+    };
+    IpTunnelInterface {
+        base: base_iface,
+        ip_tunnel,
+    }
+}
+
+fn parse_ip_tunnel_conf(
+    info_data: &InfoData,
+    iface_type: &InterfaceType,
+    np_ifaces: &HashMap<String, nispor::Iface>,
+) -> IpTunnelConfig {
+    let types = get_nla_types(iface_type);
+    let mut conf = IpTunnelConfig::new();
+    let mut encap = IpTunnelEncapConfig::new();
+    let mut iflags = 0u16;
+    let mut oflags = 0u16;
+    let mut ikey = None;
+    let mut okey = None;
+    for nla in info_data_to_nlas(info_data) {
+        let nla_type = nla.kind();
+        let data = default_nla_value(&nla);
+        let data = data.as_slice();
+        if nla_type == types.link {
+            conf.base_iface = parse_u32(data).ok().and_then(|index| {
+                np_ifaces
+                    .values()
+                    .find(|i| index != 0 && i.index == index)
+                    .map(|i| i.name.to_string())
+            });
+        } else if nla_type == types.local {
+            conf.local = parse_ip(data).ok().filter(|ip| !ip.is_unspecified());
+        } else if nla_type == types.remote {
+            conf.remote = parse_ip(data).ok().filter(|ip| !ip.is_unspecified());
+        } else if nla_type == types.ttl {
+            conf.ttl = parse_u8(data).ok();
+        } else if nla_type == types.tos {
+            conf.tos = parse_u8(data).ok();
+        } else if nla_type == types.pmtudisc {
+            conf.pmtudisc = parse_u8(data).ok().map(|v| v > 0);
+        } else if Some(nla_type) == types.iflags {
+            iflags = parse_u16_be(data).unwrap_or_default();
+        } else if Some(nla_type) == types.oflags {
+            oflags = parse_u16_be(data).unwrap_or_default();
+        } else if Some(nla_type) == types.ikey {
+            ikey = parse_u32_be(data).ok();
+        } else if Some(nla_type) == types.okey {
+            okey = parse_u32_be(data).ok();
+        } else if nla_type == types.encap_type {
+            encap.encap_type =
+                IpTunnelEncapType::from(parse_u16(data).unwrap_or_default());
+        } else if nla_type == types.encap_flags {
+            encap.checksum = parse_u16(data)
+                .ok()
+                .map(|flags| flags & TUNNEL_ENCAP_FLAG_CSUM > 0);
+        } else if nla_type == types.encap_sport {
+            encap.source_port = parse_u16_be(data).ok();
+        } else if nla_type == types.encap_dport {
+            encap.destination_port = parse_u16_be(data).ok();
+        }
+    }
+    if iflags & GRE_KEY > 0 {
+        conf.ikey = ikey;
+    }
+    if oflags & GRE_KEY > 0 {
+        conf.okey = okey;
+    }
+    if encap.encap_type != IpTunnelEncapType::None {
+        conf.encap = Some(encap);
+    }
+    conf
+}
+
+pub(crate) fn nms_ip_tunnel_conf_to_nl(
+    iface_type: &InterfaceType,
+    conf: &IpTunnelConfig,
+) -> Result<NlLinkConf, NmstateError> {
+    let kind = match iface_type {
+        InterfaceType::Gre => InfoKind::GreTun,
+        InterfaceType::GreTap => InfoKind::GreTap,
+        InterfaceType::Ipip => InfoKind::IpTun,
+        InterfaceType::Sit => InfoKind::SitTun,
+        InterfaceType::Ip6Tnl => InfoKind::Other("ip6tnl".to_string()),
+        _ => {
+            return Err(NmstateError::new(
+                ErrorKind::Bug,
+                format!("Got unexpected IP tunnel type {iface_type}"),
+            ));
+        }
+    };
+    let types = get_nla_types(iface_type);
+    let mut nlas = Vec::new();
+    if let Some(base_iface) = conf.base_iface.as_deref() {
+        nlas.push(DefaultNla::new(
+            types.link,
+            get_iface_index(base_iface)?.to_ne_bytes().to_vec(),
+        ));
+    }
+    if let Some(local) = conf.local.as_ref() {
+        nlas.push(ip_nla(types.local, local));
+    }
+    if let Some(remote) = conf.remote.as_ref() {
+        nlas.push(ip_nla(types.remote, remote));
+    }
+    if let Some(ttl) = conf.ttl {
+        nlas.push(DefaultNla::new(types.ttl, vec![ttl]));
+    }
+    if let Some(tos) = conf.tos {
+        nlas.push(DefaultNla::new(types.tos, vec![tos]));
+    }
+    if let Some(pmtudisc) = conf.pmtudisc {
+        nlas.push(DefaultNla::new(types.pmtudisc, vec![pmtudisc.into()]));
+    }
+    for (flags_type, key_type, key) in [
+        (types.iflags, types.ikey, conf.ikey),
+        (types.oflags, types.okey, conf.okey),
+    ] {
+        if let (Some(flags_type), Some(key_type), Some(key)) =
+            (flags_type, key_type, key)
+        {
+            nlas.push(DefaultNla::new(
+                flags_type,
+                GRE_KEY.to_be_bytes().to_vec(),
+            ));
+            nlas.push(DefaultNla::new(key_type, key.to_be_bytes().to_vec()));
+        }
+    }
+    if let Some(encap) = conf
+        .encap
+        .as_ref()
+        .filter(|e| e.encap_type != IpTunnelEncapType::None)
+    {
+        let encap_type: u16 = encap.encap_type.into();
+        nlas.push(DefaultNla::new(
+            types.encap_type,
+            encap_type.to_ne_bytes().to_vec(),
+        ));
+        if encap.checksum == Some(true) {
+            nlas.push(DefaultNla::new(
+                types.encap_flags,
+                TUNNEL_ENCAP_FLAG_CSUM.to_ne_bytes().to_vec(),
+            ));
+        }
+        if let Some(port) = encap.source_port {
+            nlas.push(DefaultNla::new(
+                types.encap_sport,
+                port.to_be_bytes().to_vec(),
+            ));
+        }
+        if let Some(port) = encap.destination_port {
+            nlas.push(DefaultNla::new(
+                types.encap_dport,
+                port.to_be_bytes().to_vec(),
+            ));
+        }
+    }
+    let mut nl_conf = NlLinkConf::new(kind);
+    nl_conf.info_data = Some(info_data_from_nlas(&nlas));
+    Ok(nl_conf)
+}
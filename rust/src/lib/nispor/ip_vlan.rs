@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use netlink_packet_route::link::{
+    InfoData, InfoIpVlan, InfoKind, IpVlanFlags, IpVlanMode as NlIpVlanMode,
+};
+
+use crate::{
+    nispor::netlink::NlLinkConf, BaseInterface, IpVlanConfig, IpVlanInterface,
+    IpVlanMode,
+};
+
+pub(crate) fn np_ip_vlan_to_nmstate(
+    np_iface: &nispor::Iface,
+    base_iface: BaseInterface,
+) -> IpVlanInterface {
+    let ipvlan_conf = np_iface.ip_vlan.as_ref().map(|np_ipvlan_info| {
+        let mut conf = IpVlanConfig::new();
+        conf.base_iface.clone_from(&np_ipvlan_info.base_iface);
+        conf.mode = match &np_ipvlan_info.mode {
+            nispor::IpVlanMode::L2 => IpVlanMode::L2,
+            nispor::IpVlanMode::L3 => IpVlanMode::L3,
+            nispor::IpVlanMode::L3S => IpVlanMode::L3S,
+            _ => {
+                log::warn!(
+                    "Unknown supported IPVLAN mode {:?}",
+                    np_ipvlan_info.mode
+                );
+                IpVlanMode::default()
+            }
+        };
+        conf.private =
+            Some(np_ipvlan_info.flags.contains(&nispor::IpVlanFlag::Private));
+        conf.vepa =
+            Some(np_ipvlan_info.flags.contains(&nispor::IpVlanFlag::Vepa));
+        conf
+    });
+
+    IpVlanInterface {
+        base: base_iface,
+        ipvlan: ipvlan_conf,
+    }
+}
+
+pub(crate) fn nms_ip_vlan_conf_to_nl(nms_conf: &IpVlanConfig) -> NlLinkConf {
+    let mut flags = IpVlanFlags::empty();
+    if nms_conf.private == Some(true) {
+        flags |= IpVlanFlags::Private;
+    }
+    if nms_conf.vepa == Some(true) {
+        flags |= IpVlanFlags::Vepa;
+    }
+    let mut conf = NlLinkConf::new(InfoKind::IpVlan);
+    conf.base_iface = Some(nms_conf.base_iface.to_string());
+    conf.info_data = Some(InfoData::IpVlan(vec![
+        InfoIpVlan::Mode(match nms_conf.mode {
+            IpVlanMode::L2 => NlIpVlanMode::L2,
+            IpVlanMode::L3 => NlIpVlanMode::L3,
+            IpVlanMode::L3S => NlIpVlanMode::L3S,
+        }),
+        InfoIpVlan::Flags(flags),
+    ]));
+    conf
+}
@@ -3,10 +3,14 @@
 use log::warn;
 
 use crate::{
-    nispor::linux_bridge_port_vlan::parse_port_vlan_conf, BaseInterface,
-    ErrorKind, LinuxBridgeConfig, LinuxBridgeInterface,
-    LinuxBridgeMulticastRouterType, LinuxBridgeOptions, LinuxBridgePortConfig,
-    LinuxBridgeStpOptions, NmstateError, VlanProtocol,
+    nispor::{
+        linux_bridge_port_vlan::{apply_port_vlan_conf, parse_port_vlan_conf},
+        sysfs::{sysfs_iface_path, write_sysfs},
+    },
+    BaseInterface, ErrorKind, Interface, LinuxBridgeConfig,
+    LinuxBridgeInterface, LinuxBridgeMulticastRouterType, LinuxBridgeOptions,
+    LinuxBridgePortConfig, LinuxBridgeStpOptions, MergedInterface,
+    NmstateError, VlanProtocol,
 };
 
 pub(crate) fn np_bridge_to_nmstate(
@@ -73,7 +77,7 @@ pub(crate) fn append_bridge_port_config(
     }
 }
 
-fn np_bridge_options_to_nmstate(
+pub(crate) fn np_bridge_options_to_nmstate(
     np_iface: &nispor::Iface,
 ) -> Result<LinuxBridgeOptions, NmstateError> {
     let mut options = LinuxBridgeOptions::default();
@@ -151,8 +155,16 @@ fn np_bridge_options_to_nmstate(
 //   * hello_time
 //   * max_age
 fn devide_by_user_hz(v: u32) -> Result<u32, NmstateError> {
-    let user_hz = match nix::unistd::sysconf(nix::unistd::SysconfVar::CLK_TCK) {
-        Ok(value) => value.unwrap_or_default() as u32,
+    Ok(v / get_user_hz()?)
+}
+
+fn multiply_by_user_hz(v: u32) -> Result<u32, NmstateError> {
+    Ok(v * get_user_hz()?)
+}
+
+fn get_user_hz() -> Result<u32, NmstateError> {
+    match nix::unistd::sysconf(nix::unistd::SysconfVar::CLK_TCK) {
+        Ok(value) => Ok(value.unwrap_or_default() as u32),
         Err(_) => {
             let e = NmstateError::new(
                 ErrorKind::KernelIntegerRoundedError,
@@ -160,10 +172,9 @@ fn devide_by_user_hz(v: u32) -> Result<u32, NmstateError> {
                     .to_string(),
             );
             log::error!("{}", e);
-            return Err(e);
+            Err(e)
         }
-    };
-    Ok(v / user_hz)
+    }
 }
 
 fn get_stp_options(
@@ -201,3 +212,198 @@ fn get_stp_options(
     stp_opt.priority = np_bridge.priority;
     Ok(stp_opt)
 }
+
+// Nispor can only create linux bridge and attach ports to it, the bridge
+// options, bridge port options and port VLAN filtering are applied via sysfs
+// and netlink after ports attached.
+pub(crate) fn apply_linux_bridge_conf(
+    merged_iface: &MergedInterface,
+) -> Result<(), NmstateError> {
+    let (apply_iface, merged_br_iface) =
+        match (merged_iface.for_apply.as_ref(), &merged_iface.merged) {
+            (
+                Some(Interface::LinuxBridge(a)),
+                Interface::LinuxBridge(merged_br_iface),
+            ) => (a, merged_br_iface),
+            _ => return Ok(()),
+        };
+    let cur_iface = match merged_iface.current.as_ref() {
+        Some(Interface::LinuxBridge(i)) => Some(i),
+        _ => None,
+    };
+    let br_name = apply_iface.base.name.as_str();
+    let br_conf = match apply_iface.bridge.as_ref() {
+        Some(c) => c,
+        None => return Ok(()),
+    };
+
+    if let Some(opts) = br_conf.options.as_ref() {
+        let cur_opts = cur_iface
+            .and_then(|i| i.bridge.as_ref())
+            .and_then(|c| c.options.as_ref())
+            .map(gen_bridge_sysfs_opts)
+            .transpose()?
+            .unwrap_or_default();
+        for (name, value) in gen_bridge_sysfs_opts(opts)? {
+            if !cur_opts.contains(&(name, value.clone())) {
+                write_sysfs(
+                    &sysfs_iface_path(br_name, &format!("bridge/{name}")),
+                    value.as_str(),
+                )?;
+            }
+        }
+    }
+
+    if let Some(port_confs) = br_conf.port.as_ref() {
+        let vlan_filtering = merged_br_iface.vlan_filtering_is_enabled();
+        // Only touch VLAN filtering when desired state mentions port VLAN
+        // configuration, otherwise we might flush VLANs set by others.
+        if port_confs.iter().any(|p| p.vlan.is_some()) {
+            write_sysfs(
+                &sysfs_iface_path(br_name, "bridge/vlan_filtering"),
+                if vlan_filtering { "1" } else { "0" },
+            )?;
+        }
+        for port_conf in port_confs {
+            let cur_port_conf = cur_iface
+                .and_then(|i| i.get_port_conf(port_conf.name.as_str()));
+            apply_bridge_port_conf(port_conf, cur_port_conf, vlan_filtering)?;
+        }
+    }
+    Ok(())
+}
+
+fn apply_bridge_port_conf(
+    port_conf: &LinuxBridgePortConfig,
+    cur_port_conf: Option<&LinuxBridgePortConfig>,
+    vlan_filtering: bool,
+) -> Result<(), NmstateError> {
+    let port_name = port_conf.name.as_str();
+    if let Some(v) = port_conf.stp_priority {
+        if cur_port_conf.and_then(|c| c.stp_priority) != Some(v) {
+            write_sysfs(
+                &sysfs_iface_path(port_name, "brport/priority"),
+                &v.to_string(),
+            )?;
+        }
+    }
+    if let Some(v) = port_conf.stp_path_cost {
+        if cur_port_conf.and_then(|c| c.stp_path_cost) != Some(v) {
+            write_sysfs(
+                &sysfs_iface_path(port_name, "brport/path_cost"),
+                &v.to_string(),
+            )?;
+        }
+    }
+    if let Some(v) = port_conf.stp_hairpin_mode {
+        if cur_port_conf.and_then(|c| c.stp_hairpin_mode) != Some(v) {
+            write_sysfs(
+                &sysfs_iface_path(port_name, "brport/hairpin_mode"),
+                if v { "1" } else { "0" },
+            )?;
+        }
+    }
+    if vlan_filtering {
+        if let Some(vlan_conf) = port_conf.vlan.as_ref() {
+            apply_port_vlan_conf(
+                port_name,
+                vlan_conf,
+                cur_port_conf.and_then(|c| c.vlan.as_ref()),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// The STP timers should be set before enabling STP as kernel will validate
+// their range when STP enabled.
+pub(crate) fn gen_bridge_sysfs_opts(
+    opts: &LinuxBridgeOptions,
+) -> Result<Vec<(&'static str, String)>, NmstateError> {
+    let mut ret = Vec::new();
+    if let Some(v) = opts.group_addr.as_ref() {
+        ret.push(("group_addr", v.to_uppercase()));
+    }
+    if let Some(v) = opts.group_fwd_mask.or(opts.group_forward_mask) {
+        ret.push(("group_fwd_mask", v.to_string()));
+    }
+    if let Some(v) = opts.hash_max {
+        ret.push(("hash_max", v.to_string()));
+    }
+    if let Some(v) = opts.mac_ageing_time {
+        ret.push(("ageing_time", multiply_by_user_hz(v)?.to_string()));
+    }
+    if let Some(v) = opts.multicast_last_member_count {
+        ret.push(("multicast_last_member_count", v.to_string()));
+    }
+    if let Some(v) = opts.multicast_last_member_interval {
+        ret.push(("multicast_last_member_interval", v.to_string()));
+    }
+    if let Some(v) = opts.multicast_membership_interval {
+        ret.push(("multicast_membership_interval", v.to_string()));
+    }
+    if let Some(v) = opts.multicast_querier {
+        ret.push(("multicast_querier", u8::from(v).to_string()));
+    }
+    if let Some(v) = opts.multicast_querier_interval {
+        ret.push(("multicast_querier_interval", v.to_string()));
+    }
+    if let Some(v) = opts.multicast_query_interval {
+        ret.push(("multicast_query_interval", v.to_string()));
+    }
+    if let Some(v) = opts.multicast_query_response_interval {
+        ret.push(("multicast_query_response_interval", v.to_string()));
+    }
+    if let Some(v) = opts.multicast_query_use_ifaddr {
+        ret.push(("multicast_query_use_ifaddr", u8::from(v).to_string()));
+    }
+    if let Some(v) = opts.multicast_router.as_ref() {
+        ret.push(("multicast_router", (v.clone() as u8).to_string()));
+    }
+    if let Some(v) = opts.multicast_snooping {
+        ret.push(("multicast_snooping", u8::from(v).to_string()));
+    }
+    if let Some(v) = opts.multicast_startup_query_count {
+        ret.push(("multicast_startup_query_count", v.to_string()));
+    }
+    if let Some(v) = opts.multicast_startup_query_interval {
+        ret.push(("multicast_startup_query_interval", v.to_string()));
+    }
+    if let Some(v) = opts.vlan_protocol.as_ref() {
+        ret.push((
+            "vlan_protocol",
+            match v {
+                VlanProtocol::Ieee8021Q => "0x8100",
+                VlanProtocol::Ieee8021Ad => "0x88a8",
+            }
+            .to_string(),
+        ));
+    }
+    if let Some(v) = opts.vlan_default_pvid {
+        ret.push(("default_pvid", v.to_string()));
+    }
+    if let Some(stp_opts) = opts.stp.as_ref() {
+        if let Some(v) = stp_opts.forward_delay {
+            ret.push((
+                "forward_delay",
+                multiply_by_user_hz(v.into())?.to_string(),
+            ));
+        }
+        if let Some(v) = stp_opts.hello_time {
+            ret.push((
+                "hello_time",
+                multiply_by_user_hz(v.into())?.to_string(),
+            ));
+        }
+        if let Some(v) = stp_opts.max_age {
+            ret.push(("max_age", multiply_by_user_hz(v.into())?.to_string()));
+        }
+        if let Some(v) = stp_opts.priority {
+            ret.push(("priority", v.to_string()));
+        }
+        if let Some(v) = stp_opts.enabled {
+            ret.push(("stp_state", u8::from(v).to_string()));
+        }
+    }
+    Ok(ret)
+}
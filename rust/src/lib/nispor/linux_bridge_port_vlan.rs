@@ -1,6 +1,7 @@
 use crate::{
+    nispor::netlink::{bridge_vlan_add, bridge_vlan_del, BridgeVlanInfo},
     BridgePortTrunkTag, BridgePortVlanConfig, BridgePortVlanMode,
-    BridgePortVlanRange,
+    BridgePortVlanRange, NmstateError,
 };
 
 pub(crate) fn parse_port_vlan_conf(
@@ -65,3 +66,53 @@ fn get_vlan_tag_range(np_vlan_entry: &nispor::BridgeVlanEntry) -> (u16, u16) {
         np_vlan_entry.vid.unwrap_or(1),
     ))
 }
+
+// The default PVID entry is managed by kernel, we only remove VLANs
+// mentioned in current port VLAN configuration but not in desired.
+pub(crate) fn apply_port_vlan_conf(
+    port_name: &str,
+    desired: &BridgePortVlanConfig,
+    current: Option<&BridgePortVlanConfig>,
+) -> Result<(), NmstateError> {
+    let des_vlans = port_vlan_conf_to_np(desired);
+    let cur_vlans = current.map(port_vlan_conf_to_np).unwrap_or_default();
+
+    let vlans_to_remove: Vec<BridgeVlanInfo> = cur_vlans
+        .iter()
+        .filter(|v| !des_vlans.contains(v))
+        .copied()
+        .collect();
+    let vlans_to_add: Vec<BridgeVlanInfo> = des_vlans
+        .iter()
+        .filter(|v| !cur_vlans.contains(v))
+        .copied()
+        .collect();
+
+    bridge_vlan_del(port_name, vlans_to_remove.as_slice())?;
+    bridge_vlan_add(port_name, vlans_to_add.as_slice())
+}
+
+pub(crate) fn port_vlan_conf_to_np(
+    conf: &BridgePortVlanConfig,
+) -> Vec<BridgeVlanInfo> {
+    let mut ret = Vec::new();
+    match conf.mode {
+        Some(BridgePortVlanMode::Trunk) => {
+            for trunk_tag in conf.trunk_tags.as_deref().unwrap_or_default() {
+                let (vid_min, vid_max) = trunk_tag.get_vlan_tag_range();
+                ret.push(BridgeVlanInfo::new(vid_min, vid_max, false, false));
+            }
+            if conf.enable_native == Some(true) {
+                if let Some(tag) = conf.tag {
+                    ret.push(BridgeVlanInfo::new(tag, tag, true, true));
+                }
+            }
+        }
+        _ => {
+            if let Some(tag) = conf.tag {
+                ret.push(BridgeVlanInfo::new(tag, tag, true, true));
+            }
+        }
+    }
+    ret
+}
@@ -1,6 +1,11 @@
+use netlink_packet_route::link::{
+    InfoData, InfoKind, InfoMacVlan, InfoMacVtap, MacVlanMode as NlMacVlanMode,
+};
+
 use crate::{
-    BaseInterface, MacVlanConfig, MacVlanInterface, MacVlanMode, MacVtapConfig,
-    MacVtapInterface, MacVtapMode,
+    nispor::netlink::NlLinkConf, BaseInterface, ErrorKind, MacVlanConfig,
+    MacVlanInterface, MacVlanMode, MacVtapConfig, MacVtapInterface,
+    MacVtapMode, NmstateError,
 };
 
 const MACVLAN_FLAG_NOPROMISC: u16 = 1;
@@ -76,3 +81,55 @@ pub(crate) fn np_mac_vtap_to_nmstate(
         mac_vtap: vtap_conf,
     }
 }
+
+pub(crate) fn nms_mac_vlan_conf_to_nl(
+    nms_conf: &MacVlanConfig,
+) -> Result<NlLinkConf, NmstateError> {
+    let mode = match nms_conf.mode {
+        MacVlanMode::Private => NlMacVlanMode::Private,
+        MacVlanMode::Vepa => NlMacVlanMode::Vepa,
+        MacVlanMode::Bridge => NlMacVlanMode::Bridge,
+        MacVlanMode::Passthru => NlMacVlanMode::Passthrough,
+        MacVlanMode::Source => NlMacVlanMode::Source,
+        MacVlanMode::Unknown => {
+            return Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                "Cannot create MAC VLAN with unknown mode".to_string(),
+            ));
+        }
+    };
+    let mut nlas = vec![InfoMacVlan::Mode(mode)];
+    if nms_conf.accept_all_mac == Some(false) {
+        nlas.push(InfoMacVlan::Flags(MACVLAN_FLAG_NOPROMISC));
+    }
+    let mut conf = NlLinkConf::new(InfoKind::MacVlan);
+    conf.base_iface = Some(nms_conf.base_iface.to_string());
+    conf.info_data = Some(InfoData::MacVlan(nlas));
+    Ok(conf)
+}
+
+pub(crate) fn nms_mac_vtap_conf_to_nl(
+    nms_conf: &MacVtapConfig,
+) -> Result<NlLinkConf, NmstateError> {
+    let mode = match nms_conf.mode {
+        MacVtapMode::Private => NlMacVlanMode::Private,
+        MacVtapMode::Vepa => NlMacVlanMode::Vepa,
+        MacVtapMode::Bridge => NlMacVlanMode::Bridge,
+        MacVtapMode::Passthru => NlMacVlanMode::Passthrough,
+        MacVtapMode::Source => NlMacVlanMode::Source,
+        MacVtapMode::Unknown => {
+            return Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                "Cannot create MAC VTAP with unknown mode".to_string(),
+            ));
+        }
+    };
+    let mut nlas = vec![InfoMacVtap::Mode(mode)];
+    if nms_conf.accept_all_mac == Some(false) {
+        nlas.push(InfoMacVtap::Flags(MACVTAP_FLAG_NOPROMISC));
+    }
+    let mut conf = NlLinkConf::new(InfoKind::MacVtap);
+    conf.base_iface = Some(nms_conf.base_iface.to_string());
+    conf.info_data = Some(InfoData::MacVtap(nlas));
+    Ok(conf)
+}
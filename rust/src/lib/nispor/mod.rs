@@ -1,29 +1,56 @@
 // SPDX-License-Identifier: Apache-2.0
 
-mod apply;
+pub(crate) mod apply;
+mod bareudp;
 mod base_iface;
-mod bond;
+pub(crate) mod bond;
+pub(crate) mod checkpoint;
+pub(crate) mod dhcp;
 mod dns;
 mod error;
 mod ethernet;
 mod ethtool;
+mod geneve;
 mod hostname;
 mod hsr;
 mod infiniband;
+mod ip_tunnel;
+mod ip_vlan;
 mod ip;
-mod linux_bridge;
-mod linux_bridge_port_vlan;
+pub(crate) mod linux_bridge;
+pub(crate) mod linux_bridge_port_vlan;
 mod mac_vlan;
 mod macsec;
+mod monitor;
 mod mptcp;
-mod route;
-mod route_rule;
+pub(crate) mod netlink;
+mod netns;
+mod nexthop;
+pub(crate) mod route;
+pub(crate) mod route_rule;
 mod show;
+mod sysfs;
 mod veth;
 mod vlan;
 mod vrf;
 mod vxlan;
+pub(crate) mod wireguard;
 
 pub(crate) use apply::nispor_apply;
-pub(crate) use hostname::set_running_hostname;
-pub(crate) use show::nispor_retrieve;
+pub(crate) use bond::nispor_verify_bond_opts;
+pub(crate) use checkpoint::{
+    is_kernel_checkpoint, nispor_checkpoint_arm_rollback_timer,
+    nispor_checkpoint_create, nispor_checkpoint_destroy, nispor_checkpoint_get,
+    nispor_checkpoint_timeout_extend, nispor_checkpoints, KernelCheckpoint,
+};
+pub(crate) use dns::get_dns;
+pub(crate) use hostname::{get_hostname_state, set_running_hostname};
+pub(crate) use monitor::nispor_monitor_start;
+pub(crate) use netns::apply_ifaces_netns;
+#[cfg(test)]
+pub(crate) use monitor::parse_rtnl_events;
+pub(crate) use route::get_routes;
+pub(crate) use route_rule::get_route_rules;
+pub(crate) use show::{
+    nispor_retrieve, nispor_retrieve_ifaces, nispor_retrieve_nexthops,
+};
@@ -0,0 +1,236 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Listen on rtnetlink multicast groups for changes of links, IP addresses,
+// routes and route rules.
+
+use nix::sys::socket::{
+    bind, recv, socket, AddressFamily, MsgFlags, NetlinkAddr, SockFlag,
+    SockProtocol, SockType,
+};
+
+use crate::{
+    query_apply::{WatchEvent, WatchEventSender},
+    ErrorKind, NmstateError,
+};
+
+const RTMGRP_LINK: u32 = 1 << 0;
+const RTMGRP_IPV4_IFADDR: u32 = 1 << 4;
+const RTMGRP_IPV4_ROUTE: u32 = 1 << 6;
+const RTMGRP_IPV4_RULE: u32 = 1 << 7;
+const RTMGRP_IPV6_IFADDR: u32 = 1 << 8;
+const RTMGRP_IPV6_ROUTE: u32 = 1 << 10;
+// RTNLGRP_IPV6_RULE is 19 which has no RTMGRP_ definition in kernel header
+const RTMGRP_IPV6_RULE: u32 = 1 << 18;
+// RTNLGRP_NEXTHOP is 32 which has no RTMGRP_ definition in kernel header
+const RTMGRP_NEXTHOP: u32 = 1 << 31;
+
+const MONITOR_BUFFER_SIZE: usize = 65536;
+
+const NLMSG_HDR_LEN: usize = 16;
+const IFINFOMSG_LEN: usize = 16;
+const IFADDRMSG_LEN: usize = 8;
+const RTA_HDR_LEN: usize = 4;
+
+const RTM_NEWLINK: u16 = 16;
+const RTM_DELLINK: u16 = 17;
+const RTM_NEWADDR: u16 = 20;
+const RTM_DELADDR: u16 = 21;
+const RTM_NEWROUTE: u16 = 24;
+const RTM_DELROUTE: u16 = 25;
+const RTM_NEWRULE: u16 = 32;
+const RTM_DELRULE: u16 = 33;
+const RTM_NEWNEXTHOP: u16 = 104;
+const RTM_DELNEXTHOP: u16 = 105;
+
+const IFLA_IFNAME: u16 = 3;
+const IFA_LABEL: u16 = 3;
+const IFF_UP: u32 = 1;
+
+// The monitor thread quits on next notification after receiver dropped.
+pub(crate) fn nispor_monitor_start(
+    sender: WatchEventSender,
+) -> Result<(), NmstateError> {
+    let fd = socket(
+        AddressFamily::Netlink,
+        SockType::Raw,
+        SockFlag::SOCK_CLOEXEC,
+        SockProtocol::NetlinkRoute,
+    )
+    .map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to create netlink socket: {e}"),
+        )
+    })?;
+    let groups = RTMGRP_LINK
+        | RTMGRP_IPV4_IFADDR
+        | RTMGRP_IPV4_ROUTE
+        | RTMGRP_IPV4_RULE
+        | RTMGRP_IPV6_IFADDR
+        | RTMGRP_IPV6_ROUTE
+        | RTMGRP_IPV6_RULE
+        | RTMGRP_NEXTHOP;
+    if let Err(e) = bind(fd, &NetlinkAddr::new(0, groups)) {
+        nix::unistd::close(fd).ok();
+        return Err(NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to subscribe rtnetlink multicast groups: {e}"),
+        ));
+    }
+
+    std::thread::spawn(move || {
+        let mut buffer = vec![0u8; MONITOR_BUFFER_SIZE];
+        loop {
+            match recv(fd, buffer.as_mut_slice(), MsgFlags::empty()) {
+                Ok(len) => {
+                    let events =
+                        parse_rtnl_events(&buffer[..len], iface_index_to_name);
+                    if events
+                        .into_iter()
+                        .any(|event| sender.send(event).is_err())
+                    {
+                        break;
+                    }
+                }
+                // ENOBUFS means kernel dropped some notifications due to
+                // socket buffer overflow, we cannot tell what changed.
+                Err(nix::Error::ENOBUFS) => {
+                    if sender.send(WatchEvent::All).is_err() {
+                        break;
+                    }
+                }
+                Err(nix::Error::EINTR) => (),
+                Err(e) => {
+                    log::error!("Failed to receive netlink notification: {e}");
+                    break;
+                }
+            }
+        }
+        if let Err(e) = nix::unistd::close(fd) {
+            log::warn!("Failed to close netlink socket: {e}");
+        }
+    });
+    Ok(())
+}
+
+// Convert rtnetlink multicast messages to watch events.
+// The `resolve_index` is used to find interface name of IP address
+// notification without `IFA_LABEL`.
+pub(crate) fn parse_rtnl_events<F>(
+    buffer: &[u8],
+    resolve_index: F,
+) -> Vec<WatchEvent>
+where
+    F: Fn(u32) -> Option<String>,
+{
+    let mut events = Vec::new();
+    let mut offset = 0;
+    while offset + NLMSG_HDR_LEN <= buffer.len() {
+        let msg_len = read_u32(buffer, offset) as usize;
+        if msg_len < NLMSG_HDR_LEN || offset + msg_len > buffer.len() {
+            log::debug!("Got malformed rtnetlink message {:?}", buffer);
+            events.push(WatchEvent::All);
+            break;
+        }
+        let msg_type = read_u16(buffer, offset + 4);
+        let payload = &buffer[offset + NLMSG_HDR_LEN..offset + msg_len];
+        match msg_type {
+            RTM_NEWLINK | RTM_DELLINK => {
+                let iface_name = if payload.len() >= IFINFOMSG_LEN {
+                    find_rta_str(&payload[IFINFOMSG_LEN..], IFLA_IFNAME)
+                } else {
+                    None
+                };
+                events.push(match iface_name {
+                    Some(n) => WatchEvent::Iface(n),
+                    None => WatchEvent::Ifaces,
+                });
+                // Kernel does not notify routes and nexthops removed along
+                // with interface removed or set to down.
+                let ifi_flags = if payload.len() >= IFINFOMSG_LEN {
+                    read_u32(payload, 8)
+                } else {
+                    0
+                };
+                if msg_type == RTM_DELLINK || ifi_flags & IFF_UP == 0 {
+                    events.push(WatchEvent::Routes);
+                    events.push(WatchEvent::Nexthops);
+                }
+            }
+            RTM_NEWADDR | RTM_DELADDR => {
+                let iface_name = if payload.len() >= IFADDRMSG_LEN {
+                    find_rta_str(&payload[IFADDRMSG_LEN..], IFA_LABEL)
+                        .or_else(|| resolve_index(read_u32(payload, 4)))
+                } else {
+                    None
+                };
+                events.push(match iface_name {
+                    Some(n) => WatchEvent::Iface(n),
+                    None => WatchEvent::Ifaces,
+                });
+            }
+            RTM_NEWROUTE | RTM_DELROUTE => events.push(WatchEvent::Routes),
+            RTM_NEWRULE | RTM_DELRULE => events.push(WatchEvent::RouteRules),
+            RTM_NEWNEXTHOP | RTM_DELNEXTHOP => {
+                events.push(WatchEvent::Nexthops)
+            }
+            _ => {
+                log::debug!("Ignoring rtnetlink message type {msg_type}");
+            }
+        }
+        offset += align_to_4(msg_len);
+    }
+    events
+}
+
+fn find_rta_str(mut attrs: &[u8], rta_type: u16) -> Option<String> {
+    while attrs.len() >= RTA_HDR_LEN {
+        let rta_len = read_u16(attrs, 0) as usize;
+        if rta_len < RTA_HDR_LEN || rta_len > attrs.len() {
+            return None;
+        }
+        if read_u16(attrs, 2) == rta_type {
+            let value = &attrs[RTA_HDR_LEN..rta_len];
+            let value = match value.iter().position(|c| *c == 0) {
+                Some(end) => &value[..end],
+                None => value,
+            };
+            return String::from_utf8(value.to_vec()).ok();
+        }
+        attrs = &attrs[std::cmp::min(align_to_4(rta_len), attrs.len())..];
+    }
+    None
+}
+
+fn iface_index_to_name(index: u32) -> Option<String> {
+    let mut buffer: [libc::c_char; libc::IF_NAMESIZE] = [0; libc::IF_NAMESIZE];
+    // SAFETY: The buffer is IF_NAMESIZE long as required and the string
+    // is null terminated when if_indextoname() succeeded.
+    unsafe {
+        if libc::if_indextoname(index, buffer.as_mut_ptr()).is_null() {
+            None
+        } else {
+            std::ffi::CStr::from_ptr(buffer.as_ptr())
+                .to_str()
+                .ok()
+                .map(|n| n.to_string())
+        }
+    }
+}
+
+fn read_u16(buffer: &[u8], offset: usize) -> u16 {
+    u16::from_ne_bytes([buffer[offset], buffer[offset + 1]])
+}
+
+fn read_u32(buffer: &[u8], offset: usize) -> u32 {
+    u32::from_ne_bytes([
+        buffer[offset],
+        buffer[offset + 1],
+        buffer[offset + 2],
+        buffer[offset + 3],
+    ])
+}
+
+fn align_to_4(len: usize) -> usize {
+    (len + 3) & !3
+}
@@ -0,0 +1,1225 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Nispor does not support changing bridge port VLAN filtering, creating some
+// virtual interfaces or changing route rules yet, this is a minimal netlink
+// client for them using the message types of `netlink-packet-route` and
+// `netlink-packet-generic`:
+//  * `RTM_SETLINK` and `RTM_DELLINK` of `AF_BRIDGE` family, equal to
+//    `bridge vlan add|del dev <port> ...`.
+//  * `RTM_NEWLINK` and `RTM_DELLINK`, equal to `ip link add|del ...`.
+//  * `RTM_SETLINK` with `IFLA_NET_NS_FD`, equal to `ip link set netns ...`.
+//  * `RTM_NEWLINK` with `IFLA_BOND_SLAVE_PRIO`, equal to
+//    `ip link set <port> type bond_slave prio ...`.
+//  * `RTM_GETLINK` for `IFLA_INFO_DATA` of interfaces nispor does not parse,
+//    e.g. IP tunnels.
+//  * `RTM_NEWRULE`, `RTM_DELRULE` and `RTM_GETRULE`, equal to
+//    `ip rule add|del|show ...`.
+//  * `RTM_NEWADDR`, `RTM_DELADDR`, `RTM_NEWROUTE` and `RTM_DELROUTE` for
+//    IP address and routes with lifetime or protocol, used by DHCP client.
+//  * `RTM_NEWNEXTHOP`, `RTM_DELNEXTHOP` and `RTM_GETNEXTHOP`, equal to
+//    `ip nexthop add|del|show ...`. The `netlink-packet-route` crate does
+//    not provide nexthop messages yet, they are defined here.
+//  * `RTM_GETROUTE` and `RTM_NEWROUTE` with `RTA_NH_ID` for routes referring
+//    nexthop objects.
+//  * `RTM_NEWROUTE` and `RTM_DELROUTE` for routes with type, scope, preferred
+//    source, metrics or onlink flag.
+//  * Generic netlink request, used by WireGuard.
+
+use std::fmt::Debug;
+use std::net::IpAddr;
+use std::os::unix::io::RawFd;
+
+pub(crate) use netlink_packet_core::NLM_F_DUMP;
+use netlink_packet_core::{
+    emit_u32, parse_ip, parse_u32, DecodeError, DefaultNla, Emitable,
+    ErrorContext, NetlinkDeserializable, NetlinkHeader, NetlinkMessage,
+    NetlinkPayload, NetlinkSerializable, Nla, NlaBuffer, NlasIterator,
+    Parseable, ParseableParametrized, NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL,
+    NLM_F_REPLACE, NLM_F_REQUEST,
+};
+use netlink_packet_generic::{
+    ctrl::{nlas::GenlCtrlAttrs, GenlCtrl, GenlCtrlCmd},
+    GenlFamily, GenlHeader, GenlMessage,
+};
+use netlink_packet_route::{
+    address::{AddressAttribute, AddressMessage, CacheInfo},
+    link::{
+        AfSpecBridge, BridgeVlanInfo as AfBridgeVlanInfo, BridgeVlanInfoFlags,
+        InfoBondPort, InfoData, InfoKind, InfoPortData, InfoPortKind,
+        LinkAttribute, LinkInfo, LinkMessage,
+    },
+    route::{
+        RouteAddress, RouteAttribute, RouteFlags, RouteHeader, RouteMessage,
+        RouteMetric, RouteProtocol, RouteScope, RouteType,
+    },
+    rule::{
+        RuleAction, RuleAttribute, RuleMessage, RulePortRange, RuleUidRange,
+    },
+    AddressFamily, IpProtocol, RouteNetlinkMessage,
+};
+use netlink_sys::{
+    protocols::NETLINK_GENERIC, protocols::NETLINK_ROUTE, Socket,
+};
+
+use crate::{
+    nispor::sysfs::{read_sysfs, sysfs_iface_path},
+    ErrorKind, NmstateError,
+};
+
+const FRA_DSCP: u16 = 25;
+pub(crate) const RTPROT_STATIC: u8 = 4;
+pub(crate) const RTPROT_DHCP: u8 = 16;
+pub(crate) const RTN_UNICAST: u8 = 1;
+// Bit index of locked metrics in `RTAX_LOCK`
+pub(crate) const RTAX_MTU: u16 = 2;
+pub(crate) const RTAX_CWND: u16 = 7;
+const RTA_NH_ID: u16 = 30;
+
+const RTM_NEWNEXTHOP: u16 = 104;
+const RTM_DELNEXTHOP: u16 = 105;
+const RTM_GETNEXTHOP: u16 = 106;
+const NHMSG_LEN: usize = 8;
+const NEXTHOP_GRP_LEN: usize = 8;
+const NHA_ID: u16 = 1;
+const NHA_GROUP: u16 = 2;
+const NHA_BLACKHOLE: u16 = 4;
+const NHA_OIF: u16 = 5;
+const NHA_GATEWAY: u16 = 6;
+const NHA_FDB: u16 = 11;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NlLinkConf {
+    pub(crate) kind: InfoKind,
+    /// The `IFLA_LINK` interface name
+    pub(crate) base_iface: Option<String>,
+    /// The `IFLA_INFO_DATA`
+    pub(crate) info_data: Option<InfoData>,
+}
+
+impl NlLinkConf {
+    pub(crate) fn new(kind: InfoKind) -> Self {
+        Self {
+            kind,
+            base_iface: None,
+            info_data: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NlRouteRule {
+    pub(crate) is_ipv6: bool,
+    pub(crate) action: RuleAction,
+    pub(crate) src: Option<(IpAddr, u8)>,
+    pub(crate) dst: Option<(IpAddr, u8)>,
+    pub(crate) table: Option<u32>,
+    pub(crate) priority: Option<u32>,
+    pub(crate) iif: Option<String>,
+    pub(crate) fwmark: Option<u32>,
+    pub(crate) fwmask: Option<u32>,
+    pub(crate) suppress_prefix_length: Option<u32>,
+    pub(crate) tos: u8,
+    pub(crate) oif: Option<String>,
+    pub(crate) goto: Option<u32>,
+    pub(crate) l3mdev: bool,
+    pub(crate) ip_proto: Option<u8>,
+    /// Inclusive ranges in `(start, end)`
+    pub(crate) uid_range: Option<(u32, u32)>,
+    pub(crate) sport_range: Option<(u16, u16)>,
+    pub(crate) dport_range: Option<(u16, u16)>,
+    /// Only used for querying
+    pub(crate) protocol: Option<u8>,
+}
+
+impl NlRouteRule {
+    pub(crate) fn new(is_ipv6: bool, action: RuleAction) -> Self {
+        Self {
+            is_ipv6,
+            action,
+            src: None,
+            dst: None,
+            table: None,
+            priority: None,
+            iif: None,
+            fwmark: None,
+            fwmask: None,
+            suppress_prefix_length: None,
+            tos: 0,
+            oif: None,
+            goto: None,
+            l3mdev: false,
+            ip_proto: None,
+            uid_range: None,
+            sport_range: None,
+            dport_range: None,
+            protocol: None,
+        }
+    }
+
+    pub(crate) fn to_message(&self, set_protocol: bool) -> RuleMessage {
+        let table = self.table.unwrap_or_default();
+        let mut msg = RuleMessage::default();
+        msg.header.family = if self.is_ipv6 {
+            AddressFamily::Inet6
+        } else {
+            AddressFamily::Inet
+        };
+        msg.header.dst_len = self.dst.map(|(_, l)| l).unwrap_or_default();
+        msg.header.src_len = self.src.map(|(_, l)| l).unwrap_or_default();
+        msg.header.tos = self.tos;
+        msg.header.table = if table > u8::MAX as u32 {
+            RouteHeader::RT_TABLE_UNSPEC
+        } else {
+            table as u8
+        };
+        msg.header.action = self.action;
+
+        let nlas = &mut msg.attributes;
+        if let Some((ip, _)) = self.dst {
+            nlas.push(RuleAttribute::Destination(ip));
+        }
+        if let Some((ip, _)) = self.src {
+            nlas.push(RuleAttribute::Source(ip));
+        }
+        if let Some(iif) = self.iif.as_deref() {
+            nlas.push(RuleAttribute::Iifname(iif.to_string()));
+        }
+        if let Some(v) = self.priority {
+            nlas.push(RuleAttribute::Priority(v));
+        }
+        if let Some(v) = self.fwmark {
+            nlas.push(RuleAttribute::FwMark(v));
+        }
+        if let Some(v) = self.fwmask {
+            nlas.push(RuleAttribute::FwMask(v));
+        }
+        if let Some(v) = self.suppress_prefix_length {
+            nlas.push(RuleAttribute::SuppressPrefixLen(v));
+        }
+        if self.table.is_some() {
+            nlas.push(RuleAttribute::Table(table));
+        }
+        if let Some(oif) = self.oif.as_deref() {
+            nlas.push(RuleAttribute::Oifname(oif.to_string()));
+        }
+        if let Some(v) = self.goto {
+            nlas.push(RuleAttribute::Goto(v));
+        }
+        if self.l3mdev {
+            nlas.push(RuleAttribute::L3MDev(true));
+        }
+        if let Some(v) = self.ip_proto {
+            nlas.push(RuleAttribute::IpProtocol(IpProtocol::from(i32::from(
+                v,
+            ))));
+        }
+        if let Some((start, end)) = self.uid_range {
+            nlas.push(RuleAttribute::UidRange(RuleUidRange { start, end }));
+        }
+        if let Some((start, end)) = self.sport_range {
+            nlas.push(RuleAttribute::SourcePortRange(RulePortRange {
+                start,
+                end,
+            }));
+        }
+        if let Some((start, end)) = self.dport_range {
+            nlas.push(RuleAttribute::DestinationPortRange(RulePortRange {
+                start,
+                end,
+            }));
+        }
+        if set_protocol {
+            nlas.push(RuleAttribute::Protocol(RouteProtocol::Static));
+        }
+        msg
+    }
+
+    /// Return None for multicast route rules or rules with unknown action.
+    pub(crate) fn from_message(msg: &RuleMessage) -> Option<Self> {
+        let is_ipv6 = match msg.header.family {
+            AddressFamily::Inet => false,
+            AddressFamily::Inet6 => true,
+            _ => return None,
+        };
+        match msg.header.action {
+            RuleAction::ToTable
+            | RuleAction::Goto
+            | RuleAction::Nop
+            | RuleAction::Blackhole
+            | RuleAction::Unreachable
+            | RuleAction::Prohibit => (),
+            action => {
+                log::debug!("Ignoring route rule with action {action:?}");
+                return None;
+            }
+        }
+        let mut rule = NlRouteRule::new(is_ipv6, msg.header.action);
+        rule.tos = msg.header.tos;
+        if msg.header.table != RouteHeader::RT_TABLE_UNSPEC {
+            rule.table = Some(msg.header.table.into());
+        }
+        let (dst_len, src_len) = (msg.header.dst_len, msg.header.src_len);
+        for nla in msg.attributes.iter() {
+            match nla {
+                RuleAttribute::Destination(ip) => {
+                    rule.dst = Some((*ip, dst_len))
+                }
+                RuleAttribute::Source(ip) => rule.src = Some((*ip, src_len)),
+                RuleAttribute::Iifname(v) => rule.iif = Some(v.to_string()),
+                RuleAttribute::Oifname(v) => rule.oif = Some(v.to_string()),
+                RuleAttribute::Goto(v) => rule.goto = Some(*v),
+                RuleAttribute::Priority(v) => rule.priority = Some(*v),
+                RuleAttribute::FwMark(v) => rule.fwmark = Some(*v),
+                RuleAttribute::FwMask(v) => rule.fwmask = Some(*v),
+                RuleAttribute::SuppressPrefixLen(v) => {
+                    rule.suppress_prefix_length =
+                        Some(*v).filter(|v| *v != u32::MAX)
+                }
+                RuleAttribute::Table(v) if *v != 0 => rule.table = Some(*v),
+                RuleAttribute::L3MDev(v) => rule.l3mdev = *v,
+                RuleAttribute::UidRange(v) => {
+                    rule.uid_range = Some((v.start, v.end))
+                }
+                RuleAttribute::Protocol(v) => rule.protocol = Some((*v).into()),
+                RuleAttribute::IpProtocol(v) => {
+                    rule.ip_proto = Some(i32::from(*v) as u8)
+                }
+                RuleAttribute::SourcePortRange(v) => {
+                    rule.sport_range = Some((v.start, v.end))
+                }
+                RuleAttribute::DestinationPortRange(v) => {
+                    rule.dport_range = Some((v.start, v.end))
+                }
+                // Kernel uses this instead of TOS in header when rule is
+                // created with DSCP
+                RuleAttribute::Other(nla) if nla.kind() == FRA_DSCP => {
+                    if let Some(v) = default_nla_value(nla).first() {
+                        rule.tos = v << 2;
+                    }
+                }
+                _ => (),
+            }
+        }
+        Some(rule)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct BridgeVlanInfo {
+    pub(crate) vid_min: u16,
+    pub(crate) vid_max: u16,
+    pub(crate) is_pvid: bool,
+    pub(crate) is_untagged: bool,
+}
+
+impl BridgeVlanInfo {
+    pub(crate) fn new(
+        vid_min: u16,
+        vid_max: u16,
+        is_pvid: bool,
+        is_untagged: bool,
+    ) -> Self {
+        Self {
+            vid_min,
+            vid_max,
+            is_pvid,
+            is_untagged,
+        }
+    }
+
+    fn flags(&self) -> BridgeVlanInfoFlags {
+        let mut flags = BridgeVlanInfoFlags::empty();
+        if self.is_pvid {
+            flags |= BridgeVlanInfoFlags::Pvid;
+        }
+        if self.is_untagged {
+            flags |= BridgeVlanInfoFlags::Untagged;
+        }
+        flags
+    }
+
+    pub(crate) fn to_nlas(self) -> Vec<AfSpecBridge> {
+        if self.vid_min == self.vid_max {
+            vec![AfSpecBridge::VlanInfo(AfBridgeVlanInfo {
+                flags: self.flags(),
+                vid: self.vid_min,
+            })]
+        } else {
+            vec![
+                AfSpecBridge::VlanInfo(AfBridgeVlanInfo {
+                    flags: self.flags() | BridgeVlanInfoFlags::RangeBegin,
+                    vid: self.vid_min,
+                }),
+                AfSpecBridge::VlanInfo(AfBridgeVlanInfo {
+                    flags: self.flags() | BridgeVlanInfoFlags::RangeEnd,
+                    vid: self.vid_max,
+                }),
+            ]
+        }
+    }
+}
+
+pub(crate) fn bridge_vlan_add(
+    iface_name: &str,
+    vlans: &[BridgeVlanInfo],
+) -> Result<(), NmstateError> {
+    if vlans.is_empty() {
+        return Ok(());
+    }
+    log::debug!("Adding bridge VLANs {vlans:?} to {iface_name}");
+    let msg = bridge_vlan_message(iface_name, vlans)?;
+    send_request(RouteNetlinkMessage::SetLink(msg), 0).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Failed to add bridge VLANs {vlans:?} of interface \
+                {iface_name}: {e}"
+            ),
+        )
+    })
+}
+
+pub(crate) fn bridge_vlan_del(
+    iface_name: &str,
+    vlans: &[BridgeVlanInfo],
+) -> Result<(), NmstateError> {
+    if vlans.is_empty() {
+        return Ok(());
+    }
+    log::debug!("Removing bridge VLANs {vlans:?} from {iface_name}");
+    let msg = bridge_vlan_message(iface_name, vlans)?;
+    send_request(RouteNetlinkMessage::DelLink(msg), 0).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Failed to remove bridge VLANs {vlans:?} of interface \
+                {iface_name}: {e}"
+            ),
+        )
+    })
+}
+
+fn bridge_vlan_message(
+    iface_name: &str,
+    vlans: &[BridgeVlanInfo],
+) -> Result<LinkMessage, NmstateError> {
+    let mut msg = LinkMessage::default();
+    msg.header.interface_family = AddressFamily::Bridge;
+    msg.header.index = get_iface_index(iface_name)?;
+    msg.attributes.push(LinkAttribute::AfSpecBridge(
+        vlans.iter().flat_map(|v| v.to_nlas()).collect(),
+    ));
+    Ok(msg)
+}
+
+pub(crate) fn link_add(
+    iface_name: &str,
+    conf: &NlLinkConf,
+) -> Result<(), NmstateError> {
+    log::debug!("Creating interface {iface_name} with {conf:?}");
+    let mut msg = LinkMessage::default();
+    msg.attributes
+        .push(LinkAttribute::IfName(iface_name.to_string()));
+    if let Some(base_iface) = conf.base_iface.as_deref() {
+        msg.attributes
+            .push(LinkAttribute::Link(get_iface_index(base_iface)?));
+    }
+    let mut link_info = vec![LinkInfo::Kind(conf.kind.clone())];
+    if let Some(info_data) = conf.info_data.as_ref() {
+        link_info.push(LinkInfo::Data(info_data.clone()));
+    }
+    msg.attributes.push(LinkAttribute::LinkInfo(link_info));
+
+    send_request(RouteNetlinkMessage::NewLink(msg), NLM_F_CREATE | NLM_F_EXCL)
+        .map_err(|e| {
+            NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Kernel refused to create {} interface {}: {}",
+                    conf.kind, iface_name, e
+                ),
+            )
+        })
+}
+
+/// Query the `IFLA_INFO_DATA` of specified interface.
+pub(crate) fn link_get_info_data(
+    iface_index: u32,
+) -> Result<Option<InfoData>, nix::Error> {
+    let mut msg = LinkMessage::default();
+    msg.header.index = iface_index;
+    for reply in
+        nl_request(NETLINK_ROUTE, 0, RouteNetlinkMessage::GetLink(msg))?
+    {
+        if let RouteNetlinkMessage::NewLink(link) = reply {
+            for nla in link.attributes {
+                if let LinkAttribute::LinkInfo(infos) = nla {
+                    for info in infos {
+                        if let LinkInfo::Data(info_data) = info {
+                            return Ok(Some(info_data));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The attributes nested in `IFLA_INFO_DATA` in wire format, used for
+/// interface types `netlink-packet-route` does not parse.
+pub(crate) fn info_data_nla_bytes(info_data: &InfoData) -> Vec<u8> {
+    let mut ret = vec![0u8; info_data.value_len()];
+    info_data.emit_value(&mut ret);
+    ret
+}
+
+/// Build the `IFLA_INFO_DATA` from the attributes in wire format, used for
+/// interface types `netlink-packet-route` does not support.
+pub(crate) fn info_data_from_nlas(nlas: &[DefaultNla]) -> InfoData {
+    let mut ret = vec![0u8; nlas.buffer_len()];
+    nlas.emit(&mut ret);
+    InfoData::Other(ret)
+}
+
+/// Parse the `IFLA_INFO_DATA` into attributes, used for interface types
+/// `netlink-packet-route` does not parse.
+pub(crate) fn info_data_to_nlas(info_data: &InfoData) -> Vec<DefaultNla> {
+    let bytes = info_data_nla_bytes(info_data);
+    NlasIterator::new(bytes.as_slice())
+        .filter_map(|nla| nla.and_then(|nla| DefaultNla::parse(&nla)).ok())
+        .collect()
+}
+
+pub(crate) fn ip_nla(kind: u16, ip: &IpAddr) -> DefaultNla {
+    match ip {
+        IpAddr::V4(ip) => DefaultNla::new(kind, ip.octets().to_vec()),
+        IpAddr::V6(ip) => DefaultNla::new(kind, ip.octets().to_vec()),
+    }
+}
+
+pub(crate) fn link_del(iface_name: &str) -> Result<(), NmstateError> {
+    log::debug!("Deleting interface {iface_name}");
+    let mut msg = LinkMessage::default();
+    msg.header.index = get_iface_index(iface_name)?;
+    send_request(RouteNetlinkMessage::DelLink(msg), 0).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to delete interface {iface_name}: {e}"),
+        )
+    })
+}
+
+pub(crate) fn link_set_netns(
+    iface_name: &str,
+    netns_fd: RawFd,
+) -> Result<(), NmstateError> {
+    log::debug!(
+        "Moving interface {iface_name} to network namespace fd {netns_fd}"
+    );
+    let mut msg = LinkMessage::default();
+    msg.header.index = get_iface_index(iface_name)?;
+    msg.attributes.push(LinkAttribute::NetNsFd(netns_fd));
+    send_request(RouteNetlinkMessage::SetLink(msg), 0).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Failed to move interface {iface_name} to other network \
+                namespace: {e}"
+            ),
+        )
+    })
+}
+
+/// Set the priority of bond port used by bond active slave reselection.
+pub(crate) fn link_set_bond_port_prio(
+    port_name: &str,
+    prio: i32,
+) -> Result<(), NmstateError> {
+    log::debug!("Setting bond port {port_name} priority to {prio}");
+    let mut msg = LinkMessage::default();
+    msg.header.index = get_iface_index(port_name)?;
+    msg.attributes.push(bond_port_prio_link_info(prio));
+    // Kernel only changes port options via `RTM_NEWLINK`
+    send_request(RouteNetlinkMessage::NewLink(msg), 0).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Failed to set bond port {port_name} priority to {prio}: {e}"
+            ),
+        )
+    })
+}
+
+pub(crate) fn bond_port_prio_link_info(prio: i32) -> LinkAttribute {
+    LinkAttribute::LinkInfo(vec![
+        LinkInfo::PortKind(InfoPortKind::Bond),
+        LinkInfo::PortData(InfoPortData::BondPort(vec![InfoBondPort::Prio(
+            prio,
+        )])),
+    ])
+}
+
+pub(crate) fn rule_add(rule: &NlRouteRule) -> Result<(), nix::Error> {
+    log::debug!("Adding route rule {rule:?}");
+    match send_request(
+        RouteNetlinkMessage::NewRule(rule.to_message(true)),
+        NLM_F_CREATE | NLM_F_EXCL,
+    ) {
+        Ok(()) | Err(nix::Error::EEXIST) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+pub(crate) fn rule_del(rule: &NlRouteRule) -> Result<(), nix::Error> {
+    log::debug!("Removing route rule {rule:?}");
+    match send_request(RouteNetlinkMessage::DelRule(rule.to_message(false)), 0)
+    {
+        Ok(()) | Err(nix::Error::ENOENT) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Dump route rules of all address families. Rules with unknown action are
+/// ignored.
+pub(crate) fn rule_dump() -> Result<Vec<NlRouteRule>, nix::Error> {
+    Ok(nl_request(
+        NETLINK_ROUTE,
+        NLM_F_DUMP,
+        RouteNetlinkMessage::GetRule(RuleMessage::default()),
+    )?
+    .iter()
+    .filter_map(|reply| match reply {
+        RouteNetlinkMessage::NewRule(msg) => NlRouteRule::from_message(msg),
+        _ => None,
+    })
+    .collect())
+}
+
+pub(crate) fn addr_replace(
+    iface_name: &str,
+    ip: &IpAddr,
+    prefix_len: u8,
+    valid_lft: u32,
+    preferred_lft: u32,
+) -> Result<(), NmstateError> {
+    log::debug!(
+        "Setting IP address {ip}/{prefix_len} to {iface_name} with valid \
+        lifetime {valid_lft} and preferred lifetime {preferred_lft}"
+    );
+    let mut msg = addr_message(iface_name, ip, prefix_len)?;
+    let mut cache_info = CacheInfo::default();
+    cache_info.ifa_preferred = preferred_lft;
+    cache_info.ifa_valid = valid_lft;
+    msg.attributes.push(AddressAttribute::CacheInfo(cache_info));
+
+    send_request(
+        RouteNetlinkMessage::NewAddress(msg),
+        NLM_F_CREATE | NLM_F_REPLACE,
+    )
+    .map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Failed to set IP address {ip}/{prefix_len} to \
+                {iface_name}: {e}"
+            ),
+        )
+    })
+}
+
+pub(crate) fn addr_del(
+    iface_name: &str,
+    ip: &IpAddr,
+    prefix_len: u8,
+) -> Result<(), NmstateError> {
+    log::debug!("Removing IP address {ip}/{prefix_len} from {iface_name}");
+    let msg = addr_message(iface_name, ip, prefix_len)?;
+    match send_request(RouteNetlinkMessage::DelAddress(msg), 0) {
+        Ok(()) | Err(nix::Error::EADDRNOTAVAIL) => Ok(()),
+        Err(e) => Err(NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Failed to remove IP address {ip}/{prefix_len} from \
+                {iface_name}: {e}"
+            ),
+        )),
+    }
+}
+
+fn addr_message(
+    iface_name: &str,
+    ip: &IpAddr,
+    prefix_len: u8,
+) -> Result<AddressMessage, NmstateError> {
+    let mut msg = AddressMessage::default();
+    msg.header.family = ip_family(ip);
+    msg.header.prefix_len = prefix_len;
+    msg.header.index = get_iface_index(iface_name)?;
+    msg.attributes.push(AddressAttribute::Local(*ip));
+    msg.attributes.push(AddressAttribute::Address(*ip));
+    Ok(msg)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NlRoute {
+    /// Not used when `nexthop_id` is set
+    pub(crate) oif: Option<String>,
+    pub(crate) dst: IpAddr,
+    pub(crate) dst_len: u8,
+    pub(crate) gateway: Option<IpAddr>,
+    pub(crate) table: u32,
+    pub(crate) metric: Option<u32>,
+    pub(crate) protocol: u8,
+    pub(crate) nexthop_id: Option<u32>,
+    /// The `RTN_*` route type
+    pub(crate) route_type: u8,
+    /// The `RT_SCOPE_*` route scope, use the same default scope as
+    /// `ip route add` when not defined.
+    pub(crate) scope: Option<u8>,
+    pub(crate) prefsrc: Option<IpAddr>,
+    /// The attributes nested in `RTA_METRICS`
+    pub(crate) metrics: Vec<RouteMetric>,
+    pub(crate) onlink: bool,
+}
+
+impl NlRoute {
+    fn default_scope(&self) -> RouteScope {
+        match RouteType::from(self.route_type) {
+            RouteType::Local => RouteScope::Host,
+            RouteType::Broadcast
+            | RouteType::Anycast
+            | RouteType::Multicast => RouteScope::Link,
+            RouteType::Unicast
+                if self.gateway.is_none() && self.nexthop_id.is_none() =>
+            {
+                RouteScope::Link
+            }
+            _ => RouteScope::Universe,
+        }
+    }
+
+    pub(crate) fn to_message(
+        &self,
+        is_del: bool,
+    ) -> Result<RouteMessage, NmstateError> {
+        let mut msg = RouteMessage::default();
+        msg.header.address_family = ip_family(&self.dst);
+        msg.header.destination_prefix_length = self.dst_len;
+        msg.header.table = if self.table > u8::MAX as u32 {
+            RouteHeader::RT_TABLE_UNSPEC
+        } else {
+            self.table as u8
+        };
+        msg.header.protocol = self.protocol.into();
+        // Like `ip route del`, match route of any scope when removing
+        msg.header.scope = match self.scope {
+            Some(scope) => scope.into(),
+            None if is_del => RouteScope::NoWhere,
+            None => self.default_scope(),
+        };
+        msg.header.kind = self.route_type.into();
+        if self.onlink {
+            msg.header.flags = RouteFlags::Onlink;
+        }
+
+        let nlas = &mut msg.attributes;
+        nlas.push(RouteAttribute::Table(self.table));
+        if self.dst_len > 0 {
+            nlas.push(RouteAttribute::Destination(self.dst.into()));
+        }
+        if let Some(nexthop_id) = self.nexthop_id {
+            nlas.push(RouteAttribute::Other(DefaultNla::new(
+                RTA_NH_ID,
+                nexthop_id.to_ne_bytes().to_vec(),
+            )));
+        } else if let Some(oif) = self.oif.as_deref() {
+            nlas.push(RouteAttribute::Oif(get_iface_index(oif)?));
+        }
+        if let Some(gateway) = self.gateway {
+            nlas.push(RouteAttribute::Gateway(gateway.into()));
+        }
+        if let Some(metric) = self.metric {
+            nlas.push(RouteAttribute::Priority(metric));
+        }
+        if let Some(prefsrc) = self.prefsrc {
+            nlas.push(RouteAttribute::PrefSource(prefsrc.into()));
+        }
+        if !self.metrics.is_empty() {
+            nlas.push(RouteAttribute::Metrics(self.metrics.clone()));
+        }
+        Ok(msg)
+    }
+
+    /// Only `dst`, `dst_len`, `table`, `metric`, `protocol`, `route_type`,
+    /// `scope` and `nexthop_id` are filled.
+    pub(crate) fn from_message(msg: &RouteMessage) -> Self {
+        let mut route = NlRoute {
+            oif: None,
+            dst: if msg.header.address_family == AddressFamily::Inet6 {
+                IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED)
+            } else {
+                IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED)
+            },
+            dst_len: msg.header.destination_prefix_length,
+            gateway: None,
+            table: msg.header.table.into(),
+            metric: None,
+            protocol: msg.header.protocol.into(),
+            nexthop_id: None,
+            route_type: msg.header.kind.into(),
+            scope: Some(msg.header.scope.into()),
+            prefsrc: None,
+            metrics: Vec::new(),
+            onlink: false,
+        };
+        for nla in msg.attributes.iter() {
+            match nla {
+                RouteAttribute::Destination(RouteAddress::Inet(ip)) => {
+                    route.dst = IpAddr::V4(*ip)
+                }
+                RouteAttribute::Destination(RouteAddress::Inet6(ip)) => {
+                    route.dst = IpAddr::V6(*ip)
+                }
+                RouteAttribute::Table(v) => route.table = *v,
+                RouteAttribute::Priority(v) => route.metric = Some(*v),
+                RouteAttribute::Other(nla) if nla.kind() == RTA_NH_ID => {
+                    route.nexthop_id = parse_u32(&default_nla_value(nla)).ok();
+                }
+                _ => (),
+            }
+        }
+        route
+    }
+}
+
+pub(crate) fn route_replace(route: &NlRoute) -> Result<(), NmstateError> {
+    log::debug!("Adding route {route:?}");
+    send_request(
+        RouteNetlinkMessage::NewRoute(route.to_message(false)?),
+        NLM_F_CREATE | NLM_F_REPLACE,
+    )
+    .map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to add route {route:?}: {e}"),
+        )
+    })
+}
+
+pub(crate) fn route_del(route: &NlRoute) -> Result<(), NmstateError> {
+    log::debug!("Removing route {route:?}");
+    match send_request(
+        RouteNetlinkMessage::DelRoute(route.to_message(true)?),
+        0,
+    ) {
+        Ok(()) | Err(nix::Error::ESRCH) => Ok(()),
+        Err(e) => Err(NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to remove route {route:?}: {e}"),
+        )),
+    }
+}
+
+/// Dump routes referring nexthop objects, only `dst`, `dst_len`, `table`,
+/// `metric`, `protocol` and `nexthop_id` are filled.
+pub(crate) fn route_get_nexthop_ids() -> Result<Vec<NlRoute>, nix::Error> {
+    Ok(nl_request(
+        NETLINK_ROUTE,
+        NLM_F_DUMP,
+        RouteNetlinkMessage::GetRoute(RouteMessage::default()),
+    )?
+    .iter()
+    .filter_map(|reply| match reply {
+        RouteNetlinkMessage::NewRoute(msg) => Some(NlRoute::from_message(msg)),
+        _ => None,
+    })
+    .filter(|route| route.nexthop_id.is_some())
+    .collect())
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct NlNexthop {
+    pub(crate) id: u32,
+    /// Address family of single nexthop, not used by nexthop group
+    pub(crate) is_ipv6: bool,
+    /// Interface index
+    pub(crate) oif: Option<u32>,
+    pub(crate) gateway: Option<IpAddr>,
+    /// Members of nexthop group in `(id, weight)`
+    pub(crate) group: Vec<(u32, u16)>,
+    pub(crate) protocol: u8,
+}
+
+impl NlNexthop {
+    fn to_message(&self, message_type: u16) -> NexthopMessage {
+        let mut msg = NexthopMessage {
+            message_type,
+            family: if !self.group.is_empty() {
+                AddressFamily::Unspec
+            } else if self.is_ipv6 {
+                AddressFamily::Inet6
+            } else {
+                AddressFamily::Inet
+            },
+            protocol: self.protocol,
+            attributes: vec![NexthopAttribute::Id(self.id)],
+        };
+        if !self.group.is_empty() {
+            // Kernel is using `weight + 1` as weight.
+            msg.attributes.push(NexthopAttribute::Group(
+                self.group
+                    .iter()
+                    .map(|(id, weight)| (*id, weight.saturating_sub(1) as u8))
+                    .collect(),
+            ));
+        } else {
+            if let Some(oif) = self.oif {
+                msg.attributes.push(NexthopAttribute::Oif(oif));
+            }
+            if let Some(gateway) = self.gateway {
+                msg.attributes.push(NexthopAttribute::Gateway(gateway));
+            }
+        }
+        msg
+    }
+
+    /// Return None for blackhole and FDB nexthop objects.
+    fn from_message(msg: &NexthopMessage) -> Option<Self> {
+        let mut nh = NlNexthop {
+            is_ipv6: msg.family == AddressFamily::Inet6,
+            protocol: msg.protocol,
+            ..Default::default()
+        };
+        for nla in msg.attributes.iter() {
+            match nla {
+                NexthopAttribute::Id(v) => nh.id = *v,
+                NexthopAttribute::Group(members) => {
+                    nh.group = members
+                        .iter()
+                        .map(|(id, weight)| (*id, u16::from(*weight) + 1))
+                        .collect()
+                }
+                NexthopAttribute::Oif(v) => nh.oif = Some(*v),
+                NexthopAttribute::Gateway(v) => nh.gateway = Some(*v),
+                NexthopAttribute::Blackhole | NexthopAttribute::Fdb => {
+                    return None
+                }
+                NexthopAttribute::Other(_) => (),
+            }
+        }
+        Some(nh).filter(|nh| nh.id != 0)
+    }
+}
+
+/// Dump all nexthop objects except blackhole and FDB ones.
+pub(crate) fn nexthop_dump() -> Result<Vec<NlNexthop>, nix::Error> {
+    let msg = NexthopMessage {
+        message_type: RTM_GETNEXTHOP,
+        family: AddressFamily::Unspec,
+        protocol: 0,
+        attributes: Vec::new(),
+    };
+    Ok(nl_request(NETLINK_ROUTE, NLM_F_DUMP, msg)?
+        .iter()
+        .filter_map(NlNexthop::from_message)
+        .collect())
+}
+
+/// Create or replace nexthop object.
+pub(crate) fn nexthop_replace(nh: &NlNexthop) -> Result<(), nix::Error> {
+    log::debug!("Adding nexthop {nh:?}");
+    nl_request(
+        NETLINK_ROUTE,
+        NLM_F_CREATE | NLM_F_REPLACE,
+        nh.to_message(RTM_NEWNEXTHOP),
+    )
+    .map(|_| ())
+}
+
+pub(crate) fn nexthop_del(id: u32) -> Result<(), nix::Error> {
+    log::debug!("Removing nexthop {id}");
+    let msg = NexthopMessage {
+        message_type: RTM_DELNEXTHOP,
+        family: AddressFamily::Unspec,
+        protocol: 0,
+        attributes: vec![NexthopAttribute::Id(id)],
+    };
+    match nl_request(NETLINK_ROUTE, 0, msg) {
+        Ok(_) | Err(nix::Error::ENOENT) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+// The `struct nhmsg` and `NHA_*` attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NexthopMessage {
+    pub(crate) message_type: u16,
+    pub(crate) family: AddressFamily,
+    pub(crate) protocol: u8,
+    pub(crate) attributes: Vec<NexthopAttribute>,
+}
+
+impl NetlinkSerializable for NexthopMessage {
+    fn message_type(&self) -> u16 {
+        self.message_type
+    }
+
+    fn buffer_len(&self) -> usize {
+        NHMSG_LEN + self.attributes.as_slice().buffer_len()
+    }
+
+    fn serialize(&self, buffer: &mut [u8]) {
+        buffer[..NHMSG_LEN].fill(0);
+        buffer[0] = self.family.into();
+        buffer[2] = self.protocol;
+        self.attributes.as_slice().emit(&mut buffer[NHMSG_LEN..]);
+    }
+}
+
+impl NetlinkDeserializable for NexthopMessage {
+    type Error = DecodeError;
+
+    fn deserialize(
+        header: &NetlinkHeader,
+        payload: &[u8],
+    ) -> Result<Self, Self::Error> {
+        if payload.len() < NHMSG_LEN {
+            return Err(DecodeError::buffer_too_small(
+                payload.len(),
+                NHMSG_LEN,
+            ));
+        }
+        let mut attributes = Vec::new();
+        for nla in NlasIterator::new(&payload[NHMSG_LEN..]) {
+            attributes.push(NexthopAttribute::parse(&nla?)?);
+        }
+        Ok(Self {
+            message_type: header.message_type,
+            family: payload[0].into(),
+            protocol: payload[2],
+            attributes,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NexthopAttribute {
+    Id(u32),
+    /// Members in `(id, weight)` of `struct nexthop_grp`
+    Group(Vec<(u32, u8)>),
+    Blackhole,
+    Oif(u32),
+    Gateway(IpAddr),
+    Fdb,
+    Other(DefaultNla),
+}
+
+impl Nla for NexthopAttribute {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Id(_) | Self::Oif(_) => 4,
+            Self::Group(members) => members.len() * NEXTHOP_GRP_LEN,
+            Self::Blackhole | Self::Fdb => 0,
+            Self::Gateway(IpAddr::V4(_)) => 4,
+            Self::Gateway(IpAddr::V6(_)) => 16,
+            Self::Other(nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Id(_) => NHA_ID,
+            Self::Group(_) => NHA_GROUP,
+            Self::Blackhole => NHA_BLACKHOLE,
+            Self::Oif(_) => NHA_OIF,
+            Self::Gateway(_) => NHA_GATEWAY,
+            Self::Fdb => NHA_FDB,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Id(v) | Self::Oif(v) => emit_u32(buffer, *v).unwrap(),
+            // Each `struct nexthop_grp` is `u32 id`, `u8 weight`,
+            // `u8 resvd1` and `u16 resvd2`.
+            Self::Group(members) => {
+                for ((id, weight), grp) in
+                    members.iter().zip(buffer.chunks_exact_mut(NEXTHOP_GRP_LEN))
+                {
+                    grp.fill(0);
+                    emit_u32(grp, *id).unwrap();
+                    grp[4] = *weight;
+                }
+            }
+            Self::Blackhole | Self::Fdb => (),
+            Self::Gateway(IpAddr::V4(ip)) => {
+                buffer.copy_from_slice(&ip.octets())
+            }
+            Self::Gateway(IpAddr::V6(ip)) => {
+                buffer.copy_from_slice(&ip.octets())
+            }
+            Self::Other(nla) => nla.emit_value(buffer),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for NexthopAttribute
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            NHA_ID => Self::Id(parse_u32(payload).context("invalid NHA_ID")?),
+            NHA_GROUP => Self::Group(
+                payload
+                    .chunks_exact(NEXTHOP_GRP_LEN)
+                    .map(|grp| {
+                        Ok((
+                            parse_u32(&grp[..4])
+                                .context("invalid NHA_GROUP id")?,
+                            grp[4],
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, DecodeError>>()?,
+            ),
+            NHA_BLACKHOLE => Self::Blackhole,
+            NHA_OIF => {
+                Self::Oif(parse_u32(payload).context("invalid NHA_OIF")?)
+            }
+            NHA_GATEWAY => {
+                Self::Gateway(parse_ip(payload).context("invalid NHA_GATEWAY")?)
+            }
+            NHA_FDB => Self::Fdb,
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+fn send_request(
+    msg: RouteNetlinkMessage,
+    flags: u16,
+) -> Result<(), nix::Error> {
+    nl_request(NETLINK_ROUTE, flags, msg).map(|_| ())
+}
+
+/// Send generic netlink request to the family of payload and return the
+/// payloads of reply messages.
+pub(crate) fn genl_request<F>(
+    payload: F,
+    flags: u16,
+) -> Result<Vec<F>, nix::Error>
+where
+    F: GenlFamily + Emitable + ParseableParametrized<[u8], GenlHeader> + Debug,
+{
+    let mut msg = GenlMessage::from_payload(payload);
+    msg.set_resolved_family_id(genl_family_id(F::family_name())?);
+    Ok(nl_request(NETLINK_GENERIC, flags, msg)?
+        .into_iter()
+        .map(|m| m.payload)
+        .collect())
+}
+
+fn genl_family_id(family: &str) -> Result<u16, nix::Error> {
+    let msg = GenlMessage::from_payload(GenlCtrl {
+        cmd: GenlCtrlCmd::GetFamily,
+        nlas: vec![GenlCtrlAttrs::FamilyName(family.to_string())],
+    });
+    for reply in nl_request(NETLINK_GENERIC, 0, msg)? {
+        for nla in reply.payload.nlas {
+            if let GenlCtrlAttrs::FamilyId(id) = nla {
+                return Ok(id);
+            }
+        }
+    }
+    Err(nix::Error::ENOENT)
+}
+
+// Return the payloads of reply messages.
+// For dump request, kernel does not send ACK but `NLMSG_DONE`.
+fn nl_request<I>(
+    protocol: isize,
+    flags: u16,
+    payload: I,
+) -> Result<Vec<I>, nix::Error>
+where
+    I: NetlinkSerializable + NetlinkDeserializable + Debug,
+{
+    let mut header = NetlinkHeader::default();
+    header.flags = NLM_F_REQUEST | NLM_F_ACK | flags;
+    header.sequence_number = 1;
+    let mut msg =
+        NetlinkMessage::new(header, NetlinkPayload::InnerMessage(payload));
+    msg.finalize();
+    let mut buffer = vec![0u8; msg.buffer_len()];
+    msg.serialize(&mut buffer);
+
+    let mut socket = Socket::new(protocol).map_err(io_error_to_nix)?;
+    socket.bind_auto().map_err(io_error_to_nix)?;
+    socket.send(&buffer, 0).map_err(io_error_to_nix)?;
+
+    let mut ret = Vec::new();
+    loop {
+        let (buffer, _) = socket.recv_from_full().map_err(io_error_to_nix)?;
+        let mut offset = 0;
+        while offset < buffer.len() {
+            let reply = NetlinkMessage::<I>::deserialize(&buffer[offset..])
+                .map_err(|e| {
+                    log::debug!("Failed to parse netlink reply: {e}");
+                    nix::Error::EBADMSG
+                })?;
+            // Netlink messages are 4 bytes aligned
+            offset += (reply.header.length as usize + 3) & !3;
+            match reply.payload {
+                NetlinkPayload::Error(e) => {
+                    return match e.code {
+                        Some(code) => Err(nix::Error::from_i32(-code.get())),
+                        None => Ok(ret),
+                    };
+                }
+                NetlinkPayload::Done(done) => {
+                    return if done.code == 0 {
+                        Ok(ret)
+                    } else {
+                        Err(nix::Error::from_i32(-done.code))
+                    };
+                }
+                NetlinkPayload::InnerMessage(m) => ret.push(m),
+                _ => (),
+            }
+        }
+    }
+}
+
+fn io_error_to_nix(e: std::io::Error) -> nix::Error {
+    e.raw_os_error()
+        .map(nix::Error::from_i32)
+        .unwrap_or(nix::Error::EIO)
+}
+
+/// The value of attribute `netlink-packet-route` does not parse.
+pub(crate) fn default_nla_value(nla: &DefaultNla) -> Vec<u8> {
+    let mut ret = vec![0u8; nla.value_len()];
+    nla.emit_value(&mut ret);
+    ret
+}
+
+fn ip_family(ip: &IpAddr) -> AddressFamily {
+    if ip.is_ipv6() {
+        AddressFamily::Inet6
+    } else {
+        AddressFamily::Inet
+    }
+}
+
+pub(crate) fn get_iface_index(iface_name: &str) -> Result<u32, NmstateError> {
+    read_sysfs(&sysfs_iface_path(iface_name, "ifindex"))
+        .and_then(|s| s.parse::<u32>().ok())
+        .ok_or_else(|| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Failed to get interface index of {iface_name}"),
+            )
+        })
+}
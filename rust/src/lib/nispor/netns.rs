@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::os::unix::io::AsRawFd;
+
+use crate::{
+    nispor::netlink::link_set_netns, query_apply::open_netns, MergedInterfaces,
+    NmstateError,
+};
+
+// Move interfaces with `netns` defined to specified network namespace.
+// Should be done after all other interface changes, so the parent and peer
+// of virtual interfaces are still in current network namespace when
+// creating.
+pub(crate) fn apply_ifaces_netns(
+    merged_ifaces: &MergedInterfaces,
+) -> Result<(), NmstateError> {
+    for iface in merged_ifaces
+        .kernel_ifaces
+        .values()
+        .filter(|i| i.is_changed() && !i.merged.is_absent())
+        .filter_map(|i| i.for_apply.as_ref())
+    {
+        if let Some(netns) = iface.base_iface().netns.as_deref() {
+            let fd = open_netns(netns)?;
+            log::info!(
+                "Moving interface {} to network namespace {netns}",
+                iface.name()
+            );
+            link_set_netns(iface.name(), fd.as_raw_fd())?;
+        }
+    }
+    Ok(())
+}
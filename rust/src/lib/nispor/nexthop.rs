@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use crate::{
+    nispor::netlink::{
+        get_iface_index, nexthop_del, nexthop_dump, nexthop_replace, NlNexthop,
+        RTPROT_STATIC,
+    },
+    AddressFamily, ErrorKind, MergedNexthops, NexthopEntry, NexthopGroupMember,
+    Nexthops, NmstateError,
+};
+
+// Nexthops from these protocols are included in `Nexthops.config`
+const RTPROT_UNSPEC: u8 = 0;
+const RTPROT_BOOT: u8 = 3;
+
+pub(crate) fn get_nexthops(
+    np_ifaces: &HashMap<String, nispor::Iface>,
+    running_config_only: bool,
+) -> Nexthops {
+    let mut ret = Nexthops::new();
+    let mut nl_nhs = match nexthop_dump() {
+        Ok(n) => n,
+        Err(e) => {
+            log::debug!("Failed to retrieve nexthops: {e}");
+            return ret;
+        }
+    };
+    if nl_nhs.is_empty() {
+        return ret;
+    }
+    nl_nhs.sort_unstable_by_key(|nh| nh.id);
+
+    let iface_names: HashMap<u32, &str> = np_ifaces
+        .values()
+        .map(|i| (i.index, i.name.as_str()))
+        .collect();
+
+    if !running_config_only {
+        ret.running = Some(
+            nl_nhs
+                .iter()
+                .map(|nh| nl_nexthop_to_nmstate(nh, &iface_names))
+                .collect(),
+        );
+    }
+    ret.config = Some(
+        nl_nhs
+            .iter()
+            .filter(|nh| {
+                [RTPROT_UNSPEC, RTPROT_BOOT, RTPROT_STATIC]
+                    .contains(&nh.protocol)
+            })
+            .map(|nh| nl_nexthop_to_nmstate(nh, &iface_names))
+            .collect(),
+    );
+    ret
+}
+
+fn nl_nexthop_to_nmstate(
+    nl_nh: &NlNexthop,
+    iface_names: &HashMap<u32, &str>,
+) -> NexthopEntry {
+    let mut nh = NexthopEntry::new();
+    nh.id = Some(nl_nh.id);
+    if nl_nh.group.is_empty() {
+        nh.family = Some(if nl_nh.is_ipv6 {
+            AddressFamily::IPv6
+        } else {
+            AddressFamily::IPv4
+        });
+        nh.next_hop_iface = nl_nh
+            .oif
+            .and_then(|i| iface_names.get(&i))
+            .map(|n| n.to_string());
+        nh.next_hop_addr = nl_nh.gateway.as_ref().map(|g| g.to_string());
+    } else {
+        let mut members = Vec::new();
+        for (id, weight) in nl_nh.group.iter() {
+            members.push(NexthopGroupMember {
+                id: *id,
+                weight: Some(*weight),
+            });
+        }
+        nh.group = Some(members);
+    }
+    nh
+}
+
+pub(crate) fn apply_nexthops(
+    merged_nhs: &MergedNexthops,
+) -> Result<(), NmstateError> {
+    // Kernel removes nexthop from group when deleting it, hence we remove
+    // group first.
+    let mut absent_nhs: Vec<&NexthopEntry> = merged_nhs
+        .changed
+        .iter()
+        .filter(|nh| nh.is_absent())
+        .collect();
+    absent_nhs.sort_unstable_by_key(|nh| !nh.is_group());
+    for nh in absent_nhs {
+        if let Some(id) = nh.id {
+            nexthop_del(id).map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!("Failed to remove nexthop {nh}: {e}"),
+                )
+            })?;
+        }
+    }
+
+    // Create single nexthops before groups referring them
+    let mut nhs: Vec<&NexthopEntry> = merged_nhs
+        .changed
+        .iter()
+        .filter(|nh| !nh.is_absent())
+        .collect();
+    nhs.sort_unstable_by_key(|nh| nh.is_group());
+    for nh in nhs {
+        nexthop_replace(&nms_nexthop_to_nl(nh)?).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!("Kernel refused to add nexthop {nh}: {e}"),
+            )
+        })?;
+    }
+    Ok(())
+}
+
+fn nms_nexthop_to_nl(nh: &NexthopEntry) -> Result<NlNexthop, NmstateError> {
+    let mut nl_nh = NlNexthop {
+        id: nh.id.unwrap_or_default(),
+        is_ipv6: nh.family == Some(AddressFamily::IPv6),
+        protocol: RTPROT_STATIC,
+        ..Default::default()
+    };
+    if let Some(members) = nh.group.as_ref() {
+        for member in members {
+            nl_nh.group.push((member.id, member.weight.unwrap_or(1)));
+        }
+    } else {
+        if let Some(iface) = nh.next_hop_iface.as_deref() {
+            nl_nh.oif = Some(get_iface_index(iface)?);
+        }
+        nl_nh.gateway = nh
+            .next_hop_addr
+            .as_deref()
+            .map(|a| {
+                a.parse::<IpAddr>().map_err(|e| {
+                    NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!("Invalid nexthop address {a}: {e}"),
+                    )
+                })
+            })
+            .transpose()?;
+    }
+    Ok(nl_nh)
+}
@@ -1,13 +1,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use log::warn;
+use netlink_packet_route::route::RouteMetric;
 
 use crate::{
-    ErrorKind, MergedRoutes, NmstateError, RouteEntry, RouteType, Routes,
+    nispor::{
+        ip::parse_ip_net,
+        netlink::{
+            route_del, route_get_nexthop_ids, route_replace, NlRoute,
+            RTAX_CWND, RTAX_MTU, RTN_UNICAST, RTPROT_STATIC,
+        },
+    },
+    ErrorKind, MergedRoutes, NmstateError, RouteEntry, RouteProtocol,
+    RouteScope, RouteType, Routes,
 };
 
-const SUPPORTED_ROUTE_SCOPE: [nispor::RouteScope; 2] =
-    [nispor::RouteScope::Universe, nispor::RouteScope::Link];
+// The `local` routes are using host scope
+const SUPPORTED_ROUTE_SCOPE: [nispor::RouteScope; 3] = [
+    nispor::RouteScope::Universe,
+    nispor::RouteScope::Link,
+    nispor::RouteScope::Host,
+];
 
 const SUPPORTED_ROUTE_PROTOCOL: [nispor::RouteProtocol; 7] = [
     nispor::RouteProtocol::Boot,
@@ -22,21 +35,24 @@ const SUPPORTED_ROUTE_PROTOCOL: [nispor::RouteProtocol; 7] = [
 const SUPPORTED_STATIC_ROUTE_PROTOCOL: [nispor::RouteProtocol; 2] =
     [nispor::RouteProtocol::Boot, nispor::RouteProtocol::Static];
 
+const DEFAULT_ROUTE_TABLE_ID: u32 = 254;
 const IPV4_DEFAULT_GATEWAY: &str = "0.0.0.0/0";
 const IPV6_DEFAULT_GATEWAY: &str = "::/0";
 const IPV4_EMPTY_NEXT_HOP_ADDRESS: &str = "0.0.0.0";
 const IPV6_EMPTY_NEXT_HOP_ADDRESS: &str = "::";
 
 // kernel values
-const RTAX_CWND: u32 = 7;
+const RTPROT_UNSPEC: u8 = 0;
 
 pub(crate) async fn get_routes(running_config_only: bool) -> Routes {
     let mut ret = Routes::new();
     let mut np_routes: Vec<nispor::Route> = Vec::new();
+    // Routes without next hop
     let route_type = [
         nispor::RouteType::BlackHole,
         nispor::RouteType::Unreachable,
         nispor::RouteType::Prohibit,
+        nispor::RouteType::Throw,
     ];
     let protocols = if running_config_only {
         SUPPORTED_STATIC_ROUTE_PROTOCOL.as_slice()
@@ -44,6 +60,16 @@ pub(crate) async fn get_routes(running_config_only: bool) -> Routes {
         SUPPORTED_ROUTE_PROTOCOL.as_slice()
     };
 
+    // nispor does not support RTA_NH_ID yet, the routes referring nexthop
+    // objects are shown as normal or multipath routes by nispor.
+    let nh_routes = match route_get_nexthop_ids() {
+        Ok(r) => r,
+        Err(e) => {
+            log::debug!("Failed to retrieve nexthop ID of routes: {e}");
+            Vec::new()
+        }
+    };
+
     for protocol in protocols {
         let mut rt_filter = nispor::NetStateRouteFilter::default();
         rt_filter.protocol = Some(*protocol);
@@ -71,7 +97,10 @@ pub(crate) async fn get_routes(running_config_only: bool) -> Routes {
             .iter()
             .filter(|np_route| SUPPORTED_ROUTE_SCOPE.contains(&np_route.scope))
         {
-            if is_multipath(np_route) {
+            if let Some(nh_id) = get_nexthop_id(np_route, &nh_routes) {
+                running_routes
+                    .push(np_nexthop_route_to_nmstate(np_route, nh_id));
+            } else if is_multipath(np_route) {
                 for route in flat_multipath_route(np_route) {
                     running_routes.push(route);
                 }
@@ -89,7 +118,9 @@ pub(crate) async fn get_routes(running_config_only: bool) -> Routes {
         SUPPORTED_ROUTE_SCOPE.contains(&np_route.scope)
             && SUPPORTED_STATIC_ROUTE_PROTOCOL.contains(&np_route.protocol)
     }) {
-        if is_multipath(np_route) {
+        if let Some(nh_id) = get_nexthop_id(np_route, &nh_routes) {
+            config_routes.push(np_nexthop_route_to_nmstate(np_route, nh_id));
+        } else if is_multipath(np_route) {
             for route in flat_multipath_route(np_route) {
                 config_routes.push(route);
             }
@@ -130,28 +161,49 @@ fn np_routetype_to_nmstate(np_route: &nispor::Route) -> RouteEntry {
     }
     route_entry.metric = np_route.metric.map(i64::from);
     route_entry.table_id = Some(np_route.table);
-    match np_route.route_type {
-        nispor::RouteType::BlackHole => {
-            route_entry.route_type = Some(RouteType::Blackhole)
-        }
-        nispor::RouteType::Unreachable => {
-            route_entry.route_type = Some(RouteType::Unreachable)
-        }
-        nispor::RouteType::Prohibit => {
-            route_entry.route_type = Some(RouteType::Prohibit)
-        }
-        _ => {
-            log::debug!("Got unsupported route {:?}", np_route);
-        }
-    }
-    // according to `man ip-route`, cwnd is useless without the lock flag, so
-    // we require both cwnd and its lock flag to consider cwnd as set.
-    let cwnd_lock = np_route.lock.unwrap_or(0) & (1 << RTAX_CWND) != 0;
-    route_entry.cwnd = if cwnd_lock { np_route.cwnd } else { None };
+    route_entry.route_type = np_route_type_to_nmstate(np_route);
+    np_route_attrs_to_nmstate(np_route, &mut route_entry);
 
     route_entry
 }
 
+fn get_nexthop_id(
+    np_route: &nispor::Route,
+    nh_routes: &[NlRoute],
+) -> Option<u32> {
+    nh_routes
+        .iter()
+        .find(|nl_rt| {
+            let dst = if nl_rt.dst_len == 0 {
+                None
+            } else {
+                Some(format!("{}/{}", nl_rt.dst, nl_rt.dst_len))
+            };
+            nl_rt.dst.is_ipv6()
+                == (np_route.address_family == nispor::AddressFamily::IPv6)
+                && dst == np_route.dst
+                && nl_rt.table == np_route.table
+                && nl_rt.metric == np_route.metric
+        })
+        .and_then(|nl_rt| nl_rt.nexthop_id)
+}
+
+// Route referring nexthop object is reported as single route instead of
+// flatting its next hops.
+fn np_nexthop_route_to_nmstate(
+    np_route: &nispor::Route,
+    nexthop_id: u32,
+) -> RouteEntry {
+    let mut route_entry = np_routetype_to_nmstate(np_route);
+    route_entry.next_hop_iface = None;
+    route_entry.nexthop_id = Some(nexthop_id);
+    // The onlink flag belongs to nexthop object
+    route_entry.onlink = None;
+    // Route referring nexthop object has different default scope
+    route_entry.scope = np_route_scope_to_nmstate(np_route, &route_entry);
+    route_entry
+}
+
 fn np_route_to_nmstate(np_route: &nispor::Route) -> RouteEntry {
     let destination = match &np_route.dst {
         Some(dst) => Some(dst.to_string()),
@@ -200,12 +252,94 @@ fn np_route_to_nmstate(np_route: &nispor::Route) -> RouteEntry {
     route_entry.next_hop_addr = next_hop_addr;
     route_entry.metric = np_route.metric.map(i64::from);
     route_entry.table_id = Some(np_route.table);
+    route_entry.route_type = np_route_type_to_nmstate(np_route);
+    np_route_attrs_to_nmstate(np_route, &mut route_entry);
+
+    route_entry
+}
+
+fn np_route_type_to_nmstate(np_route: &nispor::Route) -> Option<RouteType> {
+    match np_route.route_type {
+        nispor::RouteType::Unicast => None,
+        nispor::RouteType::BlackHole => Some(RouteType::Blackhole),
+        nispor::RouteType::Unreachable => Some(RouteType::Unreachable),
+        nispor::RouteType::Prohibit => Some(RouteType::Prohibit),
+        nispor::RouteType::Throw => Some(RouteType::Throw),
+        nispor::RouteType::Local => Some(RouteType::Local),
+        nispor::RouteType::Broadcast => Some(RouteType::Broadcast),
+        nispor::RouteType::Anycast => Some(RouteType::Anycast),
+        nispor::RouteType::Multicast => Some(RouteType::Multicast),
+        _ => {
+            log::debug!("Got unsupported route {:?}", np_route);
+            None
+        }
+    }
+}
+
+fn np_route_attrs_to_nmstate(
+    np_route: &nispor::Route,
+    route_entry: &mut RouteEntry,
+) {
+    let lock = np_route.lock.unwrap_or(0);
     // according to `man ip-route`, cwnd is useless without the lock flag, so
     // we require both cwnd and its lock flag to consider cwnd as set.
-    let cwnd_lock = np_route.lock.unwrap_or(0) & (1 << RTAX_CWND) != 0;
+    let cwnd_lock = lock & (1 << u32::from(RTAX_CWND)) != 0;
     route_entry.cwnd = if cwnd_lock { np_route.cwnd } else { None };
+    route_entry.source.clone_from(&np_route.prefered_src);
+    route_entry.mtu = np_route.mtu;
+    route_entry.lock_mtu = (np_route.mtu.is_some()
+        && lock & (1 << u32::from(RTAX_MTU)) != 0)
+        .then_some(true);
+    route_entry.advmss = np_route.advmss;
+    route_entry.initcwnd = np_route.initcwnd;
+    route_entry.initrwnd = np_route.initrwnd;
+    route_entry.quickack = np_route.quickack.map(|v| v > 0);
+    // nispor does not export its `RouteFlag`, check its serialized form.
+    route_entry.onlink = np_route
+        .flags
+        .iter()
+        .any(|f| {
+            serde_json::to_value(f)
+                .ok()
+                .as_ref()
+                .and_then(|v| v.as_str())
+                == Some("onlink")
+        })
+        .then_some(true);
+    route_entry.scope = np_route_scope_to_nmstate(np_route, route_entry);
+    route_entry.protocol = match np_route.protocol {
+        nispor::RouteProtocol::Boot => Some(RouteProtocol::Boot),
+        nispor::RouteProtocol::Static => Some(RouteProtocol::Static),
+        nispor::RouteProtocol::Ra => Some(RouteProtocol::Ra),
+        nispor::RouteProtocol::Dhcp => Some(RouteProtocol::Dhcp),
+        nispor::RouteProtocol::Mrouted => Some(RouteProtocol::Mrouted),
+        nispor::RouteProtocol::KeepAlived => Some(RouteProtocol::Keepalived),
+        nispor::RouteProtocol::Babel => Some(RouteProtocol::Babel),
+        _ => None,
+    };
+}
 
-    route_entry
+// Only show IPv4 route scope when it is not the kernel default, IPv6 routes
+// are always in universe scope.
+fn np_route_scope_to_nmstate(
+    np_route: &nispor::Route,
+    route_entry: &RouteEntry,
+) -> Option<RouteScope> {
+    if np_route.address_family != nispor::AddressFamily::IPv4 {
+        return None;
+    }
+    let scope = match np_route.scope {
+        nispor::RouteScope::Universe => RouteScope::Universe,
+        nispor::RouteScope::Site => RouteScope::Site,
+        nispor::RouteScope::Link => RouteScope::Link,
+        nispor::RouteScope::Host => RouteScope::Host,
+        _ => return None,
+    };
+    if scope == route_entry.default_scope() {
+        None
+    } else {
+        Some(scope)
+    }
 }
 
 fn is_multipath(np_route: &nispor::Route) -> bool {
@@ -227,6 +361,12 @@ fn flat_multipath_route(np_route: &nispor::Route) -> Vec<RouteEntry> {
             if np_route.address_family == nispor::AddressFamily::IPv4 {
                 route.weight = Some(mp_route.weight);
             }
+            if mp_route
+                .flags
+                .contains(&nispor::MultipathRouteFlags::OnLink)
+            {
+                route.onlink = Some(true);
+            }
             ret.push(route);
         }
     }
@@ -271,28 +411,177 @@ fn nmstate_to_nispor_route_conf(
         ));
     }
 
-    if nmstate_rt.route_type.is_some() {
-        return Err(NmstateError::new(
-            ErrorKind::NotImplementedError,
-            "nispor apply does not support route type yet".into(),
-        ));
-    }
-
-    if nmstate_rt.cwnd.is_some() {
-        return Err(NmstateError::new(
-            ErrorKind::NotImplementedError,
-            "nispor apply does not support route congestion window yet".into(),
-        ));
-    }
+    ret.protocol = nmstate_rt.protocol.map(|p| match p {
+        RouteProtocol::Boot => nispor::RouteProtocol::Boot,
+        RouteProtocol::Static => nispor::RouteProtocol::Static,
+        RouteProtocol::Ra => nispor::RouteProtocol::Ra,
+        RouteProtocol::Dhcp => nispor::RouteProtocol::Dhcp,
+        RouteProtocol::Mrouted => nispor::RouteProtocol::Mrouted,
+        RouteProtocol::Keepalived => nispor::RouteProtocol::KeepAlived,
+        RouteProtocol::Babel => nispor::RouteProtocol::Babel,
+    });
     Ok(ret)
 }
 
+// nispor does not support RTA_NH_ID, route type, route scope, preferred
+// source, metrics or onlink flag yet, these routes are applied via netlink.
+fn is_nl_route(nmstate_rt: &RouteEntry) -> bool {
+    nmstate_rt.nexthop_id.is_some()
+        || nmstate_rt.route_type.is_some()
+        || nmstate_rt.cwnd.is_some()
+        || nmstate_rt.source.is_some()
+        || nmstate_rt.mtu.is_some()
+        || nmstate_rt.lock_mtu.is_some()
+        || nmstate_rt.advmss.is_some()
+        || nmstate_rt.initcwnd.is_some()
+        || nmstate_rt.initrwnd.is_some()
+        || nmstate_rt.quickack.is_some()
+        || nmstate_rt.onlink.is_some()
+        || nmstate_rt.scope.is_some()
+}
+
 pub(crate) fn gen_nispor_route_confs(
     merged_routes: &MergedRoutes,
 ) -> Result<Vec<nispor::RouteConf>, NmstateError> {
     let mut ret = Vec::new();
-    for nmstate_rt in merged_routes.changed_routes.as_slice() {
+    for nmstate_rt in merged_routes
+        .changed_routes
+        .iter()
+        .filter(|r| !is_nl_route(r))
+    {
         ret.push(nmstate_to_nispor_route_conf(nmstate_rt)?)
     }
     Ok(ret)
 }
+
+pub(crate) fn apply_nl_routes(
+    merged_routes: &MergedRoutes,
+) -> Result<(), NmstateError> {
+    let mut nl_routes: Vec<&RouteEntry> = merged_routes
+        .changed_routes
+        .iter()
+        .filter(|r| is_nl_route(r))
+        .collect();
+    // Remove routes first
+    nl_routes.sort_unstable_by_key(|r| !r.is_absent());
+    for nmstate_rt in nl_routes {
+        let nl_rt = nmstate_route_to_nl(nmstate_rt)?;
+        if nmstate_rt.is_absent() {
+            route_del(&nl_rt)?;
+        } else {
+            route_replace(&nl_rt)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn nmstate_route_to_nl(
+    nmstate_rt: &RouteEntry,
+) -> Result<NlRoute, NmstateError> {
+    let (dst, dst_len) =
+        parse_ip_net(nmstate_rt.destination.as_deref().unwrap_or_default())?;
+    if nmstate_rt.weight.is_some() {
+        return Err(NmstateError::new(
+            ErrorKind::NotImplementedError,
+            format!(
+                "Route weight is not supported along with other route \
+                attributes in kernel mode yet: {nmstate_rt}"
+            ),
+        ));
+    }
+    if nmstate_rt.nexthop_id.is_some() && nmstate_rt.onlink == Some(true) {
+        return Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Route referring nexthop object cannot have onlink flag, \
+                please set it in nexthop object instead: {nmstate_rt}"
+            ),
+        ));
+    }
+    let gateway = match nmstate_rt.next_hop_addr.as_deref() {
+        Some(addr)
+            if nmstate_rt.nexthop_id.is_none() && nmstate_rt.has_next_hop() =>
+        {
+            Some(addr.parse::<std::net::IpAddr>().map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid route next-hop-address {addr}: {e}"),
+                )
+            })?)
+            .filter(|a| !a.is_unspecified())
+        }
+        _ => None,
+    };
+    let prefsrc = match nmstate_rt.source.as_deref() {
+        Some(src) => Some(src.parse::<std::net::IpAddr>().map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid route source address {src}: {e}"),
+            )
+        })?),
+        None => None,
+    };
+
+    let mut metrics = Vec::new();
+    let mut lock = 0u32;
+    if let Some(mtu) = nmstate_rt.mtu {
+        metrics.push(RouteMetric::Mtu(mtu));
+        if nmstate_rt.lock_mtu == Some(true) {
+            lock |= 1 << u32::from(RTAX_MTU);
+        }
+    }
+    // Congestion window is only used by kernel with the lock flag
+    if let Some(cwnd) = nmstate_rt.cwnd {
+        metrics.push(RouteMetric::Cwnd(cwnd));
+        lock |= 1 << u32::from(RTAX_CWND);
+    }
+    if let Some(advmss) = nmstate_rt.advmss {
+        metrics.push(RouteMetric::Advmss(advmss));
+    }
+    if let Some(initcwnd) = nmstate_rt.initcwnd {
+        metrics.push(RouteMetric::InitCwnd(initcwnd));
+    }
+    if let Some(initrwnd) = nmstate_rt.initrwnd {
+        metrics.push(RouteMetric::InitRwnd(initrwnd));
+    }
+    if nmstate_rt.quickack == Some(true) {
+        metrics.push(RouteMetric::QuickAck(1));
+    }
+    if lock != 0 {
+        metrics.push(RouteMetric::Lock(lock));
+    }
+
+    Ok(NlRoute {
+        // Kernel does not allow next hop for blackhole, unreachable, prohibit
+        // and throw routes.
+        oif: if nmstate_rt.nexthop_id.is_some() || !nmstate_rt.has_next_hop() {
+            None
+        } else {
+            nmstate_rt.next_hop_iface.clone()
+        },
+        dst,
+        dst_len,
+        gateway,
+        table: match nmstate_rt.table_id {
+            Some(RouteEntry::USE_DEFAULT_ROUTE_TABLE) | None => {
+                DEFAULT_ROUTE_TABLE_ID
+            }
+            Some(t) => t,
+        },
+        metric: nmstate_rt.metric.and_then(|m| u32::try_from(m).ok()),
+        // Like `ip route del`, match route of any protocol when removing
+        protocol: nmstate_rt.protocol.map(u8::from).unwrap_or(
+            if nmstate_rt.is_absent() {
+                RTPROT_UNSPEC
+            } else {
+                RTPROT_STATIC
+            },
+        ),
+        nexthop_id: nmstate_rt.nexthop_id,
+        route_type: nmstate_rt.route_type.map(u8::from).unwrap_or(RTN_UNICAST),
+        scope: nmstate_rt.scope.map(u8::from),
+        prefsrc,
+        metrics,
+        onlink: nmstate_rt.onlink == Some(true),
+    })
+}
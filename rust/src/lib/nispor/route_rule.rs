@@ -1,33 +1,48 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use log::warn;
+use netlink_packet_route::rule::RuleAction;
 
-use crate::{AddressFamily, RouteRuleAction, RouteRuleEntry, RouteRules};
+use crate::{
+    nispor::{
+        ip::parse_ip_net,
+        netlink::{
+            rule_add, rule_del, rule_dump, NlRouteRule, RTPROT_DHCP,
+            RTPROT_STATIC,
+        },
+    },
+    AddressFamily, ErrorKind, MergedRouteRules, NmstateError, RouteRuleAction,
+    RouteRuleEntry, RouteRuleRange, RouteRules,
+};
 
 // Due to a bug in NetworkManager all route rules added using NetworkManager are
 // using RTM_PROTOCOL Unspec. Therefore, we need to support it until it is
 // fixed.
-const SUPPORTED_STATIC_ROUTE_PROTOCOL: [nispor::RouteProtocol; 3] = [
-    nispor::RouteProtocol::Boot,
-    nispor::RouteProtocol::Static,
-    nispor::RouteProtocol::Unspec,
-];
+const SUPPORTED_STATIC_ROUTE_PROTOCOL: [u8; 3] =
+    [RTPROT_BOOT, RTPROT_STATIC, RTPROT_UNSPEC];
 
-const SUPPORTED_ROUTE_PROTOCOL: [nispor::RouteProtocol; 8] = [
-    nispor::RouteProtocol::Boot,
-    nispor::RouteProtocol::Static,
-    nispor::RouteProtocol::Ra,
-    nispor::RouteProtocol::Dhcp,
-    nispor::RouteProtocol::Mrouted,
-    nispor::RouteProtocol::KeepAlived,
-    nispor::RouteProtocol::Babel,
-    nispor::RouteProtocol::Unspec,
+const SUPPORTED_ROUTE_PROTOCOL: [u8; 8] = [
+    RTPROT_BOOT,
+    RTPROT_STATIC,
+    RTPROT_RA,
+    RTPROT_DHCP,
+    RTPROT_MROUTED,
+    RTPROT_KEEPALIVED,
+    RTPROT_BABEL,
+    RTPROT_UNSPEC,
 ];
 
+const RTPROT_UNSPEC: u8 = 0;
+const RTPROT_BOOT: u8 = 3;
+const RTPROT_RA: u8 = 9;
+const RTPROT_MROUTED: u8 = 17;
+const RTPROT_KEEPALIVED: u8 = 18;
+const RTPROT_BABEL: u8 = 42;
+
+// Nispor does not support port range and UID range of route rule yet, hence
+// query route rules via netlink directly.
 pub(crate) fn get_route_rules(
-    np_rules: &[nispor::RouteRule],
     running_config_only: bool,
-) -> RouteRules {
+) -> Result<RouteRules, NmstateError> {
     let mut ret = RouteRules::new();
 
     let mut rules = Vec::new();
@@ -37,53 +52,134 @@ pub(crate) fn get_route_rules(
         SUPPORTED_ROUTE_PROTOCOL.as_slice()
     };
 
-    for np_rule in np_rules {
-        let mut rule = RouteRuleEntry::new();
-        // We only support route rules with 'table' action
-        match np_rule.action {
-            nispor::RuleAction::Table => (),
-            nispor::RuleAction::Blackhole => {
-                rule.action = Some(RouteRuleAction::Blackhole)
-            }
-            nispor::RuleAction::Unreachable => {
-                rule.action = Some(RouteRuleAction::Unreachable)
-            }
-            nispor::RuleAction::Prohibit => {
-                rule.action = Some(RouteRuleAction::Prohibit)
-            }
-            _ => {
-                log::debug!("Got unsupported route rule {:?}", np_rule);
-                continue;
-            }
-        }
+    let nl_rules = rule_dump().map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to query route rules: {e}"),
+        )
+    })?;
+
+    for nl_rule in nl_rules {
         // Filter out the routes with protocols that we do not support
-        if let Some(rule_protocol) = np_rule.protocol.as_ref() {
+        if let Some(rule_protocol) = nl_rule.protocol.as_ref() {
             if !protocols.contains(rule_protocol) {
                 continue;
             }
         }
-        rule.iif.clone_from(&np_rule.iif);
-        rule.ip_to.clone_from(&np_rule.dst);
-        rule.ip_from.clone_from(&np_rule.src);
-        rule.table_id = np_rule.table;
-        rule.priority = np_rule.priority.map(i64::from);
-        rule.fwmark = np_rule.fw_mark;
-        rule.fwmask = np_rule.fw_mask;
-        rule.suppress_prefix_length = np_rule.suppress_prefix_len;
-        rule.family = match np_rule.address_family {
-            nispor::AddressFamily::IPv4 => Some(AddressFamily::IPv4),
-            nispor::AddressFamily::IPv6 => Some(AddressFamily::IPv6),
-            _ => {
-                warn!(
-                    "Unsupported route rule family {:?}",
-                    np_rule.address_family
-                );
-                None
-            }
-        };
-        rules.push(rule);
+        rules.push(nl_rule_to_nms(nl_rule));
     }
     ret.config = Some(rules);
 
-    ret
+    Ok(ret)
+}
+
+fn nl_rule_to_nms(nl_rule: NlRouteRule) -> RouteRuleEntry {
+    let mut rule = RouteRuleEntry::new();
+    rule.action = match nl_rule.action {
+        RuleAction::Goto => Some(RouteRuleAction::Goto),
+        RuleAction::Nop => Some(RouteRuleAction::Nop),
+        RuleAction::Blackhole => Some(RouteRuleAction::Blackhole),
+        RuleAction::Unreachable => Some(RouteRuleAction::Unreachable),
+        RuleAction::Prohibit => Some(RouteRuleAction::Prohibit),
+        // `NlRouteRule::from_message()` only keeps supported actions
+        _ => None,
+    };
+    rule.family = Some(if nl_rule.is_ipv6 {
+        AddressFamily::IPv6
+    } else {
+        AddressFamily::IPv4
+    });
+    rule.iif = nl_rule.iif;
+    rule.oif = nl_rule.oif;
+    rule.ip_to = nl_rule.dst.map(|(ip, len)| format!("{ip}/{len}"));
+    rule.ip_from = nl_rule.src.map(|(ip, len)| format!("{ip}/{len}"));
+    rule.table_id = nl_rule.table;
+    rule.priority = nl_rule.priority.map(i64::from);
+    rule.fwmark = nl_rule.fwmark;
+    rule.fwmask = nl_rule.fwmask;
+    rule.suppress_prefix_length = nl_rule.suppress_prefix_length;
+    rule.sport = nl_rule
+        .sport_range
+        .map(|(s, e)| RouteRuleRange::new(s.into(), e.into()));
+    rule.dport = nl_rule
+        .dport_range
+        .map(|(s, e)| RouteRuleRange::new(s.into(), e.into()));
+    rule.uidrange = nl_rule.uid_range.map(|(s, e)| RouteRuleRange::new(s, e));
+    rule.ipproto = nl_rule.ip_proto;
+    if nl_rule.tos != 0 {
+        rule.tos = Some(nl_rule.tos);
+    }
+    if nl_rule.l3mdev {
+        rule.l3mdev = Some(true);
+    }
+    rule.goto_priority = nl_rule.goto;
+    rule
+}
+
+pub(crate) fn apply_route_rules(
+    merged_rules: &MergedRouteRules,
+) -> Result<(), NmstateError> {
+    for rule in merged_rules.for_apply.iter().filter(|r| r.is_absent()) {
+        rule_del(&nms_rule_to_nl(rule)?).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::PluginFailure,
+                format!("Failed to remove route rule {rule}: {e}"),
+            )
+        })?;
+    }
+    for rule in merged_rules.for_apply.iter().filter(|r| !r.is_absent()) {
+        rule_add(&nms_rule_to_nl(rule)?).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!("Kernel refused to add route rule {rule}: {e}"),
+            )
+        })?;
+    }
+    Ok(())
+}
+
+pub(crate) fn nms_rule_to_nl(
+    rule: &RouteRuleEntry,
+) -> Result<NlRouteRule, NmstateError> {
+    let action = match rule.action {
+        None => RuleAction::ToTable,
+        Some(RouteRuleAction::Blackhole) => RuleAction::Blackhole,
+        Some(RouteRuleAction::Unreachable) => RuleAction::Unreachable,
+        Some(RouteRuleAction::Prohibit) => RuleAction::Prohibit,
+        Some(RouteRuleAction::Goto) => RuleAction::Goto,
+        Some(RouteRuleAction::Nop) => RuleAction::Nop,
+    };
+    let mut nl_rule =
+        NlRouteRule::new(rule.family == Some(AddressFamily::IPv6), action);
+    nl_rule.src = rule.ip_from.as_deref().map(parse_ip_net).transpose()?;
+    nl_rule.dst = rule.ip_to.as_deref().map(parse_ip_net).transpose()?;
+    nl_rule.table = match rule.table_id {
+        Some(RouteRuleEntry::USE_DEFAULT_ROUTE_TABLE) => {
+            Some(RouteRuleEntry::DEFAULR_ROUTE_TABLE_ID)
+        }
+        t => t,
+    };
+    nl_rule.priority = match rule.priority {
+        Some(RouteRuleEntry::USE_DEFAULT_PRIORITY) | None => None,
+        Some(p) => Some(u32::try_from(p).map_err(|_| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid route rule priority {p}"),
+            )
+        })?),
+    };
+    nl_rule.iif.clone_from(&rule.iif);
+    nl_rule.fwmark = rule.fwmark;
+    nl_rule.fwmask = rule.fwmask;
+    nl_rule.suppress_prefix_length = rule.suppress_prefix_length;
+    nl_rule.oif.clone_from(&rule.oif);
+    nl_rule.goto = rule.goto_priority;
+    nl_rule.l3mdev = rule.l3mdev == Some(true);
+    nl_rule.ip_proto = rule.ipproto;
+    nl_rule.tos = rule.tos.unwrap_or_default();
+    nl_rule.uid_range = rule.uidrange.map(|r| (r.start, r.end));
+    // The port range has been validated by `RouteRuleEntry::sanitize()`
+    nl_rule.sport_range = rule.sport.map(|r| (r.start as u16, r.end as u16));
+    nl_rule.dport_range = rule.dport.map(|r| (r.start as u16, r.end as u16));
+    Ok(nl_rule)
 }
@@ -4,26 +4,34 @@ use std::collections::HashMap;
 
 use crate::{
     nispor::{
+        bareudp::np_bareudp_to_nmstate,
         base_iface::np_iface_to_base_iface,
         bond::{append_bond_port_config, np_bond_to_nmstate},
+        dhcp::append_dhcp_lease,
         dns::get_dns,
         error::np_error_to_nmstate,
         ethernet::np_ethernet_to_nmstate,
+        geneve::np_geneve_to_nmstate,
         hostname::get_hostname_state,
         hsr::np_hsr_to_nmstate,
         infiniband::np_ib_to_nmstate,
+        ip_tunnel::np_ip_tunnel_to_nmstate,
+        ip_vlan::np_ip_vlan_to_nmstate,
         linux_bridge::{append_bridge_port_config, np_bridge_to_nmstate},
         mac_vlan::{np_mac_vlan_to_nmstate, np_mac_vtap_to_nmstate},
         macsec::np_macsec_to_nmstate,
+        nexthop::get_nexthops,
         route::get_routes,
         route_rule::get_route_rules,
         veth::np_veth_to_nmstate,
         vlan::np_vlan_to_nmstate,
         vrf::np_vrf_to_nmstate,
         vxlan::np_vxlan_to_nmstate,
+        wireguard::np_wireguard_to_nmstate,
     },
     DummyInterface, Interface, InterfaceType, Interfaces, LoopbackInterface,
-    NetworkState, NmstateError, OvsInterface, UnknownInterface, XfrmInterface,
+    NetworkState, Nexthops, NmstateError, OvsInterface, UnknownInterface,
+    XfrmInterface,
 };
 
 // Only report DNS config when `kernel_only: true`
@@ -43,7 +51,53 @@ pub(crate) async fn nispor_retrieve(
         .await
         .map_err(np_error_to_nmstate)?;
 
-    for (_, np_iface) in np_state.ifaces.iter() {
+    net_state.interfaces = np_ifaces_to_nmstate(
+        &np_state.ifaces,
+        running_config_only,
+        kernel_only,
+    )?;
+    net_state.nexthops = get_nexthops(&np_state.ifaces, running_config_only);
+    net_state.routes = get_routes(running_config_only).await;
+    net_state.rules = get_route_rules(running_config_only)?;
+    if kernel_only {
+        net_state.dns = get_dns();
+    }
+    Ok(net_state)
+}
+
+// Query interfaces only, used by watcher for interface changes
+pub(crate) async fn nispor_retrieve_ifaces(
+    running_config_only: bool,
+    kernel_only: bool,
+) -> Result<Interfaces, NmstateError> {
+    let mut filter = nispor::NetStateFilter::minimum();
+    filter.iface = Some(nispor::NetStateIfaceFilter::default());
+    let np_state = nispor::NetState::retrieve_with_filter_async(&filter)
+        .await
+        .map_err(np_error_to_nmstate)?;
+    np_ifaces_to_nmstate(&np_state.ifaces, running_config_only, kernel_only)
+}
+
+// Query nexthops only, used by watcher for nexthop changes
+pub(crate) async fn nispor_retrieve_nexthops(
+    running_config_only: bool,
+) -> Result<Nexthops, NmstateError> {
+    // The interface index to name mapping is all we need from interfaces
+    let mut filter = nispor::NetStateFilter::minimum();
+    filter.iface = Some(nispor::NetStateIfaceFilter::minimum());
+    let np_state = nispor::NetState::retrieve_with_filter_async(&filter)
+        .await
+        .map_err(np_error_to_nmstate)?;
+    Ok(get_nexthops(&np_state.ifaces, running_config_only))
+}
+
+fn np_ifaces_to_nmstate(
+    np_ifaces: &HashMap<String, nispor::Iface>,
+    running_config_only: bool,
+    kernel_only: bool,
+) -> Result<Interfaces, NmstateError> {
+    let mut ifaces = Interfaces::new();
+    for (_, np_iface) in np_ifaces.iter() {
         // The `ovs-system` is reserved for OVS kernel datapath
         if np_iface.name == "ovs-system" {
             continue;
@@ -58,12 +112,12 @@ pub(crate) async fn nispor_retrieve(
         }
 
         let base_iface = np_iface_to_base_iface(np_iface, running_config_only);
-        let iface = match &base_iface.iface_type {
+        let mut iface = match &base_iface.iface_type {
             InterfaceType::LinuxBridge => {
                 let mut br_iface = np_bridge_to_nmstate(np_iface, base_iface)?;
                 let mut port_np_ifaces = Vec::new();
                 for port_name in br_iface.ports().unwrap_or_default() {
-                    if let Some(p) = np_state.ifaces.get(port_name) {
+                    if let Some(p) = np_ifaces.get(port_name) {
                         port_np_ifaces.push(p)
                     }
                 }
@@ -78,7 +132,7 @@ pub(crate) async fn nispor_retrieve(
                 let mut bond_iface = np_bond_to_nmstate(np_iface, base_iface);
                 let mut port_np_ifaces = Vec::new();
                 for port_name in bond_iface.ports().unwrap_or_default() {
-                    if let Some(p) = np_state.ifaces.get(port_name) {
+                    if let Some(p) = np_ifaces.get(port_name) {
                         port_np_ifaces.push(p)
                     }
                 }
@@ -116,6 +170,9 @@ pub(crate) async fn nispor_retrieve(
             InterfaceType::MacVtap => {
                 Interface::MacVtap(np_mac_vtap_to_nmstate(np_iface, base_iface))
             }
+            InterfaceType::IpVlan => {
+                Interface::IpVlan(np_ip_vlan_to_nmstate(np_iface, base_iface))
+            }
             InterfaceType::Vrf => {
                 Interface::Vrf(np_vrf_to_nmstate(np_iface, base_iface))
             }
@@ -142,6 +199,22 @@ pub(crate) async fn nispor_retrieve(
                 iface.base = base_iface;
                 Interface::Xfrm(iface)
             }
+            InterfaceType::WireGuard => Interface::WireGuard(
+                np_wireguard_to_nmstate(np_iface, base_iface),
+            ),
+            InterfaceType::Gre
+            | InterfaceType::GreTap
+            | InterfaceType::Ipip
+            | InterfaceType::Sit
+            | InterfaceType::Ip6Tnl => Interface::IpTunnel(
+                np_ip_tunnel_to_nmstate(np_iface, base_iface, np_ifaces),
+            ),
+            InterfaceType::Geneve => {
+                Interface::Geneve(np_geneve_to_nmstate(np_iface, base_iface))
+            }
+            InterfaceType::BareUdp => {
+                Interface::BareUdp(np_bareudp_to_nmstate(np_iface, base_iface))
+            }
             _ => {
                 log::info!(
                     "Got unsupported interface {} type {:?}",
@@ -155,15 +228,13 @@ pub(crate) async fn nispor_retrieve(
                 })
             }
         };
-        net_state.append_interface_data(iface);
-    }
-    set_controller_type(&mut net_state.interfaces);
-    net_state.routes = get_routes(running_config_only).await;
-    net_state.rules = get_route_rules(&np_state.rules, running_config_only);
-    if kernel_only {
-        net_state.dns = get_dns();
+        if kernel_only {
+            append_dhcp_lease(&mut iface, running_config_only);
+        }
+        ifaces.push(iface);
     }
-    Ok(net_state)
+    set_controller_type(&mut ifaces);
+    Ok(ifaces)
 }
 
 fn set_controller_type(ifaces: &mut Interfaces) {
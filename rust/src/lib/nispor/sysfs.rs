@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Nispor does not support setting all the kernel options yet, for those
+// exposed through sysfs, we write them directly.
+
+use std::io::Write;
+
+use crate::{ErrorKind, NmstateError};
+
+const SYSFS_NET_PATH: &str = "/sys/class/net";
+const IFF_UP: u32 = 0x1;
+
+pub(crate) fn sysfs_iface_path(iface_name: &str, name: &str) -> String {
+    format!("{SYSFS_NET_PATH}/{iface_name}/{name}")
+}
+
+pub(crate) fn read_sysfs(path: &str) -> Option<String> {
+    match std::fs::read_to_string(path) {
+        Ok(s) => Some(s.trim().to_string()),
+        Err(e) => {
+            log::debug!("Failed to read {path}: {e}");
+            None
+        }
+    }
+}
+
+pub(crate) fn write_sysfs(path: &str, value: &str) -> Result<(), NmstateError> {
+    log::debug!("Writing '{value}' to {path}");
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .and_then(|mut fd| fd.write_all(value.as_bytes()))
+        .map_err(|e| {
+            NmstateError::new(
+                if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    ErrorKind::PermissionError
+                } else {
+                    ErrorKind::NotSupportedError
+                },
+                format!("Kernel refused to set '{value}' to {path}: {e}"),
+            )
+        })
+}
+
+pub(crate) fn set_iface_admin_down(
+    iface_name: &str,
+) -> Result<(), NmstateError> {
+    let path = sysfs_iface_path(iface_name, "flags");
+    let flags = read_sysfs(&path)
+        .and_then(|s| u32::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+        .ok_or_else(|| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Failed to read interface flags from {path}"),
+            )
+        })?;
+    if flags & IFF_UP > 0 {
+        write_sysfs(&path, &format!("{:#x}", flags & !IFF_UP))
+    } else {
+        Ok(())
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{BaseInterface, VrfConfig, VrfInterface};
+use netlink_packet_route::link::{InfoData, InfoKind, InfoVrf};
+
+use crate::{
+    nispor::netlink::NlLinkConf, BaseInterface, VrfConfig, VrfInterface,
+};
 
 pub(crate) fn np_vrf_to_nmstate(
     np_iface: &nispor::Iface,
@@ -18,3 +22,10 @@ pub(crate) fn np_vrf_to_nmstate(
         vrf: vrf_conf,
     }
 }
+
+pub(crate) fn nms_vrf_conf_to_nl(nms_vrf_conf: &VrfConfig) -> NlLinkConf {
+    let mut conf = NlLinkConf::new(InfoKind::Vrf);
+    conf.info_data =
+        Some(InfoData::Vrf(vec![InfoVrf::TableId(nms_vrf_conf.table_id)]));
+    conf
+}
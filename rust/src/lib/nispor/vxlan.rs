@@ -2,7 +2,14 @@
 
 use std::str::FromStr;
 
-use crate::{BaseInterface, VxlanConfig, VxlanInterface};
+use std::net::IpAddr;
+
+use netlink_packet_route::link::{InfoData, InfoKind, InfoVxlan};
+
+use crate::{
+    nispor::netlink::{get_iface_index, NlLinkConf},
+    BaseInterface, NmstateError, VxlanConfig, VxlanInterface,
+};
 
 pub(crate) fn np_vxlan_to_nmstate(
     np_iface: &nispor::Iface,
@@ -22,3 +29,33 @@ pub(crate) fn np_vxlan_to_nmstate(
         vxlan: vxlan_conf,
     }
 }
+
+pub(crate) fn nms_vxlan_conf_to_nl(
+    nms_vxlan_conf: &VxlanConfig,
+) -> Result<NlLinkConf, NmstateError> {
+    let mut nlas = vec![InfoVxlan::Id(nms_vxlan_conf.id)];
+    if !nms_vxlan_conf.base_iface.is_empty() {
+        nlas.push(InfoVxlan::Link(get_iface_index(
+            nms_vxlan_conf.base_iface.as_str(),
+        )?));
+    }
+    match nms_vxlan_conf.remote {
+        Some(IpAddr::V4(remote)) => nlas.push(InfoVxlan::Group(remote)),
+        Some(IpAddr::V6(remote)) => nlas.push(InfoVxlan::Group6(remote)),
+        None => (),
+    }
+    match nms_vxlan_conf.local {
+        Some(IpAddr::V4(local)) => nlas.push(InfoVxlan::Local(local)),
+        Some(IpAddr::V6(local)) => nlas.push(InfoVxlan::Local6(local)),
+        None => (),
+    }
+    if let Some(learning) = nms_vxlan_conf.learning {
+        nlas.push(InfoVxlan::Learning(learning));
+    }
+    if let Some(dst_port) = nms_vxlan_conf.dst_port {
+        nlas.push(InfoVxlan::Port(dst_port));
+    }
+    let mut conf = NlLinkConf::new(InfoKind::Vxlan);
+    conf.info_data = Some(InfoData::Vxlan(nlas));
+    Ok(conf)
+}
@@ -0,0 +1,691 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Nispor does not support WireGuard yet, this is a minimal generic netlink
+// client of WireGuard, equal to `wg show|set <iface> ...`.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+
+use netlink_packet_core::{
+    emit_u16, emit_u32, parse_ip, parse_string, parse_u16, parse_u32, parse_u8,
+    DecodeError, DefaultNla, Emitable, ErrorContext, Nla, NlaBuffer,
+    NlasIterator, Parseable, ParseableParametrized, NLA_F_NESTED,
+};
+use netlink_packet_generic::{GenlFamily, GenlHeader};
+
+use crate::{
+    ifaces::{wireguard_key_from_bytes, wireguard_key_to_bytes},
+    nispor::netlink::{genl_request, NLM_F_DUMP},
+    BaseInterface, ErrorKind, Interface, MergedInterface, NetworkState,
+    NmstateError, WireGuardConfig, WireGuardInterface, WireGuardPeerConfig,
+};
+
+const WG_GENL_NAME: &str = "wireguard";
+const WG_GENL_VERSION: u8 = 1;
+const WG_CMD_GET_DEVICE: u8 = 0;
+const WG_CMD_SET_DEVICE: u8 = 1;
+
+const WGDEVICE_A_IFNAME: u16 = 2;
+const WGDEVICE_A_PRIVATE_KEY: u16 = 3;
+const WGDEVICE_A_FLAGS: u16 = 5;
+const WGDEVICE_A_LISTEN_PORT: u16 = 6;
+const WGDEVICE_A_FWMARK: u16 = 7;
+const WGDEVICE_A_PEERS: u16 = 8;
+const WGDEVICE_F_REPLACE_PEERS: u32 = 1 << 0;
+
+const WGPEER_A_PUBLIC_KEY: u16 = 1;
+const WGPEER_A_PRESHARED_KEY: u16 = 2;
+const WGPEER_A_FLAGS: u16 = 3;
+const WGPEER_A_ENDPOINT: u16 = 4;
+const WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL: u16 = 5;
+const WGPEER_A_ALLOWEDIPS: u16 = 9;
+const WGPEER_F_REPLACE_ALLOWEDIPS: u32 = 1 << 1;
+
+const WGALLOWEDIP_A_FAMILY: u16 = 1;
+const WGALLOWEDIP_A_IPADDR: u16 = 2;
+const WGALLOWEDIP_A_CIDR_MASK: u16 = 3;
+
+const AF_INET: u16 = 2;
+const AF_INET6: u16 = 10;
+
+pub(crate) fn np_wireguard_to_nmstate(
+    np_iface: &nispor::Iface,
+    base_iface: BaseInterface,
+) -> WireGuardInterface {
+    let wireguard = match get_wireguard_conf(np_iface.name.as_str()) {
+        Ok(c) => Some(c),
+        Err(e) => {
+            log::warn!(
+                "Failed to query WireGuard configuration of {}: {e}",
+                np_iface.name
+            );
+            None
+        }
+    };
+    WireGuardInterface {
+        base: base_iface,
+        wireguard,
+    }
+}
+
+fn get_wireguard_conf(iface_name: &str) -> Result<WireGuardConfig, nix::Error> {
+    let replies = genl_request(
+        WireguardMessage {
+            cmd: WG_CMD_GET_DEVICE,
+            attributes: vec![WgDeviceAttr::IfName(iface_name.to_string())],
+        },
+        NLM_F_DUMP,
+    )?;
+
+    let mut conf = WireGuardConfig::new();
+    let mut peers: Vec<WireGuardPeerConfig> = Vec::new();
+    // Peers might be split into multiple reply messages, the same peer is
+    // repeated at the beginning of next message if its allowed IPs does not
+    // fit into previous message.
+    for reply in replies {
+        for attr in reply.attributes {
+            match attr {
+                WgDeviceAttr::PrivateKey(key) if !is_all_zero(&key) => {
+                    conf.private_key = Some(wireguard_key_from_bytes(&key));
+                }
+                WgDeviceAttr::ListenPort(v) => conf.listen_port = Some(v),
+                WgDeviceAttr::Fwmark(v) => conf.fwmark = Some(v),
+                WgDeviceAttr::Peers(np_peers) => {
+                    for np_peer in np_peers {
+                        let peer = parse_peer(&np_peer.0);
+                        match peers.last_mut() {
+                            Some(last_peer)
+                                if last_peer.public_key == peer.public_key =>
+                            {
+                                last_peer
+                                    .allowed_ips
+                                    .get_or_insert(Vec::new())
+                                    .extend(
+                                        peer.allowed_ips.unwrap_or_default(),
+                                    );
+                            }
+                            _ => peers.push(peer),
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+    for peer in peers.iter_mut() {
+        if let Some(allowed_ips) = peer.allowed_ips.as_mut() {
+            allowed_ips.sort_unstable();
+        }
+    }
+    conf.peers = Some(peers);
+    Ok(conf)
+}
+
+// Kernel reply all-zero key when it is not set
+fn is_all_zero(data: &[u8]) -> bool {
+    data.iter().all(|b| *b == 0)
+}
+
+fn parse_peer(attrs: &[WgPeerAttr]) -> WireGuardPeerConfig {
+    let mut peer = WireGuardPeerConfig::new();
+    for attr in attrs {
+        match attr {
+            WgPeerAttr::PublicKey(key) => {
+                peer.public_key = wireguard_key_from_bytes(key);
+            }
+            WgPeerAttr::PresharedKey(key) if !is_all_zero(key) => {
+                peer.preshared_key = Some(wireguard_key_from_bytes(key));
+            }
+            WgPeerAttr::Endpoint(addr) => {
+                peer.endpoint = Some(addr.to_string());
+            }
+            WgPeerAttr::PersistentKeepalive(v) => {
+                peer.persistent_keepalive = Some(*v);
+            }
+            WgPeerAttr::AllowedIps(np_allowed_ips) => {
+                let allowed_ips = peer.allowed_ips.get_or_insert(Vec::new());
+                for np_allowed_ip in np_allowed_ips {
+                    if let Some(allowed_ip) = parse_allowed_ip(&np_allowed_ip.0)
+                    {
+                        allowed_ips.push(allowed_ip);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    peer
+}
+
+fn parse_allowed_ip(attrs: &[WgAllowedIpAttr]) -> Option<String> {
+    let mut ip: Option<IpAddr> = None;
+    let mut prefix_len: Option<u8> = None;
+    for attr in attrs {
+        match attr {
+            WgAllowedIpAttr::IpAddr(v) => ip = Some(*v),
+            WgAllowedIpAttr::CidrMask(v) => prefix_len = Some(*v),
+            _ => (),
+        }
+    }
+    match (ip, prefix_len) {
+        (Some(ip), Some(prefix_len)) => Some(format!("{ip}/{prefix_len}")),
+        _ => None,
+    }
+}
+
+// The `struct sockaddr_in` or `struct sockaddr_in6`
+fn parse_sockaddr(data: &[u8]) -> Option<SocketAddr> {
+    let family = parse_u16(data.get(..2)?).ok()?;
+    let port = u16::from_be_bytes([*data.get(2)?, *data.get(3)?]);
+    let ip: IpAddr = match family {
+        AF_INET => {
+            Ipv4Addr::from(<[u8; 4]>::try_from(data.get(4..8)?).ok()?).into()
+        }
+        AF_INET6 => {
+            Ipv6Addr::from(<[u8; 16]>::try_from(data.get(8..24)?).ok()?).into()
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+fn sockaddr_to_bytes(addr: &SocketAddr) -> Vec<u8> {
+    let mut ret = Vec::new();
+    match addr {
+        SocketAddr::V4(a) => {
+            ret.extend_from_slice(&AF_INET.to_ne_bytes());
+            ret.extend_from_slice(&a.port().to_be_bytes());
+            ret.extend_from_slice(&a.ip().octets());
+            ret.extend_from_slice(&[0u8; 8]);
+        }
+        SocketAddr::V6(a) => {
+            ret.extend_from_slice(&AF_INET6.to_ne_bytes());
+            ret.extend_from_slice(&a.port().to_be_bytes());
+            ret.extend_from_slice(&a.flowinfo().to_be_bytes());
+            ret.extend_from_slice(&a.ip().octets());
+            ret.extend_from_slice(&a.scope_id().to_ne_bytes());
+        }
+    }
+    ret
+}
+
+pub(crate) fn apply_wireguard_conf(
+    merged_iface: &MergedInterface,
+) -> Result<(), NmstateError> {
+    let (iface_name, conf) = if let Some(Interface::WireGuard(iface)) =
+        merged_iface.for_apply.as_ref()
+    {
+        if let Some(conf) = iface.wireguard.as_ref() {
+            (iface.base.name.as_str(), conf)
+        } else {
+            return Ok(());
+        }
+    } else {
+        return Ok(());
+    };
+    let cur_conf = if let Some(Interface::WireGuard(iface)) =
+        merged_iface.current.as_ref()
+    {
+        iface.wireguard.as_ref()
+    } else {
+        None
+    };
+
+    let mut attrs = vec![WgDeviceAttr::IfName(iface_name.to_string())];
+    // Hidden private key means no change
+    if let Some(key) = conf
+        .private_key
+        .as_deref()
+        .filter(|k| *k != NetworkState::PASSWORD_HID_BY_NMSTATE)
+    {
+        attrs.push(WgDeviceAttr::PrivateKey(decode_key(key)?.to_vec()));
+    }
+    if let Some(v) = conf.listen_port {
+        attrs.push(WgDeviceAttr::ListenPort(v));
+    }
+    if let Some(v) = conf.fwmark {
+        attrs.push(WgDeviceAttr::Fwmark(v));
+    }
+    if let Some(peers) = conf.peers.as_ref() {
+        attrs.push(WgDeviceAttr::Flags(WGDEVICE_F_REPLACE_PEERS));
+        let mut np_peers = Vec::new();
+        for peer in peers {
+            let cur_peer =
+                cur_conf.and_then(|c| c.peers.as_ref()).and_then(|p| {
+                    p.iter().find(|p| p.public_key == peer.public_key)
+                });
+            np_peers.push(WgPeer(gen_peer_attrs(peer, cur_peer)?));
+        }
+        attrs.push(WgDeviceAttr::Peers(np_peers));
+    }
+
+    log::debug!("Setting WireGuard interface {iface_name} with {conf:?}");
+    genl_request(
+        WireguardMessage {
+            cmd: WG_CMD_SET_DEVICE,
+            attributes: attrs,
+        },
+        0,
+    )
+    .map(|_| ())
+    .map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Failed to set WireGuard configuration of interface \
+                {iface_name}: {e}"
+            ),
+        )
+    })
+}
+
+fn gen_peer_attrs(
+    peer: &WireGuardPeerConfig,
+    cur_peer: Option<&WireGuardPeerConfig>,
+) -> Result<Vec<WgPeerAttr>, NmstateError> {
+    let mut ret = vec![WgPeerAttr::PublicKey(
+        decode_key(peer.public_key.as_str())?.to_vec(),
+    )];
+    // Peers are replaced, hence hidden pre-shared key should be copied from
+    // current
+    let preshared_key = match peer.preshared_key.as_deref() {
+        Some(NetworkState::PASSWORD_HID_BY_NMSTATE) => {
+            cur_peer.and_then(|p| p.preshared_key.as_deref())
+        }
+        k => k,
+    };
+    if let Some(key) = preshared_key {
+        ret.push(WgPeerAttr::PresharedKey(decode_key(key)?.to_vec()));
+    }
+    if let Some(endpoint) = peer.endpoint.as_deref() {
+        let addr = SocketAddr::from_str(endpoint).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Only IP address is supported for WireGuard peer endpoint \
+                    in kernel mode, got {endpoint}: {e}"
+                ),
+            )
+        })?;
+        ret.push(WgPeerAttr::Endpoint(addr));
+    }
+    if let Some(v) = peer.persistent_keepalive {
+        ret.push(WgPeerAttr::PersistentKeepalive(v));
+    }
+    if let Some(allowed_ips) = peer.allowed_ips.as_ref() {
+        ret.push(WgPeerAttr::Flags(WGPEER_F_REPLACE_ALLOWEDIPS));
+        ret.push(WgPeerAttr::AllowedIps(
+            allowed_ips
+                .iter()
+                .map(|ip| gen_allowed_ip_attrs(ip).map(WgAllowedIp))
+                .collect::<Result<Vec<_>, NmstateError>>()?,
+        ));
+    }
+    Ok(ret)
+}
+
+fn gen_allowed_ip_attrs(
+    allowed_ip: &str,
+) -> Result<Vec<WgAllowedIpAttr>, NmstateError> {
+    let (ip, prefix_len) = allowed_ip
+        .split_once('/')
+        .and_then(|(ip, prefix_len)| {
+            Some((IpAddr::from_str(ip).ok()?, prefix_len.parse::<u8>().ok()?))
+        })
+        .ok_or_else(|| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid WireGuard allowed IP {allowed_ip}"),
+            )
+        })?;
+    Ok(vec![
+        WgAllowedIpAttr::Family(if ip.is_ipv6() { AF_INET6 } else { AF_INET }),
+        WgAllowedIpAttr::IpAddr(ip),
+        WgAllowedIpAttr::CidrMask(prefix_len),
+    ])
+}
+
+fn decode_key(key: &str) -> Result<[u8; 32], NmstateError> {
+    wireguard_key_to_bytes(key).ok_or_else(|| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            "Invalid WireGuard key, should be base64 encoded string of 32 \
+            bytes"
+                .to_string(),
+        )
+    })
+}
+
+/// The generic netlink message of WireGuard family
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WireguardMessage {
+    pub(crate) cmd: u8,
+    pub(crate) attributes: Vec<WgDeviceAttr>,
+}
+
+impl GenlFamily for WireguardMessage {
+    fn family_name() -> &'static str {
+        WG_GENL_NAME
+    }
+
+    fn command(&self) -> u8 {
+        self.cmd
+    }
+
+    fn version(&self) -> u8 {
+        WG_GENL_VERSION
+    }
+}
+
+impl Emitable for WireguardMessage {
+    fn buffer_len(&self) -> usize {
+        self.attributes.as_slice().buffer_len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        self.attributes.as_slice().emit(buffer)
+    }
+}
+
+impl ParseableParametrized<[u8], GenlHeader> for WireguardMessage {
+    fn parse_with_param(
+        buf: &[u8],
+        header: GenlHeader,
+    ) -> Result<Self, DecodeError> {
+        Ok(Self {
+            cmd: header.cmd,
+            attributes: parse_nested(buf)?,
+        })
+    }
+}
+
+fn parse_nested<T>(payload: &[u8]) -> Result<Vec<T>, DecodeError>
+where
+    T: for<'a> Parseable<NlaBuffer<&'a [u8]>>,
+{
+    let mut ret = Vec::new();
+    for nla in NlasIterator::new(payload) {
+        ret.push(T::parse(&nla?)?);
+    }
+    Ok(ret)
+}
+
+fn parse_key(payload: &[u8], name: &str) -> Result<Vec<u8>, DecodeError> {
+    if payload.len() == 32 {
+        Ok(payload.to_vec())
+    } else {
+        Err(DecodeError::from(format!("invalid {name}: {payload:?}")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum WgDeviceAttr {
+    IfName(String),
+    PrivateKey(Vec<u8>),
+    Flags(u32),
+    ListenPort(u16),
+    Fwmark(u32),
+    Peers(Vec<WgPeer>),
+    Other(DefaultNla),
+}
+
+impl Nla for WgDeviceAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::IfName(v) => v.len() + 1,
+            Self::PrivateKey(v) => v.len(),
+            Self::Flags(_) | Self::Fwmark(_) => 4,
+            Self::ListenPort(_) => 2,
+            Self::Peers(v) => v.as_slice().buffer_len(),
+            Self::Other(nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::IfName(_) => WGDEVICE_A_IFNAME,
+            Self::PrivateKey(_) => WGDEVICE_A_PRIVATE_KEY,
+            Self::Flags(_) => WGDEVICE_A_FLAGS,
+            Self::ListenPort(_) => WGDEVICE_A_LISTEN_PORT,
+            Self::Fwmark(_) => WGDEVICE_A_FWMARK,
+            Self::Peers(_) => WGDEVICE_A_PEERS | NLA_F_NESTED,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::IfName(v) => {
+                buffer[..v.len()].copy_from_slice(v.as_bytes());
+                buffer[v.len()] = 0;
+            }
+            Self::PrivateKey(v) => buffer.copy_from_slice(v),
+            Self::Flags(v) | Self::Fwmark(v) => emit_u32(buffer, *v).unwrap(),
+            Self::ListenPort(v) => emit_u16(buffer, *v).unwrap(),
+            Self::Peers(v) => v.as_slice().emit(buffer),
+            Self::Other(nla) => nla.emit_value(buffer),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for WgDeviceAttr {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            WGDEVICE_A_IFNAME => Self::IfName(
+                parse_string(payload).context("invalid WGDEVICE_A_IFNAME")?,
+            ),
+            WGDEVICE_A_PRIVATE_KEY => {
+                Self::PrivateKey(parse_key(payload, "WGDEVICE_A_PRIVATE_KEY")?)
+            }
+            WGDEVICE_A_FLAGS => Self::Flags(
+                parse_u32(payload).context("invalid WGDEVICE_A_FLAGS")?,
+            ),
+            WGDEVICE_A_LISTEN_PORT => Self::ListenPort(
+                parse_u16(payload).context("invalid WGDEVICE_A_LISTEN_PORT")?,
+            ),
+            WGDEVICE_A_FWMARK => Self::Fwmark(
+                parse_u32(payload).context("invalid WGDEVICE_A_FWMARK")?,
+            ),
+            WGDEVICE_A_PEERS => Self::Peers(
+                parse_nested(payload).context("invalid WGDEVICE_A_PEERS")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The nested attributes of a single peer in `WGDEVICE_A_PEERS`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WgPeer(pub(crate) Vec<WgPeerAttr>);
+
+impl Nla for WgPeer {
+    fn value_len(&self) -> usize {
+        self.0.as_slice().buffer_len()
+    }
+
+    // Kernel ignores the type of array member
+    fn kind(&self) -> u16 {
+        NLA_F_NESTED
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.0.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for WgPeer {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(Self(parse_nested(buf.value())?))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum WgPeerAttr {
+    PublicKey(Vec<u8>),
+    PresharedKey(Vec<u8>),
+    Flags(u32),
+    Endpoint(SocketAddr),
+    PersistentKeepalive(u16),
+    AllowedIps(Vec<WgAllowedIp>),
+    Other(DefaultNla),
+}
+
+impl Nla for WgPeerAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::PublicKey(v) | Self::PresharedKey(v) => v.len(),
+            Self::Flags(_) => 4,
+            Self::Endpoint(v) => sockaddr_to_bytes(v).len(),
+            Self::PersistentKeepalive(_) => 2,
+            Self::AllowedIps(v) => v.as_slice().buffer_len(),
+            Self::Other(nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::PublicKey(_) => WGPEER_A_PUBLIC_KEY,
+            Self::PresharedKey(_) => WGPEER_A_PRESHARED_KEY,
+            Self::Flags(_) => WGPEER_A_FLAGS,
+            Self::Endpoint(_) => WGPEER_A_ENDPOINT,
+            Self::PersistentKeepalive(_) => {
+                WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL
+            }
+            Self::AllowedIps(_) => WGPEER_A_ALLOWEDIPS | NLA_F_NESTED,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::PublicKey(v) | Self::PresharedKey(v) => {
+                buffer.copy_from_slice(v)
+            }
+            Self::Flags(v) => emit_u32(buffer, *v).unwrap(),
+            Self::Endpoint(v) => buffer.copy_from_slice(&sockaddr_to_bytes(v)),
+            Self::PersistentKeepalive(v) => emit_u16(buffer, *v).unwrap(),
+            Self::AllowedIps(v) => v.as_slice().emit(buffer),
+            Self::Other(nla) => nla.emit_value(buffer),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for WgPeerAttr {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            WGPEER_A_PUBLIC_KEY => {
+                Self::PublicKey(parse_key(payload, "WGPEER_A_PUBLIC_KEY")?)
+            }
+            WGPEER_A_PRESHARED_KEY => Self::PresharedKey(parse_key(
+                payload,
+                "WGPEER_A_PRESHARED_KEY",
+            )?),
+            WGPEER_A_FLAGS => Self::Flags(
+                parse_u32(payload).context("invalid WGPEER_A_FLAGS")?,
+            ),
+            WGPEER_A_ENDPOINT => match parse_sockaddr(payload) {
+                Some(v) => Self::Endpoint(v),
+                None => Self::Other(DefaultNla::parse(buf)?),
+            },
+            WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL => {
+                Self::PersistentKeepalive(parse_u16(payload).context(
+                    "invalid WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL",
+                )?)
+            }
+            WGPEER_A_ALLOWEDIPS => Self::AllowedIps(
+                parse_nested(payload).context("invalid WGPEER_A_ALLOWEDIPS")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
+
+/// The nested attributes of a single allowed IP in `WGPEER_A_ALLOWEDIPS`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WgAllowedIp(pub(crate) Vec<WgAllowedIpAttr>);
+
+impl Nla for WgAllowedIp {
+    fn value_len(&self) -> usize {
+        self.0.as_slice().buffer_len()
+    }
+
+    // Kernel ignores the type of array member
+    fn kind(&self) -> u16 {
+        NLA_F_NESTED
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        self.0.as_slice().emit(buffer)
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>> for WgAllowedIp {
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(Self(parse_nested(buf.value())?))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum WgAllowedIpAttr {
+    Family(u16),
+    IpAddr(IpAddr),
+    CidrMask(u8),
+    Other(DefaultNla),
+}
+
+impl Nla for WgAllowedIpAttr {
+    fn value_len(&self) -> usize {
+        match self {
+            Self::Family(_) => 2,
+            Self::IpAddr(IpAddr::V4(_)) => 4,
+            Self::IpAddr(IpAddr::V6(_)) => 16,
+            Self::CidrMask(_) => 1,
+            Self::Other(nla) => nla.value_len(),
+        }
+    }
+
+    fn kind(&self) -> u16 {
+        match self {
+            Self::Family(_) => WGALLOWEDIP_A_FAMILY,
+            Self::IpAddr(_) => WGALLOWEDIP_A_IPADDR,
+            Self::CidrMask(_) => WGALLOWEDIP_A_CIDR_MASK,
+            Self::Other(nla) => nla.kind(),
+        }
+    }
+
+    fn emit_value(&self, buffer: &mut [u8]) {
+        match self {
+            Self::Family(v) => emit_u16(buffer, *v).unwrap(),
+            Self::IpAddr(IpAddr::V4(ip)) => {
+                buffer.copy_from_slice(&ip.octets())
+            }
+            Self::IpAddr(IpAddr::V6(ip)) => {
+                buffer.copy_from_slice(&ip.octets())
+            }
+            Self::CidrMask(v) => buffer[0] = *v,
+            Self::Other(nla) => nla.emit_value(buffer),
+        }
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<NlaBuffer<&'a T>>
+    for WgAllowedIpAttr
+{
+    fn parse(buf: &NlaBuffer<&'a T>) -> Result<Self, DecodeError> {
+        let payload = buf.value();
+        Ok(match buf.kind() {
+            WGALLOWEDIP_A_FAMILY => Self::Family(
+                parse_u16(payload).context("invalid WGALLOWEDIP_A_FAMILY")?,
+            ),
+            WGALLOWEDIP_A_IPADDR => Self::IpAddr(
+                parse_ip(payload).context("invalid WGALLOWEDIP_A_IPADDR")?,
+            ),
+            WGALLOWEDIP_A_CIDR_MASK => Self::CidrMask(
+                parse_u8(payload).context("invalid WGALLOWEDIP_A_CIDR_MASK")?,
+            ),
+            _ => Self::Other(DefaultNla::parse(buf)?),
+        })
+    }
+}
@@ -0,0 +1,641 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::{
+    nm_dbus::{NmConnection, NmIpRoute, NmSettingBridgePort, NmSettingIp},
+    query_apply::{device::nm_iface_type_to_nmstate, is_lldp_enabled},
+    show::nm_conn_to_base_iface,
+};
+use crate::{
+    BaseInterface, BondConfig, BondInterface, BondMode, BondOptions,
+    DnsClientState, DnsState, DummyInterface, ErrorKind, EthernetInterface,
+    Interface, InterfaceIdentifier, InterfaceIpAddr, InterfaceState,
+    InterfaceType, LinuxBridgeConfig, LinuxBridgeInterface, LinuxBridgeOptions,
+    LinuxBridgePortConfig, LinuxBridgeStpOptions, LoopbackInterface,
+    NetworkState, NmstateError, RouteEntry, RouteRuleEntry, RouteScope,
+    RouteType, VlanConfig, VlanInterface, VlanProtocol, VrfConfig,
+    VrfInterface, VxlanConfig, VxlanInterface,
+};
+
+const NM_KEYFILE_SUFFIX: &str = ".nmconnection";
+
+// NetworkManager enables STP by default
+const NM_BRIDGE_STP_DEFAULT: bool = true;
+
+// General work flow:
+//  * Parse each `*.nmconnection` file into `NmConnection`.
+//  * Convert `NmConnection` to nmstate `Interface` using the same code used
+//    by querying NetworkManager, then fill the properties which nmstate
+//    query normally retrieves from kernel.
+//  * Attach ports to their controllers.
+//  * Move routes, route rules and DNS to the top level of `NetworkState`.
+pub(crate) fn nm_import_keyfiles(
+    dir: &str,
+) -> Result<NetworkState, NmstateError> {
+    let nm_conns = read_nm_keyfiles(dir)?;
+
+    // Controller could be referred by UUID, profile name or interface name.
+    let mut ctrl_name_index: HashMap<&str, &str> = HashMap::new();
+    for nm_conn in nm_conns.iter().map(|(_, c)| c) {
+        if let Some(iface_name) = nm_conn.iface_name() {
+            if let Some(uuid) = nm_conn.uuid() {
+                ctrl_name_index.insert(uuid, iface_name);
+            }
+            if let Some(id) = nm_conn.id() {
+                ctrl_name_index.insert(id, iface_name);
+            }
+            ctrl_name_index.insert(iface_name, iface_name);
+        }
+    }
+
+    let mut net_state = NetworkState::new();
+    let mut ifaces: Vec<Interface> = Vec::new();
+    let mut routes: Vec<RouteEntry> = Vec::new();
+    let mut br_port_sets: HashMap<String, &NmSettingBridgePort> =
+        HashMap::new();
+    for (file_path, nm_conn) in nm_conns.iter() {
+        let lldp_neighbors = if is_lldp_enabled(nm_conn) {
+            Some(Vec::new())
+        } else {
+            None
+        };
+        let mut base_iface = match nm_conn_to_base_iface(
+            None,
+            nm_conn,
+            Some(nm_conn),
+            lldp_neighbors,
+        ) {
+            Some(b) => b,
+            None => {
+                log::warn!(
+                    "Ignoring NetworkManager keyfile {file_path} which \
+                    has no interface name defined"
+                );
+                continue;
+            }
+        };
+        base_iface.iface_type = nm_iface_type_to_nmstate(
+            nm_conn.iface_type().unwrap_or_default(),
+            false,
+        );
+        fill_base_iface(nm_conn, &mut base_iface, &ctrl_name_index);
+        if let Some(br_port_set) = nm_conn.bridge_port.as_ref() {
+            br_port_sets.insert(base_iface.name.clone(), br_port_set);
+        }
+        routes.extend(get_routes(&base_iface.name, nm_conn)?);
+
+        let iface = match nm_conn_to_iface(nm_conn, base_iface)? {
+            Some(i) => i,
+            None => {
+                log::warn!(
+                    "Ignoring NetworkManager keyfile {file_path} holding \
+                    interface type {} which is not supported yet",
+                    nm_conn.iface_type().unwrap_or_default()
+                );
+                continue;
+            }
+        };
+        ifaces.push(iface);
+    }
+
+    attach_ports(&mut ifaces, &br_port_sets);
+
+    let mut rules: Vec<RouteRuleEntry> = Vec::new();
+    let mut dns_conf = DnsClientState::default();
+    for iface in ifaces.iter_mut() {
+        let base_iface = iface.base_iface_mut();
+        if let Some(ip_conf) = base_iface.ipv4.as_mut() {
+            rules.extend(ip_conf.rules.take().unwrap_or_default());
+            merge_dns_conf(&mut dns_conf, ip_conf.dns.take());
+        }
+        if let Some(ip_conf) = base_iface.ipv6.as_mut() {
+            rules.extend(ip_conf.rules.take().unwrap_or_default());
+            merge_dns_conf(&mut dns_conf, ip_conf.dns.take());
+        }
+    }
+    for iface in ifaces {
+        net_state.append_interface_data(iface);
+    }
+    if !routes.is_empty() {
+        net_state.routes.config = Some(routes);
+    }
+    if !rules.is_empty() {
+        net_state.rules.config = Some(rules);
+    }
+    if !dns_conf.is_empty() {
+        net_state.dns = Some(DnsState {
+            config: Some(dns_conf),
+            ..Default::default()
+        });
+    }
+    Ok(net_state)
+}
+
+fn read_nm_keyfiles(
+    dir: &str,
+) -> Result<Vec<(String, NmConnection)>, NmstateError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Failed to read folder {dir}: {e}"),
+        )
+    })?;
+    let mut file_paths: Vec<String> = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Failed to read folder {dir}: {e}"),
+            )
+        })?;
+        let file_path = entry.path();
+        if file_path.is_file()
+            && file_path
+                .to_str()
+                .map(|p| p.ends_with(NM_KEYFILE_SUFFIX))
+                .unwrap_or_default()
+        {
+            file_paths.push(file_path.display().to_string());
+        }
+    }
+    // We should be persistent on output, hence sort by file name.
+    file_paths.sort_unstable();
+
+    let mut ret = Vec::new();
+    for file_path in file_paths {
+        let content =
+            std::fs::read_to_string(Path::new(&file_path)).map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Failed to read file {file_path}: {e}"),
+                )
+            })?;
+        let nm_conn = NmConnection::from_keyfile(&content).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Failed to parse NetworkManager keyfile {file_path}: {}",
+                    e.msg
+                ),
+            )
+        })?;
+        ret.push((file_path, nm_conn));
+    }
+    Ok(ret)
+}
+
+// Fill the properties nmstate query retrieves from kernel
+fn fill_base_iface(
+    nm_conn: &NmConnection,
+    base_iface: &mut BaseInterface,
+    ctrl_name_index: &HashMap<&str, &str>,
+) {
+    if nm_conn.connection.as_ref().and_then(|c| c.autoconnect) == Some(false) {
+        base_iface.state = InterfaceState::Down;
+    }
+    if base_iface.lldp.as_ref().map(|l| l.enabled) == Some(false) {
+        base_iface.lldp = None;
+    }
+    if let Some(wired_set) = nm_conn.wired.as_ref() {
+        base_iface.mtu = wired_set.mtu.map(u64::from);
+        // The `cloned-mac-address` might be special values like `preserve`
+        // or `random` which is not supported by nmstate
+        base_iface.mac_address =
+            if base_iface.identifier == Some(InterfaceIdentifier::MacAddress) {
+                wired_set.mac_address.clone()
+            } else {
+                wired_set
+                    .cloned_mac_address
+                    .as_ref()
+                    .filter(|m| m.contains(':'))
+                    .cloned()
+            };
+    } else if let Some(lo_set) = nm_conn.loopback.as_ref() {
+        base_iface.mtu = lo_set.mtu.map(u64::from);
+    }
+    if base_iface.identifier == Some(InterfaceIdentifier::Name) {
+        base_iface.identifier = None;
+    }
+    if let Some(ctrl) = nm_conn.controller() {
+        base_iface.controller = Some(
+            ctrl_name_index
+                .get(ctrl)
+                .copied()
+                .unwrap_or(ctrl)
+                .to_string(),
+        );
+        base_iface.controller_type = nm_conn
+            .controller_type()
+            .map(|t| nm_iface_type_to_nmstate(t, false));
+    }
+    // NetworkManager does not store addresses in `BaseInterface` when
+    // querying as they are retrieved from kernel.
+    if let (Some(ip_conf), Some(ip_set)) =
+        (base_iface.ipv4.as_mut(), nm_conn.ipv4.as_ref())
+    {
+        if ip_conf.enabled && ip_conf.dhcp != Some(true) {
+            ip_conf.dhcp = Some(false);
+        }
+        if ip_conf.enabled {
+            ip_conf.addresses = get_ip_addresses(ip_set);
+        }
+    }
+    if let (Some(ip_conf), Some(ip_set)) =
+        (base_iface.ipv6.as_mut(), nm_conn.ipv6.as_ref())
+    {
+        if ip_conf.enabled {
+            ip_conf.addresses = get_ip_addresses(ip_set);
+        }
+    }
+}
+
+fn get_ip_addresses(ip_set: &NmSettingIp) -> Option<Vec<InterfaceIpAddr>> {
+    let mut addrs = Vec::new();
+    for addr_str in ip_set.addresses.as_slice() {
+        match InterfaceIpAddr::try_from(addr_str.as_str()) {
+            Ok(addr) => addrs.push(addr),
+            Err(e) => {
+                log::warn!("Ignoring invalid IP address {addr_str}: {e}");
+            }
+        }
+    }
+    if addrs.is_empty() {
+        None
+    } else {
+        Some(addrs)
+    }
+}
+
+fn nm_conn_to_iface(
+    nm_conn: &NmConnection,
+    base_iface: BaseInterface,
+) -> Result<Option<Interface>, NmstateError> {
+    Ok(Some(match &base_iface.iface_type {
+        InterfaceType::Ethernet => Interface::Ethernet({
+            let mut iface = EthernetInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Dummy => Interface::Dummy({
+            let mut iface = DummyInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Loopback => Interface::Loopback({
+            let mut iface = LoopbackInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Bond => Interface::Bond({
+            let mut iface = BondInterface::new();
+            iface.bond = Some(get_bond_conf(&base_iface.name, nm_conn)?);
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::LinuxBridge => Interface::LinuxBridge({
+            let mut iface = LinuxBridgeInterface::new();
+            iface.bridge = Some(get_bridge_conf(nm_conn));
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Vlan => Interface::Vlan({
+            let mut iface = VlanInterface::new();
+            iface.vlan = Some(get_vlan_conf(&base_iface.name, nm_conn)?);
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Vxlan => Interface::Vxlan({
+            let mut iface = VxlanInterface::new();
+            iface.vxlan = Some(get_vxlan_conf(&base_iface.name, nm_conn)?);
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Vrf => Interface::Vrf({
+            let mut iface = VrfInterface::new();
+            iface.vrf = Some(VrfConfig {
+                table_id: nm_conn
+                    .vrf
+                    .as_ref()
+                    .and_then(|v| v.table)
+                    .unwrap_or_default(),
+                port: Some(Vec::new()),
+                ..Default::default()
+            });
+            iface.base = base_iface;
+            iface
+        }),
+        _ => return Ok(None),
+    }))
+}
+
+fn get_bond_conf(
+    iface_name: &str,
+    nm_conn: &NmConnection,
+) -> Result<BondConfig, NmstateError> {
+    let mut bond_conf = BondConfig::new();
+    bond_conf.port = Some(Vec::new());
+    let mut nm_opts = nm_conn
+        .bond
+        .as_ref()
+        .map(|b| b.options.clone())
+        .unwrap_or_default();
+    if let Some(mode) = nm_opts.remove("mode") {
+        bond_conf.mode = Some(
+            serde_json::from_value::<BondMode>(serde_json::Value::String(
+                mode.clone(),
+            ))
+            .map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid bond mode {mode} of {iface_name}: {e}"),
+                )
+            })?,
+        );
+    }
+    if !nm_opts.is_empty() {
+        let opts: serde_json::Map<String, serde_json::Value> = nm_opts
+            .drain()
+            .map(|(k, v)| (k, serde_json::Value::String(v)))
+            .collect();
+        bond_conf.options = Some(
+            serde_json::from_value::<BondOptions>(serde_json::Value::Object(
+                opts,
+            ))
+            .map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid bond options of {iface_name}: {e}"),
+                )
+            })?,
+        );
+    }
+    Ok(bond_conf)
+}
+
+fn get_bridge_conf(nm_conn: &NmConnection) -> LinuxBridgeConfig {
+    let mut br_conf = LinuxBridgeConfig::new();
+    br_conf.port = Some(Vec::new());
+    let mut br_opts = LinuxBridgeOptions::new();
+    let mut stp_opts = LinuxBridgeStpOptions::new();
+    if let Some(br_set) = nm_conn.bridge.as_ref() {
+        stp_opts.enabled = Some(br_set.stp.unwrap_or(NM_BRIDGE_STP_DEFAULT));
+        stp_opts.priority = br_set.priority.and_then(|v| u16::try_from(v).ok());
+        stp_opts.forward_delay =
+            br_set.forward_delay.and_then(|v| u8::try_from(v).ok());
+        stp_opts.hello_time =
+            br_set.hello_time.and_then(|v| u8::try_from(v).ok());
+        stp_opts.max_age = br_set.max_age.and_then(|v| u8::try_from(v).ok());
+        br_opts.mac_ageing_time = br_set.ageing_time;
+        br_opts.group_forward_mask = br_set
+            .group_forward_mask
+            .and_then(|v| u16::try_from(v).ok());
+        br_opts.multicast_snooping = br_set.multicast_snooping;
+        if br_set.vlan_filtering == Some(true) {
+            br_opts.vlan_default_pvid =
+                br_set.vlan_default_pvid.and_then(|v| u16::try_from(v).ok());
+        }
+    } else {
+        stp_opts.enabled = Some(NM_BRIDGE_STP_DEFAULT);
+    }
+    br_opts.stp = Some(stp_opts);
+    br_conf.options = Some(br_opts);
+    br_conf
+}
+
+fn get_vlan_conf(
+    iface_name: &str,
+    nm_conn: &NmConnection,
+) -> Result<VlanConfig, NmstateError> {
+    let vlan_set = nm_conn.vlan.as_ref();
+    let id = vlan_set
+        .and_then(|v| v.id)
+        .and_then(|id| u16::try_from(id).ok())
+        .ok_or_else(|| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("VLAN {iface_name} has no valid VLAN ID defined"),
+            )
+        })?;
+    Ok(VlanConfig {
+        base_iface: vlan_set.and_then(|v| v.parent.clone()).unwrap_or_default(),
+        id,
+        protocol: match vlan_set.and_then(|v| v.protocol.as_deref()) {
+            Some("802.1ad") => Some(VlanProtocol::Ieee8021Ad),
+            Some("802.1Q") => Some(VlanProtocol::Ieee8021Q),
+            _ => None,
+        },
+        ..Default::default()
+    })
+}
+
+fn get_vxlan_conf(
+    iface_name: &str,
+    nm_conn: &NmConnection,
+) -> Result<VxlanConfig, NmstateError> {
+    let vxlan_set = nm_conn.vxlan.as_ref();
+    let id = vxlan_set.and_then(|v| v.id).ok_or_else(|| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("VXLAN {iface_name} has no VXLAN ID defined"),
+        )
+    })?;
+    Ok(VxlanConfig {
+        base_iface: vxlan_set
+            .and_then(|v| v.parent.clone())
+            .unwrap_or_default(),
+        id,
+        learning: vxlan_set.and_then(|v| v.learning),
+        local: vxlan_set
+            .and_then(|v| v.local.as_deref())
+            .and_then(|v| v.parse().ok()),
+        remote: vxlan_set
+            .and_then(|v| v.remote.as_deref())
+            .and_then(|v| v.parse().ok()),
+        dst_port: vxlan_set
+            .and_then(|v| v.dst_port)
+            .and_then(|v| u16::try_from(v).ok()),
+        ..Default::default()
+    })
+}
+
+// Move the `controller` property of port to the port list of controller,
+// the `controller` property is only kept when controller is not imported.
+fn attach_ports(
+    ifaces: &mut [Interface],
+    br_port_sets: &HashMap<String, &NmSettingBridgePort>,
+) {
+    let ctrl_names: Vec<String> = ifaces
+        .iter()
+        .filter(|i| i.is_controller())
+        .map(|i| i.name().to_string())
+        .collect();
+    let mut ctrl_ports: HashMap<String, Vec<String>> = HashMap::new();
+    for iface in ifaces.iter_mut() {
+        let base_iface = iface.base_iface_mut();
+        if let Some(ctrl) = base_iface
+            .controller
+            .as_ref()
+            .filter(|c| ctrl_names.contains(c))
+        {
+            ctrl_ports
+                .entry(ctrl.to_string())
+                .or_default()
+                .push(base_iface.name.clone());
+            base_iface.controller = None;
+            base_iface.controller_type = None;
+        }
+    }
+    for iface in ifaces.iter_mut() {
+        let port_names = match ctrl_ports.remove(iface.name()) {
+            Some(p) => p,
+            None => continue,
+        };
+        match iface {
+            Interface::Bond(iface) => {
+                if let Some(bond_conf) = iface.bond.as_mut() {
+                    bond_conf.port = Some(port_names);
+                }
+            }
+            Interface::LinuxBridge(iface) => {
+                if let Some(br_conf) = iface.bridge.as_mut() {
+                    br_conf.port = Some(
+                        port_names
+                            .into_iter()
+                            .map(|name| {
+                                let mut port_conf =
+                                    LinuxBridgePortConfig::new();
+                                if let Some(port_set) = br_port_sets.get(&name)
+                                {
+                                    port_conf.stp_priority = port_set
+                                        .priority
+                                        .and_then(|v| u16::try_from(v).ok());
+                                    port_conf.stp_path_cost =
+                                        port_set.path_cost;
+                                    port_conf.stp_hairpin_mode =
+                                        port_set.hairpin_mode;
+                                }
+                                port_conf.name = name;
+                                port_conf
+                            })
+                            .collect(),
+                    );
+                }
+            }
+            Interface::Vrf(iface) => {
+                if let Some(vrf_conf) = iface.vrf.as_mut() {
+                    vrf_conf.port = Some(port_names);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+fn get_routes(
+    iface_name: &str,
+    nm_conn: &NmConnection,
+) -> Result<Vec<RouteEntry>, NmstateError> {
+    let mut ret = Vec::new();
+    for (ip_set, is_ipv6) in [
+        (nm_conn.ipv4.as_ref(), false),
+        (nm_conn.ipv6.as_ref(), true),
+    ] {
+        let ip_set = match ip_set {
+            Some(i) => i,
+            None => continue,
+        };
+        if let Some(gw) = ip_set.gateway.as_ref() {
+            let mut rt = RouteEntry::new();
+            rt.destination = Some(if is_ipv6 {
+                "::/0".to_string()
+            } else {
+                "0.0.0.0/0".to_string()
+            });
+            rt.next_hop_iface = Some(iface_name.to_string());
+            rt.next_hop_addr = Some(gw.to_string());
+            rt.metric = ip_set.route_metric.filter(|m| *m >= 0);
+            rt.table_id = ip_set.route_table.filter(|t| *t != 0);
+            ret.push(rt);
+        }
+        for nm_route in ip_set.routes.as_slice() {
+            ret.push(nm_route_to_nmstate(iface_name, ip_set, nm_route)?);
+        }
+    }
+    Ok(ret)
+}
+
+fn nm_route_to_nmstate(
+    iface_name: &str,
+    ip_set: &NmSettingIp,
+    nm_route: &NmIpRoute,
+) -> Result<RouteEntry, NmstateError> {
+    let mut rt = RouteEntry::new();
+    if let (Some(dest), Some(prefix)) =
+        (nm_route.dest.as_ref(), nm_route.prefix.as_ref())
+    {
+        rt.destination = Some(format!("{dest}/{prefix}"));
+    }
+    rt.route_type = match nm_route.route_type.as_deref() {
+        Some("blackhole") => Some(RouteType::Blackhole),
+        Some("unreachable") => Some(RouteType::Unreachable),
+        Some("prohibit") => Some(RouteType::Prohibit),
+        Some("throw") => Some(RouteType::Throw),
+        Some("local") => Some(RouteType::Local),
+        Some("unicast") | None => None,
+        Some(t) => {
+            return Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Route type {t} of route {:?} on interface {iface_name} \
+                    is not supported",
+                    rt.destination
+                ),
+            ));
+        }
+    };
+    if rt.has_next_hop() {
+        rt.next_hop_iface = Some(iface_name.to_string());
+        rt.next_hop_addr.clone_from(&nm_route.next_hop);
+    }
+    rt.metric = nm_route
+        .metric
+        .map(i64::from)
+        .or_else(|| ip_set.route_metric.filter(|m| *m >= 0));
+    rt.table_id = nm_route.table.or(ip_set.route_table).filter(|t| *t != 0);
+    rt.weight = nm_route.weight.and_then(|w| u16::try_from(w).ok());
+    rt.cwnd = nm_route.cwnd;
+    rt.source.clone_from(&nm_route.src);
+    rt.mtu = nm_route.mtu;
+    rt.lock_mtu = nm_route.lock_mtu;
+    rt.advmss = nm_route.advmss;
+    rt.initcwnd = nm_route.initcwnd;
+    rt.initrwnd = nm_route.initrwnd;
+    rt.quickack = nm_route.quickack;
+    rt.onlink = nm_route.onlink;
+    rt.scope = nm_route.scope.map(RouteScope::try_from).transpose()?;
+    Ok(rt)
+}
+
+fn merge_dns_conf(
+    dns_conf: &mut DnsClientState,
+    ip_dns: Option<DnsClientState>,
+) {
+    let ip_dns = match ip_dns {
+        Some(d) => d,
+        None => return,
+    };
+    for (target, items) in [
+        (&mut dns_conf.server, ip_dns.server),
+        (&mut dns_conf.search, ip_dns.search),
+        (&mut dns_conf.options, ip_dns.options),
+    ] {
+        for item in items.unwrap_or_default() {
+            let target = target.get_or_insert_with(Vec::new);
+            if !target.contains(&item) {
+                target.push(item);
+            }
+        }
+    }
+}
@@ -11,6 +11,10 @@ pub(crate) mod dns;
 mod error;
 #[cfg(feature = "gen_conf")]
 mod gen_conf;
+#[cfg(feature = "query_apply")]
+mod import;
+#[cfg(feature = "query_apply")]
+mod monitor;
 #[allow(unused_imports)]
 mod nm_dbus;
 mod profile;
@@ -30,6 +34,17 @@ pub(crate) use checkpoint::{
 #[cfg(feature = "gen_conf")]
 pub(crate) use gen_conf::nm_gen_conf;
 #[cfg(feature = "query_apply")]
-pub(crate) use query_apply::nm_apply;
+pub(crate) use import::nm_import_keyfiles;
+#[cfg(feature = "query_apply")]
+pub(crate) use monitor::nm_monitor_start;
+#[cfg(feature = "query_apply")]
+pub(crate) use query_apply::{nm_apply, nm_apply_dry_run};
 #[cfg(feature = "query_apply")]
 pub(crate) use show::nm_retrieve;
+#[cfg(all(test, feature = "query_apply"))]
+pub(crate) use nm_dbus::{
+    nm_props_changed_targets, NmActiveConnection, NmChange, NmConnection,
+    NmSettingConnection, NmSignalTarget,
+};
+#[cfg(all(test, feature = "query_apply"))]
+pub(crate) use query_apply::gen_nm_apply_report;
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    nm::{
+        error::nm_error_to_nmstate,
+        nm_dbus::{NmChange, NmSignalMonitor},
+    },
+    query_apply::{WatchEvent, WatchEventSender},
+    NmstateError,
+};
+
+// The monitor thread quits on next signal after receiver dropped.
+pub(crate) fn nm_monitor_start(
+    sender: WatchEventSender,
+) -> Result<(), NmstateError> {
+    let monitor = NmSignalMonitor::new().map_err(nm_error_to_nmstate)?;
+    std::thread::spawn(move || loop {
+        let changes = match monitor.wait_changes() {
+            Ok(c) => c,
+            Err(e) => {
+                log::error!(
+                    "Failed to receive NetworkManager D-Bus signal: {e}"
+                );
+                break;
+            }
+        };
+        if changes
+            .into_iter()
+            .map(nm_change_to_watch_event)
+            .any(|event| sender.send(event).is_err())
+        {
+            break;
+        }
+    });
+    Ok(())
+}
+
+fn nm_change_to_watch_event(change: NmChange) -> WatchEvent {
+    match change {
+        NmChange::Iface(name) => WatchEvent::Iface(name),
+        NmChange::Dns => WatchEvent::Dns,
+        NmChange::Hostname => WatchEvent::Hostname,
+        _ => WatchEvent::Ifaces,
+    }
+}
@@ -16,6 +16,8 @@ use super::super::{
     connection::ieee8021x::NmSetting8021X,
     connection::infiniband::NmSettingInfiniBand,
     connection::ip::NmSettingIp,
+    connection::ip_tunnel::NmSettingIpTunnel,
+    connection::ip_vlan::NmSettingIpVlan,
     connection::loopback::NmSettingLoopback,
     connection::mac_vlan::NmSettingMacVlan,
     connection::macsec::NmSettingMacSec,
@@ -32,6 +34,7 @@ use super::super::{
     connection::vrf::NmSettingVrf,
     connection::vxlan::NmSettingVxlan,
     connection::wired::NmSettingWired,
+    connection::wireguard::NmSettingWireGuard,
     convert::ToDbusValue,
     NmError,
 };
@@ -109,6 +112,9 @@ pub struct NmConnection {
     pub macsec: Option<NmSettingMacSec>,
     pub hsr: Option<NmSettingHsr>,
     pub vpn: Option<NmSettingVpn>,
+    pub wireguard: Option<NmSettingWireGuard>,
+    pub ip_tunnel: Option<NmSettingIpTunnel>,
+    pub ip_vlan: Option<NmSettingIpVlan>,
     #[serde(skip)]
     pub obj_path: String,
     #[serde(skip)]
@@ -187,6 +193,13 @@ impl TryFrom<NmConnectionDbusOwnedValue> for NmConnection {
             loopback: _from_map!(v, "loopback", NmSettingLoopback::try_from)?,
             hsr: _from_map!(v, "hsr", NmSettingHsr::try_from)?,
             vpn: _from_map!(v, "vpn", NmSettingVpn::try_from)?,
+            wireguard: _from_map!(
+                v,
+                "wireguard",
+                NmSettingWireGuard::try_from
+            )?,
+            ip_tunnel: _from_map!(v, "ip-tunnel", NmSettingIpTunnel::try_from)?,
+            ip_vlan: _from_map!(v, "ipvlan", NmSettingIpVlan::try_from)?,
             _other: v,
             ..Default::default()
         })
@@ -305,6 +318,15 @@ impl NmConnection {
         if let Some(v) = &self.vpn {
             ret.insert("vpn", v.to_value()?);
         }
+        if let Some(v) = &self.wireguard {
+            ret.insert("wireguard", v.to_value()?);
+        }
+        if let Some(v) = &self.ip_tunnel {
+            ret.insert("ip-tunnel", v.to_value()?);
+        }
+        if let Some(v) = &self.ip_vlan {
+            ret.insert("ipvlan", v.to_value()?);
+        }
         for (key, setting_value) in &self._other {
             let mut other_setting_value: HashMap<&str, zvariant::Value> =
                 HashMap::new();
@@ -474,6 +496,16 @@ pub(crate) fn nm_con_get_from_obj_path(
             }
         }
     }
+    if let Some(wg_conf) = nm_conn.wireguard.as_mut() {
+        if let Ok(nm_secrets) = proxy.call::<&str, NmConnectionDbusOwnedValue>(
+            "GetSecrets",
+            &"wireguard",
+        ) {
+            if let Some(nm_secret) = nm_secrets.get("wireguard") {
+                wg_conf.fill_secrets(nm_secret);
+            }
+        }
+    }
     if let Ok(flags) = proxy.get_property::<u32>("Flags") {
         nm_conn.flags = from_u32_to_vec_nm_conn_flags(flags);
     }
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingIpTunnel {
+    pub mode: Option<u32>,
+    pub parent: Option<String>,
+    pub local: Option<String>,
+    pub remote: Option<String>,
+    pub ttl: Option<u32>,
+    pub tos: Option<u32>,
+    pub path_mtu_discovery: Option<bool>,
+    pub input_key: Option<String>,
+    pub output_key: Option<String>,
+    pub mtu: Option<u32>,
+    _other: HashMap<String, zvariant::OwnedValue>,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingIpTunnel {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            mode: _from_map!(v, "mode", u32::try_from)?,
+            parent: _from_map!(v, "parent", String::try_from)?,
+            local: _from_map!(v, "local", String::try_from)?,
+            remote: _from_map!(v, "remote", String::try_from)?,
+            ttl: _from_map!(v, "ttl", u32::try_from)?,
+            tos: _from_map!(v, "tos", u32::try_from)?,
+            path_mtu_discovery: _from_map!(
+                v,
+                "path-mtu-discovery",
+                bool::try_from
+            )?,
+            input_key: _from_map!(v, "input-key", String::try_from)?,
+            output_key: _from_map!(v, "output-key", String::try_from)?,
+            mtu: _from_map!(v, "mtu", u32::try_from)?,
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingIpTunnel {
+    fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = self.mode {
+            ret.insert("mode", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.parent.as_deref() {
+            if !v.is_empty() {
+                ret.insert("parent", zvariant::Value::new(v));
+            }
+        }
+        if let Some(v) = &self.local {
+            ret.insert("local", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.remote {
+            ret.insert("remote", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.ttl {
+            ret.insert("ttl", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.tos {
+            ret.insert("tos", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.path_mtu_discovery {
+            ret.insert("path-mtu-discovery", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.input_key {
+            ret.insert("input-key", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.output_key {
+            ret.insert("output-key", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.mtu {
+            ret.insert("mtu", zvariant::Value::new(v));
+        }
+        ret.extend(self._other.iter().map(|(key, value)| {
+            (key.as_str(), zvariant::Value::from(value.clone()))
+        }));
+        Ok(ret)
+    }
+}
@@ -25,6 +25,8 @@ mod hsr;
 mod ieee8021x;
 mod infiniband;
 mod ip;
+mod ip_tunnel;
+mod ip_vlan;
 mod loopback;
 mod mac_vlan;
 mod macsec;
@@ -39,6 +41,7 @@ mod vpn;
 mod vrf;
 mod vxlan;
 mod wired;
+mod wireguard;
 
 pub use self::bond::{NmSettingBond, NmSettingBondPort};
 pub use self::bridge::{
@@ -52,6 +55,8 @@ pub use self::hsr::NmSettingHsr;
 pub use self::ieee8021x::NmSetting8021X;
 pub use self::infiniband::NmSettingInfiniBand;
 pub use self::ip::{NmSettingIp, NmSettingIpMethod};
+pub use self::ip_tunnel::NmSettingIpTunnel;
+pub use self::ip_vlan::NmSettingIpVlan;
 pub use self::loopback::NmSettingLoopback;
 pub use self::mac_vlan::NmSettingMacVlan;
 pub use self::macsec::NmSettingMacSec;
@@ -70,6 +75,7 @@ pub use self::vpn::NmSettingVpn;
 pub use self::vrf::NmSettingVrf;
 pub use self::vxlan::NmSettingVxlan;
 pub use self::wired::NmSettingWired;
+pub use self::wireguard::{NmSettingWireGuard, NmSettingWireGuardPeer};
 
 pub(crate) use self::conn::DbusDictionary;
 #[cfg(feature = "query_apply")]
@@ -19,6 +19,15 @@ pub struct NmIpRoute {
     pub route_type: Option<String>,
     pub cwnd: Option<u32>,
     pub lock_cwnd: Option<bool>,
+    pub src: Option<String>,
+    pub mtu: Option<u32>,
+    pub lock_mtu: Option<bool>,
+    pub advmss: Option<u32>,
+    pub initcwnd: Option<u32>,
+    pub initrwnd: Option<u32>,
+    pub quickack: Option<bool>,
+    pub onlink: Option<bool>,
+    pub scope: Option<u8>,
     _other: DbusDictionary,
 }
 
@@ -41,6 +50,15 @@ impl TryFrom<DbusDictionary> for NmIpRoute {
             route_type: _from_map!(v, "type", String::try_from)?,
             cwnd: _from_map!(v, "cwnd", u32::try_from)?,
             lock_cwnd: _from_map!(v, "lock-cwnd", bool::try_from)?,
+            src: _from_map!(v, "src", String::try_from)?,
+            mtu: _from_map!(v, "mtu", u32::try_from)?,
+            lock_mtu: _from_map!(v, "lock-mtu", bool::try_from)?,
+            advmss: _from_map!(v, "advmss", u32::try_from)?,
+            initcwnd: _from_map!(v, "initcwnd", u32::try_from)?,
+            initrwnd: _from_map!(v, "initrwnd", u32::try_from)?,
+            quickack: _from_map!(v, "quickack", bool::try_from)?,
+            onlink: _from_map!(v, "onlink", bool::try_from)?,
+            scope: _from_map!(v, "scope", u8::try_from)?,
             _other: v,
         })
     }
@@ -106,6 +124,60 @@ impl NmIpRoute {
                 zvariant::Value::new(zvariant::Value::new(v)),
             )?;
         }
+        if let Some(v) = &self.src {
+            ret.append(
+                zvariant::Value::new("src"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.mtu {
+            ret.append(
+                zvariant::Value::new("mtu"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.lock_mtu {
+            ret.append(
+                zvariant::Value::new("lock-mtu"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.advmss {
+            ret.append(
+                zvariant::Value::new("advmss"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.initcwnd {
+            ret.append(
+                zvariant::Value::new("initcwnd"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.initrwnd {
+            ret.append(
+                zvariant::Value::new("initrwnd"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.quickack {
+            ret.append(
+                zvariant::Value::new("quickack"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.onlink {
+            ret.append(
+                zvariant::Value::new("onlink"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.scope {
+            ret.append(
+                zvariant::Value::new("scope"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
         for (key, value) in self._other.iter() {
             ret.append(
                 zvariant::Value::new(key.as_str()),
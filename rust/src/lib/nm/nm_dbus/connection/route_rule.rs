@@ -35,6 +35,15 @@ pub struct NmIpRouteRule {
     pub iifname: Option<String>,
     pub action: Option<NmIpRouteRuleAction>,
     pub suppress_prefixlength: Option<i32>,
+    pub sport_start: Option<u16>,
+    pub sport_end: Option<u16>,
+    pub dport_start: Option<u16>,
+    pub dport_end: Option<u16>,
+    pub ipproto: Option<u8>,
+    pub uid_range_start: Option<u32>,
+    pub uid_range_end: Option<u32>,
+    pub tos: Option<u8>,
+    pub oifname: Option<String>,
     _other: DbusDictionary,
 }
 
@@ -59,6 +68,15 @@ impl TryFrom<DbusDictionary> for NmIpRouteRule {
                 "suppress-prefixlength",
                 i32::try_from
             )?,
+            sport_start: _from_map!(v, "sport-start", u16::try_from)?,
+            sport_end: _from_map!(v, "sport-end", u16::try_from)?,
+            dport_start: _from_map!(v, "dport-start", u16::try_from)?,
+            dport_end: _from_map!(v, "dport-end", u16::try_from)?,
+            ipproto: _from_map!(v, "ipproto", u8::try_from)?,
+            uid_range_start: _from_map!(v, "uid-range-start", u32::try_from)?,
+            uid_range_end: _from_map!(v, "uid-range-end", u32::try_from)?,
+            tos: _from_map!(v, "tos", u8::try_from)?,
+            oifname: _from_map!(v, "oifname", String::try_from)?,
             _other: v,
         })
     }
@@ -142,6 +160,49 @@ impl NmIpRouteRule {
                 zvariant::Value::new(zvariant::Value::new(v)),
             )?;
         }
+        for (key, value) in [
+            ("sport-start", self.sport_start),
+            ("sport-end", self.sport_end),
+            ("dport-start", self.dport_start),
+            ("dport-end", self.dport_end),
+        ] {
+            if let Some(v) = value {
+                ret.append(
+                    zvariant::Value::new(key),
+                    zvariant::Value::new(zvariant::Value::new(v)),
+                )?;
+            }
+        }
+        if let Some(v) = &self.ipproto {
+            ret.append(
+                zvariant::Value::new("ipproto"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.uid_range_start {
+            ret.append(
+                zvariant::Value::new("uid-range-start"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.uid_range_end {
+            ret.append(
+                zvariant::Value::new("uid-range-end"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.tos {
+            ret.append(
+                zvariant::Value::new("tos"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
+        if let Some(v) = &self.oifname {
+            ret.append(
+                zvariant::Value::new("oifname"),
+                zvariant::Value::new(zvariant::Value::new(v)),
+            )?;
+        }
 
         for (key, value) in self._other.iter() {
             ret.append(
@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
+
+// NM_SETTING_SECRET_FLAG_NONE: the secret is stored by NetworkManager
+const NM_SETTING_SECRET_FLAG_NONE: u32 = 0;
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingWireGuard {
+    pub private_key: Option<String>,
+    pub listen_port: Option<u32>,
+    pub fwmark: Option<u32>,
+    pub peers: Option<Vec<NmSettingWireGuardPeer>>,
+    _other: HashMap<String, zvariant::OwnedValue>,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingWireGuard {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            private_key: None,
+            listen_port: _from_map!(v, "listen-port", u32::try_from)?,
+            fwmark: _from_map!(v, "fwmark", u32::try_from)?,
+            peers: _from_map!(v, "peers", own_value_to_wireguard_peers)?,
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingWireGuard {
+    fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = &self.private_key {
+            ret.insert("private-key", zvariant::Value::new(v.clone()));
+            ret.insert(
+                "private-key-flags",
+                zvariant::Value::new(NM_SETTING_SECRET_FLAG_NONE),
+            );
+        }
+        if let Some(v) = self.listen_port {
+            ret.insert("listen-port", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.fwmark {
+            ret.insert("fwmark", zvariant::Value::new(v));
+        }
+        if let Some(peers) = &self.peers {
+            let mut peer_values = zvariant::Array::new(
+                zvariant::Signature::from_str_unchecked("a{sv}"),
+            );
+            for peer in peers {
+                peer_values.append(peer.to_value()?)?;
+            }
+            ret.insert("peers", zvariant::Value::Array(peer_values));
+        }
+        ret.extend(self._other.iter().map(|(key, value)| {
+            (key.as_str(), zvariant::Value::from(value.clone()))
+        }));
+        Ok(ret)
+    }
+}
+
+impl NmSettingWireGuard {
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn fill_secrets(&mut self, secrets: &DbusDictionary) {
+        if let Some(v) = secrets.get("private-key") {
+            match String::try_from(v.clone()) {
+                Ok(s) => {
+                    self.private_key = Some(s);
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Failed to convert WireGuard private-key: {:?} {:?}",
+                        v,
+                        e
+                    );
+                }
+            }
+        }
+        if let Some(v) = secrets.get("peers") {
+            match own_value_to_wireguard_peers(v.clone()) {
+                Ok(secret_peers) => {
+                    for peer in self.peers.as_mut().into_iter().flatten() {
+                        if let Some(secret_peer) = secret_peers
+                            .iter()
+                            .find(|p| p.public_key == peer.public_key)
+                        {
+                            peer.preshared_key
+                                .clone_from(&secret_peer.preshared_key);
+                        }
+                    }
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Failed to convert WireGuard peers secrets: \
+                        {:?} {:?}",
+                        v,
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingWireGuardPeer {
+    pub public_key: Option<String>,
+    pub preshared_key: Option<String>,
+    pub endpoint: Option<String>,
+    pub allowed_ips: Option<Vec<String>>,
+    pub persistent_keepalive: Option<u32>,
+    _other: DbusDictionary,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingWireGuardPeer {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            public_key: _from_map!(v, "public-key", String::try_from)?,
+            preshared_key: _from_map!(v, "preshared-key", String::try_from)?,
+            endpoint: _from_map!(v, "endpoint", String::try_from)?,
+            allowed_ips: _from_map!(v, "allowed-ips", Vec::<String>::try_from)?,
+            persistent_keepalive: _from_map!(
+                v,
+                "persistent-keepalive",
+                u32::try_from
+            )?,
+            _other: v,
+        })
+    }
+}
+
+impl NmSettingWireGuardPeer {
+    fn to_value(&self) -> Result<zvariant::Value, NmError> {
+        let mut ret = zvariant::Dict::new(
+            zvariant::Signature::from_str_unchecked("s"),
+            zvariant::Signature::from_str_unchecked("v"),
+        );
+        if let Some(v) = &self.public_key {
+            ret.append(
+                zvariant::Value::new("public-key"),
+                zvariant::Value::new(zvariant::Value::new(v.clone())),
+            )?;
+        }
+        if let Some(v) = &self.preshared_key {
+            ret.append(
+                zvariant::Value::new("preshared-key"),
+                zvariant::Value::new(zvariant::Value::new(v.clone())),
+            )?;
+            ret.append(
+                zvariant::Value::new("preshared-key-flags"),
+                zvariant::Value::new(zvariant::Value::U32(
+                    NM_SETTING_SECRET_FLAG_NONE,
+                )),
+            )?;
+        }
+        if let Some(v) = &self.endpoint {
+            ret.append(
+                zvariant::Value::new("endpoint"),
+                zvariant::Value::new(zvariant::Value::new(v.clone())),
+            )?;
+        }
+        if let Some(v) = &self.allowed_ips {
+            ret.append(
+                zvariant::Value::new("allowed-ips"),
+                zvariant::Value::new(zvariant::Value::new(v.clone())),
+            )?;
+        }
+        if let Some(v) = self.persistent_keepalive {
+            ret.append(
+                zvariant::Value::new("persistent-keepalive"),
+                zvariant::Value::new(zvariant::Value::U32(v)),
+            )?;
+        }
+        Ok(zvariant::Value::Dict(ret))
+    }
+}
+
+fn own_value_to_wireguard_peers(
+    value: zvariant::OwnedValue,
+) -> Result<Vec<NmSettingWireGuardPeer>, NmError> {
+    let mut ret = Vec::new();
+    let raw_peers = Vec::<DbusDictionary>::try_from(value)?;
+    for raw_peer in raw_peers {
+        ret.push(NmSettingWireGuardPeer::try_from(raw_peer)?);
+    }
+    Ok(ret)
+}
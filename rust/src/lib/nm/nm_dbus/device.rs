@@ -344,6 +344,7 @@ pub struct NmDevice {
     pub state: NmDeviceState,
     pub state_reason: NmDeviceStateReason,
     pub is_mac_vtap: bool,
+    pub ip_tunnel_mode: Option<u32>,
     pub obj_path: String,
     pub real: bool,
     pub mac_address: String,
@@ -11,6 +11,11 @@ impl ToKeyfile for NmSettingConnection {}
 
 impl NmConnection {
     pub fn to_keyfile(&self) -> Result<String, NmError> {
+        let wg_peer_sections = self
+            .wireguard
+            .as_ref()
+            .map(|w| w.peers_to_keyfile())
+            .unwrap_or_default();
         let mut sections: Vec<(&str, HashMap<String, zvariant::Value>)> =
             Vec::new();
         if let Some(con_set) = &self.connection {
@@ -94,6 +99,18 @@ impl NmConnection {
                 sections.push(("vpn-secrets", s));
             }
         }
+        if let Some(ip_tunnel) = &self.ip_tunnel {
+            sections.push(("ip-tunnel", ip_tunnel.to_keyfile()?));
+        }
+        if let Some(ip_vlan) = &self.ip_vlan {
+            sections.push(("ipvlan", ip_vlan.to_keyfile()?));
+        }
+        if let Some(wg_cfg) = &self.wireguard {
+            sections.push(("wireguard", wg_cfg.to_keyfile()?));
+            for (name, peer_cfg) in wg_peer_sections.iter() {
+                sections.push((name.as_str(), peer_cfg.clone()));
+            }
+        }
 
         keyfile_sections_to_string(&sections)
     }
@@ -7,6 +7,8 @@ mod ethtool;
 mod ieee8021x;
 mod infiniband;
 mod ip;
+mod ip_tunnel;
+mod ip_vlan;
 mod keyfile;
 mod mac_vlan;
 mod ovs;
@@ -20,5 +22,6 @@ mod vpn;
 mod vrf;
 mod vxlan;
 mod wired;
+mod wireguard;
 
 pub(crate) use keyfile::ToKeyfile;
@@ -35,6 +35,33 @@ impl NmIpRoute {
             if let Some(lock_cwnd) = self.lock_cwnd {
                 write!(opt_string, ",lock-cwnd={}", lock_cwnd).ok();
             }
+            if let Some(src) = self.src.as_ref() {
+                write!(opt_string, ",src={}", src).ok();
+            }
+            if let Some(mtu) = self.mtu {
+                write!(opt_string, ",mtu={}", mtu).ok();
+            }
+            if let Some(lock_mtu) = self.lock_mtu {
+                write!(opt_string, ",lock-mtu={}", lock_mtu).ok();
+            }
+            if let Some(advmss) = self.advmss {
+                write!(opt_string, ",advmss={}", advmss).ok();
+            }
+            if let Some(initcwnd) = self.initcwnd {
+                write!(opt_string, ",initcwnd={}", initcwnd).ok();
+            }
+            if let Some(initrwnd) = self.initrwnd {
+                write!(opt_string, ",initrwnd={}", initrwnd).ok();
+            }
+            if let Some(quickack) = self.quickack {
+                write!(opt_string, ",quickack={}", quickack).ok();
+            }
+            if let Some(onlink) = self.onlink {
+                write!(opt_string, ",onlink={}", onlink).ok();
+            }
+            if let Some(scope) = self.scope {
+                write!(opt_string, ",scope={}", scope).ok();
+            }
             ret.insert("options".to_string(), opt_string);
         }
         ret
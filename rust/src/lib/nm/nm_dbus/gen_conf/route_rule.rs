@@ -51,6 +51,32 @@ impl NmIpRouteRule {
             if let Some(iif) = self.iifname.as_ref() {
                 keys.push(format!("iif {iif}"));
             }
+            if let Some(oif) = self.oifname.as_ref() {
+                keys.push(format!("oif {oif}"));
+            }
+            if let Some(v) = self.tos {
+                keys.push(format!("tos {v:#04x}"));
+            }
+            if let Some(v) = self.ipproto {
+                keys.push(format!("ipproto {v}"));
+            }
+            for (key, start, end) in [
+                ("sport", self.sport_start, self.sport_end),
+                ("dport", self.dport_start, self.dport_end),
+            ] {
+                match (start, end) {
+                    (Some(s), Some(e)) if s != e => {
+                        keys.push(format!("{key} {s}-{e}"))
+                    }
+                    (Some(s), _) => keys.push(format!("{key} {s}")),
+                    _ => (),
+                }
+            }
+            if let (Some(s), Some(e)) =
+                (self.uid_range_start, self.uid_range_end)
+            {
+                keys.push(format!("uidrange {s}-{e}"));
+            }
             if let Some(v) = self.suppress_prefixlength {
                 keys.push(format!("suppress_prefixlength {v}"));
             }
@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use zvariant::Value;
+
+use super::super::{
+    NmError, NmSettingWireGuard, NmSettingWireGuardPeer, ToDbusValue, ToKeyfile,
+};
+
+impl ToKeyfile for NmSettingWireGuard {
+    fn to_keyfile(&self) -> Result<HashMap<String, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        // Peers are stored in dedicated `[wireguard-peer.<public-key>]`
+        // sections
+        for (k, v) in self.to_value()?.drain().filter(|(k, _)| k != &"peers") {
+            ret.insert(k.to_string(), v);
+        }
+        Ok(ret)
+    }
+}
+
+impl NmSettingWireGuard {
+    pub(crate) fn peers_to_keyfile(
+        &self,
+    ) -> Vec<(String, HashMap<String, zvariant::Value>)> {
+        self.peers
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|peer| {
+                peer.public_key.as_ref().map(|public_key| {
+                    (format!("wireguard-peer.{public_key}"), peer.to_keyfile())
+                })
+            })
+            .collect()
+    }
+}
+
+impl NmSettingWireGuardPeer {
+    fn to_keyfile(&self) -> HashMap<String, zvariant::Value> {
+        let mut ret = HashMap::new();
+        if let Some(v) = &self.endpoint {
+            ret.insert("endpoint".to_string(), Value::new(v.clone()));
+        }
+        if let Some(v) = &self.allowed_ips {
+            ret.insert("allowed-ips".to_string(), Value::new(v.clone()));
+        }
+        if let Some(v) = &self.preshared_key {
+            ret.insert("preshared-key".to_string(), Value::new(v.clone()));
+            ret.insert("preshared-key-flags".to_string(), Value::new(0u32));
+        }
+        if let Some(v) = self.persistent_keepalive {
+            ret.insert("persistent-keepalive".to_string(), Value::new(v));
+        }
+        ret
+    }
+}
@@ -0,0 +1,656 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use super::{
+    ErrorKind, NmConnection, NmError, NmIpRoute, NmIpRouteRule,
+    NmIpRouteRuleAction, NmSettingBond, NmSettingBondPort, NmSettingBridge,
+    NmSettingBridgePort, NmSettingConnection, NmSettingIp, NmSettingIpMethod,
+    NmSettingLoopback, NmSettingVlan, NmSettingVrf, NmSettingVxlan,
+    NmSettingWired,
+};
+
+const KEYFILE_LIST_SEPARATOR: char = ';';
+const AF_INET: i32 = 2;
+const AF_INET6: i32 = 10;
+
+const ADDR_GEN_MODE_EUI64: i32 = 0;
+const ADDR_GEN_MODE_STABLE_PRIVACY: i32 = 1;
+const ADDR_GEN_MODE_STABLE_DEFAULT_OR_EUI64: i32 = 2;
+const ADDR_GEN_MODE_STABLE_DEFAULT: i32 = 3;
+
+type KeyfileSection = HashMap<String, String>;
+
+impl NmConnection {
+    /// Parse the content of NetworkManager keyfile. Only the settings and
+    /// properties supported by nmstate are parsed, others are ignored.
+    pub fn from_keyfile(content: &str) -> Result<Self, NmError> {
+        let mut nm_conn = NmConnection::default();
+        for (name, section) in parse_keyfile_sections(content)?.drain(..) {
+            match name.as_str() {
+                "connection" => {
+                    nm_conn.connection = Some(parse_conn_setting(&section)?);
+                }
+                "ethernet" | "802-3-ethernet" => {
+                    nm_conn.wired = Some(parse_wired_setting(&section)?);
+                }
+                "bond" => {
+                    let mut setting = NmSettingBond::default();
+                    setting.options = section;
+                    nm_conn.bond = Some(setting);
+                }
+                "bond-port" => {
+                    nm_conn.bond_port =
+                        Some(parse_bond_port_setting(&section)?);
+                }
+                "bridge" => {
+                    nm_conn.bridge = Some(parse_bridge_setting(&section)?);
+                }
+                "bridge-port" => {
+                    nm_conn.bridge_port =
+                        Some(parse_bridge_port_setting(&section)?);
+                }
+                "vlan" => {
+                    nm_conn.vlan = Some(parse_vlan_setting(&section)?);
+                }
+                "vxlan" => {
+                    nm_conn.vxlan = Some(parse_vxlan_setting(&section)?);
+                }
+                "vrf" => {
+                    let mut setting = NmSettingVrf::default();
+                    setting.table = get_u32(&section, "table")?;
+                    nm_conn.vrf = Some(setting);
+                }
+                "loopback" => {
+                    let mut setting = NmSettingLoopback::default();
+                    setting.mtu = get_u32(&section, "mtu")?;
+                    nm_conn.loopback = Some(setting);
+                }
+                "ipv4" => {
+                    nm_conn.ipv4 = Some(parse_ip_setting(&section, false)?);
+                }
+                "ipv6" => {
+                    nm_conn.ipv6 = Some(parse_ip_setting(&section, true)?);
+                }
+                _ => {
+                    log::debug!("Ignoring unsupported keyfile section {name}");
+                }
+            }
+        }
+        Ok(nm_conn)
+    }
+}
+
+// Return sections in the order of keyfile content.
+fn parse_keyfile_sections(
+    content: &str,
+) -> Result<Vec<(String, KeyfileSection)>, NmError> {
+    let mut ret: Vec<(String, KeyfileSection)> = Vec::new();
+    for (line_num, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) =
+            line.strip_prefix('[').and_then(|l| l.strip_suffix(']'))
+        {
+            ret.push((name.trim().to_string(), HashMap::new()));
+        } else if let Some((key, value)) = line.split_once('=') {
+            if let Some((_, section)) = ret.last_mut() {
+                section.insert(
+                    key.trim().to_string(),
+                    unescape_keyfile_value(value.trim()),
+                );
+            } else {
+                return Err(NmError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Line {}: key {} is not in any section",
+                        line_num + 1,
+                        key.trim()
+                    ),
+                ));
+            }
+        } else {
+            return Err(NmError::new(
+                ErrorKind::InvalidArgument,
+                format!("Line {}: invalid keyfile line {line}", line_num + 1),
+            ));
+        }
+    }
+    Ok(ret)
+}
+
+fn unescape_keyfile_value(value: &str) -> String {
+    let mut ret = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('s') => ret.push(' '),
+                Some('t') => ret.push('\t'),
+                Some('n') => ret.push('\n'),
+                Some(c) => ret.push(c),
+                None => ret.push('\\'),
+            }
+        } else {
+            ret.push(c);
+        }
+    }
+    ret
+}
+
+fn get_str(section: &KeyfileSection, key: &str) -> Option<String> {
+    section
+        .get(key)
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string())
+}
+
+fn get_list(section: &KeyfileSection, key: &str) -> Option<Vec<String>> {
+    section.get(key).map(|v| {
+        v.split(KEYFILE_LIST_SEPARATOR)
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect()
+    })
+}
+
+fn get_bool(
+    section: &KeyfileSection,
+    key: &str,
+) -> Result<Option<bool>, NmError> {
+    match section.get(key).map(|v| v.as_str()) {
+        None => Ok(None),
+        Some("true") | Some("yes") | Some("1") => Ok(Some(true)),
+        Some("false") | Some("no") | Some("0") => Ok(Some(false)),
+        Some(v) => Err(NmError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid boolean value {v} for key {key}"),
+        )),
+    }
+}
+
+fn get_u32(
+    section: &KeyfileSection,
+    key: &str,
+) -> Result<Option<u32>, NmError> {
+    get_num(section, key)
+}
+
+fn get_i32(
+    section: &KeyfileSection,
+    key: &str,
+) -> Result<Option<i32>, NmError> {
+    get_num(section, key)
+}
+
+fn get_num<T>(section: &KeyfileSection, key: &str) -> Result<Option<T>, NmError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::fmt::Display,
+{
+    section.get(key).map(|v| parse_num(key, v)).transpose()
+}
+
+fn parse_num<T>(key: &str, value: &str) -> Result<T, NmError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: std::fmt::Display,
+{
+    value.trim().parse::<T>().map_err(|e| {
+        NmError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid value {value} for key {key}: {e}"),
+        )
+    })
+}
+
+fn parse_conn_setting(
+    section: &KeyfileSection,
+) -> Result<NmSettingConnection, NmError> {
+    let mut setting = NmSettingConnection::default();
+    setting.id = get_str(section, "id");
+    setting.uuid = get_str(section, "uuid");
+    // NetworkManager is using alias `ethernet` in keyfile
+    setting.iface_type = get_str(section, "type").map(|t| {
+        if t == "ethernet" {
+            "802-3-ethernet".to_string()
+        } else {
+            t
+        }
+    });
+    setting.iface_name = get_str(section, "interface-name");
+    setting.controller =
+        get_str(section, "controller").or_else(|| get_str(section, "master"));
+    setting.controller_type = get_str(section, "port-type")
+        .or_else(|| get_str(section, "slave-type"));
+    setting.autoconnect = get_bool(section, "autoconnect")?;
+    let autoconnect_ports = if section.contains_key("autoconnect-ports") {
+        get_i32(section, "autoconnect-ports")?
+    } else {
+        get_i32(section, "autoconnect-slaves")?
+    };
+    setting.autoconnect_ports = match autoconnect_ports {
+        Some(1) => Some(true),
+        Some(0) => Some(false),
+        _ => None,
+    };
+    setting.lldp = match section.get("lldp").map(|v| v.as_str()) {
+        Some("enable-rx") | Some("1") => Some(true),
+        Some("disable") | Some("0") => Some(false),
+        _ => None,
+    };
+    Ok(setting)
+}
+
+fn parse_wired_setting(
+    section: &KeyfileSection,
+) -> Result<NmSettingWired, NmError> {
+    let mut setting = NmSettingWired::default();
+    setting.mac_address = get_str(section, "mac-address");
+    setting.cloned_mac_address = get_str(section, "cloned-mac-address");
+    setting.mtu = get_u32(section, "mtu")?;
+    setting.speed = get_u32(section, "speed")?;
+    setting.duplex = get_str(section, "duplex");
+    setting.auto_negotiate = get_bool(section, "auto-negotiate")?;
+    Ok(setting)
+}
+
+fn parse_bond_port_setting(
+    section: &KeyfileSection,
+) -> Result<NmSettingBondPort, NmError> {
+    let mut setting = NmSettingBondPort::default();
+    setting.priority = get_i32(section, "prio")?;
+    setting.queue_id = get_u32(section, "queue-id")?;
+    Ok(setting)
+}
+
+fn parse_bridge_setting(
+    section: &KeyfileSection,
+) -> Result<NmSettingBridge, NmError> {
+    let mut setting = NmSettingBridge::default();
+    setting.stp = get_bool(section, "stp")?;
+    setting.priority = get_u32(section, "priority")?;
+    setting.forward_delay = get_u32(section, "forward-delay")?;
+    setting.hello_time = get_u32(section, "hello-time")?;
+    setting.max_age = get_u32(section, "max-age")?;
+    setting.ageing_time = get_u32(section, "ageing-time")?;
+    setting.group_forward_mask = get_u32(section, "group-forward-mask")?;
+    setting.multicast_snooping = get_bool(section, "multicast-snooping")?;
+    setting.vlan_filtering = get_bool(section, "vlan-filtering")?;
+    setting.vlan_default_pvid = get_u32(section, "vlan-default-pvid")?;
+    Ok(setting)
+}
+
+fn parse_bridge_port_setting(
+    section: &KeyfileSection,
+) -> Result<NmSettingBridgePort, NmError> {
+    let mut setting = NmSettingBridgePort::default();
+    setting.priority = get_u32(section, "priority")?;
+    setting.path_cost = get_u32(section, "path-cost")?;
+    setting.hairpin_mode = get_bool(section, "hairpin-mode")?;
+    Ok(setting)
+}
+
+fn parse_vlan_setting(
+    section: &KeyfileSection,
+) -> Result<NmSettingVlan, NmError> {
+    let mut setting = NmSettingVlan::default();
+    setting.id = get_u32(section, "id")?;
+    setting.parent = get_str(section, "parent");
+    setting.protocol = get_str(section, "protocol");
+    Ok(setting)
+}
+
+fn parse_vxlan_setting(
+    section: &KeyfileSection,
+) -> Result<NmSettingVxlan, NmError> {
+    let mut setting = NmSettingVxlan::default();
+    setting.id = get_u32(section, "id")?;
+    setting.parent = get_str(section, "parent");
+    setting.local = get_str(section, "local");
+    setting.remote = get_str(section, "remote");
+    setting.dst_port = get_u32(section, "destination-port")?;
+    setting.learning = get_bool(section, "learning")?;
+    Ok(setting)
+}
+
+fn parse_ip_setting(
+    section: &KeyfileSection,
+    is_ipv6: bool,
+) -> Result<NmSettingIp, NmError> {
+    let mut setting = NmSettingIp::default();
+    setting.method = section
+        .get("method")
+        .map(|m| parse_ip_method(m.as_str()))
+        .transpose()?;
+    setting.gateway = get_str(section, "gateway");
+    setting.dns = get_list(section, "dns");
+    setting.dns_search = get_list(section, "dns-search");
+    setting.dns_options = get_list(section, "dns-options");
+    setting.dns_priority = get_i32(section, "dns-priority")?;
+    setting.ignore_auto_dns = get_bool(section, "ignore-auto-dns")?;
+    setting.ignore_auto_routes = get_bool(section, "ignore-auto-routes")?;
+    setting.never_default = get_bool(section, "never-default")?;
+    setting.may_fail = get_bool(section, "may-fail")?;
+    setting.route_table = get_u32(section, "route-table")?;
+    setting.route_metric = get_num(section, "route-metric")?;
+    setting.dhcp_client_id = get_str(section, "dhcp-client-id");
+    setting.dhcp_timeout = get_i32(section, "dhcp-timeout")?;
+    setting.dhcp_send_hostname = get_bool(section, "dhcp-send-hostname")?;
+    setting.dhcp_hostname = get_str(section, "dhcp-hostname");
+    setting.dhcp_fqdn = get_str(section, "dhcp-fqdn");
+    if is_ipv6 {
+        setting.ra_timeout = get_i32(section, "ra-timeout")?;
+        setting.dhcp_duid = get_str(section, "dhcp-duid");
+        setting.dhcp_iaid = get_str(section, "dhcp-iaid");
+        setting.token = get_str(section, "token");
+        setting.addr_gen_mode = section
+            .get("addr-gen-mode")
+            .map(|m| parse_addr_gen_mode(m.as_str()))
+            .transpose()?;
+    }
+
+    // The `addressN`, `routeN` and `routing-ruleN` are sorted by N.
+    let mut addrs: Vec<(u32, &str)> = Vec::new();
+    let mut routes: Vec<(u32, &str)> = Vec::new();
+    let mut rules: Vec<(u32, &str)> = Vec::new();
+    for (key, value) in section.iter() {
+        if let Some(index) = key
+            .strip_prefix("addresses")
+            .or_else(|| key.strip_prefix("address"))
+            .and_then(|i| i.parse::<u32>().ok())
+        {
+            addrs.push((index, value.as_str()));
+        } else if let Some(index) = key
+            .strip_prefix("routes")
+            .or_else(|| key.strip_prefix("route"))
+            .and_then(|i| i.parse::<u32>().ok())
+        {
+            routes.push((index, value.as_str()));
+        } else if let Some(index) = key
+            .strip_prefix("routing-rule")
+            .and_then(|i| i.parse::<u32>().ok())
+        {
+            rules.push((index, value.as_str()));
+        }
+    }
+    addrs.sort_unstable();
+    routes.sort_unstable();
+    rules.sort_unstable();
+
+    for (_, value) in addrs {
+        // Old keyfile might append gateway to address with comma
+        let mut items = value.split(',');
+        if let Some(addr) = items.next() {
+            setting.addresses.push(with_default_prefix(addr, is_ipv6));
+        }
+        if let Some(gw) = items.next().filter(|g| !g.is_empty()) {
+            if setting.gateway.is_none() {
+                setting.gateway = Some(gw.to_string());
+            }
+        }
+    }
+    for (index, value) in routes {
+        let options = section
+            .get(&format!("route{index}_options"))
+            .or_else(|| section.get(&format!("routes{index}_options")));
+        setting.routes.push(parse_route(value, options, is_ipv6)?);
+    }
+    for (_, value) in rules {
+        setting.route_rules.push(parse_route_rule(value, is_ipv6)?);
+    }
+    Ok(setting)
+}
+
+fn parse_ip_method(method: &str) -> Result<NmSettingIpMethod, NmError> {
+    match method {
+        "auto" => Ok(NmSettingIpMethod::Auto),
+        "disabled" => Ok(NmSettingIpMethod::Disabled),
+        "link-local" => Ok(NmSettingIpMethod::LinkLocal),
+        "manual" => Ok(NmSettingIpMethod::Manual),
+        "shared" => Ok(NmSettingIpMethod::Shared),
+        "dhcp" => Ok(NmSettingIpMethod::Dhcp),
+        "ignore" => Ok(NmSettingIpMethod::Ignore),
+        _ => Err(NmError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid IP method {method}"),
+        )),
+    }
+}
+
+fn parse_addr_gen_mode(mode: &str) -> Result<i32, NmError> {
+    match mode {
+        "eui64" => Ok(ADDR_GEN_MODE_EUI64),
+        "stable-privacy" => Ok(ADDR_GEN_MODE_STABLE_PRIVACY),
+        "default-or-eui64" => Ok(ADDR_GEN_MODE_STABLE_DEFAULT_OR_EUI64),
+        "default" => Ok(ADDR_GEN_MODE_STABLE_DEFAULT),
+        _ => parse_num("addr-gen-mode", mode),
+    }
+}
+
+fn with_default_prefix(addr: &str, is_ipv6: bool) -> String {
+    let addr = addr.trim();
+    if addr.contains('/') {
+        addr.to_string()
+    } else if is_ipv6 {
+        format!("{addr}/128")
+    } else {
+        format!("{addr}/32")
+    }
+}
+
+fn split_ip_prefix(
+    value: &str,
+    is_ipv6: bool,
+) -> Result<(String, u32), NmError> {
+    let value = with_default_prefix(value, is_ipv6);
+    if let Some((ip, prefix)) = value.split_once('/') {
+        Ok((ip.to_string(), parse_num("prefix", prefix)?))
+    } else {
+        Err(NmError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid IP address {value}"),
+        ))
+    }
+}
+
+// Format: `dest/prefix[,next_hop[,metric]]` with optional
+// `routeN_options=key=value,...`
+fn parse_route(
+    value: &str,
+    options: Option<&String>,
+    is_ipv6: bool,
+) -> Result<NmIpRoute, NmError> {
+    let mut route = NmIpRoute::default();
+    let mut items = value.split(',').map(|i| i.trim());
+    if let Some(dest) = items.next() {
+        let (ip, prefix) = split_ip_prefix(dest, is_ipv6)?;
+        route.dest = Some(ip);
+        route.prefix = Some(prefix);
+    }
+    route.next_hop = items
+        .next()
+        .filter(|n| !n.is_empty() && *n != "0.0.0.0" && *n != "::")
+        .map(|n| n.to_string());
+    route.metric = items
+        .next()
+        .filter(|m| !m.is_empty())
+        .map(|m| parse_num("route metric", m))
+        .transpose()?;
+
+    for option in options
+        .map(|o| o.as_str())
+        .unwrap_or_default()
+        .split(',')
+        .filter(|o| !o.is_empty())
+    {
+        match option.split_once('=') {
+            Some(("table", v)) => route.table = Some(parse_num("table", v)?),
+            Some(("weight", v)) => route.weight = Some(parse_num("weight", v)?),
+            Some(("type", v)) => route.route_type = Some(v.to_string()),
+            Some(("cwnd", v)) => route.cwnd = Some(parse_num("cwnd", v)?),
+            Some(("lock-cwnd", v)) => {
+                route.lock_cwnd = Some(v == "true" || v == "1")
+            }
+            Some(("src", v)) => route.src = Some(v.to_string()),
+            Some(("mtu", v)) => route.mtu = Some(parse_num("mtu", v)?),
+            Some(("lock-mtu", v)) => {
+                route.lock_mtu = Some(v == "true" || v == "1")
+            }
+            Some(("advmss", v)) => route.advmss = Some(parse_num("advmss", v)?),
+            Some(("initcwnd", v)) => {
+                route.initcwnd = Some(parse_num("initcwnd", v)?)
+            }
+            Some(("initrwnd", v)) => {
+                route.initrwnd = Some(parse_num("initrwnd", v)?)
+            }
+            Some(("quickack", v)) => {
+                route.quickack = Some(v == "true" || v == "1")
+            }
+            Some(("onlink", v)) => route.onlink = Some(v == "true" || v == "1"),
+            Some(("scope", v)) => route.scope = Some(parse_num("scope", v)?),
+            _ => {
+                log::debug!("Ignoring unsupported route option {option}");
+            }
+        }
+    }
+    Ok(route)
+}
+
+// Format: `priority N [from IP/LEN] [to IP/LEN] [iif NAME] [oif NAME]
+// [tos TOS] [ipproto N] [sport N[-M]] [dport N[-M]] [uidrange N-M]
+// [table N] [fwmark MARK[/MASK]] [suppress_prefixlength N] [type ACTION]`
+fn parse_route_rule(
+    value: &str,
+    is_ipv6: bool,
+) -> Result<NmIpRouteRule, NmError> {
+    let mut rule = NmIpRouteRule::default();
+    rule.family = Some(if is_ipv6 { AF_INET6 } else { AF_INET });
+    let tokens: Vec<&str> = value.split_whitespace().collect();
+    let mut tokens = tokens.as_slice();
+    while let [key, value, remains @ ..] = tokens {
+        match *key {
+            "priority" | "prio" => {
+                rule.priority = Some(parse_num("priority", value)?);
+            }
+            "from" => {
+                let (ip, prefix) = split_ip_prefix(value, is_ipv6)?;
+                // `from 0.0.0.0/0` is used as placeholder by NetworkManager
+                if prefix != 0 {
+                    rule.from = Some(ip);
+                    rule.from_len = Some(prefix as u8);
+                }
+            }
+            "to" => {
+                let (ip, prefix) = split_ip_prefix(value, is_ipv6)?;
+                rule.to = Some(ip);
+                rule.to_len = Some(prefix as u8);
+            }
+            "table" | "lookup" => {
+                rule.table = Some(parse_num("table", value)?);
+            }
+            "iif" => {
+                rule.iifname = Some(value.to_string());
+            }
+            "oif" => {
+                rule.oifname = Some(value.to_string());
+            }
+            "tos" | "dsfield" => {
+                let tos = parse_hex_or_dec("tos", value)?;
+                rule.tos = Some(u8::try_from(tos).map_err(|e| {
+                    NmError::new(
+                        ErrorKind::InvalidArgument,
+                        format!("Invalid value {value} for key tos: {e}"),
+                    )
+                })?);
+            }
+            "ipproto" => {
+                rule.ipproto = Some(parse_num("ipproto", value)?);
+            }
+            "sport" => {
+                let (start, end) = parse_range("sport", value)?;
+                rule.sport_start = Some(start);
+                rule.sport_end = Some(end);
+            }
+            "dport" => {
+                let (start, end) = parse_range("dport", value)?;
+                rule.dport_start = Some(start);
+                rule.dport_end = Some(end);
+            }
+            "uidrange" => {
+                let (start, end) = parse_range("uidrange", value)?;
+                rule.uid_range_start = Some(start);
+                rule.uid_range_end = Some(end);
+            }
+            "fwmark" => {
+                let (mark, mask) = match value.split_once('/') {
+                    Some((mark, mask)) => (mark, Some(mask)),
+                    None => (*value, None),
+                };
+                rule.fw_mark = Some(parse_hex_or_dec("fwmark", mark)?);
+                rule.fw_mask =
+                    mask.map(|m| parse_hex_or_dec("fwmask", m)).transpose()?;
+            }
+            "suppress_prefixlength" => {
+                rule.suppress_prefixlength =
+                    Some(parse_num("suppress_prefixlength", value)?);
+            }
+            "type" => {
+                rule.action = Some(match *value {
+                    "blackhole" => NmIpRouteRuleAction::Blackhole,
+                    "unreachable" => NmIpRouteRuleAction::Unreachable,
+                    "prohibit" => NmIpRouteRuleAction::Prohibit,
+                    v => NmIpRouteRuleAction::from(parse_num::<u8>("type", v)?),
+                });
+            }
+            _ => {
+                return Err(NmError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Unsupported route rule property {key} in {value}"),
+                ));
+            }
+        }
+        tokens = remains;
+    }
+    if let [key] = tokens {
+        return Err(NmError::new(
+            ErrorKind::InvalidArgument,
+            format!("Route rule property {key} has no value"),
+        ));
+    }
+    Ok(rule)
+}
+
+// Parse `N` or `N-M`
+fn parse_range<T>(key: &str, value: &str) -> Result<(T, T), NmError>
+where
+    T: FromStr + Copy,
+    <T as FromStr>::Err: std::fmt::Display,
+{
+    match value.split_once('-') {
+        Some((start, end)) => {
+            Ok((parse_num(key, start)?, parse_num(key, end)?))
+        }
+        None => {
+            let v = parse_num(key, value)?;
+            Ok((v, v))
+        }
+    }
+}
+
+fn parse_hex_or_dec(key: &str, value: &str) -> Result<u32, NmError> {
+    if let Some(hex) = value.strip_prefix("0x") {
+        u32::from_str_radix(hex, 16).map_err(|e| {
+            NmError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid value {value} for key {key}: {e}"),
+            )
+        })
+    } else {
+        parse_num(key, value)
+    }
+}
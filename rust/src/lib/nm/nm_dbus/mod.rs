@@ -12,8 +12,12 @@ mod device;
 mod dns;
 mod error;
 #[cfg(feature = "query_apply")]
+mod keyfile;
+#[cfg(feature = "query_apply")]
 mod lldp;
 #[cfg(feature = "query_apply")]
+mod monitor;
+#[cfg(feature = "query_apply")]
 mod nm_api;
 
 #[cfg(feature = "gen_conf")]
@@ -29,12 +33,13 @@ pub use self::connection::{
     NmSetting8021X, NmSettingBond, NmSettingBondPort, NmSettingBridge,
     NmSettingBridgePort, NmSettingBridgeVlanRange, NmSettingConnection,
     NmSettingEthtool, NmSettingInfiniBand, NmSettingIp, NmSettingIpMethod,
-    NmSettingLoopback, NmSettingMacSec, NmSettingMacVlan, NmSettingOvsBridge,
-    NmSettingOvsDpdk, NmSettingOvsExtIds, NmSettingOvsIface,
-    NmSettingOvsOtherConfig, NmSettingOvsPatch, NmSettingOvsPort,
-    NmSettingSriov, NmSettingSriovVf, NmSettingSriovVfVlan, NmSettingUser,
-    NmSettingVeth, NmSettingVlan, NmSettingVlanFlag, NmSettingVpn,
-    NmSettingVrf, NmSettingVxlan, NmSettingWired, NmSettingsConnectionFlag,
+    NmSettingIpTunnel, NmSettingIpVlan, NmSettingLoopback, NmSettingMacSec,
+    NmSettingMacVlan, NmSettingOvsBridge, NmSettingOvsDpdk, NmSettingOvsExtIds,
+    NmSettingOvsIface, NmSettingOvsOtherConfig, NmSettingOvsPatch,
+    NmSettingOvsPort, NmSettingSriov, NmSettingSriovVf, NmSettingSriovVfVlan,
+    NmSettingUser, NmSettingVeth, NmSettingVlan, NmSettingVlanFlag,
+    NmSettingVpn, NmSettingVrf, NmSettingVxlan, NmSettingWireGuard,
+    NmSettingWireGuardPeer, NmSettingWired, NmSettingsConnectionFlag,
     NmVlanProtocol,
 };
 pub use self::device::{NmDevice, NmDeviceState, NmDeviceStateReason};
@@ -51,6 +56,10 @@ pub use self::lldp::{
     NmLldpNeighborMgmtAddr,
 };
 #[cfg(feature = "query_apply")]
+pub use self::monitor::{NmChange, NmSignalMonitor};
+#[cfg(all(test, feature = "query_apply"))]
+pub(crate) use self::monitor::{nm_props_changed_targets, NmSignalTarget};
+#[cfg(feature = "query_apply")]
 pub use self::nm_api::NmApi;
 
 pub(crate) use self::convert::ToDbusValue;
@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use super::{
+    connection::nm_con_get_from_obj_path, query_apply::device::nm_dev_name_get,
+    NmError,
+};
+
+const NM_SIGNAL_MATCH_RULES: [&str; 3] = [
+    "type='signal',sender='org.freedesktop.NetworkManager',\
+    interface='org.freedesktop.DBus.Properties',member='PropertiesChanged'",
+    "type='signal',sender='org.freedesktop.NetworkManager',\
+    interface='org.freedesktop.NetworkManager.Settings'",
+    "type='signal',sender='org.freedesktop.NetworkManager',\
+    interface='org.freedesktop.NetworkManager.Settings.Connection',\
+    member='Updated'",
+];
+
+const NM_DBUS_PATH_ROOT: &str = "/org/freedesktop/NetworkManager";
+const NM_DBUS_PATH_DEVICES: &str = "/org/freedesktop/NetworkManager/Devices/";
+const NM_DBUS_PATH_DNS_MANAGER: &str =
+    "/org/freedesktop/NetworkManager/DnsManager";
+const NM_DBUS_PATH_SETTINGS: &str = "/org/freedesktop/NetworkManager/Settings";
+
+const NM_DBUS_INTERFACE_SETTINGS: &str =
+    "org.freedesktop.NetworkManager.Settings";
+const NM_DBUS_INTERFACE_SETTINGS_CONNECTION: &str =
+    "org.freedesktop.NetworkManager.Settings.Connection";
+const NM_DBUS_INTERFACE_DEVICE_STATISTICS: &str =
+    "org.freedesktop.NetworkManager.Device.Statistics";
+
+/// Change notified by NetworkManager D-Bus signals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NmChange {
+    /// Device or profile of specified interface changed.
+    Iface(String),
+    /// Device or profile changed, but not able to tell which interface.
+    Ifaces,
+    Dns,
+    Hostname,
+}
+
+// The NetworkManager object a signal is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NmSignalTarget {
+    Device(String),
+    Connection(String),
+    Change(NmChange),
+}
+
+/// Subscriber of NetworkManager D-Bus signals about devices, connection
+/// profiles, DNS and hostname.
+pub struct NmSignalMonitor {
+    connection: zbus::Connection,
+}
+
+impl NmSignalMonitor {
+    pub fn new() -> Result<Self, NmError> {
+        let connection = zbus::Connection::new_system()?;
+        let proxy = zbus::fdo::DBusProxy::new(&connection)?;
+        for rule in NM_SIGNAL_MATCH_RULES {
+            proxy.add_match(rule)?;
+        }
+        Ok(Self { connection })
+    }
+
+    /// Block till next NetworkManager D-Bus signal we are interested in.
+    pub fn wait_changes(&self) -> Result<Vec<NmChange>, NmError> {
+        loop {
+            let msg = self.connection.receive_message()?;
+            let header = match msg.header() {
+                Ok(h) => h,
+                Err(e) => {
+                    log::debug!("Ignoring D-Bus message with bad header {e}");
+                    continue;
+                }
+            };
+            if header.message_type().ok() != Some(zbus::MessageType::Signal) {
+                continue;
+            }
+            let obj_path = header
+                .path()
+                .ok()
+                .flatten()
+                .map(|p| p.as_str().to_string())
+                .unwrap_or_default();
+            let interface =
+                header.interface().ok().flatten().unwrap_or_default();
+            let member = header.member().ok().flatten().unwrap_or_default();
+            let targets = match (interface, member) {
+                ("org.freedesktop.DBus.Properties", "PropertiesChanged") => {
+                    match msg.body::<(
+                        String,
+                        HashMap<String, zvariant::OwnedValue>,
+                        Vec<String>,
+                    )>() {
+                        Ok((dbus_iface, changed, invalidated)) => {
+                            let prop_names: Vec<&str> = changed
+                                .keys()
+                                .chain(invalidated.iter())
+                                .map(|k| k.as_str())
+                                .collect();
+                            nm_props_changed_targets(
+                                &obj_path,
+                                &dbus_iface,
+                                &prop_names,
+                            )
+                        }
+                        Err(e) => {
+                            log::debug!(
+                                "Ignoring PropertiesChanged signal of \
+                                {obj_path}: {e}"
+                            );
+                            Vec::new()
+                        }
+                    }
+                }
+                (NM_DBUS_INTERFACE_SETTINGS, "NewConnection") => {
+                    match msg.body::<zvariant::OwnedObjectPath>() {
+                        Ok(p) => vec![NmSignalTarget::Connection(
+                            p.as_str().to_string(),
+                        )],
+                        Err(_) => {
+                            vec![NmSignalTarget::Change(NmChange::Ifaces)]
+                        }
+                    }
+                }
+                (NM_DBUS_INTERFACE_SETTINGS, "ConnectionRemoved") => {
+                    vec![NmSignalTarget::Change(NmChange::Ifaces)]
+                }
+                (NM_DBUS_INTERFACE_SETTINGS_CONNECTION, "Updated") => {
+                    vec![NmSignalTarget::Connection(obj_path)]
+                }
+                _ => Vec::new(),
+            };
+            if !targets.is_empty() {
+                return Ok(targets
+                    .into_iter()
+                    .map(|target| self.resolve(target))
+                    .collect());
+            }
+        }
+    }
+
+    // The object might be already removed, in that case we cannot tell
+    // which interface changed.
+    fn resolve(&self, target: NmSignalTarget) -> NmChange {
+        match target {
+            NmSignalTarget::Device(obj_path) => {
+                match nm_dev_name_get(&self.connection, &obj_path) {
+                    Ok(name) => NmChange::Iface(name),
+                    Err(e) => {
+                        log::debug!("Failed to resolve NM device: {e}");
+                        NmChange::Ifaces
+                    }
+                }
+            }
+            NmSignalTarget::Connection(obj_path) => {
+                match nm_con_get_from_obj_path(&self.connection, &obj_path) {
+                    Ok(nm_conn) => match nm_conn.iface_name() {
+                        Some(name) => NmChange::Iface(name.to_string()),
+                        None => NmChange::Ifaces,
+                    },
+                    Err(e) => {
+                        log::debug!("Failed to resolve NM connection: {e}");
+                        NmChange::Ifaces
+                    }
+                }
+            }
+            NmSignalTarget::Change(change) => change,
+        }
+    }
+}
+
+// Changes of IP config, DHCP config and active connection objects are
+// ignored as they are also notified by kernel or device properties.
+pub(crate) fn nm_props_changed_targets(
+    obj_path: &str,
+    dbus_iface: &str,
+    prop_names: &[&str],
+) -> Vec<NmSignalTarget> {
+    let mut ret = Vec::new();
+    if obj_path.starts_with(NM_DBUS_PATH_DEVICES) {
+        if dbus_iface != NM_DBUS_INTERFACE_DEVICE_STATISTICS {
+            ret.push(NmSignalTarget::Device(obj_path.to_string()));
+        }
+    } else if obj_path == NM_DBUS_PATH_DNS_MANAGER {
+        ret.push(NmSignalTarget::Change(NmChange::Dns));
+    } else if obj_path == NM_DBUS_PATH_SETTINGS {
+        if prop_names.contains(&"Hostname") {
+            ret.push(NmSignalTarget::Change(NmChange::Hostname));
+        }
+    } else if obj_path == NM_DBUS_PATH_ROOT {
+        if prop_names.contains(&"GlobalDnsConfiguration") {
+            ret.push(NmSignalTarget::Change(NmChange::Dns));
+        }
+        if prop_names.contains(&"Devices") || prop_names.contains(&"AllDevices")
+        {
+            ret.push(NmSignalTarget::Change(NmChange::Ifaces));
+        }
+    }
+    ret
+}
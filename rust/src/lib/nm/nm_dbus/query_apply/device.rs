@@ -42,8 +42,9 @@ const NM_DEVICE_TYPE_WIREGUARD: u32 = 29;
 const NM_DEVICE_TYPE_WIFI_P2P: u32 = 30;
 const NM_DEVICE_TYPE_VRF: u32 = 31;
 const NM_DEVICE_TYPE_LOOPBACK: u32 = 32;
+const NM_DEVICE_TYPE_IPVLAN: u32 = 34;
 
-fn nm_dev_name_get(
+pub(crate) fn nm_dev_name_get(
     dbus_conn: &zbus::Connection,
     obj_path: &str,
 ) -> Result<String, NmError> {
@@ -108,6 +109,7 @@ fn nm_dev_iface_type_get(
             NM_DEVICE_TYPE_WIFI_P2P => "wifi-p2p".to_string(),
             NM_DEVICE_TYPE_VRF => "vrf".to_string(),
             NM_DEVICE_TYPE_LOOPBACK => "loopback".to_string(),
+            NM_DEVICE_TYPE_IPVLAN => "ipvlan".to_string(),
             _ => format!("unknown({i})"),
         }),
         Err(e) => Err(NmError::new(
@@ -160,6 +162,28 @@ fn nm_dev_is_mac_vtap_get(
     }
 }
 
+fn nm_dev_ip_tunnel_mode_get(
+    dbus_conn: &zbus::Connection,
+    obj_path: &str,
+) -> Result<u32, NmError> {
+    let dbus_iface = format!("{NM_DBUS_INTERFACE_DEV}.IPTunnel");
+    let proxy = zbus::Proxy::new(
+        dbus_conn,
+        NM_DBUS_INTERFACE_ROOT,
+        obj_path,
+        &dbus_iface,
+    )?;
+    match proxy.get_property::<u32>("Mode") {
+        Ok(v) => Ok(v),
+        Err(e) => Err(NmError::new(
+            ErrorKind::Bug,
+            format!(
+                "Failed to retrieve IPTunnel.Mode of device {obj_path}: {e}"
+            ),
+        )),
+    }
+}
+
 fn nm_dev_real_get(
     dbus_conn: &zbus::Connection,
     obj_path: &str,
@@ -192,12 +216,17 @@ pub(crate) fn nm_dev_from_obj_path(
         state_reason,
         obj_path: obj_path.to_string(),
         is_mac_vtap: false,
+        ip_tunnel_mode: None,
         real,
         mac_address: nm_dev_get_mac_address(dbus_conn, obj_path)?,
     };
     if dev.iface_type == "macvlan" {
         dev.is_mac_vtap = nm_dev_is_mac_vtap_get(dbus_conn, obj_path)?;
     }
+    if dev.iface_type == "ip-tunnel" {
+        dev.ip_tunnel_mode =
+            Some(nm_dev_ip_tunnel_mode_get(dbus_conn, obj_path)?);
+    }
     Ok(dev)
 }
 
@@ -9,7 +9,7 @@ use super::super::{
         store_dns_search_or_option_to_iface,
     },
     error::nm_error_to_nmstate,
-    nm_dbus::{NmApi, NmConnection},
+    nm_dbus::{NmActiveConnection, NmApi, NmConnection, NmDevice},
     profile::{perpare_nm_conns, PerparedNmConnections},
     query_apply::{
         activate_nm_profiles, create_index_for_nm_conns_by_name_type,
@@ -19,8 +19,9 @@ use super::super::{
             is_iface_dns_desired, purge_global_dns_config,
             store_dns_config_via_global_api,
         },
-        is_mptcp_flags_changed, is_route_removed, is_veth_peer_changed,
-        is_vlan_changed, is_vrf_table_id_changed, is_vxlan_changed,
+        get_exist_profiles_to_delete, is_mptcp_flags_changed, is_route_removed,
+        is_veth_peer_changed, is_vlan_changed, is_vrf_table_id_changed,
+        is_vxlan_changed,
         profile::is_uuid,
         save_nm_profiles,
         vpn::get_match_ipsec_nm_conn,
@@ -35,8 +36,8 @@ use super::super::{
 };
 
 use crate::{
-    InterfaceIdentifier, InterfaceType, MergedInterfaces, MergedNetworkState,
-    NmstateError,
+    ApplyReport, InterfaceIdentifier, InterfaceType, MergedInterfaces,
+    MergedNetworkState, NmProfileReport, NmstateError,
 };
 
 // There is plan to simply the `add_net_state`, `chg_net_state`, `del_net_state`
@@ -87,48 +88,8 @@ pub(crate) fn nm_apply(
 
     store_route_rule_config(&mut merged_state)?;
 
-    if merged_state.dns.is_changed()
-        || !cur_dns_ifaces_still_valid_for_dns(&merged_state.interfaces)
-    {
-        purge_global_dns_config(&mut nm_api)?;
+    store_dns_config(Some(&mut nm_api), &mut merged_state, &nm_acs, &nm_devs)?;
 
-        if merged_state.dns.is_search_or_option_only() {
-            // When user desire static DNS search and dynamic DNS nameserver,
-            // we cannot use global DNS in this case because global DNS suppress
-            // DNS nameserver learn from DHCP/autoconf.
-            store_dns_search_or_option_to_iface(
-                &mut merged_state,
-                &nm_acs,
-                &nm_devs,
-            )?;
-        } else if is_iface_dns_desired(&merged_state) {
-            if let Err(e) =
-                store_dns_config_to_iface(&mut merged_state, &nm_acs, &nm_devs)
-            {
-                log::info!(
-                    "Cannot store DNS to interface profile: {e}, \
-                    will try to set via global DNS"
-                );
-                store_dns_config_via_global_api(
-                    &mut nm_api,
-                    merged_state.dns.servers.as_slice(),
-                    merged_state.dns.searches.as_slice(),
-                    merged_state.dns.options.as_slice(),
-                )?;
-            }
-        } else if merged_state.dns.is_purge() {
-            // Also need to purge interface level DNS
-            store_dns_config_to_iface(&mut merged_state, &nm_acs, &nm_devs)
-                .ok();
-        } else {
-            store_dns_config_via_global_api(
-                &mut nm_api,
-                merged_state.dns.servers.as_slice(),
-                merged_state.dns.searches.as_slice(),
-                merged_state.dns.options.as_slice(),
-            )?;
-        }
-    }
     let PerparedNmConnections {
         to_store: nm_conns_to_store,
         to_activate: nm_conns_to_activate,
@@ -190,14 +151,223 @@ pub(crate) fn nm_apply(
     Ok(())
 }
 
+// Fill `report` with the NM profile changes `nm_apply()` would perform
+// without changing anything in NetworkManager.
+pub(crate) fn nm_apply_dry_run(
+    merged_state: &MergedNetworkState,
+    report: &mut ApplyReport,
+) -> Result<(), NmstateError> {
+    let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+
+    check_nm_version(&nm_api);
+
+    let exist_nm_conns =
+        nm_api.connections_get().map_err(nm_error_to_nmstate)?;
+    let nm_acs = nm_api
+        .active_connections_get()
+        .map_err(nm_error_to_nmstate)?;
+    let nm_devs = nm_api.devices_get().map_err(nm_error_to_nmstate)?;
+
+    gen_nm_apply_report(
+        merged_state,
+        exist_nm_conns.as_slice(),
+        nm_acs.as_slice(),
+        nm_devs.as_slice(),
+        report,
+    )
+}
+
+// Classify the NM profiles changes against the existing profiles, active
+// connections and devices retrieved from NetworkManager.
+pub(crate) fn gen_nm_apply_report(
+    merged_state: &MergedNetworkState,
+    exist_nm_conns: &[NmConnection],
+    nm_acs: &[NmActiveConnection],
+    nm_devs: &[NmDevice],
+    report: &mut ApplyReport,
+) -> Result<(), NmstateError> {
+    let mut nm_conns_to_delete: Vec<&NmConnection> = Vec::new();
+    if !merged_state.memory_only {
+        let uuids = get_absent_iface_nm_conn_uuids(
+            merged_state,
+            exist_nm_conns,
+        )?;
+        nm_conns_to_delete.extend(
+            exist_nm_conns
+                .iter()
+                .filter(|c| c.uuid().map(|u| uuids.contains(u)) == Some(true)),
+        );
+    }
+
+    let mut merged_state = merged_state.clone();
+
+    store_route_config(&mut merged_state)?;
+
+    store_route_rule_config(&mut merged_state)?;
+
+    store_dns_config(None, &mut merged_state, nm_acs, nm_devs)?;
+
+    let PerparedNmConnections {
+        to_store: nm_conns_to_store,
+        to_activate: nm_conns_to_activate,
+        to_deactivate: nm_conns_to_deactivate,
+    } = perpare_nm_conns(
+        &merged_state,
+        exist_nm_conns,
+        nm_acs,
+        false,
+    )?;
+
+    if !merged_state.memory_only {
+        for nm_conn in get_exist_profiles_to_delete(
+            exist_nm_conns,
+            nm_conns_to_store.as_slice(),
+        ) {
+            if !nm_conns_to_delete
+                .iter()
+                .any(|c| c.uuid() == nm_conn.uuid())
+            {
+                nm_conns_to_delete.push(nm_conn);
+            }
+        }
+    }
+
+    let nm_ac_uuids: Vec<&str> =
+        nm_acs.iter().map(|nm_ac| &nm_ac.uuid as &str).collect();
+    let activated_nm_conns: Vec<&NmConnection> = exist_nm_conns
+        .iter()
+        .filter(|c| {
+            if let Some(uuid) = c.uuid() {
+                nm_ac_uuids.contains(&uuid)
+            } else {
+                false
+            }
+        })
+        .collect();
+    let mut nm_conns_to_deactivate_first = gen_nm_conn_need_to_deactivate_first(
+        &merged_state.interfaces,
+        nm_conns_to_activate.as_slice(),
+        activated_nm_conns.as_slice(),
+    );
+    for nm_conn in nm_conns_to_deactivate {
+        if !nm_conns_to_deactivate_first
+            .iter()
+            .any(|c| c.uuid() == nm_conn.uuid())
+        {
+            nm_conns_to_deactivate_first.push(nm_conn);
+        }
+    }
+
+    for nm_conn in nm_conns_to_store.iter() {
+        if nm_conn.obj_path.is_empty() {
+            report.nm_profiles_to_add.push(nm_conn.into());
+        } else {
+            report.nm_profiles_to_modify.push(nm_conn.into());
+        }
+    }
+    report.nm_profiles_to_activate = nm_conns_to_activate
+        .iter()
+        .map(NmProfileReport::from)
+        .collect();
+    report.nm_profiles_to_deactivate = nm_conns_to_deactivate_first
+        .iter()
+        .map(NmProfileReport::from)
+        .collect();
+    report.nm_profiles_to_delete = nm_conns_to_delete
+        .into_iter()
+        .map(NmProfileReport::from)
+        .collect();
+    Ok(())
+}
+
+impl From<&NmConnection> for NmProfileReport {
+    fn from(nm_conn: &NmConnection) -> Self {
+        Self {
+            id: nm_conn.id().map(|s| s.to_string()),
+            uuid: nm_conn.uuid().map(|s| s.to_string()),
+            interface_name: nm_conn.iface_name().map(|s| s.to_string()),
+            interface_type: nm_conn.iface_type().map(|s| s.to_string()),
+        }
+    }
+}
+
+// The global DNS configuration is untouched when `nm_api` is None.
+fn store_dns_config(
+    mut nm_api: Option<&mut NmApi>,
+    merged_state: &mut MergedNetworkState,
+    nm_acs: &[NmActiveConnection],
+    nm_devs: &[NmDevice],
+) -> Result<(), NmstateError> {
+    if merged_state.dns.is_changed()
+        || !cur_dns_ifaces_still_valid_for_dns(&merged_state.interfaces)
+    {
+        if let Some(nm_api) = nm_api.as_deref_mut() {
+            purge_global_dns_config(nm_api)?;
+        }
+
+        if merged_state.dns.is_search_or_option_only() {
+            // When user desire static DNS search and dynamic DNS nameserver,
+            // we cannot use global DNS in this case because global DNS suppress
+            // DNS nameserver learn from DHCP/autoconf.
+            store_dns_search_or_option_to_iface(merged_state, nm_acs, nm_devs)?;
+        } else if is_iface_dns_desired(merged_state) {
+            if let Err(e) =
+                store_dns_config_to_iface(merged_state, nm_acs, nm_devs)
+            {
+                log::info!(
+                    "Cannot store DNS to interface profile: {e}, \
+                    will try to set via global DNS"
+                );
+                if let Some(nm_api) = nm_api {
+                    store_dns_config_via_global_api(
+                        nm_api,
+                        merged_state.dns.servers.as_slice(),
+                        merged_state.dns.searches.as_slice(),
+                        merged_state.dns.options.as_slice(),
+                    )?;
+                }
+            }
+        } else if merged_state.dns.is_purge() {
+            // Also need to purge interface level DNS
+            store_dns_config_to_iface(merged_state, nm_acs, nm_devs).ok();
+        } else if let Some(nm_api) = nm_api {
+            store_dns_config_via_global_api(
+                nm_api,
+                merged_state.dns.servers.as_slice(),
+                merged_state.dns.searches.as_slice(),
+                merged_state.dns.options.as_slice(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
 fn delete_ifaces(
     nm_api: &mut NmApi,
     merged_state: &MergedNetworkState,
 ) -> Result<(), NmstateError> {
     let all_nm_conns = nm_api.connections_get().map_err(nm_error_to_nmstate)?;
+    let uuids_to_delete =
+        get_absent_iface_nm_conn_uuids(merged_state, &all_nm_conns)?;
 
+    for uuid in &uuids_to_delete {
+        nm_api
+            .connection_delete(uuid)
+            .map_err(nm_error_to_nmstate)?;
+    }
+
+    delete_orphan_ports(nm_api, &uuids_to_delete)?;
+    delete_remain_virtual_interface_as_desired(nm_api, merged_state)?;
+    Ok(())
+}
+
+// The UUIDs of NM connections should be deleted for absent interfaces
+fn get_absent_iface_nm_conn_uuids<'a>(
+    merged_state: &MergedNetworkState,
+    all_nm_conns: &'a [NmConnection],
+) -> Result<HashSet<&'a str>, NmstateError> {
     let nm_conns_name_type_index =
-        create_index_for_nm_conns_by_name_type(&all_nm_conns);
+        create_index_for_nm_conns_by_name_type(all_nm_conns);
     let mut uuids_to_delete: HashSet<&str> = HashSet::new();
 
     for merged_iface in merged_state
@@ -208,8 +378,7 @@ fn delete_ifaces(
         let iface = &merged_iface.merged;
 
         if iface.iface_type() == InterfaceType::Ipsec {
-            for nm_conn in get_match_ipsec_nm_conn(iface.name(), &all_nm_conns)
-            {
+            for nm_conn in get_match_ipsec_nm_conn(iface.name(), all_nm_conns) {
                 if let Some(uuid) = nm_conn.uuid() {
                     uuids_to_delete.insert(uuid);
                 }
@@ -222,7 +391,6 @@ fn delete_ifaces(
         let mut nm_conns_to_delete: Vec<&NmConnection> =
             if iface.iface_type() == InterfaceType::Unknown {
                 all_nm_conns
-                    .as_slice()
                     .iter()
                     .filter(|c| c.iface_name() == Some(iface.name()))
                     .collect()
@@ -240,7 +408,7 @@ fn delete_ifaces(
                 && cur_iface.base_iface().profile_name.as_deref()
                     == Some(iface.name())
             {
-                for nm_conn in &all_nm_conns {
+                for nm_conn in all_nm_conns {
                     if nm_conn.id() == Some(iface.name()) {
                         nm_conns_to_delete.push(nm_conn);
                     }
@@ -254,7 +422,7 @@ fn delete_ifaces(
                 && cur_iface.base_iface().name.as_str() == iface.name()
             {
                 if let Some(mac) = cur_iface.base_iface().mac_address.as_ref() {
-                    for nm_conn in &all_nm_conns {
+                    for nm_conn in all_nm_conns {
                         if nm_conn
                             .wired
                             .as_ref()
@@ -318,15 +486,7 @@ fn delete_ifaces(
         }
     }
 
-    for uuid in &uuids_to_delete {
-        nm_api
-            .connection_delete(uuid)
-            .map_err(nm_error_to_nmstate)?;
-    }
-
-    delete_orphan_ports(nm_api, &uuids_to_delete)?;
-    delete_remain_virtual_interface_as_desired(nm_api, merged_state)?;
-    Ok(())
+    Ok(uuids_to_delete)
 }
 
 fn delete_remain_virtual_interface_as_desired(
@@ -3,20 +3,36 @@
 use crate::{
     nm::nm_dbus::NmDevice,
     nm::settings::{
-        NM_SETTING_BOND_SETTING_NAME, NM_SETTING_BRIDGE_SETTING_NAME,
-        NM_SETTING_DUMMY_SETTING_NAME, NM_SETTING_HSR_SETTING_NAME,
-        NM_SETTING_INFINIBAND_SETTING_NAME, NM_SETTING_LOOPBACK_SETTING_NAME,
-        NM_SETTING_MACSEC_SETTING_NAME, NM_SETTING_MACVLAN_SETTING_NAME,
-        NM_SETTING_OVS_BRIDGE_SETTING_NAME, NM_SETTING_OVS_IFACE_SETTING_NAME,
-        NM_SETTING_VETH_SETTING_NAME, NM_SETTING_VLAN_SETTING_NAME,
-        NM_SETTING_VRF_SETTING_NAME, NM_SETTING_VXLAN_SETTING_NAME,
-        NM_SETTING_WIRED_SETTING_NAME,
+        nm_ip_tunnel_mode_to_nmstate, NM_SETTING_BOND_SETTING_NAME,
+        NM_SETTING_BRIDGE_SETTING_NAME, NM_SETTING_DUMMY_SETTING_NAME,
+        NM_SETTING_HSR_SETTING_NAME, NM_SETTING_INFINIBAND_SETTING_NAME,
+        NM_SETTING_IPVLAN_SETTING_NAME, NM_SETTING_IP_TUNNEL_SETTING_NAME,
+        NM_SETTING_LOOPBACK_SETTING_NAME, NM_SETTING_MACSEC_SETTING_NAME,
+        NM_SETTING_MACVLAN_SETTING_NAME, NM_SETTING_OVS_BRIDGE_SETTING_NAME,
+        NM_SETTING_OVS_IFACE_SETTING_NAME, NM_SETTING_VETH_SETTING_NAME,
+        NM_SETTING_VLAN_SETTING_NAME, NM_SETTING_VRF_SETTING_NAME,
+        NM_SETTING_VXLAN_SETTING_NAME, NM_SETTING_WIRED_SETTING_NAME,
+        NM_SETTING_WIREGUARD_SETTING_NAME,
     },
     InterfaceType,
 };
 
 pub(crate) fn nm_dev_iface_type_to_nmstate(nm_dev: &NmDevice) -> InterfaceType {
-    match nm_dev.iface_type.as_str() {
+    if nm_dev.iface_type == NM_SETTING_IP_TUNNEL_SETTING_NAME {
+        if let Some(iface_type) =
+            nm_dev.ip_tunnel_mode.and_then(nm_ip_tunnel_mode_to_nmstate)
+        {
+            return iface_type;
+        }
+    }
+    nm_iface_type_to_nmstate(nm_dev.iface_type.as_str(), nm_dev.is_mac_vtap)
+}
+
+pub(crate) fn nm_iface_type_to_nmstate(
+    nm_iface_type: &str,
+    is_mac_vtap: bool,
+) -> InterfaceType {
+    match nm_iface_type {
         NM_SETTING_WIRED_SETTING_NAME => InterfaceType::Ethernet,
         NM_SETTING_VETH_SETTING_NAME => InterfaceType::Ethernet,
         NM_SETTING_BOND_SETTING_NAME => InterfaceType::Bond,
@@ -28,7 +44,7 @@ pub(crate) fn nm_dev_iface_type_to_nmstate(nm_dev: &NmDevice) -> InterfaceType {
         NM_SETTING_VLAN_SETTING_NAME => InterfaceType::Vlan,
         NM_SETTING_VXLAN_SETTING_NAME => InterfaceType::Vxlan,
         NM_SETTING_MACVLAN_SETTING_NAME => {
-            if nm_dev.is_mac_vtap {
+            if is_mac_vtap {
                 InterfaceType::MacVtap
             } else {
                 InterfaceType::MacVlan
@@ -38,6 +54,8 @@ pub(crate) fn nm_dev_iface_type_to_nmstate(nm_dev: &NmDevice) -> InterfaceType {
         NM_SETTING_INFINIBAND_SETTING_NAME => InterfaceType::InfiniBand,
         NM_SETTING_MACSEC_SETTING_NAME => InterfaceType::MacSec,
         NM_SETTING_HSR_SETTING_NAME => InterfaceType::Hsr,
-        _ => InterfaceType::Other(nm_dev.iface_type.to_string()),
+        NM_SETTING_WIREGUARD_SETTING_NAME => InterfaceType::WireGuard,
+        NM_SETTING_IPVLAN_SETTING_NAME => InterfaceType::IpVlan,
+        _ => InterfaceType::Other(nm_iface_type.to_string()),
     }
 }
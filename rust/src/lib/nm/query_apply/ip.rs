@@ -10,7 +10,7 @@ use super::dns::nm_dns_to_nmstate;
 
 use crate::{
     AddressFamily, Dhcpv4ClientId, Dhcpv6Duid, InterfaceIpv4, InterfaceIpv6,
-    Ipv6AddrGenMode, RouteRuleAction, RouteRuleEntry, WaitIp,
+    Ipv6AddrGenMode, RouteRuleAction, RouteRuleEntry, RouteRuleRange, WaitIp,
 };
 
 const ADDR_GEN_MODE_EUI64: i32 = 0;
@@ -285,6 +285,26 @@ fn nm_rules_to_nmstate(
                 }
             }
         }
+        if let Some(v) = nm_rule.oifname.as_ref() {
+            rule.oif = Some(v.to_string());
+        }
+        if let (Some(start), Some(end)) =
+            (nm_rule.sport_start, nm_rule.sport_end)
+        {
+            rule.sport = Some(RouteRuleRange::new(start.into(), end.into()));
+        }
+        if let (Some(start), Some(end)) =
+            (nm_rule.dport_start, nm_rule.dport_end)
+        {
+            rule.dport = Some(RouteRuleRange::new(start.into(), end.into()));
+        }
+        if let (Some(start), Some(end)) =
+            (nm_rule.uid_range_start, nm_rule.uid_range_end)
+        {
+            rule.uidrange = Some(RouteRuleRange::new(start, end));
+        }
+        rule.ipproto = nm_rule.ipproto;
+        rule.tos = nm_rule.tos.filter(|v| *v != 0);
         if let Some(v) = nm_rule.action.as_ref() {
             rule.action = Some(match v {
                 NmIpRouteRuleAction::Blackhole => RouteRuleAction::Blackhole,
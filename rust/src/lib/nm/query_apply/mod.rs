@@ -18,7 +18,9 @@ pub(crate) mod vpn;
 mod vrf;
 mod vxlan;
 
-pub(crate) use self::apply::nm_apply;
+pub(crate) use self::apply::{nm_apply, nm_apply_dry_run};
+#[cfg(test)]
+pub(crate) use self::apply::gen_nm_apply_report;
 pub(crate) use self::dns::retrieve_dns_info;
 pub(crate) use self::ieee8021x::nm_802_1x_to_nmstate;
 pub(crate) use self::ip::{
@@ -29,7 +31,8 @@ pub(crate) use self::mptcp::is_mptcp_flags_changed;
 pub(crate) use self::ovs::delete_orphan_ovs_ports;
 pub(crate) use self::profile::{
     activate_nm_profiles, create_index_for_nm_conns_by_name_type,
-    deactivate_nm_profiles, delete_exist_profiles, save_nm_profiles,
+    deactivate_nm_profiles, delete_exist_profiles,
+    get_exist_profiles_to_delete, save_nm_profiles,
 };
 pub(crate) use self::route::is_route_removed;
 pub(crate) use self::user::get_description;
@@ -33,9 +33,33 @@ pub(crate) fn delete_exist_profiles(
     exist_nm_conns: &[NmConnection],
     nm_conns: &[NmConnection],
 ) -> Result<(), NmstateError> {
+    let mut uuids_to_delete = Vec::new();
+    for exist_nm_conn in get_exist_profiles_to_delete(exist_nm_conns, nm_conns)
+    {
+        if let Some(uuid) = exist_nm_conn.uuid() {
+            log::info!(
+                "Deleting existing connection \
+                UUID {}, id {:?} type {:?} name {:?}",
+                uuid,
+                exist_nm_conn.id(),
+                exist_nm_conn.iface_type(),
+                exist_nm_conn.iface_name(),
+            );
+            uuids_to_delete.push(uuid);
+        }
+    }
+    delete_profiles(nm_api, &uuids_to_delete)
+}
+
+// Existing profiles sharing the same interface name and type with the
+// profiles going to be saved.
+pub(crate) fn get_exist_profiles_to_delete<'a>(
+    exist_nm_conns: &'a [NmConnection],
+    nm_conns: &[NmConnection],
+) -> Vec<&'a NmConnection> {
     let mut excluded_uuids: Vec<&str> = Vec::new();
     let mut changed_iface_name_types: Vec<(&str, &str)> = Vec::new();
-    let mut uuids_to_delete = Vec::new();
+    let mut ret = Vec::new();
     for nm_conn in nm_conns {
         if let Some(uuid) = nm_conn.uuid() {
             excluded_uuids.push(uuid);
@@ -87,20 +111,10 @@ pub(crate) fn delete_exist_profiles(
         if !excluded_uuids.contains(&uuid)
             && changed_iface_name_types.contains(&(iface_name, nm_iface_type))
         {
-            if let Some(uuid) = exist_nm_conn.uuid() {
-                uuids_to_delete.push(uuid);
-                log::info!(
-                    "Deleting existing connection \
-                UUID {}, id {:?} type {:?} name {:?}",
-                    uuid,
-                    exist_nm_conn.id(),
-                    exist_nm_conn.iface_type(),
-                    exist_nm_conn.iface_name(),
-                );
-            }
+            ret.push(exist_nm_conn);
         }
     }
-    delete_profiles(nm_api, &uuids_to_delete)
+    ret
 }
 
 pub(crate) fn save_nm_profiles(
@@ -6,6 +6,9 @@ pub(crate) fn store_route_config(
     merged_state: &mut MergedNetworkState,
 ) -> Result<(), NmstateError> {
     if merged_state.routes.is_changed() {
+        merged_state
+            .routes
+            .expand_nexthop_routes(&merged_state.nexthops)?;
         let empty_rts = Vec::new();
         for iface_name in merged_state.routes.route_changed_ifaces.as_slice() {
             let rts =
@@ -229,7 +229,7 @@ fn append_route_rule(
     Ok(())
 }
 
-// * If rule has `iif`, we use that
+// * If rule has `iif` or `oif`, we use that
 // * If rule has table id, we find a interface configured for that route table
 // * fallback to first desired interface with ip stack enabled.
 // * fallback to use loop interface.
@@ -237,7 +237,7 @@ fn find_interface_for_rule<'a>(
     merged_state: &'a MergedNetworkState,
     rule: &RouteRuleEntry,
 ) -> Result<&'a str, NmstateError> {
-    if let Some(iif) = rule.iif.as_ref() {
+    if let Some(iif) = rule.iif.as_ref().or(rule.oif.as_ref()) {
         if let Some(iface) = merged_state.interfaces.kernel_ifaces.get(iif) {
             return Ok(iface.merged.name());
         } else {
@@ -1,8 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::super::nm_dbus::{
-    NmConnection, NmSettingConnection, NmSettingMacVlan, NmSettingVeth,
-    NmSettingVrf, NmSettingVxlan, NmSettingsConnectionFlag,
+    NmConnection, NmSettingConnection, NmSettingIpVlan, NmSettingMacVlan,
+    NmSettingVeth, NmSettingVrf, NmSettingVxlan, NmSettingsConnectionFlag,
 };
 use super::{
     bond::{gen_nm_bond_port_setting, gen_nm_bond_setting},
@@ -12,6 +12,7 @@ use super::{
     ieee8021x::gen_nm_802_1x_setting,
     infiniband::gen_nm_ib_setting,
     ip::gen_nm_ip_setting,
+    ip_tunnel::gen_nm_ip_tunnel_setting,
     loopback::gen_nm_loopback_setting,
     macsec::gen_nm_macsec_setting,
     mptcp::apply_mptcp_conf,
@@ -26,6 +27,7 @@ use super::{
     vlan::gen_nm_vlan_setting,
     vpn::gen_nm_ipsec_vpn_setting,
     wired::gen_nm_wired_setting,
+    wireguard::gen_nm_wireguard_setting,
 };
 
 use crate::{
@@ -50,8 +52,11 @@ pub(crate) const NM_SETTING_INFINIBAND_SETTING_NAME: &str = "infiniband";
 pub(crate) const NM_SETTING_LOOPBACK_SETTING_NAME: &str = "loopback";
 pub(crate) const NM_SETTING_HSR_SETTING_NAME: &str = "hsr";
 pub(crate) const NM_SETTING_VPN_SETTING_NAME: &str = "vpn";
+pub(crate) const NM_SETTING_WIREGUARD_SETTING_NAME: &str = "wireguard";
+pub(crate) const NM_SETTING_IP_TUNNEL_SETTING_NAME: &str = "ip-tunnel";
+pub(crate) const NM_SETTING_IPVLAN_SETTING_NAME: &str = "ipvlan";
 
-pub(crate) const SUPPORTED_NM_KERNEL_IFACE_TYPES: [&str; 14] = [
+pub(crate) const SUPPORTED_NM_KERNEL_IFACE_TYPES: [&str; 17] = [
     NM_SETTING_WIRED_SETTING_NAME,
     NM_SETTING_VETH_SETTING_NAME,
     NM_SETTING_BOND_SETTING_NAME,
@@ -66,6 +71,9 @@ pub(crate) const SUPPORTED_NM_KERNEL_IFACE_TYPES: [&str; 14] = [
     NM_SETTING_INFINIBAND_SETTING_NAME,
     NM_SETTING_MACSEC_SETTING_NAME,
     NM_SETTING_HSR_SETTING_NAME,
+    NM_SETTING_WIREGUARD_SETTING_NAME,
+    NM_SETTING_IP_TUNNEL_SETTING_NAME,
+    NM_SETTING_IPVLAN_SETTING_NAME,
 ];
 
 pub(crate) fn iface_to_nm_connections(
@@ -150,10 +158,17 @@ pub(crate) fn iface_to_nm_connections(
         iface.base_iface().routes.as_deref(),
         &mut nm_conn,
     )?;
-    // InfiniBand over IP and loopback can not have layer 2 configuration.
-    if iface.iface_type() != InterfaceType::InfiniBand
-        && iface.iface_type() != InterfaceType::Loopback
-    {
+    // InfiniBand over IP, loopback and layer 3 IP tunnels can not have
+    // layer 2 configuration.
+    if !matches!(
+        iface.iface_type(),
+        InterfaceType::InfiniBand
+            | InterfaceType::Loopback
+            | InterfaceType::Gre
+            | InterfaceType::Ipip
+            | InterfaceType::Sit
+            | InterfaceType::Ip6Tnl
+    ) {
         gen_nm_wired_setting(iface, &mut nm_conn);
     }
     gen_nm_iface_ovs_db_setting(iface, &mut nm_conn);
@@ -258,6 +273,17 @@ pub(crate) fn iface_to_nm_connections(
         Interface::Ipsec(iface) => {
             gen_nm_ipsec_vpn_setting(iface, &mut nm_conn);
         }
+        Interface::WireGuard(iface) => {
+            gen_nm_wireguard_setting(iface, &mut nm_conn);
+        }
+        Interface::IpTunnel(iface) => {
+            gen_nm_ip_tunnel_setting(iface, &mut nm_conn)?;
+        }
+        Interface::IpVlan(iface) => {
+            if let Some(conf) = iface.ipvlan.as_ref() {
+                nm_conn.ip_vlan = Some(NmSettingIpVlan::from(conf));
+            }
+        }
         _ => (),
     };
 
@@ -380,6 +406,17 @@ pub(crate) fn iface_type_to_nm(
         InterfaceType::MacSec => Ok(NM_SETTING_MACSEC_SETTING_NAME.to_string()),
         InterfaceType::Hsr => Ok(NM_SETTING_HSR_SETTING_NAME.to_string()),
         InterfaceType::Ipsec => Ok(NM_SETTING_VPN_SETTING_NAME.to_string()),
+        InterfaceType::WireGuard => {
+            Ok(NM_SETTING_WIREGUARD_SETTING_NAME.to_string())
+        }
+        InterfaceType::Gre
+        | InterfaceType::GreTap
+        | InterfaceType::Ipip
+        | InterfaceType::Sit
+        | InterfaceType::Ip6Tnl => {
+            Ok(NM_SETTING_IP_TUNNEL_SETTING_NAME.to_string())
+        }
+        InterfaceType::IpVlan => Ok(NM_SETTING_IPVLAN_SETTING_NAME.to_string()),
         InterfaceType::Other(s) => Ok(s.to_string()),
         _ => Err(NmstateError::new(
             ErrorKind::NotImplementedError,
@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::NmConnection;
+
+use crate::{ErrorKind, InterfaceType, IpTunnelInterface, NmstateError};
+
+const NM_IP_TUNNEL_MODE_IPIP: u32 = 1;
+const NM_IP_TUNNEL_MODE_GRE: u32 = 2;
+const NM_IP_TUNNEL_MODE_SIT: u32 = 3;
+const NM_IP_TUNNEL_MODE_IP6IP6: u32 = 6;
+const NM_IP_TUNNEL_MODE_IPIP6: u32 = 7;
+const NM_IP_TUNNEL_MODE_GRETAP: u32 = 10;
+
+#[cfg(feature = "query_apply")]
+pub(crate) fn nm_ip_tunnel_mode_to_nmstate(mode: u32) -> Option<InterfaceType> {
+    match mode {
+        NM_IP_TUNNEL_MODE_IPIP => Some(InterfaceType::Ipip),
+        NM_IP_TUNNEL_MODE_GRE => Some(InterfaceType::Gre),
+        NM_IP_TUNNEL_MODE_SIT => Some(InterfaceType::Sit),
+        // Both IPv6 and IPv4 over IPv6 are `ip6tnl` in kernel
+        NM_IP_TUNNEL_MODE_IP6IP6 | NM_IP_TUNNEL_MODE_IPIP6 => {
+            Some(InterfaceType::Ip6Tnl)
+        }
+        NM_IP_TUNNEL_MODE_GRETAP => Some(InterfaceType::GreTap),
+        _ => None,
+    }
+}
+
+pub(crate) fn gen_nm_ip_tunnel_setting(
+    iface: &IpTunnelInterface,
+    nm_conn: &mut NmConnection,
+) -> Result<(), NmstateError> {
+    let mut nm_tunnel_set =
+        nm_conn.ip_tunnel.as_ref().cloned().unwrap_or_default();
+    nm_tunnel_set.mode = Some(match iface.base.iface_type {
+        InterfaceType::Ipip => NM_IP_TUNNEL_MODE_IPIP,
+        InterfaceType::Gre => NM_IP_TUNNEL_MODE_GRE,
+        InterfaceType::Sit => NM_IP_TUNNEL_MODE_SIT,
+        InterfaceType::GreTap => NM_IP_TUNNEL_MODE_GRETAP,
+        // Keep IPv4 over IPv6 mode of existing profile
+        InterfaceType::Ip6Tnl => match nm_tunnel_set.mode {
+            Some(NM_IP_TUNNEL_MODE_IPIP6) => NM_IP_TUNNEL_MODE_IPIP6,
+            _ => NM_IP_TUNNEL_MODE_IP6IP6,
+        },
+        _ => {
+            return Err(NmstateError::new(
+                ErrorKind::Bug,
+                format!(
+                    "Got unexpected IP tunnel type {} for interface {}",
+                    iface.base.iface_type, iface.base.name
+                ),
+            ));
+        }
+    });
+    if let Some(conf) = iface.ip_tunnel.as_ref() {
+        if conf.encap.is_some() && !conf.is_encap_none() {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "NetworkManager does not support UDP encapsulation of \
+                    IP tunnel, please use kernel mode for interface {}",
+                    iface.base.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if let Some(v) = conf.base_iface.as_ref() {
+            nm_tunnel_set.parent = Some(v.to_string());
+        }
+        if let Some(v) = conf.local.as_ref() {
+            nm_tunnel_set.local = Some(v.to_string());
+        }
+        if let Some(v) = conf.remote.as_ref() {
+            nm_tunnel_set.remote = Some(v.to_string());
+        }
+        if let Some(v) = conf.ttl {
+            nm_tunnel_set.ttl = Some(v.into());
+        }
+        if let Some(v) = conf.tos {
+            nm_tunnel_set.tos = Some(v.into());
+        }
+        if let Some(v) = conf.pmtudisc {
+            nm_tunnel_set.path_mtu_discovery = Some(v);
+        }
+        if let Some(v) = conf.ikey {
+            nm_tunnel_set.input_key = Some(v.to_string());
+        }
+        if let Some(v) = conf.okey {
+            nm_tunnel_set.output_key = Some(v.to_string());
+        }
+    }
+    if let Some(mtu) = iface.base.mtu {
+        nm_tunnel_set.mtu = Some(mtu as u32);
+    }
+    nm_conn.ip_tunnel = Some(nm_tunnel_set);
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::nm::nm_dbus::NmSettingIpVlan;
+
+use crate::{IpVlanConfig, IpVlanMode};
+
+const NM_SETTING_IPVLAN_MODE_L2: u32 = 1;
+const NM_SETTING_IPVLAN_MODE_L3: u32 = 2;
+const NM_SETTING_IPVLAN_MODE_L3S: u32 = 3;
+
+impl From<&IpVlanConfig> for NmSettingIpVlan {
+    fn from(config: &IpVlanConfig) -> Self {
+        let mut settings = NmSettingIpVlan::default();
+        settings.parent = Some(config.base_iface.clone());
+        settings.mode = Some(match config.mode {
+            IpVlanMode::L2 => NM_SETTING_IPVLAN_MODE_L2,
+            IpVlanMode::L3 => NM_SETTING_IPVLAN_MODE_L3,
+            IpVlanMode::L3S => NM_SETTING_IPVLAN_MODE_L3S,
+        });
+        if let Some(v) = config.private {
+            settings.private = Some(v);
+        }
+        if let Some(v) = config.vepa {
+            settings.vepa = Some(v);
+        }
+        settings
+    }
+}
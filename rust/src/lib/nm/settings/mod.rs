@@ -9,6 +9,8 @@ mod hsr;
 mod ieee8021x;
 mod infiniband;
 mod ip;
+mod ip_tunnel;
+mod ip_vlan;
 mod loopback;
 mod mac_vlan;
 mod macsec;
@@ -24,6 +26,7 @@ mod vpn;
 mod vrf;
 mod vxlan;
 mod wired;
+mod wireguard;
 
 pub(crate) use self::connection::{
     get_exist_profile, iface_to_nm_connections, SUPPORTED_NM_KERNEL_IFACE_TYPES,
@@ -33,14 +36,18 @@ pub(crate) use self::connection::{
     iface_type_to_nm, NM_SETTING_BOND_SETTING_NAME,
     NM_SETTING_BRIDGE_SETTING_NAME, NM_SETTING_DUMMY_SETTING_NAME,
     NM_SETTING_HSR_SETTING_NAME, NM_SETTING_INFINIBAND_SETTING_NAME,
+    NM_SETTING_IPVLAN_SETTING_NAME, NM_SETTING_IP_TUNNEL_SETTING_NAME,
     NM_SETTING_LOOPBACK_SETTING_NAME, NM_SETTING_MACSEC_SETTING_NAME,
     NM_SETTING_MACVLAN_SETTING_NAME, NM_SETTING_OVS_BRIDGE_SETTING_NAME,
     NM_SETTING_OVS_IFACE_SETTING_NAME, NM_SETTING_OVS_PORT_SETTING_NAME,
     NM_SETTING_VETH_SETTING_NAME, NM_SETTING_VLAN_SETTING_NAME,
     NM_SETTING_VPN_SETTING_NAME, NM_SETTING_VRF_SETTING_NAME,
     NM_SETTING_VXLAN_SETTING_NAME, NM_SETTING_WIRED_SETTING_NAME,
+    NM_SETTING_WIREGUARD_SETTING_NAME,
 };
 pub(crate) use self::ip::fix_ip_dhcp_timeout;
+#[cfg(feature = "query_apply")]
+pub(crate) use self::ip_tunnel::nm_ip_tunnel_mode_to_nmstate;
 
 #[cfg(feature = "query_apply")]
 pub(crate) use self::bond::get_bond_balance_slb;
@@ -3,7 +3,8 @@
 use super::super::nm_dbus::NmIpRoute;
 
 use crate::{
-    ip::is_ipv6_addr, InterfaceIpAddr, NmstateError, RouteEntry, RouteType,
+    ip::is_ipv6_addr, ErrorKind, InterfaceIpAddr, NmstateError, RouteEntry,
+    RouteProtocol, RouteType,
 };
 
 pub(crate) fn gen_nm_ip_routes(
@@ -36,13 +37,52 @@ pub(crate) fn gen_nm_ip_routes(
             nm_route.weight = Some(weight as u32);
         }
         nm_route.route_type = match route.route_type {
-            Some(RouteType::Blackhole) => Some("blackhole".to_string()),
-            Some(RouteType::Prohibit) => Some("prohibit".to_string()),
-            Some(RouteType::Unreachable) => Some("unreachable".to_string()),
+            Some(
+                t @ (RouteType::Blackhole
+                | RouteType::Prohibit
+                | RouteType::Unreachable
+                | RouteType::Throw
+                | RouteType::Local),
+            ) => Some(t.to_string()),
+            Some(t) => {
+                return Err(NmstateError::new(
+                    ErrorKind::NotSupportedError,
+                    format!(
+                        "NetworkManager does not support route type {t}: \
+                        {route}"
+                    ),
+                ));
+            }
             None => None,
         };
         nm_route.cwnd = route.cwnd;
         nm_route.lock_cwnd = route.cwnd.map(|_| true);
+        nm_route.src.clone_from(&route.source);
+        nm_route.mtu = route.mtu;
+        nm_route.lock_mtu = route.lock_mtu;
+        nm_route.advmss = route.advmss;
+        nm_route.initcwnd = route.initcwnd;
+        nm_route.initrwnd = route.initrwnd;
+        nm_route.quickack = route.quickack;
+        nm_route.onlink = route.onlink;
+        // NetworkManager only support route scope for IPv4
+        if !is_ipv6 {
+            nm_route.scope = route.scope.map(u8::from);
+        }
+        // NetworkManager always install route with `static` protocol.
+        // The `boot` protocol is used by current routes created by `ip route`
+        // command which will be taken over by NetworkManager.
+        if let Some(protocol) = route.protocol.filter(|p| {
+            *p != RouteProtocol::Static && *p != RouteProtocol::Boot
+        }) {
+            return Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "NetworkManager does not support route protocol \
+                    {protocol}, only static is supported: {route}"
+                ),
+            ));
+        }
         ret.push(nm_route);
     }
     Ok(ret)
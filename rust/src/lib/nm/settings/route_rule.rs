@@ -4,7 +4,7 @@ use super::super::nm_dbus::NmIpRouteRule;
 
 use crate::{
     ip::is_ipv6_addr, ip::AddressFamily, ErrorKind, InterfaceIpAddr,
-    NmstateError, RouteRuleEntry,
+    NmstateError, RouteRuleAction, RouteRuleEntry,
 };
 
 const AF_INET6: i32 = 10;
@@ -68,8 +68,41 @@ pub(crate) fn gen_nm_ip_rules(
             nm_rule.iifname = Some(iif.to_string());
         }
         if let Some(action) = rule.action.as_ref() {
+            if matches!(action, RouteRuleAction::Goto | RouteRuleAction::Nop) {
+                return Err(NmstateError::new(
+                    ErrorKind::NotSupportedError,
+                    format!(
+                        "Route rule action {action} is not supported by \
+                        NetworkManager: {rule}"
+                    ),
+                ));
+            }
             nm_rule.action = Some(u8::from(*action).into());
         }
+        if rule.l3mdev == Some(true) {
+            return Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Route rule l3mdev is not supported by NetworkManager: \
+                    {rule}"
+                ),
+            ));
+        }
+        if let Some(v) = rule.sport {
+            nm_rule.sport_start = Some(v.start as u16);
+            nm_rule.sport_end = Some(v.end as u16);
+        }
+        if let Some(v) = rule.dport {
+            nm_rule.dport_start = Some(v.start as u16);
+            nm_rule.dport_end = Some(v.end as u16);
+        }
+        if let Some(v) = rule.uidrange {
+            nm_rule.uid_range_start = Some(v.start);
+            nm_rule.uid_range_end = Some(v.end);
+        }
+        nm_rule.ipproto = rule.ipproto;
+        nm_rule.tos = rule.tos;
+        nm_rule.oifname.clone_from(&rule.oif);
         if let Some(v) = rule.suppress_prefix_length.as_ref() {
             nm_rule.suppress_prefixlength =
                 Some(i32::try_from(*v).map_err(|e| {
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::{NmConnection, NmSettingWireGuardPeer};
+
+use crate::{NetworkState, WireGuardInterface};
+
+pub(crate) fn gen_nm_wireguard_setting(
+    iface: &WireGuardInterface,
+    nm_conn: &mut NmConnection,
+) {
+    let mut nm_wg_set = nm_conn.wireguard.as_ref().cloned().unwrap_or_default();
+    if let Some(wg_conf) = iface.wireguard.as_ref() {
+        // The hidden secrets means using the existing ones
+        if let Some(v) = wg_conf.private_key.as_deref() {
+            if v != NetworkState::PASSWORD_HID_BY_NMSTATE {
+                nm_wg_set.private_key = Some(v.to_string());
+            }
+        }
+        if let Some(v) = wg_conf.listen_port {
+            nm_wg_set.listen_port = Some(v.into());
+        }
+        if let Some(v) = wg_conf.fwmark {
+            nm_wg_set.fwmark = Some(v);
+        }
+        if let Some(peers) = wg_conf.peers.as_ref() {
+            let cur_peers = nm_wg_set.peers.take().unwrap_or_default();
+            let mut nm_peers = Vec::new();
+            for peer in peers {
+                let mut nm_peer = NmSettingWireGuardPeer::default();
+                nm_peer.public_key = Some(peer.public_key.clone());
+                nm_peer.endpoint.clone_from(&peer.endpoint);
+                nm_peer.allowed_ips.clone_from(&peer.allowed_ips);
+                nm_peer.persistent_keepalive =
+                    peer.persistent_keepalive.map(u32::from);
+                nm_peer.preshared_key = if peer.preshared_key.as_deref()
+                    == Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+                {
+                    cur_peers
+                        .iter()
+                        .find(|p| p.public_key == nm_peer.public_key)
+                        .and_then(|p| p.preshared_key.clone())
+                } else {
+                    peer.preshared_key.clone()
+                };
+                nm_peers.push(nm_peer);
+            }
+            nm_wg_set.peers = Some(nm_peers);
+        }
+    }
+    nm_conn.wireguard = Some(nm_wg_set);
+}
@@ -27,11 +27,11 @@ use super::{
 use crate::{
     BaseInterface, BondConfig, BondInterface, BondOptions, DummyInterface,
     EthernetInterface, HsrInterface, InfiniBandInterface, Interface,
-    InterfaceIdentifier, InterfaceState, InterfaceType, LinuxBridgeInterface,
-    LoopbackInterface, MacSecConfig, MacSecInterface, MacVlanInterface,
-    MacVtapInterface, NetworkState, NmstateError, OvsBridgeInterface,
-    OvsInterface, UnknownInterface, VlanInterface, VrfInterface,
-    VxlanInterface,
+    InterfaceIdentifier, InterfaceState, InterfaceType, IpTunnelInterface,
+    IpVlanInterface, LinuxBridgeInterface, LoopbackInterface, MacSecConfig,
+    MacSecInterface, MacVlanInterface, MacVtapInterface, NetworkState,
+    NmstateError, OvsBridgeInterface, OvsInterface, UnknownInterface,
+    VlanInterface, VrfInterface, VxlanInterface, WireGuardInterface,
 };
 
 pub(crate) fn nm_retrieve(
@@ -328,6 +328,11 @@ fn iface_get(
                 iface.base = base_iface;
                 iface
             }),
+            InterfaceType::IpVlan => Interface::IpVlan({
+                let mut iface = IpVlanInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
             InterfaceType::Vrf => Interface::Vrf({
                 let mut iface = VrfInterface::new();
                 iface.base = base_iface;
@@ -368,6 +373,20 @@ fn iface_get(
                 iface.base = base_iface;
                 iface
             }),
+            InterfaceType::WireGuard => Interface::WireGuard({
+                let mut iface = WireGuardInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            InterfaceType::Gre
+            | InterfaceType::GreTap
+            | InterfaceType::Ipip
+            | InterfaceType::Sit
+            | InterfaceType::Ip6Tnl => Interface::IpTunnel({
+                let mut iface = IpTunnelInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
             _ => {
                 log::debug!("Skip unsupported interface {:?}", base_iface);
                 return None;
@@ -495,6 +514,11 @@ fn nm_dev_to_nm_iface(nm_dev: &NmDevice) -> Option<Interface> {
             iface.base = base_iface;
             iface
         }),
+        InterfaceType::IpVlan => Interface::IpVlan({
+            let mut iface = IpVlanInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
         InterfaceType::Vrf => Interface::Vrf({
             let mut iface = VrfInterface::new();
             iface.base = base_iface;
@@ -515,6 +539,20 @@ fn nm_dev_to_nm_iface(nm_dev: &NmDevice) -> Option<Interface> {
             iface.base = base_iface;
             iface
         }),
+        InterfaceType::WireGuard => Interface::WireGuard({
+            let mut iface = WireGuardInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Gre
+        | InterfaceType::GreTap
+        | InterfaceType::Ipip
+        | InterfaceType::Sit
+        | InterfaceType::Ip6Tnl => Interface::IpTunnel({
+            let mut iface = IpTunnelInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
         InterfaceType::InfiniBand => Interface::InfiniBand({
             InfiniBandInterface {
                 base: base_iface,
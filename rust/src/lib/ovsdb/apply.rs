@@ -1,6 +1,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{ovsdb::db::OvsDbConnection, MergedNetworkState, NmstateError};
+use serde_json::Value;
+
+use crate::{
+    ovsdb::{
+        db::OvsDbConnection,
+        operation::{OvsDbUpdate, OVS_DB_NAME},
+    },
+    MergedNetworkState, NmstateError,
+};
 
 pub(crate) fn ovsdb_apply(
     merged_state: &MergedNetworkState,
@@ -13,3 +21,18 @@ pub(crate) fn ovsdb_apply(
         Ok(())
     }
 }
+
+// The `transact` parameters `ovsdb_apply()` would send
+pub(crate) fn ovsdb_apply_dry_run(
+    merged_state: &MergedNetworkState,
+) -> Vec<Value> {
+    if merged_state.ovsdb.is_changed {
+        let update: OvsDbUpdate = (&merged_state.ovsdb).into();
+        vec![Value::Array(vec![
+            Value::String(OVS_DB_NAME.to_string()),
+            update.to_value(),
+        ])]
+    } else {
+        Vec::new()
+    }
+}
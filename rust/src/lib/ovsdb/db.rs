@@ -4,14 +4,17 @@ use std::collections::HashMap;
 
 use serde_json::{Map, Value};
 
-use super::json_rpc::OvsDbJsonRpc;
+use super::{
+    json_rpc::OvsDbJsonRpc,
+    operation::{
+        OvsDbCondition, OvsDbUpdate, GLOBAL_CONFIG_TABLE, OVS_DB_NAME,
+    },
+};
 
 use crate::{
     ErrorKind, MergedOvsDbGlobalConfig, NmstateError, OvsDbGlobalConfig,
 };
 
-const OVS_DB_NAME: &str = "Open_vSwitch";
-pub(crate) const GLOBAL_CONFIG_TABLE: &str = "Open_vSwitch";
 const NM_RESERVED_EXTERNAL_ID: &str = "NM.connection.uuid";
 
 pub(crate) const DEFAULT_OVS_DB_SOCKET_PATH: &str = "/run/openvswitch/db.sock";
@@ -28,23 +31,6 @@ pub(crate) struct OvsDbSelect {
     columns: Option<Vec<&'static str>>,
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub(crate) struct OvsDbCondition {
-    column: String,
-    function: String,
-    value: Value,
-}
-
-impl OvsDbCondition {
-    fn to_value(&self) -> Value {
-        Value::Array(vec![
-            Value::String(self.column.to_string()),
-            Value::String(self.function.to_string()),
-            self.value.clone(),
-        ])
-    }
-}
-
 impl OvsDbSelect {
     fn to_value(&self) -> Value {
         let mut ret = Map::new();
@@ -395,27 +381,3 @@ pub(crate) fn parse_uuid_array(v: &[Value]) -> Vec<String> {
     }
     ret
 }
-
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub(crate) struct OvsDbUpdate {
-    pub(crate) table: String,
-    pub(crate) conditions: Vec<OvsDbCondition>,
-    pub(crate) row: HashMap<String, Value>,
-}
-
-impl OvsDbUpdate {
-    fn to_value(&self) -> Value {
-        let mut ret = Map::new();
-        ret.insert("op".to_string(), Value::String("update".to_string()));
-        ret.insert("table".to_string(), Value::String(self.table.clone()));
-        let condition_values: Vec<Value> =
-            self.conditions.iter().map(|c| c.to_value()).collect();
-        ret.insert("where".to_string(), Value::Array(condition_values));
-        let mut row_map = Map::new();
-        for (k, v) in self.row.iter() {
-            row_map.insert(k.to_string(), v.clone());
-        }
-        ret.insert("row".to_string(), Value::Object(row_map));
-        Value::Object(ret)
-    }
-}
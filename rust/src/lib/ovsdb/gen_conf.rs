@@ -0,0 +1,439 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{Map, Value};
+
+use super::operation::{
+    to_ovsdb_str_map, OvsDbInsert, OvsDbMutateInsert, OvsDbUpdate,
+    GLOBAL_CONFIG_TABLE, OVS_DB_NAME,
+};
+use crate::{
+    Interface, MergedNetworkState, NmstateError, OvsBridgeBondMode,
+    OvsBridgeInterface, OvsBridgePortConfig, OvsDbIfaceConfig,
+};
+
+const OVSDB_TRANSACT_FILE_NAME: &str = "ovsdb-transact.json";
+const OVS_VSCTL_SCRIPT_FILE_NAME: &str = "ovs-vsctl.sh";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OvsDbOperation {
+    Update(OvsDbUpdate),
+    Insert(OvsDbInsert),
+    MutateInsert(OvsDbMutateInsert),
+}
+
+impl OvsDbOperation {
+    fn to_value(&self) -> Value {
+        match self {
+            Self::Update(op) => op.to_value(),
+            Self::Insert(op) => op.to_value(),
+            Self::MutateInsert(op) => op.to_value(),
+        }
+    }
+
+    // Convert to equivalent ovs-vsctl database command
+    fn to_ovs_vsctl_args(&self) -> Vec<String> {
+        match self {
+            Self::Update(op) => {
+                let mut ret =
+                    vec!["set".to_string(), op.table.clone(), ".".to_string()];
+                for (k, v) in op.row.iter() {
+                    ret.push(format!("{k}={}", to_ovs_vsctl_value(v)));
+                }
+                ret
+            }
+            Self::Insert(op) => {
+                let mut ret = vec![
+                    format!("--id=@{}", op.uuid_name),
+                    "create".to_string(),
+                    op.table.clone(),
+                ];
+                for (k, v) in op.row.iter() {
+                    ret.push(format!("{k}={}", to_ovs_vsctl_value(v)));
+                }
+                ret
+            }
+            Self::MutateInsert(op) => {
+                let mut ret = vec![
+                    "add".to_string(),
+                    op.table.clone(),
+                    ".".to_string(),
+                    op.column.clone(),
+                ];
+                match op.value.as_array().map(|v| v.as_slice()) {
+                    Some([Value::String(t), Value::Array(items)])
+                        if t == "set" =>
+                    {
+                        ret.extend(items.iter().map(to_ovs_vsctl_value));
+                    }
+                    _ => ret.push(to_ovs_vsctl_value(&op.value)),
+                }
+                ret
+            }
+        }
+    }
+}
+
+// Generate OVSDB JSON-RPC `transact` request and the equivalent ovs-vsctl
+// script holding:
+//  * The global configuration(including OVN bridge mappings) which
+//    `ovsdb_apply()` write to `Open_vSwitch` table.
+//  * OVS bridges, ports and interfaces if `include_bridges` is true, which
+//    is for network backend not managing OVS bridges.
+// Return empty Vec if nothing to generate.
+pub(crate) fn ovsdb_gen_conf(
+    merged_state: &MergedNetworkState,
+    include_bridges: bool,
+) -> Result<Vec<(String, String)>, NmstateError> {
+    let mut ops = Vec::new();
+    if merged_state.ovsdb.is_changed {
+        ops.push(OvsDbOperation::Update((&merged_state.ovsdb).into()));
+    }
+    if include_bridges {
+        gen_ovs_bridge_ops(merged_state, &mut ops);
+    }
+    if ops.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut params = vec![Value::String(OVS_DB_NAME.to_string())];
+    params.extend(ops.iter().map(|op| op.to_value()));
+    let mut transact = Map::new();
+    transact.insert("method".to_string(), Value::String("transact".into()));
+    transact.insert("params".to_string(), Value::Array(params));
+    transact.insert("id".to_string(), Value::from(0));
+    let transact_content =
+        format!("{}\n", serde_json::to_string_pretty(&transact)?);
+
+    // All the commands are in single ovs-vsctl invocation to share the same
+    // transaction, so the `@name` references are valid.
+    let mut script_content = "#!/bin/sh\novs-vsctl".to_string();
+    for op in ops.as_slice() {
+        script_content.push_str(" \\\n    --");
+        for arg in op.to_ovs_vsctl_args() {
+            script_content.push(' ');
+            script_content.push_str(&shell_quote(&arg));
+        }
+    }
+    script_content.push('\n');
+
+    Ok(vec![
+        (OVSDB_TRANSACT_FILE_NAME.to_string(), transact_content),
+        (OVS_VSCTL_SCRIPT_FILE_NAME.to_string(), script_content),
+    ])
+}
+
+fn gen_ovs_bridge_ops(
+    merged_state: &MergedNetworkState,
+    ops: &mut Vec<OvsDbOperation>,
+) {
+    let mut br_ifaces: Vec<&OvsBridgeInterface> = merged_state
+        .interfaces
+        .user_ifaces
+        .values()
+        .filter_map(|i| i.for_apply.as_ref())
+        .filter(|i| i.is_up())
+        .filter_map(|i| {
+            if let Interface::OvsBridge(br_iface) = i {
+                Some(br_iface)
+            } else {
+                None
+            }
+        })
+        .collect();
+    // We should be persistent on output, hence sort by interface name.
+    br_ifaces.sort_unstable_by_key(|i| i.base.name.as_str());
+
+    let mut iface_count = 0usize;
+    let mut port_count = 0usize;
+    let mut br_uuid_names = Vec::new();
+    for (br_index, br_iface) in br_ifaces.into_iter().enumerate() {
+        let mut port_uuid_names = Vec::new();
+        for port_conf in br_iface.port_confs() {
+            let iface_names: Vec<&str> = match port_conf.bond.as_ref() {
+                Some(bond_conf) => bond_conf.ports(),
+                None => vec![port_conf.name.as_str()],
+            };
+            let mut iface_uuid_names = Vec::new();
+            for iface_name in iface_names {
+                let uuid_name = format!("iface{iface_count}");
+                iface_count += 1;
+                ops.push(OvsDbOperation::Insert(OvsDbInsert {
+                    table: "Interface".to_string(),
+                    uuid_name: uuid_name.clone(),
+                    row: gen_iface_row(merged_state, iface_name),
+                }));
+                iface_uuid_names.push(uuid_name);
+            }
+            let uuid_name = format!("port{port_count}");
+            port_count += 1;
+            ops.push(OvsDbOperation::Insert(OvsDbInsert {
+                table: "Port".to_string(),
+                uuid_name: uuid_name.clone(),
+                row: gen_port_row(port_conf, &iface_uuid_names),
+            }));
+            port_uuid_names.push(uuid_name);
+        }
+        let uuid_name = format!("bridge{br_index}");
+        ops.push(OvsDbOperation::Insert(OvsDbInsert {
+            table: "Bridge".to_string(),
+            uuid_name: uuid_name.clone(),
+            row: gen_bridge_row(br_iface, &port_uuid_names),
+        }));
+        br_uuid_names.push(uuid_name);
+    }
+    if !br_uuid_names.is_empty() {
+        ops.push(OvsDbOperation::MutateInsert(OvsDbMutateInsert {
+            table: GLOBAL_CONFIG_TABLE.to_string(),
+            column: "bridges".to_string(),
+            value: to_ovsdb_uuid_set(&br_uuid_names),
+        }));
+    }
+}
+
+fn gen_bridge_row(
+    br_iface: &OvsBridgeInterface,
+    port_uuid_names: &[String],
+) -> BTreeMap<String, Value> {
+    let mut row = BTreeMap::new();
+    row.insert("name".to_string(), Value::from(br_iface.base.name.as_str()));
+    row.insert("ports".to_string(), to_ovsdb_uuid_set(port_uuid_names));
+    if let Some(opts) =
+        br_iface.bridge.as_ref().and_then(|b| b.options.as_ref())
+    {
+        if let Some(v) = opts.stp.as_ref().and_then(|s| s.enabled) {
+            row.insert("stp_enable".to_string(), Value::from(v));
+        }
+        if let Some(v) = opts.rstp {
+            row.insert("rstp_enable".to_string(), Value::from(v));
+        }
+        if let Some(v) = opts.mcast_snooping_enable {
+            row.insert("mcast_snooping_enable".to_string(), Value::from(v));
+        }
+        if let Some(v) = opts.fail_mode.as_deref().filter(|v| !v.is_empty()) {
+            row.insert("fail_mode".to_string(), Value::from(v));
+        }
+        if let Some(v) = opts.datapath.as_deref().filter(|v| !v.is_empty()) {
+            row.insert("datapath_type".to_string(), Value::from(v));
+        }
+    }
+    if let Some(ovsdb_conf) = br_iface.base.ovsdb.as_ref() {
+        insert_ovsdb_iface_conf(ovsdb_conf, &mut row);
+    }
+    row
+}
+
+fn gen_port_row(
+    port_conf: &OvsBridgePortConfig,
+    iface_uuid_names: &[String],
+) -> BTreeMap<String, Value> {
+    let mut row = BTreeMap::new();
+    row.insert("name".to_string(), Value::from(port_conf.name.as_str()));
+    row.insert(
+        "interfaces".to_string(),
+        to_ovsdb_uuid_set(iface_uuid_names),
+    );
+    if let Some(bond_conf) = port_conf.bond.as_ref() {
+        // Follow what NetworkManager does for each OVS bond mode.
+        match bond_conf.mode.as_ref() {
+            Some(OvsBridgeBondMode::Lacp) => {
+                row.insert("lacp".to_string(), Value::from("active"));
+            }
+            Some(
+                mode @ (OvsBridgeBondMode::ActiveBackup
+                | OvsBridgeBondMode::BalanceSlb),
+            ) => {
+                row.insert("lacp".to_string(), Value::from("off"));
+                row.insert(
+                    "bond_mode".to_string(),
+                    Value::from(mode.to_string()),
+                );
+            }
+            Some(mode @ OvsBridgeBondMode::BalanceTcp) => {
+                row.insert("lacp".to_string(), Value::from("active"));
+                row.insert(
+                    "bond_mode".to_string(),
+                    Value::from(mode.to_string()),
+                );
+            }
+            None => (),
+        }
+        if let Some(v) = bond_conf.bond_downdelay {
+            row.insert("bond_downdelay".to_string(), Value::from(v));
+        }
+        if let Some(v) = bond_conf.bond_updelay {
+            row.insert("bond_updelay".to_string(), Value::from(v));
+        }
+        if let Some(ovsdb_conf) = bond_conf.ovsdb.as_ref() {
+            insert_ovsdb_iface_conf(ovsdb_conf, &mut row);
+        }
+    }
+    if let Some(vlan_conf) = port_conf.vlan.as_ref() {
+        if let Some(v) = vlan_conf.tag {
+            row.insert("tag".to_string(), Value::from(v));
+        }
+        if let Some(v) = vlan_conf.mode {
+            row.insert("vlan_mode".to_string(), Value::from(v.to_string()));
+        }
+        if let Some(trunk_tags) = vlan_conf.trunk_tags.as_ref() {
+            let mut vids = Vec::new();
+            for trunk_tag in trunk_tags {
+                let (vid_min, vid_max) = trunk_tag.get_vlan_tag_range();
+                vids.extend((vid_min..=vid_max).map(Value::from));
+            }
+            row.insert("trunks".to_string(), to_ovsdb_set(vids));
+        }
+    }
+    row
+}
+
+fn gen_iface_row(
+    merged_state: &MergedNetworkState,
+    iface_name: &str,
+) -> BTreeMap<String, Value> {
+    let mut row = BTreeMap::new();
+    row.insert("name".to_string(), Value::from(iface_name));
+    let iface = if let Some(i) = merged_state
+        .interfaces
+        .kernel_ifaces
+        .get(iface_name)
+        .map(|i| &i.merged)
+    {
+        i
+    } else {
+        return row;
+    };
+    if let Interface::OvsInterface(ovs_iface) = iface {
+        let mut options: HashMap<String, Option<String>> = HashMap::new();
+        if let Some(patch_conf) = ovs_iface.patch.as_ref() {
+            row.insert("type".to_string(), Value::from("patch"));
+            options.insert("peer".into(), Some(patch_conf.peer.to_string()));
+        } else if let Some(dpdk_conf) = ovs_iface.dpdk.as_ref() {
+            row.insert("type".to_string(), Value::from("dpdk"));
+            options.insert(
+                "dpdk-devargs".into(),
+                Some(dpdk_conf.devargs.to_string()),
+            );
+            if let Some(v) = dpdk_conf.rx_queue {
+                options.insert("n_rxq".into(), Some(v.to_string()));
+            }
+            if let Some(v) = dpdk_conf.n_rxq_desc {
+                options.insert("n_rxq_desc".into(), Some(v.to_string()));
+            }
+            if let Some(v) = dpdk_conf.n_txq_desc {
+                options.insert("n_txq_desc".into(), Some(v.to_string()));
+            }
+        } else {
+            row.insert("type".to_string(), Value::from("internal"));
+        }
+        if !options.is_empty() {
+            row.insert("options".to_string(), to_ovsdb_str_map(&options));
+        }
+        if let Some(mtu) = ovs_iface.base.mtu {
+            row.insert("mtu_request".to_string(), Value::from(mtu));
+        }
+    }
+    if let Some(ovsdb_conf) = iface.base_iface().ovsdb.as_ref() {
+        insert_ovsdb_iface_conf(ovsdb_conf, &mut row);
+    }
+    row
+}
+
+fn insert_ovsdb_iface_conf(
+    ovsdb_conf: &OvsDbIfaceConfig,
+    row: &mut BTreeMap<String, Value>,
+) {
+    if let Some(ids) = ovsdb_conf.external_ids.as_ref() {
+        row.insert("external_ids".to_string(), to_ovsdb_str_map(ids));
+    }
+    if let Some(cfgs) = ovsdb_conf.other_config.as_ref() {
+        row.insert("other_config".to_string(), to_ovsdb_str_map(cfgs));
+    }
+}
+
+fn to_ovsdb_set(items: Vec<Value>) -> Value {
+    Value::Array(vec![Value::from("set"), Value::Array(items)])
+}
+
+fn to_ovsdb_uuid_set(uuid_names: &[String]) -> Value {
+    to_ovsdb_set(
+        uuid_names
+            .iter()
+            .map(|n| {
+                Value::Array(vec![
+                    Value::from("named-uuid"),
+                    Value::from(n.as_str()),
+                ])
+            })
+            .collect(),
+    )
+}
+
+// Convert OVSDB JSON value to ovs-vsctl syntax.
+fn to_ovs_vsctl_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => to_ovs_vsctl_str(s),
+        Value::Array(items) => match items.as_slice() {
+            [Value::String(t), Value::String(name)] if t == "named-uuid" => {
+                format!("@{name}")
+            }
+            [Value::String(t), Value::Array(items)] if t == "set" => format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(to_ovs_vsctl_value)
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            [Value::String(t), Value::Array(kvs)] if t == "map" => format!(
+                "{{{}}}",
+                kvs.iter()
+                    .filter_map(|kv| {
+                        match kv.as_array().map(|a| a.as_slice()) {
+                            Some([k, v]) => Some(format!(
+                                "{}={}",
+                                to_ovs_vsctl_value(k),
+                                to_ovs_vsctl_value(v)
+                            )),
+                            _ => None,
+                        }
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            _ => value.to_string(),
+        },
+        _ => value.to_string(),
+    }
+}
+
+// The ovs-vsctl allows bare string for identifier alike string, otherwise
+// it should be quoted in C style which is identical to JSON string.
+fn to_ovs_vsctl_str(s: &str) -> String {
+    let is_bare = s
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or_default()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_.-".contains(c))
+        && s != "true"
+        && s != "false";
+    if is_bare {
+        s.to_string()
+    } else {
+        Value::from(s).to_string()
+    }
+}
+
+fn shell_quote(arg: &str) -> String {
+    if arg
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c))
+    {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
@@ -1,12 +1,18 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "query_apply")]
 use std::collections::HashMap;
 
+#[cfg(feature = "query_apply")]
 use serde_json::{Map, Value};
 
+#[cfg(feature = "query_apply")]
+use crate::{ovsdb::db::parse_str_map, OvsDbGlobalConfig};
 use crate::{
-    ovsdb::db::{parse_str_map, OvsDbUpdate, GLOBAL_CONFIG_TABLE},
-    MergedOvsDbGlobalConfig, OvsDbGlobalConfig,
+    ovsdb::operation::{to_ovsdb_str_map, OvsDbUpdate, GLOBAL_CONFIG_TABLE},
+    MergedOvsDbGlobalConfig,
 };
 
+#[cfg(feature = "query_apply")]
 impl From<&Map<std::string::String, Value>> for OvsDbGlobalConfig {
     fn from(m: &Map<std::string::String, Value>) -> Self {
         let mut ret = Self::default();
@@ -21,6 +27,7 @@ impl From<&Map<std::string::String, Value>> for OvsDbGlobalConfig {
 }
 
 // Convert HashMap<String, String> to HashMap<String, Option<String>>
+#[cfg(feature = "query_apply")]
 fn convert_map(
     mut m: HashMap<String, String>,
 ) -> HashMap<String, Option<String>> {
@@ -33,38 +40,14 @@ fn convert_map(
 
 impl From<&MergedOvsDbGlobalConfig> for OvsDbUpdate {
     fn from(ovs_conf: &MergedOvsDbGlobalConfig) -> Self {
-        let mut row = HashMap::new();
-        let mut value_array = Vec::new();
-        for (k, v) in ovs_conf.external_ids.iter() {
-            if let Some(v) = v {
-                value_array.push(Value::Array(vec![
-                    Value::String(k.to_string()),
-                    Value::String(v.to_string()),
-                ]));
-            }
-        }
+        let mut row = BTreeMap::new();
         row.insert(
             "external_ids".to_string(),
-            Value::Array(vec![
-                Value::String("map".to_string()),
-                Value::Array(value_array),
-            ]),
+            to_ovsdb_str_map(&ovs_conf.external_ids),
         );
-        let mut value_array = Vec::new();
-        for (k, v) in ovs_conf.other_config.iter() {
-            if let Some(v) = v {
-                value_array.push(Value::Array(vec![
-                    Value::String(k.to_string()),
-                    Value::String(v.to_string()),
-                ]));
-            }
-        }
         row.insert(
             "other_config".to_string(),
-            Value::Array(vec![
-                Value::String("map".to_string()),
-                Value::Array(value_array),
-            ]),
+            to_ovsdb_str_map(&ovs_conf.other_config),
         );
 
         OvsDbUpdate {
@@ -13,6 +13,8 @@ const BUFFER_SIZE: usize = 4096;
 pub(crate) struct OvsDbJsonRpc {
     socket: UnixStream,
     transaction_id: u64,
+    // Received data not yet parsed as notification
+    pending: Vec<u8>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
@@ -36,6 +38,15 @@ pub(crate) struct OvsDbRpcReply {
     id: u64,
 }
 
+// Request or notification initiated by OVSDB server.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OvsDbRpcNotification {
+    pub(crate) method: String,
+    pub(crate) params: Value,
+    // Null for notification
+    pub(crate) id: Value,
+}
+
 impl OvsDbJsonRpc {
     pub(crate) fn connect(socket_path: &str) -> Result<Self, NmstateError> {
         Ok(Self {
@@ -43,6 +54,7 @@ impl OvsDbJsonRpc {
                 NmstateError::new(ErrorKind::Bug, format!("socket error {e}"))
             })?,
             transaction_id: get_sec_since_epoch(),
+            pending: Vec::new(),
         })
     }
 
@@ -70,6 +82,57 @@ impl OvsDbJsonRpc {
         }
     }
 
+    // Block till next request or notification from OVSDB server, e.g. the
+    // `update` notification of `monitor` method.
+    pub(crate) fn recv_notification(
+        &mut self,
+    ) -> Result<OvsDbRpcNotification, NmstateError> {
+        loop {
+            let mut stream =
+                serde_json::Deserializer::from_slice(&self.pending)
+                    .into_iter::<OvsDbRpcNotification>();
+            let notification = match stream.next() {
+                Some(Ok(n)) => Some((n, stream.byte_offset())),
+                Some(Err(e)) if !e.is_eof() => return Err(e.into()),
+                _ => None,
+            };
+            if let Some((notification, offset)) = notification {
+                self.pending.drain(..offset);
+                log::debug!("OVSDB: recv notification {:?}", notification);
+                return Ok(notification);
+            }
+            let mut buffer = [0u8; BUFFER_SIZE];
+            let read = self
+                .socket
+                .read(&mut buffer)
+                .map_err(parse_socket_io_error)?;
+            if read == 0 {
+                return Err(NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    "OVSDB closed the connection".to_string(),
+                ));
+            }
+            self.pending.extend_from_slice(&buffer[..read]);
+        }
+    }
+
+    // Reply to request initiated by OVSDB server
+    pub(crate) fn reply(
+        &mut self,
+        id: &Value,
+        result: &Value,
+    ) -> Result<(), NmstateError> {
+        let buffer = serde_json::to_string(&serde_json::json!({
+            "id": id,
+            "result": result,
+            "error": Value::Null,
+        }))?;
+        log::debug!("OVSDB: sending reply {}", buffer);
+        self.socket
+            .write_all(buffer.as_bytes())
+            .map_err(parse_socket_io_error)
+    }
+
     fn recv(&mut self) -> Result<Value, NmstateError> {
         let mut response: Vec<u8> = Vec::new();
         loop {
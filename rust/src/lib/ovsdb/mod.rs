@@ -1,12 +1,31 @@
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "query_apply")]
 mod apply;
+#[cfg(feature = "query_apply")]
 mod db;
+#[cfg(feature = "gen_conf")]
+mod gen_conf;
 mod global_conf;
+#[cfg(feature = "query_apply")]
 mod json_rpc;
+#[cfg(feature = "query_apply")]
+mod monitor;
+mod operation;
+#[cfg(feature = "query_apply")]
 mod show;
 
+#[cfg(feature = "query_apply")]
 pub(crate) use self::db::DEFAULT_OVS_DB_SOCKET_PATH;
-pub(crate) use apply::ovsdb_apply;
+#[cfg(feature = "query_apply")]
+pub(crate) use apply::{ovsdb_apply, ovsdb_apply_dry_run};
+#[cfg(feature = "gen_conf")]
+pub(crate) use gen_conf::ovsdb_gen_conf;
+#[cfg(feature = "query_apply")]
+pub(crate) use monitor::ovsdb_monitor_start;
+#[cfg(all(test, feature = "query_apply"))]
+pub(crate) use monitor::ovsdb_update_to_events;
+#[cfg(feature = "query_apply")]
 pub(crate) use show::ovsdb_is_running;
+#[cfg(feature = "query_apply")]
 pub(crate) use show::ovsdb_retrieve;
@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::{json, Value};
+
+use super::{
+    db::DEFAULT_OVS_DB_SOCKET_PATH, json_rpc::OvsDbJsonRpc,
+    operation::OVS_DB_NAME,
+};
+use crate::{
+    query_apply::{WatchEvent, WatchEventSender},
+    NmstateError,
+};
+
+const MONITOR_TABLES: [&str; 4] =
+    ["Open_vSwitch", "Bridge", "Port", "Interface"];
+
+// The monitor thread quits on next notification after receiver dropped.
+pub(crate) fn ovsdb_monitor_start(
+    sender: WatchEventSender,
+) -> Result<(), NmstateError> {
+    let mut rpc = OvsDbJsonRpc::connect(DEFAULT_OVS_DB_SOCKET_PATH)?;
+    let mut monitor_requests = serde_json::Map::new();
+    for table in MONITOR_TABLES {
+        // Existing rows are already included in initial query
+        monitor_requests
+            .insert(table.to_string(), json!({"select": {"initial": false}}));
+    }
+    rpc.exec(
+        "monitor",
+        &json!([OVS_DB_NAME, Value::Null, monitor_requests]),
+    )?;
+
+    std::thread::spawn(move || loop {
+        match rpc.recv_notification() {
+            Ok(notification) => match notification.method.as_str() {
+                "update" => {
+                    if ovsdb_update_to_events(&notification.params)
+                        .into_iter()
+                        .any(|event| sender.send(event).is_err())
+                    {
+                        break;
+                    }
+                }
+                "echo" => {
+                    if let Err(e) =
+                        rpc.reply(&notification.id, &notification.params)
+                    {
+                        log::error!("Failed to reply OVSDB echo: {e}");
+                        break;
+                    }
+                }
+                _ => {
+                    log::debug!("OVSDB: ignoring notification {notification:?}")
+                }
+            },
+            Err(e) => {
+                log::error!("Failed to receive OVSDB notification: {e}");
+                break;
+            }
+        }
+    });
+    Ok(())
+}
+
+// The `params` of `update` notification is `[<json-value>, <table-updates>]`
+// where `<table-updates>` is `{<table>: {<uuid>: {"old": <row>, "new": <row>}}}`.
+// The `name` column of `Bridge` and `Interface` rows is the interface name,
+// `Port` rows are not interfaces, so all interfaces are treated as changed.
+pub(crate) fn ovsdb_update_to_events(params: &Value) -> Vec<WatchEvent> {
+    let mut events = Vec::new();
+    let table_updates = match params.get(1).and_then(|v| v.as_object()) {
+        Some(t) => t,
+        None => {
+            log::debug!("OVSDB: got unexpected update params {params}");
+            return vec![WatchEvent::Ifaces, WatchEvent::Ovsdb];
+        }
+    };
+    for (table, rows) in table_updates.iter() {
+        match table.as_str() {
+            "Open_vSwitch" => events.push(WatchEvent::Ovsdb),
+            "Bridge" | "Interface" => {
+                for row_update in
+                    rows.as_object().map(|r| r.values()).into_iter().flatten()
+                {
+                    match ["new", "old"].iter().find_map(|k| {
+                        row_update
+                            .get(k)
+                            .and_then(|row| row.get("name"))
+                            .and_then(|n| n.as_str())
+                    }) {
+                        Some(name) => {
+                            events.push(WatchEvent::Iface(name.to_string()))
+                        }
+                        None => events.push(WatchEvent::Ifaces),
+                    }
+                }
+            }
+            _ => events.push(WatchEvent::Ifaces),
+        }
+    }
+    events
+}
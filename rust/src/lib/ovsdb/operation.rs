@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{Map, Value};
+
+pub(crate) const OVS_DB_NAME: &str = "Open_vSwitch";
+pub(crate) const GLOBAL_CONFIG_TABLE: &str = "Open_vSwitch";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OvsDbCondition {
+    column: String,
+    function: String,
+    value: Value,
+}
+
+impl OvsDbCondition {
+    pub(crate) fn to_value(&self) -> Value {
+        Value::Array(vec![
+            Value::String(self.column.to_string()),
+            Value::String(self.function.to_string()),
+            self.value.clone(),
+        ])
+    }
+}
+
+// Use BTreeMap for row to be persistent on generated configurations.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OvsDbUpdate {
+    pub(crate) table: String,
+    pub(crate) conditions: Vec<OvsDbCondition>,
+    pub(crate) row: BTreeMap<String, Value>,
+}
+
+impl OvsDbUpdate {
+    pub(crate) fn to_value(&self) -> Value {
+        let mut ret = Map::new();
+        ret.insert("op".to_string(), Value::String("update".to_string()));
+        ret.insert("table".to_string(), Value::String(self.table.clone()));
+        let condition_values: Vec<Value> =
+            self.conditions.iter().map(|c| c.to_value()).collect();
+        ret.insert("where".to_string(), Value::Array(condition_values));
+        ret.insert("row".to_string(), row_to_value(&self.row));
+        Value::Object(ret)
+    }
+}
+
+#[cfg(feature = "gen_conf")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OvsDbInsert {
+    pub(crate) table: String,
+    // The symbolic name used by other operations of the same transaction
+    // to refer the UUID of inserted row.
+    pub(crate) uuid_name: String,
+    pub(crate) row: BTreeMap<String, Value>,
+}
+
+#[cfg(feature = "gen_conf")]
+impl OvsDbInsert {
+    pub(crate) fn to_value(&self) -> Value {
+        let mut ret = Map::new();
+        ret.insert("op".to_string(), Value::String("insert".to_string()));
+        ret.insert("table".to_string(), Value::String(self.table.clone()));
+        ret.insert("row".to_string(), row_to_value(&self.row));
+        ret.insert(
+            "uuid-name".to_string(),
+            Value::String(self.uuid_name.clone()),
+        );
+        Value::Object(ret)
+    }
+}
+
+// Only support inserting into set column of all rows in table which is
+// enough for adding bridges to global `Open_vSwitch` table.
+#[cfg(feature = "gen_conf")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OvsDbMutateInsert {
+    pub(crate) table: String,
+    pub(crate) column: String,
+    pub(crate) value: Value,
+}
+
+#[cfg(feature = "gen_conf")]
+impl OvsDbMutateInsert {
+    pub(crate) fn to_value(&self) -> Value {
+        let mut ret = Map::new();
+        ret.insert("op".to_string(), Value::String("mutate".to_string()));
+        ret.insert("table".to_string(), Value::String(self.table.clone()));
+        ret.insert("where".to_string(), Value::Array(Vec::new()));
+        ret.insert(
+            "mutations".to_string(),
+            Value::Array(vec![Value::Array(vec![
+                Value::String(self.column.clone()),
+                Value::String("insert".to_string()),
+                self.value.clone(),
+            ])]),
+        );
+        Value::Object(ret)
+    }
+}
+
+fn row_to_value(row: &BTreeMap<String, Value>) -> Value {
+    let mut row_map = Map::new();
+    for (k, v) in row.iter() {
+        row_map.insert(k.to_string(), v.clone());
+    }
+    Value::Object(row_map)
+}
+
+// Convert to OVSDB map with `None` value discarded and sorted by key to be
+// persistent on generated configurations.
+pub(crate) fn to_ovsdb_str_map(m: &HashMap<String, Option<String>>) -> Value {
+    let mut kvs: Vec<(&String, &String)> = m
+        .iter()
+        .filter_map(|(k, v)| v.as_ref().map(|v| (k, v)))
+        .collect();
+    kvs.sort_unstable();
+    Value::Array(vec![
+        Value::String("map".to_string()),
+        Value::Array(
+            kvs.into_iter()
+                .map(|(k, v)| {
+                    Value::Array(vec![
+                        Value::String(k.to_string()),
+                        Value::String(v.to_string()),
+                    ])
+                })
+                .collect(),
+        ),
+    ])
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Serialize;
+
+use crate::{MergedNetworkState, Nexthops, RouteEntry, RouteRules, Routes};
+
+/// The actions [crate::NetworkState::apply()] would take, generated by
+/// [crate::NetworkState::apply_dry_run()] without changing anything.
+///
+/// Example yaml(some lines omitted) serialized ApplyReport would be:
+///
+/// ```yaml
+/// nm-profiles-to-add:
+/// - id: br0
+///   uuid: 8b4a2a5e-2a7b-4b8c-8a3e-6b2f1d6d2c11
+///   interface-name: br0
+///   interface-type: bridge
+/// nm-profiles-to-activate:
+/// - id: br0
+///   uuid: 8b4a2a5e-2a7b-4b8c-8a3e-6b2f1d6d2c11
+///   interface-name: br0
+///   interface-type: bridge
+/// kernel-changes:
+/// - create interface br0 (linux-bridge)
+/// - add route destination: 198.51.100.0/24 next-hop-interface: br0
+/// ```
+#[derive(Clone, Debug, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct ApplyReport {
+    /// NetworkManager profiles to be created
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub nm_profiles_to_add: Vec<NmProfileReport>,
+    /// Existing NetworkManager profiles to be updated
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub nm_profiles_to_modify: Vec<NmProfileReport>,
+    /// NetworkManager profiles to be activated
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub nm_profiles_to_activate: Vec<NmProfileReport>,
+    /// NetworkManager profiles to be deactivated
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub nm_profiles_to_deactivate: Vec<NmProfileReport>,
+    /// Existing NetworkManager profiles to be deleted
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub nm_profiles_to_delete: Vec<NmProfileReport>,
+    /// Parameters of OVSDB JSON-RPC `transact` requests
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ovsdb_transactions: Vec<serde_json::Value>,
+    /// Human readable changes of interfaces, nexthops, routes, route rules,
+    /// hostname and DNS in kernel
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub kernel_changes: Vec<String>,
+}
+
+impl ApplyReport {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl std::fmt::Display for ApplyReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_yaml::to_string(self).map_err(|_| std::fmt::Error)?
+        )
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub struct NmProfileReport {
+    /// NetworkManager connection ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_name: Option<String>,
+    /// NetworkManager connection type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_type: Option<String>,
+}
+
+impl MergedNetworkState {
+    pub(crate) fn gen_kernel_changes(&self) -> Vec<String> {
+        let mut ret = Vec::new();
+        let mut ifaces: Vec<_> =
+            self.interfaces.iter().filter(|i| i.is_changed()).collect();
+        ifaces.sort_unstable_by_key(|i| {
+            (i.merged.name(), i.merged.is_userspace())
+        });
+        for iface in ifaces {
+            let action = if iface.merged.is_absent() {
+                if iface.current.is_none() {
+                    continue;
+                }
+                "delete"
+            } else if iface.current.is_none() {
+                "create"
+            } else if iface.merged.is_down() {
+                "deactivate"
+            } else {
+                "change"
+            };
+            ret.push(format!(
+                "{action} interface {} ({})",
+                iface.merged.name(),
+                iface.merged.iface_type()
+            ));
+        }
+
+        if self.nexthops.is_changed() {
+            let Nexthops { config, .. } = self.nexthops.gen_diff();
+            for mut nh in config.unwrap_or_default() {
+                let action = if nh.is_absent() { "remove" } else { "add" };
+                nh.state = None;
+                ret.push(format!("{action} nexthop {nh}"));
+            }
+        }
+        if self.routes.is_changed() {
+            // The `MergedRoutes::gen_diff()` only contains the routes to
+            // keep, the removed ones are found in `changed_routes`.
+            let mut removed_rts: Vec<RouteEntry> = self
+                .routes
+                .changed_routes
+                .iter()
+                .filter(|rt| rt.is_absent())
+                .cloned()
+                .collect();
+            removed_rts.sort_unstable();
+            for mut rt in removed_rts {
+                rt.state = None;
+                ret.push(format!("remove route {rt}"));
+            }
+            let Routes { config, .. } = self.routes.gen_diff();
+            for rt in config.unwrap_or_default() {
+                if !rt.is_absent() {
+                    ret.push(format!("add route {rt}"));
+                }
+            }
+        }
+        if self.rules.is_changed() {
+            let RouteRules { config, .. } = self.rules.gen_diff();
+            for mut rule in config.unwrap_or_default() {
+                let action = if rule.is_absent() { "remove" } else { "add" };
+                rule.state = None;
+                ret.push(format!("{action} route rule {rule}"));
+            }
+        }
+        if let Some(hostname) = self
+            .hostname
+            .desired
+            .as_ref()
+            .and_then(|c| c.running.as_ref())
+        {
+            ret.push(format!("set running hostname to {hostname}"));
+        }
+        if self.dns.is_changed() {
+            ret.push("change DNS resolver configuration".to_string());
+        }
+        ret
+    }
+}
@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BareUdpInterface;
+
+impl BareUdpInterface {
+    pub(crate) fn update_bareudp(&mut self, other: &BareUdpInterface) {
+        if other.bareudp.is_some() {
+            self.bareudp.clone_from(&other.bareudp);
+        }
+    }
+}
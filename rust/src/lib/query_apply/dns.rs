@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{DnsState, ErrorKind, MergedDnsState, NmstateError};
+use crate::{DnsState, MergedDnsState, NmstateError, VerificationDiff};
 
 impl MergedDnsState {
     pub(crate) fn is_purge(&self) -> bool {
@@ -33,37 +33,49 @@ impl MergedDnsState {
         let cur_conf = if let Some(c) = current.config.as_ref() {
             c
         } else {
-            return Err(NmstateError::new(
-                ErrorKind::VerificationError,
+            return Err(NmstateError::new_verification_error(
                 "Current DNS config is empty".to_string(),
+                VerificationDiff::new(
+                    "dns-resolver.config".to_string(),
+                    serde_json::to_value(&self.desired)?,
+                    serde_json::Value::Null,
+                ),
             ));
         };
 
         if cur_srvs != self.servers
             && !(cur_conf.server.is_none() && self.servers.is_empty())
         {
-            return Err(NmstateError::new(
-                ErrorKind::VerificationError,
+            return Err(NmstateError::new_verification_error(
                 format!(
                     "Failed to apply DNS config: desire name servers '{}', \
                     got '{}'",
                     self.servers.as_slice().join(" "),
                     cur_srvs.as_slice().join(" "),
                 ),
+                VerificationDiff::new(
+                    "dns-resolver.config.server".to_string(),
+                    serde_json::to_value(&self.servers)?,
+                    serde_json::to_value(&cur_srvs)?,
+                ),
             ));
         }
 
         if cur_schs != self.searches
             && !(cur_conf.search.is_none() && self.searches.is_empty())
         {
-            return Err(NmstateError::new(
-                ErrorKind::VerificationError,
+            return Err(NmstateError::new_verification_error(
                 format!(
                     "Failed to apply DNS config: desire searches '{}', \
                     got '{}'",
                     self.searches.as_slice().join(" "),
                     cur_schs.as_slice().join(" "),
                 ),
+                VerificationDiff::new(
+                    "dns-resolver.config.search".to_string(),
+                    serde_json::to_value(&self.searches)?,
+                    serde_json::to_value(&cur_schs)?,
+                ),
             ));
         }
         let mut des_opts = self.options.clone();
@@ -79,14 +91,18 @@ impl MergedDnsState {
         cur_opts.sort_unstable();
 
         if des_opts != cur_opts {
-            return Err(NmstateError::new(
-                ErrorKind::VerificationError,
+            return Err(NmstateError::new_verification_error(
                 format!(
                     "Failed to apply DNS config: desire options '{}', \
                     got '{}'",
                     des_opts.as_slice().join(" "),
                     cur_opts.as_slice().join(" "),
                 ),
+                VerificationDiff::new(
+                    "dns-resolver.config.options".to_string(),
+                    serde_json::to_value(&des_opts)?,
+                    serde_json::to_value(&cur_opts)?,
+                ),
             ));
         }
 
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{GeneveConfig, GeneveInterface};
+
+impl GeneveInterface {
+    pub(crate) fn update_geneve(&mut self, other: &GeneveInterface) {
+        if let Some(conf) = &mut self.geneve {
+            conf.update(other.geneve.as_ref());
+        } else {
+            self.geneve.clone_from(&other.geneve);
+        }
+    }
+}
+
+impl GeneveConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            self.id = other.id;
+            if other.remote.is_some() {
+                self.remote = other.remote;
+            }
+            if other.dst_port.is_some() {
+                self.dst_port = other.dst_port;
+            }
+            if other.ttl.is_some() {
+                self.ttl = other.ttl;
+            }
+            if other.tos.is_some() {
+                self.tos = other.tos;
+            }
+            if other.df.is_some() {
+                self.df = other.df;
+            }
+            if other.udp_checksum.is_some() {
+                self.udp_checksum = other.udp_checksum;
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{ErrorKind, HostNameState, MergedHostNameState, NmstateError};
+use crate::{
+    ErrorKind, HostNameState, MergedHostNameState, NmstateError,
+    VerificationDiff,
+};
 
 impl HostNameState {
     pub(crate) fn update(&mut self, other: &Self) {
@@ -40,14 +43,18 @@ impl MergedHostNameState {
 
         if let Some(running) = desired.running.as_ref() {
             if Some(running) != current.running.as_ref() {
-                let e = NmstateError::new(
-                    ErrorKind::VerificationError,
+                let e = NmstateError::new_verification_error(
                     format!(
                         "Verification fail, desire hostname.running: \
                         {}, current: {:?}",
                         running,
                         current.running.as_ref()
                     ),
+                    VerificationDiff::new(
+                        "hostname.running".to_string(),
+                        serde_json::to_value(running)?,
+                        serde_json::to_value(current.running.as_ref())?,
+                    ),
                 );
                 log::error!("{}", e);
                 return Err(e);
@@ -55,14 +62,18 @@ impl MergedHostNameState {
         }
         if let Some(config) = desired.config.as_ref() {
             if Some(config) != current.config.as_ref() {
-                let e = NmstateError::new(
-                    ErrorKind::VerificationError,
+                let e = NmstateError::new_verification_error(
                     format!(
                         "Verification fail, desire hostname.config: \
                         {}, current: {:?}",
                         config,
                         current.config.as_ref()
                     ),
+                    VerificationDiff::new(
+                        "hostname.config".to_string(),
+                        serde_json::to_value(config)?,
+                        serde_json::to_value(current.config.as_ref())?,
+                    ),
                 );
                 log::error!("{}", e);
                 return Err(e);
@@ -2,7 +2,7 @@
 
 use crate::{
     state::get_json_value_difference, ErrorKind, Interface, InterfaceType,
-    LinuxBridgeInterface, NmstateError,
+    LinuxBridgeInterface, NmstateError, VerificationDiff,
 };
 
 impl Interface {
@@ -24,6 +24,8 @@ impl Interface {
             iface.sanitize_desired_for_verify();
         } else if let Interface::Hsr(iface) = self {
             iface.sanitize_desired_for_verify();
+        } else if let Interface::IpTunnel(iface) = self {
+            iface.sanitize_desired_for_verify();
         }
     }
 
@@ -37,8 +39,9 @@ impl Interface {
         let self_value = serde_json::to_value(self.clone())?;
         let current_value = serde_json::to_value(current.clone())?;
 
+        let prefix = format!("{}.interface", self.name());
         if let Some((reference, desire, current)) = get_json_value_difference(
-            format!("{}.interface", self.name()),
+            prefix.clone(),
             &self_value,
             &current_value,
         ) {
@@ -71,12 +74,20 @@ impl Interface {
                 }
             }
 
-            Err(NmstateError::new(
-                ErrorKind::VerificationError,
+            Err(NmstateError::new_verification_error(
                 format!(
                     "Verification failure: {reference} desire '{desire}', \
                     current '{current}'"
                 ),
+                VerificationDiff::new(
+                    reference.replacen(
+                        prefix.as_str(),
+                        format!("interfaces[name={}]", self.name()).as_str(),
+                        1,
+                    ),
+                    desire.clone(),
+                    current.clone(),
+                ),
             ))
         } else {
             Ok(())
@@ -244,13 +255,68 @@ impl Interface {
                     );
                 }
             }
+            Self::WireGuard(iface) => {
+                if let Self::WireGuard(other_iface) = other {
+                    iface.update_wireguard(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::IpTunnel(iface) => {
+                if let Self::IpTunnel(other_iface) = other {
+                    iface.update_ip_tunnel(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Geneve(iface) => {
+                if let Self::Geneve(other_iface) = other {
+                    iface.update_geneve(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::BareUdp(iface) => {
+                if let Self::BareUdp(other_iface) = other {
+                    iface.update_bareudp(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::IpVlan(iface) => {
+                if let Self::IpVlan(other_iface) = other {
+                    iface.update_ipvlan(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
             _ => (),
         }
     }
 }
 
 impl InterfaceType {
-    pub(crate) const SUPPORTED_LIST: [InterfaceType; 18] = [
+    pub(crate) const SUPPORTED_LIST: [InterfaceType; 27] = [
         InterfaceType::Bond,
         InterfaceType::LinuxBridge,
         InterfaceType::Dummy,
@@ -269,5 +335,14 @@ impl InterfaceType {
         InterfaceType::Hsr,
         InterfaceType::Ipsec,
         InterfaceType::Xfrm,
+        InterfaceType::WireGuard,
+        InterfaceType::Gre,
+        InterfaceType::GreTap,
+        InterfaceType::Ipip,
+        InterfaceType::Sit,
+        InterfaceType::Ip6Tnl,
+        InterfaceType::Geneve,
+        InterfaceType::BareUdp,
+        InterfaceType::IpVlan,
     ];
 }
@@ -2,8 +2,8 @@
 
 use crate::{
     state::{gen_diff_json_value, merge_json_value},
-    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterfaces,
-    NmstateError,
+    Interface, InterfaceType, Interfaces, MergedInterfaces, NmstateError,
+    VerificationDiff,
 };
 
 impl Interfaces {
@@ -125,14 +125,18 @@ fn verify_desire_absent_but_found_in_current(
 ) -> Result<(), NmstateError> {
     if cur_iface.is_virtual() {
         // Virtual interface should be deleted by absent action
-        let e = NmstateError::new(
-            ErrorKind::VerificationError,
+        let e = NmstateError::new_verification_error(
             format!(
                 "Absent/Down interface {}/{} still found as {:?}",
                 des_iface.name(),
                 des_iface.iface_type(),
                 cur_iface
             ),
+            VerificationDiff::new(
+                format!("interfaces[name={}]", des_iface.name()),
+                serde_json::Value::Null,
+                serde_json::to_value(cur_iface)?,
+            ),
         );
         log::error!("{}", e);
         Err(e)
@@ -200,6 +204,14 @@ impl MergedInterfaces {
             iface.sanitize_current_for_verify();
         }
 
+        let netns_ifaces: Vec<String> = merged
+            .kernel_ifaces
+            .values()
+            .filter_map(|i| i.for_verify.as_ref())
+            .filter(|i| i.base_iface().netns.is_some())
+            .map(|i| i.name().to_string())
+            .collect();
+
         for des_iface in merged.iter_mut().filter(|i| i.is_desired()) {
             let iface = if let Some(i) = des_iface.for_verify.as_mut() {
                 i
@@ -208,6 +220,17 @@ impl MergedInterfaces {
             };
             iface.sanitize(false).ok();
             iface.sanitize_desired_for_verify();
+            // Veth peer in other network namespace is not visible
+            if let Interface::Ethernet(eth_iface) = iface {
+                if eth_iface
+                    .veth
+                    .as_ref()
+                    .map(|v| netns_ifaces.contains(&v.peer))
+                    == Some(true)
+                {
+                    eth_iface.veth = None;
+                }
+            }
         }
 
         for des_iface in merged.iter_mut().filter(|i| i.is_desired()) {
@@ -216,7 +239,30 @@ impl MergedInterfaces {
             } else {
                 continue;
             };
-            if iface.is_absent() || (iface.is_virtual() && iface.is_down()) {
+            if let Some(netns) = iface.base_iface().netns.as_deref() {
+                if let Some(cur_iface) =
+                    current.get_iface(iface.name(), iface.iface_type())
+                {
+                    let e = NmstateError::new_verification_error(
+                        format!(
+                            "Interface {} still found in current network \
+                            namespace after moved to {netns}",
+                            iface.name(),
+                        ),
+                        VerificationDiff::new(
+                            format!("interfaces[name={}].netns", iface.name()),
+                            serde_json::Value::String(netns.to_string()),
+                            serde_json::to_value(
+                                cur_iface.base_iface().netns.as_deref(),
+                            )?,
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            } else if iface.is_absent()
+                || (iface.is_virtual() && iface.is_down())
+            {
                 if let Some(cur_iface) =
                     current.get_iface(iface.name(), iface.iface_type())
                 {
@@ -237,13 +283,17 @@ impl MergedInterfaces {
                     }
                 }
             } else if iface.is_up() {
-                return Err(NmstateError::new(
-                    ErrorKind::VerificationError,
+                return Err(NmstateError::new_verification_error(
                     format!(
                         "Failed to find desired interface {} {:?}",
                         iface.name(),
                         iface.iface_type()
                     ),
+                    VerificationDiff::new(
+                        format!("interfaces[name={}]", iface.name()),
+                        serde_json::to_value(&*iface)?,
+                        serde_json::Value::Null,
+                    ),
                 ));
             }
         }
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{IpTunnelConfig, IpTunnelInterface};
+
+impl IpTunnelInterface {
+    pub(crate) fn update_ip_tunnel(&mut self, other: &IpTunnelInterface) {
+        if let Some(conf) = &mut self.ip_tunnel {
+            conf.update(other.ip_tunnel.as_ref());
+        } else {
+            self.ip_tunnel.clone_from(&other.ip_tunnel);
+            if let Some(conf) = self.ip_tunnel.as_mut() {
+                if conf.is_encap_none() {
+                    conf.encap = None;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn sanitize_desired_for_verify(&mut self) {
+        if let Some(conf) = &mut self.ip_tunnel {
+            if conf.is_encap_none() {
+                conf.encap = None;
+            }
+        }
+    }
+}
+
+impl IpTunnelConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.base_iface.is_some() {
+                self.base_iface.clone_from(&other.base_iface);
+            }
+            if other.local.is_some() {
+                self.local = other.local;
+            }
+            if other.remote.is_some() {
+                self.remote = other.remote;
+            }
+            if other.ttl.is_some() {
+                self.ttl = other.ttl;
+            }
+            if other.tos.is_some() {
+                self.tos = other.tos;
+            }
+            if other.ikey.is_some() {
+                self.ikey = other.ikey;
+            }
+            if other.okey.is_some() {
+                self.okey = other.okey;
+            }
+            if other.pmtudisc.is_some() {
+                self.pmtudisc = other.pmtudisc;
+            }
+            if other.is_encap_none() {
+                self.encap = None;
+            } else if other.encap.is_some() {
+                self.encap.clone_from(&other.encap);
+            }
+        }
+    }
+}
@@ -1,16 +1,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod apply_report;
+mod bareudp;
 mod base;
 mod bond;
 mod dispatch;
 mod dns;
 mod ethernet;
+mod geneve;
 mod hostname;
 mod hsr;
 mod iface;
 mod infiniband;
 mod inter_ifaces;
 mod ip;
+mod ip_tunnel;
+mod ip_vlan;
 mod ipsec;
 mod linux_bridge;
 mod mac_vlan;
@@ -18,14 +23,29 @@ mod mac_vtap;
 mod macsec;
 mod mptcp;
 mod net_state;
+mod netns;
+mod nexthop;
 pub(crate) mod ovn;
 mod ovs;
 mod route;
 mod route_rule;
+mod route_table;
 mod sriov;
 mod vlan;
 mod vrf;
 mod vxlan;
+mod watch;
+mod wireguard;
 
+pub use self::apply_report::{ApplyReport, NmProfileReport};
+pub(crate) use self::netns::{
+    entered_netns, open_netns, run_in_netns, spawn_blocking_in_netns,
+};
+pub use self::watch::NetworkStateWatcher;
+pub(crate) use self::watch::{WatchEvent, WatchEventSender};
+#[cfg(test)]
+pub(crate) use netns::netns_path;
 #[cfg(test)]
 pub(crate) use route::is_route_delayed_by_nm;
+#[cfg(test)]
+pub(crate) use watch::{gen_watch_delta, merge_watch_scope, WatchScope};
@@ -3,17 +3,25 @@
 use std::future::Future;
 
 use crate::{
-    nispor::{nispor_apply, nispor_retrieve, set_running_hostname},
+    nispor::{
+        apply_ifaces_netns, is_kernel_checkpoint, nispor_apply,
+        nispor_checkpoint_arm_rollback_timer, nispor_checkpoint_create,
+        nispor_checkpoint_destroy, nispor_checkpoint_get,
+        nispor_checkpoint_timeout_extend, nispor_checkpoints, nispor_retrieve,
+        nispor_verify_bond_opts, set_running_hostname, KernelCheckpoint,
+    },
     nm::{
-        nm_apply, nm_checkpoint_create, nm_checkpoint_destroy,
-        nm_checkpoint_rollback, nm_checkpoint_timeout_extend, nm_retrieve,
+        nm_apply, nm_apply_dry_run, nm_checkpoint_create,
+        nm_checkpoint_destroy, nm_checkpoint_rollback,
+        nm_checkpoint_timeout_extend, nm_import_keyfiles, nm_retrieve,
     },
     ovsdb::{
-        ovsdb_apply, ovsdb_is_running, ovsdb_retrieve,
+        ovsdb_apply, ovsdb_apply_dry_run, ovsdb_is_running, ovsdb_retrieve,
         DEFAULT_OVS_DB_SOCKET_PATH,
     },
-    ErrorKind, MergedInterfaces, MergedNetworkState, NetworkState,
-    NmstateError,
+    query_apply::{entered_netns, run_in_netns},
+    ApplyReport, ErrorKind, MergedInterfaces, MergedNetworkState, NetworkState,
+    NmstateError, RouteTableNames,
 };
 
 const DEFAULT_ROLLBACK_TIMEOUT: u32 = 60;
@@ -29,17 +37,48 @@ const MAX_SUPPORTED_INTERFACES: usize = 1000;
 
 impl NetworkState {
     /// Rollback a checkpoint.
-    /// Not available for `kernel only` mode.
+    /// The checkpoint could be created by NetworkManager or by `kernel only`
+    /// mode. Empty string means the last active checkpoint.
     /// Only available for feature `query_apply`.
     pub fn checkpoint_rollback(checkpoint: &str) -> Result<(), NmstateError> {
-        nm_checkpoint_rollback(checkpoint)
+        if is_kernel_checkpoint(checkpoint)
+            || (checkpoint.is_empty() && !nispor_checkpoints()?.is_empty())
+        {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_io()
+                .build()
+                .map_err(|e| {
+                    NmstateError::new(
+                        ErrorKind::Bug,
+                        format!("tokio::runtime::Builder failed with {e}"),
+                    )
+                })?;
+            rt.block_on(kernel_checkpoint_rollback(checkpoint))
+        } else {
+            nm_checkpoint_rollback(checkpoint)
+        }
     }
 
     /// Commit a checkpoint.
-    /// Not available for `kernel only` mode.
+    /// The checkpoint could be created by NetworkManager or by `kernel only`
+    /// mode. Empty string means the last active checkpoint.
     /// Only available for feature `query_apply`.
     pub fn checkpoint_commit(checkpoint: &str) -> Result<(), NmstateError> {
-        nm_checkpoint_destroy(checkpoint)
+        if is_kernel_checkpoint(checkpoint)
+            || (checkpoint.is_empty() && !nispor_checkpoints()?.is_empty())
+        {
+            kernel_checkpoint_commit(checkpoint)
+        } else {
+            nm_checkpoint_destroy(checkpoint)
+        }
+    }
+
+    /// Create `NetworkState` from the NetworkManager keyfiles
+    /// (`*.nmconnection`) in specified folder without NetworkManager
+    /// running. Properties not supported by nmstate are ignored.
+    /// Only available for feature `query_apply`.
+    pub fn from_nm_keyfiles(dir: &str) -> Result<Self, NmstateError> {
+        nm_import_keyfiles(dir)
     }
 
     /// Retrieve the `NetworkState`.
@@ -60,14 +99,30 @@ impl NetworkState {
     /// Retrieve the `NetworkState`.
     /// Only available for feature `query_apply`.
     pub async fn retrieve_async(&mut self) -> Result<&mut Self, NmstateError> {
+        if let Some(netns) = self.netns.as_deref() {
+            self.validate_kernel_only_for_netns(netns)?;
+            let mut state = self.clone_for_netns();
+            state = run_in_netns(netns, move || async move {
+                state.retrieve_async().await?;
+                Ok(state)
+            })
+            .await?;
+            self.interfaces = state.interfaces;
+            self.nexthops = state.nexthops;
+            self.routes = state.routes;
+            self.rules = state.rules;
+            return Ok(self);
+        }
         let state =
             nispor_retrieve(self.running_config_only, self.kernel_only).await?;
         self.hostname = state.hostname;
         self.interfaces = state.interfaces;
+        self.nexthops = state.nexthops;
         self.routes = state.routes;
         self.rules = state.rules;
         self.dns = state.dns;
-        if ovsdb_is_running() {
+        // The OpenvSwitch daemon is serving its own network namespace only
+        if ovsdb_is_running() && entered_netns().is_none() {
             match ovsdb_retrieve() {
                 Ok(mut ovsdb_state) => {
                     ovsdb_state.isolate_ovn()?;
@@ -83,6 +138,7 @@ impl NetworkState {
             // TODO: Priority handling
             self.update_state(&nm_state);
         }
+        self.fill_route_table_names();
         if !self.include_secrets {
             self.hide_secrets();
         }
@@ -113,6 +169,14 @@ impl NetworkState {
     /// Apply the `NetworkState`.
     /// Only available for feature `query_apply`.
     pub async fn apply_async(&self) -> Result<(), NmstateError> {
+        if let Some(netns) = self.netns.as_deref() {
+            self.validate_for_netns(netns)?;
+            let state = self.clone_for_netns();
+            return run_in_netns(netns, move || async move {
+                state.apply_async().await
+            })
+            .await;
+        }
         if self.interfaces.kernel_ifaces.len()
             + self.interfaces.user_ifaces.len()
             >= MAX_SUPPORTED_INTERFACES
@@ -131,14 +195,176 @@ impl NetworkState {
             );
         }
 
+        if self.dry_run {
+            let report = self.apply_dry_run_async().await?;
+            if report.is_empty() {
+                log::info!("Dry run: nothing to change");
+            } else {
+                log::info!("Dry run: planned changes are:\n{report}");
+            }
+            return Ok(());
+        }
+
         if !self.kernel_only {
             self.apply_with_nm_backend().await
         } else {
-            // TODO: Need checkpoint for kernel only mode
             self.apply_without_nm_backend().await
         }
     }
 
+    /// Validate the `NetworkState` and generate report of the actions
+    /// [NetworkState::apply()] would take without changing anything.
+    /// Only available for feature `query_apply`.
+    pub fn apply_dry_run(&self) -> Result<ApplyReport, NmstateError> {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_io()
+            .build()
+            .map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::Bug,
+                    format!("tokio::runtime::Builder failed with {e}"),
+                )
+            })?;
+        rt.block_on(self.apply_dry_run_async())
+    }
+
+    /// Validate the `NetworkState` and generate report of the actions
+    /// [NetworkState::apply()] would take without changing anything.
+    /// Only available for feature `query_apply`.
+    pub async fn apply_dry_run_async(
+        &self,
+    ) -> Result<ApplyReport, NmstateError> {
+        if let Some(netns) = self.netns.as_deref() {
+            self.validate_for_netns(netns)?;
+            let state = self.clone_for_netns();
+            return run_in_netns(netns, move || async move {
+                state.apply_dry_run_async().await
+            })
+            .await;
+        }
+        let mut cur_net_state = NetworkState::new();
+        cur_net_state.set_kernel_only(self.kernel_only);
+        cur_net_state.set_include_secrets(true);
+        cur_net_state.retrieve_async().await?;
+
+        let merged_state = MergedNetworkState::new(
+            self.clone(),
+            cur_net_state,
+            false,
+            self.memory_only,
+        )?;
+
+        let mut report = ApplyReport::default();
+        if !self.kernel_only {
+            nm_apply_dry_run(&merged_state, &mut report)?;
+            if ovsdb_is_running() {
+                report.ovsdb_transactions = ovsdb_apply_dry_run(&merged_state);
+            }
+        }
+        report.kernel_changes = merged_state.gen_kernel_changes();
+        Ok(report)
+    }
+
+    /// Check whether current network state still matches this desired
+    /// `NetworkState` using the same verification as
+    /// [NetworkState::apply()]. Return [ErrorKind::VerificationError] on
+    /// mismatch.
+    /// Only available for feature `query_apply`.
+    pub fn verify(&self) -> Result<(), NmstateError> {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_io()
+            .build()
+            .map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::Bug,
+                    format!("tokio::runtime::Builder failed with {e}"),
+                )
+            })?;
+        rt.block_on(self.verify_async())
+    }
+
+    /// Check whether current network state still matches this desired
+    /// `NetworkState` using the same verification as
+    /// [NetworkState::apply()]. Return [ErrorKind::VerificationError] on
+    /// mismatch.
+    /// Only available for feature `query_apply`.
+    pub async fn verify_async(&self) -> Result<(), NmstateError> {
+        if let Some(netns) = self.netns.as_deref() {
+            self.validate_for_netns(netns)?;
+            let state = self.clone_for_netns();
+            return run_in_netns(netns, move || async move {
+                state.verify_async().await
+            })
+            .await;
+        }
+        let mut cur_net_state = NetworkState::new();
+        cur_net_state.set_kernel_only(self.kernel_only);
+        cur_net_state.set_include_secrets(true);
+        cur_net_state.retrieve_async().await?;
+
+        let merged_state = MergedNetworkState::new(
+            self.clone(),
+            cur_net_state.clone(),
+            false,
+            self.memory_only,
+        )?;
+        merged_state.verify(&cur_net_state)
+    }
+
+    // The copy of this state used by the thread entered the network namespace
+    fn clone_for_netns(&self) -> Self {
+        let mut state = self.clone();
+        state.netns = None;
+        state
+    }
+
+    // NetworkManager is only managing its own network namespace
+    fn validate_kernel_only_for_netns(
+        &self,
+        netns: &str,
+    ) -> Result<(), NmstateError> {
+        if !self.kernel_only {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Only kernel only mode is supported for network \
+                    namespace {netns}"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn validate_for_netns(&self, netns: &str) -> Result<(), NmstateError> {
+        self.validate_kernel_only_for_netns(netns)?;
+        // The `/etc/iproute2` is shared by all network namespaces
+        if self.create_route_table_names {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Creating route table names is not supported in network \
+                    namespace {netns}"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if self.dns.is_some() || self.hostname.is_some() {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "DNS and hostname are not supported in network \
+                    namespace {netns}"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+
     async fn apply_with_nm_backend(&self) -> Result<(), NmstateError> {
         let mut merged_state = None;
         let mut cur_net_state = NetworkState::new();
@@ -201,58 +427,75 @@ impl NetworkState {
 
         log::info!("Created checkpoint {}", &checkpoint);
 
-        with_nm_checkpoint(&checkpoint, self.no_commit, || async {
-            if let Some(pf_state) = pf_state {
-                let pf_merged_state = MergedNetworkState::new(
-                    pf_state,
-                    cur_net_state.clone(),
-                    false,
-                    self.memory_only,
-                )?;
+        // Route table names are stored once checkpoint committed
+        let route_table_names = if self.memory_only {
+            RouteTableNames::default()
+        } else {
+            RouteTableNames::new(
+                &self.routes,
+                &self.rules,
+                self.create_route_table_names,
+            )?
+        };
+
+        with_nm_checkpoint(
+            &checkpoint,
+            self.no_commit,
+            &route_table_names,
+            || async {
+                if let Some(pf_state) = pf_state {
+                    let pf_merged_state = MergedNetworkState::new(
+                        pf_state,
+                        cur_net_state.clone(),
+                        false,
+                        self.memory_only,
+                    )?;
+                    let verify_count = get_proper_verify_retry_count(
+                        &pf_merged_state.interfaces,
+                    );
+                    self.apply_with_nm_backend_and_under_checkpoint(
+                        &pf_merged_state,
+                        &cur_net_state,
+                        &checkpoint,
+                        verify_count,
+                        timeout,
+                    )
+                    .await?;
+                    // Refresh current state
+                    cur_net_state.retrieve_async().await?;
+                    merged_state = Some(MergedNetworkState::new(
+                        self.clone(),
+                        cur_net_state.clone(),
+                        false,
+                        self.memory_only,
+                    )?);
+                }
+
+                let merged_state = if let Some(merged_state) = merged_state {
+                    merged_state
+                } else {
+                    return Err(NmstateError::new(
+                        ErrorKind::Bug,
+                        "Got unexpected None for merged_state in \
+                    apply_with_nm_backend()"
+                            .into(),
+                    ));
+                };
                 let verify_count =
-                    get_proper_verify_retry_count(&pf_merged_state.interfaces);
+                    get_proper_verify_retry_count(&merged_state.interfaces);
+
+                self.interfaces.check_sriov_capability()?;
+
                 self.apply_with_nm_backend_and_under_checkpoint(
-                    &pf_merged_state,
+                    &merged_state,
                     &cur_net_state,
                     &checkpoint,
                     verify_count,
                     timeout,
                 )
-                .await?;
-                // Refresh current state
-                cur_net_state.retrieve_async().await?;
-                merged_state = Some(MergedNetworkState::new(
-                    self.clone(),
-                    cur_net_state.clone(),
-                    false,
-                    self.memory_only,
-                )?);
-            }
-
-            let merged_state = if let Some(merged_state) = merged_state {
-                merged_state
-            } else {
-                return Err(NmstateError::new(
-                    ErrorKind::Bug,
-                    "Got unexpected None for merged_state in \
-                    apply_with_nm_backend()"
-                        .into(),
-                ));
-            };
-            let verify_count =
-                get_proper_verify_retry_count(&merged_state.interfaces);
-
-            self.interfaces.check_sriov_capability()?;
-
-            self.apply_with_nm_backend_and_under_checkpoint(
-                &merged_state,
-                &cur_net_state,
-                &checkpoint,
-                verify_count,
-                timeout,
-            )
-            .await
-        })
+                .await
+            },
+        )
         .await
     }
 
@@ -269,6 +512,8 @@ impl NetworkState {
         with_retry(RETRY_NM_INTERVAL_MILLISECONDS, RETRY_NM_COUNT, || async {
             nm_checkpoint_timeout_extend(checkpoint, timeout)?;
             nm_apply(merged_state, checkpoint, timeout)?;
+            // NetworkManager cannot move interface to other network namespace
+            apply_ifaces_netns(&merged_state.interfaces)?;
             if merged_state.ovsdb.is_changed && ovsdb_is_running() {
                 ovsdb_apply(merged_state)?;
             }
@@ -298,11 +543,14 @@ impl NetworkState {
     }
 
     async fn apply_without_nm_backend(&self) -> Result<(), NmstateError> {
+        rollback_expired_kernel_checkpoints().await;
+
         let mut cur_net_state = NetworkState::new();
         cur_net_state.set_kernel_only(self.kernel_only);
         cur_net_state.set_include_secrets(true);
         cur_net_state.retrieve_async().await?;
 
+        // Do early pre-apply validation before checkpoint.
         let merged_state = MergedNetworkState::new(
             self.clone(),
             cur_net_state.clone(),
@@ -310,7 +558,35 @@ impl NetworkState {
             self.memory_only,
         )?;
 
-        nispor_apply(&merged_state).await?;
+        let timeout = self.timeout.unwrap_or(DEFAULT_ROLLBACK_TIMEOUT);
+        // Route table names are stored once checkpoint committed
+        let route_table_names: &[(u32, String)] = if self.memory_only {
+            &[]
+        } else {
+            merged_state.route_table_names.new_entries.as_slice()
+        };
+        let checkpoint = nispor_checkpoint_create(
+            self,
+            &cur_net_state,
+            route_table_names,
+            timeout,
+        )?;
+
+        log::info!("Created checkpoint {}", &checkpoint);
+
+        with_kernel_checkpoint(&checkpoint, self.no_commit, timeout, || async {
+            self.apply_with_nispor_backend(&merged_state, &cur_net_state)
+                .await
+        })
+        .await
+    }
+
+    async fn apply_with_nispor_backend(
+        &self,
+        merged_state: &MergedNetworkState,
+        cur_net_state: &Self,
+    ) -> Result<(), NmstateError> {
+        nispor_apply(merged_state).await?;
         if let Some(running_hostname) =
             self.hostname.as_ref().and_then(|c| c.running.as_ref())
         {
@@ -371,6 +647,7 @@ impl NetworkState {
             ret.hostname.clone_from(&self.hostname);
         }
 
+        ret.nexthops = merged_state.nexthops.gen_diff();
         ret.routes = merged_state.routes.gen_diff();
         ret.rules = merged_state.rules.gen_diff();
         if self.description != current.description {
@@ -391,6 +668,7 @@ impl NetworkState {
 async fn with_nm_checkpoint<T, Fut>(
     checkpoint: &str,
     no_commit: bool,
+    route_table_names: &RouteTableNames,
     func: T,
 ) -> Result<(), NmstateError>
 where
@@ -404,8 +682,17 @@ where
                 nm_checkpoint_destroy(checkpoint)?;
 
                 log::info!("Destroyed checkpoint {}", checkpoint);
+                route_table_names.save_new_entries()?;
             } else {
                 log::info!("Skipping commit for checkpoint {}", checkpoint);
+                if !route_table_names.new_entries.is_empty() {
+                    log::warn!(
+                        "Route table names {:?} are not stored as \
+                        checkpoint {} is not committed",
+                        route_table_names.new_entries,
+                        checkpoint
+                    );
+                }
             }
             Ok(())
         }
@@ -419,6 +706,112 @@ where
     }
 }
 
+async fn with_kernel_checkpoint<T, Fut>(
+    checkpoint: &str,
+    no_commit: bool,
+    timeout: u32,
+    func: T,
+) -> Result<(), NmstateError>
+where
+    T: FnOnce() -> Fut,
+    // Once `std::ops::AsyncFnOnce` is stable, use it instead
+    Fut: Future<Output = Result<(), NmstateError>>,
+{
+    match func().await {
+        Ok(()) => {
+            if !no_commit {
+                kernel_checkpoint_commit(checkpoint)?;
+            } else {
+                // Like NetworkManager, the rollback timeout starts after
+                // the apply action finished.
+                nispor_checkpoint_timeout_extend(checkpoint, timeout)?;
+                nispor_checkpoint_arm_rollback_timer(checkpoint, timeout)?;
+                log::info!("Skipping commit for checkpoint {}", checkpoint);
+            }
+            Ok(())
+        }
+        Err(e) => {
+            if let Err(e) = kernel_checkpoint_rollback(checkpoint).await {
+                log::warn!("kernel_checkpoint_rollback() failed: {}", e);
+            }
+            log::info!("Rollbacked to checkpoint {}", checkpoint);
+            Err(e)
+        }
+    }
+}
+
+// Remove the checkpoint and store the route table names created by the
+// apply of this checkpoint.
+fn kernel_checkpoint_commit(checkpoint: &str) -> Result<(), NmstateError> {
+    let cp = nispor_checkpoint_get(checkpoint)?;
+    nispor_checkpoint_destroy(&cp.name)?;
+    log::info!("Destroyed checkpoint {}", cp.name);
+    RouteTableNames::from_new_entries(cp.route_table_names).save_new_entries()
+}
+
+// Apply the revert of checkpoint desired state and remove the checkpoint
+// regardless of the result, otherwise follow up apply will be blocked.
+// The revert is done in the network namespace the checkpoint created in.
+async fn kernel_checkpoint_rollback(
+    checkpoint: &str,
+) -> Result<(), NmstateError> {
+    let cp = nispor_checkpoint_get(checkpoint)?;
+    let result = match cp.netns.as_deref() {
+        Some(netns) if entered_netns().as_deref() != Some(netns) => {
+            let cp = cp.clone();
+            run_in_netns(netns, move || async move {
+                kernel_checkpoint_revert(&cp).await
+            })
+            .await
+        }
+        _ => kernel_checkpoint_revert(&cp).await,
+    };
+    nispor_checkpoint_destroy(&cp.name)?;
+    result
+}
+
+async fn kernel_checkpoint_revert(
+    cp: &KernelCheckpoint,
+) -> Result<(), NmstateError> {
+    let revert_state = cp.revert_state()?;
+    log::debug!(
+        "Reverting kernel checkpoint {} with {revert_state:?}",
+        cp.name
+    );
+    let mut cur_net_state = NetworkState::new();
+    cur_net_state.set_kernel_only(true);
+    cur_net_state.set_include_secrets(true);
+    cur_net_state.retrieve_async().await?;
+    let merged_state = MergedNetworkState::new(
+        revert_state.clone(),
+        cur_net_state.clone(),
+        false,
+        false,
+    )?;
+    revert_state
+        .apply_with_nispor_backend(&merged_state, &cur_net_state)
+        .await
+}
+
+// Kernel mode has no daemon to rollback the checkpoint on timeout, the
+// systemd timer might not be armed, hence we also do it before next kernel
+// mode apply action.
+async fn rollback_expired_kernel_checkpoints() {
+    let checkpoints = match nispor_checkpoints() {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Failed to retrieve kernel checkpoints: {e}");
+            return;
+        }
+    };
+    for cp in checkpoints.iter().filter(|cp| cp.is_expired()) {
+        log::info!("Rolling back expired checkpoint {}", cp.name);
+        if let Err(e) = kernel_checkpoint_rollback(&cp.name).await {
+            log::warn!("Failed to rollback checkpoint {}: {e}", cp.name);
+        }
+    }
+}
+
 async fn with_retry<T, Fut>(
     interval_ms: u64,
     count: usize,
@@ -457,6 +850,9 @@ impl MergedNetworkState {
     fn verify(&self, current: &NetworkState) -> Result<(), NmstateError> {
         self.hostname.verify(current.hostname.as_ref())?;
         self.interfaces.verify(&current.interfaces)?;
+        if current.kernel_only {
+            nispor_verify_bond_opts(&self.interfaces, &current.interfaces)?;
+        }
         let ignored_kernel_ifaces: Vec<&str> = self
             .interfaces
             .ignored_ifaces
@@ -465,10 +861,13 @@ impl MergedNetworkState {
             .filter(|(_, t)| !t.is_userspace())
             .map(|(n, _)| n.as_str())
             .collect();
+        self.nexthops
+            .verify(&current.nexthops, ignored_kernel_ifaces.as_slice())?;
         self.routes.verify(
             &current.routes,
             ignored_kernel_ifaces.as_slice(),
             &current.interfaces,
+            &self.nexthops,
         )?;
         self.rules
             .verify(&current.rules, ignored_kernel_ifaces.as_slice())?;
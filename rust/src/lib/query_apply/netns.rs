@@ -0,0 +1,170 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Network namespace is a per-thread attribute, hence the query and apply
+// action targeting a network namespace is done in a dedicated thread which
+// entered that network namespace via `setns()`.
+// Like `ip netns exec`, the thread also unshares mount namespace and remounts
+// `/sys`, so sysfs reflects the network interfaces of target namespace.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::os::unix::io::AsRawFd;
+
+use nix::mount::{mount, umount2, MntFlags, MsFlags};
+use nix::sched::{setns, unshare, CloneFlags};
+
+use crate::{ErrorKind, NmstateError};
+
+const NETNS_RUN_DIR: &str = "/run/netns";
+
+thread_local! {
+    // The network namespace entered by current thread via `run_in_netns()`
+    static ENTERED_NETNS: RefCell<Option<String>> =
+        const { RefCell::new(None) };
+}
+
+/// Resolve the network namespace into file path:
+///  * Absolute path is used as it is, e.g. `/proc/1234/ns/net`.
+///  * Number is treated as PID, resolved into `/proc/<pid>/ns/net`.
+///  * Otherwise treated as name of namespace in `/run/netns`.
+pub(crate) fn netns_path(netns: &str) -> String {
+    if netns.starts_with('/') {
+        netns.to_string()
+    } else if !netns.is_empty() && netns.chars().all(|c| c.is_ascii_digit()) {
+        format!("/proc/{netns}/ns/net")
+    } else {
+        format!("{NETNS_RUN_DIR}/{netns}")
+    }
+}
+
+/// The network namespace current thread is running in, `None` means the
+/// network namespace of nmstate process.
+pub(crate) fn entered_netns() -> Option<String> {
+    ENTERED_NETNS.with(|n| n.borrow().clone())
+}
+
+pub(crate) fn open_netns(netns: &str) -> Result<std::fs::File, NmstateError> {
+    let path = netns_path(netns);
+    std::fs::File::open(&path).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Failed to open network namespace {netns} at {path}: {e}"),
+        )
+    })
+}
+
+/// Run the function with its own tokio runtime in a new thread which entered
+/// specified network namespace.
+pub(crate) async fn run_in_netns<T, F, Fut>(
+    netns: &str,
+    func: F,
+) -> Result<T, NmstateError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Fut + Send + 'static,
+    // Once `std::ops::AsyncFnOnce` is stable, use it instead
+    Fut: Future<Output = Result<T, NmstateError>>,
+{
+    let netns = netns.to_string();
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    std::thread::Builder::new()
+        .name(format!("nmstate-netns-{netns}"))
+        .spawn(move || {
+            let result = enter_netns(&netns).and_then(|()| {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_io()
+                    .build()
+                    .map_err(|e| {
+                        NmstateError::new(
+                            ErrorKind::Bug,
+                            format!("tokio::runtime::Builder failed with {e}"),
+                        )
+                    })
+                    .and_then(|rt| rt.block_on(func()))
+            });
+            sender.send(result).ok();
+        })
+        .map_err(|e| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Failed to spawn thread for network namespace: {e}"),
+            )
+        })?;
+    receiver.await.map_err(|_| {
+        NmstateError::new(
+            ErrorKind::Bug,
+            "Thread for network namespace quit without result".to_string(),
+        )
+    })?
+}
+
+/// Run the blocking function in tokio blocking thread pool, the thread will
+/// enter the network namespace of current thread first.
+pub(crate) async fn spawn_blocking_in_netns<T, F>(
+    func: F,
+) -> Result<T, NmstateError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, NmstateError> + Send + 'static,
+{
+    let netns = entered_netns();
+    tokio::task::spawn_blocking(move || {
+        // The blocking thread might be reused and entered already
+        if let Some(netns) = netns.as_deref() {
+            if entered_netns().as_deref() != Some(netns) {
+                enter_netns(netns)?;
+            }
+        }
+        func()
+    })
+    .await
+    .map_err(|e| {
+        NmstateError::new(
+            ErrorKind::Bug,
+            format!("Failed to run blocking task: {e}"),
+        )
+    })?
+}
+
+fn enter_netns(netns: &str) -> Result<(), NmstateError> {
+    let fd = open_netns(netns)?;
+    setns(fd.as_raw_fd(), CloneFlags::CLONE_NEWNET).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PermissionError,
+            format!("Failed to enter network namespace {netns}: {e}"),
+        )
+    })?;
+    // Without remounting, the sysfs based bond, bridge and DHCP code would
+    // read and write the `/sys` of host.
+    remount_sysfs().map_err(|e| {
+        NmstateError::new(
+            ErrorKind::PermissionError,
+            format!(
+                "Failed to remount /sys for network namespace {netns}: {e}"
+            ),
+        )
+    })?;
+    log::debug!("Entered network namespace {netns}");
+    ENTERED_NETNS.with(|n| n.replace(Some(netns.to_string())));
+    Ok(())
+}
+
+fn remount_sysfs() -> Result<(), nix::Error> {
+    unshare(CloneFlags::CLONE_NEWNS)?;
+    // Do not propagate the mount changes back to host
+    mount::<str, str, str, str>(
+        None,
+        "/",
+        None,
+        MsFlags::MS_SLAVE | MsFlags::MS_REC,
+        None,
+    )?;
+    umount2("/sys", MntFlags::MNT_DETACH)?;
+    mount::<str, str, str, str>(
+        Some("sysfs"),
+        "/sys",
+        Some("sysfs"),
+        MsFlags::empty(),
+        None,
+    )
+}
@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    MergedNexthops, NexthopEntry, Nexthops, NmstateError, VerificationDiff,
+};
+
+impl MergedNexthops {
+    pub(crate) fn gen_diff(&self) -> Nexthops {
+        let mut changed_nhs = self.changed.clone();
+        changed_nhs.sort_unstable_by_key(|nh| (!nh.is_absent(), nh.id));
+
+        Nexthops {
+            config: if changed_nhs.is_empty() {
+                None
+            } else {
+                Some(changed_nhs)
+            },
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn verify(
+        &self,
+        current: &Nexthops,
+        ignored_ifaces: &[&str],
+    ) -> Result<(), NmstateError> {
+        // NetworkManager does not create nexthop objects, routes referring
+        // them are verified by `MergedRoutes::verify()`.
+        if !self.kernel_only {
+            return Ok(());
+        }
+        let mut cur_nhs: Vec<&NexthopEntry> = Vec::new();
+        if let Some(nhs) = current.config.as_ref() {
+            for cur_nh in nhs {
+                if let Some(iface) = cur_nh.next_hop_iface.as_ref() {
+                    if ignored_ifaces.contains(&iface.as_str()) {
+                        continue;
+                    }
+                }
+                cur_nhs.push(cur_nh);
+            }
+        }
+        let mut desired_nhs: Vec<NexthopEntry> = Vec::new();
+        for nh in self.desired.config.as_deref().unwrap_or_default() {
+            let mut nh = nh.clone();
+            nh.sanitize().ok();
+            desired_nhs.push(nh);
+        }
+
+        for (i, nh) in desired_nhs.iter().enumerate() {
+            if nh.is_absent() {
+                // Ignore absent nexthop when desired matches
+                if desired_nhs
+                    .as_slice()
+                    .iter()
+                    .any(|n| !n.is_absent() && nh.is_match(n))
+                {
+                    continue;
+                }
+                if let Some(cur_nh) =
+                    cur_nhs.as_slice().iter().find(|cur| nh.is_match(cur))
+                {
+                    return Err(NmstateError::new_verification_error(
+                        format!(
+                            "Desired absent nexthop {nh} still found \
+                            after apply: {cur_nh}"
+                        ),
+                        VerificationDiff::new(
+                            format!("nexthops.config[{i}]"),
+                            serde_json::Value::Null,
+                            serde_json::to_value(cur_nh)?,
+                        ),
+                    ));
+                }
+            } else if !cur_nhs.contains(&nh) {
+                return Err(NmstateError::new_verification_error(
+                    format!("Desired nexthop {nh} not found after apply"),
+                    VerificationDiff::new(
+                        format!("nexthops.config[{i}]"),
+                        serde_json::to_value(nh)?,
+                        serde_json::to_value(
+                            cur_nhs
+                                .as_slice()
+                                .iter()
+                                .find(|cur| cur.id == nh.id),
+                        )?,
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,8 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    state::get_json_value_difference, ErrorKind, MergedOvnConfiguration,
-    NmstateError, OvnConfiguration,
+    state::get_json_value_difference, MergedOvnConfiguration, NmstateError,
+    OvnConfiguration, VerificationDiff,
 };
 
 impl MergedOvnConfiguration {
@@ -48,12 +48,16 @@ impl MergedOvnConfiguration {
             &desired_value,
             &current_value,
         ) {
-            Err(NmstateError::new(
-                ErrorKind::VerificationError,
+            Err(NmstateError::new_verification_error(
                 format!(
                     "Verification failure: {reference} desire '{desire}', \
                     current '{current}'"
                 ),
+                VerificationDiff::new(
+                    reference,
+                    desire.clone(),
+                    current.clone(),
+                ),
             ))
         } else {
             Ok(())
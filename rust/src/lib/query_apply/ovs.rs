@@ -3,10 +3,10 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::{
-    state::get_json_value_difference, ErrorKind, Interface, InterfaceState,
-    InterfaceType, Interfaces, MergedInterfaces, MergedOvsDbGlobalConfig,
-    NetworkState, NmstateError, OvsBridgeBondConfig, OvsBridgeConfig,
-    OvsBridgeInterface, OvsDbGlobalConfig, OvsDbIfaceConfig, OvsInterface,
+    state::get_json_value_difference, Interface, InterfaceState, InterfaceType,
+    Interfaces, MergedInterfaces, MergedOvsDbGlobalConfig, NetworkState,
+    NmstateError, OvsBridgeBondConfig, OvsBridgeConfig, OvsBridgeInterface,
+    OvsDbGlobalConfig, OvsDbIfaceConfig, OvsInterface, VerificationDiff,
 };
 
 impl MergedOvsDbGlobalConfig {
@@ -72,12 +72,16 @@ impl MergedOvsDbGlobalConfig {
             &desired_value,
             &current_value,
         ) {
-            Err(NmstateError::new(
-                ErrorKind::VerificationError,
+            Err(NmstateError::new_verification_error(
                 format!(
                     "Verification failure: {reference} desire '{desire}', \
                     current '{current}'"
                 ),
+                VerificationDiff::new(
+                    reference.replacen("ovsdb", "ovs-db", 1),
+                    desire.clone(),
+                    current.clone(),
+                ),
             ))
         } else {
             Ok(())
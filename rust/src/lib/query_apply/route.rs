@@ -3,8 +3,8 @@
 use std::collections::HashSet;
 
 use crate::{
-    ErrorKind, InterfaceType, Interfaces, MergedRoutes, NmstateError,
-    RouteEntry, Routes,
+    InterfaceType, Interfaces, MergedNexthops, MergedRoutes, NmstateError,
+    RouteEntry, RouteProtocol, Routes, VerificationDiff,
 };
 
 impl MergedRoutes {
@@ -18,11 +18,9 @@ impl MergedRoutes {
             }
         }
 
-        for rts in self.merged.values() {
-            for rt in rts {
-                if rt.is_absent() || !current_routes.contains(rt) {
-                    changed_routes.push(rt.clone());
-                }
+        for rt in self.merged.values().flatten().chain(&self.nexthop_routes) {
+            if rt.is_absent() || !current_routes.contains(rt) {
+                changed_routes.push(rt.clone());
             }
         }
 
@@ -36,13 +34,11 @@ impl MergedRoutes {
         }
     }
 
-    fn routes_for_verify(&self) -> Vec<RouteEntry> {
+    fn routes_for_verify(&self, kernel_only: bool) -> Vec<RouteEntry> {
         let mut desired_routes = Vec::new();
         if let Some(rts) = self.desired.config.as_ref() {
             for rt in rts {
-                let mut rt = rt.clone();
-                rt.sanitize().ok();
-                desired_routes.push(rt);
+                desired_routes.push(route_for_verify(rt, kernel_only));
             }
         }
         desired_routes.sort_unstable();
@@ -62,6 +58,19 @@ impl MergedRoutes {
         new_desired_routes
     }
 
+    // Path of desired route entry in the form of `routes.config[<index>]`.
+    fn desired_route_path(&self, rt: &RouteEntry, kernel_only: bool) -> String {
+        self.desired
+            .config
+            .as_deref()
+            .and_then(|rts| {
+                rts.iter()
+                    .position(|r| &route_for_verify(r, kernel_only) == rt)
+            })
+            .map(|i| format!("routes.config[{i}]"))
+            .unwrap_or_else(|| "routes.config".to_string())
+    }
+
     // Kernel might append additional routes. For example, IPv6 default
     // gateway will generate /128 static direct route.
     // Hence, we only check:
@@ -73,13 +82,14 @@ impl MergedRoutes {
         current: &Routes,
         ignored_ifaces: &[&str],
         current_ifaces: &Interfaces,
+        nexthops: &MergedNexthops,
     ) -> Result<(), NmstateError> {
         let mut cur_routes: Vec<&RouteEntry> = Vec::new();
         if let Some(cur_rts) = current.config.as_ref() {
             for cur_rt in cur_rts {
                 if let Some(via) = cur_rt.next_hop_iface.as_ref() {
                     if ignored_ifaces.contains(&via.as_str())
-                        && cur_rt.route_type.is_none()
+                        && cur_rt.has_next_hop()
                     {
                         continue;
                     }
@@ -88,9 +98,10 @@ impl MergedRoutes {
             }
         }
         cur_routes.dedup();
-        let routes_for_verify = self.routes_for_verify();
+        let routes_for_verify = self.routes_for_verify(nexthops.kernel_only);
 
         for mut rt in routes_for_verify.as_slice() {
+            let des_rt = rt;
             if rt.is_absent() {
                 // We do not valid absent route if desire has a match there.
                 // For example, user is changing a gateway.
@@ -106,33 +117,61 @@ impl MergedRoutes {
                     .iter()
                     .find(|cur_rt| rt.is_match(cur_rt))
                 {
-                    return Err(NmstateError::new(
-                        ErrorKind::VerificationError,
+                    return Err(NmstateError::new_verification_error(
                         format!(
                             "Desired absent route {rt} still found \
                             after apply: {cur_rt}",
                         ),
+                        VerificationDiff::new(
+                            self.desired_route_path(rt, nexthops.kernel_only),
+                            serde_json::Value::Null,
+                            serde_json::to_value(cur_rt)?,
+                        ),
                     ));
                 }
             } else {
                 let mut rt2;
-                if rt.route_type.is_some() && !rt.is_ipv6() {
+                if !rt.has_next_hop() && !rt.is_ipv6() {
                     // In nispor, the IPv4 route with route type `Blackhole`,
-                    // `Unreachable`, `Prohibit` does not have the route oif
-                    // setting.
+                    // `Unreachable`, `Prohibit`, `Throw` does not have the
+                    // route oif setting.
                     rt2 = rt.clone();
                     rt2.next_hop_iface = None;
                     rt = &rt2
                 }
 
                 if !cur_routes.iter().any(|cur_rt| rt.is_match(cur_rt)) {
+                    // Backend without nexthop object support stores route
+                    // referring nexthop as ECMP routes of member interfaces.
+                    if rt.nexthop_id.is_some()
+                        && !nexthops.kernel_only
+                        && nexthops
+                            .expand_route(rt)
+                            .map(|rts| {
+                                rts.iter().all(|rt| {
+                                    cur_routes
+                                        .iter()
+                                        .any(|cur_rt| rt.is_match(cur_rt))
+                                })
+                            })
+                            .unwrap_or_default()
+                    {
+                        continue;
+                    }
                     if is_route_delayed_by_nm(rt, current_ifaces) {
                         log::warn!("Route {rt} still missing due to NetworkManager waiting to receive an IP address");
                     }
 
-                    return Err(NmstateError::new(
-                        ErrorKind::VerificationError,
+                    return Err(NmstateError::new_verification_error(
                         format!("Desired route {rt} not found after apply"),
+                        VerificationDiff::new(
+                            self.desired_route_path(
+                                des_rt,
+                                nexthops.kernel_only,
+                            ),
+                            serde_json::to_value(rt)?,
+                            serde_json::Value::Null,
+                        ),
                     ));
                 }
             }
@@ -195,3 +234,15 @@ pub(crate) fn is_route_delayed_by_nm(
 
     !has_address
 }
+
+fn route_for_verify(rt: &RouteEntry, kernel_only: bool) -> RouteEntry {
+    let mut rt = rt.clone();
+    rt.sanitize().ok();
+    // NetworkManager always install route with `static`
+    // protocol, the `boot` protocol of route created by
+    // `ip route` command will be changed after taken over.
+    if !kernel_only && rt.protocol == Some(RouteProtocol::Boot) {
+        rt.protocol = Some(RouteProtocol::Static);
+    }
+    rt
+}
@@ -3,7 +3,8 @@
 use std::collections::HashSet;
 
 use crate::{
-    ErrorKind, MergedRouteRules, NmstateError, RouteRuleEntry, RouteRules,
+    MergedRouteRules, NmstateError, RouteRuleEntry, RouteRules,
+    VerificationDiff,
 };
 
 impl MergedRouteRules {
@@ -36,6 +37,28 @@ impl MergedRouteRules {
         }
     }
 
+    // Path of desired route rule entry in the form of
+    // `route-rules.config[<index>]`. The desired absent rule is matched by
+    // wildcard as `for_verify` holds the current rules it covers.
+    fn desired_rule_path(&self, rule: &RouteRuleEntry) -> String {
+        self.desired
+            .config
+            .as_deref()
+            .and_then(|rules| {
+                rules.iter().position(|r| {
+                    if r.is_absent() {
+                        r.is_match(rule)
+                    } else {
+                        let mut r = r.clone();
+                        r.sanitize().ok();
+                        &r == rule
+                    }
+                })
+            })
+            .map(|i| format!("route-rules.config[{i}]"))
+            .unwrap_or_else(|| "route-rules.config".to_string())
+    }
+
     pub(crate) fn verify(
         &self,
         current: &RouteRules,
@@ -44,10 +67,12 @@ impl MergedRouteRules {
         let mut cur_rules: Vec<&RouteRuleEntry> = Vec::new();
         if let Some(rules) = current.config.as_ref() {
             for cur_rule in rules {
-                if let Some(iif) = cur_rule.iif.as_ref() {
-                    if ignored_ifaces.contains(&iif.as_str()) {
-                        continue;
-                    }
+                if [cur_rule.iif.as_deref(), cur_rule.oif.as_deref()]
+                    .into_iter()
+                    .flatten()
+                    .any(|i| ignored_ifaces.contains(&i))
+                {
+                    continue;
                 }
                 cur_rules.push(cur_rule);
             }
@@ -68,12 +93,16 @@ impl MergedRouteRules {
                     .iter()
                     .find(|cur_r| rule.is_match(cur_r))
                 {
-                    return Err(NmstateError::new(
-                        ErrorKind::VerificationError,
+                    return Err(NmstateError::new_verification_error(
                         format!(
                             "Desired absent route rule {rule} still found \
                             after apply: {cur_rt}"
                         ),
+                        VerificationDiff::new(
+                            self.desired_rule_path(rule),
+                            serde_json::Value::Null,
+                            serde_json::to_value(cur_rt)?,
+                        ),
                     ));
                 }
             } else if !cur_rules
@@ -81,9 +110,13 @@ impl MergedRouteRules {
                 .iter()
                 .any(|cur_r| rule.is_match(cur_r))
             {
-                return Err(NmstateError::new(
-                    ErrorKind::VerificationError,
+                return Err(NmstateError::new_verification_error(
                     format!("Desired route rule {rule} not found after apply"),
+                    VerificationDiff::new(
+                        self.desired_rule_path(rule),
+                        serde_json::to_value(rule)?,
+                        serde_json::Value::Null,
+                    ),
                 ));
             }
         }
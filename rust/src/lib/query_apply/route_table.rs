@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+
+use crate::{
+    route_table::RT_TABLES_DIR, ErrorKind, NetworkState, NmstateError,
+    RouteTableNames,
+};
+
+const NMSTATE_RT_TABLES_FILE: &str = "nmstate.conf";
+
+impl RouteTableNames {
+    pub(crate) fn retrieve() -> Self {
+        let mut ret = Self::default();
+        ret.load_system();
+        ret
+    }
+
+    pub(crate) fn from_new_entries(new_entries: Vec<(u32, String)>) -> Self {
+        let mut ret = Self::default();
+        ret.new_entries = new_entries;
+        ret
+    }
+
+    /// Append route table names created by nmstate to
+    /// `/etc/iproute2/rt_tables.d/nmstate.conf`.
+    /// Should only be invoked after checkpoint committed.
+    pub(crate) fn save_new_entries(&self) -> Result<(), NmstateError> {
+        self.save_new_entries_to(RT_TABLES_DIR)
+    }
+
+    pub(crate) fn save_new_entries_to(
+        &self,
+        dir_path: &str,
+    ) -> Result<(), NmstateError> {
+        if self.new_entries.is_empty() {
+            return Ok(());
+        }
+        let file_path = format!("{dir_path}/{NMSTATE_RT_TABLES_FILE}");
+
+        // Apply might retry, do not store duplicate entries
+        let mut saved = Self::default();
+        let existing = std::fs::read_to_string(&file_path).unwrap_or_default();
+        saved.parse(&existing);
+
+        let mut content = String::new();
+        if existing.is_empty() {
+            content.push_str("# Route table names created by nmstate\n");
+        } else if !existing.ends_with('\n') {
+            content.push('\n');
+        }
+        for (table_id, name) in self.new_entries.as_slice() {
+            if saved.get_id(name) != Some(*table_id) {
+                content.push_str(&format!("{table_id} {name}\n"));
+            }
+        }
+        if content.trim().is_empty() {
+            return Ok(());
+        }
+
+        std::fs::create_dir_all(dir_path).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::PermissionError,
+                format!("Failed to create folder {dir_path}: {e}"),
+            )
+        })?;
+        log::info!("Storing route table names to {file_path}");
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&file_path)
+            .and_then(|mut fd| fd.write_all(content.as_bytes()))
+            .map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::PermissionError,
+                    format!("Failed to write {file_path}: {e}"),
+                )
+            })
+    }
+}
+
+impl NetworkState {
+    // Show route table name for routes and route rules using route table
+    // defined in `rt_tables`.
+    pub(crate) fn fill_route_table_names(&mut self) {
+        let names = RouteTableNames::retrieve();
+        for rt in self
+            .routes
+            .running
+            .iter_mut()
+            .chain(self.routes.config.iter_mut())
+            .flatten()
+        {
+            if let Some(table_id) = rt.table_id {
+                rt.table_name = names.get_name(table_id).map(|n| n.to_string());
+            }
+        }
+        for rule in self.rules.config.iter_mut().flatten() {
+            if let Some(table_id) = rule.table_id {
+                rule.table_name =
+                    names.get_name(table_id).map(|n| n.to_string());
+            }
+        }
+    }
+}
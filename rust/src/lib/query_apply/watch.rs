@@ -0,0 +1,416 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+use tokio::sync::mpsc::{
+    unbounded_channel, UnboundedReceiver, UnboundedSender,
+};
+
+use crate::{
+    nispor::{
+        get_dns, get_hostname_state, get_route_rules, get_routes,
+        nispor_monitor_start, nispor_retrieve_ifaces, nispor_retrieve_nexthops,
+    },
+    nm::{nm_monitor_start, nm_retrieve},
+    ovsdb::{ovsdb_is_running, ovsdb_monitor_start, ovsdb_retrieve},
+    InterfaceState, Interfaces, NetworkState, NmstateError,
+};
+
+// Kernel and NetworkManager emit burst of notifications for single change,
+// wait a while after the first one to merge them into single query.
+const WATCH_SETTLE_MILLISECONDS: u64 = 100;
+
+/// Change notified by kernel, NetworkManager or OVSDB monitors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum WatchEvent {
+    /// Interface of specified name changed or removed.
+    Iface(String),
+    /// Some interfaces changed, but monitor cannot tell which.
+    Ifaces,
+    Nexthops,
+    Routes,
+    RouteRules,
+    Dns,
+    Hostname,
+    /// Global config of OVSDB changed.
+    Ovsdb,
+    /// Notifications lost, everything should be queried.
+    All,
+}
+
+pub(crate) type WatchEventSender = UnboundedSender<WatchEvent>;
+
+// Parts of network state to query, accumulated from [WatchEvent]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct WatchScope {
+    pub(crate) all: bool,
+    pub(crate) all_ifaces: bool,
+    pub(crate) ifaces: HashSet<String>,
+    pub(crate) nexthops: bool,
+    pub(crate) routes: bool,
+    pub(crate) rules: bool,
+    pub(crate) dns: bool,
+    pub(crate) hostname: bool,
+    pub(crate) ovsdb: bool,
+}
+
+impl WatchScope {
+    pub(crate) fn add(&mut self, event: WatchEvent) {
+        match event {
+            WatchEvent::Iface(name) => {
+                self.ifaces.insert(name);
+            }
+            WatchEvent::Ifaces => self.all_ifaces = true,
+            WatchEvent::Nexthops => self.nexthops = true,
+            WatchEvent::Routes => self.routes = true,
+            WatchEvent::RouteRules => self.rules = true,
+            WatchEvent::Dns => self.dns = true,
+            WatchEvent::Hostname => self.hostname = true,
+            WatchEvent::Ovsdb => self.ovsdb = true,
+            WatchEvent::All => self.all = true,
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    fn has_ifaces(&self) -> bool {
+        self.all_ifaces || !self.ifaces.is_empty()
+    }
+}
+
+type WatchQuery =
+    Pin<Box<dyn Future<Output = Result<NetworkState, NmstateError>> + Send>>;
+
+/// Stream of network state changes created by [NetworkState::watch()].
+///
+/// Each item is a partial [NetworkState] containing only the changed
+/// sections:
+///  * Interfaces added or changed are included in full. Removed interfaces
+///    are included with name, type and `state: absent`.
+///  * Nexthops, routes, route rules, DNS and hostname are included in full
+///    when changed.
+///
+/// Only the interfaces and sections notified by monitors are queried.
+/// The stream ends when all the monitors stopped.
+/// Requires tokio runtime with IO and time drivers enabled.
+pub struct NetworkStateWatcher {
+    receiver: UnboundedReceiver<WatchEvent>,
+    closed: bool,
+    current: NetworkState,
+    scope: WatchScope,
+    settle: Option<Pin<Box<tokio::time::Sleep>>>,
+    query: Option<WatchQuery>,
+}
+
+impl std::fmt::Debug for NetworkStateWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NetworkStateWatcher")
+            .field("closed", &self.closed)
+            .field("current", &self.current)
+            .field("scope", &self.scope)
+            .field("querying", &self.query.is_some())
+            .finish()
+    }
+}
+
+impl NetworkStateWatcher {
+    /// The network state as of the last emitted change.
+    pub fn current(&self) -> &NetworkState {
+        &self.current
+    }
+}
+
+impl Stream for NetworkStateWatcher {
+    type Item = Result<NetworkState, NmstateError>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let watcher = self.get_mut();
+        loop {
+            // Events arrived during querying are kept for next query
+            while !watcher.closed {
+                match watcher.receiver.poll_recv(cx) {
+                    Poll::Ready(Some(event)) => {
+                        log::debug!("Got network state change {event:?}");
+                        watcher.scope.add(event);
+                    }
+                    Poll::Ready(None) => watcher.closed = true,
+                    Poll::Pending => break,
+                }
+            }
+            if let Some(query) = watcher.query.as_mut() {
+                let result = ready!(query.as_mut().poll(cx));
+                watcher.query = None;
+                let new_state = match result {
+                    Ok(s) => s,
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                };
+                let delta = gen_watch_delta(&watcher.current, &new_state);
+                watcher.current = new_state;
+                if !delta.is_empty() {
+                    return Poll::Ready(Some(Ok(delta)));
+                }
+                continue;
+            }
+            if watcher.scope.is_empty() {
+                return if watcher.closed {
+                    Poll::Ready(None)
+                } else {
+                    Poll::Pending
+                };
+            }
+            let settle = watcher.settle.get_or_insert_with(|| {
+                Box::pin(tokio::time::sleep(Duration::from_millis(
+                    WATCH_SETTLE_MILLISECONDS,
+                )))
+            });
+            ready!(settle.as_mut().poll(cx));
+            watcher.settle = None;
+            let scope = std::mem::take(&mut watcher.scope);
+            let current = watcher.current.clone();
+            watcher.query = Some(Box::pin(async move {
+                let queried = retrieve_watch_scope(&current, &scope).await?;
+                Ok(merge_watch_scope(current, queried, &scope))
+            }));
+        }
+    }
+}
+
+impl NetworkState {
+    /// Subscribe to network state changes from kernel rtnetlink multicast
+    /// groups, NetworkManager D-Bus signals(skipped in kernel only mode)
+    /// and OVSDB `monitor` RPC(when OVS daemon is running).
+    /// The kernel only, running config only and include secrets flags of
+    /// this [NetworkState] are honored when querying.
+    /// Only available for feature `query_apply`.
+    pub async fn watch(&self) -> Result<NetworkStateWatcher, NmstateError> {
+        let (sender, receiver) = unbounded_channel();
+        // Start monitors before the initial query, so change in between is
+        // not lost.
+        nispor_monitor_start(sender.clone())?;
+        if !self.kernel_only {
+            nm_monitor_start(sender.clone())?;
+        }
+        if ovsdb_is_running() {
+            ovsdb_monitor_start(sender)?;
+        }
+        let mut current = self.clone_watch_flags();
+        current.retrieve_async().await?;
+        Ok(NetworkStateWatcher {
+            receiver,
+            closed: false,
+            current,
+            scope: WatchScope::default(),
+            settle: None,
+            query: None,
+        })
+    }
+
+    fn clone_watch_flags(&self) -> Self {
+        Self {
+            kernel_only: self.kernel_only,
+            running_config_only: self.running_config_only,
+            include_secrets: self.include_secrets,
+            ..Default::default()
+        }
+    }
+}
+
+// Query the sections in scope only, the same way as
+// `NetworkState::retrieve_async()` does.
+async fn retrieve_watch_scope(
+    current: &NetworkState,
+    scope: &WatchScope,
+) -> Result<NetworkState, NmstateError> {
+    let mut state = current.clone_watch_flags();
+    if scope.all {
+        state.retrieve_async().await?;
+        return Ok(state);
+    }
+    let running_config_only = state.running_config_only;
+    if scope.has_ifaces() {
+        state.interfaces =
+            nispor_retrieve_ifaces(running_config_only, state.kernel_only)
+                .await?;
+    }
+    if (scope.has_ifaces() || scope.ovsdb) && ovsdb_is_running() {
+        match ovsdb_retrieve() {
+            Ok(mut ovsdb_state) => {
+                ovsdb_state.isolate_ovn()?;
+                state.update_state(&ovsdb_state);
+            }
+            Err(e) => {
+                log::warn!("Failed to retrieve OVS DB state: {}", e);
+            }
+        }
+    }
+    if scope.dns && state.kernel_only {
+        state.dns = get_dns();
+    }
+    if (scope.has_ifaces() || scope.dns) && !state.kernel_only {
+        let nm_state = nm_retrieve(running_config_only)?;
+        state.update_state(&nm_state);
+    }
+    if scope.nexthops {
+        state.nexthops = nispor_retrieve_nexthops(running_config_only).await?;
+    }
+    if scope.routes {
+        state.routes = get_routes(running_config_only).await;
+    }
+    if scope.rules {
+        state.rules = get_route_rules(running_config_only)?;
+    }
+    if scope.hostname {
+        state.hostname = get_hostname_state();
+    }
+    state.fill_route_table_names();
+    if !state.include_secrets {
+        state.hide_secrets();
+    }
+    state
+        .interfaces
+        .user_ifaces
+        .retain(|_, iface| !iface.is_ignore());
+    Ok(state)
+}
+
+// Replace the sections and interfaces in scope of `current` with the
+// queried ones.
+// The controller and ports of changed interface are also replaced as
+// their port config or controller might changed along.
+pub(crate) fn merge_watch_scope(
+    mut current: NetworkState,
+    queried: NetworkState,
+    scope: &WatchScope,
+) -> NetworkState {
+    if scope.all {
+        return queried;
+    }
+    if scope.all_ifaces {
+        current.interfaces = queried.interfaces;
+    } else if !scope.ifaces.is_empty() {
+        let mut iface_names = scope.ifaces.clone();
+        for ifaces in [&current.interfaces, &queried.interfaces] {
+            for iface in ifaces.iter() {
+                if scope.ifaces.contains(iface.name()) {
+                    if let Some(ctrl) = iface.base_iface().controller.as_ref() {
+                        iface_names.insert(ctrl.to_string());
+                    }
+                    for port in iface.ports().unwrap_or_default() {
+                        iface_names.insert(port.to_string());
+                    }
+                }
+            }
+        }
+        for iface_name in iface_names.iter() {
+            replace_ifaces_by_name(
+                &mut current.interfaces,
+                &queried.interfaces,
+                iface_name,
+            );
+        }
+    }
+    if scope.nexthops {
+        current.nexthops = queried.nexthops;
+    }
+    if scope.routes {
+        current.routes = queried.routes;
+    }
+    if scope.rules {
+        current.rules = queried.rules;
+    }
+    if scope.dns {
+        current.dns = queried.dns;
+    }
+    if scope.hostname {
+        current.hostname = queried.hostname;
+    }
+    if scope.ovsdb {
+        current.ovsdb = queried.ovsdb;
+    }
+    current
+}
+
+// Both kernel and user space interfaces might use the same name.
+fn replace_ifaces_by_name(
+    current: &mut Interfaces,
+    queried: &Interfaces,
+    iface_name: &str,
+) {
+    current.kernel_ifaces.remove(iface_name);
+    current
+        .user_ifaces
+        .retain(|(name, _), _| name != iface_name);
+    current.insert_order.retain(|(name, _)| name != iface_name);
+    for iface in queried.iter().filter(|i| i.name() == iface_name) {
+        current.push(iface.clone());
+    }
+}
+
+pub(crate) fn gen_watch_delta(
+    old: &NetworkState,
+    new: &NetworkState,
+) -> NetworkState {
+    let mut ret = NetworkState::new();
+    for iface in new.interfaces.iter() {
+        if old.interfaces.get_iface(iface.name(), iface.iface_type())
+            != Some(iface)
+        {
+            ret.interfaces.push(iface.clone());
+        }
+    }
+    for iface in old.interfaces.iter() {
+        if new
+            .interfaces
+            .get_iface(iface.name(), iface.iface_type())
+            .is_none()
+        {
+            let mut absent_iface = iface.clone_name_type_only();
+            absent_iface.base_iface_mut().state = InterfaceState::Absent;
+            ret.interfaces.push(absent_iface);
+        }
+    }
+    if old.nexthops != new.nexthops {
+        ret.nexthops = new.nexthops.clone();
+        // Make sure removing all nexthops is not treated as no change
+        if ret.nexthops.running.is_none() && old.nexthops.running.is_some() {
+            ret.nexthops.running = Some(Vec::new());
+        }
+        if ret.nexthops.config.is_none() && old.nexthops.config.is_some() {
+            ret.nexthops.config = Some(Vec::new());
+        }
+    }
+    if old.routes != new.routes {
+        ret.routes = new.routes.clone();
+        // Make sure removing all routes is not treated as no change
+        if ret.routes.running.is_none() && old.routes.running.is_some() {
+            ret.routes.running = Some(Vec::new());
+        }
+        if ret.routes.config.is_none() && old.routes.config.is_some() {
+            ret.routes.config = Some(Vec::new());
+        }
+    }
+    if old.rules != new.rules {
+        ret.rules.config =
+            Some(new.rules.config.as_ref().cloned().unwrap_or_default());
+    }
+    if old.dns != new.dns {
+        ret.dns = Some(new.dns.as_ref().cloned().unwrap_or_default());
+    }
+    if old.hostname != new.hostname {
+        ret.hostname = Some(new.hostname.as_ref().cloned().unwrap_or_default());
+    }
+    if old.ovsdb != new.ovsdb {
+        ret.ovsdb = Some(new.ovsdb.as_ref().cloned().unwrap_or_default());
+    }
+    ret
+}
@@ -4,6 +4,7 @@ mod dns;
 mod hostname;
 mod ifaces;
 mod net_state;
+mod nexthop;
 mod ovn;
 mod ovsdb;
 mod route;
@@ -15,6 +15,7 @@ impl NetworkState {
         )?;
         Ok(Self {
             interfaces: merged_state.interfaces.generate_revert()?,
+            nexthops: merged_state.nexthops.generate_revert(),
             routes: merged_state.routes.generate_revert(),
             rules: merged_state.rules.generate_revert(),
             dns: merged_state.dns.generate_revert(),
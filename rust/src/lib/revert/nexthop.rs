@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MergedNexthops, NexthopEntry, NexthopState, Nexthops};
+
+impl MergedNexthops {
+    pub(crate) fn generate_revert(&self) -> Nexthops {
+        let mut revert_nhs: Vec<NexthopEntry> = Vec::new();
+        let empty_vec: Vec<NexthopEntry> = Vec::new();
+        let current_nhs = self.current.config.as_ref().unwrap_or(&empty_vec);
+
+        for des_nh in self.desired.config.as_ref().unwrap_or(&empty_vec) {
+            if des_nh.is_absent() {
+                // Add back the deleted nexthops
+                for cur_nh in current_nhs.iter().filter(|n| des_nh.is_match(n))
+                {
+                    revert_nhs.push(cur_nh.clone());
+                }
+            } else if let Some(cur_nh) =
+                current_nhs.iter().find(|n| n.id == des_nh.id)
+            {
+                // Restore the replaced nexthop
+                revert_nhs.push(cur_nh.clone());
+            } else {
+                // Delete the added nexthop
+                let mut nh = NexthopEntry::new();
+                nh.id = des_nh.id;
+                nh.state = Some(NexthopState::Absent);
+                revert_nhs.push(nh);
+            }
+        }
+
+        revert_nhs.sort_unstable_by_key(|nh| (!nh.is_absent(), nh.id));
+        revert_nhs.dedup();
+
+        if revert_nhs.is_empty() {
+            Nexthops::default()
+        } else {
+            Nexthops {
+                config: Some(revert_nhs),
+                ..Default::default()
+            }
+        }
+    }
+}
@@ -9,7 +9,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     ip::{is_ipv6_addr, sanitize_ip_network},
-    ErrorKind, InterfaceType, MergedInterfaces, NmstateError,
+    ErrorKind, InterfaceType, MergedInterfaces, MergedNexthops, NmstateError,
+    RouteTableNames,
 };
 
 const DEFAULT_TABLE_ID: u32 = 254; // main route table ID
@@ -67,13 +68,41 @@ impl Routes {
         self.running.is_none() && self.config.is_none()
     }
 
+    pub(crate) fn resolve_table_names(
+        &mut self,
+        route_table_names: &RouteTableNames,
+    ) -> Result<(), NmstateError> {
+        if let Some(rts) = self.config.as_mut() {
+            for rt in rts {
+                rt.resolve_table_name(route_table_names)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn validate(&self) -> Result<(), NmstateError> {
         // All desire non-absent route should have next hop interface except
-        // for route with route type `Blackhole`, `Unreachable`, `Prohibit`.
+        // for route with route type `Blackhole`, `Unreachable`, `Prohibit`,
+        // `Throw` or route referring nexthop object.
         if let Some(config_routes) = self.config.as_ref() {
             for route in config_routes.iter() {
                 if !route.is_absent() {
-                    if !route.is_unicast()
+                    if route.nexthop_id.is_some() {
+                        if !route.is_unicast()
+                            || route.next_hop_iface.is_some()
+                            || route.next_hop_addr.is_some()
+                            || route.weight.is_some()
+                        {
+                            return Err(NmstateError::new(
+                                ErrorKind::InvalidArgument,
+                                format!(
+                                    "Route referring nexthop object cannot \
+                                    have route-type, next-hop-interface, \
+                                    next-hop-address or weight: {route:?}"
+                                ),
+                            ));
+                        }
+                    } else if !route.has_next_hop()
                         && (route.next_hop_iface.is_some()
                             && route.next_hop_iface
                                 != Some(LOOPBACK_IFACE_NAME.to_string())
@@ -87,8 +116,23 @@ impl Routes {
                                 route.route_type.unwrap()
                             ),
                         ));
+                    } else if !route.is_unicast()
+                        && route
+                            .next_hop_addr
+                            .as_deref()
+                            .map(|a| !a.is_empty())
+                            .unwrap_or_default()
+                    {
+                        return Err(NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "A {:?} Route cannot have a next \
+                                hop address: {route:?}",
+                                route.route_type.unwrap()
+                            ),
+                        ));
                     } else if route.next_hop_iface.is_none()
-                        && route.is_unicast()
+                        && route.has_next_hop()
                     {
                         return Err(NmstateError::new(
                             ErrorKind::NotImplementedError,
@@ -140,7 +184,7 @@ pub struct RouteEntry {
     /// Route next hop interface name.
     /// Serialize and deserialize to/from `next-hop-interface`.
     /// Mandatory for every non-absent routes except for route with
-    /// route type `Blackhole`, `Unreachable`, `Prohibit`.
+    /// route type `Blackhole`, `Unreachable`, `Prohibit`, `Throw`.
     pub next_hop_iface: Option<String>,
     #[serde(
         skip_serializing_if = "Option::is_none",
@@ -167,6 +211,11 @@ pub struct RouteEntry {
     /// Route table id. [RouteEntry::USE_DEFAULT_ROUTE_TABLE] for main
     /// route table 254.
     pub table_id: Option<u32>,
+    /// Route table name defined in `/etc/iproute2/rt_tables` or
+    /// `/etc/iproute2/rt_tables.d/*.conf`. When applying, resolved to
+    /// [RouteEntry.table_id].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_name: Option<String>,
 
     /// ECMP(Equal-Cost Multi-Path) route weight
     /// The valid range of this property is 1-256.
@@ -183,6 +232,84 @@ pub struct RouteEntry {
     /// Congestion window clamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cwnd: Option<u32>,
+    /// Preferred source address when sending to destinations covered by
+    /// this route.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Path MTU of this route.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub mtu: Option<u32>,
+    /// Lock the route MTU to prevent kernel from updating it via path MTU
+    /// discovery.
+    /// Serialize and deserialize to/from `lock-mtu`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub lock_mtu: Option<bool>,
+    /// Maximal segment size advertised to these destinations when
+    /// establishing TCP connections.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub advmss: Option<u32>,
+    /// Initial congestion window size for connections to this destination.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub initcwnd: Option<u32>,
+    /// Initial receive window size for connections to this destination.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub initrwnd: Option<u32>,
+    /// Enable quick ACK for connections to this destination.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub quickack: Option<bool>,
+    /// Pretend that the next hop is directly attached to this link, even if
+    /// it does not match any interface prefix.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub onlink: Option<bool>,
+    /// Scope of the destinations covered by the route.
+    /// When querying, only shown if not the kernel default which is `host`
+    /// for local route, `link` for broadcast, anycast, multicast route and
+    /// unicast route without gateway, `universe` for others.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<RouteScope>,
+    /// Routing protocol identifier of this route.
+    /// NetworkManager always uses [RouteProtocol::Static].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<RouteProtocol>,
+    /// ID of nexthop object defined in [crate::Nexthops] to forward packets
+    /// to. Cannot be used with `next-hop-interface`, `next-hop-address` and
+    /// `weight`.
+    /// Serialize and deserialize to/from `nexthop-id`.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "nexthop-id",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub nexthop_id: Option<u32>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -193,6 +320,17 @@ pub enum RouteType {
     Blackhole,
     Unreachable,
     Prohibit,
+    /// Stop the lookup in current table and continue the policy routing
+    /// lookup with next route rule.
+    Throw,
+    /// Destinations are assigned to this host.
+    Local,
+    /// Destinations are broadcast addresses.
+    Broadcast,
+    /// Destinations are anycast addresses assigned to this host.
+    Anycast,
+    /// Multicast route.
+    Multicast,
 }
 
 impl std::fmt::Display for RouteType {
@@ -204,15 +342,25 @@ impl std::fmt::Display for RouteType {
                 Self::Blackhole => "blackhole",
                 Self::Unreachable => "unreachable",
                 Self::Prohibit => "prohibit",
+                Self::Throw => "throw",
+                Self::Local => "local",
+                Self::Broadcast => "broadcast",
+                Self::Anycast => "anycast",
+                Self::Multicast => "multicast",
             }
         )
     }
 }
 
 const RTN_UNICAST: u8 = 1;
+const RTN_LOCAL: u8 = 2;
+const RTN_BROADCAST: u8 = 3;
+const RTN_ANYCAST: u8 = 4;
+const RTN_MULTICAST: u8 = 5;
 const RTN_BLACKHOLE: u8 = 6;
 const RTN_UNREACHABLE: u8 = 7;
 const RTN_PROHIBIT: u8 = 8;
+const RTN_THROW: u8 = 9;
 
 impl From<RouteType> for u8 {
     fn from(v: RouteType) -> u8 {
@@ -220,6 +368,221 @@ impl From<RouteType> for u8 {
             RouteType::Blackhole => RTN_BLACKHOLE,
             RouteType::Unreachable => RTN_UNREACHABLE,
             RouteType::Prohibit => RTN_PROHIBIT,
+            RouteType::Throw => RTN_THROW,
+            RouteType::Local => RTN_LOCAL,
+            RouteType::Broadcast => RTN_BROADCAST,
+            RouteType::Anycast => RTN_ANYCAST,
+            RouteType::Multicast => RTN_MULTICAST,
+        }
+    }
+}
+
+impl TryFrom<u8> for RouteType {
+    type Error = NmstateError;
+    fn try_from(v: u8) -> Result<Self, NmstateError> {
+        match v {
+            RTN_BLACKHOLE => Ok(Self::Blackhole),
+            RTN_UNREACHABLE => Ok(Self::Unreachable),
+            RTN_PROHIBIT => Ok(Self::Prohibit),
+            RTN_THROW => Ok(Self::Throw),
+            RTN_LOCAL => Ok(Self::Local),
+            RTN_BROADCAST => Ok(Self::Broadcast),
+            RTN_ANYCAST => Ok(Self::Anycast),
+            RTN_MULTICAST => Ok(Self::Multicast),
+            _ => Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!("Unsupported route type {v}"),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum RouteScope {
+    /// Global route.
+    /// Deserialize and serialize from/to `universe`, also deserialize from
+    /// `global`.
+    #[serde(alias = "global")]
+    Universe,
+    /// Interior route in the local autonomous system.
+    Site,
+    /// Route on this link.
+    Link,
+    /// Route on the local host.
+    Host,
+}
+
+impl std::fmt::Display for RouteScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Universe => "universe",
+                Self::Site => "site",
+                Self::Link => "link",
+                Self::Host => "host",
+            }
+        )
+    }
+}
+
+const RT_SCOPE_UNIVERSE: u8 = 0;
+const RT_SCOPE_SITE: u8 = 200;
+const RT_SCOPE_LINK: u8 = 253;
+const RT_SCOPE_HOST: u8 = 254;
+
+impl From<RouteScope> for u8 {
+    fn from(v: RouteScope) -> u8 {
+        match v {
+            RouteScope::Universe => RT_SCOPE_UNIVERSE,
+            RouteScope::Site => RT_SCOPE_SITE,
+            RouteScope::Link => RT_SCOPE_LINK,
+            RouteScope::Host => RT_SCOPE_HOST,
+        }
+    }
+}
+
+impl TryFrom<u8> for RouteScope {
+    type Error = NmstateError;
+    fn try_from(v: u8) -> Result<Self, NmstateError> {
+        match v {
+            RT_SCOPE_UNIVERSE => Ok(Self::Universe),
+            RT_SCOPE_SITE => Ok(Self::Site),
+            RT_SCOPE_LINK => Ok(Self::Link),
+            RT_SCOPE_HOST => Ok(Self::Host),
+            _ => Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!("Unsupported route scope {v}"),
+            )),
+        }
+    }
+}
+
+impl FromStr for RouteScope {
+    type Err = NmstateError;
+    fn from_str(s: &str) -> Result<Self, NmstateError> {
+        match s {
+            "universe" | "global" => Ok(Self::Universe),
+            "site" => Ok(Self::Site),
+            "link" => Ok(Self::Link),
+            "host" => Ok(Self::Host),
+            _ => match s.parse::<u8>() {
+                Ok(v) => Self::try_from(v),
+                Err(_) => Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid route scope {s}, expecting \
+                        universe|global|site|link|host"
+                    ),
+                )),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum RouteProtocol {
+    /// Route installed during boot, often used by `iproute` command.
+    Boot,
+    /// Route installed by administrator.
+    Static,
+    /// Route installed by IPv6 router advertisement.
+    Ra,
+    /// Route installed by DHCP client.
+    Dhcp,
+    /// Route installed by multicast daemon.
+    Mrouted,
+    /// Route installed by keepalived daemon.
+    Keepalived,
+    /// Route installed by babel daemon.
+    Babel,
+}
+
+impl std::fmt::Display for RouteProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Boot => "boot",
+                Self::Static => "static",
+                Self::Ra => "ra",
+                Self::Dhcp => "dhcp",
+                Self::Mrouted => "mrouted",
+                Self::Keepalived => "keepalived",
+                Self::Babel => "babel",
+            }
+        )
+    }
+}
+
+const RTPROT_BOOT: u8 = 3;
+const RTPROT_STATIC: u8 = 4;
+const RTPROT_RA: u8 = 9;
+const RTPROT_DHCP: u8 = 16;
+const RTPROT_MROUTED: u8 = 17;
+const RTPROT_KEEPALIVED: u8 = 18;
+const RTPROT_BABEL: u8 = 42;
+
+impl From<RouteProtocol> for u8 {
+    fn from(v: RouteProtocol) -> u8 {
+        match v {
+            RouteProtocol::Boot => RTPROT_BOOT,
+            RouteProtocol::Static => RTPROT_STATIC,
+            RouteProtocol::Ra => RTPROT_RA,
+            RouteProtocol::Dhcp => RTPROT_DHCP,
+            RouteProtocol::Mrouted => RTPROT_MROUTED,
+            RouteProtocol::Keepalived => RTPROT_KEEPALIVED,
+            RouteProtocol::Babel => RTPROT_BABEL,
+        }
+    }
+}
+
+impl TryFrom<u8> for RouteProtocol {
+    type Error = NmstateError;
+    fn try_from(v: u8) -> Result<Self, NmstateError> {
+        match v {
+            RTPROT_BOOT => Ok(Self::Boot),
+            RTPROT_STATIC => Ok(Self::Static),
+            RTPROT_RA => Ok(Self::Ra),
+            RTPROT_DHCP => Ok(Self::Dhcp),
+            RTPROT_MROUTED => Ok(Self::Mrouted),
+            RTPROT_KEEPALIVED => Ok(Self::Keepalived),
+            RTPROT_BABEL => Ok(Self::Babel),
+            _ => Err(NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!("Unsupported route protocol {v}"),
+            )),
+        }
+    }
+}
+
+impl FromStr for RouteProtocol {
+    type Err = NmstateError;
+    fn from_str(s: &str) -> Result<Self, NmstateError> {
+        match s {
+            "boot" => Ok(Self::Boot),
+            "static" => Ok(Self::Static),
+            "ra" => Ok(Self::Ra),
+            "dhcp" => Ok(Self::Dhcp),
+            "mrouted" => Ok(Self::Mrouted),
+            "keepalived" => Ok(Self::Keepalived),
+            "babel" => Ok(Self::Babel),
+            _ => match s.parse::<u8>() {
+                Ok(v) => Self::try_from(v),
+                Err(_) => Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid route protocol {s}, expecting \
+                        boot|static|ra|dhcp|mrouted|keepalived|babel"
+                    ),
+                )),
+            },
         }
     }
 }
@@ -271,13 +634,74 @@ impl RouteEntry {
         if self.cwnd.is_some() && self.cwnd != other.cwnd {
             return false;
         }
+        if self.source.is_some() && self.source != other.source {
+            return false;
+        }
+        if self.mtu.is_some() && self.mtu != other.mtu {
+            return false;
+        }
+        if self.lock_mtu.is_some()
+            && self.lock_mtu.unwrap_or_default()
+                != other.lock_mtu.unwrap_or_default()
+        {
+            return false;
+        }
+        if self.advmss.is_some() && self.advmss != other.advmss {
+            return false;
+        }
+        if self.initcwnd.is_some() && self.initcwnd != other.initcwnd {
+            return false;
+        }
+        if self.initrwnd.is_some() && self.initrwnd != other.initrwnd {
+            return false;
+        }
+        if self.quickack.is_some()
+            && self.quickack.unwrap_or_default()
+                != other.quickack.unwrap_or_default()
+        {
+            return false;
+        }
+        if self.onlink.is_some()
+            && self.onlink.unwrap_or_default()
+                != other.onlink.unwrap_or_default()
+        {
+            return false;
+        }
+        // Route scope is not shown when it is the kernel default
+        if self.scope.is_some()
+            && self.scope
+                != Some(other.scope.unwrap_or_else(|| other.default_scope()))
+        {
+            return false;
+        }
+        if self.protocol.is_some() && self.protocol != other.protocol {
+            return false;
+        }
+        if self.nexthop_id.is_some() && self.nexthop_id != other.nexthop_id {
+            return false;
+        }
         true
     }
 
     // Return tuple of (no_absent, is_ipv4, table_id, next_hop_iface,
-    // destination, next_hop_addr, weight, cwnd)
-    // Metric is ignored
-    fn sort_key(&self) -> (bool, bool, u32, &str, &str, &str, u16, u32) {
+    // destination, next_hop_addr, weight, cwnd, (source, mtu, lock_mtu,
+    // advmss, initcwnd, initrwnd, quickack, onlink), nexthop_id)
+    // Metric, scope and protocol are ignored
+    #[allow(clippy::type_complexity)]
+    fn sort_key(
+        &self,
+    ) -> (
+        bool,
+        bool,
+        u32,
+        &str,
+        &str,
+        &str,
+        u16,
+        u32,
+        (&str, u32, bool, u32, u32, u32, bool, bool),
+        u32,
+    ) {
         (
             !matches!(self.state, Some(RouteState::Absent)),
             !self
@@ -293,9 +717,41 @@ impl RouteEntry {
             self.next_hop_addr.as_deref().unwrap_or(""),
             self.weight.unwrap_or_default(),
             self.cwnd.unwrap_or_default(),
+            (
+                self.source.as_deref().unwrap_or(""),
+                self.mtu.unwrap_or_default(),
+                self.lock_mtu.unwrap_or_default(),
+                self.advmss.unwrap_or_default(),
+                self.initcwnd.unwrap_or_default(),
+                self.initrwnd.unwrap_or_default(),
+                self.quickack.unwrap_or_default(),
+                self.onlink.unwrap_or_default(),
+            ),
+            self.nexthop_id.unwrap_or_default(),
         )
     }
 
+    // Set the `table_id` from `table_name`
+    pub(crate) fn resolve_table_name(
+        &mut self,
+        route_table_names: &RouteTableNames,
+    ) -> Result<(), NmstateError> {
+        if let Some(name) = self.table_name.as_deref() {
+            match route_table_names.resolve(self.table_id, name) {
+                Ok(table_id) => self.table_id = Some(table_id),
+                Err(e) => {
+                    let e = NmstateError::new(
+                        e.kind(),
+                        format!("{} for route {self}", e.msg()),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn sanitize(&mut self) -> Result<(), NmstateError> {
         if let Some(dst) = self.destination.as_ref() {
             if dst.is_empty() {
@@ -351,6 +807,49 @@ impl RouteEntry {
                 ));
             }
         }
+        if let Some(src) = self.source.as_ref() {
+            let new_src = match src.parse::<std::net::IpAddr>() {
+                Ok(i) => i.to_string(),
+                Err(e) => {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!("Invalid route source address {src}: {e}"),
+                    ));
+                }
+            };
+            if let Some(dst) = self.destination.as_deref() {
+                if is_ipv6_addr(dst) != is_ipv6_addr(&new_src) {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Route source address {src} is not in the \
+                            same IP family as route destination {dst}"
+                        ),
+                    ));
+                }
+            }
+            if src != &new_src {
+                log::warn!("Route source {} sanitized to {}", src, new_src);
+                self.source = Some(new_src);
+            }
+        }
+        if self.lock_mtu == Some(true) && self.mtu.is_none() {
+            return Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                "The 'lock-mtu' cannot be enabled without 'mtu' defined"
+                    .to_string(),
+            ));
+        }
+        if let Some(scope) = self.scope {
+            if self.is_ipv6() && scope != RouteScope::Universe {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "IPv6 route only support scope universe, got {scope}"
+                    ),
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -359,10 +858,53 @@ impl RouteEntry {
             == Some(true)
     }
 
+    // Blackhole, unreachable, prohibit and throw routes have no next hop
+    pub(crate) fn has_next_hop(&self) -> bool {
+        !matches!(
+            self.route_type,
+            Some(
+                RouteType::Blackhole
+                    | RouteType::Unreachable
+                    | RouteType::Prohibit
+                    | RouteType::Throw
+            )
+        )
+    }
+
     pub(crate) fn is_unicast(&self) -> bool {
         self.route_type.is_none()
             || u8::from(self.route_type.unwrap()) == RTN_UNICAST
     }
+
+    /// The scope used by kernel when not defined, same as `ip route add`:
+    ///  * `host` for local route.
+    ///  * `link` for broadcast, anycast and multicast route, and unicast
+    ///    route without gateway or nexthop object.
+    ///  * `universe` for others and all IPv6 routes.
+    pub(crate) fn default_scope(&self) -> RouteScope {
+        if self.is_ipv6() {
+            return RouteScope::Universe;
+        }
+        match self.route_type {
+            Some(RouteType::Local) => RouteScope::Host,
+            Some(
+                RouteType::Broadcast
+                | RouteType::Anycast
+                | RouteType::Multicast,
+            ) => RouteScope::Link,
+            None if self.nexthop_id.is_none()
+                && self
+                    .next_hop_addr
+                    .as_deref()
+                    .and_then(|a| a.parse::<std::net::IpAddr>().ok())
+                    .map(|a| a.is_unspecified())
+                    .unwrap_or(true) =>
+            {
+                RouteScope::Link
+            }
+            _ => RouteScope::Universe,
+        }
+    }
 }
 
 // For Vec::dedup()
@@ -416,12 +958,48 @@ impl std::fmt::Display for RouteEntry {
         if let Some(v) = self.table_id.as_ref() {
             props.push(format!("table-id: {v}"));
         }
+        if let Some(v) = self.table_name.as_ref() {
+            props.push(format!("table-name: {v}"));
+        }
         if let Some(v) = self.weight {
             props.push(format!("weight: {v}"));
         }
         if let Some(v) = self.cwnd {
             props.push(format!("cwnd: {v}"));
         }
+        if let Some(v) = self.source.as_ref() {
+            props.push(format!("source: {v}"));
+        }
+        if let Some(v) = self.mtu {
+            props.push(format!("mtu: {v}"));
+        }
+        if let Some(v) = self.lock_mtu {
+            props.push(format!("lock-mtu: {v}"));
+        }
+        if let Some(v) = self.advmss {
+            props.push(format!("advmss: {v}"));
+        }
+        if let Some(v) = self.initcwnd {
+            props.push(format!("initcwnd: {v}"));
+        }
+        if let Some(v) = self.initrwnd {
+            props.push(format!("initrwnd: {v}"));
+        }
+        if let Some(v) = self.quickack {
+            props.push(format!("quickack: {v}"));
+        }
+        if let Some(v) = self.onlink {
+            props.push(format!("onlink: {v}"));
+        }
+        if let Some(v) = self.scope {
+            props.push(format!("scope: {v}"));
+        }
+        if let Some(v) = self.protocol {
+            props.push(format!("protocol: {v}"));
+        }
+        if let Some(v) = self.nexthop_id {
+            props.push(format!("nexthop-id: {v}"));
+        }
 
         write!(f, "{}", props.join(" "))
     }
@@ -435,6 +1013,8 @@ pub(crate) struct MergedRoutes {
     // For backend use incremental route changes, please use
     // `MergedRoutes.changed_routes`.
     pub(crate) merged: HashMap<String, Vec<RouteEntry>>,
+    // Routes referring nexthop objects, they have no next hop interface.
+    pub(crate) nexthop_routes: Vec<RouteEntry>,
     pub(crate) route_changed_ifaces: Vec<String>,
     // The `changed_routes` contains changed routes including those been marked
     // as absent. Not including desired route equal to current route.
@@ -445,10 +1025,13 @@ pub(crate) struct MergedRoutes {
 
 impl MergedRoutes {
     pub(crate) fn new(
-        desired: Routes,
+        mut desired: Routes,
         current: Routes,
         merged_ifaces: &MergedInterfaces,
+        nexthops: &MergedNexthops,
+        route_table_names: &RouteTableNames,
     ) -> Result<Self, NmstateError> {
+        desired.resolve_table_names(route_table_names)?;
         desired.validate()?;
         let mut desired_routes = Vec::new();
         if let Some(rts) = desired.config.as_ref() {
@@ -489,7 +1072,18 @@ impl MergedRoutes {
             .iter()
             .filter(|rt| !rt.is_absent())
         {
-            if let Some(via) = rt.next_hop_iface.as_ref() {
+            if let Some(id) = rt.nexthop_id {
+                if !nexthops.merged.contains_key(&id) {
+                    return Err(NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Desired route '{rt}' is referring nonexistent \
+                            nexthop {id}"
+                        ),
+                    ));
+                }
+                changed_ifaces.extend(nexthops.get_ifaces(id));
+            } else if let Some(via) = rt.next_hop_iface.as_ref() {
                 if ifaces_marked_as_absent.contains(&via.as_str()) {
                     return Err(NmstateError::new(
                         ErrorKind::InvalidArgument,
@@ -534,7 +1128,9 @@ impl MergedRoutes {
             if let Some(cur_rts) = current.config.as_ref() {
                 for rt in cur_rts {
                     if absent_rt.is_match(rt) {
-                        if let Some(via) = rt.next_hop_iface.as_ref() {
+                        if let Some(id) = rt.nexthop_id {
+                            changed_ifaces.extend(nexthops.get_ifaces(id));
+                        } else if let Some(via) = rt.next_hop_iface.as_ref() {
                             changed_ifaces.insert(via.as_str());
                         } else {
                             changed_ifaces.insert(LOOPBACK_IFACE_NAME);
@@ -571,6 +1167,37 @@ impl MergedRoutes {
                     } else {
                         merged_routes.push(rt.clone());
                     }
+                } else if let Some(id) = rt.nexthop_id {
+                    // Kernel removes routes along with the nexthop they
+                    // referring.
+                    if !nexthops.merged.contains_key(&id)
+                        || desired_routes
+                            .as_slice()
+                            .iter()
+                            .filter(|r| r.is_absent())
+                            .any(|absent_rt| absent_rt.is_match(rt))
+                    {
+                        let mut new_rt = rt.clone();
+                        new_rt.state = Some(RouteState::Absent);
+                        changed_routes.insert(new_rt);
+                    } else {
+                        merged_routes.push(rt.clone());
+                    }
+                } else if rt.route_type.is_some() {
+                    // IPv4 blackhole, unreachable, prohibit and throw routes
+                    // have no next hop interface.
+                    if desired_routes
+                        .as_slice()
+                        .iter()
+                        .filter(|r| r.is_absent())
+                        .any(|absent_rt| absent_rt.is_match(rt))
+                    {
+                        let mut new_rt = rt.clone();
+                        new_rt.state = Some(RouteState::Absent);
+                        changed_routes.insert(new_rt);
+                    } else {
+                        merged_routes.push(rt.clone());
+                    }
                 }
             }
         }
@@ -594,9 +1221,17 @@ impl MergedRoutes {
         merged_routes.dedup();
 
         let mut merged: HashMap<String, Vec<RouteEntry>> = HashMap::new();
+        let mut nexthop_routes: Vec<RouteEntry> = Vec::new();
 
         for rt in merged_routes {
-            if let Some(via) = rt.next_hop_iface.as_ref() {
+            if let Some(id) = rt.nexthop_id {
+                // Backend without nexthop object support need to update
+                // routes of member interfaces when nexthop changed.
+                if nexthops.is_nexthop_changed(id) {
+                    changed_ifaces.extend(nexthops.get_ifaces(id));
+                }
+                nexthop_routes.push(rt);
+            } else if let Some(via) = rt.next_hop_iface.as_ref() {
                 let rts: &mut Vec<RouteEntry> =
                     match merged.entry(via.to_string()) {
                         Entry::Occupied(o) => o.into_mut(),
@@ -618,6 +1253,7 @@ impl MergedRoutes {
 
         Ok(Self {
             merged,
+            nexthop_routes,
             desired,
             current,
             route_changed_ifaces,
@@ -650,6 +1286,37 @@ impl MergedRoutes {
     pub(crate) fn is_changed(&self) -> bool {
         !self.route_changed_ifaces.is_empty()
     }
+
+    // Store routes referring nexthop as ECMP routes of member interfaces for
+    // backends without nexthop object support.
+    pub(crate) fn expand_nexthop_routes(
+        &mut self,
+        nexthops: &MergedNexthops,
+    ) -> Result<(), NmstateError> {
+        for rt in std::mem::take(&mut self.nexthop_routes) {
+            for new_rt in nexthops.expand_route(&rt)? {
+                if let Some(via) = new_rt.next_hop_iface.as_ref() {
+                    let rts: &mut Vec<RouteEntry> =
+                        match self.merged.entry(via.to_string()) {
+                            Entry::Occupied(o) => o.into_mut(),
+                            Entry::Vacant(v) => v.insert(Vec::new()),
+                        };
+                    // Replace current ECMP route stored for old nexthop
+                    let mut rt_without_weight = new_rt.clone();
+                    rt_without_weight.weight = None;
+                    rts.retain(|r| {
+                        r.weight.is_none() || !rt_without_weight.is_match(r)
+                    });
+                    rts.push(new_rt);
+                }
+            }
+        }
+        for rts in self.merged.values_mut() {
+            rts.sort_unstable();
+            rts.dedup();
+        }
+        Ok(())
+    }
 }
 
 // Validating if the route destination network is valid,
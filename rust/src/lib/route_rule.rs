@@ -1,15 +1,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
+    deserializer::NumberAsString,
     ip::{is_ipv6_addr, sanitize_ip_network, AddressFamily},
-    ErrorKind, InterfaceIpAddr, InterfaceType, NmstateError,
+    ErrorKind, InterfaceIpAddr, InterfaceType, NmstateError, RouteTableNames,
 };
 
 const ROUTE_RULE_DEFAULT_PRIORIRY: i64 = 30000;
+// The lowest 2 bits of TOS are used by ECN
+const TOS_ECN_MASK: u8 = 0x03;
+const DSCP_MAX: u8 = 63;
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -42,6 +47,18 @@ impl RouteRules {
     pub fn is_empty(&self) -> bool {
         self.config.is_none()
     }
+
+    pub(crate) fn resolve_table_names(
+        &mut self,
+        route_table_names: &RouteTableNames,
+    ) -> Result<(), NmstateError> {
+        if let Some(rules) = self.config.as_mut() {
+            for rule in rules {
+                rule.resolve_table_name(route_table_names)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -98,6 +115,15 @@ pub struct RouteRuleEntry {
     /// The routing table ID to lookup if the rule selector matches.
     /// Serialize and deserialize to/from `route-table`.
     pub table_id: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "route-table-name"
+    )]
+    /// The routing table name defined in `/etc/iproute2/rt_tables` or
+    /// `/etc/iproute2/rt_tables.d/*.conf`. When applying, resolved to
+    /// [RouteRuleEntry.table_id].
+    /// Serialize and deserialize to/from `route-table-name`.
+    pub table_name: Option<String>,
     #[serde(
         skip_serializing_if = "Option::is_none",
         default,
@@ -129,6 +155,60 @@ pub struct RouteRuleEntry {
         alias = "suppress_prefixlength"
     )]
     pub suppress_prefix_length: Option<u32>,
+    /// Source port or port range to match, e.g. `80` or `1024-65535`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sport: Option<RouteRuleRange>,
+    /// Destination port or port range to match, e.g. `80` or `1024-65535`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dport: Option<RouteRuleRange>,
+    /// IP protocol number to match, e.g. 6 for TCP, 17 for UDP.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    pub ipproto: Option<u8>,
+    /// User ID or user ID range of the socket owner to match, e.g.
+    /// `1000-1999`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uidrange: Option<RouteRuleRange>,
+    /// Type of service(TOS) to match. The ECN bits should be zero.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    pub tos: Option<u8>,
+    /// DSCP(Differentiated Services Code Point) to match, the range is 0 to
+    /// 63. This is the upper 6 bits of TOS, hence will be converted to
+    /// [RouteRuleEntry.tos] when applying and never shown when querying.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    pub dscp: Option<u8>,
+    /// Outgoing interface.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oif: Option<String>,
+    /// Lookup the route table of the L3 master device(e.g. VRF) of the
+    /// incoming or outgoing interface. Cannot be used with
+    /// [RouteRuleEntry.table_id].
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub l3mdev: Option<bool>,
+    /// The priority of route rule to jump to when [RouteRuleEntry.action]
+    /// is [RouteRuleAction::Goto]. Should be bigger than the priority of
+    /// this route rule.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub goto_priority: Option<u32>,
 }
 
 impl RouteRuleEntry {
@@ -206,6 +286,99 @@ impl RouteRuleEntry {
         Ok(())
     }
 
+    fn validate_selectors(&self) -> Result<(), NmstateError> {
+        for (prop, range) in [("sport", self.sport), ("dport", self.dport)] {
+            if let Some(range) = range {
+                if range.start == 0 || range.end > u16::MAX.into() {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The {prop} {range} should be in the range of \
+                            1 to 65535 '{self}'"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        if let Some(tos) = self.tos {
+            if tos & TOS_ECN_MASK != 0 {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The ECN bits of tos {tos:#04x} should be zero '{self}'"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        if self.l3mdev == Some(true)
+            && (self.table_id.is_some() || self.action.is_some())
+        {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The l3mdev route rule cannot have route-table or \
+                    action defined '{self}'"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        match (self.action, self.goto_priority) {
+            (Some(RouteRuleAction::Goto), None) => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The goto-priority is required by goto action \
+                        '{self}'"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            (Some(RouteRuleAction::Goto), Some(goto_priority)) => {
+                if let Some(priority) = self
+                    .priority
+                    .filter(|p| *p != RouteRuleEntry::USE_DEFAULT_PRIORITY)
+                {
+                    if i64::from(goto_priority) <= priority {
+                        let e = NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "The goto-priority {goto_priority} should be \
+                                bigger than the priority {priority} of \
+                                route rule '{self}'"
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
+                }
+            }
+            (_, Some(_)) => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The goto-priority is only valid for goto action \
+                        '{self}'"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    // The `dscp` is the upper 6 bits of `tos`.
+    fn get_tos(&self) -> Option<u8> {
+        self.tos.or_else(|| self.dscp.map(|d| d << 2))
+    }
+
     pub(crate) fn is_absent(&self) -> bool {
         matches!(self.state, Some(RouteRuleState::Absent))
     }
@@ -308,42 +481,120 @@ impl RouteRuleEntry {
         {
             return false;
         }
+        if self.sport.is_some() && self.sport != other.sport {
+            return false;
+        }
+        if self.dport.is_some() && self.dport != other.dport {
+            return false;
+        }
+        if self.ipproto.is_some() && self.ipproto != other.ipproto {
+            return false;
+        }
+        if self.uidrange.is_some() && self.uidrange != other.uidrange {
+            return false;
+        }
+        if let Some(tos) = self.get_tos() {
+            if other.get_tos() != Some(tos) {
+                return false;
+            }
+        }
+        if self.oif.is_some() && self.oif != other.oif {
+            return false;
+        }
+        if self.l3mdev.is_some()
+            && self.l3mdev.unwrap_or_default()
+                != other.l3mdev.unwrap_or_default()
+        {
+            return false;
+        }
+        if self.goto_priority.is_some()
+            && self.goto_priority != other.goto_priority
+        {
+            return false;
+        }
         true
     }
 
-    // Return tuple of (no_absent, is_ipv4, table_id, ip_from,
-    // ip_to, priority, fwmark, fwmask, action, suppress_prefix_length)
+    // Return tuple of ((no_absent, is_ipv4, table_id, ip_from,
+    // ip_to, priority, fwmark, fwmask, action, suppress_prefix_length),
+    // (sport, dport, ipproto, uidrange, tos, oif, l3mdev, goto_priority))
+    #[allow(clippy::type_complexity)]
     fn sort_key(
         &self,
-    ) -> (bool, bool, u32, &str, &str, i64, u32, u32, u8, u32) {
+    ) -> (
+        (bool, bool, u32, &str, &str, i64, u32, u32, u8, u32),
         (
-            !matches!(self.state, Some(RouteRuleState::Absent)),
-            {
-                if let Some(ip_from) = self.ip_from.as_ref() {
-                    !is_ipv6_addr(ip_from.as_str())
-                } else if let Some(ip_to) = self.ip_to.as_ref() {
-                    !is_ipv6_addr(ip_to.as_str())
-                } else if let Some(family) = self.family.as_ref() {
-                    *family == AddressFamily::IPv4
-                } else {
-                    log::warn!(
-                        "Neither ip-from, ip-to nor family \
+            Option<RouteRuleRange>,
+            Option<RouteRuleRange>,
+            u8,
+            Option<RouteRuleRange>,
+            u8,
+            &str,
+            bool,
+            u32,
+        ),
+    ) {
+        (
+            (
+                !matches!(self.state, Some(RouteRuleState::Absent)),
+                {
+                    if let Some(ip_from) = self.ip_from.as_ref() {
+                        !is_ipv6_addr(ip_from.as_str())
+                    } else if let Some(ip_to) = self.ip_to.as_ref() {
+                        !is_ipv6_addr(ip_to.as_str())
+                    } else if let Some(family) = self.family.as_ref() {
+                        *family == AddressFamily::IPv4
+                    } else {
+                        log::warn!(
+                            "Neither ip-from, ip-to nor family \
                         is defined, treating it a IPv4 route rule"
+                        );
+                        true
+                    }
+                },
+                self.table_id
+                    .unwrap_or(RouteRuleEntry::USE_DEFAULT_ROUTE_TABLE),
+                self.ip_from.as_deref().unwrap_or(""),
+                self.ip_to.as_deref().unwrap_or(""),
+                self.priority
+                    .unwrap_or(RouteRuleEntry::USE_DEFAULT_PRIORITY),
+                self.fwmark.unwrap_or(0),
+                self.fwmask.unwrap_or(0),
+                self.action.map(u8::from).unwrap_or(0),
+                self.suppress_prefix_length.unwrap_or_default(),
+            ),
+            (
+                self.sport,
+                self.dport,
+                self.ipproto.unwrap_or_default(),
+                self.uidrange,
+                self.get_tos().unwrap_or_default(),
+                self.oif.as_deref().unwrap_or(""),
+                self.l3mdev.unwrap_or_default(),
+                self.goto_priority.unwrap_or_default(),
+            ),
+        )
+    }
+
+    // Set the `table_id` from `table_name`
+    pub(crate) fn resolve_table_name(
+        &mut self,
+        route_table_names: &RouteTableNames,
+    ) -> Result<(), NmstateError> {
+        if let Some(name) = self.table_name.as_deref() {
+            match route_table_names.resolve(self.table_id, name) {
+                Ok(table_id) => self.table_id = Some(table_id),
+                Err(e) => {
+                    let e = NmstateError::new(
+                        e.kind(),
+                        format!("{} for route rule {self}", e.msg()),
                     );
-                    true
+                    log::error!("{}", e);
+                    return Err(e);
                 }
-            },
-            self.table_id
-                .unwrap_or(RouteRuleEntry::USE_DEFAULT_ROUTE_TABLE),
-            self.ip_from.as_deref().unwrap_or(""),
-            self.ip_to.as_deref().unwrap_or(""),
-            self.priority
-                .unwrap_or(RouteRuleEntry::USE_DEFAULT_PRIORITY),
-            self.fwmark.unwrap_or(0),
-            self.fwmask.unwrap_or(0),
-            self.action.map(u8::from).unwrap_or(0),
-            self.suppress_prefix_length.unwrap_or_default(),
-        )
+            }
+        }
+        Ok(())
     }
 
     pub(crate) fn sanitize(&mut self) -> Result<(), NmstateError> {
@@ -389,10 +640,31 @@ impl RouteRuleEntry {
                 }
             }
         }
+        if let Some(dscp) = self.dscp {
+            if dscp > DSCP_MAX
+                || self.tos.is_some() && self.tos != Some(dscp << 2)
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The dscp should be in the range of 0 to {DSCP_MAX} \
+                        and not conflicting with tos '{self}'"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            self.tos = Some(dscp << 2);
+            self.dscp = None;
+        }
         self.validate_ip_from_to()?;
         self.validate_fwmark_and_fwmask()?;
+        self.validate_selectors()?;
 
-        if self.action.is_none() && self.table_id.is_none() {
+        if self.action.is_none()
+            && self.table_id.is_none()
+            && self.l3mdev != Some(true)
+        {
             log::info!(
                 "Route rule {self} has no action or route-table \
                 defined, using default route table 254"
@@ -455,6 +727,9 @@ impl std::fmt::Display for RouteRuleEntry {
         if let Some(v) = self.table_id.as_ref() {
             props.push(format!("route-table: {v}"));
         }
+        if let Some(v) = self.table_name.as_ref() {
+            props.push(format!("route-table-name: {v}"));
+        }
         if let Some(v) = self.fwmask.as_ref() {
             props.push(format!("fwmask: {v}"));
         }
@@ -470,6 +745,33 @@ impl std::fmt::Display for RouteRuleEntry {
         if let Some(v) = self.suppress_prefix_length.as_ref() {
             props.push(format!("suppress-prefix-length: {v}"));
         }
+        if let Some(v) = self.sport.as_ref() {
+            props.push(format!("sport: {v}"));
+        }
+        if let Some(v) = self.dport.as_ref() {
+            props.push(format!("dport: {v}"));
+        }
+        if let Some(v) = self.ipproto.as_ref() {
+            props.push(format!("ipproto: {v}"));
+        }
+        if let Some(v) = self.uidrange.as_ref() {
+            props.push(format!("uidrange: {v}"));
+        }
+        if let Some(v) = self.tos.as_ref() {
+            props.push(format!("tos: {v}"));
+        }
+        if let Some(v) = self.dscp.as_ref() {
+            props.push(format!("dscp: {v}"));
+        }
+        if let Some(v) = self.oif.as_ref() {
+            props.push(format!("oif: {v}"));
+        }
+        if let Some(v) = self.l3mdev.as_ref() {
+            props.push(format!("l3mdev: {v}"));
+        }
+        if let Some(v) = self.goto_priority.as_ref() {
+            props.push(format!("goto-priority: {v}"));
+        }
         write!(f, "{}", props.join(" "))
     }
 }
@@ -482,6 +784,11 @@ pub enum RouteRuleAction {
     Blackhole,
     Unreachable,
     Prohibit,
+    /// Jump to the route rule with priority defined by
+    /// [RouteRuleEntry.goto_priority].
+    Goto,
+    /// Do nothing, continue with next route rule.
+    Nop,
 }
 
 impl std::fmt::Display for RouteRuleAction {
@@ -493,11 +800,15 @@ impl std::fmt::Display for RouteRuleAction {
                 Self::Blackhole => "blackhole",
                 Self::Unreachable => "unreachable",
                 Self::Prohibit => "prohibit",
+                Self::Goto => "goto",
+                Self::Nop => "nop",
             }
         )
     }
 }
 
+const FR_ACT_GOTO: u8 = 2;
+const FR_ACT_NOP: u8 = 3;
 const FR_ACT_BLACKHOLE: u8 = 6;
 const FR_ACT_UNREACHABLE: u8 = 7;
 const FR_ACT_PROHIBIT: u8 = 8;
@@ -508,6 +819,86 @@ impl From<RouteRuleAction> for u8 {
             RouteRuleAction::Blackhole => FR_ACT_BLACKHOLE,
             RouteRuleAction::Unreachable => FR_ACT_UNREACHABLE,
             RouteRuleAction::Prohibit => FR_ACT_PROHIBIT,
+            RouteRuleAction::Goto => FR_ACT_GOTO,
+            RouteRuleAction::Nop => FR_ACT_NOP,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[non_exhaustive]
+/// Inclusive range used by [RouteRuleEntry.sport], [RouteRuleEntry.dport]
+/// and [RouteRuleEntry.uidrange].
+/// Deserialize from integer or string in the format of `start-end`.
+/// Serialize into integer when `start` equal to `end`, otherwise string in
+/// the format of `start-end`.
+pub struct RouteRuleRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl RouteRuleRange {
+    pub fn new(start: u32, end: u32) -> Self {
+        Self { start, end }
+    }
+}
+
+impl FromStr for RouteRuleRange {
+    type Err = NmstateError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |v: &str| {
+            v.trim().parse::<u32>().map_err(|e| {
+                NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid range {s}: {e}"),
+                )
+            })
+        };
+        let (start, end) = match s.split_once('-') {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None => (parse(s)?, parse(s)?),
+        };
+        if start > end {
+            return Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid range {s}: start is bigger than end"),
+            ));
+        }
+        Ok(Self { start, end })
+    }
+}
+
+impl std::fmt::Display for RouteRuleRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RouteRuleRange {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        NumberAsString::deserialize(deserializer)?
+            .as_str()
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for RouteRuleRange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.start == self.end {
+            serializer.serialize_u32(self.start)
+        } else {
+            serializer.serialize_str(&self.to_string())
         }
     }
 }
@@ -527,9 +918,11 @@ pub(crate) struct MergedRouteRules {
 
 impl MergedRouteRules {
     pub(crate) fn new(
-        desired: RouteRules,
+        mut desired: RouteRules,
         current: RouteRules,
+        route_table_names: &RouteTableNames,
     ) -> Result<Self, NmstateError> {
+        desired.resolve_table_names(route_table_names)?;
         let mut for_apply: Vec<RouteRuleEntry> = Vec::new();
         let mut merged_rules: Vec<RouteRuleEntry> = Vec::new();
 
@@ -589,11 +982,10 @@ impl MergedRouteRules {
             .collect();
 
         self.for_apply.retain(|rule| {
-            if let Some(iif) = rule.iif.as_ref() {
-                !ignored_ifaces.contains(&iif.as_str())
-            } else {
-                true
-            }
+            [rule.iif.as_deref(), rule.oif.as_deref()]
+                .into_iter()
+                .flatten()
+                .all(|i| !ignored_ifaces.contains(&i))
         })
     }
 
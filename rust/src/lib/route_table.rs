@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crate::{ErrorKind, NmstateError, RouteRules, Routes};
+
+pub(crate) const RT_TABLES_PATH: &str = "/etc/iproute2/rt_tables";
+pub(crate) const RT_TABLES_DIR: &str = "/etc/iproute2/rt_tables.d";
+// Recent iproute2 ships the defaults in `/usr/share` and use `/etc` as
+// override.
+const USR_RT_TABLES_PATH: &str = "/usr/share/iproute2/rt_tables";
+const USR_RT_TABLES_DIR: &str = "/usr/share/iproute2/rt_tables.d";
+
+// Table names always known by iproute2 even without `rt_tables` file
+const RESERVED_TABLES: [(u32, &str); 4] = [
+    (255, "local"),
+    (254, "main"),
+    (253, "default"),
+    (0, "unspec"),
+];
+
+/// Mapping between route table name and route table ID defined in
+/// `/usr/share/iproute2/rt_tables`, `/usr/share/iproute2/rt_tables.d/*.conf`,
+/// `/etc/iproute2/rt_tables` and `/etc/iproute2/rt_tables.d/*.conf`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct RouteTableNames {
+    names: HashMap<String, u32>,
+    // Mappings requested by user but not found in system, they will be
+    // stored to `rt_tables.d` folder when applying.
+    pub(crate) new_entries: Vec<(u32, String)>,
+}
+
+impl Default for RouteTableNames {
+    fn default() -> Self {
+        Self {
+            names: RESERVED_TABLES
+                .iter()
+                .map(|(id, name)| (name.to_string(), *id))
+                .collect(),
+            new_entries: Vec::new(),
+        }
+    }
+}
+
+impl RouteTableNames {
+    /// Load route table names from system only when desired state is
+    /// referring route table by name.
+    /// When `create` is true, route table name unknown to system will be
+    /// created using the `route-table` of the same entry.
+    pub(crate) fn new(
+        routes: &Routes,
+        rules: &RouteRules,
+        create: bool,
+    ) -> Result<Self, NmstateError> {
+        let mut entries: Vec<(Option<u32>, &str)> = Vec::new();
+        if let Some(rts) = routes.config.as_ref() {
+            for rt in rts {
+                if let Some(name) = rt.table_name.as_deref() {
+                    entries.push((rt.table_id, name));
+                }
+            }
+        }
+        if let Some(rules) = rules.config.as_ref() {
+            for rule in rules {
+                if let Some(name) = rule.table_name.as_deref() {
+                    entries.push((rule.table_id, name));
+                }
+            }
+        }
+
+        let mut ret = Self::default();
+        if entries.is_empty() {
+            return Ok(ret);
+        }
+        ret.load_system();
+
+        if create {
+            for (table_id, name) in entries {
+                if let Some(table_id) = table_id {
+                    if ret.get_id(name).is_none() {
+                        validate_table_name(name)?;
+                        log::info!(
+                            "Creating route table name {name} for route \
+                            table {table_id}"
+                        );
+                        ret.names.insert(name.to_string(), table_id);
+                        ret.new_entries.push((table_id, name.to_string()));
+                    }
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Load mappings from `/usr/share/iproute2` first, then let
+    /// `/etc/iproute2` override them.
+    pub(crate) fn load_system(&mut self) {
+        self.load(USR_RT_TABLES_PATH, USR_RT_TABLES_DIR);
+        self.load(RT_TABLES_PATH, RT_TABLES_DIR);
+    }
+
+    /// Load mappings from specified `rt_tables` file and `*.conf` files
+    /// in specified folder. Missing file or folder is ignored.
+    pub(crate) fn load(&mut self, file_path: &str, dir_path: &str) {
+        let mut file_paths = vec![file_path.to_string()];
+        match std::fs::read_dir(dir_path) {
+            Ok(dir) => {
+                let mut conf_files: Vec<String> = dir
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| {
+                        p.extension().and_then(|e| e.to_str()) == Some("conf")
+                    })
+                    .map(|p| p.display().to_string())
+                    .collect();
+                conf_files.sort_unstable();
+                file_paths.extend(conf_files);
+            }
+            Err(e) => {
+                log::debug!("Failed to read {dir_path}: {e}");
+            }
+        }
+        for file_path in file_paths {
+            match std::fs::read_to_string(&file_path) {
+                Ok(content) => self.parse(&content),
+                Err(e) => {
+                    log::debug!("Failed to read {file_path}: {e}");
+                }
+            }
+        }
+    }
+
+    /// Parse `rt_tables` content in the format of `<id> <name>` per line.
+    pub(crate) fn parse(&mut self, content: &str) {
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut items = line.split_whitespace();
+            let table_id = items.next().and_then(parse_table_id);
+            match (table_id, items.next()) {
+                (Some(table_id), Some(name)) => {
+                    self.names.insert(name.to_string(), table_id);
+                }
+                _ => {
+                    log::warn!("Ignoring invalid route table entry: {line}");
+                }
+            }
+        }
+    }
+
+    pub(crate) fn get_id(&self, name: &str) -> Option<u32> {
+        self.names.get(name).copied()
+    }
+
+    /// Get the name of specified route table ID, reserved route tables
+    /// like `main` are not included.
+    #[cfg_attr(not(feature = "query_apply"), allow(dead_code))]
+    pub(crate) fn get_name(&self, table_id: u32) -> Option<&str> {
+        if RESERVED_TABLES.iter().any(|(id, _)| *id == table_id) {
+            return None;
+        }
+        let mut names: Vec<&str> = self
+            .names
+            .iter()
+            .filter_map(|(name, id)| {
+                if *id == table_id {
+                    Some(name.as_str())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        // Use the first name in alphabet order when multiple names defined
+        names.sort_unstable();
+        names.first().copied()
+    }
+
+    /// Resolve route table name to route table ID, raise
+    /// [ErrorKind::InvalidArgument] if name is unknown or pointing to
+    /// different route table ID than `table_id`.
+    pub(crate) fn resolve(
+        &self,
+        table_id: Option<u32>,
+        name: &str,
+    ) -> Result<u32, NmstateError> {
+        let resolved_id = match self.get_id(name) {
+            Some(i) => i,
+            None => {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Route table name {name} is not defined in \
+                        {RT_TABLES_PATH}, {RT_TABLES_DIR}, \
+                        {USR_RT_TABLES_PATH} or {USR_RT_TABLES_DIR}"
+                    ),
+                ));
+            }
+        };
+        if let Some(table_id) = table_id {
+            if table_id != resolved_id {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Route table name {name} is pointing to route table \
+                        {resolved_id}, but route-table {table_id} desired"
+                    ),
+                ));
+            }
+        }
+        Ok(resolved_id)
+    }
+}
+
+fn parse_table_id(value: &str) -> Option<u32> {
+    if let Some(hex) = value.strip_prefix("0x") {
+        u32::from_str_radix(hex, 16).ok()
+    } else {
+        value.parse::<u32>().ok()
+    }
+}
+
+fn validate_table_name(name: &str) -> Result<(), NmstateError> {
+    if name.is_empty()
+        || name.parse::<u32>().is_ok()
+        || name.chars().any(|c| c.is_whitespace() || c == '#')
+    {
+        Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Invalid route table name '{name}', should not be number \
+                or contain whitespace or '#'"
+            ),
+        ))
+    } else {
+        Ok(())
+    }
+}
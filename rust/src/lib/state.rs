@@ -95,7 +95,7 @@ pub(crate) fn get_json_value_difference<'a, 'b>(
 
 #[cfg(feature = "query_apply")]
 fn should_ignore(reference: &str, desire: &Value, current: &Value) -> bool {
-    if reference.contains("interface.link-aggregation.options") {
+    if reference.contains(".link-aggregation.options") {
         // Per oVirt request, bond option difference should not
         // fail verification.
         log::warn!(
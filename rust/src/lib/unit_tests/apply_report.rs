@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ovsdb::ovsdb_apply_dry_run, MergedNetworkState, NetworkState};
+
+fn gen_current_state() -> NetworkState {
+    serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+- name: eth2
+  type: ethernet
+  state: up
+- name: dummy0
+  type: dummy
+  state: up
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+    table-id: 254
+    metric: 100
+ovs-db:
+  external_ids:
+    a: A0
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_kernel_changes_of_interfaces() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: br0
+  type: linux-bridge
+  state: up
+- name: dummy0
+  type: dummy
+  state: absent
+- name: dummy1
+  type: dummy
+  state: absent
+- name: eth2
+  type: ethernet
+  state: down
+",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current_state(), false, false)
+            .unwrap();
+
+    assert_eq!(
+        merged_state.gen_kernel_changes(),
+        vec![
+            "create interface br0 (linux-bridge)".to_string(),
+            "delete interface dummy0 (dummy)".to_string(),
+            "deactivate interface eth2 (ethernet)".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_kernel_changes_of_routes_hostname_and_dns() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    state: absent
+  - destination: 203.0.113.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+hostname:
+  running: host-a
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.53
+",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current_state(), false, false)
+            .unwrap();
+
+    assert_eq!(
+        merged_state.gen_kernel_changes(),
+        vec![
+            "remove route destination: 198.51.100.0/24 \
+            next-hop-interface: eth1 next-hop-address: 192.0.2.1 \
+            metric: 100 table-id: 254"
+                .to_string(),
+            "add route destination: 203.0.113.0/24 \
+            next-hop-interface: eth1 next-hop-address: 192.0.2.1"
+                .to_string(),
+            "set running hostname to host-a".to_string(),
+            "change DNS resolver configuration".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_ovsdb_apply_dry_run() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+ovs-db:
+  external_ids:
+    a: A1
+    b: B1
+",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current_state(), false, false)
+            .unwrap();
+
+    assert_eq!(
+        ovsdb_apply_dry_run(&merged_state),
+        vec![serde_json::json!([
+            "Open_vSwitch",
+            {
+                "op": "update",
+                "table": "Open_vSwitch",
+                "where": [],
+                "row": {
+                    "external_ids": ["map", [["a", "A1"], ["b", "B1"]]],
+                    "other_config": ["map", []],
+                }
+            }
+        ])]
+    );
+}
+
+#[test]
+fn test_ovsdb_apply_dry_run_no_change() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: dummy0
+  type: dummy
+  state: up
+",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, gen_current_state(), false, false)
+            .unwrap();
+
+    assert!(ovsdb_apply_dry_run(&merged_state).is_empty());
+}
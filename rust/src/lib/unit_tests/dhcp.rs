@@ -0,0 +1,97 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{DhcpLease, DhcpLeaseState, InterfaceIpv4};
+
+fn gen_test_lease() -> DhcpLease {
+    serde_yaml::from_str(
+        r"---
+address: 192.0.2.100
+prefix-length: 24
+server: 192.0.2.1
+obtained: 1000
+lease-time: 3600
+renew-time: 1800
+rebind-time: 3150
+gateways:
+- 192.0.2.1
+dns-servers:
+- 192.0.2.1
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_dhcp_lease_state() {
+    let lease = gen_test_lease();
+
+    assert_eq!(lease.state_at(1000), DhcpLeaseState::Bound);
+    assert_eq!(lease.state_at(2799), DhcpLeaseState::Bound);
+    assert_eq!(lease.state_at(2800), DhcpLeaseState::Renewing);
+    assert_eq!(lease.state_at(4150), DhcpLeaseState::Rebinding);
+    assert_eq!(lease.state_at(4600), DhcpLeaseState::Expired);
+    // Clock goes backwards
+    assert_eq!(lease.state_at(0), DhcpLeaseState::Bound);
+}
+
+#[test]
+fn test_dhcp_lease_remaining_time() {
+    let mut lease = gen_test_lease();
+
+    assert_eq!(lease.remaining_at(1100), 3500);
+    assert_eq!(lease.preferred_remaining_at(1100), 3500);
+    assert_eq!(lease.remaining_at(9999), 0);
+
+    lease.preferred_time = Some(1200);
+    assert_eq!(lease.preferred_remaining_at(1100), 1100);
+    assert_eq!(lease.preferred_remaining_at(9999), 0);
+}
+
+#[test]
+fn test_dhcp_lease_sanitize_default_t1_t2() {
+    let mut lease = gen_test_lease();
+    lease.renew_time = 0;
+    lease.rebind_time = 0;
+
+    lease.sanitize();
+
+    assert_eq!(lease.renew_time, 1800);
+    assert_eq!(lease.rebind_time, 3150);
+}
+
+#[test]
+fn test_dhcp_lease_sanitize_invalid_t2() {
+    let mut lease = gen_test_lease();
+    lease.renew_time = 3000;
+    lease.rebind_time = 2000;
+
+    lease.sanitize();
+
+    assert_eq!(lease.renew_time, 3000);
+    assert_eq!(lease.rebind_time, 3150);
+}
+
+#[test]
+fn test_ipv4_dhcp_lease_is_query_only() {
+    let mut ipv4: InterfaceIpv4 = serde_yaml::from_str(
+        r"---
+enabled: true
+dhcp: true
+dhcp-lease:
+  state: renewing
+  address: 192.0.2.100
+  prefix-length: 24
+  lease-time: 3600
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        ipv4.dhcp_lease.as_ref().map(|l| l.state),
+        Some(DhcpLeaseState::Renewing)
+    );
+
+    ipv4.sanitize(true).unwrap();
+
+    assert_eq!(ipv4.dhcp_lease, None);
+}
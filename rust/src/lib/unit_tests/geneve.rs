@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, GeneveDf, Interface, InterfaceType, Interfaces};
+
+#[test]
+fn test_geneve_deserialize() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: geneve1
+  type: geneve
+  geneve:
+    id: '102'
+    remote: 2001:db8::1
+    destination-port: 6081
+    ttl: 64
+    tos: 1
+    df: inherit
+    udp-checksum: false
+",
+    )
+    .unwrap();
+
+    let iface = ifaces.kernel_ifaces.get_mut("geneve1").unwrap();
+    iface.sanitize(true).unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::Geneve);
+    if let Interface::Geneve(iface) = iface {
+        let conf = iface.geneve.as_ref().unwrap();
+        assert_eq!(conf.id, 102);
+        assert_eq!(conf.remote, Some("2001:db8::1".parse().unwrap()));
+        assert_eq!(conf.dst_port, Some(6081));
+        assert_eq!(conf.ttl, Some(64));
+        assert_eq!(conf.tos, Some(1));
+        assert_eq!(conf.df, Some(GeneveDf::Inherit));
+        assert_eq!(conf.udp_checksum, Some(false));
+    } else {
+        panic!("Expecting Geneve interface, but got {iface:?}");
+    }
+}
+
+#[test]
+fn test_geneve_vni_out_of_range() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: geneve1
+  type: geneve
+  geneve:
+    id: 16777216
+",
+    )
+    .unwrap();
+
+    let result = ifaces
+        .kernel_ifaces
+        .get_mut("geneve1")
+        .unwrap()
+        .sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_geneve_merge() {
+    let mut cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: geneve1
+  type: geneve
+  geneve:
+    id: 102
+    remote: 192.0.2.1
+    destination-port: 6081
+    ttl: 64
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: geneve1
+  type: geneve
+  geneve:
+    id: 103
+    remote: 192.0.2.2
+",
+    )
+    .unwrap();
+
+    let cur_iface = cur_ifaces.kernel_ifaces.get_mut("geneve1").unwrap();
+    cur_iface.update(des_ifaces.kernel_ifaces.get("geneve1").unwrap());
+
+    if let Interface::Geneve(iface) = cur_iface {
+        let conf = iface.geneve.as_ref().unwrap();
+        assert_eq!(conf.id, 103);
+        assert_eq!(conf.remote, Some("192.0.2.2".parse().unwrap()));
+        assert_eq!(conf.dst_port, Some(6081));
+        assert_eq!(conf.ttl, Some(64));
+    } else {
+        panic!("Expecting Geneve interface, but got {cur_iface:?}");
+    }
+}
+
+#[test]
+fn test_bareudp_deserialize() {
+    let ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bareudp1
+  type: bareudp
+  bareudp:
+    destination-port: '6635'
+    ethertype: mpls-uc
+",
+    )
+    .unwrap();
+
+    let iface = ifaces.kernel_ifaces.get("bareudp1").unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::BareUdp);
+    if let Interface::BareUdp(iface) = iface {
+        let conf = iface.bareudp.as_ref().unwrap();
+        assert_eq!(conf.dst_port, 6635);
+        assert_eq!(u16::from(conf.ethertype), 0x8847);
+    } else {
+        panic!("Expecting bareudp interface, but got {iface:?}");
+    }
+}
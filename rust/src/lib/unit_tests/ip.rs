@@ -178,6 +178,49 @@ fn test_ipv4_not_allow_extra_address() {
     }
 }
 
+#[test]
+fn test_ipv4_verification_diff() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: "true"
+    dhcp: "false"
+    address:
+    - ip: "192.168.1.1"
+      prefix-length: "24"
+"#,
+    )
+    .unwrap();
+    let current: Interfaces = serde_yaml::from_str(
+        r#"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: "true"
+    dhcp: "true"
+"#,
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(desired, gen_test_eth_ifaces(), false, false)
+            .unwrap();
+
+    let e = merged_ifaces.verify(&current).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::VerificationError);
+    assert!(e
+        .msg()
+        .starts_with("Verification failure: eth1.interface.ipv4.dhcp "));
+    let diff = e.verification_diff().unwrap();
+    assert_eq!(diff.path.as_str(), "interfaces[name=eth1].ipv4.dhcp");
+    assert_eq!(diff.desired, serde_json::json!(false));
+    assert_eq!(diff.current, serde_json::json!(true));
+}
+
 #[test]
 fn test_ipv6_not_allow_extra_address() {
     let desired: Interfaces = serde_yaml::from_str(
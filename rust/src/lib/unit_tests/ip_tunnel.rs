@@ -0,0 +1,235 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, Interface, InterfaceType, Interfaces};
+
+#[test]
+fn test_ip_tunnel_deserialize_and_expand_key() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: gre1
+  type: gre
+  ip-tunnel:
+    base-iface: eth1
+    local: 192.0.2.1
+    remote: 198.51.100.1
+    ttl: 64
+    key: '100'
+    pmtudisc: true
+",
+    )
+    .unwrap();
+
+    let iface = ifaces.kernel_ifaces.get_mut("gre1").unwrap();
+    iface.sanitize(true).unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::Gre);
+    assert_eq!(iface.parent(), Some("eth1"));
+    if let Interface::IpTunnel(iface) = iface {
+        let conf = iface.ip_tunnel.as_ref().unwrap();
+        assert_eq!(conf.local, Some("192.0.2.1".parse().unwrap()));
+        assert_eq!(conf.remote, Some("198.51.100.1".parse().unwrap()));
+        assert_eq!(conf.ttl, Some(64));
+        assert_eq!(conf.key, None);
+        assert_eq!(conf.ikey, Some(100));
+        assert_eq!(conf.okey, Some(100));
+        assert_eq!(conf.pmtudisc, Some(true));
+    } else {
+        panic!("Expecting IP tunnel interface, but got {iface:?}");
+    }
+}
+
+#[test]
+fn test_ip_tunnel_types() {
+    let ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: tap1
+  type: gretap
+- name: ipip1
+  type: ipip
+- name: sit1
+  type: sit
+- name: ip6tnl1
+  type: ip6tnl
+",
+    )
+    .unwrap();
+
+    for (name, iface_type) in [
+        ("tap1", InterfaceType::GreTap),
+        ("ipip1", InterfaceType::Ipip),
+        ("sit1", InterfaceType::Sit),
+        ("ip6tnl1", InterfaceType::Ip6Tnl),
+    ] {
+        let iface = ifaces.kernel_ifaces.get(name).unwrap();
+        assert!(matches!(iface, Interface::IpTunnel(_)));
+        assert_eq!(iface.iface_type(), iface_type);
+    }
+}
+
+#[test]
+fn test_ip_tunnel_key_on_ipip() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: ipip1
+  type: ipip
+  ip-tunnel:
+    remote: 198.51.100.1
+    ikey: 100
+",
+    )
+    .unwrap();
+
+    let result = ifaces
+        .kernel_ifaces
+        .get_mut("ipip1")
+        .unwrap()
+        .sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ip_tunnel_conflict_key() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: gre1
+  type: gre
+  ip-tunnel:
+    key: 100
+    ikey: 200
+",
+    )
+    .unwrap();
+
+    let result = ifaces.kernel_ifaces.get_mut("gre1").unwrap().sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ip_tunnel_wrong_ip_family() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: ip6tnl1
+  type: ip6tnl
+  ip-tunnel:
+    local: 2001:db8::1
+    remote: 198.51.100.1
+",
+    )
+    .unwrap();
+
+    let result = ifaces
+        .kernel_ifaces
+        .get_mut("ip6tnl1")
+        .unwrap()
+        .sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ip_tunnel_merge_encap_none() {
+    let mut cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: gre1
+  type: gre
+  ip-tunnel:
+    remote: 198.51.100.1
+    ttl: 64
+    encap:
+      type: fou
+      destination-port: 5555
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: gre1
+  type: gre
+  ip-tunnel:
+    remote: 198.51.100.2
+    encap:
+      type: none
+",
+    )
+    .unwrap();
+
+    let cur_iface = cur_ifaces.kernel_ifaces.get_mut("gre1").unwrap();
+    cur_iface.update(des_ifaces.kernel_ifaces.get("gre1").unwrap());
+
+    if let Interface::IpTunnel(iface) = cur_iface {
+        let conf = iface.ip_tunnel.as_ref().unwrap();
+        assert_eq!(conf.remote, Some("198.51.100.2".parse().unwrap()));
+        assert_eq!(conf.ttl, Some(64));
+        assert_eq!(conf.encap, None);
+    } else {
+        panic!("Expecting IP tunnel interface, but got {cur_iface:?}");
+    }
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_ip_tunnel_gen_conf_keyfile() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: gre1
+  type: gre
+  state: up
+  mtu: 1400
+  ip-tunnel:
+    local: 192.0.2.1
+    remote: 198.51.100.1
+    ttl: 64
+    key: 100
+",
+    )
+    .unwrap();
+
+    let mut confs = state.gen_conf().unwrap();
+    let confs = confs.remove("NetworkManager").unwrap();
+    assert_eq!(confs.len(), 1);
+    let content = confs[0].1.as_str();
+
+    assert!(content.contains("type=ip-tunnel\n"));
+    assert!(content.contains(
+        "[ip-tunnel]\ninput-key=100\nlocal=192.0.2.1\nmode=2\nmtu=1400\n\
+        output-key=100\nremote=198.51.100.1\nttl=64\n"
+    ));
+    assert!(!content.contains("[ethernet]"));
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_ip_tunnel_gen_conf_encap_not_supported() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: ipip1
+  type: ipip
+  state: up
+  ip-tunnel:
+    remote: 198.51.100.1
+    encap:
+      type: fou
+      destination-port: 5555
+",
+    )
+    .unwrap();
+
+    let result = state.gen_conf();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, Interface, InterfaceType, Interfaces, IpVlanMode};
+
+#[test]
+fn test_ip_vlan_deserialize() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: ipvlan0
+  type: ipvlan
+  ipvlan:
+    base-iface: eth1
+    mode: l3s
+    private: 'true'
+",
+    )
+    .unwrap();
+
+    let iface = ifaces.kernel_ifaces.get_mut("ipvlan0").unwrap();
+    iface.sanitize(true).unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::IpVlan);
+    assert_eq!(iface.parent(), Some("eth1"));
+    if let Interface::IpVlan(iface) = iface {
+        let conf = iface.ipvlan.as_ref().unwrap();
+        assert_eq!(conf.mode, IpVlanMode::L3S);
+        assert_eq!(conf.private, Some(true));
+        assert_eq!(conf.vepa, None);
+    } else {
+        panic!("Expecting IPVLAN interface, but got {iface:?}");
+    }
+}
+
+#[test]
+fn test_ip_vlan_private_and_vepa() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: ipvlan0
+  type: ipvlan
+  ipvlan:
+    base-iface: eth1
+    private: true
+    vepa: true
+",
+    )
+    .unwrap();
+
+    let result = ifaces
+        .kernel_ifaces
+        .get_mut("ipvlan0")
+        .unwrap()
+        .sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ip_vlan_merge_flags() {
+    let mut cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: ipvlan0
+  type: ipvlan
+  ipvlan:
+    base-iface: eth1
+    mode: l2
+    private: true
+    vepa: false
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: ipvlan0
+  type: ipvlan
+  ipvlan:
+    base-iface: eth1
+    mode: l2
+    vepa: true
+",
+    )
+    .unwrap();
+
+    let cur_iface = cur_ifaces.kernel_ifaces.get_mut("ipvlan0").unwrap();
+    cur_iface.update(des_ifaces.kernel_ifaces.get("ipvlan0").unwrap());
+
+    if let Interface::IpVlan(iface) = cur_iface {
+        let conf = iface.ipvlan.as_ref().unwrap();
+        assert_eq!(conf.mode, IpVlanMode::L2);
+        assert_eq!(conf.private, None);
+        assert_eq!(conf.vepa, Some(true));
+    } else {
+        panic!("Expecting IPVLAN interface, but got {cur_iface:?}");
+    }
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_ip_vlan_gen_conf_keyfile() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: ipvlan0
+  type: ipvlan
+  state: up
+  ipvlan:
+    base-iface: eth1
+    mode: l2
+    vepa: true
+",
+    )
+    .unwrap();
+
+    let mut confs = state.gen_conf().unwrap();
+    let confs = confs.remove("NetworkManager").unwrap();
+    assert_eq!(confs.len(), 1);
+    let content = confs[0].1.as_str();
+
+    assert!(content.contains("type=ipvlan\n"));
+    assert!(content.contains("[ipvlan]\nmode=1\nparent=eth1\nvepa=true\n"));
+}
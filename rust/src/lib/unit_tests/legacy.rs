@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, ImportWarning, NetworkState};
+
+const TEST_DATA_FOLDER_PATH: &str = "unit_tests/legacy_test_files";
+
+fn test_data_path(name: &str) -> String {
+    std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join(TEST_DATA_FOLDER_PATH)
+        .join(name)
+        .display()
+        .to_string()
+}
+
+fn assert_state_eq(net_state: &NetworkState, expected_yaml: &str) {
+    let expected: NetworkState = serde_yaml::from_str(expected_yaml).unwrap();
+    assert_eq!(
+        serde_yaml::to_string(net_state).unwrap(),
+        serde_yaml::to_string(&expected).unwrap()
+    );
+}
+
+// Return (file name, line, config) of warnings
+fn simplify_warnings(
+    warnings: &[ImportWarning],
+) -> Vec<(String, Option<usize>, String)> {
+    warnings
+        .iter()
+        .map(|w| {
+            (
+                w.file.rsplit('/').next().unwrap_or_default().to_string(),
+                w.line,
+                w.config.clone(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_import_ifcfg() {
+    let (net_state, warnings) =
+        NetworkState::from_ifcfg_dir(&test_data_path("ifcfg")).unwrap();
+
+    assert_state_eq(
+        &net_state,
+        r"---
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.53
+    - 198.51.100.53
+    search:
+    - example.org
+    - example.com
+route-rules:
+  config:
+  - family: ipv4
+    ip-from: 192.0.2.0/24
+    priority: 1000
+    route-table: 100
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-interface: bond0
+    next-hop-address: 192.0.2.1
+  - destination: ::/0
+    next-hop-interface: bond0
+    next-hop-address: 2001:db8::1
+  - destination: 198.51.100.0/24
+    next-hop-interface: bond0
+    next-hop-address: 192.0.2.254
+    metric: 150
+    table-id: 100
+  - destination: 172.16.0.0/12
+    next-hop-interface: bond0.100
+    next-hop-address: 203.0.113.1
+  - destination: 2001:db8:1::/64
+    next-hop-interface: bond0
+    next-hop-address: 2001:db8::fe
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.10
+      prefix-length: 24
+  ipv6:
+    enabled: true
+    dhcp: false
+    autoconf: false
+    address:
+    - ip: 2001:db8::10
+      prefix-length: 64
+  link-aggregation:
+    mode: 802.3ad
+    options:
+      miimon: 100
+      xmit_hash_policy: layer3+4
+    port:
+    - eth1
+    - eth2
+- name: bond0.100
+  type: vlan
+  state: up
+  mtu: 1400
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 203.0.113.10
+      prefix-length: 24
+  vlan:
+    base-iface: bond0
+    id: 100
+- name: br0
+  type: linux-bridge
+  state: down
+  ipv4:
+    enabled: true
+    dhcp: true
+    auto-dns: false
+  bridge:
+    options:
+      multicast-snooping: false
+      stp:
+        enabled: false
+        forward-delay: 5
+        priority: 4096
+    port:
+    - name: eth3
+      stp-path-cost: 100
+      stp-priority: 32
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+- name: eth3
+  type: ethernet
+  state: up
+  mtu: 9000
+",
+    );
+    assert_eq!(
+        simplify_warnings(&warnings),
+        vec![
+            (
+                "ifcfg-bond0".to_string(),
+                Some(5),
+                "BONDING_OPTS no_such_opt=1".to_string()
+            ),
+            (
+                "ifcfg-bond0".to_string(),
+                Some(18),
+                "ZONE=public".to_string()
+            ),
+            (
+                "ifcfg-team0".to_string(),
+                Some(2),
+                "DEVICETYPE=Team".to_string()
+            ),
+            (
+                "route-bond0".to_string(),
+                Some(2),
+                "10.0.0.0/8 via 192.0.2.254 realm 5".to_string()
+            ),
+            (
+                "rule-bond0".to_string(),
+                Some(2),
+                "from 192.0.2.1 lookup 100 nat 192.0.2.99".to_string()
+            ),
+        ]
+    );
+    assert_eq!(warnings[1].iface.as_deref(), Some("bond0"));
+}
+
+#[test]
+fn test_import_debian_interfaces() {
+    let (net_state, warnings) = NetworkState::from_debian_interfaces(
+        &test_data_path("debian/interfaces"),
+    )
+    .unwrap();
+
+    assert_state_eq(
+        &net_state,
+        r"---
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.53
+    - 198.51.100.53
+    search:
+    - example.org
+route-rules:
+  config:
+  - family: ipv4
+    ip-from: 192.0.2.0/24
+    priority: 1000
+    route-table: 100
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: bond0
+    next-hop-address: 192.0.2.254
+    table-id: 100
+  - destination: 0.0.0.0/0
+    next-hop-interface: bond0
+    next-hop-address: 192.0.2.1
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.10
+      prefix-length: 24
+  ipv6:
+    enabled: true
+    dhcp: false
+    autoconf: false
+    address:
+    - ip: 2001:db8::10
+      prefix-length: 64
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 100
+      primary: eth1
+    port:
+    - eth1
+    - eth2
+- name: bond0.100
+  type: vlan
+  state: up
+  mtu: 1400
+  ipv4:
+    enabled: true
+    dhcp: true
+  vlan:
+    base-iface: bond0
+    id: 100
+- name: br0
+  type: linux-bridge
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 203.0.113.10
+      prefix-length: 24
+  bridge:
+    options:
+      stp:
+        enabled: false
+        forward-delay: 5
+    port:
+    - name: eth3
+      stp-path-cost: 100
+    - name: eth4
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+- name: eth3
+  type: ethernet
+  state: up
+- name: eth4
+  type: ethernet
+  state: up
+- name: eth5
+  type: ethernet
+  state: down
+  ipv4:
+    enabled: true
+    dhcp: true
+",
+    );
+    assert_eq!(
+        simplify_warnings(&warnings),
+        vec![
+            (
+                "interfaces".to_string(),
+                Some(18),
+                "post-up /usr/local/bin/custom.sh".to_string()
+            ),
+            ("br0".to_string(), Some(14), "mapping eth6".to_string()),
+            (
+                "br0".to_string(),
+                Some(17),
+                "iface ppp0 inet ppp".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_import_ifcfg_folder_not_exist() {
+    let result =
+        NetworkState::from_ifcfg_dir(&test_data_path("not_exist_folder"));
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(all(test, feature = "query_apply"))]
+mod apply_report;
 #[cfg(test)]
 mod base;
 #[cfg(test)]
@@ -9,6 +11,8 @@ mod bridge;
 #[cfg(test)]
 mod debug_trait;
 #[cfg(test)]
+mod dhcp;
+#[cfg(test)]
 mod dns;
 #[cfg(test)]
 mod ethernet;
@@ -19,6 +23,8 @@ mod gen_diff;
 #[cfg(test)]
 mod gen_revert;
 #[cfg(test)]
+mod geneve;
+#[cfg(test)]
 mod ifaces;
 #[cfg(test)]
 mod ifaces_ctrller;
@@ -27,8 +33,14 @@ mod infiniband;
 #[cfg(test)]
 mod ip;
 #[cfg(test)]
+mod ip_tunnel;
+#[cfg(test)]
+mod ip_vlan;
+#[cfg(test)]
 mod ipsec;
 #[cfg(test)]
+mod legacy;
+#[cfg(test)]
 mod lldp;
 #[cfg(test)]
 mod mac_vlan;
@@ -38,6 +50,16 @@ mod mac_vtap;
 mod mptcp;
 #[cfg(test)]
 mod net_state;
+#[cfg(all(test, feature = "query_apply"))]
+mod netns;
+#[cfg(all(test, feature = "query_apply"))]
+mod nispor;
+#[cfg(all(test, feature = "gen_conf"))]
+mod netplan;
+#[cfg(all(test, feature = "gen_conf"))]
+mod networkd;
+#[cfg(test)]
+mod nexthop;
 #[cfg(test)]
 mod nm;
 #[cfg(test)]
@@ -46,6 +68,8 @@ mod ovn;
 mod ovs;
 #[cfg(test)]
 mod ovsdb;
+#[cfg(all(test, feature = "gen_conf"))]
+mod ovsdb_gen_conf;
 #[cfg(test)]
 mod policy;
 #[cfg(test)]
@@ -53,6 +77,8 @@ mod route;
 #[cfg(test)]
 mod route_rule;
 #[cfg(test)]
+mod route_table;
+#[cfg(test)]
 mod sriov;
 #[cfg(test)]
 mod statistic;
@@ -64,3 +90,7 @@ mod vlan;
 mod vrf;
 #[cfg(test)]
 mod vxlan;
+#[cfg(all(test, feature = "query_apply"))]
+mod watch;
+#[cfg(test)]
+mod wireguard;
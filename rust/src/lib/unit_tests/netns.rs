@@ -0,0 +1,176 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    query_apply::netns_path, ErrorKind, Interface, Interfaces,
+    MergedInterfaces, NetworkState,
+};
+
+#[test]
+fn test_netns_path() {
+    assert_eq!(netns_path("ns1"), "/run/netns/ns1");
+    assert_eq!(netns_path("1234"), "/proc/1234/ns/net");
+    assert_eq!(netns_path("/proc/1/ns/net"), "/proc/1/ns/net");
+}
+
+#[test]
+fn test_netns_new_veth_peer_moved_after_created() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: veth1
+  type: veth
+  veth:
+    peer: veth1-ep
+- name: veth1-ep
+  type: ethernet
+  netns: ns1
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, Interfaces::default(), false, false)
+            .unwrap();
+
+    let peer = merged_ifaces.kernel_ifaces.get("veth1-ep").unwrap();
+    assert!(peer.is_desired());
+    assert_eq!(
+        peer.for_apply
+            .as_ref()
+            .and_then(|i| i.base_iface().netns.as_deref()),
+        Some("ns1")
+    );
+}
+
+#[test]
+fn test_netns_iface_moved_already() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: veth1
+  type: veth
+  veth:
+    peer: veth1-ep
+- name: veth1-ep
+  type: ethernet
+  netns: ns1
+",
+    )
+    .unwrap();
+    // The veth peer in other network namespace is hidden
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: veth1
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    assert!(!merged_ifaces.kernel_ifaces.contains_key("veth1-ep"));
+    let veth1 = merged_ifaces.kernel_ifaces.get("veth1").unwrap();
+    if let Some(Interface::Ethernet(eth_iface)) = veth1.for_apply.as_ref() {
+        assert!(eth_iface.veth.is_none());
+    } else {
+        panic!("Expecting ethernet interface, but got {:?}", veth1);
+    }
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_netns_verify_iface_not_moved() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  netns: ns1
+",
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    let result = merged_ifaces.verify(&cur_ifaces);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+        assert_eq!(
+            e.verification_diff().map(|d| d.path.as_str()),
+            Some("interfaces[name=eth1].netns")
+        );
+    }
+}
+
+#[test]
+fn test_netns_not_allowed_for_port() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  controller: br0
+  netns: ns1
+- name: br0
+  type: linux-bridge
+",
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_netns_require_kernel_only() {
+    let mut net_state = NetworkState::new();
+    net_state.set_netns("ns1");
+
+    let result = net_state.apply();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+
+    let result = net_state.retrieve();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
+
+#[test]
+fn test_netns_create_route_table_names_not_supported() {
+    let mut net_state = NetworkState::new();
+    net_state.set_netns("ns1");
+    net_state.set_kernel_only(true);
+    net_state.set_create_route_table_names(true);
+
+    let result = net_state.apply();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
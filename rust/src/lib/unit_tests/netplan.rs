@@ -0,0 +1,267 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, GenConfBackend, NetworkState};
+
+fn gen_netplan_conf(yaml: &str) -> String {
+    let mut net_state: NetworkState = serde_yaml::from_str(yaml).unwrap();
+    net_state.set_gen_conf_backend(GenConfBackend::Netplan);
+    let mut confs = net_state.gen_conf().unwrap();
+    let mut confs = confs.remove("netplan").unwrap();
+    assert_eq!(confs.len(), 1);
+    let (file_name, content) = confs.remove(0);
+    assert_eq!(file_name, "90-nmstate.yaml");
+    content
+}
+
+fn gen_netplan_conf_err(yaml: &str) -> crate::NmstateError {
+    let mut net_state: NetworkState = serde_yaml::from_str(yaml).unwrap();
+    net_state.set_gen_conf_backend(GenConfBackend::Netplan);
+    net_state.gen_conf().unwrap_err()
+}
+
+#[test]
+fn test_netplan_gen_conf_bond_with_static_ip() {
+    let conf = gen_netplan_conf(
+        r"---
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.1
+    search:
+    - example.org
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-interface: bond99
+    next-hop-address: 192.0.2.1
+    metric: 100
+route-rules:
+  config:
+  - ip-from: 192.0.2.0/24
+    route-table: 100
+    priority: 1000
+interfaces:
+- name: bond99
+  type: bond
+  state: up
+  mtu: 9000
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+  ipv6:
+    enabled: false
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 100
+      primary: eth1
+    port:
+    - eth1
+",
+    );
+
+    assert_eq!(
+        conf,
+        r"network:
+  version: 2
+  ethernets:
+    eth1:
+      dhcp4: false
+      dhcp6: false
+      accept-ra: false
+      link-local: []
+  bonds:
+    bond99:
+      interfaces:
+      - eth1
+      parameters:
+        mode: active-backup
+        mii-monitor-interval: 100
+        primary: eth1
+      mtu: 9000
+      dhcp4: false
+      dhcp6: false
+      accept-ra: false
+      link-local: []
+      addresses:
+      - 192.0.2.2/24
+      nameservers:
+        addresses:
+        - 192.0.2.1
+        search:
+        - example.org
+      routes:
+      - to: default
+        via: 192.0.2.1
+        metric: 100
+      routing-policy:
+      - from: 192.0.2.0/24
+        table: 100
+        priority: 1000
+"
+    );
+}
+
+#[test]
+fn test_netplan_gen_conf_vlan_and_vxlan_over_undesired_parent() {
+    let conf = gen_netplan_conf(
+        r"---
+interfaces:
+- name: eth1.10
+  type: vlan
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: true
+  vlan:
+    base-iface: eth1
+    id: 10
+- name: vxlan20
+  type: vxlan
+  state: up
+  vxlan:
+    base-iface: eth1
+    id: 20
+    remote: 192.0.2.9
+    destination-port: 4789
+",
+    );
+
+    assert_eq!(
+        conf,
+        r"network:
+  version: 2
+  ethernets:
+    eth1: {}
+  vlans:
+    eth1.10:
+      id: 10
+      link: eth1
+      dhcp4: true
+      dhcp6: false
+      dhcp4-overrides:
+        use-dns: true
+        use-routes: true
+      accept-ra: false
+      link-local: []
+  tunnels:
+    vxlan20:
+      mode: vxlan
+      id: 20
+      link: eth1
+      remote: 192.0.2.9
+      port: 4789
+      dhcp4: false
+      dhcp6: false
+      accept-ra: false
+      link-local: []
+"
+    );
+}
+
+#[test]
+fn test_netplan_gen_conf_bridge_vlan_filtering_not_supported() {
+    let err = gen_netplan_conf_err(
+        r"---
+interfaces:
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+      vlan:
+        mode: access
+        tag: 100
+",
+    );
+
+    assert_eq!(err.kind(), ErrorKind::NotSupportedError);
+    assert!(err.msg().contains("bridge.port.vlan"));
+    assert!(err.msg().contains("br0"));
+}
+
+#[test]
+fn test_netplan_gen_conf_route_rule_iif_not_supported() {
+    let err = gen_netplan_conf_err(
+        r"---
+route-rules:
+  config:
+  - ip-to: 192.0.2.0/24
+    iif: eth1
+    route-table: 100
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+",
+    );
+
+    assert_eq!(err.kind(), ErrorKind::NotSupportedError);
+    assert!(err.msg().contains("route-rules.iif"));
+}
+
+#[test]
+fn test_netplan_gen_conf_ovs_bridge_port() {
+    let conf = gen_netplan_conf(
+        r"---
+interfaces:
+- name: br0
+  type: ovs-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+",
+    );
+
+    assert_eq!(
+        conf,
+        r"network:
+  version: 2
+  ethernets:
+    eth1:
+      dhcp4: false
+      dhcp6: false
+      accept-ra: false
+      link-local: []
+"
+    );
+}
+
+#[test]
+fn test_netplan_gen_conf_nexthop_not_supported() {
+    let err = gen_netplan_conf_err(
+        r"---
+nexthops:
+  config:
+  - id: 1
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+routes:
+  config:
+  - destination: 203.0.113.0/24
+    nexthop-id: 1
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+",
+    );
+
+    assert_eq!(err.kind(), ErrorKind::NotSupportedError);
+    assert!(err.msg().contains("nexthop-id"));
+}
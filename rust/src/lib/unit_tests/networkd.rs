@@ -0,0 +1,273 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crate::{ErrorKind, GenConfBackend, NetworkState};
+
+fn gen_networkd_conf(yaml: &str) -> HashMap<String, String> {
+    let mut net_state: NetworkState = serde_yaml::from_str(yaml).unwrap();
+    net_state.set_gen_conf_backend(GenConfBackend::SystemdNetworkd);
+    let mut confs = net_state.gen_conf().unwrap();
+    confs
+        .remove("systemd-networkd")
+        .unwrap()
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn test_networkd_gen_conf_bond_with_static_ip() {
+    let confs = gen_networkd_conf(
+        r"---
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.1
+    search:
+    - example.org
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-interface: bond99
+    next-hop-address: 192.0.2.1
+    metric: 100
+interfaces:
+- name: bond99
+  type: bond
+  state: up
+  mtu: 9000
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+  ipv6:
+    enabled: false
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 100
+      primary: eth1
+    port:
+    - eth1
+    - eth2
+",
+    );
+
+    assert_eq!(
+        confs["10-bond99.netdev"],
+        "[NetDev]\nName=bond99\nKind=bond\n\n\
+        [Bond]\nMode=active-backup\nMIIMonitorSec=100ms\n"
+    );
+    let bond_network = confs["10-bond99.network"].as_str();
+    assert!(bond_network.starts_with("[Match]\nName=bond99\n"));
+    assert!(bond_network.contains("MTUBytes=9000\n"));
+    assert!(bond_network.contains("DHCP=no\n"));
+    assert!(bond_network.contains("LinkLocalAddressing=no\n"));
+    assert!(bond_network.contains("DNS=192.0.2.1\n"));
+    assert!(bond_network.contains("Domains=example.org\n"));
+    assert!(bond_network.contains("[Address]\nAddress=192.0.2.2/24\n"));
+    assert!(bond_network.contains(
+        "[Route]\nDestination=0.0.0.0/0\nGateway=192.0.2.1\nMetric=100\n"
+    ));
+
+    let eth1_network = confs["10-eth1.network"].as_str();
+    assert!(eth1_network.contains("Bond=bond99\n"));
+    assert!(eth1_network.contains("PrimarySlave=yes\n"));
+    let eth2_network = confs["10-eth2.network"].as_str();
+    assert!(eth2_network.contains("Bond=bond99\n"));
+    assert!(!eth2_network.contains("PrimarySlave"));
+}
+
+#[test]
+fn test_networkd_gen_conf_vlan_over_undesired_parent() {
+    let confs = gen_networkd_conf(
+        r"---
+interfaces:
+- name: eth1.101
+  type: vlan
+  state: up
+  vlan:
+    base-iface: eth1
+    id: 101
+",
+    );
+
+    assert_eq!(
+        confs["10-eth1.101.netdev"],
+        "[NetDev]\nName=eth1.101\nKind=vlan\n\n\
+        [VLAN]\nId=101\nReorderHeader=yes\n"
+    );
+    assert_eq!(
+        confs["10-eth1.network"],
+        "[Match]\nName=eth1\n\n[Network]\nVLAN=eth1.101\n"
+    );
+}
+
+#[test]
+fn test_networkd_gen_conf_route_rule_and_vrf() {
+    let confs = gen_networkd_conf(
+        r"---
+route-rules:
+  config:
+  - ip-from: 192.0.2.0/24
+    priority: 1000
+    route-table: 100
+interfaces:
+- name: vrf0
+  type: vrf
+  state: up
+  vrf:
+    port:
+    - eth1
+    route-table-id: 100
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: true
+    auto-dns: false
+",
+    );
+
+    assert_eq!(
+        confs["10-vrf0.netdev"],
+        "[NetDev]\nName=vrf0\nKind=vrf\n\n[VRF]\nTable=100\n"
+    );
+    let eth1_network = confs["10-eth1.network"].as_str();
+    assert!(eth1_network.contains("DHCP=ipv4\n"));
+    assert!(eth1_network.contains("VRF=vrf0\n"));
+    assert!(eth1_network.contains("[DHCPv4]\nUseDNS=no\n"));
+    assert!(eth1_network.contains(
+        "[RoutingPolicyRule]\nFrom=192.0.2.0/24\nPriority=1000\nTable=100\n"
+    ));
+}
+
+#[test]
+fn test_networkd_gen_conf_geneve_and_bareudp() {
+    let confs = gen_networkd_conf(
+        r"---
+interfaces:
+- name: geneve1
+  type: geneve
+  state: up
+  geneve:
+    id: 102
+    remote: 192.0.2.1
+    destination-port: 6081
+    ttl: 64
+    df: inherit
+    udp-checksum: true
+- name: bareudp1
+  type: bareudp
+  state: up
+  bareudp:
+    destination-port: 6635
+    ethertype: mpls-uc
+",
+    );
+
+    assert_eq!(
+        confs["10-geneve1.netdev"],
+        "[NetDev]\nName=geneve1\nKind=geneve\n\n\
+        [GENEVE]\nId=102\nRemote=192.0.2.1\nDestinationPort=6081\nTTL=64\n\
+        IPDoNotFragment=inherit\nUDPChecksum=yes\n"
+    );
+    assert_eq!(
+        confs["10-bareudp1.netdev"],
+        "[NetDev]\nName=bareudp1\nKind=bareudp\n\n\
+        [BareUDP]\nDestinationPort=6635\nEtherType=mpls-uc\n"
+    );
+}
+
+#[test]
+fn test_networkd_gen_conf_unsupported_property() {
+    let mut net_state: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: bond99
+  type: bond
+  state: up
+  link-aggregation:
+    mode: balance-rr
+    options:
+      use_carrier: false
+",
+    )
+    .unwrap();
+    net_state.set_gen_conf_backend(GenConfBackend::SystemdNetworkd);
+
+    let result = net_state.gen_conf();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+        assert!(e.msg().contains("link-aggregation.options.use_carrier"));
+    }
+}
+
+#[test]
+fn test_networkd_gen_conf_ovs_bridge_port() {
+    let confs = gen_networkd_conf(
+        r"---
+interfaces:
+- name: br0
+  type: ovs-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+",
+    );
+
+    assert_eq!(confs.len(), 1);
+    assert_eq!(
+        confs.get("10-eth1.network").unwrap(),
+        "[Match]
+Name=eth1
+
+[Network]
+DHCP=no
+LinkLocalAddressing=no
+IPv6AcceptRA=no
+"
+    );
+}
+
+#[test]
+fn test_networkd_gen_conf_nexthop_not_supported() {
+    let mut net_state: NetworkState = serde_yaml::from_str(
+        r"---
+nexthops:
+  config:
+  - id: 1
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+routes:
+  config:
+  - destination: 203.0.113.0/24
+    nexthop-id: 1
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+",
+    )
+    .unwrap();
+    net_state.set_gen_conf_backend(GenConfBackend::SystemdNetworkd);
+
+    let result = net_state.gen_conf();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+        assert!(e.msg().contains("nexthop-id"));
+    }
+}
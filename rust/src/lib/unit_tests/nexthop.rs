@@ -0,0 +1,317 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    unit_tests::testlib::new_eth_iface, ErrorKind, MergedNetworkState,
+    MergedNexthops, NetworkState, NexthopEntry, Nexthops, RouteEntry,
+};
+
+fn gen_test_current_state() -> NetworkState {
+    let mut state = NetworkState::new();
+    state.interfaces.push(new_eth_iface("eth1"));
+    state.interfaces.push(new_eth_iface("eth2"));
+    state
+}
+
+fn gen_test_nexthops() -> Nexthops {
+    serde_yaml::from_str(
+        r"
+config:
+- id: 1
+  family: ipv4
+  next-hop-interface: eth1
+  next-hop-address: 192.0.2.1
+- id: 2
+  family: ipv4
+  next-hop-interface: eth2
+  next-hop-address: 198.51.100.1
+- id: 100
+  group:
+  - id: 1
+    weight: 3
+  - id: 2
+    weight: 1
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_nexthop_stringlized_attributes() {
+    let nh: NexthopEntry = serde_yaml::from_str(
+        r#"
+id: "100"
+group:
+- id: "1"
+  weight: 2
+"#,
+    )
+    .unwrap();
+    assert_eq!(nh.id, Some(100));
+    assert_eq!(nh.group.as_ref().unwrap()[0].id, 1);
+}
+
+#[test]
+fn test_nexthop_sanitize_group() {
+    let mut nh: NexthopEntry = serde_yaml::from_str(
+        r"
+id: 100
+group:
+- id: 2
+- id: 1
+  weight: 3
+",
+    )
+    .unwrap();
+    nh.sanitize().unwrap();
+    let members = nh.group.as_ref().unwrap();
+    assert_eq!(members[0].id, 1);
+    assert_eq!(members[0].weight, Some(3));
+    assert_eq!(members[1].id, 2);
+    assert_eq!(members[1].weight, Some(1));
+}
+
+#[test]
+fn test_nexthop_sanitize_family() {
+    let mut nh: NexthopEntry = serde_yaml::from_str(
+        r"
+id: 1
+next-hop-interface: eth1
+next-hop-address: 2001:db8:0::1
+",
+    )
+    .unwrap();
+    nh.sanitize().unwrap();
+    assert_eq!(nh.next_hop_addr.as_deref(), Some("2001:db8::1"));
+    assert_eq!(nh.family, Some(crate::AddressFamily::IPv6));
+}
+
+#[test]
+fn test_nexthop_invalid() {
+    for yml in [
+        "next-hop-interface: eth1",
+        "id: 1",
+        "id: 1\nfamily: ipv6\nnext-hop-interface: eth1\n\
+        next-hop-address: 192.0.2.1",
+        "id: 100\nnext-hop-interface: eth1\ngroup:\n- id: 1",
+        "id: 100\ngroup: []",
+        "id: 100\ngroup:\n- id: 100",
+        "id: 100\ngroup:\n- id: 1\n- id: 1",
+        "id: 100\ngroup:\n- id: 1\n  weight: 257",
+    ] {
+        let mut nh: NexthopEntry = serde_yaml::from_str(yml).unwrap();
+        let result = nh.sanitize();
+        assert!(result.is_err(), "{yml}");
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        }
+    }
+}
+
+#[test]
+fn test_nexthop_group_refer_nonexistent_member() {
+    let mut desired = NetworkState::new();
+    desired.nexthops = serde_yaml::from_str(
+        r"
+config:
+- id: 1
+  next-hop-interface: eth1
+- id: 100
+  group:
+  - id: 1
+  - id: 2
+",
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(
+        desired,
+        gen_test_current_state(),
+        false,
+        false,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_route_refer_nonexistent_nexthop() {
+    let mut desired = NetworkState::new();
+    desired.routes = serde_yaml::from_str(
+        r"
+config:
+- destination: 203.0.113.0/24
+  nexthop-id: 100
+",
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(
+        desired,
+        gen_test_current_state(),
+        false,
+        false,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_route_with_nexthop_id_and_next_hop() {
+    let mut desired = NetworkState::new();
+    desired.nexthops = gen_test_nexthops();
+    desired.routes = serde_yaml::from_str(
+        r"
+config:
+- destination: 203.0.113.0/24
+  next-hop-interface: eth1
+  nexthop-id: 100
+",
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(
+        desired,
+        gen_test_current_state(),
+        false,
+        false,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_nexthop_expand_route() {
+    let merged = MergedNexthops::new(
+        gen_test_nexthops(),
+        Nexthops::new(),
+        &Default::default(),
+        false,
+    )
+    .unwrap();
+    let rt: RouteEntry = serde_yaml::from_str(
+        r"
+destination: 203.0.113.0/24
+nexthop-id: 100
+",
+    )
+    .unwrap();
+
+    let expected: Vec<RouteEntry> = serde_yaml::from_str(
+        r"
+- destination: 203.0.113.0/24
+  next-hop-interface: eth1
+  next-hop-address: 192.0.2.1
+  weight: 3
+- destination: 203.0.113.0/24
+  next-hop-interface: eth2
+  next-hop-address: 198.51.100.1
+  weight: 1
+",
+    )
+    .unwrap();
+    assert_eq!(merged.expand_route(&rt).unwrap(), expected);
+}
+
+#[test]
+fn test_nexthop_expand_ipv6_route_with_weight() {
+    let merged = MergedNexthops::new(
+        serde_yaml::from_str(
+            r"
+config:
+- id: 1
+  next-hop-interface: eth1
+  next-hop-address: 2001:db8:1::1
+- id: 2
+  next-hop-interface: eth2
+  next-hop-address: 2001:db8:2::1
+- id: 100
+  group:
+  - id: 1
+    weight: 2
+  - id: 2
+",
+        )
+        .unwrap(),
+        Nexthops::new(),
+        &Default::default(),
+        false,
+    )
+    .unwrap();
+    let rt: RouteEntry = serde_yaml::from_str(
+        r"
+destination: 2001:db8:f::/64
+nexthop-id: 100
+",
+    )
+    .unwrap();
+
+    let result = merged.expand_route(&rt);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
+
+#[test]
+fn test_nexthop_gen_diff() {
+    let mut current = gen_test_current_state();
+    current.nexthops = gen_test_nexthops();
+    let mut desired = NetworkState::new();
+    desired.nexthops = serde_yaml::from_str(
+        r"
+config:
+- id: 2
+  next-hop-interface: eth2
+  next-hop-address: 198.51.100.1
+- id: 100
+  state: absent
+- id: 3
+  next-hop-interface: eth2
+",
+    )
+    .unwrap();
+
+    let diff = desired.gen_diff(&current).unwrap();
+
+    let nhs = diff.nexthops.config.as_ref().unwrap();
+    assert_eq!(nhs.len(), 2);
+    assert!(nhs[0].is_absent());
+    assert_eq!(nhs[0].id, Some(100));
+    assert!(!nhs[1].is_absent());
+    assert_eq!(nhs[1].id, Some(3));
+}
+
+#[test]
+fn test_nexthop_interface_marked_as_absent() {
+    let mut current = gen_test_current_state();
+    current.nexthops = gen_test_nexthops();
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r"
+interfaces:
+- name: eth2
+  type: ethernet
+  state: absent
+",
+    )
+    .unwrap();
+    desired.nexthops = serde_yaml::from_str(
+        r"
+config:
+- id: 3
+  next-hop-interface: eth2
+",
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(desired, current, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use netlink_packet_route::link::{InfoData, InfoKind, InfoVrf, InfoVxlan};
+
+use crate::{
+    nispor::{
+        apply::{gen_nl_link_conf, gen_reattach_list},
+        netlink::info_data_nla_bytes,
+    },
+    ErrorKind, Interface, InterfaceType, MergedInterface,
+};
+
+#[test]
+fn test_gen_nl_link_conf_dummy() {
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+name: dummy1
+type: dummy
+",
+    )
+    .unwrap();
+
+    let conf = gen_nl_link_conf(&iface).unwrap().unwrap();
+
+    assert_eq!(conf.kind, InfoKind::Dummy);
+    assert!(conf.info_data.is_none());
+}
+
+#[test]
+fn test_gen_nl_link_conf_vrf() {
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+name: vrf0
+type: vrf
+vrf:
+  route-table-id: 100
+",
+    )
+    .unwrap();
+
+    let conf = gen_nl_link_conf(&iface).unwrap().unwrap();
+
+    assert_eq!(conf.kind, InfoKind::Vrf);
+    assert_eq!(
+        conf.info_data,
+        Some(InfoData::Vrf(vec![InfoVrf::TableId(100)]))
+    );
+}
+
+#[test]
+fn test_gen_nl_link_conf_vxlan() {
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+name: vxlan0
+type: vxlan
+vxlan:
+  id: 101
+  remote: 192.0.2.1
+  destination-port: 4789
+",
+    )
+    .unwrap();
+
+    let conf = gen_nl_link_conf(&iface).unwrap().unwrap();
+
+    assert_eq!(conf.kind, InfoKind::Vxlan);
+    assert_eq!(
+        conf.info_data,
+        Some(InfoData::Vxlan(vec![
+            InfoVxlan::Id(101),
+            InfoVxlan::Group("192.0.2.1".parse().unwrap()),
+            InfoVxlan::Port(4789),
+        ]))
+    );
+
+    let mut expected: Vec<u8> = Vec::new();
+    // IFLA_VXLAN_ID
+    expected.extend_from_slice(&8u16.to_ne_bytes());
+    expected.extend_from_slice(&1u16.to_ne_bytes());
+    expected.extend_from_slice(&101u32.to_ne_bytes());
+    // IFLA_VXLAN_GROUP
+    expected.extend_from_slice(&8u16.to_ne_bytes());
+    expected.extend_from_slice(&2u16.to_ne_bytes());
+    expected.extend_from_slice(&[192, 0, 2, 1]);
+    // IFLA_VXLAN_PORT in network byte order with padding
+    expected.extend_from_slice(&6u16.to_ne_bytes());
+    expected.extend_from_slice(&15u16.to_ne_bytes());
+    expected.extend_from_slice(&4789u16.to_be_bytes());
+    expected.extend_from_slice(&[0, 0]);
+
+    assert_eq!(
+        info_data_nla_bytes(conf.info_data.as_ref().unwrap()),
+        expected
+    );
+}
+
+#[test]
+fn test_gen_nl_link_conf_changed_property() {
+    let old: Interface = serde_yaml::from_str(
+        r"---
+name: vrf0
+type: vrf
+vrf:
+  route-table-id: 100
+",
+    )
+    .unwrap();
+    let new: Interface = serde_yaml::from_str(
+        r"---
+name: vrf0
+type: vrf
+vrf:
+  route-table-id: 101
+",
+    )
+    .unwrap();
+
+    assert_ne!(
+        gen_nl_link_conf(&old).unwrap(),
+        gen_nl_link_conf(&new).unwrap()
+    );
+}
+
+#[test]
+fn test_gen_nl_link_conf_missing_conf() {
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+name: vrf0
+type: vrf
+",
+    )
+    .unwrap();
+
+    let result = gen_nl_link_conf(&iface);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_gen_nl_link_conf_not_netlink_created() {
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+name: eth1
+type: ethernet
+",
+    )
+    .unwrap();
+
+    assert_eq!(gen_nl_link_conf(&iface).unwrap(), None);
+}
+
+#[test]
+fn test_gen_reattach_list_vrf_ports() {
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+name: vrf0
+type: vrf
+vrf:
+  port:
+  - eth1
+  - eth2
+  route-table-id: 100
+",
+    )
+    .unwrap();
+    let merged_iface = MergedInterface::new(Some(iface), None).unwrap();
+
+    assert_eq!(
+        gen_reattach_list(&merged_iface).unwrap(),
+        vec![
+            ("eth1".to_string(), "vrf0".to_string()),
+            ("eth2".to_string(), "vrf0".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_gen_reattach_list_port_of_controller() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r"---
+name: vxlan0
+type: vxlan
+vxlan:
+  id: 101
+",
+    )
+    .unwrap();
+    iface.base_iface_mut().controller = Some("br0".to_string());
+    iface.base_iface_mut().controller_type = Some(InterfaceType::LinuxBridge);
+    let merged_iface = MergedInterface::new(None, Some(iface)).unwrap();
+
+    assert_eq!(
+        gen_reattach_list(&merged_iface).unwrap(),
+        vec![("vxlan0".to_string(), "br0".to_string())]
+    );
+}
+
+#[test]
+fn test_gen_reattach_list_ovs_bridge_port() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r"---
+name: vxlan0
+type: vxlan
+vxlan:
+  id: 101
+",
+    )
+    .unwrap();
+    iface.base_iface_mut().controller = Some("ovs-br0".to_string());
+    iface.base_iface_mut().controller_type = Some(InterfaceType::OvsBridge);
+    let merged_iface = MergedInterface::new(None, Some(iface)).unwrap();
+
+    let result = gen_reattach_list(&merged_iface);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use netlink_packet_core::Emitable;
+
+use crate::{
+    nispor::{
+        bond::gen_bond_sysfs_opts, netlink::bond_port_prio_link_info,
+        nispor_verify_bond_opts,
+    },
+    BondConfig, BondOptions, ErrorKind, Interfaces, MergedInterfaces,
+};
+
+fn gen_bond_ifaces(miimon: u32) -> Interfaces {
+    serde_yaml::from_str(&format!(
+        r"---
+- name: bond99
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: {miimon}
+"
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_bond_sysfs_mode() {
+    for (mode, sysfs_value) in [
+        ("balance-rr", "balance-rr"),
+        ("active-backup", "active-backup"),
+        ("balance-xor", "balance-xor"),
+        ("broadcast", "broadcast"),
+        ("802.3ad", "802.3ad"),
+        ("balance-tlb", "balance-tlb"),
+        ("balance-alb", "balance-alb"),
+    ] {
+        let conf: BondConfig =
+            serde_yaml::from_str(&format!("mode: {mode}")).unwrap();
+        assert_eq!(conf.mode.unwrap().to_string(), sysfs_value);
+    }
+}
+
+#[test]
+fn test_bond_sysfs_opts() {
+    let opts: BondOptions = serde_yaml::from_str(
+        r"---
+miimon: 100
+xmit_hash_policy: layer3+4
+lacp_rate: fast
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        gen_bond_sysfs_opts(&opts),
+        vec![
+            ("lacp_rate", "fast".to_string()),
+            ("miimon", "100".to_string()),
+            ("xmit_hash_policy", "layer3+4".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_bond_sysfs_opts_numeric_lacp_rate_and_hash_policy() {
+    let opts: BondOptions = serde_yaml::from_str(
+        r"---
+xmit_hash_policy: 2
+lacp_rate: 0
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        gen_bond_sysfs_opts(&opts),
+        vec![
+            ("lacp_rate", "slow".to_string()),
+            ("xmit_hash_policy", "layer2+3".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_bond_sysfs_opts_arp_ip_target_before_arp_interval() {
+    let opts: BondOptions = serde_yaml::from_str(
+        r"---
+arp_interval: 1000
+arp_ip_target: 192.0.2.1,192.0.2.2
+all_slaves_active: delivered
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        gen_bond_sysfs_opts(&opts),
+        vec![
+            ("all_slaves_active", "1".to_string()),
+            ("arp_ip_target", "192.0.2.1,192.0.2.2".to_string()),
+            ("arp_interval", "1000".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_bond_port_prio_netlink() {
+    let mut expected: Vec<u8> = Vec::new();
+    // IFLA_LINKINFO
+    expected.extend_from_slice(&28u16.to_ne_bytes());
+    expected.extend_from_slice(&18u16.to_ne_bytes());
+    // IFLA_INFO_SLAVE_KIND
+    expected.extend_from_slice(&9u16.to_ne_bytes());
+    expected.extend_from_slice(&4u16.to_ne_bytes());
+    expected.extend_from_slice(b"bond\0\0\0\0");
+    // IFLA_INFO_SLAVE_DATA
+    expected.extend_from_slice(&12u16.to_ne_bytes());
+    expected.extend_from_slice(&5u16.to_ne_bytes());
+    // IFLA_BOND_SLAVE_PRIO
+    expected.extend_from_slice(&8u16.to_ne_bytes());
+    expected.extend_from_slice(&9u16.to_ne_bytes());
+    expected.extend_from_slice(&(-5i32).to_ne_bytes());
+
+    let nla = bond_port_prio_link_info(-5);
+    let mut buf = vec![0u8; nla.buffer_len()];
+    nla.emit(&mut buf);
+    assert_eq!(buf, expected);
+}
+
+#[test]
+fn test_bond_opts_verify_kernel_mode() {
+    let merged_ifaces = MergedInterfaces::new(
+        gen_bond_ifaces(100),
+        Interfaces::new(),
+        false,
+        false,
+    )
+    .unwrap();
+
+    nispor_verify_bond_opts(&merged_ifaces, &gen_bond_ifaces(100)).unwrap();
+}
+
+#[test]
+fn test_bond_opts_verify_kernel_mode_mismatch() {
+    let merged_ifaces = MergedInterfaces::new(
+        gen_bond_ifaces(100),
+        Interfaces::new(),
+        false,
+        false,
+    )
+    .unwrap();
+
+    let result = nispor_verify_bond_opts(&merged_ifaces, &gen_bond_ifaces(0));
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+        assert_eq!(
+            e.msg(),
+            "Verification failure: bond99.interface.link-aggregation.\
+            options.miimon desire '100', current '0'"
+        );
+        let diff = e.verification_diff().unwrap();
+        assert_eq!(
+            diff.path,
+            "interfaces[name=bond99].link-aggregation.options.miimon"
+        );
+        assert_eq!(diff.desired, serde_json::json!(100));
+        assert_eq!(diff.current, serde_json::json!(0));
+    }
+}
@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::os::unix::fs::PermissionsExt;
+
+use crate::{
+    nispor::checkpoint::{
+        checkpoint_create, checkpoint_destroy, checkpoint_file_path,
+        checkpoint_get, checkpoint_timeout_extend, checkpoints,
+        rollback_timer_args, rollback_timer_unit,
+    },
+    ErrorKind, NetworkState,
+};
+
+const TEST_CHECKPOINT_DIR: &str = "/run/nmstate/checkpoint";
+
+// Each test use its own folder as tests are running in parallel
+fn gen_test_dir(name: &str) -> String {
+    let dir_path = std::env::temp_dir()
+        .join(format!("nmstate_checkpoint_{name}_{}", std::process::id()))
+        .display()
+        .to_string();
+    std::fs::remove_dir_all(&dir_path).ok();
+    dir_path
+}
+
+fn gen_test_states() -> (NetworkState, NetworkState) {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+",
+    )
+    .unwrap();
+    let pre_apply: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: false
+",
+    )
+    .unwrap();
+    (desired, pre_apply)
+}
+
+#[test]
+fn test_kernel_checkpoint_create_and_commit() {
+    let dir_path = gen_test_dir("commit");
+    let (desired, pre_apply) = gen_test_states();
+    let route_table_names = vec![(100u32, "nmstate_a".to_string())];
+
+    let name = checkpoint_create(
+        &dir_path,
+        &desired,
+        &pre_apply,
+        &route_table_names,
+        60,
+    )
+    .unwrap();
+
+    let file_path = checkpoint_file_path(&dir_path, &name).unwrap();
+    let mode = std::fs::metadata(&file_path).unwrap().permissions().mode();
+    let cp = checkpoint_get(&dir_path, &name).unwrap();
+    let last_cp = checkpoint_get(&dir_path, "").unwrap();
+    // Another checkpoint is not allowed before this one expired
+    let result = checkpoint_create(&dir_path, &desired, &pre_apply, &[], 60);
+    checkpoint_destroy(&dir_path, &name).unwrap();
+    let cps_after_commit = checkpoints(&dir_path).unwrap();
+    let get_after_commit = checkpoint_get(&dir_path, &name);
+    std::fs::remove_dir_all(&dir_path).ok();
+
+    assert!(name.starts_with(&format!("{dir_path}/")));
+    assert_eq!(mode & 0o777, 0o600);
+    assert_eq!(cp.name, name);
+    assert!(!cp.is_expired());
+    assert_eq!(cp.desired, desired);
+    assert_eq!(cp.pre_apply, pre_apply);
+    assert_eq!(cp.netns, None);
+    assert_eq!(cp.route_table_names, route_table_names);
+    assert_eq!(cp.rollback_timer, None);
+    assert_eq!(last_cp, cp);
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::PluginFailure);
+    assert!(cps_after_commit.is_empty());
+    assert_eq!(
+        get_after_commit.unwrap_err().kind(),
+        ErrorKind::InvalidArgument
+    );
+}
+
+#[test]
+fn test_kernel_checkpoint_expire() {
+    let dir_path = gen_test_dir("expire");
+    let (desired, pre_apply) = gen_test_states();
+
+    let name =
+        checkpoint_create(&dir_path, &desired, &pre_apply, &[], 60).unwrap();
+    checkpoint_timeout_extend(&dir_path, &name, 0).unwrap();
+    let cp = checkpoint_get(&dir_path, &name).unwrap();
+    // Expired checkpoint does not block new checkpoint
+    let new_name =
+        checkpoint_create(&dir_path, &desired, &pre_apply, &[], 60).unwrap();
+    let expired: Vec<String> = checkpoints(&dir_path)
+        .unwrap()
+        .into_iter()
+        .filter(|cp| cp.is_expired())
+        .map(|cp| cp.name)
+        .collect();
+    std::fs::remove_dir_all(&dir_path).ok();
+
+    assert!(cp.is_expired());
+    assert_ne!(name, new_name);
+    assert_eq!(expired, vec![name]);
+}
+
+#[test]
+fn test_kernel_checkpoint_rollback_revert_state() {
+    let dir_path = gen_test_dir("rollback");
+    let (desired, pre_apply) = gen_test_states();
+
+    let name =
+        checkpoint_create(&dir_path, &desired, &pre_apply, &[], 60).unwrap();
+    let cp = checkpoint_get(&dir_path, &name).unwrap();
+    std::fs::remove_dir_all(&dir_path).ok();
+
+    let revert_state = cp.revert_state().unwrap();
+    assert!(revert_state.kernel_only());
+    let iface = revert_state.interfaces.kernel_ifaces.get("eth1").unwrap();
+    assert_eq!(
+        iface.base_iface().ipv4.as_ref().map(|i| i.enabled),
+        Some(false)
+    );
+}
+
+#[test]
+fn test_kernel_checkpoint_name_validation() {
+    let valid = format!("{TEST_CHECKPOINT_DIR}/0123456789abcdef");
+    assert_eq!(
+        checkpoint_file_path(TEST_CHECKPOINT_DIR, &valid).unwrap(),
+        format!("{valid}.yml")
+    );
+    for invalid in [
+        "",
+        TEST_CHECKPOINT_DIR,
+        "/run/nmstate/checkpoint/",
+        "/run/nmstate/checkpoint/../../../etc/passwd",
+        "/run/nmstate/checkpoint/abc/def",
+        "/run/nmstate/checkpoint/xyz",
+        "/run/nmstate/checkpoint0123",
+        "/etc/0123456789abcdef",
+        "/org/freedesktop/NetworkManager/Checkpoint/1",
+    ] {
+        let result = checkpoint_file_path(TEST_CHECKPOINT_DIR, invalid);
+        assert_eq!(
+            result.unwrap_err().kind(),
+            ErrorKind::InvalidArgument,
+            "{invalid} should be invalid"
+        );
+    }
+}
+
+#[test]
+fn test_kernel_checkpoint_rollback_timer() {
+    let checkpoint = format!("{TEST_CHECKPOINT_DIR}/0123456789abcdef");
+    let unit = rollback_timer_unit(&checkpoint).unwrap();
+
+    assert_eq!(unit, "nmstate-checkpoint-0123456789abcdef");
+    assert_eq!(
+        rollback_timer_args(&unit, &checkpoint, 60, "/usr/bin/nmstatectl"),
+        vec![
+            "--quiet".to_string(),
+            "--unit=nmstate-checkpoint-0123456789abcdef".to_string(),
+            "--on-active=60s".to_string(),
+            "--timer-property=AccuracySec=1s".to_string(),
+            "/usr/bin/nmstatectl".to_string(),
+            "rollback".to_string(),
+            checkpoint,
+        ]
+    );
+    assert!(rollback_timer_unit("/tmp/0123456789abcdef").is_err());
+}
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{nispor::dhcp::mark_leased_addr, DhcpLease, InterfaceIpAddr};
+
+fn gen_test_lease() -> DhcpLease {
+    serde_yaml::from_str(
+        r"---
+address: 192.0.2.100
+prefix-length: 24
+obtained: 1000
+lease-time: 3600
+renew-time: 1800
+rebind-time: 3150
+",
+    )
+    .unwrap()
+}
+
+fn gen_test_addrs() -> Option<Vec<InterfaceIpAddr>> {
+    serde_yaml::from_str(
+        r"---
+- ip: 192.0.2.100
+  prefix-length: 24
+- ip: 198.51.100.1
+  prefix-length: 24
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_dhcp_mark_leased_addr_as_dynamic() {
+    let lease = gen_test_lease();
+    let mut addrs = gen_test_addrs();
+
+    mark_leased_addr(&mut addrs, &lease, 1600, false);
+
+    let addrs = addrs.unwrap();
+    assert_eq!(addrs.len(), 2);
+    assert!(addrs[0].is_auto());
+    assert_eq!(addrs[0].valid_life_time.as_deref(), Some("3000sec"));
+    assert_eq!(addrs[0].preferred_life_time.as_deref(), Some("3000sec"));
+    assert!(!addrs[1].is_auto());
+}
+
+#[test]
+fn test_dhcp_remove_leased_addr_for_running_config() {
+    let lease = gen_test_lease();
+    let mut addrs = gen_test_addrs();
+
+    mark_leased_addr(&mut addrs, &lease, 1600, true);
+
+    let addrs = addrs.unwrap();
+    assert_eq!(addrs.len(), 1);
+    assert_eq!(addrs[0].ip.to_string(), "198.51.100.1");
+}
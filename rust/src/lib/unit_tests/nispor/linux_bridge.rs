@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    nispor::{
+        linux_bridge::{gen_bridge_sysfs_opts, np_bridge_options_to_nmstate},
+        linux_bridge_port_vlan::{parse_port_vlan_conf, port_vlan_conf_to_np},
+        netlink::BridgeVlanInfo,
+    },
+    BridgePortVlanConfig, LinuxBridgeOptions,
+};
+
+// Mimic kernel storing the sysfs values and nispor reporting them back
+fn sysfs_opts_to_np_iface(opts: &[(&str, String)]) -> nispor::Iface {
+    let mut np_bridge = nispor::BridgeInfo::default();
+    for (name, value) in opts {
+        let value = value.as_str();
+        match *name {
+            "group_addr" => np_bridge.group_addr = Some(value.to_lowercase()),
+            "group_fwd_mask" => {
+                np_bridge.group_fwd_mask = Some(value.parse().unwrap())
+            }
+            "hash_max" => {
+                np_bridge.multicast_hash_max = Some(value.parse().unwrap())
+            }
+            "ageing_time" => {
+                np_bridge.ageing_time = Some(value.parse().unwrap())
+            }
+            "multicast_last_member_count" => {
+                np_bridge.multicast_last_member_count =
+                    Some(value.parse().unwrap())
+            }
+            "multicast_last_member_interval" => {
+                np_bridge.multicast_last_member_interval =
+                    Some(value.parse().unwrap())
+            }
+            "multicast_membership_interval" => {
+                np_bridge.multicast_membership_interval =
+                    Some(value.parse().unwrap())
+            }
+            "multicast_querier" => {
+                np_bridge.multicast_querier = Some(value == "1")
+            }
+            "multicast_querier_interval" => {
+                np_bridge.multicast_querier_interval =
+                    Some(value.parse().unwrap())
+            }
+            "multicast_query_interval" => {
+                np_bridge.multicast_query_interval =
+                    Some(value.parse().unwrap())
+            }
+            "multicast_query_response_interval" => {
+                np_bridge.multicast_query_response_interval =
+                    Some(value.parse().unwrap())
+            }
+            "multicast_query_use_ifaddr" => {
+                np_bridge.multicast_query_use_ifaddr = Some(value == "1")
+            }
+            "multicast_router" => {
+                np_bridge.multicast_router =
+                    Some(value.parse::<u8>().unwrap().into())
+            }
+            "multicast_snooping" => {
+                np_bridge.multicast_snooping = Some(value == "1")
+            }
+            "multicast_startup_query_count" => {
+                np_bridge.multicast_startup_query_count =
+                    Some(value.parse().unwrap())
+            }
+            "multicast_startup_query_interval" => {
+                np_bridge.multicast_startup_query_interval =
+                    Some(value.parse().unwrap())
+            }
+            "vlan_protocol" => {
+                np_bridge.vlan_protocol = Some(
+                    u16::from_str_radix(value.trim_start_matches("0x"), 16)
+                        .unwrap()
+                        .into(),
+                )
+            }
+            "default_pvid" => {
+                np_bridge.default_pvid = Some(value.parse().unwrap())
+            }
+            "forward_delay" => {
+                np_bridge.forward_delay = Some(value.parse().unwrap())
+            }
+            "hello_time" => np_bridge.hello_time = Some(value.parse().unwrap()),
+            "max_age" => np_bridge.max_age = Some(value.parse().unwrap()),
+            "priority" => np_bridge.priority = Some(value.parse().unwrap()),
+            "stp_state" => {
+                np_bridge.stp_state = Some(value.parse::<u32>().unwrap().into())
+            }
+            _ => panic!("Unexpected bridge sysfs option {name}"),
+        }
+    }
+    let mut np_iface = nispor::Iface::default();
+    np_iface.bridge = Some(np_bridge);
+    np_iface
+}
+
+// Mimic kernel reporting the VLANs added via netlink, including the
+// default PVID entry managed by kernel.
+fn vlan_infos_to_np(vlans: &[BridgeVlanInfo]) -> Vec<nispor::BridgeVlanEntry> {
+    let mut default_entry = nispor::BridgeVlanEntry::default();
+    default_entry.vid = Some(1);
+    default_entry.is_egress_untagged = true;
+    let mut ret = vec![default_entry];
+    for vlan in vlans {
+        let mut entry = nispor::BridgeVlanEntry::default();
+        if vlan.vid_min == vlan.vid_max {
+            entry.vid = Some(vlan.vid_min);
+        } else {
+            entry.vid_range = Some((vlan.vid_min, vlan.vid_max));
+        }
+        entry.is_pvid = vlan.is_pvid;
+        entry.is_egress_untagged = vlan.is_untagged;
+        ret.push(entry);
+    }
+    ret
+}
+
+#[test]
+fn test_bridge_sysfs_opts_round_trip() {
+    let opts: LinuxBridgeOptions = serde_yaml::from_str(
+        r"---
+group-addr: 01:80:C2:00:00:00
+group-fwd-mask: 8
+hash-max: 4096
+mac-ageing-time: 300
+multicast-last-member-count: 2
+multicast-last-member-interval: 100
+multicast-membership-interval: 26000
+multicast-querier: false
+multicast-querier-interval: 25500
+multicast-query-interval: 12500
+multicast-query-response-interval: 1000
+multicast-query-use-ifaddr: false
+multicast-router: auto
+multicast-snooping: true
+multicast-startup-query-count: 2
+multicast-startup-query-interval: 3125
+vlan-protocol: 802.1ad
+vlan-default-pvid: 1
+stp:
+  enabled: true
+  forward-delay: 15
+  hello-time: 2
+  max-age: 20
+  priority: 32768
+",
+    )
+    .unwrap();
+
+    let np_iface = sysfs_opts_to_np_iface(
+        gen_bridge_sysfs_opts(&opts).unwrap().as_slice(),
+    );
+    let queried = np_bridge_options_to_nmstate(&np_iface).unwrap();
+
+    let mut expected = opts.clone();
+    expected.group_forward_mask = opts.group_fwd_mask;
+    assert_eq!(queried, expected);
+}
+
+#[test]
+fn test_bridge_port_vlan_trunk_round_trip() {
+    let conf: BridgePortVlanConfig = serde_yaml::from_str(
+        r"---
+mode: trunk
+enable-native: true
+tag: 102
+trunk-tags:
+- id: 103
+- id-range:
+    min: 105
+    max: 1024
+",
+    )
+    .unwrap();
+
+    let np_vlans = vlan_infos_to_np(port_vlan_conf_to_np(&conf).as_slice());
+
+    assert_eq!(parse_port_vlan_conf(np_vlans.as_slice(), None), Some(conf));
+}
+
+#[test]
+fn test_bridge_port_vlan_trunk_without_native_round_trip() {
+    let conf: BridgePortVlanConfig = serde_yaml::from_str(
+        r"---
+mode: trunk
+enable-native: false
+trunk-tags:
+- id: 103
+",
+    )
+    .unwrap();
+
+    let np_vlans = vlan_infos_to_np(port_vlan_conf_to_np(&conf).as_slice());
+
+    assert_eq!(parse_port_vlan_conf(np_vlans.as_slice(), None), Some(conf));
+}
+
+#[test]
+fn test_bridge_port_vlan_access_round_trip() {
+    let conf: BridgePortVlanConfig = serde_yaml::from_str(
+        r"---
+mode: access
+tag: 300
+",
+    )
+    .unwrap();
+
+    let np_vlans = vlan_infos_to_np(port_vlan_conf_to_np(&conf).as_slice());
+    let queried = parse_port_vlan_conf(np_vlans.as_slice(), None).unwrap();
+
+    assert_eq!(queried.mode, conf.mode);
+    assert_eq!(queried.tag, conf.tag);
+    assert_eq!(queried.trunk_tags, Some(Vec::new()));
+}
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(test)]
+mod apply;
+#[cfg(test)]
+mod bond;
+#[cfg(test)]
+mod checkpoint;
+#[cfg(test)]
+mod dhcp;
+#[cfg(test)]
+mod linux_bridge;
+#[cfg(test)]
+mod netlink;
+#[cfg(test)]
+mod route;
+#[cfg(test)]
+mod route_rule;
@@ -0,0 +1,271 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use netlink_packet_core::{
+    DefaultNla, Emitable, NetlinkDeserializable, NetlinkHeader,
+    NetlinkSerializable, Parseable, ParseableParametrized,
+};
+use netlink_packet_generic::GenlHeader;
+use netlink_packet_route::{
+    link::{AfSpecBridge, BridgeVlanInfoFlags},
+    route::{
+        RouteAttribute, RouteHeader, RouteMessage, RouteMessageBuffer,
+        RouteMetric,
+    },
+    rule::{RuleAction, RuleAttribute, RuleMessage, RuleMessageBuffer},
+    AddressFamily,
+};
+
+use crate::nispor::{
+    netlink::{
+        BridgeVlanInfo, NexthopAttribute, NexthopMessage, NlRoute, NlRouteRule,
+        RTN_UNICAST, RTPROT_STATIC,
+    },
+    wireguard::{
+        WgAllowedIp, WgAllowedIpAttr, WgDeviceAttr, WgPeer, WgPeerAttr,
+        WireguardMessage,
+    },
+};
+
+const RTM_NEWNEXTHOP: u16 = 104;
+
+fn emit<T: Emitable>(msg: &T) -> Vec<u8> {
+    let mut buf = vec![0u8; msg.buffer_len()];
+    msg.emit(&mut buf);
+    buf
+}
+
+#[test]
+fn test_rule_message_round_trip() {
+    let mut rule = NlRouteRule::new(false, RuleAction::ToTable);
+    rule.src = Some(("192.0.2.0".parse().unwrap(), 24));
+    rule.dst = Some(("198.51.100.1".parse().unwrap(), 32));
+    rule.table = Some(500);
+    rule.priority = Some(1000);
+    rule.iif = Some("eth1".to_string());
+    rule.fwmark = Some(0x10);
+    rule.fwmask = Some(0xff);
+    rule.suppress_prefix_length = Some(0);
+    rule.ip_proto = Some(6);
+    rule.uid_range = Some((1000, 2000));
+    rule.sport_range = Some((80, 80));
+    rule.dport_range = Some((1024, 2048));
+
+    let msg = rule.to_message(false);
+    // Table ID larger than 255 is only stored in FRA_TABLE
+    assert_eq!(msg.header.table, RouteHeader::RT_TABLE_UNSPEC);
+    assert!(msg.attributes.contains(&RuleAttribute::Table(500)));
+
+    let buf = emit(&msg);
+    let parsed = RuleMessage::parse(&RuleMessageBuffer::new(&buf)).unwrap();
+    assert_eq!(NlRouteRule::from_message(&parsed), Some(rule));
+}
+
+#[test]
+fn test_rule_message_with_protocol() {
+    let rule = NlRouteRule::new(true, RuleAction::Blackhole);
+    let msg = rule.to_message(true);
+    let parsed = NlRouteRule::from_message(&msg).unwrap();
+
+    assert_eq!(parsed.action, RuleAction::Blackhole);
+    assert!(parsed.is_ipv6);
+    assert_eq!(parsed.protocol, Some(RTPROT_STATIC));
+}
+
+#[test]
+fn test_rule_message_dscp() {
+    let mut msg = RuleMessage::default();
+    msg.header.family = AddressFamily::Inet;
+    msg.header.action = RuleAction::ToTable;
+    msg.attributes
+        .push(RuleAttribute::Other(DefaultNla::new(25, vec![0x0a])));
+
+    assert_eq!(NlRouteRule::from_message(&msg).unwrap().tos, 0x28);
+}
+
+#[test]
+fn test_rule_message_ignore_unknown_family() {
+    let mut msg = RuleMessage::default();
+    msg.header.family = AddressFamily::Inet6;
+    msg.header.action = RuleAction::Other(200);
+    assert_eq!(NlRouteRule::from_message(&msg), None);
+
+    msg.header.family = AddressFamily::Unspec;
+    msg.header.action = RuleAction::ToTable;
+    assert_eq!(NlRouteRule::from_message(&msg), None);
+}
+
+#[test]
+fn test_route_message_with_nexthop_id() {
+    let route = NlRoute {
+        oif: None,
+        dst: "2001:db8:1::".parse().unwrap(),
+        dst_len: 64,
+        gateway: None,
+        table: 1000,
+        metric: Some(150),
+        protocol: RTPROT_STATIC,
+        nexthop_id: Some(9),
+        route_type: RTN_UNICAST,
+        scope: None,
+        prefsrc: None,
+        metrics: vec![RouteMetric::Mtu(1400), RouteMetric::Lock(1 << 2)],
+        onlink: false,
+    };
+
+    let msg = route.to_message(false).unwrap();
+    assert!(msg
+        .attributes
+        .contains(&RouteAttribute::Metrics(route.metrics.clone())));
+
+    let buf = emit(&msg);
+    let parsed = RouteMessage::parse(&RouteMessageBuffer::new(&buf)).unwrap();
+    let parsed = NlRoute::from_message(&parsed);
+    assert_eq!(parsed.dst, route.dst);
+    assert_eq!(parsed.dst_len, 64);
+    assert_eq!(parsed.table, 1000);
+    assert_eq!(parsed.metric, Some(150));
+    assert_eq!(parsed.protocol, RTPROT_STATIC);
+    assert_eq!(parsed.nexthop_id, Some(9));
+    assert_eq!(parsed.route_type, RTN_UNICAST);
+    // Gateway is resolved by next hop, hence universe scope
+    assert_eq!(parsed.scope, Some(0));
+}
+
+#[test]
+fn test_bridge_vlan_range_nlas() {
+    let nlas = BridgeVlanInfo::new(10, 20, false, true).to_nlas();
+
+    assert_eq!(nlas.len(), 2);
+    if let (AfSpecBridge::VlanInfo(begin), AfSpecBridge::VlanInfo(end)) =
+        (&nlas[0], &nlas[1])
+    {
+        assert_eq!(begin.vid, 10);
+        assert_eq!(
+            begin.flags,
+            BridgeVlanInfoFlags::Untagged | BridgeVlanInfoFlags::RangeBegin
+        );
+        assert_eq!(end.vid, 20);
+        assert_eq!(
+            end.flags,
+            BridgeVlanInfoFlags::Untagged | BridgeVlanInfoFlags::RangeEnd
+        );
+    } else {
+        panic!("Expecting VLAN info, got {nlas:?}");
+    }
+}
+
+#[test]
+fn test_bridge_vlan_single_nlas() {
+    let nlas = BridgeVlanInfo::new(1, 1, true, true).to_nlas();
+
+    assert_eq!(nlas.len(), 1);
+    if let AfSpecBridge::VlanInfo(info) = &nlas[0] {
+        assert_eq!(info.vid, 1);
+        assert_eq!(
+            info.flags,
+            BridgeVlanInfoFlags::Pvid | BridgeVlanInfoFlags::Untagged
+        );
+    } else {
+        panic!("Expecting VLAN info, got {nlas:?}");
+    }
+}
+
+#[test]
+fn test_nexthop_message_group() {
+    let msg = NexthopMessage {
+        message_type: RTM_NEWNEXTHOP,
+        family: AddressFamily::Unspec,
+        protocol: RTPROT_STATIC,
+        attributes: vec![
+            NexthopAttribute::Id(3),
+            NexthopAttribute::Group(vec![(1, 0), (2, 9)]),
+        ],
+    };
+    let mut buf = vec![0u8; msg.buffer_len()];
+    msg.serialize(&mut buf);
+
+    let mut expected: Vec<u8> = vec![0, 0, RTPROT_STATIC, 0, 0, 0, 0, 0];
+    // NHA_ID
+    expected.extend_from_slice(&8u16.to_ne_bytes());
+    expected.extend_from_slice(&1u16.to_ne_bytes());
+    expected.extend_from_slice(&3u32.to_ne_bytes());
+    // NHA_GROUP
+    expected.extend_from_slice(&20u16.to_ne_bytes());
+    expected.extend_from_slice(&2u16.to_ne_bytes());
+    expected.extend_from_slice(&1u32.to_ne_bytes());
+    expected.extend_from_slice(&[0, 0, 0, 0]);
+    expected.extend_from_slice(&2u32.to_ne_bytes());
+    expected.extend_from_slice(&[9, 0, 0, 0]);
+    assert_eq!(buf, expected);
+
+    let mut header = NetlinkHeader::default();
+    header.message_type = RTM_NEWNEXTHOP;
+    assert_eq!(NexthopMessage::deserialize(&header, &buf).unwrap(), msg);
+}
+
+#[test]
+fn test_nexthop_message_gateway() {
+    let gateway: IpAddr = "2001:db8::1".parse().unwrap();
+    let msg = NexthopMessage {
+        message_type: RTM_NEWNEXTHOP,
+        family: AddressFamily::Inet6,
+        protocol: RTPROT_STATIC,
+        attributes: vec![
+            NexthopAttribute::Id(1),
+            NexthopAttribute::Oif(2),
+            NexthopAttribute::Gateway(gateway),
+        ],
+    };
+    let mut buf = vec![0u8; msg.buffer_len()];
+    msg.serialize(&mut buf);
+    // nhmsg + NHA_ID + NHA_OIF + NHA_GATEWAY
+    assert_eq!(buf.len(), 8 + 8 + 8 + 20);
+
+    let mut header = NetlinkHeader::default();
+    header.message_type = RTM_NEWNEXTHOP;
+    assert_eq!(NexthopMessage::deserialize(&header, &buf).unwrap(), msg);
+}
+
+#[test]
+fn test_wireguard_message_round_trip() {
+    let msg = WireguardMessage {
+        cmd: 1,
+        attributes: vec![
+            WgDeviceAttr::IfName("wg0".to_string()),
+            WgDeviceAttr::PrivateKey(vec![1u8; 32]),
+            WgDeviceAttr::ListenPort(51820),
+            WgDeviceAttr::Flags(1),
+            WgDeviceAttr::Peers(vec![WgPeer(vec![
+                WgPeerAttr::PublicKey(vec![2u8; 32]),
+                WgPeerAttr::Endpoint("[2001:db8::1]:51820".parse().unwrap()),
+                WgPeerAttr::PersistentKeepalive(25),
+                WgPeerAttr::Flags(2),
+                WgPeerAttr::AllowedIps(vec![
+                    WgAllowedIp(vec![
+                        WgAllowedIpAttr::Family(2),
+                        WgAllowedIpAttr::IpAddr("192.0.2.0".parse().unwrap()),
+                        WgAllowedIpAttr::CidrMask(24),
+                    ]),
+                    WgAllowedIp(vec![
+                        WgAllowedIpAttr::Family(10),
+                        WgAllowedIpAttr::IpAddr("2001:db8::".parse().unwrap()),
+                        WgAllowedIpAttr::CidrMask(64),
+                    ]),
+                ]),
+            ])]),
+        ],
+    };
+    let buf = emit(&msg);
+
+    // WGDEVICE_A_IFNAME is NUL terminated
+    assert_eq!(&buf[..12], &[8, 0, 2, 0, b'w', b'g', b'0', 0, 36, 0, 3, 0]);
+
+    let parsed = WireguardMessage::parse_with_param(
+        buf.as_slice(),
+        GenlHeader { cmd: 1, version: 1 },
+    )
+    .unwrap();
+    assert_eq!(parsed, msg);
+}
@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use netlink_packet_route::route::RouteMetric;
+
+use crate::{
+    nispor::{
+        netlink::{RTAX_CWND, RTAX_MTU, RTN_UNICAST, RTPROT_STATIC},
+        route::nmstate_route_to_nl,
+    },
+    RouteEntry, RouteProtocol, RouteScope, RouteType,
+};
+
+#[test]
+fn test_nispor_route_attrs_to_netlink() {
+    let route: RouteEntry = serde_yaml::from_str(
+        r"
+        destination: 198.51.100.0/24
+        next-hop-interface: eth1
+        next-hop-address: 192.0.2.1
+        source: 192.0.2.2
+        mtu: 1400
+        lock-mtu: true
+        advmss: 1300
+        initcwnd: 20
+        initrwnd: 30
+        quickack: true
+        cwnd: 15
+        onlink: true
+        scope: site
+        protocol: boot
+        table-id: 100
+        metric: 150
+        ",
+    )
+    .unwrap();
+
+    let nl_route = nmstate_route_to_nl(&route).unwrap();
+
+    assert_eq!(nl_route.oif.as_deref(), Some("eth1"));
+    assert_eq!(nl_route.dst, "198.51.100.0".parse::<IpAddr>().unwrap());
+    assert_eq!(nl_route.dst_len, 24);
+    assert_eq!(nl_route.gateway, Some("192.0.2.1".parse().unwrap()));
+    assert_eq!(nl_route.prefsrc, Some("192.0.2.2".parse().unwrap()));
+    assert_eq!(nl_route.table, 100);
+    assert_eq!(nl_route.metric, Some(150));
+    assert_eq!(nl_route.protocol, u8::from(RouteProtocol::Boot));
+    assert_eq!(nl_route.scope, Some(u8::from(RouteScope::Site)));
+    assert_eq!(nl_route.route_type, RTN_UNICAST);
+    assert!(nl_route.onlink);
+    assert_eq!(
+        nl_route.metrics,
+        vec![
+            RouteMetric::Mtu(1400),
+            RouteMetric::Cwnd(15),
+            RouteMetric::Advmss(1300),
+            RouteMetric::InitCwnd(20),
+            RouteMetric::InitRwnd(30),
+            RouteMetric::QuickAck(1),
+            RouteMetric::Lock(
+                (1 << u32::from(RTAX_MTU)) | (1 << u32::from(RTAX_CWND))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_nispor_route_default_attrs_to_netlink() {
+    let route: RouteEntry = serde_yaml::from_str(
+        r"
+        destination: 2001:db8:2::/64
+        next-hop-interface: eth1
+        next-hop-address: '::'
+        quickack: false
+        onlink: false
+        ",
+    )
+    .unwrap();
+
+    let nl_route = nmstate_route_to_nl(&route).unwrap();
+
+    assert_eq!(nl_route.gateway, None);
+    assert_eq!(nl_route.table, 254);
+    assert_eq!(nl_route.protocol, RTPROT_STATIC);
+    assert_eq!(nl_route.scope, None);
+    assert!(!nl_route.onlink);
+    assert!(nl_route.metrics.is_empty());
+}
+
+#[test]
+fn test_nispor_absent_route_to_netlink_match_any_protocol() {
+    let route: RouteEntry = serde_yaml::from_str(
+        r"
+        destination: 198.51.100.0/24
+        next-hop-interface: eth1
+        mtu: 1400
+        state: absent
+        ",
+    )
+    .unwrap();
+
+    let nl_route = nmstate_route_to_nl(&route).unwrap();
+
+    assert_eq!(nl_route.protocol, 0);
+}
+
+#[test]
+fn test_nispor_throw_route_to_netlink() {
+    let route: RouteEntry = serde_yaml::from_str(
+        r"
+        destination: 198.51.100.0/24
+        next-hop-interface: lo
+        route-type: throw
+        table-id: 100
+        ",
+    )
+    .unwrap();
+
+    let nl_route = nmstate_route_to_nl(&route).unwrap();
+
+    assert_eq!(nl_route.route_type, u8::from(RouteType::Throw));
+    assert_eq!(nl_route.oif, None);
+    assert_eq!(nl_route.gateway, None);
+    assert_eq!(nl_route.table, 100);
+}
@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use netlink_packet_route::rule::RuleAction;
+
+use crate::{
+    nispor::{netlink::NlRouteRule, route_rule::nms_rule_to_nl},
+    ErrorKind, RouteRuleEntry,
+};
+
+#[test]
+fn test_route_rule_to_nl_ipv4() {
+    let rule: RouteRuleEntry = serde_yaml::from_str(
+        r"---
+family: ipv4
+ip-from: 192.0.2.0/24
+ip-to: 198.51.100.1
+priority: 1000
+route-table: 100
+fwmark: 16
+fwmask: 255
+iif: eth1
+",
+    )
+    .unwrap();
+
+    let mut expected = NlRouteRule::new(false, RuleAction::ToTable);
+    expected.src = Some((IpAddr::from_str("192.0.2.0").unwrap(), 24));
+    expected.dst = Some((IpAddr::from_str("198.51.100.1").unwrap(), 32));
+    expected.priority = Some(1000);
+    expected.table = Some(100);
+    expected.fwmark = Some(16);
+    expected.fwmask = Some(255);
+    expected.iif = Some("eth1".to_string());
+
+    assert_eq!(nms_rule_to_nl(&rule).unwrap(), expected);
+}
+
+#[test]
+fn test_route_rule_to_nl_ipv6_ranges() {
+    let rule: RouteRuleEntry = serde_yaml::from_str(
+        r"---
+family: ipv6
+ip-to: 2001:db8::/64
+ipproto: 6
+sport: 1024-65535
+dport: 80
+uidrange: 1000-1999
+action: blackhole
+",
+    )
+    .unwrap();
+
+    let mut expected = NlRouteRule::new(true, RuleAction::Blackhole);
+    expected.dst = Some((IpAddr::from_str("2001:db8::").unwrap(), 64));
+    expected.ip_proto = Some(6);
+    expected.sport_range = Some((1024, 65535));
+    expected.dport_range = Some((80, 80));
+    expected.uid_range = Some((1000, 1999));
+
+    assert_eq!(nms_rule_to_nl(&rule).unwrap(), expected);
+}
+
+#[test]
+fn test_route_rule_to_nl_default_table_and_priority() {
+    let rule: RouteRuleEntry = serde_yaml::from_str(
+        r"---
+family: ipv4
+ip-from: 192.0.2.1
+priority: -1
+route-table: 0
+",
+    )
+    .unwrap();
+
+    let nl_rule = nms_rule_to_nl(&rule).unwrap();
+
+    assert_eq!(nl_rule.table, Some(RouteRuleEntry::DEFAULR_ROUTE_TABLE_ID));
+    assert_eq!(nl_rule.priority, None);
+}
+
+#[test]
+fn test_route_rule_to_nl_goto() {
+    let rule: RouteRuleEntry = serde_yaml::from_str(
+        r"---
+family: ipv4
+priority: 1000
+action: goto
+goto-priority: 2000
+",
+    )
+    .unwrap();
+
+    let nl_rule = nms_rule_to_nl(&rule).unwrap();
+
+    assert_eq!(nl_rule.action, RuleAction::Goto);
+    assert_eq!(nl_rule.goto, Some(2000));
+}
+
+#[test]
+fn test_route_rule_to_nl_invalid_ip() {
+    let rule: RouteRuleEntry = serde_yaml::from_str(
+        r"---
+family: ipv4
+ip-from: 192.0.2.300/24
+",
+    )
+    .unwrap();
+
+    let result = nms_rule_to_nl(&rule);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,107 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    nm::{
+        gen_nm_apply_report, NmActiveConnection, NmConnection,
+        NmSettingConnection,
+    },
+    ApplyReport, MergedNetworkState, NetworkState, NmProfileReport,
+};
+
+fn gen_exist_nm_conn(name: &str, iface_type: &str, uuid: &str) -> NmConnection {
+    let mut nm_conn_set = NmSettingConnection::default();
+    nm_conn_set.id = Some(name.to_string());
+    nm_conn_set.uuid = Some(uuid.to_string());
+    nm_conn_set.iface_type = Some(iface_type.to_string());
+    nm_conn_set.iface_name = Some(name.to_string());
+    nm_conn_set.autoconnect = Some(true);
+    let mut nm_conn = NmConnection::default();
+    nm_conn.connection = Some(nm_conn_set);
+    nm_conn.obj_path =
+        format!("/org/freedesktop/NetworkManager/Settings/{uuid}");
+    nm_conn
+}
+
+fn gen_nm_ac(nm_conn: &NmConnection) -> NmActiveConnection {
+    NmActiveConnection {
+        uuid: nm_conn.uuid().unwrap().to_string(),
+        iface_type: nm_conn.iface_type().unwrap().to_string(),
+        iface_name: nm_conn.iface_name().unwrap().to_string(),
+        state_flags: 0,
+    }
+}
+
+fn profile_ids(profiles: &[NmProfileReport]) -> Vec<&str> {
+    profiles.iter().filter_map(|p| p.id.as_deref()).collect()
+}
+
+#[test]
+fn test_nm_apply_report() {
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+- name: eth2
+  type: ethernet
+  state: up
+- name: dummy0
+  type: dummy
+  state: up
+",
+    )
+    .unwrap();
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 9000
+- name: eth2
+  type: ethernet
+  state: down
+- name: dummy0
+  type: dummy
+  state: absent
+- name: br0
+  type: linux-bridge
+  state: up
+",
+    )
+    .unwrap();
+    let exist_nm_conns = vec![
+        gen_exist_nm_conn("eth1", "802-3-ethernet", "u1"),
+        gen_exist_nm_conn("eth2", "802-3-ethernet", "u2"),
+        gen_exist_nm_conn("dummy0", "dummy", "u3"),
+    ];
+    let nm_acs: Vec<NmActiveConnection> =
+        exist_nm_conns.iter().map(gen_nm_ac).collect();
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    let mut report = ApplyReport::default();
+    gen_nm_apply_report(
+        &merged_state,
+        exist_nm_conns.as_slice(),
+        nm_acs.as_slice(),
+        &[],
+        &mut report,
+    )
+    .unwrap();
+
+    assert_eq!(profile_ids(&report.nm_profiles_to_add), vec!["br0"]);
+    assert_eq!(
+        profile_ids(&report.nm_profiles_to_modify),
+        vec!["eth1", "eth2"]
+    );
+    assert_eq!(
+        profile_ids(&report.nm_profiles_to_activate),
+        vec!["br0", "eth1"]
+    );
+    assert_eq!(profile_ids(&report.nm_profiles_to_deactivate), vec!["eth2"]);
+    assert_eq!(profile_ids(&report.nm_profiles_to_delete), vec!["dummy0"]);
+    assert_eq!(report.nm_profiles_to_delete[0].uuid.as_deref(), Some("u3"));
+}
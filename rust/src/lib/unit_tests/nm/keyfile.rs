@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, NetworkState};
+
+const TEST_DATA_FOLDER_PATH: &str = "unit_tests/nm_keyfile_test_files";
+
+fn test_data_path(name: &str) -> String {
+    std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join(TEST_DATA_FOLDER_PATH)
+        .join(name)
+        .display()
+        .to_string()
+}
+
+#[test]
+fn test_nm_keyfiles_import() {
+    let net_state =
+        NetworkState::from_nm_keyfiles(&test_data_path("basic")).unwrap();
+
+    let expected: NetworkState = serde_yaml::from_str(
+        r"---
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.53
+    search:
+    - example.org
+route-rules:
+  config:
+  - family: ipv4
+    ip-from: 192.0.2.0/24
+    priority: 1000
+    route-table: 100
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-interface: bond0.100
+    next-hop-address: 192.0.2.1
+  - destination: 198.51.100.0/24
+    next-hop-interface: bond0.100
+    next-hop-address: 192.0.2.254
+    metric: 150
+    table-id: 100
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  wait-ip: any
+  ipv4:
+    enabled: false
+    dhcp: false
+  ipv6:
+    enabled: false
+    dhcp: false
+    autoconf: false
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 100
+      primary: eth1
+    port:
+    - eth1
+    - eth2
+- name: bond0.100
+  type: vlan
+  state: up
+  wait-ip: any
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.10
+      prefix-length: 24
+  ipv6:
+    enabled: false
+    dhcp: false
+    autoconf: false
+  vlan:
+    base-iface: bond0
+    id: 100
+- name: br0
+  type: linux-bridge
+  state: down
+  wait-ip: any
+  ipv4:
+    enabled: true
+    dhcp: true
+    auto-dns: true
+    auto-gateway: true
+    auto-routes: true
+    auto-route-table-id: 0
+    dhcp-send-hostname: true
+  ipv6:
+    enabled: false
+    dhcp: false
+    autoconf: false
+  bridge:
+    options:
+      stp:
+        enabled: false
+    port:
+    - name: eth3
+      stp-path-cost: 100
+      stp-priority: 16
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 9000
+- name: eth2
+  type: ethernet
+  state: up
+- name: eth3
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        serde_yaml::to_string(&net_state).unwrap(),
+        serde_yaml::to_string(&expected).unwrap()
+    );
+}
+
+#[test]
+fn test_nm_keyfiles_import_invalid_route_rule() {
+    let result = NetworkState::from_nm_keyfiles(&test_data_path("invalid"));
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("eth1.nmconnection"));
+    }
+}
+
+#[test]
+fn test_nm_keyfiles_import_folder_not_exist() {
+    let result =
+        NetworkState::from_nm_keyfiles(&test_data_path("not_exist_folder"));
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
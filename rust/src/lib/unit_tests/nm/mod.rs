@@ -1,7 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(all(test, feature = "query_apply"))]
+mod apply;
 #[cfg(test)]
 mod dns;
+#[cfg(all(test, feature = "query_apply"))]
+mod keyfile;
 #[cfg(test)]
 mod route;
 #[cfg(test)]
@@ -212,3 +212,76 @@ routes:
     assert!(eth1_iface.is_up());
     assert!(br0_iface.is_absent());
 }
+
+#[test]
+fn test_nexthop_group_route_expanded_to_member_ifaces() {
+    let current: NetworkState = serde_yaml::from_str(
+        r"
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.251
+      prefix-length: 24
+- name: eth2
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 198.51.100.251
+      prefix-length: 24
+",
+    )
+    .unwrap();
+    let desired: NetworkState = serde_yaml::from_str(
+        r"
+nexthops:
+  config:
+  - id: 1
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+  - id: 2
+    next-hop-interface: eth2
+    next-hop-address: 198.51.100.1
+  - id: 100
+    group:
+    - id: 1
+      weight: 2
+    - id: 2
+routes:
+  config:
+  - destination: 203.0.113.0/24
+    nexthop-id: 100
+",
+    )
+    .unwrap();
+
+    let mut merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    store_route_config(&mut merged_state).unwrap();
+
+    for (iface_name, gw, weight) in
+        [("eth1", "192.0.2.1", 2), ("eth2", "198.51.100.1", 1)]
+    {
+        let iface = merged_state
+            .interfaces
+            .get_iface(iface_name, InterfaceType::Ethernet)
+            .unwrap()
+            .for_apply
+            .as_ref()
+            .unwrap();
+        let routes = iface.base_iface().routes.as_ref().unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].destination.as_deref(), Some("203.0.113.0/24"));
+        assert_eq!(routes[0].next_hop_iface.as_deref(), Some(iface_name));
+        assert_eq!(routes[0].next_hop_addr.as_deref(), Some(gw));
+        assert_eq!(routes[0].weight, Some(weight));
+        assert_eq!(routes[0].nexthop_id, None);
+    }
+}
@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crate::{GenConfBackend, NetworkState};
+
+fn gen_ovsdb_conf(
+    yaml: &str,
+    backend: GenConfBackend,
+) -> HashMap<String, String> {
+    let mut net_state: NetworkState = serde_yaml::from_str(yaml).unwrap();
+    net_state.set_gen_conf_backend(backend);
+    let mut confs = net_state.gen_conf().unwrap();
+    confs.remove("ovsdb").unwrap().into_iter().collect()
+}
+
+#[test]
+fn test_ovsdb_gen_conf_global_config_only_for_nm() {
+    let confs = gen_ovsdb_conf(
+        r"---
+ovs-db:
+  external_ids:
+    hostname: node1
+  other_config:
+    stats-update-interval: 1000
+ovn:
+  bridge-mappings:
+  - localnet: net1
+    bridge: br0
+interfaces:
+- name: br0
+  type: ovs-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+",
+        GenConfBackend::NetworkManager,
+    );
+
+    assert_eq!(
+        confs.get("ovs-vsctl.sh").unwrap(),
+        "#!/bin/sh
+ovs-vsctl \\
+    -- set Open_vSwitch . \
+'external_ids={hostname=node1,ovn-bridge-mappings=\"net1:br0\"}' \
+'other_config={stats-update-interval=\"1000\"}'
+"
+    );
+    let transact: serde_json::Value =
+        serde_json::from_str(confs.get("ovsdb-transact.json").unwrap())
+            .unwrap();
+    assert_eq!(transact["method"], "transact");
+    assert_eq!(transact["params"][0], "Open_vSwitch");
+    assert_eq!(transact["params"][1]["op"], "update");
+    assert_eq!(transact["params"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_ovsdb_gen_conf_bridge_for_networkd() {
+    let confs = gen_ovsdb_conf(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: ovs0
+  type: ovs-interface
+  state: up
+- name: br0
+  type: ovs-bridge
+  state: up
+  bridge:
+    options:
+      stp: false
+    port:
+    - name: eth1
+      vlan:
+        mode: access
+        tag: 100
+    - name: ovs0
+",
+        GenConfBackend::SystemdNetworkd,
+    );
+
+    assert_eq!(
+        confs.get("ovs-vsctl.sh").unwrap(),
+        "#!/bin/sh
+ovs-vsctl \\
+    -- --id=@iface0 create Interface name=eth1 \\
+    -- --id=@port0 create Port 'interfaces=[@iface0]' name=eth1 tag=100 \
+vlan_mode=access \\
+    -- --id=@iface1 create Interface name=ovs0 type=internal \\
+    -- --id=@port1 create Port 'interfaces=[@iface1]' name=ovs0 \\
+    -- --id=@bridge0 create Bridge name=br0 'ports=[@port0,@port1]' \
+stp_enable=false \\
+    -- add Open_vSwitch . bridges @bridge0
+"
+    );
+    let transact: serde_json::Value =
+        serde_json::from_str(confs.get("ovsdb-transact.json").unwrap())
+            .unwrap();
+    let ops = transact["params"].as_array().unwrap();
+    assert_eq!(ops.len(), 7);
+    assert_eq!(ops[5]["op"], "insert");
+    assert_eq!(ops[5]["table"], "Bridge");
+    assert_eq!(ops[5]["uuid-name"], "bridge0");
+    assert_eq!(ops[6]["op"], "mutate");
+    assert_eq!(ops[6]["mutations"][0][0], "bridges");
+}
+
+#[test]
+fn test_ovsdb_gen_conf_not_generated_without_ovs() {
+    let mut net_state: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+    net_state.set_gen_conf_backend(GenConfBackend::SystemdNetworkd);
+    let confs = net_state.gen_conf().unwrap();
+    assert!(!confs.contains_key("ovsdb"));
+}
+
+#[test]
+fn test_ovsdb_gen_conf_not_generated_without_ovsdb_changes() {
+    for backend in [
+        GenConfBackend::NetworkManager,
+        GenConfBackend::SystemdNetworkd,
+        GenConfBackend::Netplan,
+    ] {
+        for yml in [
+            "interfaces:\n- name: eth1\n  type: ethernet\n  state: up",
+            "interfaces:\n- name: eth1\n  type: ethernet\n  state: up\n\
+            ovs-db: {}",
+            "ovs-db:\n  external_ids:\n    foo: null",
+            "ovn:\n  bridge-mappings: []",
+        ] {
+            let mut net_state: NetworkState =
+                serde_yaml::from_str(yml).unwrap();
+            net_state.set_gen_conf_backend(backend);
+            let confs = net_state.gen_conf().unwrap();
+            assert_eq!(confs.len(), 1, "{backend} {yml}");
+            assert!(confs.contains_key(&backend.to_string()));
+        }
+    }
+}
@@ -8,8 +8,9 @@ use crate::{
         TEST_IPV4_NET1, TEST_IPV6_ADDR1, TEST_IPV6_ADDR2, TEST_IPV6_NET1,
         TEST_IPV6_NET2, TEST_NIC, TEST_ROUTE_METRIC,
     },
-    ErrorKind, InterfaceType, Interfaces, MergedRoutes, RouteEntry, RouteState,
-    Routes,
+    ErrorKind, InterfaceType, Interfaces, MergedNexthops, MergedRoutes,
+    RouteEntry, RouteProtocol, RouteScope, RouteState, RouteTableNames,
+    RouteType, Routes,
 };
 
 #[test]
@@ -39,11 +40,22 @@ fn test_sort_uniqe_routes() {
 
     let (merged_ifaces, current_ifaces) = gen_merged_ifaces_for_route_test();
 
-    let merged_routes =
-        MergedRoutes::new(des_routes, Routes::new(), &merged_ifaces).unwrap();
+    let merged_routes = MergedRoutes::new(
+        des_routes,
+        Routes::new(),
+        &merged_ifaces,
+        &MergedNexthops::default(),
+        &RouteTableNames::default(),
+    )
+    .unwrap();
 
     merged_routes
-        .verify(&cur_routes, &[], &current_ifaces)
+        .verify(
+            &cur_routes,
+            &[],
+            &current_ifaces,
+            &MergedNexthops::default(),
+        )
         .unwrap();
 
     test_routes.sort_unstable();
@@ -59,16 +71,32 @@ fn test_verify_desire_route_not_found() {
     let mut cur_routes = Routes::new();
     let mut cur_route_entries = gen_test_route_entries();
     cur_route_entries.pop();
+    let missing_index = cur_route_entries.len();
     cur_routes.config = Some(cur_route_entries);
 
     let (merged_ifaces, current_ifaces) = gen_merged_ifaces_for_route_test();
 
-    let merged_routes =
-        MergedRoutes::new(des_routes, Routes::new(), &merged_ifaces).unwrap();
+    let merged_routes = MergedRoutes::new(
+        des_routes,
+        Routes::new(),
+        &merged_ifaces,
+        &MergedNexthops::default(),
+        &RouteTableNames::default(),
+    )
+    .unwrap();
 
-    let result = merged_routes.verify(&cur_routes, &[], &current_ifaces);
-    assert!(result.is_err());
-    assert_eq!(result.err().unwrap().kind(), ErrorKind::VerificationError);
+    let result = merged_routes.verify(
+        &cur_routes,
+        &[],
+        &current_ifaces,
+        &MergedNexthops::default(),
+    );
+    let e = result.unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::VerificationError);
+    assert_eq!(
+        e.verification_diff().map(|d| d.path.as_str()),
+        Some(format!("routes.config[{missing_index}]").as_str())
+    );
 }
 
 #[test]
@@ -85,13 +113,27 @@ fn test_verify_absent_route_still_found() {
 
     let (merged_ifaces, current_ifaces) = gen_merged_ifaces_for_route_test();
 
-    let merged_routes =
-        MergedRoutes::new(absent_routes, Routes::new(), &merged_ifaces)
-            .unwrap();
+    let merged_routes = MergedRoutes::new(
+        absent_routes,
+        Routes::new(),
+        &merged_ifaces,
+        &MergedNexthops::default(),
+        &RouteTableNames::default(),
+    )
+    .unwrap();
 
-    let result = merged_routes.verify(&cur_routes, &[], &current_ifaces);
-    assert!(result.is_err());
-    assert_eq!(result.err().unwrap().kind(), ErrorKind::VerificationError);
+    let result = merged_routes.verify(
+        &cur_routes,
+        &[],
+        &current_ifaces,
+        &MergedNexthops::default(),
+    );
+    let e = result.unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::VerificationError);
+    assert_eq!(
+        e.verification_diff().map(|d| d.path.as_str()),
+        Some("routes.config[0]")
+    );
 }
 
 #[test]
@@ -109,10 +151,21 @@ fn test_verify_current_has_more_routes() {
 
     let (merged_ifaces, current_ifaces) = gen_merged_ifaces_for_route_test();
 
-    let merged_routes =
-        MergedRoutes::new(des_routes, Routes::new(), &merged_ifaces).unwrap();
+    let merged_routes = MergedRoutes::new(
+        des_routes,
+        Routes::new(),
+        &merged_ifaces,
+        &MergedNexthops::default(),
+        &RouteTableNames::default(),
+    )
+    .unwrap();
     merged_routes
-        .verify(&cur_routes, &[], &current_ifaces)
+        .verify(
+            &cur_routes,
+            &[],
+            &current_ifaces,
+            &MergedNexthops::default(),
+        )
         .unwrap();
 }
 
@@ -139,8 +192,14 @@ config:
 
     let (merged_ifaces, _) = gen_merged_ifaces_for_route_test();
 
-    let mut merged_routes =
-        MergedRoutes::new(routes, Routes::new(), &merged_ifaces).unwrap();
+    let mut merged_routes = MergedRoutes::new(
+        routes,
+        Routes::new(),
+        &merged_ifaces,
+        &MergedNexthops::default(),
+        &RouteTableNames::default(),
+    )
+    .unwrap();
 
     let ignored_ifaces = vec![("eth1".to_string(), InterfaceType::Ethernet)];
 
@@ -181,15 +240,26 @@ config:
 
     let (merged_ifaces, current_ifaces) = gen_merged_ifaces_for_route_test();
 
-    let mut merged_routes =
-        MergedRoutes::new(desire, Routes::new(), &merged_ifaces).unwrap();
+    let mut merged_routes = MergedRoutes::new(
+        desire,
+        Routes::new(),
+        &merged_ifaces,
+        &MergedNexthops::default(),
+        &RouteTableNames::default(),
+    )
+    .unwrap();
 
     let ignored_ifaces = vec![("eth1".to_string(), InterfaceType::Ethernet)];
 
     merged_routes.remove_routes_to_ignored_ifaces(ignored_ifaces.as_slice());
 
     merged_routes
-        .verify(&current, &["eth1"], &current_ifaces)
+        .verify(
+            &current,
+            &["eth1"],
+            &current_ifaces,
+            &MergedNexthops::default(),
+        )
         .unwrap();
 }
 
@@ -612,3 +682,486 @@ fn test_route_without_options_is_match_with_any() {
     assert!(!desired_route.is_match(&not_match_route));
     assert!(desired_route.is_match(&match_route));
 }
+
+#[test]
+fn test_route_extended_attrs_is_match() {
+    let desired_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: "198.51.100.0/24"
+        source: "192.0.2.2"
+        mtu: 1400
+        lock-mtu: true
+        advmss: 1360
+        initcwnd: 10
+        initrwnd: 20
+        quickack: true
+        onlink: true
+        scope: link
+        protocol: static
+        "#,
+    )
+    .unwrap();
+
+    let match_route = desired_route.clone();
+    assert!(desired_route.is_match(&match_route));
+
+    let mut not_match_route = desired_route.clone();
+    not_match_route.source = Some("192.0.2.3".into());
+    assert!(!desired_route.is_match(&not_match_route));
+
+    let mut not_match_route = desired_route.clone();
+    not_match_route.lock_mtu = None;
+    assert!(!desired_route.is_match(&not_match_route));
+
+    let mut not_match_route = desired_route.clone();
+    not_match_route.initrwnd = Some(30);
+    assert!(!desired_route.is_match(&not_match_route));
+
+    let mut not_match_route = desired_route.clone();
+    not_match_route.scope = Some(RouteScope::Universe);
+    assert!(!desired_route.is_match(&not_match_route));
+
+    let mut not_match_route = desired_route.clone();
+    not_match_route.protocol = Some(RouteProtocol::Boot);
+    assert!(!desired_route.is_match(&not_match_route));
+}
+
+#[test]
+fn test_route_disabled_onlink_match_unset() {
+    let desired_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: "198.51.100.0/24"
+        onlink: false
+        quickack: false
+        "#,
+    )
+    .unwrap();
+    let current_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: "198.51.100.0/24"
+        scope: global
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(current_route.scope, Some(RouteScope::Universe));
+    assert!(desired_route.is_match(&current_route));
+}
+
+#[test]
+fn test_route_absent_ipv4_throw_route() {
+    let cur_routes: Routes = serde_yaml::from_str(
+        r#"
+        config:
+        - destination: "198.51.100.0/24"
+          route-type: throw
+          table-id: 100
+        - destination: "203.0.113.0/24"
+          route-type: throw
+          table-id: 100
+        "#,
+    )
+    .unwrap();
+    let des_routes: Routes = serde_yaml::from_str(
+        r#"
+        config:
+        - destination: "198.51.100.0/24"
+          route-type: throw
+          table-id: 100
+          state: absent
+        "#,
+    )
+    .unwrap();
+
+    let (merged_ifaces, _) = gen_merged_ifaces_for_route_test();
+
+    let merged_routes = MergedRoutes::new(
+        des_routes,
+        cur_routes,
+        &merged_ifaces,
+        &MergedNexthops::default(),
+        &RouteTableNames::default(),
+    )
+    .unwrap();
+
+    assert_eq!(merged_routes.changed_routes.len(), 1);
+    let changed_route = &merged_routes.changed_routes[0];
+    assert!(changed_route.is_absent());
+    assert_eq!(
+        changed_route.destination.as_deref(),
+        Some("198.51.100.0/24")
+    );
+    assert_eq!(changed_route.route_type, Some(RouteType::Throw));
+    assert_eq!(merged_routes.merged.get("lo").map(|rts| rts.len()), Some(1));
+}
+
+#[test]
+fn test_route_default_scope() {
+    let routes: Vec<RouteEntry> = serde_yaml::from_str(
+        r#"
+        - destination: "198.51.100.0/24"
+          next-hop-interface: eth1
+          next-hop-address: 192.0.2.1
+        - destination: "198.51.100.0/24"
+          next-hop-interface: eth1
+          next-hop-address: 0.0.0.0
+        - destination: "198.51.100.0/24"
+          next-hop-interface: eth1
+        - destination: "198.51.100.0/24"
+          nexthop-id: 1
+        - destination: "198.51.100.1/32"
+          next-hop-interface: eth1
+          route-type: local
+        - destination: "198.51.100.255/32"
+          next-hop-interface: eth1
+          route-type: broadcast
+        - destination: "198.51.100.0/24"
+          route-type: blackhole
+        - destination: "2001:db8:2::/64"
+          next-hop-interface: eth1
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        routes.iter().map(|r| r.default_scope()).collect::<Vec<_>>(),
+        vec![
+            RouteScope::Universe,
+            RouteScope::Link,
+            RouteScope::Link,
+            RouteScope::Universe,
+            RouteScope::Host,
+            RouteScope::Link,
+            RouteScope::Universe,
+            RouteScope::Universe,
+        ]
+    );
+}
+
+#[test]
+fn test_route_scope_match_default_scope() {
+    let desired_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: "198.51.100.0/24"
+        next-hop-interface: eth1
+        scope: link
+        "#,
+    )
+    .unwrap();
+    // Default scope is not shown
+    let mut current_route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: "198.51.100.0/24"
+        next-hop-interface: eth1
+        next-hop-address: 0.0.0.0
+        "#,
+    )
+    .unwrap();
+
+    assert!(desired_route.is_match(&current_route));
+
+    current_route.next_hop_addr = Some("192.0.2.1".into());
+    assert!(!desired_route.is_match(&current_route));
+}
+
+#[test]
+fn test_route_verify_boot_protocol_taken_over_by_nm() {
+    let mut desired_route =
+        gen_route_entry(TEST_IPV4_NET1, TEST_NIC, TEST_IPV4_ADDR1);
+    desired_route.protocol = Some(RouteProtocol::Boot);
+    let mut current_route = desired_route.clone();
+    current_route.protocol = Some(RouteProtocol::Static);
+
+    let cur_routes = Routes {
+        running: None,
+        config: Some(vec![current_route]),
+    };
+    let des_routes = Routes {
+        running: None,
+        config: Some(vec![desired_route]),
+    };
+
+    let (merged_ifaces, current_ifaces) = gen_merged_ifaces_for_route_test();
+
+    let merged_routes = MergedRoutes::new(
+        des_routes,
+        Routes::new(),
+        &merged_ifaces,
+        &MergedNexthops::default(),
+        &RouteTableNames::default(),
+    )
+    .unwrap();
+
+    merged_routes
+        .verify(
+            &cur_routes,
+            &[],
+            &current_ifaces,
+            &MergedNexthops::default(),
+        )
+        .unwrap();
+
+    // Kernel mode should preserve the protocol
+    let kernel_nexthops = MergedNexthops {
+        kernel_only: true,
+        ..Default::default()
+    };
+    let result = merged_routes.verify(
+        &cur_routes,
+        &[],
+        &current_ifaces,
+        &kernel_nexthops,
+    );
+    assert!(result.is_err());
+    assert_eq!(result.err().unwrap().kind(), ErrorKind::VerificationError);
+}
+
+#[test]
+fn test_route_source_sanitize() {
+    let mut route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: "2001:db8:1::/64"
+        source: "2001:db8:0::1"
+        "#,
+    )
+    .unwrap();
+
+    route.sanitize().unwrap();
+    assert_eq!(route.source, Some("2001:db8::1".to_string()));
+}
+
+#[test]
+fn test_route_source_ip_family_mismatch() {
+    let mut route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: "2001:db8:1::/64"
+        source: "192.0.2.1"
+        "#,
+    )
+    .unwrap();
+
+    let result = route.sanitize();
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidArgument);
+}
+
+#[test]
+fn test_route_lock_mtu_without_mtu() {
+    let mut route: RouteEntry = serde_yaml::from_str(
+        r#"
+        destination: "198.51.100.0/24"
+        lock-mtu: true
+        "#,
+    )
+    .unwrap();
+
+    let result = route.sanitize();
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidArgument);
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_route_extended_attrs_gen_conf_keyfile() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+    source: 192.0.2.2
+    mtu: 1400
+    lock-mtu: true
+    advmss: 1360
+    initcwnd: 10
+    initrwnd: 20
+    quickack: true
+    onlink: true
+    scope: universe
+",
+    )
+    .unwrap();
+
+    let mut confs = state.gen_conf().unwrap();
+    let confs = confs.remove("NetworkManager").unwrap();
+    let content = confs[0].1.as_str();
+
+    assert!(content.contains(
+        "route0_options=table=254,src=192.0.2.2,mtu=1400,lock-mtu=true,\
+        advmss=1360,initcwnd=10,initrwnd=20,quickack=true,onlink=true,\
+        scope=0\n"
+    ));
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_route_unsupported_protocol_gen_conf_keyfile() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+    protocol: dhcp
+",
+    )
+    .unwrap();
+
+    let result = state.gen_conf();
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::NotSupportedError);
+}
+
+#[test]
+fn test_route_type_u8_conversion() {
+    for route_type in [
+        RouteType::Blackhole,
+        RouteType::Unreachable,
+        RouteType::Prohibit,
+        RouteType::Throw,
+        RouteType::Local,
+        RouteType::Broadcast,
+        RouteType::Anycast,
+        RouteType::Multicast,
+    ] {
+        assert_eq!(
+            RouteType::try_from(u8::from(route_type)).unwrap(),
+            route_type
+        );
+    }
+    assert_eq!(u8::from(RouteType::Throw), 9);
+    assert_eq!(u8::from(RouteType::Local), 2);
+    assert!(RouteType::try_from(1).is_err());
+}
+
+#[test]
+fn test_route_throw_with_next_hop() {
+    let routes: Routes = serde_yaml::from_str(
+        r"
+        config:
+        - destination: 198.51.100.0/24
+          route-type: throw
+          next-hop-interface: eth1
+        ",
+    )
+    .unwrap();
+
+    let result = routes.validate();
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidArgument);
+}
+
+#[test]
+fn test_route_local_with_next_hop_address() {
+    let routes: Routes = serde_yaml::from_str(
+        r"
+        config:
+        - destination: 203.0.113.1/32
+          route-type: local
+          next-hop-interface: eth1
+          next-hop-address: 192.0.2.1
+        ",
+    )
+    .unwrap();
+
+    let result = routes.validate();
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidArgument);
+}
+
+#[test]
+fn test_route_local_without_next_hop_iface() {
+    let routes: Routes = serde_yaml::from_str(
+        r"
+        config:
+        - destination: 203.0.113.1/32
+          route-type: local
+        ",
+    )
+    .unwrap();
+
+    let result = routes.validate();
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::NotImplementedError);
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_route_type_local_gen_conf_keyfile() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+routes:
+  config:
+  - destination: 203.0.113.1/32
+    next-hop-interface: eth1
+    route-type: local
+    table-id: 100
+",
+    )
+    .unwrap();
+
+    let mut confs = state.gen_conf().unwrap();
+    let confs = confs.remove("NetworkManager").unwrap();
+    let content = confs[0].1.as_str();
+
+    assert!(content.contains(
+        "route0=203.0.113.1/32,,0\nroute0_options=table=100,type=local\n"
+    ));
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_route_type_multicast_gen_conf_keyfile() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+routes:
+  config:
+  - destination: 224.0.0.0/4
+    next-hop-interface: eth1
+    route-type: multicast
+",
+    )
+    .unwrap();
+
+    let result = state.gen_conf();
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::NotSupportedError);
+}
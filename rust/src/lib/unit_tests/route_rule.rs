@@ -1,8 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    unit_tests::testlib::gen_test_rule_entries, MergedRouteRules,
-    RouteRuleEntry, RouteRules,
+    unit_tests::testlib::gen_test_rule_entries, ErrorKind, MergedRouteRules,
+    RouteRuleEntry, RouteRuleRange, RouteRules, RouteTableNames,
 };
 
 #[test]
@@ -249,7 +249,12 @@ fn test_route_rule_auto_priority_increasing_from_desire() {
     )
     .unwrap();
 
-    let merged = MergedRouteRules::new(des_rules, cur_rules).unwrap();
+    let merged = MergedRouteRules::new(
+        des_rules,
+        cur_rules,
+        &RouteTableNames::default(),
+    )
+    .unwrap();
 
     let mut rules = merged.for_apply;
     rules.sort_unstable();
@@ -298,7 +303,12 @@ fn test_route_rule_auto_priority_increasing_from_empty() {
 
     let cur_rules = RouteRules::new();
 
-    let merged = MergedRouteRules::new(des_rules, cur_rules).unwrap();
+    let merged = MergedRouteRules::new(
+        des_rules,
+        cur_rules,
+        &RouteTableNames::default(),
+    )
+    .unwrap();
 
     let mut rules = merged.for_apply;
     rules.sort_unstable();
@@ -320,3 +330,270 @@ fn test_route_rule_auto_priority_increasing_from_empty() {
     );
     assert_eq!(rules[2].priority, Some(30002));
 }
+
+#[test]
+fn test_route_rule_selectors_serialize() {
+    let rule: RouteRuleEntry = serde_yaml::from_str(
+        r#"
+ip-from: 192.0.2.0/24
+sport: 80
+dport: "1000-2000"
+ipproto: "6"
+uidrange: 1000-1999
+tos: "0x10"
+oif: eth1
+route-table: 100
+"#,
+    )
+    .unwrap();
+    assert_eq!(rule.sport, Some(RouteRuleRange::new(80, 80)));
+    assert_eq!(rule.dport, Some(RouteRuleRange::new(1000, 2000)));
+    assert_eq!(rule.ipproto, Some(6));
+    assert_eq!(rule.uidrange, Some(RouteRuleRange::new(1000, 1999)));
+    assert_eq!(rule.tos, Some(0x10));
+    assert_eq!(rule.oif.as_deref(), Some("eth1"));
+
+    let yml = serde_yaml::to_string(&rule).unwrap();
+    assert!(yml.contains("sport: 80\n"));
+    assert!(yml.contains("dport: 1000-2000\n"));
+    assert!(yml.contains("uidrange: 1000-1999\n"));
+}
+
+#[test]
+fn test_route_rule_invalid_range() {
+    assert!(serde_yaml::from_str::<RouteRuleEntry>("sport: 2000-1000").is_err());
+    assert!(serde_yaml::from_str::<RouteRuleEntry>("dport: abc").is_err());
+}
+
+#[test]
+fn test_route_rule_sanitize_dscp() {
+    let mut rule: RouteRuleEntry = serde_yaml::from_str(
+        r"
+family: ipv6
+dscp: 46
+route-table: 100
+",
+    )
+    .unwrap();
+    rule.sanitize().unwrap();
+    assert_eq!(rule.tos, Some(184));
+    assert_eq!(rule.dscp, None);
+}
+
+#[test]
+fn test_route_rule_invalid_selectors() {
+    for yml in [
+        "family: ipv4\ndscp: 64",
+        "family: ipv4\ndscp: 1\ntos: 8",
+        "family: ipv4\ntos: 1",
+        "family: ipv4\nsport: 0-80",
+        "family: ipv4\ndport: 65536",
+        "family: ipv4\nl3mdev: true\nroute-table: 100",
+        "family: ipv4\naction: goto",
+        "family: ipv4\npriority: 1000\naction: goto\ngoto-priority: 999",
+        "family: ipv4\ngoto-priority: 2000\nroute-table: 100",
+    ] {
+        let mut rule: RouteRuleEntry = serde_yaml::from_str(yml).unwrap();
+        let result = rule.sanitize();
+        assert!(result.is_err(), "{yml}");
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        }
+    }
+}
+
+#[test]
+fn test_route_rule_l3mdev_and_goto_no_default_table() {
+    for yml in [
+        "family: ipv4\npriority: 1000\nl3mdev: true",
+        "family: ipv4\npriority: 1000\naction: goto\ngoto-priority: 2000",
+    ] {
+        let mut rule: RouteRuleEntry = serde_yaml::from_str(yml).unwrap();
+        rule.sanitize().unwrap();
+        assert_eq!(rule.table_id, None);
+    }
+}
+
+#[test]
+fn test_route_rule_selectors_matching() {
+    let absent_rule: RouteRuleEntry = serde_yaml::from_str(
+        r"
+state: absent
+dport: 443
+dscp: 4
+",
+    )
+    .unwrap();
+    let rules: Vec<RouteRuleEntry> = serde_yaml::from_str(
+        r"
+- family: ipv4
+  dport: 443
+  tos: 16
+  route-table: 100
+- family: ipv4
+  dport: 443
+  route-table: 100
+- family: ipv4
+  dport: 80
+  tos: 16
+  route-table: 100
+",
+    )
+    .unwrap();
+
+    assert!(absent_rule.is_match(&rules[0]));
+    assert!(!absent_rule.is_match(&rules[1]));
+    assert!(!absent_rule.is_match(&rules[2]));
+    assert_ne!(rules[0], rules[1]);
+    assert_ne!(rules[0], rules[2]);
+}
+
+#[test]
+fn test_route_rule_merge_absent_by_sport() {
+    let current: RouteRules = serde_yaml::from_str(
+        r"
+config:
+- family: ipv4
+  priority: 1000
+  sport: 1000-2000
+  route-table: 100
+- family: ipv4
+  priority: 1001
+  sport: 3000
+  route-table: 100
+",
+    )
+    .unwrap();
+    let desired: RouteRules = serde_yaml::from_str(
+        r"
+config:
+- state: absent
+  sport: 1000-2000
+",
+    )
+    .unwrap();
+
+    let merged =
+        MergedRouteRules::new(desired, current, &RouteTableNames::default())
+            .unwrap();
+
+    assert_eq!(merged.for_apply.len(), 1);
+    assert!(merged.for_apply[0].is_absent());
+    assert_eq!(merged.for_apply[0].priority, Some(1000));
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_route_rule_selectors_gen_conf_keyfile() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+route-rules:
+  config:
+  - ip-from: 192.0.2.0/24
+    priority: 1000
+    iif: eth1
+    oif: eth1
+    dscp: 4
+    ipproto: 6
+    sport: 1000-2000
+    dport: 443
+    uidrange: 1000-1999
+    route-table: 100
+",
+    )
+    .unwrap();
+
+    let mut confs = state.gen_conf().unwrap();
+    let confs = confs.remove("NetworkManager").unwrap();
+    let content = confs[0].1.as_str();
+
+    assert!(content.contains(
+        "routing-rule0=priority 1000 from 192.0.2.0/24 iif eth1 oif eth1 \
+        tos 0x10 ipproto 6 sport 1000-2000 dport 443 uidrange 1000-1999 \
+        table 100\n"
+    ));
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_route_rule_goto_not_supported_by_nm() {
+    let state: crate::NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+route-rules:
+  config:
+  - ip-from: 192.0.2.0/24
+    priority: 1000
+    iif: eth1
+    action: goto
+    goto-priority: 2000
+",
+    )
+    .unwrap();
+
+    let result = state.gen_conf();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
+
+#[test]
+fn test_route_rule_verify_diff_path() {
+    let des_rules: RouteRules = serde_yaml::from_str(
+        r"
+        config:
+        - ip-to: 192.168.2.30
+          priority: 100
+          route-table: 200
+          family: ipv4
+        - ip-to: 192.168.2.31
+          priority: 101
+          route-table: 200
+          family: ipv4
+        ",
+    )
+    .unwrap();
+
+    let cur_rules: RouteRules = serde_yaml::from_str(
+        r"
+        config:
+        - ip-to: 192.168.2.30/32
+          priority: 100
+          route-table: 200
+          family: ipv4
+        ",
+    )
+    .unwrap();
+
+    let merged = MergedRouteRules::new(
+        des_rules,
+        RouteRules::new(),
+        &RouteTableNames::default(),
+    )
+    .unwrap();
+
+    let e = merged.verify(&cur_rules, &[]).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::VerificationError);
+    assert_eq!(
+        e.verification_diff().map(|d| d.path.as_str()),
+        Some("route-rules.config[1]")
+    );
+}
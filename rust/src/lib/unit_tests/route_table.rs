@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    unit_tests::testlib::{
+        gen_merged_ifaces_for_route_test, TEST_IPV4_ADDR1, TEST_NIC,
+    },
+    ErrorKind, MergedNexthops, MergedRouteRules, MergedRoutes, RouteRules,
+    RouteTableNames, Routes,
+};
+
+const TEST_DATA_FOLDER_PATH: &str = "unit_tests/route_table_test_files";
+
+fn test_data_path(name: &str) -> String {
+    std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join(TEST_DATA_FOLDER_PATH)
+        .join(name)
+        .display()
+        .to_string()
+}
+
+fn gen_test_route_table_names() -> RouteTableNames {
+    let mut names = RouteTableNames::default();
+    names.load(
+        test_data_path("rt_tables").as_str(),
+        test_data_path("rt_tables.d").as_str(),
+    );
+    names
+}
+
+#[test]
+fn test_route_table_names_load() {
+    let names = gen_test_route_table_names();
+
+    assert_eq!(names.get_id("main"), Some(254));
+    assert_eq!(names.get_id("mgmt"), Some(100));
+    assert_eq!(names.get_id("storage"), Some(101));
+    assert_eq!(names.get_id("vpn"), Some(200));
+    assert_eq!(names.get_id("ignored"), None);
+    assert_eq!(names.get_name(100), Some("mgmt"));
+    assert_eq!(names.get_name(254), None);
+    assert_eq!(names.get_name(300), None);
+}
+
+#[test]
+fn test_route_table_names_etc_override_usr() {
+    // Treat the default test files as `/usr/share/iproute2`
+    let mut names = gen_test_route_table_names();
+    names.load(
+        test_data_path("etc/rt_tables").as_str(),
+        test_data_path("etc/rt_tables.d").as_str(),
+    );
+
+    assert_eq!(names.get_id("mgmt"), Some(100));
+    assert_eq!(names.get_id("storage"), Some(101));
+    assert_eq!(names.get_id("backup"), Some(102));
+    assert_eq!(names.get_id("vpn"), Some(201));
+}
+
+#[test]
+fn test_route_table_names_builtin() {
+    let mut names = RouteTableNames::default();
+    names.load("/nonexistent/rt_tables", "/nonexistent/rt_tables.d");
+
+    assert_eq!(names.get_id("main"), Some(254));
+    assert_eq!(names.get_id("local"), Some(255));
+}
+
+#[test]
+fn test_route_table_name_resolve() {
+    let names = gen_test_route_table_names();
+
+    assert_eq!(names.resolve(None, "mgmt").unwrap(), 100);
+    assert_eq!(names.resolve(Some(100), "mgmt").unwrap(), 100);
+    for (table_id, name) in [(None, "unknown"), (Some(101), "mgmt")] {
+        let result = names.resolve(table_id, name);
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        }
+    }
+}
+
+#[test]
+fn test_route_table_name_serialize() {
+    let routes: Routes = serde_yaml::from_str(
+        r"
+config:
+- destination: 203.0.113.0/24
+  next-hop-interface: eth1
+  table-name: mgmt
+",
+    )
+    .unwrap();
+    let rules: RouteRules = serde_yaml::from_str(
+        r"
+config:
+- ip-from: 192.0.2.0/24
+  route-table-name: mgmt
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        routes.config.as_ref().unwrap()[0].table_name.as_deref(),
+        Some("mgmt")
+    );
+    assert_eq!(
+        rules.config.as_ref().unwrap()[0].table_name.as_deref(),
+        Some("mgmt")
+    );
+    assert!(serde_yaml::to_string(&routes)
+        .unwrap()
+        .contains("table-name: mgmt\n"));
+    assert!(serde_yaml::to_string(&rules)
+        .unwrap()
+        .contains("route-table-name: mgmt\n"));
+}
+
+#[test]
+fn test_merged_routes_resolve_table_name() {
+    let des_routes: Routes = serde_yaml::from_str(&format!(
+        r"
+config:
+- destination: 203.0.113.0/24
+  next-hop-interface: {TEST_NIC}
+  next-hop-address: {TEST_IPV4_ADDR1}
+  table-name: mgmt
+"
+    ))
+    .unwrap();
+    let (merged_ifaces, _) = gen_merged_ifaces_for_route_test();
+
+    let merged_routes = MergedRoutes::new(
+        des_routes,
+        Routes::new(),
+        &merged_ifaces,
+        &MergedNexthops::default(),
+        &gen_test_route_table_names(),
+    )
+    .unwrap();
+
+    let rts = merged_routes.merged.get(TEST_NIC).unwrap();
+    assert_eq!(rts[0].table_id, Some(100));
+    assert_eq!(rts[0].table_name.as_deref(), Some("mgmt"));
+}
+
+#[test]
+fn test_merged_routes_unknown_table_name() {
+    let des_routes: Routes = serde_yaml::from_str(&format!(
+        r"
+config:
+- destination: 203.0.113.0/24
+  next-hop-interface: {TEST_NIC}
+  table-name: unknown
+"
+    ))
+    .unwrap();
+    let (merged_ifaces, _) = gen_merged_ifaces_for_route_test();
+
+    let result = MergedRoutes::new(
+        des_routes,
+        Routes::new(),
+        &merged_ifaces,
+        &MergedNexthops::default(),
+        &gen_test_route_table_names(),
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_merged_route_rules_resolve_table_name() {
+    let desired: RouteRules = serde_yaml::from_str(
+        r"
+config:
+- ip-from: 192.0.2.0/24
+  priority: 1000
+  route-table-name: vpn
+",
+    )
+    .unwrap();
+    let current: RouteRules = serde_yaml::from_str(
+        r"
+config:
+- ip-from: 198.51.100.0/24
+  priority: 1001
+  route-table: 100
+",
+    )
+    .unwrap();
+    let absent: RouteRules = serde_yaml::from_str(
+        r"
+config:
+- state: absent
+  route-table-name: mgmt
+",
+    )
+    .unwrap();
+
+    let merged = MergedRouteRules::new(
+        desired,
+        RouteRules::new(),
+        &gen_test_route_table_names(),
+    )
+    .unwrap();
+    assert_eq!(merged.for_apply[0].table_id, Some(200));
+
+    let merged =
+        MergedRouteRules::new(absent, current, &gen_test_route_table_names())
+            .unwrap();
+    assert!(merged.for_apply[0].is_absent());
+    assert_eq!(merged.for_apply[0].priority, Some(1001));
+}
+
+#[test]
+fn test_route_table_names_create() {
+    let routes: Routes = serde_yaml::from_str(
+        r"
+config:
+- destination: 203.0.113.0/24
+  next-hop-interface: eth1
+  table-id: 150
+  table-name: nmstate-unit-test-table
+",
+    )
+    .unwrap();
+
+    let names =
+        RouteTableNames::new(&routes, &RouteRules::new(), true).unwrap();
+    assert_eq!(names.get_id("nmstate-unit-test-table"), Some(150));
+    assert_eq!(
+        names.new_entries,
+        vec![(150, "nmstate-unit-test-table".to_string())]
+    );
+
+    let result = RouteTableNames::new(&routes, &RouteRules::new(), false)
+        .unwrap()
+        .resolve(Some(150), "nmstate-unit-test-table");
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_route_table_names_create_invalid_name() {
+    let rules: RouteRules = serde_yaml::from_str(
+        r#"
+config:
+- ip-from: 192.0.2.0/24
+  route-table: 150
+  route-table-name: "151"
+"#,
+    )
+    .unwrap();
+
+    let result = RouteTableNames::new(&Routes::new(), &rules, true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_route_table_names_save_new_entries() {
+    let dir_path = std::env::temp_dir()
+        .join(format!("nmstate_rt_tables_test_{}", std::process::id()));
+    let dir_path = dir_path.display().to_string();
+    let file_path = format!("{dir_path}/nmstate.conf");
+    std::fs::remove_dir_all(&dir_path).ok();
+
+    RouteTableNames::default()
+        .save_new_entries_to(&dir_path)
+        .unwrap();
+    assert!(!std::path::Path::new(&file_path).exists());
+
+    let names = RouteTableNames::from_new_entries(vec![
+        (100, "nmstate_a".to_string()),
+        (101, "nmstate_b".to_string()),
+    ]);
+    names.save_new_entries_to(&dir_path).unwrap();
+    // Retry of apply should not store duplicate entries
+    names.save_new_entries_to(&dir_path).unwrap();
+    RouteTableNames::from_new_entries(vec![(102, "nmstate_c".to_string())])
+        .save_new_entries_to(&dir_path)
+        .unwrap();
+
+    let content = std::fs::read_to_string(&file_path).unwrap();
+    std::fs::remove_dir_all(&dir_path).ok();
+
+    assert_eq!(
+        content,
+        "# Route table names created by nmstate\n\
+        100 nmstate_a\n\
+        101 nmstate_b\n\
+        102 nmstate_c\n"
+    );
+    let mut saved = RouteTableNames::default();
+    saved.parse(&content);
+    assert_eq!(saved.get_id("nmstate_b"), Some(101));
+}
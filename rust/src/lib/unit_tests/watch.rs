@@ -0,0 +1,449 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::json;
+
+use crate::{
+    nispor::parse_rtnl_events,
+    nm::{nm_props_changed_targets, NmChange, NmSignalTarget},
+    ovsdb::ovsdb_update_to_events,
+    query_apply::{gen_watch_delta, merge_watch_scope, WatchEvent, WatchScope},
+    NetworkState,
+};
+
+#[test]
+fn test_watch_delta_iface_added_changed_removed() {
+    let old: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          mtu: 1500
+        - name: eth2
+          type: ethernet
+          state: up
+        - name: dummy0
+          type: dummy
+          state: up
+        ",
+    )
+    .unwrap();
+    let new: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          mtu: 9000
+        - name: eth2
+          type: ethernet
+          state: up
+        - name: br0
+          type: linux-bridge
+          state: up
+        ",
+    )
+    .unwrap();
+    let expected: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          mtu: 9000
+        - name: br0
+          type: linux-bridge
+          state: up
+        - name: dummy0
+          type: dummy
+          state: absent
+        ",
+    )
+    .unwrap();
+
+    let delta = gen_watch_delta(&old, &new);
+
+    assert_eq!(delta.interfaces.to_vec(), expected.interfaces.to_vec());
+    assert!(delta.routes.is_empty());
+    assert!(delta.dns.is_none());
+}
+
+#[test]
+fn test_watch_delta_all_routes_removed() {
+    let old: NetworkState = serde_yaml::from_str(
+        r"---
+        routes:
+          running:
+          - destination: 198.51.100.0/24
+            next-hop-interface: eth1
+        interfaces: []
+        ",
+    )
+    .unwrap();
+    let new = NetworkState::new();
+
+    let delta = gen_watch_delta(&old, &new);
+
+    assert_eq!(delta.routes.running, Some(Vec::new()));
+    assert!(delta.interfaces.is_empty());
+}
+
+#[test]
+fn test_watch_delta_no_change() {
+    let state: NetworkState = serde_yaml::from_str(
+        r"---
+        dns-resolver:
+          running:
+            server:
+            - 192.0.2.1
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+        ",
+    )
+    .unwrap();
+
+    assert!(gen_watch_delta(&state, &state).is_empty());
+}
+
+fn gen_current_state() -> NetworkState {
+    serde_yaml::from_str(
+        r"---
+        routes:
+          running:
+          - destination: 198.51.100.0/24
+            next-hop-interface: eth1
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          controller: bond0
+        - name: eth2
+          type: ethernet
+          state: up
+          controller: bond0
+        - name: bond0
+          type: bond
+          state: up
+          link-aggregation:
+            mode: active-backup
+            port:
+            - eth1
+            - eth2
+        - name: eth3
+          type: ethernet
+          state: up
+        ",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_watch_merge_scope_iface_with_controller() {
+    let current = gen_current_state();
+    // Everything changed in queried state, only eth1 and its controller
+    // should be taken.
+    let queried: NetworkState = serde_yaml::from_str(
+        r"---
+        routes:
+          running:
+          - destination: 203.0.113.0/24
+            next-hop-interface: eth3
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          mtu: 9000
+        - name: eth2
+          type: ethernet
+          state: up
+          controller: bond0
+        - name: bond0
+          type: bond
+          state: up
+          link-aggregation:
+            mode: active-backup
+            port:
+            - eth2
+        - name: eth3
+          type: ethernet
+          state: up
+          mtu: 9000
+        ",
+    )
+    .unwrap();
+    let mut scope = WatchScope::default();
+    scope.add(WatchEvent::Iface("eth1".to_string()));
+
+    let new_state = merge_watch_scope(current.clone(), queried, &scope);
+    let delta = gen_watch_delta(&current, &new_state);
+
+    let expected: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          mtu: 9000
+        - name: bond0
+          type: bond
+          state: up
+          link-aggregation:
+            mode: active-backup
+            port:
+            - eth2
+        ",
+    )
+    .unwrap();
+    let mut delta_ifaces = delta.interfaces.to_vec();
+    delta_ifaces.sort_unstable_by_key(|i| i.name());
+    let mut expected_ifaces = expected.interfaces.to_vec();
+    expected_ifaces.sort_unstable_by_key(|i| i.name());
+    assert_eq!(delta_ifaces, expected_ifaces);
+    assert!(delta.routes.is_empty());
+}
+
+#[test]
+fn test_watch_merge_scope_removed_iface_and_routes() {
+    let current = gen_current_state();
+    let queried: NetworkState = serde_yaml::from_str(
+        r"---
+        routes:
+          running: []
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          controller: bond0
+        - name: eth2
+          type: ethernet
+          state: up
+          controller: bond0
+        - name: bond0
+          type: bond
+          state: up
+          link-aggregation:
+            mode: active-backup
+            port:
+            - eth1
+            - eth2
+        ",
+    )
+    .unwrap();
+    let mut scope = WatchScope::default();
+    scope.add(WatchEvent::Iface("eth3".to_string()));
+    scope.add(WatchEvent::Routes);
+
+    let new_state = merge_watch_scope(current.clone(), queried, &scope);
+    let delta = gen_watch_delta(&current, &new_state);
+
+    let expected: NetworkState = serde_yaml::from_str(
+        r"---
+        routes:
+          running: []
+        interfaces:
+        - name: eth3
+          type: ethernet
+          state: absent
+        ",
+    )
+    .unwrap();
+    assert_eq!(delta.interfaces.to_vec(), expected.interfaces.to_vec());
+    assert_eq!(delta.routes, expected.routes);
+}
+
+#[test]
+fn test_watch_scope_is_empty() {
+    let mut scope = WatchScope::default();
+    assert!(scope.is_empty());
+    scope.add(WatchEvent::Hostname);
+    assert!(!scope.is_empty());
+}
+
+fn gen_nlmsg(msg_type: u16, header: &[u8], attrs: &[(u16, &[u8])]) -> Vec<u8> {
+    let mut payload = header.to_vec();
+    for (rta_type, value) in attrs {
+        let rta_len = 4 + value.len();
+        payload.extend_from_slice(&(rta_len as u16).to_ne_bytes());
+        payload.extend_from_slice(&rta_type.to_ne_bytes());
+        payload.extend_from_slice(value);
+        payload.resize((payload.len() + 3) & !3, 0);
+    }
+    let mut msg = Vec::new();
+    msg.extend_from_slice(&(16 + payload.len() as u32).to_ne_bytes());
+    msg.extend_from_slice(&msg_type.to_ne_bytes());
+    msg.extend_from_slice(&[0u8; 10]);
+    msg.extend_from_slice(&payload);
+    msg
+}
+
+fn gen_ifinfomsg(index: i32, flags: u32) -> Vec<u8> {
+    let mut ret = vec![0u8; 4];
+    ret.extend_from_slice(&index.to_ne_bytes());
+    ret.extend_from_slice(&flags.to_ne_bytes());
+    ret.extend_from_slice(&[0u8; 4]);
+    ret
+}
+
+fn gen_ifaddrmsg(index: u32) -> Vec<u8> {
+    let mut ret = vec![10u8, 64, 0, 0];
+    ret.extend_from_slice(&index.to_ne_bytes());
+    ret
+}
+
+fn no_resolve(_index: u32) -> Option<String> {
+    None
+}
+
+#[test]
+fn test_watch_rtnl_link_events() {
+    // IFF_UP | IFF_BROADCAST | IFF_RUNNING
+    let mut buffer = gen_nlmsg(16, &gen_ifinfomsg(3, 0x43), &[(3, b"eth1\0")]);
+    buffer.extend_from_slice(&gen_nlmsg(
+        17,
+        &gen_ifinfomsg(4, 0),
+        &[(4, &1500u32.to_ne_bytes()), (3, b"dummy0\0")],
+    ));
+
+    assert_eq!(
+        parse_rtnl_events(&buffer, no_resolve),
+        vec![
+            WatchEvent::Iface("eth1".to_string()),
+            WatchEvent::Iface("dummy0".to_string()),
+            WatchEvent::Routes,
+            WatchEvent::Nexthops,
+        ]
+    );
+}
+
+#[test]
+fn test_watch_rtnl_addr_route_rule_nexthop_events() {
+    let mut buffer = gen_nlmsg(20, &gen_ifaddrmsg(3), &[(3, b"eth1\0")]);
+    buffer.extend_from_slice(&gen_nlmsg(21, &gen_ifaddrmsg(5), &[]));
+    buffer.extend_from_slice(&gen_nlmsg(21, &gen_ifaddrmsg(6), &[]));
+    buffer.extend_from_slice(&gen_nlmsg(24, &[0u8; 12], &[]));
+    buffer.extend_from_slice(&gen_nlmsg(33, &[0u8; 12], &[]));
+    buffer.extend_from_slice(&gen_nlmsg(104, &[0u8; 8], &[]));
+
+    assert_eq!(
+        parse_rtnl_events(&buffer, |index| if index == 5 {
+            Some("eth5".to_string())
+        } else {
+            None
+        }),
+        vec![
+            WatchEvent::Iface("eth1".to_string()),
+            WatchEvent::Iface("eth5".to_string()),
+            WatchEvent::Ifaces,
+            WatchEvent::Routes,
+            WatchEvent::RouteRules,
+            WatchEvent::Nexthops,
+        ]
+    );
+}
+
+#[test]
+fn test_watch_rtnl_truncated_message() {
+    let mut buffer = gen_nlmsg(16, &gen_ifinfomsg(3, 1), &[(3, b"eth1\0")]);
+    buffer.truncate(buffer.len() - 4);
+
+    assert_eq!(
+        parse_rtnl_events(&buffer, no_resolve),
+        vec![WatchEvent::All]
+    );
+}
+
+#[test]
+fn test_watch_ovsdb_update_events() {
+    let params = json!([null, {
+        "Interface": {
+            "8d5fd6e4-0a54-4c2f-8a2a-5a2e3a1b0a01": {
+                "new": {"name": "ovs0", "mtu": 1500},
+                "old": {"mtu": 9000}
+            },
+            "8d5fd6e4-0a54-4c2f-8a2a-5a2e3a1b0a02": {
+                "old": {"name": "eth1"}
+            }
+        },
+        "Open_vSwitch": {
+            "8d5fd6e4-0a54-4c2f-8a2a-5a2e3a1b0a03": {
+                "new": {"external_ids": ["map", []]}
+            }
+        },
+        "Port": {
+            "8d5fd6e4-0a54-4c2f-8a2a-5a2e3a1b0a04": {
+                "new": {"name": "bond1"}
+            }
+        }
+    }]);
+
+    assert_eq!(
+        ovsdb_update_to_events(&params),
+        vec![
+            WatchEvent::Iface("ovs0".to_string()),
+            WatchEvent::Iface("eth1".to_string()),
+            WatchEvent::Ovsdb,
+            WatchEvent::Ifaces,
+        ]
+    );
+}
+
+#[test]
+fn test_watch_nm_properties_changed_targets() {
+    assert_eq!(
+        nm_props_changed_targets(
+            "/org/freedesktop/NetworkManager/Devices/3",
+            "org.freedesktop.NetworkManager.Device",
+            &["State", "StateReason"],
+        ),
+        vec![NmSignalTarget::Device(
+            "/org/freedesktop/NetworkManager/Devices/3".to_string()
+        )]
+    );
+    assert!(nm_props_changed_targets(
+        "/org/freedesktop/NetworkManager/Devices/3",
+        "org.freedesktop.NetworkManager.Device.Statistics",
+        &["TxBytes"],
+    )
+    .is_empty());
+    assert!(nm_props_changed_targets(
+        "/org/freedesktop/NetworkManager/IP4Config/7",
+        "org.freedesktop.NetworkManager.IP4Config",
+        &["AddressData"],
+    )
+    .is_empty());
+    assert_eq!(
+        nm_props_changed_targets(
+            "/org/freedesktop/NetworkManager/DnsManager",
+            "org.freedesktop.NetworkManager.DnsManager",
+            &["Configuration"],
+        ),
+        vec![NmSignalTarget::Change(NmChange::Dns)]
+    );
+    assert_eq!(
+        nm_props_changed_targets(
+            "/org/freedesktop/NetworkManager/Settings",
+            "org.freedesktop.NetworkManager.Settings",
+            &["Hostname"],
+        ),
+        vec![NmSignalTarget::Change(NmChange::Hostname)]
+    );
+    assert_eq!(
+        nm_props_changed_targets(
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+            &["GlobalDnsConfiguration", "AllDevices", "State"],
+        ),
+        vec![
+            NmSignalTarget::Change(NmChange::Dns),
+            NmSignalTarget::Change(NmChange::Ifaces),
+        ]
+    );
+    assert!(nm_props_changed_targets(
+        "/org/freedesktop/NetworkManager",
+        "org.freedesktop.NetworkManager",
+        &["Connectivity"],
+    )
+    .is_empty());
+}
@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, Interface, Interfaces, NetworkState};
+
+const PRIVATE_KEY: &str = "yAnz5TF+lXXJte14tji3zlMNq+hd2rYUIgJBgB3fBmk=";
+const PUBLIC_KEY: &str = "xTIBA5rboUvnH4htodjb6e697QjLERt1NAB4mZqp8Dg=";
+const PRESHARED_KEY: &str = "FpCyhws9cxwWoV4xELtfJvjJN+zQVRPISllRWgeopVE=";
+
+#[test]
+fn test_wireguard_hide_secrets() {
+    let mut state: NetworkState = serde_yaml::from_str(&format!(
+        r"---
+interfaces:
+- name: wg0
+  type: wireguard
+  state: up
+  wireguard:
+    private-key: {PRIVATE_KEY}
+    listen-port: 51820
+    peers:
+    - public-key: {PUBLIC_KEY}
+      preshared-key: {PRESHARED_KEY}
+      endpoint: 192.0.2.1:51820
+      allowed-ips:
+      - 10.10.10.0/24
+"
+    ))
+    .unwrap();
+
+    state.hide_secrets();
+    let output = serde_yaml::to_string(&state).unwrap();
+    assert!(!output.contains(PRIVATE_KEY));
+    assert!(!output.contains(PRESHARED_KEY));
+    assert!(output.contains(PUBLIC_KEY));
+}
+
+#[test]
+fn test_wireguard_debug_hide_secrets() {
+    let ifaces: Interfaces = serde_yaml::from_str(&format!(
+        r"---
+- name: wg0
+  type: wireguard
+  wireguard:
+    private-key: {PRIVATE_KEY}
+    peers:
+    - public-key: {PUBLIC_KEY}
+      preshared-key: {PRESHARED_KEY}
+"
+    ))
+    .unwrap();
+
+    let output = format!("{ifaces:?}");
+    assert!(!output.contains(PRIVATE_KEY));
+    assert!(!output.contains(PRESHARED_KEY));
+}
+
+#[test]
+fn test_wireguard_invalid_private_key() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: wg0
+  type: wireguard
+  wireguard:
+    private-key: not-a-valid-key
+",
+    )
+    .unwrap();
+
+    let result = ifaces.kernel_ifaces.get_mut("wg0").unwrap().sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_wireguard_sanitize_allowed_ips() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(&format!(
+        r"---
+- name: wg0
+  type: wireguard
+  wireguard:
+    peers:
+    - public-key: {PUBLIC_KEY}
+      allowed-ips:
+      - fd00:10::1/64
+      - 10.10.10.1/24
+      - 10.10.10.0/24
+"
+    ))
+    .unwrap();
+
+    let iface = ifaces.kernel_ifaces.get_mut("wg0").unwrap();
+    iface.sanitize(true).unwrap();
+
+    if let Interface::WireGuard(iface) = iface {
+        let peers = iface.wireguard.as_ref().unwrap().peers.as_ref().unwrap();
+        assert_eq!(
+            peers[0].allowed_ips,
+            Some(vec![
+                "10.10.10.0/24".to_string(),
+                "fd00:10::/64".to_string()
+            ])
+        );
+    } else {
+        panic!("Expecting WireGuard interface, but got {iface:?}");
+    }
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_wireguard_gen_conf_keyfile() {
+    let state: NetworkState = serde_yaml::from_str(&format!(
+        r"---
+interfaces:
+- name: wg0
+  type: wireguard
+  state: up
+  wireguard:
+    private-key: {PRIVATE_KEY}
+    listen-port: 51820
+    fwmark: 100
+    peers:
+    - public-key: {PUBLIC_KEY}
+      preshared-key: {PRESHARED_KEY}
+      endpoint: 192.0.2.1:51820
+      allowed-ips:
+      - 10.10.10.0/24
+      - fd00:10::/64
+      persistent-keepalive: 25
+"
+    ))
+    .unwrap();
+
+    let mut confs = state.gen_conf().unwrap();
+    let confs = confs.remove("NetworkManager").unwrap();
+    assert_eq!(confs.len(), 1);
+    let content = confs[0].1.as_str();
+
+    assert!(content.contains("type=wireguard\n"));
+    assert!(content.contains(&format!(
+        "[wireguard]\nfwmark=100\nlisten-port=51820\n\
+        private-key={PRIVATE_KEY}\nprivate-key-flags=0\n"
+    )));
+    assert!(content.contains(&format!(
+        "[wireguard-peer.{PUBLIC_KEY}]\n\
+        allowed-ips=10.10.10.0/24;fd00:10::/64\n\
+        endpoint=192.0.2.1:51820\n\
+        persistent-keepalive=25\n\
+        preshared-key={PRESHARED_KEY}\n\
+        preshared-key-flags=0\n"
+    )));
+}
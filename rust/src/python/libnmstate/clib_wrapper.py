@@ -40,6 +40,7 @@ NMSTATE_FLAG_INCLUDE_SECRETS = 1 << 4
 NMSTATE_FLAG_NO_COMMIT = 1 << 5
 NMSTATE_FLAG_MEMORY_ONLY = 1 << 6
 NMSTATE_FLAG_RUNNING_CONFIG_ONLY = 1 << 7
+NMSTATE_FLAG_JSON_ERROR = 1 << 10
 NMSTATE_PASS = 0
 
 
@@ -97,7 +98,7 @@ def apply_net_state(
     c_err_kind = c_char_p()
     c_state = c_char_p(json.dumps(state).encode("utf-8"))
     c_log = c_char_p()
-    flags = NMSTATE_FLAG_NONE
+    flags = NMSTATE_FLAG_JSON_ERROR
     if kernel_only:
         flags |= NMSTATE_FLAG_KERNEL_ONLY
 
@@ -125,7 +126,8 @@ def apply_net_state(
     lib.nmstate_cstring_free(c_err_kind)
     lib.nmstate_cstring_free(c_err_msg)
     if rc != NMSTATE_PASS:
-        raise map_error(err_kind, err_msg)
+        err_msg, verification_diff = parse_json_error(err_msg)
+        raise map_error(err_kind, err_msg, verification_diff)
 
 
 def commit_checkpoint(checkpoint):
@@ -281,11 +283,31 @@ def net_state_from_policy(policy, cur_state):
     # pylint: enable=no-member
 
 
-def map_error(err_kind, err_msg):
+def parse_json_error(err_msg):
+    """
+    Return error message and verification difference from the JSON error
+    generated with NMSTATE_FLAG_JSON_ERROR.
+    """
+    try:
+        err = json.loads(err_msg.decode("utf-8"))
+    except Exception:
+        return err_msg, None
+    if not isinstance(err, dict) or "msg" not in err:
+        return err_msg, None
+    return err["msg"].encode("utf-8"), err.get("verification-diff")
+
+
+def map_error(err_kind, err_msg, verification_diff=None):
     err_msg = err_msg.decode("utf-8")
     err_kind = err_kind.decode("utf-8")
     if err_kind == "VerificationError":
-        return NmstateVerificationError(err_msg)
+        verification_diff = verification_diff or {}
+        return NmstateVerificationError(
+            err_msg,
+            path=verification_diff.get("path"),
+            desired=verification_diff.get("desired"),
+            current=verification_diff.get("current"),
+        )
     elif err_kind == "InvalidArgument":
         return NmstateValueError(err_msg)
     elif err_kind == "Bug":
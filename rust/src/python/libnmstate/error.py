@@ -74,9 +74,16 @@ class NmstateVerificationError(NmstateError):
     """
     After applied desired state, current state does not match desired state for
     unknown reason.
+    The `path`(e.g. `interfaces[name=eth1].ipv4.dhcp`), `desired` and
+    `current` attributes hold the first mismatched property if known,
+    otherwise None.
     """
 
-    pass
+    def __init__(self, msg, path=None, desired=None, current=None):
+        super().__init__(msg)
+        self.path = path
+        self.desired = desired
+        self.current = current
 
 
 class NmstateKernelIntegerRoundedError(NmstateVerificationError):
@@ -35,6 +35,7 @@ class Route:
     STATE = "state"
     STATE_ABSENT = "absent"
     TABLE_ID = "table-id"
+    TABLE_NAME = "table-name"
     DESTINATION = "destination"
     NEXT_HOP_INTERFACE = "next-hop-interface"
     NEXT_HOP_ADDRESS = "next-hop-address"
@@ -44,9 +45,47 @@ class Route:
     ROUTETYPE_BLACKHOLE = "blackhole"
     ROUTETYPE_UNREACHABLE = "unreachable"
     ROUTETYPE_PROHIBIT = "prohibit"
+    ROUTETYPE_THROW = "throw"
+    ROUTETYPE_LOCAL = "local"
+    ROUTETYPE_BROADCAST = "broadcast"
+    ROUTETYPE_ANYCAST = "anycast"
+    ROUTETYPE_MULTICAST = "multicast"
     USE_DEFAULT_METRIC = -1
     USE_DEFAULT_ROUTE_TABLE = 0
     CWND = "cwnd"
+    SOURCE = "source"
+    MTU = "mtu"
+    LOCK_MTU = "lock-mtu"
+    ADVMSS = "advmss"
+    INITCWND = "initcwnd"
+    INITRWND = "initrwnd"
+    QUICKACK = "quickack"
+    ONLINK = "onlink"
+    SCOPE = "scope"
+    SCOPE_UNIVERSE = "universe"
+    SCOPE_SITE = "site"
+    SCOPE_LINK = "link"
+    SCOPE_HOST = "host"
+    PROTOCOL = "protocol"
+    NEXTHOP_ID = "nexthop-id"
+
+
+class Nexthop:
+    KEY = "nexthops"
+
+    RUNNING = "running"
+    CONFIG = "config"
+    STATE = "state"
+    STATE_ABSENT = "absent"
+    ID = "id"
+    FAMILY = "family"
+    FAMILY_IPV4 = "ipv4"
+    FAMILY_IPV6 = "ipv6"
+    NEXT_HOP_INTERFACE = "next-hop-interface"
+    NEXT_HOP_ADDRESS = "next-hop-address"
+    GROUP = "group"
+    GROUP_ID = "id"
+    GROUP_WEIGHT = "weight"
 
 
 class RouteRule:
@@ -56,6 +95,7 @@ class RouteRule:
     IP_TO = "ip-to"
     PRIORITY = "priority"
     ROUTE_TABLE = "route-table"
+    ROUTE_TABLE_NAME = "route-table-name"
     USE_DEFAULT_PRIORITY = -1
     USE_DEFAULT_ROUTE_TABLE = 0
     STATE = "state"
@@ -70,7 +110,18 @@ class RouteRule:
     ACTION_BLACKHOLE = "blackhole"
     ACTION_UNREACHABLE = "unreachable"
     ACTION_PROHIBIT = "prohibit"
+    ACTION_GOTO = "goto"
+    ACTION_NOP = "nop"
     SUPPRESS_PREFIX_LENGTH = "suppress-prefix-length"
+    SPORT = "sport"
+    DPORT = "dport"
+    IPPROTO = "ipproto"
+    UIDRANGE = "uidrange"
+    TOS = "tos"
+    DSCP = "dscp"
+    OIF = "oif"
+    L3MDEV = "l3mdev"
+    GOTO_PRIORITY = "goto-priority"
 
 
 class DNS:
@@ -120,6 +171,15 @@ class InterfaceType:
     OTHER = "other"
     LOOPBACK = "loopback"
     IPSEC = "ipsec"
+    WIREGUARD = "wireguard"
+    GRE = "gre"
+    GRETAP = "gretap"
+    IPIP = "ipip"
+    SIT = "sit"
+    IP6TNL = "ip6tnl"
+    GENEVE = "geneve"
+    BAREUDP = "bareudp"
+    IPVLAN = "ipvlan"
 
     VIRT_TYPES = (
         BOND,
@@ -134,6 +194,15 @@ class InterfaceType:
         VLAN,
         VXLAN,
         IPSEC,
+        WIREGUARD,
+        GRE,
+        GRETAP,
+        IPIP,
+        SIT,
+        IP6TNL,
+        GENEVE,
+        BAREUDP,
+        IPVLAN,
     )
 
 
@@ -459,6 +528,20 @@ class MacVtap(MacVlan):
     CONFIG_SUBTREE = "mac-vtap"
 
 
+class IpVlan:
+    TYPE = InterfaceType.IPVLAN
+    CONFIG_SUBTREE = "ipvlan"
+    BASE_IFACE = "base-iface"
+    MODE = "mode"
+    PRIVATE = "private"
+    VEPA = "vepa"
+
+    class Mode:
+        L2 = "l2"
+        L3 = "l3"
+        L3S = "l3s"
+
+
 class MacSec:
     CONFIG_SUBTREE = "macsec"
     ENCRYPT = "encrypt"
@@ -486,6 +569,68 @@ class Hsr:
     PROTOCOL = "protocol"
 
 
+class WireGuard:
+    CONFIG_SUBTREE = "wireguard"
+    PRIVATE_KEY = "private-key"
+    LISTEN_PORT = "listen-port"
+    FWMARK = "fwmark"
+    PEERS = "peers"
+    PEER_PUBLIC_KEY = "public-key"
+    PEER_PRESHARED_KEY = "preshared-key"
+    PEER_ENDPOINT = "endpoint"
+    PEER_ALLOWED_IPS = "allowed-ips"
+    PEER_PERSISTENT_KEEPALIVE = "persistent-keepalive"
+
+
+class IpTunnel:
+    CONFIG_SUBTREE = "ip-tunnel"
+    BASE_IFACE = "base-iface"
+    LOCAL = "local"
+    REMOTE = "remote"
+    TTL = "ttl"
+    TOS = "tos"
+    KEY = "key"
+    IKEY = "ikey"
+    OKEY = "okey"
+    PMTUDISC = "pmtudisc"
+    ENCAP = "encap"
+    ENCAP_TYPE = "type"
+    ENCAP_TYPE_NONE = "none"
+    ENCAP_TYPE_FOU = "fou"
+    ENCAP_TYPE_GUE = "gue"
+    ENCAP_SOURCE_PORT = "source-port"
+    ENCAP_DESTINATION_PORT = "destination-port"
+    ENCAP_CHECKSUM = "checksum"
+
+
+class Geneve:
+    TYPE = InterfaceType.GENEVE
+    CONFIG_SUBTREE = "geneve"
+
+    ID = "id"
+    REMOTE = "remote"
+    DESTINATION_PORT = "destination-port"
+    TTL = "ttl"
+    TOS = "tos"
+    DF = "df"
+    DF_UNSET = "unset"
+    DF_SET = "set"
+    DF_INHERIT = "inherit"
+    UDP_CHECKSUM = "udp-checksum"
+
+
+class BareUdp:
+    TYPE = InterfaceType.BAREUDP
+    CONFIG_SUBTREE = "bareudp"
+
+    DESTINATION_PORT = "destination-port"
+    ETHERTYPE = "ethertype"
+    ETHERTYPE_IPV4 = "ipv4"
+    ETHERTYPE_IPV6 = "ipv6"
+    ETHERTYPE_MPLS_UC = "mpls-uc"
+    ETHERTYPE_MPLS_MC = "mpls-mc"
+
+
 class Ieee8021X:
     CONFIG_SUBTREE = "802.1x"
     IDENTITY = "identity"
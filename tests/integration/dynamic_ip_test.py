@@ -2031,6 +2031,72 @@ def test_static_ipv6_route_not_covert_auto_ip(dhcpcli_up_with_dynamic_ip):
     assert pre_dhcpv6_addrs == new_dhcpv6_addrs
 
 
+@pytest.fixture
+def dhcpcli_up_in_kernel_mode(dhcp_env):
+    cmdlib.exec_cmd(f"ip link set {DHCP_CLI_NIC} up".split(), check=True)
+    yield
+    libnmstate.apply(
+        {
+            Interface.KEY: [
+                {
+                    Interface.NAME: DHCP_CLI_NIC,
+                    Interface.IPV4: _create_ipv4_state(enabled=False),
+                }
+            ]
+        },
+        kernel_only=True,
+    )
+
+
+def test_kernel_mode_ipv4_dhcp(dhcpcli_up_in_kernel_mode):
+    desired_state = {
+        Interface.KEY: [
+            {
+                Interface.NAME: DHCP_CLI_NIC,
+                Interface.STATE: InterfaceState.UP,
+                Interface.IPV4: _create_ipv4_state(enabled=True, dhcp=True),
+            }
+        ]
+    }
+    libnmstate.apply(desired_state, kernel_only=True)
+
+    iface_state = _show_kernel_iface(DHCP_CLI_NIC)
+    ipv4_state = iface_state[Interface.IPV4]
+    assert ipv4_state[InterfaceIPv4.DHCP]
+    lease = ipv4_state["dhcp-lease"]
+    assert lease["state"] == "bound"
+    assert lease["server"] == DHCP_SRV_IP4
+    assert [lease["address"]] == get_dhcp_addr(iface_state)[0]
+
+    # Kernel should not purge the leased IP address before nmstate renew it
+    output = cmdlib.exec_cmd(
+        f"ip -4 addr show dev {DHCP_CLI_NIC}".split(), check=True
+    )[1]
+    assert lease["address"] in output
+    assert "valid_lft forever" in output
+
+    ipv4_config = _show_kernel_iface(DHCP_CLI_NIC, running_config=True)[
+        Interface.IPV4
+    ]
+    assert ipv4_config[InterfaceIPv4.DHCP]
+    assert not ipv4_config[InterfaceIPv4.ADDRESS]
+    assert "dhcp-lease" not in ipv4_config
+
+    # Reapplying the same state should not request new lease
+    libnmstate.apply(desired_state, kernel_only=True)
+    new_ipv4_state = _show_kernel_iface(DHCP_CLI_NIC)[Interface.IPV4]
+    new_lease = new_ipv4_state["dhcp-lease"]
+    assert new_lease["obtained"] == lease["obtained"]
+
+
+def _show_kernel_iface(ifname, running_config=False):
+    cmd = ["nmstatectl", "show", "-k", ifname, "--json"]
+    if running_config:
+        cmd.append("-r")
+    output = cmdlib.exec_cmd(cmd, check=True)[1]
+    return json.loads(output)[Interface.KEY][0]
+
+
 def get_dhcp_addr(iface_state):
     """
     Return ([DHCPV4_ADDR], [DHCPV6_ADDR])
@@ -1,6 +1,7 @@
 # SPDX-License-Identifier: LGPL-2.1-or-later
 
 import copy
+import json
 
 import pytest
 
@@ -2164,3 +2165,154 @@ def test_kernel_mode_static_route_and_remove(cleanup_veth1_kernel_mode):
     assert_routes_missing(
         desired_state[Route.KEY][Route.CONFIG], cur_state, nic="veth1"
     )
+
+
+def _get_kernel_config_routes(nic):
+    cur_state = libnmstate.show(kernel_only=True)
+    return [
+        rt
+        for rt in cur_state[Route.KEY][Route.CONFIG]
+        if rt.get(Route.NEXT_HOP_INTERFACE) == nic
+    ]
+
+
+def test_kernel_mode_route_attributes_show_and_apply(
+    cleanup_veth1_kernel_mode,
+):
+    desired_state = load_yaml(
+        """---
+        interfaces:
+        - name: veth1
+          type: veth
+          state: up
+          veth:
+            peer: veth1_peer
+          ipv4:
+            address:
+            - ip: 192.0.2.251
+              prefix-length: 24
+            dhcp: false
+            enabled: true
+          ipv6:
+            enabled: true
+            autoconf: false
+            dhcp: false
+            address:
+              - ip: 2001:db8:1::1
+                prefix-length: 64
+        routes:
+         config:
+           - destination: 198.51.100.0/24
+             next-hop-address: 192.0.2.1
+             next-hop-interface: veth1
+             source: 192.0.2.251
+             mtu: 1400
+             lock-mtu: true
+             advmss: 1300
+             initcwnd: 20
+             initrwnd: 30
+             quickack: true
+             table-id: 100
+           - destination: 203.0.113.0/24
+             next-hop-address: 198.51.100.254
+             next-hop-interface: veth1
+             onlink: true
+           - destination: 203.0.113.128/25
+             next-hop-interface: veth1
+             scope: host
+           - destination: 2001:db8:2::/64
+             next-hop-address: 2001:db8:1::2
+             next-hop-interface: veth1
+             mtu: 1300
+        """
+    )
+    libnmstate.apply(desired_state, kernel_only=True)
+
+    cur_routes = _get_kernel_config_routes("veth1")
+    for des_rt in desired_state[Route.KEY][Route.CONFIG]:
+        assert any(
+            all(cur_rt.get(k) == v for k, v in des_rt.items())
+            for cur_rt in cur_routes
+        )
+    # Default scope should not be shown
+    assert all(
+        rt.get(Route.SCOPE) in (None, Route.SCOPE_HOST) for rt in cur_routes
+    )
+
+    # Applying the shown state should not fail or change anything
+    libnmstate.apply({Route.KEY: {Route.CONFIG: cur_routes}}, kernel_only=True)
+    assert _get_kernel_config_routes("veth1") == cur_routes
+
+    libnmstate.apply(
+        load_yaml(
+            """---
+            routes:
+             config:
+               - state: absent
+                 next-hop-interface: veth1
+            """
+        ),
+        kernel_only=True,
+    )
+    cur_dsts = [
+        rt[Route.DESTINATION] for rt in _get_kernel_config_routes("veth1")
+    ]
+    for des_rt in desired_state[Route.KEY][Route.CONFIG]:
+        assert des_rt[Route.DESTINATION] not in cur_dsts
+
+
+def test_kernel_mode_throw_route_in_custom_table_and_revert(
+    cleanup_veth1_kernel_mode, tmp_path
+):
+    libnmstate.apply(
+        load_yaml(
+            """---
+            interfaces:
+            - name: veth1
+              type: veth
+              state: up
+              veth:
+                peer: veth1_peer
+            """
+        ),
+        kernel_only=True,
+    )
+    desired_state = load_yaml(
+        f"""---
+        routes:
+         config:
+           - destination: 198.51.100.0/24
+             route-type: throw
+             table-id: {TEST_ROUTE_TABLE_ID}
+           - destination: 2001:db8:a::/64
+             route-type: throw
+             table-id: {TEST_ROUTE_TABLE_ID}
+        """
+    )
+    state_file = tmp_path / "throw_routes.yml"
+    state_file.write_text(json.dumps(desired_state))
+    cur_state_file = tmp_path / "current.yml"
+    cur_state_file.write_text(json.dumps(libnmstate.show(kernel_only=True)))
+    _, revert_state, _ = cmdlib.exec_cmd(
+        [
+            "nmstatectl",
+            "gr",
+            "--json",
+            "-c",
+            str(cur_state_file),
+            str(state_file),
+        ],
+        check=True,
+    )
+
+    libnmstate.apply(desired_state, kernel_only=True)
+    assert "throw 198.51.100.0/24" in _get_routes_from_iproute(
+        4, TEST_ROUTE_TABLE_ID
+    )
+    assert "throw 2001:db8:a::/64" in _get_routes_from_iproute(
+        6, TEST_ROUTE_TABLE_ID
+    )
+
+    libnmstate.apply(json.loads(revert_state), kernel_only=True)
+    assert "throw" not in _get_routes_from_iproute(4, TEST_ROUTE_TABLE_ID)
+    assert "throw" not in _get_routes_from_iproute(6, TEST_ROUTE_TABLE_ID)